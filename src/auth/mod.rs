@@ -13,9 +13,15 @@ impl GitHubAuth {
     /// Attempts to authenticate using various methods in order of preference:
     /// 1. GitHub CLI (gh)
     /// 2. GITHUB_TOKEN environment variable
-    pub async fn authenticate() -> Result<AuthMethod> {
+    ///
+    /// `enterprise_host` asks the CLI for its token for that host instead of github.com's.
+    pub async fn authenticate(enterprise_host: Option<&str>) -> Result<AuthMethod> {
         // Try GitHub CLI first
-        if let Ok(token) = Self::get_github_cli_token() {
+        let cli_token = match enterprise_host {
+            Some(host) => Self::get_github_cli_host_token(host),
+            None => Self::get_github_cli_token(),
+        };
+        if let Ok(token) = cli_token {
             tracing::info!("Using GitHub CLI authentication");
             return Ok(AuthMethod::GitHubCli(token));
         }
@@ -73,6 +79,23 @@ impl GitHubAuth {
         anyhow::bail!("Failed to get authentication token from GitHub CLI");
     }
 
+    /// `gh auth token --hostname <host>`; `gh auth status` would report github.com's token
+    fn get_github_cli_host_token(host: &str) -> Result<String> {
+        let output = Command::new("gh")
+            .args(["auth", "token", "--hostname", host])
+            .output()
+            .context("Failed to execute gh command. Is GitHub CLI installed?")?;
+
+        if output.status.success() {
+            let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !token.is_empty() {
+                return Ok(token);
+            }
+        }
+
+        anyhow::bail!("GitHub CLI not authenticated to {}. Run 'gh auth login --hostname {}'", host, host);
+    }
+
     pub fn get_token(auth_method: &AuthMethod) -> &str {
         match auth_method {
             AuthMethod::GitHubCli(token) | AuthMethod::PersonalAccessToken(token) => token,
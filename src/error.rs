@@ -0,0 +1,219 @@
+//! Typed errors for the library layers (`git`, `github`, `config`) so embedders can
+//! match on failures such as conflicts or rate limits. The binary converts them to
+//! `anyhow::Error` at the edges for display.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum GitError {
+    #[error("No Git repository found. Please run this command from within a Git repository.")]
+    RepositoryNotFound {
+        #[source]
+        source: git2::Error,
+    },
+    #[error("Invalid commit SHA: {sha}")]
+    InvalidSha { sha: String },
+    #[error("Commit not found: {sha}")]
+    CommitNotFound { sha: String },
+    #[error("Branch '{branch}' not found")]
+    BranchNotFound { branch: String },
+    /// The branch isn't local and several remotes other than the configured one have it
+    #[error(
+        "Branch '{branch}' is on several remotes: {}. Set github.remote_name to the one to track",
+        candidates.join(", ")
+    )]
+    AmbiguousRemoteBranch { branch: String, candidates: Vec<String> },
+    /// The index still has conflicted paths
+    #[error("Unresolved conflicts in: {}", files.join(", "))]
+    Conflicts { files: Vec<String> },
+    #[error("{path} has no conflict to resolve")]
+    NotConflicted { path: String },
+    /// The commit isn't in this clone, most likely because it's the wrong repository
+    #[error("{message}")]
+    RepositoryMismatch { message: String },
+    /// Changes outside the picked commit were staged and would have been
+    /// committed along with it
+    #[error(
+        "Staged changes outside commit {sha} would be committed with it: {}. \
+         Commit, stash or unstage them, or set pick_only_commit_paths to leave them out of the pick",
+        paths.join(", ")
+    )]
+    StagedOutsidePick { sha: String, paths: Vec<String> },
+    /// Another git process still held the index lock after every retry
+    #[error(
+        "{} is held by another git process, most likely an editor, IDE or git command still running. \
+         Wait for it to finish, or delete the file if nothing is running",
+        lock.display()
+    )]
+    IndexLocked { lock: PathBuf },
+    /// The disk filled up while git was writing
+    #[error("Disk full during {action}; free some space, and the repository may need `git fsck`")]
+    DiskFull { action: String },
+    /// HEAD wasn't on the branch just checked out; nothing is committed onto it
+    #[error("Expected HEAD on {expected} before committing, but it is {found}")]
+    HeadMismatch { expected: String, found: String },
+    #[error("Commit {sha} has {parents} parent(s), so it can't be picked against parent {mainline}")]
+    InvalidMainline { sha: String, mainline: u32, parents: usize },
+    #[error("Push of '{branch}' was rejected: {reason}")]
+    PushRejected { branch: String, reason: String },
+    /// Changes stashed for a checkout conflict with the branch; the stash is kept
+    #[error(
+        "Uncommitted changes conflict with '{branch}'; they are kept in the stash as \"{message}\" \
+        (`git stash pop` restores them once the conflict is dealt with)"
+    )]
+    StashConflicts { branch: String, message: String },
+    #[error("Unexpected repository state after cherry-pick: {state}")]
+    UnexpectedState { state: String },
+    /// The `git` binary exited with an error (cli engine)
+    #[error("git {args} failed: {stderr}")]
+    Command { args: String, stderr: String },
+    #[error("Invalid tag pattern: {0}")]
+    InvalidPattern(#[from] glob::PatternError),
+    #[error("{context}: {source}")]
+    Operation {
+        context: String,
+        #[source]
+        source: git2::Error,
+    },
+    #[error(transparent)]
+    Git(#[from] git2::Error),
+}
+
+impl GitError {
+    /// True when retrying may succeed, i.e. another process held the index lock
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            GitError::IndexLocked { .. } => true,
+            GitError::Operation { source, .. } | GitError::Git(source) => {
+                crate::git::is_retryable_git_error(source)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum GitHubError {
+    /// `reset` is when the quota refills, if GitHub could tell us
+    #[error(
+        "GitHub API rate limit exceeded{}",
+        reset.map(|r| format!("; resets at {}", r.format("%H:%M:%S UTC"))).unwrap_or_default()
+    )]
+    RateLimited { reset: Option<DateTime<Utc>> },
+    #[error("GitHub rejected the credentials: {message}")]
+    Unauthorized { message: String },
+    #[error("{what}: not found or not accessible")]
+    NotFound { what: String },
+    /// The organization enforces SAML SSO and the token isn't authorized for it.
+    /// `url` is the authorization page GitHub offered, if it offered one.
+    #[error(
+        "Your token needs SSO authorization for {} — {}",
+        org.as_deref().unwrap_or("this organization"),
+        url.as_deref()
+            .map(|url| format!("visit {}", url))
+            .unwrap_or_else(|| "authorize it for the organization at https://github.com/settings/tokens".to_string())
+    )]
+    SsoRequired { org: Option<String>, url: Option<String> },
+    /// No usable token could be obtained
+    #[error("{reason}")]
+    Auth { reason: String },
+    #[error("{reason}")]
+    InvalidInput { reason: String },
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("{context}: {source}")]
+    Api {
+        context: String,
+        #[source]
+        source: Box<octocrab::Error>,
+    },
+    /// A listing that failed partway; `prs` matched on the pages read before it did
+    #[error("{source} ({} matching PR(s) fetched before the error)", prs.len())]
+    PartialListing {
+        prs: Vec<crate::github::PrInfo>,
+        #[source]
+        source: Box<GitHubError>,
+    },
+}
+
+impl GitHubError {
+    /// The SSO authorization page to send the user to, looking inside a partial listing
+    pub fn sso_url(&self) -> Option<&str> {
+        match self {
+            GitHubError::SsoRequired { url, .. } => url.as_deref(),
+            GitHubError::PartialListing { source, .. } => source.sso_url(),
+            _ => None,
+        }
+    }
+
+    /// Whether the request never got an answer (the connection failed or timed
+    /// out) or GitHub failed with a server error, so sending it again may work
+    pub fn is_transient(&self) -> bool {
+        let GitHubError::Api { source, .. } = self else {
+            return false;
+        };
+        match &**source {
+            octocrab::Error::Service { .. } | octocrab::Error::Hyper { .. } => true,
+            octocrab::Error::GitHub { source, .. } => source.status_code.is_server_error(),
+            _ => false,
+        }
+    }
+
+    /// Whether the connection to GitHub failed, so the request was never sent.
+    /// Only these failures are safe to retry for a write that isn't idempotent.
+    pub fn is_connect_failure(&self) -> bool {
+        let GitHubError::Api { source, .. } = self else {
+            return false;
+        };
+        // octocrab boxes hyper's client error, which knows the stage that failed
+        std::iter::successors(Some(&**source as &dyn std::error::Error), |e| e.source())
+            .filter_map(|e| e.downcast_ref::<hyper_util::client::legacy::Error>())
+            .any(hyper_util::client::legacy::Error::is_connect)
+    }
+
+    /// Whether the failure concerns only what was asked for (it's gone, or
+    /// GitHub errored on it) rather than every request, as limits, credentials
+    /// and a lost connection do
+    pub fn is_item_specific(&self) -> bool {
+        match self {
+            GitHubError::NotFound { .. } => true,
+            GitHubError::Api { source, .. } => matches!(**source, octocrab::Error::GitHub { .. }),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ConfigError {
+    #[error("Invalid value for {field}: {reason}")]
+    Invalid { field: String, reason: String },
+}
+
+impl ConfigError {
+    pub(crate) fn invalid(field: impl Into<String>, reason: impl ToString) -> Self {
+        ConfigError::Invalid {
+            field: field.into(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Attaches a human-readable context to a raw `git2` failure
+pub(crate) trait GitContext<T> {
+    fn git_context(self, context: impl Into<String>) -> Result<T, GitError>;
+}
+
+impl<T> GitContext<T> for Result<T, git2::Error> {
+    fn git_context(self, context: impl Into<String>) -> Result<T, GitError> {
+        self.map_err(|source| GitError::Operation {
+            context: context.into(),
+            source,
+        })
+    }
+}
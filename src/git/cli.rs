@@ -0,0 +1,212 @@
+//! [`Engine`] that shells out to the system `git` binary, for when libgit2's
+//! cherry-pick falls short (rename detection, rerere, hooks).
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use git2::{Oid, Repository, RepositoryState};
+
+use super::engine::Engine;
+use super::{conflicted_paths, CherrypickResult, PickScope, Result};
+use crate::error::{GitContext, GitError};
+
+pub(crate) struct CliEngine;
+
+impl CliEngine {
+    /// True when a `git` binary can be run
+    pub(crate) fn available() -> bool {
+        Command::new("git")
+            .arg("--version")
+            .output()
+            .is_ok_and(|out| out.status.success())
+    }
+
+    /// Refs `url` advertises, one `<sha>\t<ref>` line each
+    pub(crate) fn ls_remote(repo: &Repository, url: &str) -> Result<String> {
+        let output = Self::git(repo, &["ls-remote", "--", url])?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Runs `git <args>` in the repository's working directory. A non-zero
+    /// exit becomes `GitError::Command` carrying git's stderr.
+    fn git(repo: &Repository, args: &[&str]) -> Result<Output> {
+        let output = Self::run(repo, args)?;
+        if !output.status.success() {
+            return Err(command_error(args, &output));
+        }
+        Ok(output)
+    }
+
+    /// Like [`CliEngine::git`], but returns the output whatever the exit status
+    fn run(repo: &Repository, args: &[&str]) -> Result<Output> {
+        let dir = repo.workdir().unwrap_or_else(|| repo.path());
+        let output = git_command(dir)
+            .args(args)
+            .output()
+            .map_err(|e| GitError::Command {
+                args: args.join(" "),
+                stderr: e.to_string(),
+            })?;
+
+        tracing::debug!(
+            args = ?args,
+            status = ?output.status.code(),
+            stdout = %String::from_utf8_lossy(&output.stdout).trim_end(),
+            stderr = %String::from_utf8_lossy(&output.stderr).trim_end(),
+            "Ran git"
+        );
+        Ok(output)
+    }
+}
+
+impl Engine for CliEngine {
+    fn checkout_branch(&self, repo: &Repository, branch_name: &str, remote: &str) -> Result<()> {
+        if repo.find_branch(branch_name, git2::BranchType::Local).is_ok() {
+            Self::git(repo, &["checkout", branch_name])?;
+            return Ok(());
+        }
+        // git would guess the remote itself, but only when exactly one has the branch
+        let tracked = format!("{}/{}", remote, branch_name);
+        if repo.find_branch(&tracked, git2::BranchType::Remote).is_err() {
+            return Err(GitError::BranchNotFound {
+                branch: branch_name.to_string(),
+            });
+        }
+        Self::git(repo, &["checkout", "--track", &tracked])?;
+        Ok(())
+    }
+
+    // git refuses to pick onto a dirty index, so `git commit` never sweeps in
+    // anything outside the pick and the scope needs no checking here
+    fn cherry_pick(
+        &self,
+        repo: &Repository,
+        commit: &git2::Commit,
+        _scope: &PickScope,
+        mainline: Option<u32>,
+    ) -> Result<CherrypickResult> {
+        let sha = commit.id().to_string();
+        let parent = mainline.map(|parent| parent.to_string());
+        let mut args = vec!["cherry-pick"];
+        if let Some(parent) = &parent {
+            args.extend(["-m", parent]);
+        }
+        args.push(&sha);
+
+        let output = Self::run(repo, &args)?;
+        if output.status.success() {
+            let head = repo
+                .head()
+                .and_then(|h| h.peel_to_commit())
+                .git_context("Failed to resolve HEAD after cherry-pick")?;
+            return Ok(CherrypickResult {
+                success: true,
+                conflicts: Vec::new(),
+                commit_sha: Some(head.id().to_string()),
+                rerere_resolved: Vec::new(),
+                binary_conflicts: Vec::new(),
+                mainline: None,
+            });
+        }
+
+        let conflicts = conflicted_paths(repo)?;
+        if conflicts.is_empty() {
+            return Err(command_error(&args, &output));
+        }
+        // With rerere enabled git has already rewritten these files but leaves them unstaged
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let rerere_resolved = stderr
+            .lines()
+            .filter_map(|line| line.strip_prefix("Resolved '")?.strip_suffix("' using previous resolution."))
+            .map(str::to_string)
+            .collect();
+        Ok(CherrypickResult {
+            success: false,
+            conflicts,
+            commit_sha: None,
+            rerere_resolved,
+            binary_conflicts: Vec::new(),
+            mainline: None,
+        })
+    }
+
+    fn continue_cherry_pick(&self, repo: &Repository, commit_message: Option<&str>) -> Result<String> {
+        Self::git(repo, &["add", "--all"])?;
+        match commit_message {
+            Some(message) => Self::git(repo, &["commit", "--message", message])?,
+            None if matches!(repo.state(), RepositoryState::CherryPick | RepositoryState::CherryPickSequence) => {
+                Self::git(repo, &["cherry-pick", "--continue"])?
+            }
+            None => Self::git(
+                repo,
+                &["commit", "--message", "Cherry-pick (resolved conflicts)"],
+            )?,
+        };
+
+        let head = repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .git_context("Failed to resolve HEAD after commit")?;
+        Ok(head.id().to_string())
+    }
+
+    fn abort_cherry_pick(&self, repo: &Repository) -> Result<()> {
+        if matches!(repo.state(), RepositoryState::CherryPick | RepositoryState::CherryPickSequence) {
+            Self::git(repo, &["cherry-pick", "--abort"])?;
+        } else {
+            Self::git(repo, &["reset", "--hard", "HEAD"])?;
+        }
+        Ok(())
+    }
+
+    fn push(&self, repo: &Repository, remote: &str, branch: &str, lease: Option<Oid>) -> Result<()> {
+        let refspec = format!("refs/heads/{0}:refs/heads/{0}", branch);
+        // An empty expected value leases on the branch not existing yet
+        let lease = lease.map(|lease| {
+            let expect = if lease.is_zero() { String::new() } else { lease.to_string() };
+            format!("--force-with-lease=refs/heads/{}:{}", branch, expect)
+        });
+        let mut args = vec!["push", "--porcelain"];
+        if let Some(lease) = &lease {
+            args.push(lease);
+        }
+        args.extend(["--", remote, &refspec]);
+
+        let output = Self::run(repo, &args)?;
+        if output.status.success() {
+            return Ok(());
+        }
+        // --porcelain reports each ref as tab-separated flag, `<from>:<to>` and summary; `!` flags a rejection
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let rejected = stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("!\t")?.split('\t').nth(1));
+        match rejected {
+            Some(reason) => Err(GitError::PushRejected {
+                branch: branch.to_string(),
+                reason: reason.to_string(),
+            }),
+            None => Err(command_error(&args, &output)),
+        }
+    }
+}
+
+fn git_command(dir: &Path) -> Command {
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(dir)
+        // Never stop to open an editor or ask for credentials; the TUI owns the terminal
+        .env("GIT_EDITOR", "true")
+        .env("GIT_TERMINAL_PROMPT", "0");
+    command
+}
+
+fn command_error(args: &[&str], output: &Output) -> GitError {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    GitError::Command {
+        args: args.join(" "),
+        stderr: if stderr.is_empty() { stdout } else { stderr },
+    }
+}
@@ -0,0 +1,324 @@
+//! The mutating half of `GitOperations`. Checkout, cherry-pick and push go
+//! through an [`Engine`] so the system `git` binary can stand in for libgit2
+//! where the two disagree; read-only queries always use libgit2.
+
+use git2::{CherrypickOptions, Oid, Repository, RepositoryState};
+
+use super::{conflicted_paths, rerere, signature, CherrypickResult, PickScope, Result};
+use crate::error::{GitContext, GitError};
+
+pub(crate) trait Engine: Send + Sync {
+    /// Switches to `branch_name`, creating it to track `remote`'s when it
+    /// only exists there
+    fn checkout_branch(&self, repo: &Repository, branch_name: &str, remote: &str) -> Result<()>;
+
+    /// Applies `commit` onto HEAD, committing it when it applies cleanly.
+    /// The commit holds no more than `scope` allows. A merge commit is
+    /// applied against its `mainline` parent (1-based).
+    fn cherry_pick(
+        &self,
+        repo: &Repository,
+        commit: &git2::Commit,
+        scope: &PickScope,
+        mainline: Option<u32>,
+    ) -> Result<CherrypickResult>;
+
+    /// Commits a resolved conflict, returning the new commit's sha
+    fn continue_cherry_pick(&self, repo: &Repository, commit_message: Option<&str>) -> Result<String>;
+
+    fn abort_cherry_pick(&self, repo: &Repository) -> Result<()>;
+
+    /// Pushes local `branch` to the same branch on `remote`. With a `lease`
+    /// the push may rewrite the remote's branch, but only while it still
+    /// points there (zero: while it doesn't exist).
+    fn push(&self, repo: &Repository, remote: &str, branch: &str, lease: Option<Oid>) -> Result<()>;
+}
+
+/// In-process engine backed by libgit2
+pub(crate) struct Libgit2Engine;
+
+impl Engine for Libgit2Engine {
+    fn checkout_branch(&self, repo: &Repository, branch_name: &str, remote: &str) -> Result<()> {
+        // Find the branch
+        let branch = repo
+            .find_branch(branch_name, git2::BranchType::Local)
+            .or_else(|_| {
+                // Try to find remote branch and create local tracking branch
+                create_tracking_branch(repo, branch_name, remote)
+            })
+            .map_err(|_| GitError::BranchNotFound {
+                branch: branch_name.to_string(),
+            })?;
+
+        let commit = branch
+            .get()
+            .peel_to_commit()
+            .git_context("Failed to get commit for branch")?;
+
+        // Checkout the branch
+        repo.checkout_tree(commit.as_object(), None)
+            .git_context("Failed to checkout tree")?;
+
+        // Update HEAD
+        repo.set_head(&format!("refs/heads/{}", branch_name))
+            .git_context("Failed to update HEAD")?;
+        Ok(())
+    }
+
+    fn cherry_pick(
+        &self,
+        repo: &Repository,
+        commit: &git2::Commit,
+        scope: &PickScope,
+        mainline: Option<u32>,
+    ) -> Result<CherrypickResult> {
+        let commit_sha = commit.id().to_string();
+
+        // Perform the cherry-pick
+        let mut opts = CherrypickOptions::new();
+        if let Some(parent) = mainline {
+            opts.mainline(parent);
+        }
+        repo.cherrypick(commit, Some(&mut opts))
+            .git_context("Failed to cherry-pick commit")?;
+
+        // Check repository state after cherry-pick. libgit2 leaves the repository in
+        // the CherryPick state even when the pick applied cleanly, so look at the index.
+        let conflicted = repo.index()?.has_conflicts();
+    match repo.state() {
+        RepositoryState::CherryPick if !conflicted => commit_cherry_pick(repo, commit, &commit_sha, scope),
+        RepositoryState::Clean | RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => {
+                // No conflicts, commit the change
+                commit_cherry_pick(repo, commit, &commit_sha, scope)
+            }
+        RepositoryState::CherryPick | RepositoryState::Merge | RepositoryState::Revert | RepositoryState::RebaseMerge | RepositoryState::Rebase | RepositoryState::RebaseInteractive | RepositoryState::CherryPickSequence => {
+                // There are conflicts; reuse recorded resolutions where git would
+                let conflicts = conflicted_paths(repo)?;
+                let rerere_resolved = if rerere::enabled(repo) {
+                    rerere::apply_recorded(repo, &conflicts)?
+                } else {
+                    Vec::new()
+                };
+                Ok(CherrypickResult {
+                    success: false,
+                    conflicts,
+                    commit_sha: None,
+                    rerere_resolved,
+                    binary_conflicts: Vec::new(),
+                    mainline: None,
+                })
+            }
+            state => Err(GitError::UnexpectedState {
+                state: format!("{:?}", state),
+            }),
+        }
+    }
+
+    fn continue_cherry_pick(&self, repo: &Repository, commit_message: Option<&str>) -> Result<String> {
+        // Stage all changes
+        let mut index = repo.index()?;
+        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+        index.write()?;
+
+        // Create commit
+        let signature = signature(repo)?;
+        let tree_id = index.write_tree()?;
+        let tree = repo.find_tree(tree_id)?;
+        let parent = repo.head()?.peel_to_commit()?;
+
+        let message = commit_message.unwrap_or("Cherry-pick (resolved conflicts)");
+        let commit_id = repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &[&parent],
+        )?;
+
+    // Clean up cherry-pick state if any
+    let _ = repo.cleanup_state();
+
+        Ok(commit_id.to_string())
+    }
+
+    fn abort_cherry_pick(&self, repo: &Repository) -> Result<()> {
+    let _ = repo.cleanup_state();
+
+        // Reset to HEAD
+        let head = repo.head()?.peel_to_commit()?;
+        repo.reset(head.as_object(), git2::ResetType::Hard, None)
+            .git_context("Failed to reset to HEAD")?;
+        Ok(())
+    }
+
+    fn push(&self, repo: &Repository, remote: &str, branch: &str, lease: Option<Oid>) -> Result<()> {
+        let mut pushing = repo
+            .find_remote(remote)
+            .git_context(format!("Failed to find '{}' remote", remote))?;
+        let mut refspec = format!("refs/heads/{0}:refs/heads/{0}", branch);
+        if lease.is_some() {
+            refspec.insert(0, '+');
+        }
+
+        let config = repo.config().git_context("Failed to get git config")?;
+        let lease_broken = std::cell::Cell::new(false);
+        let rejected = std::cell::RefCell::new(None);
+        let mut callbacks = git2::RemoteCallbacks::new();
+        // libgit2 asks again after each refused credential, so each kind is offered once
+        let mut offered = git2::CredentialType::empty();
+        callbacks.credentials(move |url, username, allowed| {
+            let kind = if allowed.contains(git2::CredentialType::SSH_KEY) {
+                git2::CredentialType::SSH_KEY
+            } else {
+                git2::CredentialType::USER_PASS_PLAINTEXT
+            };
+            if offered.contains(kind) {
+                return Err(git2::Error::from_str(&format!("{} refused the credentials", url)));
+            }
+            offered |= kind;
+            if kind == git2::CredentialType::SSH_KEY {
+                git2::Cred::ssh_key_from_agent(username.unwrap_or("git"))
+            } else {
+                git2::Cred::credential_helper(&config, url, username)
+            }
+        });
+        if let Some(lease) = lease {
+            let lease_broken = &lease_broken;
+            callbacks.push_negotiation(move |updates| {
+                if updates.iter().any(|update| update.src() != lease) {
+                    lease_broken.set(true);
+                    return Err(git2::Error::from_str("stale remote-tracking branch"));
+                }
+                Ok(())
+            });
+        }
+        callbacks.push_update_reference(|_, status| {
+            if let Some(reason) = status {
+                *rejected.borrow_mut() = Some(reason.to_string());
+            }
+            Ok(())
+        });
+        let mut options = git2::PushOptions::new();
+        options.remote_callbacks(callbacks);
+
+        let pushed = pushing.push(&[refspec.as_str()], Some(&mut options));
+        // Remote servers reject per reference; a local remote fails the whole push
+        let reason = match &pushed {
+            _ if lease_broken.get() => Some(format!("{}/{} moved since it was last fetched", remote, branch)),
+            Err(e) if e.code() == git2::ErrorCode::NotFastForward => Some(e.message().to_string()),
+            _ => rejected.take(),
+        };
+        if let Some(reason) = reason {
+            return Err(GitError::PushRejected {
+                branch: branch.to_string(),
+                reason,
+            });
+        }
+        pushed.git_context(format!("Failed to push '{}'", branch))?;
+        Ok(())
+    }
+}
+
+pub(super) fn create_tracking_branch<'r>(
+    repo: &'r Repository,
+    branch_name: &str,
+    remote: &str,
+) -> Result<git2::Branch<'r>, git2::Error> {
+    let remote_branch = repo.find_branch(&format!("{}/{}", remote, branch_name), git2::BranchType::Remote)?;
+    let remote_commit = remote_branch.get().peel_to_commit()?;
+
+    // Create local tracking branch
+    let local_branch = repo.branch(branch_name, &remote_commit, false)?;
+
+    // Set up tracking
+    let mut branch_config = repo.config()?;
+    branch_config.set_str(&format!("branch.{}.remote", branch_name), remote)?;
+    branch_config.set_str(
+        &format!("branch.{}.merge", branch_name),
+        &format!("refs/heads/{}", branch_name),
+    )?;
+
+    Ok(local_branch)
+}
+
+/// Commits the cleanly applied pick of `commit` and clears the cherry-pick state.
+/// libgit2 can report a clean pick while the index also holds changes staged
+/// by something else; those are refused, or left out when `scope` says so.
+fn commit_cherry_pick(
+    repo: &Repository,
+    commit: &git2::Commit,
+    commit_sha: &str,
+    scope: &PickScope,
+) -> Result<CherrypickResult> {
+    let signature = signature(repo)?;
+    let mut index = repo.index()?;
+    let stray = scope.stray_staged(repo, &index)?;
+    let tree_id = if stray.is_empty() {
+        index.write_tree()?
+    } else if scope.only_commit_paths {
+        tracing::warn!(commit_sha = %commit_sha, stray = ?stray, "Leaving staged changes out of the pick");
+        scope.limited_tree(repo, &index)?
+    } else {
+        return Err(scope.refusal(stray));
+    };
+    let tree = repo.find_tree(tree_id)?;
+    let parent = repo.head()?.peel_to_commit()?;
+
+    let commit_id = repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        commit.message().unwrap_or("Cherry-pick"),
+        &tree,
+        &[&parent],
+    )?;
+    repo.cleanup_state()
+        .git_context("Failed to clear cherry-pick state")?;
+
+    tracing::debug!(commit_sha = %commit_sha, new_commit_sha = %commit_id, "Committed pick");
+
+    Ok(CherrypickResult {
+        success: true,
+        conflicts: Vec::new(),
+        commit_sha: Some(commit_id.to_string()),
+        rerere_resolved: Vec::new(),
+        binary_conflicts: Vec::new(),
+        mainline: None,
+    })
+}
+
+/// Libgit2, counting the cherry-picks it is asked for, so a test can tell
+/// whether a code path reaches git at all
+#[cfg(test)]
+pub(crate) struct CountingEngine(pub std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+#[cfg(test)]
+impl Engine for CountingEngine {
+    fn checkout_branch(&self, repo: &Repository, branch_name: &str, remote: &str) -> Result<()> {
+        Libgit2Engine.checkout_branch(repo, branch_name, remote)
+    }
+
+    fn cherry_pick(
+        &self,
+        repo: &Repository,
+        commit: &git2::Commit,
+        scope: &PickScope,
+        mainline: Option<u32>,
+    ) -> Result<CherrypickResult> {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Libgit2Engine.cherry_pick(repo, commit, scope, mainline)
+    }
+
+    fn continue_cherry_pick(&self, repo: &Repository, commit_message: Option<&str>) -> Result<String> {
+        Libgit2Engine.continue_cherry_pick(repo, commit_message)
+    }
+
+    fn abort_cherry_pick(&self, repo: &Repository) -> Result<()> {
+        Libgit2Engine.abort_cherry_pick(repo)
+    }
+
+    fn push(&self, repo: &Repository, remote: &str, branch: &str, lease: Option<Oid>) -> Result<()> {
+        Libgit2Engine.push(repo, remote, branch, lease)
+    }
+}
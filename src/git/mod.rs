@@ -1,34 +1,233 @@
-use anyhow::{Context, Result};
-use git2::{CherrypickOptions, Oid, Repository, RepositoryState, Signature};
+mod cli;
+mod engine;
+mod rerere;
+mod worktree;
+
+use chrono::{DateTime, TimeZone, Utc};
+use git2::{IndexConflict, IndexEntry, Oid, Repository, RepositoryState, Signature};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::path::Path;
+use std::time::Duration;
+
+use crate::config::GitEngine;
+use crate::error::{GitContext, GitError};
+use crate::util::{parse_github_url, GitHubCoords};
+use crate::workflow::{BranchPlan, BranchPresence};
+use engine::Engine;
+
+pub use rerere::{conflict_signature, ConflictSignature};
+pub use worktree::{WorktreePick, WorktreePicker};
+
+type Result<T, E = GitError> = std::result::Result<T, E>;
+
+/// Attempts at a write while another git process holds the index lock; the
+/// other process usually finishes within a couple of seconds
+const LOCK_ATTEMPTS: u32 = 6;
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(500);
 
 pub struct GitOperations {
     repo: Repository,
+    /// Performs checkout and cherry-pick; queries always go through `repo`
+    engine: Box<dyn Engine>,
+    /// Leave unrelated staged changes out of a pick's commit instead of refusing
+    only_commit_paths: bool,
+    /// Remote a branch missing locally is tracked from when it has the branch
+    remote: String,
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct CherrypickResult {
     pub success: bool,
     pub conflicts: Vec<String>,
     pub commit_sha: Option<String>,
+    /// Conflicted paths settled by a recorded rerere resolution
+    pub rerere_resolved: Vec<String>,
+    /// Conflicted paths whose content is binary, which an editor can't merge
+    pub binary_conflicts: Vec<String>,
+    /// The parent (1-based) a merge commit was picked against
+    pub mainline: Option<u32>,
+}
+
+/// Which version of a conflicted file to keep whole
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictSide {
+    /// The target branch's version
+    Ours,
+    /// The picked commit's version
+    Theirs,
+}
+
+/// A commit on a local branch that its counterpart on the remote doesn't have
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnpushedCommit {
+    pub sha: String,
+    /// First line of the message
+    pub summary: String,
+}
+
+/// A commit on a branch, with its full message for reading trailers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchCommit {
+    pub sha: String,
+    pub message: String,
+    pub committed_at: DateTime<Utc>,
+}
+
+/// A commit's parents and authorship, enough to tell how a PR was merged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitHeader {
+    pub parent_count: usize,
+    pub message: String,
+    pub authored_at: DateTime<Utc>,
+}
+
+/// A commit moving a submodule's pointer (its gitlink entry) to another commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmoduleUpdate {
+    pub path: String,
+    /// Commit the submodule points at after the change
+    pub sha: String,
+}
+
+/// What HEAD points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadState {
+    /// On a branch, by short name
+    Branch(String),
+    /// At a commit with no branch, e.g. after a failed rebase; holds the sha
+    Detached(String),
+    /// On a branch with no commits yet
+    Unborn(String),
+}
+
+impl std::fmt::Display for HeadState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeadState::Branch(name) => write!(f, "{}", name),
+            HeadState::Detached(sha) => write!(f, "detached at {}", crate::util::short_sha(sha)),
+            HeadState::Unborn(name) => write!(f, "{} (no commits yet)", name),
+        }
+    }
+}
+
+/// Returns true for git2 errors that are worth retrying: another process holding
+/// the index lock (`GIT_ELOCKED`). Conflicts and everything else are not retried.
+pub fn is_retryable_git_error(e: &git2::Error) -> bool {
+    e.code() == git2::ErrorCode::Locked
+}
+
+/// Turns a held index lock into [`GitError::IndexLocked`] naming the lock file
+/// under `git_dir`, and an out-of-space failure into [`GitError::DiskFull`].
+/// Other errors are returned unchanged.
+pub fn classify_git_error(err: GitError, git_dir: &Path) -> GitError {
+    let (locked, message, action) = match &err {
+        GitError::Operation { context, source } => {
+            (is_retryable_git_error(source), source.message(), context.clone())
+        }
+        GitError::Git(source) => (
+            is_retryable_git_error(source),
+            source.message(),
+            "a repository write".to_string(),
+        ),
+        GitError::Command { args, stderr } => (
+            stderr.contains("index.lock': File exists"),
+            stderr.as_str(),
+            format!("git {}", args),
+        ),
+        _ => return err,
+    };
+    if locked {
+        GitError::IndexLocked {
+            lock: git_dir.join("index.lock"),
+        }
+    } else if message.contains("No space left on device") {
+        GitError::DiskFull { action }
+    } else {
+        err
+    }
 }
 
 #[allow(dead_code)] // Methods for future Git operations functionality
 impl GitOperations {
     pub fn new<P: AsRef<Path>>(repo_path: P) -> Result<Self> {
         let repo = Repository::open(repo_path)
-            .context("Failed to open Git repository. Are you in a Git repository?")?;
+            .map_err(|source| GitError::RepositoryNotFound { source })?;
 
-        Ok(Self { repo })
+        Ok(Self::with_repo(repo))
     }
 
     /// Discovers the Git repository from the current directory
     pub fn discover() -> Result<Self> {
-        let repo = Repository::discover(".").context(
-            "No Git repository found. Please run this command from within a Git repository.",
-        )?;
+        let repo =
+            Repository::discover(".").map_err(|source| GitError::RepositoryNotFound { source })?;
+
+        Ok(Self::with_repo(repo))
+    }
+
+    /// The repository at `path` (`github.repo_path`) when one is configured,
+    /// otherwise the one containing the current directory
+    pub fn open_configured(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) => Self::new(path),
+            None => Self::discover(),
+        }
+    }
+
+    /// The working tree, when it isn't the current directory. `None` for a
+    /// bare repository or when either path can't be resolved.
+    pub fn workdir_elsewhere(&self) -> Option<&Path> {
+        let workdir = self.repo.workdir()?;
+        let here = std::env::current_dir().ok()?.canonicalize().ok()?;
+        (workdir.canonicalize().ok()? != here).then_some(workdir)
+    }
+
+    fn with_repo(repo: Repository) -> Self {
+        Self {
+            repo,
+            engine: Box::new(engine::Libgit2Engine),
+            only_commit_paths: false,
+            remote: "origin".to_string(),
+        }
+    }
+
+    /// Switches how checkout and cherry-pick are performed. Asking for the
+    /// `git` binary when none is installed keeps libgit2 and logs a warning.
+    pub fn with_engine(mut self, kind: GitEngine) -> Self {
+        self.engine = match kind {
+            GitEngine::Libgit2 => Box::new(engine::Libgit2Engine),
+            GitEngine::Cli if cli::CliEngine::available() => Box::new(cli::CliEngine),
+            GitEngine::Cli => {
+                tracing::warn!("git_engine = \"cli\" but no git binary was found; using libgit2");
+                Box::new(engine::Libgit2Engine)
+            }
+        };
+        self
+    }
+
+    /// Counts the cherry-picks handed to the engine in `picks`
+    #[cfg(test)]
+    pub(crate) fn counting_picks(mut self, picks: std::sync::Arc<std::sync::atomic::AtomicUsize>) -> Self {
+        self.engine = Box::new(engine::CountingEngine(picks));
+        self
+    }
+
+    /// With `on`, changes already staged outside a picked commit's paths are
+    /// set aside while it's picked and staged again afterwards, so the pick's
+    /// commit holds only what the commit changed. Without it such a pick is
+    /// refused with [`GitError::StagedOutsidePick`].
+    pub fn with_only_commit_paths(mut self, on: bool) -> Self {
+        self.only_commit_paths = on;
+        self
+    }
 
-        Ok(Self { repo })
+    /// Checks out branches missing locally from `remote` (`github.remote_name`)
+    /// when it has them; see [`GitOperations::checkout_branch`]
+    pub fn with_remote(mut self, remote: &str) -> Self {
+        self.remote = remote.to_string();
+        self
     }
 
     /// Checks if the repository is in a clean state
@@ -36,157 +235,520 @@ impl GitOperations {
         let statuses = self
             .repo
             .statuses(None)
-            .context("Failed to check repository status")?;
+            .git_context("Failed to check repository status")?;
 
         Ok(statuses.is_empty())
     }
 
     /// Gets the current branch name
     pub fn current_branch(&self) -> Result<String> {
-        let head = self.repo.head().context("Failed to get HEAD reference")?;
+        let head = self.repo.head().git_context("Failed to get HEAD reference")?;
 
-        let branch_name = head.shorthand().context("Failed to get branch name")?;
+        let branch_name = head.shorthand().ok_or_else(|| GitError::BranchNotFound {
+            branch: "HEAD".to_string(),
+        })?;
 
         Ok(branch_name.to_string())
     }
 
-    /// Switches to the specified branch
-    pub fn checkout_branch(&self, branch_name: &str) -> Result<()> {
-        tracing::info!("Checking out branch: {}", branch_name);
+    /// Whether HEAD is on a branch, detached, or on a branch with no commits
+    pub fn head_state(&self) -> Result<HeadState> {
+        if self.repo.head_detached().git_context("Failed to read HEAD")? {
+            let sha = self.head_sha()?;
+            return Ok(HeadState::Detached(sha));
+        }
+        match self.repo.head() {
+            Ok(head) => Ok(HeadState::Branch(
+                head.shorthand().unwrap_or("HEAD").to_string(),
+            )),
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+                let head = self.repo.find_reference("HEAD").git_context("Failed to read HEAD")?;
+                let target = head.symbolic_target().unwrap_or("HEAD");
+                Ok(HeadState::Unborn(
+                    target.strip_prefix("refs/heads/").unwrap_or(target).to_string(),
+                ))
+            }
+            Err(e) => Err(GitError::Operation {
+                context: "Failed to read HEAD".into(),
+                source: e,
+            }),
+        }
+    }
+
+    /// Fails with [`GitError::HeadMismatch`] unless HEAD is on `branch`, so a
+    /// pick never commits onto some other branch or a detached HEAD
+    pub fn ensure_on_branch(&self, branch: &str) -> Result<()> {
+        match self.head_state()? {
+            HeadState::Branch(name) if name == branch => Ok(()),
+            found => Err(GitError::HeadMismatch {
+                expected: branch.to_string(),
+                found: found.to_string(),
+            }),
+        }
+    }
+
+    /// Runs `write`, trying again while another git process holds the index
+    /// lock. Failures come back classified by [`classify_git_error`].
+    fn retry_locked<T>(&self, mut write: impl FnMut() -> Result<T>) -> Result<T> {
+        let mut attempt = 1;
+        loop {
+            match write().map_err(|e| classify_git_error(e, self.repo.path())) {
+                Err(e) if attempt < LOCK_ATTEMPTS && e.is_retryable() => {
+                    tracing::warn!(attempt, "Index locked by another git process, retrying in 500ms...");
+                    std::thread::sleep(LOCK_RETRY_DELAY);
+                    attempt += 1;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    /// Switches to the specified branch. One that only exists on remotes is
+    /// created tracking the configured remote's, or failing that the only
+    /// other remote's that has it; that other remote is returned.
+    pub fn checkout_branch(&self, branch_name: &str) -> Result<Option<String>> {
+        tracing::info!(branch = %branch_name, "Checking out branch");
+
+        // Uncommitted changes are carried over to the branch through the stash
+        let message = format!("gh_cherry: checking out {}", branch_name);
+        let stashed = if self.is_clean()? { None } else { self.stash_push(&message)? };
+        let checked_out = self.checkout_branch_as_is(branch_name);
+        if let Some(stash) = stashed {
+            // After a failed checkout this restores them where they were.
+            // libgit2 pops a conflicting stash with conflict markers, so that's checked first.
+            if !self.stash_applies_cleanly(stash)? {
+                let branch = self.current_branch().unwrap_or_else(|_| branch_name.to_string());
+                return Err(GitError::StashConflicts { branch, message });
+            }
+            self.stash_pop(0)?;
+        }
+        checked_out
+    }
+
+    /// [`Self::checkout_branch`] without moving uncommitted changes out of the way
+    fn checkout_branch_as_is(&self, branch_name: &str) -> Result<Option<String>> {
+        let remote = if self.branch_exists(branch_name) {
+            self.remote.clone()
+        } else {
+            self.tracking_remote(branch_name)?
+        };
+        self.retry_locked(|| self.engine.checkout_branch(&self.repo, branch_name, &remote))?;
+        self.ensure_on_branch(branch_name)?;
+
+        tracing::info!(branch = %branch_name, "Successfully checked out branch");
+        if remote == self.remote {
+            return Ok(None);
+        }
+        tracing::info!(branch = %branch_name, remote = %remote, "Tracking a remote other than the configured one");
+        Ok(Some(remote))
+    }
+
+    /// Stashes uncommitted changes, untracked files included, under `message`.
+    /// `None` when there was nothing to stash.
+    pub fn stash_push(&self, message: &str) -> Result<Option<Oid>> {
+        let mut repo = self.reopen()?;
+        let signature = signature(&repo)?;
+        match repo.stash_save(&signature, message, Some(git2::StashFlags::INCLUDE_UNTRACKED)) {
+            Ok(oid) => {
+                tracing::info!(stash = %oid, "Stashed uncommitted changes");
+                Ok(Some(oid))
+            }
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e).git_context("Failed to stash uncommitted changes"),
+        }
+    }
+
+    /// Applies stash entry `index` (0 is the latest) with its staged changes,
+    /// then drops it. Changes that conflict are written with conflict markers.
+    pub fn stash_pop(&self, index: usize) -> Result<()> {
+        let mut repo = self.reopen()?;
+        let mut options = git2::StashApplyOptions::new();
+        options.reinstantiate_index();
+        repo.stash_pop(index, Some(&mut options))
+            .git_context("Failed to restore stashed changes")?;
+        tracing::info!("Restored stashed changes");
+        Ok(())
+    }
 
-        // Find the branch
-        let branch = self
+    /// Whether stash commit `stash` would apply onto HEAD without conflicts:
+    /// its changes merge with HEAD's, and none of its untracked files exist there
+    fn stash_applies_cleanly(&self, stash: Oid) -> Result<bool> {
+        let stash = self.repo.find_commit(stash).git_context("Failed to read the stash")?;
+        let base = stash.parent(0).git_context("Failed to read the stash's base")?;
+        let head = self
             .repo
-            .find_branch(branch_name, git2::BranchType::Local)
+            .head()
+            .and_then(|head| head.peel_to_tree())
+            .git_context("Failed to read HEAD")?;
+        let base_tree = base.tree().git_context("Failed to read the stash's base")?;
+        let stash_tree = stash.tree().git_context("Failed to read the stash")?;
+        let merged = self
+            .repo
+            .merge_trees(&base_tree, &head, &stash_tree, None)
+            .git_context("Failed to merge the stash")?;
+        if merged.has_conflicts() {
+            return Ok(false);
+        }
+        // The third parent holds the untracked files
+        if let Ok(untracked) = stash.parent(2) {
+            let untracked = untracked.tree().git_context("Failed to read the stash")?;
+            let mut clash = false;
+            untracked
+                .walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+                    let path = format!("{}{}", dir, entry.name().unwrap_or_default());
+                    if entry.kind() == Some(git2::ObjectType::Blob) && head.get_path(Path::new(&path)).is_ok() {
+                        clash = true;
+                        return git2::TreeWalkResult::Abort;
+                    }
+                    git2::TreeWalkResult::Ok
+                })
+                .or_else(|e| if clash { Ok(()) } else { Err(e) })
+                .git_context("Failed to read the stash")?;
+            if clash {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Another handle on the repository, for the libgit2 calls that need it mutably
+    fn reopen(&self) -> Result<Repository> {
+        Repository::open(self.repo.path()).git_context("Failed to open repository")
+    }
+
+    /// Creates local branch `new_name` at the tip of `from_branch` (local, or
+    /// the configured remote's copy) and checks it out
+    pub fn create_and_checkout_branch(&self, new_name: &str, from_branch: &str) -> Result<()> {
+        tracing::info!(branch = %new_name, from = %from_branch, "Creating branch");
+
+        let from = self
+            .repo
+            .find_branch(from_branch, git2::BranchType::Local)
             .or_else(|_| {
-                // Try to find remote branch and create local tracking branch
-                self.create_tracking_branch(branch_name)
+                self.repo
+                    .find_branch(&format!("{}/{}", self.remote, from_branch), git2::BranchType::Remote)
             })
-            .with_context(|| format!("Branch '{}' not found", branch_name))?;
-
-        let commit = branch
+            .map_err(|_| GitError::BranchNotFound {
+                branch: from_branch.to_string(),
+            })?;
+        let tip = from
             .get()
             .peel_to_commit()
-            .context("Failed to get commit for branch")?;
-
-        // Checkout the branch
+            .git_context(format!("Failed to read the tip of '{}'", from_branch))?;
         self.repo
-            .checkout_tree(commit.as_object(), None)
-            .context("Failed to checkout tree")?;
+            .branch(new_name, &tip, false)
+            .git_context(format!("Failed to create branch '{}'", new_name))?;
 
-        // Update HEAD
-        self.repo
-            .set_head(&format!("refs/heads/{}", branch_name))
-            .context("Failed to update HEAD")?;
-
-        tracing::info!("Successfully checked out branch: {}", branch_name);
-        Ok(())
+        self.retry_locked(|| self.engine.checkout_branch(&self.repo, new_name, &self.remote))?;
+        self.ensure_on_branch(new_name)
     }
 
-    fn create_tracking_branch(&self, branch_name: &str) -> Result<git2::Branch<'_>, git2::Error> {
-        // Try to find remote branch (usually origin/branch_name)
-        let remote_branch = self
-            .repo
-            .find_branch(&format!("origin/{}", branch_name), git2::BranchType::Remote)?;
-        let remote_commit = remote_branch.get().peel_to_commit()?;
+    /// Gets onto the branch `plan` names: created from `from_branch`, checked
+    /// out as [`Self::checkout_branch`] does, or deleted and created again.
+    /// Returns the remote a reused branch was tracked from, as `checkout_branch` does.
+    pub fn checkout_planned_branch(&self, plan: &BranchPlan, from_branch: &str) -> Result<Option<String>> {
+        match plan {
+            BranchPlan::Create { name } => self.create_and_checkout_branch(name, from_branch).map(|()| None),
+            BranchPlan::Reuse { name, from_remote } => {
+                // Found with `git ls-remote`, so it may never have been fetched
+                if *from_remote {
+                    self.fetch_branch(name)?;
+                }
+                self.checkout_branch(name)
+            }
+            BranchPlan::Recreate {
+                name,
+                delete_local,
+                force_push,
+            } => {
+                // The force-push leases on the remote-tracking branch, which must be current
+                if *force_push {
+                    self.fetch_branch(name)?;
+                }
+                if *delete_local {
+                    // The checked-out branch can't be deleted
+                    if self.current_branch().ok().as_deref() == Some(name.as_str()) {
+                        self.checkout_branch(from_branch)?;
+                    }
+                    self.delete_branch(name)?;
+                }
+                self.create_and_checkout_branch(name, from_branch).map(|()| None)
+            }
+        }
+    }
 
-        // Create local tracking branch
-        let local_branch = self.repo.branch(branch_name, &remote_commit, false)?;
+    /// Checks `branch` out in a new linked worktree `name` at `path`, first
+    /// creating it from the remote's copy when it only exists there. Fails
+    /// while `branch` is checked out in another worktree.
+    pub fn add_worktree(&self, name: &str, path: &Path, branch: &str) -> Result<()> {
+        let local = match self.repo.find_branch(branch, git2::BranchType::Local) {
+            Ok(local) => local,
+            Err(_) => engine::create_tracking_branch(&self.repo, branch, &self.tracking_remote(branch)?)
+                .map_err(|_| GitError::BranchNotFound {
+                    branch: branch.to_string(),
+                })?,
+        };
+        let mut opts = git2::WorktreeAddOptions::new();
+        opts.reference(Some(local.get()));
+        self.repo
+            .worktree(name, path, Some(&opts))
+            .git_context(format!("Failed to add a worktree for '{}'", branch))?;
+        tracing::info!(branch = %branch, path = %path.display(), "Added worktree");
+        Ok(())
+    }
 
-        // Set up tracking
-        let mut branch_config = self.repo.config()?;
-        branch_config.set_str(&format!("branch.{}.remote", branch_name), "origin")?;
-        branch_config.set_str(
-            &format!("branch.{}.merge", branch_name),
-            &format!("refs/heads/{}", branch_name),
-        )?;
+    /// Removes linked worktree `name` along with its working tree; one that
+    /// doesn't exist is already removed
+    pub fn remove_worktree(&self, name: &str) -> Result<()> {
+        let Ok(worktree) = self.repo.find_worktree(name) else {
+            return Ok(());
+        };
+        let mut opts = git2::WorktreePruneOptions::new();
+        opts.valid(true).working_tree(true);
+        worktree
+            .prune(Some(&mut opts))
+            .git_context(format!("Failed to remove worktree '{}'", name))?;
+        tracing::info!(worktree = %name, "Removed worktree");
+        Ok(())
+    }
 
-        Ok(local_branch)
+    /// The remote to create local `branch_name` from: the configured one
+    /// when it has the branch, otherwise the single other remote that does
+    fn tracking_remote(&self, branch_name: &str) -> Result<String> {
+        let remotes = self.repo.remotes().git_context("Failed to list remotes")?;
+        let has_branch = |remote: &str| {
+            self.repo
+                .find_branch(&format!("{}/{}", remote, branch_name), git2::BranchType::Remote)
+                .is_ok()
+        };
+        if has_branch(&self.remote) {
+            return Ok(self.remote.clone());
+        }
+        let mut found: Vec<&str> = remotes.iter().flatten().filter(|remote| has_branch(remote)).collect();
+        found.sort_unstable();
+        match found.len() {
+            // Let the engine report it the way it reports a missing local branch
+            0 => Ok(self.remote.clone()),
+            1 => Ok(found.remove(0).to_string()),
+            _ => Err(GitError::AmbiguousRemoteBranch {
+                branch: branch_name.to_string(),
+                candidates: found.iter().map(|remote| format!("{}/{}", remote, branch_name)).collect(),
+            }),
+        }
     }
 
-    /// Cherry-picks a commit to the current branch
+    /// Cherry-picks a commit to the current branch. A merge commit is picked
+    /// against its first parent, i.e. as the changes it brought into the
+    /// branch it was merged into.
     pub fn cherry_pick(&self, commit_sha: &str) -> Result<CherrypickResult> {
-        tracing::info!("Cherry-picking commit: {}", commit_sha);
+        self.cherry_pick_with_mainline(commit_sha, None)
+    }
+
+    /// Like [`GitOperations::cherry_pick`], but picks a merge commit against
+    /// parent `mainline` (1-based) when given. Naming a parent the commit
+    /// doesn't have, or one for a commit that isn't a merge, fails.
+    pub fn cherry_pick_with_mainline(&self, commit_sha: &str, mainline: Option<u32>) -> Result<CherrypickResult> {
+        tracing::info!(commit_sha = %commit_sha, ?mainline, "Cherry-picking commit");
 
         // First, validate if we're in the correct repository
         self.validate_repository_context(commit_sha)?;
 
-        let oid = Oid::from_str(commit_sha)
-            .with_context(|| format!("Invalid commit SHA: {}", commit_sha))?;
+        let oid = Oid::from_str(commit_sha).map_err(|_| GitError::InvalidSha {
+            sha: commit_sha.to_string(),
+        })?;
 
         let commit = self
             .repo
             .find_commit(oid)
-            .with_context(|| format!("Commit not found: {}", commit_sha))?;
-
-        // Perform the cherry-pick
-        let mut opts = CherrypickOptions::new();
-        self.repo
-            .cherrypick(&commit, Some(&mut opts))
-            .context("Failed to cherry-pick commit")?;
-
-        // Check repository state after cherry-pick
-    match self.repo.state() {
-        RepositoryState::Clean | RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => {
-                // No conflicts, commit the change
-                let signature = self.get_signature()?;
-                let tree_id = self.repo.index()?.write_tree()?;
-                let tree = self.repo.find_tree(tree_id)?;
-                let parent = self.repo.head()?.peel_to_commit()?;
-
-                let commit_id = self.repo.commit(
-                    Some("HEAD"),
-                    &signature,
-                    &signature,
-            commit.message().unwrap_or("Cherry-pick"),
-                    &tree,
-                    &[&parent],
-                )?;
-
-                tracing::info!("Cherry-pick successful, created commit: {}", commit_id);
-
-                Ok(CherrypickResult {
-                    success: true,
-                    conflicts: Vec::new(),
-                    commit_sha: Some(commit_id.to_string()),
+            .map_err(|_| GitError::CommitNotFound {
+                sha: commit_sha.to_string(),
+            })?;
+
+        let parents = commit.parent_count();
+        let mainline = match mainline {
+            None => (parents > 1).then_some(1),
+            Some(parent) if parents > 1 && (1..=parents).contains(&(parent as usize)) => Some(parent),
+            Some(parent) => {
+                return Err(GitError::InvalidMainline {
+                    sha: commit_sha.to_string(),
+                    mainline: parent,
+                    parents,
                 })
             }
-        RepositoryState::CherryPick | RepositoryState::Merge | RepositoryState::Revert | RepositoryState::RebaseMerge | RepositoryState::Rebase | RepositoryState::RebaseInteractive | RepositoryState::CherryPickSequence => {
-                // There are conflicts
-                let conflicts = self.get_conflicts()?;
-                tracing::warn!("Cherry-pick has conflicts: {:?}", conflicts);
-
-                Ok(CherrypickResult {
-                    success: false,
-                    conflicts,
-                    commit_sha: None,
-                })
-            }
-            state => {
-                anyhow::bail!("Unexpected repository state after cherry-pick: {:?}", state);
-            }
+        };
+
+        let scope = PickScope::new(&self.repo, &commit, mainline, self.only_commit_paths)?;
+        let stray = scope.stray_staged(&self.repo, &self.repo.index()?)?;
+        if !stray.is_empty() && !scope.only_commit_paths {
+            return Err(scope.refusal(stray));
+        }
+        let set_aside = self.retry_locked(|| self.set_aside_staged(&stray))?;
+        let picked = self.retry_locked(|| self.engine.cherry_pick(&self.repo, &commit, &scope, mainline));
+        self.restage(set_aside)?;
+
+        let mut result = picked?;
+        result.mainline = mainline;
+        if !result.success && !result.rerere_resolved.is_empty() {
+            result = self.finish_rerere(&commit, result)?;
+        }
+        if !result.success {
+            result.binary_conflicts = binary_conflicts(&self.repo)?;
+        }
+        if result.success {
+            tracing::info!(
+                commit_sha = %commit_sha,
+                new_commit_sha = ?result.commit_sha,
+                "Cherry-pick successful"
+            );
+        } else {
+            tracing::warn!(
+                commit_sha = %commit_sha,
+                conflict_count = result.conflicts.len(),
+                conflict_files = ?result.conflicts,
+                "Cherry-pick has conflicts"
+            );
         }
+        Ok(result)
     }
 
-    fn get_conflicts(&self) -> Result<Vec<String>> {
+    /// Resets the index entries of `paths` to HEAD, returning what was staged
+    /// for each so [`GitOperations::restage`] can put it back. The working
+    /// tree is left alone.
+    fn set_aside_staged(&self, paths: &[String]) -> Result<Vec<(String, Option<IndexEntry>)>> {
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
         let index = self.repo.index()?;
-        let mut conflicts = Vec::new();
-
-        if index.has_conflicts() {
-            let conflict_iter = index
-                .conflicts()
-                .context("Failed to get conflicts iterator")?;
-
-            for conflict in conflict_iter {
-                let conflict = conflict?;
-                if let Some(our) = conflict.our {
-                    let path = String::from_utf8_lossy(&our.path).to_string();
-                    conflicts.push(path);
+        let staged = paths
+            .iter()
+            .map(|path| (path.clone(), index.get_path(Path::new(path), 0)))
+            .collect();
+        let head = self
+            .repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .git_context("Failed to resolve HEAD")?;
+        self.repo
+            .reset_default(Some(head.as_object()), paths)
+            .git_context("Failed to set staged changes aside")?;
+
+        tracing::info!(paths = ?paths, "Set aside staged changes outside the picked commit");
+        Ok(staged)
+    }
+
+    /// Stages again what [`GitOperations::set_aside_staged`] set aside
+    fn restage(&self, staged: Vec<(String, Option<IndexEntry>)>) -> Result<()> {
+        if staged.is_empty() {
+            return Ok(());
+        }
+        let mut index = self.repo.index()?;
+        index.read(false).git_context("Failed to read index")?;
+        for (path, entry) in staged {
+            match entry {
+                Some(entry) => index.add(&entry),
+                None if index.get_path(Path::new(&path), 0).is_some() => index.remove_path(Path::new(&path)),
+                None => continue,
+            }
+            .git_context(format!("Failed to restage {}", path))?;
+        }
+        index.write().git_context("Failed to write index")?;
+        Ok(())
+    }
+
+    /// Stages the files rerere resolved and, if that leaves no conflicts,
+    /// commits the pick as if it had applied cleanly
+    fn finish_rerere(&self, commit: &git2::Commit, result: CherrypickResult) -> Result<CherrypickResult> {
+        let mut index = self.repo.index()?;
+        index.read(false).git_context("Failed to read index")?;
+        for path in &result.rerere_resolved {
+            index
+                .add_path(Path::new(path))
+                .git_context(format!("Failed to stage {}", path))?;
+        }
+        index.write().git_context("Failed to write index")?;
+
+        let conflicts = conflicted_paths(&self.repo)?;
+        if !conflicts.is_empty() {
+            return Ok(CherrypickResult { conflicts, ..result });
+        }
+
+        let new_sha = self.engine.continue_cherry_pick(&self.repo, commit.message())?;
+        tracing::info!(
+            resolved = ?result.rerere_resolved,
+            "Conflicts resolved via rerere"
+        );
+        Ok(CherrypickResult {
+            success: true,
+            conflicts: Vec::new(),
+            commit_sha: Some(new_sha),
+            rerere_resolved: result.rerere_resolved,
+            binary_conflicts: Vec::new(),
+            mainline: result.mainline,
+        })
+    }
+
+    /// Whether recorded conflict resolutions are reused in this repository
+    pub fn rerere_enabled(&self) -> bool {
+        rerere::enabled(&self.repo)
+    }
+
+    /// Signature of the current conflicts in `paths`, read from the working tree
+    pub fn conflict_signature(&self, paths: &[String]) -> Option<ConflictSignature> {
+        let workdir = self.repo.workdir()?;
+        let files: Vec<(String, String)> = paths
+            .iter()
+            .filter_map(|p| Some((p.clone(), std::fs::read_to_string(workdir.join(p)).ok()?)))
+            .collect();
+        conflict_signature(&files)
+    }
+
+    /// Settles the conflict at `path` by keeping `side` whole: the chosen blob
+    /// replaces the conflict in the index and is written to the working tree.
+    /// When that side deleted the file, the file is removed instead.
+    pub fn resolve_conflict(&self, path: &str, side: ConflictSide) -> Result<()> {
+        let mut index = self.repo.index()?;
+        index.read(false).git_context("Failed to read index")?;
+        let conflict = index
+            .conflicts()
+            .git_context("Failed to get conflicts iterator")?
+            .filter_map(|c| c.ok())
+            .find(|c| conflict_path(c).as_deref() == Some(path))
+            .ok_or_else(|| GitError::NotConflicted {
+                path: path.to_string(),
+            })?;
+        let chosen = match side {
+            ConflictSide::Ours => conflict.our,
+            ConflictSide::Theirs => conflict.their,
+        };
+
+        index
+            .conflict_remove(Path::new(path))
+            .git_context(format!("Failed to clear the conflict on {}", path))?;
+        match chosen {
+            Some(mut entry) => {
+                // Stage 0: a resolved entry rather than one side of a conflict
+                entry.flags &= !INDEX_STAGE_MASK;
+                index
+                    .add(&entry)
+                    .git_context(format!("Failed to stage {}", path))?;
+                index.write().git_context("Failed to write index")?;
+                let mut checkout = git2::build::CheckoutBuilder::new();
+                checkout.force().path(path);
+                self.repo
+                    .checkout_index(Some(&mut index), Some(&mut checkout))
+                    .git_context(format!("Failed to write {}", path))?;
+            }
+            None => {
+                index.write().git_context("Failed to write index")?;
+                if let Some(workdir) = self.repo.workdir() {
+                    // Already gone is as good as removed
+                    let _ = std::fs::remove_file(workdir.join(path));
                 }
             }
         }
 
-        Ok(conflicts)
+        tracing::info!(path = %path, side = ?side, "Resolved conflict by taking one side");
+        Ok(())
     }
 
     /// Continues cherry-pick after conflicts are resolved
@@ -194,77 +756,297 @@ impl GitOperations {
         tracing::info!("Continuing cherry-pick after conflict resolution");
 
         // Check if conflicts are resolved
-        let index = self.repo.index()?;
-        if index.has_conflicts() {
-            anyhow::bail!("There are still unresolved conflicts. Please resolve them first.");
+        let conflicts = conflicted_paths(&self.repo)?;
+        if !conflicts.is_empty() {
+            return Err(GitError::Conflicts { files: conflicts });
         }
 
-        // Stage all changes
-        let mut index = self.repo.index()?;
-        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
-        index.write()?;
-
-        // Create commit
-        let signature = self.get_signature()?;
-        let tree_id = index.write_tree()?;
-        let tree = self.repo.find_tree(tree_id)?;
-        let parent = self.repo.head()?.peel_to_commit()?;
-
-        let message = commit_message.unwrap_or("Cherry-pick (resolved conflicts)");
-        let commit_id = self.repo.commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            message,
-            &tree,
-            &[&parent],
-        )?;
-
-    // Clean up cherry-pick state if any
-    let _ = self.repo.cleanup_state();
+        let commit_id = self.retry_locked(|| self.engine.continue_cherry_pick(&self.repo, commit_message))?;
 
         tracing::info!(
-            "Cherry-pick continued successfully, created commit: {}",
-            commit_id
+            new_commit_sha = %commit_id,
+            "Cherry-pick continued successfully"
         );
-        Ok(commit_id.to_string())
+        Ok(commit_id)
     }
 
     /// Aborts the current cherry-pick operation
     pub fn abort_cherry_pick(&self) -> Result<()> {
         tracing::info!("Aborting cherry-pick");
 
-    let _ = self.repo.cleanup_state();
+        self.retry_locked(|| self.engine.abort_cherry_pick(&self.repo))?;
+
+        tracing::info!("Cherry-pick aborted successfully");
+        Ok(())
+    }
+
+    /// True while a cherry-pick stopped on conflicts is waiting to be continued
+    /// or aborted, including one commit of a stopped `git cherry-pick A B`
+    pub fn cherry_pick_in_progress(&self) -> bool {
+        matches!(
+            self.repo.state(),
+            RepositoryState::CherryPick | RepositoryState::CherryPickSequence
+        )
+    }
 
-        // Reset to HEAD
-        let head = self.repo.head()?.peel_to_commit()?;
+    /// Files still conflicted in the index; a file drops off once it's
+    /// resolved and staged, here or in another terminal
+    pub fn conflicted_files(&self) -> Result<Vec<String>> {
+        conflicted_paths(&self.repo)
+    }
+
+    /// The commit the stopped cherry-pick is applying, from `CHERRY_PICK_HEAD`
+    pub fn cherry_pick_head(&self) -> Option<String> {
+        let head = self.repo.find_reference("CHERRY_PICK_HEAD").ok()?;
+        Some(head.peel_to_commit().ok()?.id().to_string())
+    }
+
+    /// Commits the stopped cherry-pick once its conflicts are resolved, with
+    /// the message of the commit being picked
+    pub fn resume_cherry_pick(&self) -> Result<String> {
+        let message = self
+            .repo
+            .find_reference("CHERRY_PICK_HEAD")
+            .and_then(|head| head.peel_to_commit())
+            .ok()
+            .and_then(|commit| commit.message().map(str::to_string));
+        self.continue_cherry_pick(message.as_deref())
+    }
+
+    /// Sha of the commit HEAD points at
+    pub fn head_sha(&self) -> Result<String> {
+        let head = self
+            .repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .git_context("Failed to resolve HEAD")?;
+        Ok(head.id().to_string())
+    }
+
+    /// Hard-resets the current branch to HEAD's first parent, returning the
+    /// sha of the commit that was dropped
+    pub fn undo_last_commit(&self) -> Result<String> {
+        let head = self
+            .repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .git_context("Failed to resolve HEAD")?;
+        let parent = head
+            .parent(0)
+            .git_context(format!("Commit {} has no parent to reset to", head.id()))?;
+
+        self.retry_locked(|| {
+            self.repo
+                .reset(parent.as_object(), git2::ResetType::Hard, None)
+                .git_context("Failed to reset to parent commit")
+        })?;
+
+        tracing::info!(undone = %head.id(), head = %parent.id(), "Undid last commit");
+        Ok(head.id().to_string())
+    }
+
+    /// Replaces the commits made on the current branch since `base` with one
+    /// commit of the same tree, its message built by [`squash_message`] from
+    /// `title` and `source_shas` (the commits those picks were copied from).
+    /// Returns the new commit's sha.
+    pub fn squash_picks(&self, base: &str, title: &str, source_shas: &[String]) -> Result<String> {
+        let base_oid = Oid::from_str(base).map_err(|_| GitError::InvalidSha { sha: base.to_string() })?;
+        let base_commit = self
+            .repo
+            .find_commit(base_oid)
+            .map_err(|_| GitError::CommitNotFound { sha: base.to_string() })?;
+        let head = self
+            .repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .git_context("Failed to resolve HEAD")?;
+
+        let mut sources = Vec::new();
+        for sha in source_shas {
+            let commit = Oid::from_str(sha)
+                .and_then(|oid| self.repo.find_commit(oid))
+                .map_err(|_| GitError::CommitNotFound { sha: sha.clone() })?;
+            sources.push((sha.clone(), commit.summary().unwrap_or_default().to_string()));
+        }
+        let message = squash_message(title, &sources);
+
+        let signature = signature(&self.repo)?;
+        let tree = head.tree().git_context("Failed to read HEAD's tree")?;
+        let squashed = self
+            .repo
+            .commit(None, &signature, &signature, &message, &tree, &[&base_commit])
+            .git_context("Failed to create squashed commit")?;
+        let squashed = self.repo.find_commit(squashed)?;
+        // The tree is unchanged, so a soft reset only moves the branch
         self.repo
-            .reset(head.as_object(), git2::ResetType::Hard, None)
-            .context("Failed to reset to HEAD")?;
+            .reset(squashed.as_object(), git2::ResetType::Soft, None)
+            .git_context("Failed to move branch to squashed commit")?;
 
-        tracing::info!("Cherry-pick aborted successfully");
+        tracing::info!(base = %base, squashed = %squashed.id(), picks = source_shas.len(), "Squashed picks");
+        Ok(squashed.id().to_string())
+    }
+
+    /// Local branches and the configured remote's, without the remote's
+    /// prefix, sorted and deduplicated
+    pub fn branch_names(&self) -> Result<Vec<String>> {
+        let mut names = BTreeSet::new();
+        for branch in self.repo.branches(None).git_context("Failed to list branches")? {
+            let (branch, kind) = branch?;
+            let Some(name) = branch.name()? else {
+                continue;
+            };
+            let name = match kind {
+                git2::BranchType::Local => name,
+                git2::BranchType::Remote => match name.strip_prefix(&format!("{}/", self.remote)) {
+                    Some("HEAD") | None => continue,
+                    Some(name) => name,
+                },
+            };
+            names.insert(name.to_string());
+        }
+        Ok(names.into_iter().collect())
+    }
+
+    pub fn branch_exists(&self, name: &str) -> bool {
+        self.repo.find_branch(name, git2::BranchType::Local).is_ok()
+    }
+
+    /// Whether `name` exists as a local branch and on the configured remote.
+    /// The remote is asked directly (`git ls-remote`), since a remote-tracking
+    /// ref may be stale.
+    pub fn branch_presence(&self, name: &str) -> Result<BranchPresence> {
+        let heads = cli::CliEngine::ls_remote(&self.repo, &self.remote)?;
+        let remote_ref = format!("refs/heads/{}", name);
+        Ok(BranchPresence {
+            local: self.branch_exists(name),
+            remote: heads.lines().any(|line| line.split('\t').nth(1) == Some(remote_ref.as_str())),
+        })
+    }
+
+    /// Deletes local branch `name`. Fails for the checked-out branch.
+    pub fn delete_branch(&self, name: &str) -> Result<()> {
+        let mut branch = self
+            .repo
+            .find_branch(name, git2::BranchType::Local)
+            .map_err(|_| GitError::BranchNotFound {
+                branch: name.to_string(),
+            })?;
+        branch
+            .delete()
+            .git_context(format!("Failed to delete branch '{}'", name))?;
+
+        tracing::info!(branch = %name, "Deleted branch");
+        Ok(())
+    }
+
+    /// How many commits local `branch` is ahead of and behind `<remote>/<branch>`.
+    /// `None` when either branch is missing.
+    pub fn ahead_behind(&self, branch: &str) -> Result<Option<(usize, usize)>> {
+        let (Some(local), Some(remote)) = (self.branch_tip(branch, false), self.branch_tip(branch, true)) else {
+            return Ok(None);
+        };
+        let counts = self
+            .repo
+            .graph_ahead_behind(local, remote)
+            .git_context(format!("Failed to compare '{}' with {}", branch, self.remote))?;
+        Ok(Some(counts))
+    }
+
+    /// Commits on local `branch` that `<remote>/<branch>` doesn't have, newest first
+    pub fn unpushed_commits(&self, branch: &str) -> Result<Vec<UnpushedCommit>> {
+        match self.ahead_behind(branch)? {
+            Some((ahead, _)) if ahead > 0 => {}
+            _ => return Ok(Vec::new()),
+        }
+        let (Some(local), Some(remote)) = (self.branch_tip(branch, false), self.branch_tip(branch, true)) else {
+            return Ok(Vec::new());
+        };
+
+        let mut walk = self.repo.revwalk().git_context("Failed to walk history")?;
+        walk.push(local)?;
+        walk.hide(remote)?;
+        walk.map(|oid| {
+            let commit = self.repo.find_commit(oid?)?;
+            Ok(UnpushedCommit {
+                sha: commit.id().to_string(),
+                summary: commit.summary().unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+    }
+
+    /// Points local `branch` back at `<remote>/<branch>`, discarding its
+    /// unpushed commits. A checked-out branch gets a hard reset.
+    pub fn reset_branch_to_remote(&self, branch: &str) -> Result<()> {
+        let remote = self
+            .branch_tip(branch, true)
+            .ok_or_else(|| GitError::BranchNotFound {
+                branch: format!("{}/{}", self.remote, branch),
+            })?;
+        let commit = self.repo.find_commit(remote)?;
+
+        if self.current_branch().ok().as_deref() == Some(branch) {
+            self.repo
+                .reset(commit.as_object(), git2::ResetType::Hard, None)
+                .git_context(format!("Failed to reset '{}' to {}", branch, self.remote))?;
+        } else {
+            self.repo
+                .branch(branch, &commit, true)
+                .git_context(format!("Failed to move '{}' to {}", branch, self.remote))?;
+        }
+
+        tracing::info!(branch = %branch, remote = %self.remote, head = %remote, "Reset branch to the remote");
         Ok(())
     }
 
-    fn get_signature(&self) -> Result<Signature<'_>> {
-        // Try to get signature from git config
-        let config = self.repo.config().context("Failed to get git config")?;
+    /// Commits on `branch` committed at or after `since`, newest first. The
+    /// local branch is read when there is one, otherwise `<remote>/<branch>`.
+    pub fn commits_since(&self, branch: &str, since: DateTime<Utc>) -> Result<Vec<BranchCommit>> {
+        let tip = self
+            .branch_tip(branch, false)
+            .or_else(|| self.branch_tip(branch, true))
+            .ok_or_else(|| GitError::BranchNotFound {
+                branch: branch.to_string(),
+            })?;
+        let mut walk = self.repo.revwalk().git_context("Failed to walk history")?;
+        walk.set_sorting(git2::Sort::TIME)?;
+        walk.push(tip)?;
 
-        let name = config
-            .get_string("user.name")
-            .context("Git user.name not configured")?;
-        let email = config
-            .get_string("user.email")
-            .context("Git user.email not configured")?;
+        let mut commits = Vec::new();
+        for oid in walk {
+            let commit = self.repo.find_commit(oid?)?;
+            let committed_at = Utc
+                .timestamp_opt(commit.time().seconds(), 0)
+                .single()
+                .unwrap_or_default();
+            if committed_at < since {
+                break;
+            }
+            commits.push(BranchCommit {
+                sha: commit.id().to_string(),
+                message: commit.message().unwrap_or_default().to_string(),
+                committed_at,
+            });
+        }
+        Ok(commits)
+    }
 
-        Signature::now(&name, &email).context("Failed to create git signature")
+    /// Tip of local `branch`, or of `<remote>/<branch>` when `remote`
+    fn branch_tip(&self, branch: &str, remote: bool) -> Option<Oid> {
+        let (name, kind) = if remote {
+            (format!("{}/{}", self.remote, branch), git2::BranchType::Remote)
+        } else {
+            (branch.to_string(), git2::BranchType::Local)
+        };
+        self.repo.find_branch(&name, kind).ok()?.get().target()
     }
 
     /// Validates if we're in the correct repository context for the commit
     fn validate_repository_context(&self, commit_sha: &str) -> Result<()> {
         // Check if the commit exists locally first
-        let oid = Oid::from_str(commit_sha)
-            .with_context(|| format!("Invalid commit SHA: {}", commit_sha))?;
+        let oid = Oid::from_str(commit_sha).map_err(|_| GitError::InvalidSha {
+            sha: commit_sha.to_string(),
+        })?;
 
         if self.repo.find_commit(oid).is_ok() {
             return Ok(()); // Commit exists, we're good
@@ -272,10 +1054,10 @@ impl GitOperations {
 
         // Get the current repository's remote URL
         let remote_url = match self.get_repository_remote_url() {
-            Ok(url) => url,
-            Err(_) => {
+            Some(url) => url,
+            None => {
                 // No remote configured
-                anyhow::bail!(
+                let message = format!(
                     "⚠️  Repository Mismatch Warning ⚠️\n\n\
                     The commit {} was not found in the current repository.\n\
                     This repository has no remote configured.\n\n\
@@ -289,11 +1071,16 @@ impl GitOperations {
                         .unwrap_or_else(|_| "unknown".to_string()),
                     &commit_sha[..8]
                 );
+                return Err(GitError::RepositoryMismatch { message });
             }
         };
 
-        // Show detailed warning
-        anyhow::bail!(
+        // Show detailed warning, naming the repository rather than the raw URL when possible
+        let current = match parse_github_url(&remote_url) {
+            Some(coords) => format!("{} ({})", coords.full_name(), coords.host),
+            None => remote_url,
+        };
+        let message = format!(
             "⚠️  Repository Mismatch Warning ⚠️\n\n\
             The commit {} was not found in the current repository.\n\
             This likely means you are in a different repository than expected.\n\n\
@@ -306,47 +1093,296 @@ impl GitOperations {
             3. Run 'git log --oneline | grep {}' to verify the commit exists\n\n\
             Operation cancelled for safety.",
             commit_sha,
-            remote_url,
+            current,
             std::env::current_dir()
                 .map(|p| p.display().to_string())
                 .unwrap_or_else(|_| "unknown".to_string()),
             commit_sha,
             &commit_sha[..8]
         );
+        Err(GitError::RepositoryMismatch { message })
     }
 
     /// Gets the remote URL of the repository
-    fn get_repository_remote_url(&self) -> Result<String> {
-        let remote = self
-            .repo
-            .find_remote("origin")
-            .context("No 'origin' remote found")?;
+    fn get_repository_remote_url(&self) -> Option<String> {
+        let remote = self.repo.find_remote("origin").ok()?;
 
-        let url = remote
-            .url()
-            .context("Remote URL not available")?
-            .to_string();
+        remote.url().map(str::to_string)
+    }
 
-        Ok(url)
+    /// GitHub host/owner/repo of the `origin` remote, if it points at one
+    pub fn origin_coords(&self) -> Option<GitHubCoords> {
+        parse_github_url(&self.get_repository_remote_url()?)
     }
 
-    /// Fetches latest changes from remote
+    /// Fetches latest changes from the configured remote
     pub fn fetch(&self) -> Result<()> {
-        tracing::info!("Fetching latest changes from remote");
+        tracing::info!(remote = %self.remote, "Fetching latest changes from remote");
+
+        let mut remote = self.find_remote()?;
+        remote
+            .fetch(&[] as &[&str], None, None)
+            .git_context("Failed to fetch from remote")?;
+
+        tracing::info!(remote = %self.remote, "Successfully fetched changes from remote");
+        Ok(())
+    }
 
-        let mut remote = self
+    fn find_remote(&self) -> Result<git2::Remote<'_>> {
+        self.repo
+            .find_remote(&self.remote)
+            .git_context(format!("Failed to find '{}' remote", self.remote))
+    }
+
+    /// Pushes local `branch_name` to the same branch on the configured remote
+    /// and tracks it there. With `force_with_lease` the push may rewrite the
+    /// remote's branch, but only while it's still where `<remote>/<branch>`
+    /// last saw it.
+    pub fn push_branch(&self, branch_name: &str, force_with_lease: bool) -> Result<()> {
+        tracing::info!(remote = %self.remote, branch = branch_name, force_with_lease, "Pushing branch");
+
+        // Where the remote's branch must still be; zero for one never fetched
+        let lease = force_with_lease.then(|| self.branch_tip(branch_name, true).unwrap_or_else(Oid::zero));
+        self.engine.push(&self.repo, &self.remote, branch_name, lease)?;
+
+        // The push updated <remote>/<branch>, so it can be the upstream now
+        let upstream = self
             .repo
-            .find_remote("origin")
-            .context("Failed to find 'origin' remote")?;
+            .find_branch(branch_name, git2::BranchType::Local)
+            .and_then(|mut branch| branch.set_upstream(Some(&format!("{}/{}", self.remote, branch_name))));
+        if let Err(e) = upstream {
+            tracing::warn!(branch = branch_name, "Pushed, but could not track {}: {}", self.remote, e);
+        }
+
+        tracing::info!(remote = %self.remote, branch = branch_name, "Successfully pushed branch");
+        Ok(())
+    }
 
+    /// Fetches a single branch from the configured remote into
+    /// `refs/remotes/<remote>/<branch>`
+    pub fn fetch_branch(&self, branch: &str) -> Result<()> {
+        tracing::info!(remote = %self.remote, branch, "Fetching branch from remote");
+
+        let mut remote = self.find_remote()?;
+        let tracking = format!("refs/remotes/{}/{}", self.remote, branch);
+        let refspec = format!("+refs/heads/{}:{}", branch, tracking);
         remote
-            .fetch(&[] as &[&str], None, None)
-            .context("Failed to fetch from remote")?;
+            .fetch(&[refspec.as_str()], None, None)
+            .git_context(format!("Failed to fetch branch '{}'", branch))?;
+
+        // libgit2 treats a refspec matching nothing as success
+        if self.repo.find_reference(&tracking).is_err() {
+            return Err(GitError::BranchNotFound {
+                branch: format!("{}/{}", self.remote, branch),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Fetches `refs/pull/<number>/head` from the configured remote, which
+    /// GitHub keeps even after the PR's head branch is deleted
+    pub fn fetch_pull_ref(&self, number: u64) -> Result<()> {
+        tracing::info!(remote = %self.remote, pr = number, "Fetching pull request ref");
+
+        let mut remote = self.find_remote()?;
+        let refspec = format!("+refs/pull/{0}/head:refs/remotes/{1}/pr/{0}", number, self.remote);
+        remote
+            .fetch(&[refspec.as_str()], None, None)
+            .git_context(format!("Failed to fetch refs/pull/{}/head", number))?;
 
-        tracing::info!("Successfully fetched changes from remote");
         Ok(())
     }
 
+    /// True if `sha` names a commit present in the local object database
+    pub fn commit_exists(&self, sha: &str) -> bool {
+        Oid::from_str(sha)
+            .and_then(|oid| self.repo.find_commit(oid))
+            .is_ok()
+    }
+
+    /// Parents and authorship of commit `sha`, which must exist locally
+    pub fn commit_header(&self, sha: &str) -> Result<CommitHeader> {
+        let commit = Oid::from_str(sha)
+            .and_then(|oid| self.repo.find_commit(oid))
+            .map_err(|_| GitError::CommitNotFound { sha: sha.to_string() })?;
+        let authored = commit.author().when().seconds();
+        Ok(CommitHeader {
+            parent_count: commit.parent_count(),
+            message: String::from_utf8_lossy(commit.message_bytes()).into_owned(),
+            authored_at: DateTime::from_timestamp(authored, 0).unwrap_or_default(),
+        })
+    }
+
+    /// Dry run of picking `shas` in order onto `branch` (local, else
+    /// `<remote>/<branch>`): true when every commit would apply without
+    /// conflicts. Merges happen in memory; the working tree, index and refs
+    /// are left alone.
+    pub fn predict_clean_pick(&self, branch: &str, shas: &[String]) -> Result<bool> {
+        let onto = self
+            .branch_tip(branch, false)
+            .or_else(|| self.branch_tip(branch, true))
+            .ok_or_else(|| GitError::BranchNotFound {
+                branch: branch.to_string(),
+            })?;
+        let mut ours = self.repo.find_commit(onto)?.tree()?;
+        for sha in shas {
+            let commit = Oid::from_str(sha)
+                .and_then(|oid| self.repo.find_commit(oid))
+                .map_err(|_| GitError::CommitNotFound { sha: sha.clone() })?;
+            let base = match commit.parent(0) {
+                Ok(parent) => parent.tree()?,
+                Err(_) => {
+                    let empty = self.repo.treebuilder(None)?.write()?;
+                    self.repo.find_tree(empty)?
+                }
+            };
+            let mut merged = self
+                .repo
+                .merge_trees(&base, &ours, &commit.tree()?, None)
+                .git_context(format!("Failed to merge {} in memory", sha))?;
+            if merged.has_conflicts() {
+                return Ok(false);
+            }
+            let tree = merged.write_tree_to(&self.repo)?;
+            ours = self.repo.find_tree(tree)?;
+        }
+        Ok(true)
+    }
+
+    /// What commit `sha` changes compared with its first parent
+    fn diff_with_parent(&self, sha: &str) -> Result<git2::Diff<'_>> {
+        let commit = Oid::from_str(sha)
+            .and_then(|oid| self.repo.find_commit(oid))
+            .map_err(|_| GitError::CommitNotFound { sha: sha.to_string() })?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        self.repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)
+            .git_context(format!("Failed to diff {}", sha))
+    }
+
+    /// Submodule pointers commit `sha` adds or moves, compared with its first parent
+    pub fn submodule_updates(&self, sha: &str) -> Result<Vec<SubmoduleUpdate>> {
+        let diff = self.diff_with_parent(sha)?;
+        Ok(diff
+            .deltas()
+            .filter(|delta| delta.new_file().mode() == git2::FileMode::Commit)
+            .filter_map(|delta| {
+                Some(SubmoduleUpdate {
+                    path: delta.new_file().path()?.to_string_lossy().into_owned(),
+                    sha: delta.new_file().id().to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// Paths commit `sha` adds, changes or removes compared with its first
+    /// parent; a rename gives both its old and new path
+    pub fn changed_paths(&self, sha: &str) -> Result<Vec<String>> {
+        let diff = self.diff_with_parent(sha)?;
+        let mut paths: Vec<String> = diff
+            .deltas()
+            .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+            .flatten()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+
+    /// Contents of the file at `path` on `branch`, or on the configured
+    /// remote's copy when there's no local branch. `None` when the branch
+    /// doesn't have the file or it isn't text.
+    pub fn file_on_branch(&self, branch: &str, path: &str) -> Result<Option<String>> {
+        let tree = self
+            .repo
+            .find_branch(branch, git2::BranchType::Local)
+            .or_else(|_| {
+                self.repo
+                    .find_branch(&format!("{}/{}", self.remote, branch), git2::BranchType::Remote)
+            })
+            .map_err(|_| GitError::BranchNotFound {
+                branch: branch.to_string(),
+            })?
+            .get()
+            .peel_to_tree()?;
+        let Ok(entry) = tree.get_path(Path::new(path)) else {
+            return Ok(None);
+        };
+        let Ok(blob) = self.repo.find_blob(entry.id()) else {
+            return Ok(None);
+        };
+        Ok(std::str::from_utf8(blob.content()).ok().map(str::to_string))
+    }
+
+    /// Best-effort check that `update.sha` exists for the submodule: true when
+    /// its local checkout has the commit or its remote lists it as a ref tip
+    /// (`git ls-remote`), which misses commits behind a tip. `None` when
+    /// there's nothing to ask: no `.gitmodules` entry, a relative URL, or
+    /// `ls-remote` failing.
+    pub fn submodule_commit_exists(&self, update: &SubmoduleUpdate) -> Option<bool> {
+        let submodule = self.repo.find_submodule(&update.path).ok()?;
+        let checked_out = submodule.open().ok().is_some_and(|repo| {
+            Oid::from_str(&update.sha)
+                .and_then(|oid| repo.find_commit(oid))
+                .is_ok()
+        });
+        if checked_out {
+            return Some(true);
+        }
+        let url = submodule.url()?;
+        if url.starts_with("./") || url.starts_with("../") {
+            return None;
+        }
+        match cli::CliEngine::ls_remote(&self.repo, url) {
+            Ok(refs) => Some(refs.lines().any(|line| line.starts_with(&update.sha))),
+            Err(e) => {
+                tracing::warn!(url = %url, path = %update.path, error = %e, "Could not list submodule remote");
+                None
+            }
+        }
+    }
+
+    /// The working tree; `None` for a bare repository
+    pub fn workdir(&self) -> Option<&Path> {
+        self.repo.workdir()
+    }
+
+    /// Path of the `.git` directory, for reopening the repository on another thread
+    pub fn path(&self) -> &Path {
+        self.repo.path()
+    }
+
+    /// Lists tag names, optionally filtered by a shell-style pattern (e.g. `v1.*`)
+    pub fn list_tags(&self, pattern: Option<&str>) -> Result<Vec<String>> {
+        let pattern = pattern.map(glob::Pattern::new).transpose()?;
+
+        let mut tags = Vec::new();
+        for reference in self
+            .repo
+            .references_glob("refs/tags/*")
+            .git_context("Failed to list tags")?
+        {
+            let reference = reference?;
+            let Some(name) = reference
+                .name()
+                .and_then(|n| n.strip_prefix("refs/tags/"))
+            else {
+                continue;
+            };
+            if pattern.as_ref().is_none_or(|p| p.matches(name)) {
+                tags.push(name.to_string());
+            }
+        }
+
+        tags.sort();
+        Ok(tags)
+    }
+
     /// Gets the list of commits between two references
     pub fn get_commits_between(&self, from: &str, to: &str) -> Result<Vec<git2::Commit<'_>>> {
         let from_oid = self.repo.revparse_single(from)?.id();
@@ -366,3 +1402,162 @@ impl GitOperations {
         Ok(commits)
     }
 }
+
+/// Message for a PR's picks squashed into one commit: `title`, a bullet per
+/// original subject, then git's `(cherry picked from commit ...)` trailer for
+/// each source sha. `sources` pairs each sha with its subject, in pick order.
+pub fn squash_message(title: &str, sources: &[(String, String)]) -> String {
+    let bullets: Vec<String> = sources.iter().map(|(_, subject)| format!("- {}", subject)).collect();
+    let trailers: Vec<String> = sources
+        .iter()
+        .map(|(sha, _)| format!("(cherry picked from commit {})", sha))
+        .collect();
+    format!("{}\n\n{}\n\n{}\n", title.trim(), bullets.join("\n"), trailers.join("\n"))
+}
+
+/// What a pick may commit: the paths its source commit changed. Anything
+/// else staged would ride along in the commit unnoticed.
+pub(crate) struct PickScope {
+    sha: String,
+    paths: BTreeSet<String>,
+    /// Commit a tree limited to `paths` rather than refusing stray changes
+    only_commit_paths: bool,
+}
+
+impl PickScope {
+    /// Scope of picking `commit`, which is applied against its `mainline`
+    /// parent, or its first
+    fn new(repo: &Repository, commit: &git2::Commit, mainline: Option<u32>, only_commit_paths: bool) -> Result<Self> {
+        let parent = mainline.map_or(0, |parent| parent as usize - 1);
+        let parent_tree = match commit.parent(parent) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let tree = commit.tree()?;
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .git_context(format!("Failed to diff commit {}", commit.id()))?;
+        Ok(Self {
+            sha: commit.id().to_string(),
+            paths: diff_paths(&diff),
+            only_commit_paths,
+        })
+    }
+
+    /// Paths staged in `index` relative to HEAD that the commit didn't change
+    fn stray_staged(&self, repo: &Repository, index: &git2::Index) -> Result<Vec<String>> {
+        let head = repo.head().and_then(|h| h.peel_to_tree()).ok();
+        let diff = repo
+            .diff_tree_to_index(head.as_ref(), Some(index), None)
+            .git_context("Failed to compare the index with HEAD")?;
+        Ok(diff_paths(&diff).difference(&self.paths).cloned().collect())
+    }
+
+    /// HEAD's tree with only the commit's paths taken from `index`
+    fn limited_tree(&self, repo: &Repository, index: &git2::Index) -> Result<Oid> {
+        let head = repo
+            .head()
+            .and_then(|h| h.peel_to_tree())
+            .git_context("Failed to resolve HEAD")?;
+        let mut limited = git2::Index::new()?;
+        limited.read_tree(&head)?;
+        for path in &self.paths {
+            let path = Path::new(path);
+            match index.get_path(path, 0) {
+                Some(entry) => limited.add(&entry)?,
+                None if limited.get_path(path, 0).is_some() => limited.remove_path(path)?,
+                None => {}
+            }
+        }
+        limited
+            .write_tree_to(repo)
+            .git_context("Failed to write the pick's tree")
+    }
+
+    fn refusal(&self, paths: Vec<String>) -> GitError {
+        GitError::StagedOutsidePick {
+            sha: crate::util::short_sha(&self.sha).to_string(),
+            paths,
+        }
+    }
+}
+
+/// Every path a diff touches, on either side
+fn diff_paths(diff: &git2::Diff) -> BTreeSet<String> {
+    diff.deltas()
+        .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+        .flatten()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect()
+}
+
+/// Paths with unresolved conflicts in the index. Re-reads the index from disk
+/// because the `git` binary may have changed it behind libgit2's back.
+fn conflicted_paths(repo: &Repository) -> Result<Vec<String>> {
+    let mut index = repo.index()?;
+    index.read(false).git_context("Failed to read index")?;
+    let mut conflicts = Vec::new();
+
+    if index.has_conflicts() {
+        let conflict_iter = index
+            .conflicts()
+            .git_context("Failed to get conflicts iterator")?;
+
+        for conflict in conflict_iter {
+            let conflict = conflict?;
+            if let Some(our) = conflict.our {
+                let path = String::from_utf8_lossy(&our.path).to_string();
+                conflicts.push(path);
+            }
+        }
+    }
+
+    Ok(conflicts)
+}
+
+/// Stage bits of an index entry's flags (`GIT_INDEX_ENTRY_STAGEMASK`)
+const INDEX_STAGE_MASK: u16 = 0x3000;
+
+/// Path of a conflict, from whichever side still has the file
+fn conflict_path(conflict: &IndexConflict) -> Option<String> {
+    [&conflict.our, &conflict.their, &conflict.ancestor]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+}
+
+/// Conflicted paths where either side's blob looks binary
+fn binary_conflicts(repo: &Repository) -> Result<Vec<String>> {
+    let mut index = repo.index()?;
+    index.read(false).git_context("Failed to read index")?;
+    let mut binary = Vec::new();
+    for conflict in index
+        .conflicts()
+        .git_context("Failed to get conflicts iterator")?
+    {
+        let conflict = conflict?;
+        let is_binary = [&conflict.our, &conflict.their]
+            .into_iter()
+            .flatten()
+            .any(|entry| repo.find_blob(entry.id).is_ok_and(|blob| blob.is_binary()));
+        if let Some(path) = conflict_path(&conflict).filter(|_| is_binary) {
+            binary.push(path);
+        }
+    }
+    Ok(binary)
+}
+
+fn signature(repo: &Repository) -> Result<Signature<'static>> {
+    // Try to get signature from git config
+    let config = repo.config().git_context("Failed to get git config")?;
+
+    let name = config
+        .get_string("user.name")
+        .git_context("Git user.name not configured")?;
+    let email = config
+        .get_string("user.email")
+        .git_context("Git user.email not configured")?;
+
+    Signature::now(&name, &email).git_context("Failed to create git signature")
+}
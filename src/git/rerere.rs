@@ -0,0 +1,178 @@
+//! Reuse of recorded conflict resolutions (`git rerere`). The git binary
+//! applies them on its own; for libgit2 picks the recorded pre/postimages in
+//! `.git/rr-cache` are matched against the conflicted files here.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+
+use git2::Repository;
+
+use super::Result;
+use crate::error::GitContext;
+
+/// Identifies a conflict by its normalized hunks, so the same conflict met
+/// again (e.g. backporting one fix to several branches) compares equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConflictSignature(u64);
+
+/// Rewrites conflicted file content the way git's rerere records a preimage:
+/// marker labels and diff3 base sections are dropped and the two sides of each
+/// hunk are put in byte order, so it doesn't matter which side was "ours".
+/// Returns `None` when the text has no complete conflict hunk.
+pub fn normalize_conflict(text: &str) -> Option<String> {
+    enum Part {
+        Outside,
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut part = Part::Outside;
+    let (mut ours, mut theirs) = (String::new(), String::new());
+    let mut hunks = 0;
+
+    for line in text.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\r', '\n']);
+        match part {
+            Part::Outside if is_marker(body, '<') => part = Part::Ours,
+            Part::Outside => out.push_str(line),
+            Part::Ours if is_marker(body, '|') => part = Part::Base,
+            Part::Ours | Part::Base if body == "=======" => part = Part::Theirs,
+            Part::Ours => ours.push_str(line),
+            Part::Base => {}
+            Part::Theirs if is_marker(body, '>') => {
+                let (first, second) = if ours <= theirs {
+                    (&ours, &theirs)
+                } else {
+                    (&theirs, &ours)
+                };
+                out.push_str("<<<<<<<\n");
+                out.push_str(first);
+                out.push_str("=======\n");
+                out.push_str(second);
+                out.push_str(">>>>>>>\n");
+                ours.clear();
+                theirs.clear();
+                hunks += 1;
+                part = Part::Outside;
+            }
+            Part::Theirs => theirs.push_str(line),
+        }
+    }
+
+    match part {
+        Part::Outside if hunks > 0 => Some(out),
+        _ => None,
+    }
+}
+
+/// Signature over the normalized conflicts in `files` (`(path, content)`).
+/// `None` when none of them contain conflict markers.
+pub fn conflict_signature(files: &[(String, String)]) -> Option<ConflictSignature> {
+    let mut normalized: Vec<(&str, String)> = files
+        .iter()
+        .filter_map(|(path, text)| Some((path.as_str(), normalize_conflict(text)?)))
+        .collect();
+    if normalized.is_empty() {
+        return None;
+    }
+    normalized.sort();
+
+    let mut hasher = DefaultHasher::new();
+    normalized.hash(&mut hasher);
+    Some(ConflictSignature(hasher.finish()))
+}
+
+fn is_marker(line: &str, c: char) -> bool {
+    let mut chars = line.chars();
+    chars.by_ref().take(7).filter(|&m| m == c).count() == 7
+        && matches!(chars.next(), None | Some(' '))
+}
+
+/// Whether git would record and reuse resolutions: `rerere.enabled`, or, when
+/// unset, the presence of `.git/rr-cache`
+pub(crate) fn enabled(repo: &Repository) -> bool {
+    repo.config()
+        .and_then(|c| c.get_bool("rerere.enabled"))
+        .unwrap_or_else(|_| repo.path().join("rr-cache").is_dir())
+}
+
+/// Writes the recorded resolution over each conflicted file whose normalized
+/// content matches a preimage in `.git/rr-cache`. Returns the paths resolved.
+pub(crate) fn apply_recorded(repo: &Repository, conflicts: &[String]) -> Result<Vec<String>> {
+    let cache = repo.path().join("rr-cache");
+    let Some(workdir) = repo.workdir() else {
+        return Ok(Vec::new());
+    };
+    let Ok(entries) = std::fs::read_dir(&cache) else {
+        return Ok(Vec::new());
+    };
+    let recorded: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+
+    let mut resolved = Vec::new();
+    for path in conflicts {
+        let Some(preimage) = std::fs::read_to_string(workdir.join(path))
+            .ok()
+            .and_then(|text| normalize_conflict(&text))
+        else {
+            continue;
+        };
+        if let Some(postimage) = recorded.iter().find_map(|dir| postimage_for(dir, &preimage)) {
+            std::fs::write(workdir.join(path), postimage)
+                .map_err(|e| git2::Error::from_str(&e.to_string()))
+                .git_context(format!("Failed to write recorded resolution of {}", path))?;
+            tracing::info!(path = %path, "Applied recorded rerere resolution");
+            resolved.push(path.clone());
+        }
+    }
+    Ok(resolved)
+}
+
+fn postimage_for(dir: &Path, preimage: &str) -> Option<String> {
+    let recorded = std::fs::read_to_string(dir.join("preimage")).ok()?;
+    if normalize_conflict(&recorded).as_deref() != Some(preimage) {
+        return None;
+    }
+    std::fs::read_to_string(dir.join("postimage")).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OURS_FIRST: &str = "a\n<<<<<<< HEAD\nmaster\n=======\nfeature\n>>>>>>> 1234abc (Fix)\nz\n";
+    const THEIRS_FIRST: &str = "a\n<<<<<<< ours\nfeature\n||||||| base\nbase\n=======\nmaster\n>>>>>>> theirs\nz\n";
+
+    #[test]
+    fn normalization_drops_labels_and_base_and_orders_sides() {
+        let expected = "a\n<<<<<<<\nfeature\n=======\nmaster\n>>>>>>>\nz\n";
+        assert_eq!(normalize_conflict(OURS_FIRST).as_deref(), Some(expected));
+        assert_eq!(normalize_conflict(THEIRS_FIRST).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn text_without_complete_hunks_is_not_a_conflict() {
+        assert_eq!(normalize_conflict("plain\ntext\n"), None);
+        assert_eq!(normalize_conflict("<<<<<<< HEAD\nleft\n=======\nright\n"), None);
+        // Eight '<' is content, not a marker
+        assert_eq!(normalize_conflict("<<<<<<<< not a marker\n"), None);
+    }
+
+    #[test]
+    fn signature_matches_the_same_conflict_from_either_side() {
+        let one = conflict_signature(&[("a.txt".into(), OURS_FIRST.into())]);
+        let two = conflict_signature(&[("a.txt".into(), THEIRS_FIRST.into())]);
+        assert!(one.is_some());
+        assert_eq!(one, two);
+
+        let elsewhere = conflict_signature(&[("b.txt".into(), OURS_FIRST.into())]);
+        assert_ne!(one, elsewhere);
+        let other = conflict_signature(&[(
+            "a.txt".into(),
+            OURS_FIRST.replace("feature", "hotfix"),
+        )]);
+        assert_ne!(one, other);
+        assert_eq!(conflict_signature(&[("a.txt".into(), "clean\n".into())]), None);
+    }
+}
@@ -0,0 +1,147 @@
+//! Picks onto one of several target branches in a linked worktree of its
+//! own, so [`crate::workflow::pick_targets`] can run them side by side
+//! without sharing an index or working tree.
+
+use std::path::{Path, PathBuf};
+
+use super::{GitOperations, Result};
+use crate::config::{GitEngine, GitHubConfig};
+use crate::github::PickedCommit;
+use crate::workflow::TargetPick;
+
+/// What became of picking onto one target in its worktree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorktreePick {
+    /// Every commit applied; the new commits, in pick order
+    Picked(Vec<PickedCommit>),
+    /// Commit `sha` conflicted in `files`. The pick was undone, leaving the
+    /// target branch as it was.
+    Conflicted { sha: String, files: Vec<String> },
+    Failed(String),
+}
+
+/// Applies the same commits onto each target it's given, in a worktree
+/// under `root` (an existing directory) that is removed again once the pick is done
+pub struct WorktreePicker {
+    /// The repository's `.git` directory; each pick opens its own handle
+    git_dir: PathBuf,
+    root: PathBuf,
+    engine: GitEngine,
+    remote: String,
+    shas: Vec<String>,
+    /// Squash the picks into one commit with this title
+    squash_title: Option<String>,
+}
+
+impl WorktreePicker {
+    /// Picks `shas` with the repository at `git_dir` and the engine and
+    /// remote `github` configures, keeping worktrees under `root`
+    pub fn new(git_dir: &Path, root: PathBuf, github: &GitHubConfig, shas: Vec<String>) -> Self {
+        Self {
+            git_dir: git_dir.to_path_buf(),
+            root,
+            engine: github.git_engine,
+            remote: github.remote_name.clone(),
+            shas,
+            squash_title: None,
+        }
+    }
+
+    /// Squashes each target's picks into one commit titled `title`
+    pub fn squashed(mut self, title: &str) -> Self {
+        self.squash_title = Some(title.to_string());
+        self
+    }
+
+    fn open(&self, path: &Path) -> Result<GitOperations> {
+        Ok(GitOperations::new(path)?.with_engine(self.engine).with_remote(&self.remote))
+    }
+
+    fn try_pick(&self, target: &str) -> Result<WorktreePick> {
+        let repo = self.open(&self.git_dir)?;
+        let name = worktree_name(target);
+        // Left behind by an earlier pick of this process that didn't get to clean up
+        repo.remove_worktree(&name)?;
+        let path = self.root.join(&name);
+        repo.add_worktree(&name, &path, target)?;
+        let picked = self.open(&path).and_then(|tree| self.pick_in(&tree));
+        repo.remove_worktree(&name)?;
+        picked
+    }
+
+    /// Picks into `tree`, taking every commit back off the target again
+    /// unless all of them applied
+    fn pick_in(&self, tree: &GitOperations) -> Result<WorktreePick> {
+        let base = tree.head_sha()?;
+        let mut commits = Vec::new();
+        let picked = self.pick_all(tree, &base, &mut commits);
+        if !matches!(picked, Ok(WorktreePick::Picked(_))) {
+            tree.abort_cherry_pick()?;
+            for _ in &commits {
+                // A failed squash may already have moved the branch back
+                if tree.head_sha()? == base {
+                    break;
+                }
+                tree.undo_last_commit()?;
+            }
+        }
+        picked
+    }
+
+    /// Applies each commit onto `base`, recording the ones that landed in `commits`
+    fn pick_all(&self, tree: &GitOperations, base: &str, commits: &mut Vec<PickedCommit>) -> Result<WorktreePick> {
+        for sha in &self.shas {
+            let result = tree.cherry_pick(sha)?;
+            if !result.success {
+                return Ok(WorktreePick::Conflicted {
+                    sha: sha.clone(),
+                    files: result.conflicts,
+                });
+            }
+            if let Some(new_sha) = result.commit_sha {
+                commits.push(PickedCommit {
+                    source_sha: sha.clone(),
+                    new_sha,
+                });
+            }
+        }
+        if let Some(title) = self.squash_title.as_deref().filter(|_| self.shas.len() > 1) {
+            let squashed = tree.squash_picks(base, title, &self.shas)?;
+            for picked in commits.iter_mut() {
+                picked.new_sha = squashed.clone();
+            }
+        }
+        Ok(WorktreePick::Picked(std::mem::take(commits)))
+    }
+}
+
+impl TargetPick for WorktreePicker {
+    type Outcome = WorktreePick;
+
+    fn pick(&self, target: &str) -> WorktreePick {
+        self.try_pick(target).unwrap_or_else(|e| {
+            tracing::warn!(branch = %target, "Pick in a worktree failed: {}", e);
+            WorktreePick::Failed(e.to_string())
+        })
+    }
+
+    /// Each pick removes its worktree as soon as it's done, so this only
+    /// catches one whose removal failed
+    fn clean_up(&self, target: &str) {
+        let removed = self.open(&self.git_dir).and_then(|repo| repo.remove_worktree(&worktree_name(target)));
+        if let Err(e) = removed {
+            tracing::warn!(branch = %target, "Could not remove the worktree: {}", e);
+        }
+    }
+}
+
+/// The worktree `target` is picked in: its name with anything but letters,
+/// digits, `.`, `-` and `_` replaced, then this process's id, as another
+/// run may pick onto the same target at the same time
+fn worktree_name(target: &str) -> String {
+    let name: String = target
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '-' })
+        .collect();
+    format!("gh_cherry-{}-{}", name, std::process::id())
+}
@@ -0,0 +1,163 @@
+//! Spotting follow-up PRs ("fix forgotten file, follow-up to #123") so they
+//! can be listed and picked next to the PR they follow up.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use super::PrInfo;
+
+/// `#123` not preceded by a word character or `/`, so `owner/repo#123` and
+/// `abc#1` don't count
+fn reference_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"(?:^|[^\w/])#(\d+)\b").expect("valid reference pattern"))
+}
+
+/// PR numbers `title` and `body` reference, in order of first mention,
+/// leaving out `own` number
+pub fn pr_references(own: u64, title: &str, body: &str) -> Vec<u64> {
+    let mut references = Vec::new();
+    for text in [title, body] {
+        for captures in reference_pattern().captures_iter(text) {
+            if let Ok(number) = captures[1].parse::<u64>() {
+                if number != own && !references.contains(&number) {
+                    references.push(number);
+                }
+            }
+        }
+    }
+    references
+}
+
+/// Narrows each PR's `related_to` to PRs in `prs`, then moves follow-ups to
+/// directly after the PR they follow up. A PR referencing several listed PRs
+/// goes after the first one it mentions.
+pub fn link_follow_ups(prs: Vec<PrInfo>) -> Vec<PrInfo> {
+    let listed: HashSet<u64> = prs.iter().map(|pr| pr.number).collect();
+    let prs: Vec<PrInfo> = prs
+        .into_iter()
+        .map(|mut pr| {
+            pr.related_to.retain(|n| listed.contains(n));
+            pr
+        })
+        .collect();
+    group_follow_ups(prs)
+}
+
+/// Orders `prs` so each follow-up comes right after its parent (its first
+/// `related_to` entry), keeping the original order otherwise. PRs caught in a
+/// reference cycle come after the rest, in their original order.
+pub fn group_follow_ups(prs: Vec<PrInfo>) -> Vec<PrInfo> {
+    let position: HashMap<u64, usize> = prs.iter().enumerate().map(|(i, pr)| (pr.number, i)).collect();
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+    for (i, pr) in prs.iter().enumerate() {
+        match pr.related_to.first().and_then(|n| position.get(n)) {
+            Some(&parent) => children.entry(parent).or_default().push(i),
+            None => roots.push(i),
+        }
+    }
+
+    let mut placed = vec![false; prs.len()];
+    let mut order = Vec::with_capacity(prs.len());
+    // A cycle has no root, so its members are placed from the second pass
+    for start in roots.into_iter().chain(0..prs.len()) {
+        let mut stack = vec![start];
+        while let Some(i) = stack.pop() {
+            if placed[i] {
+                continue;
+            }
+            placed[i] = true;
+            order.push(i);
+            if let Some(kids) = children.get(&i) {
+                stack.extend(kids.iter().rev());
+            }
+        }
+    }
+
+    let mut slots: Vec<Option<PrInfo>> = prs.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|i| slots[i].take()).collect()
+}
+
+/// Numbers of the PRs in `prs` that follow up PR `parent`, directly or by
+/// following up one of its follow-ups, in list order
+pub fn follow_ups_of(prs: &[PrInfo], parent: u64) -> Vec<u64> {
+    let mut family = HashSet::from([parent]);
+    loop {
+        let before = family.len();
+        for pr in prs {
+            if pr.related_to.first().is_some_and(|p| family.contains(p)) {
+                family.insert(pr.number);
+            }
+        }
+        if family.len() == before {
+            break;
+        }
+    }
+    prs.iter()
+        .map(|pr| pr.number)
+        .filter(|n| *n != parent && family.contains(n))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::PrState;
+
+    fn pr(number: u64, related_to: &[u64]) -> PrInfo {
+        PrInfo {
+            number,
+            title: format!("Change {}", number),
+            base_ref: "main".into(),
+            head_ref: "feature".into(),
+            state: PrState::Open,
+            related_to: related_to.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn numbers(prs: &[PrInfo]) -> Vec<u64> {
+        prs.iter().map(|pr| pr.number).collect()
+    }
+
+    #[test]
+    fn references_skip_other_repositories_and_the_pr_itself() {
+        assert_eq!(
+            pr_references(
+                130,
+                "Fix forgotten file (#123 follow-up)",
+                "Fixes #123 and #124, see acme/other#5 and #130.\nAlso #124 again"
+            ),
+            [123, 124]
+        );
+        assert!(pr_references(1, "Bump version to v1#2", "").is_empty());
+    }
+
+    #[test]
+    fn follow_ups_move_directly_after_their_parent() {
+        // Newest first, as listed: the follow-ups were opened after their parents
+        let listed = vec![
+            pr(140, &[999, 120]),
+            pr(135, &[]),
+            pr(131, &[120]),
+            pr(130, &[131]),
+            pr(120, &[]),
+        ];
+        let grouped = link_follow_ups(listed);
+        assert_eq!(numbers(&grouped), [135, 120, 140, 131, 130]);
+        // The reference to a PR outside the listing is dropped
+        assert_eq!(grouped[2].related_to, [120]);
+        assert_eq!(follow_ups_of(&grouped, 120), [140, 131, 130]);
+        assert_eq!(follow_ups_of(&grouped, 131), [130]);
+        assert_eq!(follow_ups_of(&grouped, 135), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn reference_cycles_still_list_every_pr() {
+        let grouped = group_follow_ups(vec![pr(1, &[2]), pr(3, &[]), pr(2, &[1])]);
+        assert_eq!(numbers(&grouped), [3, 1, 2]);
+    }
+}
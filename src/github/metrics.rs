@@ -0,0 +1,144 @@
+//! Counts of the GitHub API requests a [`super::GitHubClient`] made, by kind,
+//! so the chattiest parts of a session show up in its summary.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+/// What a request was for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiCategory {
+    /// A page of the PR listing
+    PullsList,
+    /// One PR, for its details
+    PullGet,
+    /// A page of a PR's commits
+    PullCommits,
+    PullCreate,
+    /// The issue side of a PR, read for its labels
+    IssueGet,
+    LabelUpdate,
+    /// Listing, creating or editing comments
+    Comment,
+    ReviewRequest,
+    Search,
+    /// PRs associated with a commit
+    Commits,
+    /// Getting, listing or forking repositories
+    Repository,
+    /// Files read from the default branch
+    Contents,
+    Organization,
+    User,
+    RateLimit,
+}
+
+impl fmt::Display for ApiCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::PullsList => "pulls list",
+            Self::PullGet => "pull get",
+            Self::PullCommits => "pull commits",
+            Self::PullCreate => "pull create",
+            Self::IssueGet => "issue get",
+            Self::LabelUpdate => "label update",
+            Self::Comment => "comment",
+            Self::ReviewRequest => "review request",
+            Self::Search => "search",
+            Self::Commits => "commits",
+            Self::Repository => "repository",
+            Self::Contents => "contents",
+            Self::Organization => "organization",
+            Self::User => "user",
+            Self::RateLimit => "rate limit",
+        })
+    }
+}
+
+/// Requests made so far, per category. Categories without requests are left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApiUsage(BTreeMap<ApiCategory, u64>);
+
+impl ApiUsage {
+    pub fn get(&self, category: ApiCategory) -> u64 {
+        self.0.get(&category).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.0.values().sum()
+    }
+
+    /// Requests made after `earlier` was taken from the same counters
+    pub fn since(&self, earlier: &ApiUsage) -> ApiUsage {
+        ApiUsage(
+            self.0
+                .iter()
+                .map(|(&category, &count)| (category, count.saturating_sub(earlier.get(category))))
+                .filter(|&(_, count)| count > 0)
+                .collect(),
+        )
+    }
+}
+
+/// `5 requests (pulls list 1, issue get 2, pull commits 2)`
+impl fmt::Display for ApiUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total();
+        write!(f, "{} request{}", total, if total == 1 { "" } else { "s" })?;
+        if total > 0 {
+            let parts: Vec<String> = self.0.iter().map(|(category, count)| format!("{} {}", category, count)).collect();
+            write!(f, " ({})", parts.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Shared tally behind [`ApiUsage`]; clones of a client count into the same one
+#[derive(Debug, Clone, Default)]
+pub struct ApiCounters(Arc<Mutex<ApiUsage>>);
+
+impl ApiCounters {
+    pub fn record(&self, category: ApiCategory) {
+        self.record_n(category, 1);
+    }
+
+    pub fn record_n(&self, category: ApiCategory, count: u64) {
+        if count == 0 {
+            return;
+        }
+        let mut usage = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        *usage.0.entry(category).or_insert(0) += count;
+    }
+
+    pub fn snapshot(&self) -> ApiUsage {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage_reads_as_a_total_then_each_category() {
+        let counters = ApiCounters::default();
+        assert_eq!(counters.snapshot().to_string(), "0 requests");
+
+        counters.record(ApiCategory::PullsList);
+        let after_first = counters.snapshot();
+        counters.record_n(ApiCategory::IssueGet, 2);
+        counters.record(ApiCategory::PullsList);
+        let usage = counters.snapshot();
+
+        assert_eq!(usage.to_string(), "4 requests (pulls list 2, issue get 2)");
+        assert_eq!(
+            usage.since(&after_first).to_string(),
+            "3 requests (pulls list 1, issue get 2)"
+        );
+        assert_eq!(usage.since(&usage), ApiUsage::default());
+    }
+}
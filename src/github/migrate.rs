@@ -0,0 +1,347 @@
+//! `--migrate-labels`: moves every open PR from one label to another, e.g.
+//! when the pending tag is renamed org-wide. The new label is added before the
+//! old one is removed, so a PR never ends up with neither, and labels changed
+//! on it since the search are left alone. Running it again after a failure
+//! only finds the PRs still carrying the old label.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use super::{GitHubError, GitHubOps, RepoSlug};
+
+/// An open PR found by its label
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelledPr {
+    pub repo: RepoSlug,
+    pub number: u64,
+    pub title: String,
+    pub labels: Vec<String>,
+}
+
+/// How fast label changes are sent
+#[derive(Debug, Clone)]
+pub struct Pacing {
+    /// PRs changed between pauses
+    pub batch_size: usize,
+    /// Pause after each batch, to stay under GitHub's secondary rate limits
+    pub between_batches: Duration,
+    /// Longest wait for the rate limit to reset before stopping
+    pub max_rate_limit_wait: Duration,
+}
+
+impl Default for Pacing {
+    fn default() -> Self {
+        Self {
+            batch_size: 20,
+            between_batches: Duration::from_secs(1),
+            max_rate_limit_wait: Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+/// How a migration went
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    pub migrated: usize,
+    /// `owner/repo#number` and why its labels couldn't be changed
+    pub failed: Vec<(String, GitHubError)>,
+    /// PRs not tried because the rate limit wouldn't reset in time
+    pub not_attempted: usize,
+}
+
+/// Like GitHub, labels are compared ignoring case
+fn same_label(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// Adds `to` to `pr`, then takes `from` off it, spelled as the PR has it.
+/// Both are safe to send again: adding a label it has and removing one it
+/// lacks change nothing.
+async fn move_label<O: GitHubOps + ?Sized>(ops: &O, pr: &LabelledPr, from: &str, to: &str) -> Result<(), GitHubError> {
+    ops.add_pr_labels(&pr.repo, pr.number, &[to.to_string()]).await?;
+    let old = pr.labels.iter().find(|label| same_label(label, from)).map_or(from, String::as_str);
+    if same_label(old, to) {
+        return Ok(());
+    }
+    ops.remove_pr_label(&pr.repo, pr.number, old).await
+}
+
+/// How long to wait for a rate limit resetting at `reset`; `None` when it's
+/// unknown or further off than `max`
+fn rate_limit_wait(reset: Option<DateTime<Utc>>, now: DateTime<Utc>, max: Duration) -> Option<Duration> {
+    let wait = (reset? - now).to_std().unwrap_or(Duration::ZERO);
+    (wait <= max).then_some(wait)
+}
+
+/// Moves each of `prs` from `from` to `to`, `pacing.batch_size` at a time.
+/// A rate-limited change is retried once the limit resets; when it won't
+/// reset soon enough the rest are left for the next run. `progress` is told
+/// about each PR as it's done, with the error if it failed.
+pub async fn migrate_labels<O: GitHubOps + ?Sized>(
+    ops: &O,
+    prs: &[LabelledPr],
+    from: &str,
+    to: &str,
+    pacing: &Pacing,
+    mut progress: impl FnMut(usize, &LabelledPr, Option<&GitHubError>),
+) -> MigrationReport {
+    let mut report = MigrationReport::default();
+    for (index, pr) in prs.iter().enumerate() {
+        if index > 0 && index % pacing.batch_size.max(1) == 0 {
+            tokio::time::sleep(pacing.between_batches).await;
+        }
+
+        let mut result = move_label(ops, pr, from, to).await;
+        if let Err(GitHubError::RateLimited { reset }) = &result {
+            match rate_limit_wait(*reset, Utc::now(), pacing.max_rate_limit_wait) {
+                Some(wait) => {
+                    tracing::info!("Rate limited; waiting {}s before retrying", wait.as_secs());
+                    tokio::time::sleep(wait).await;
+                    result = move_label(ops, pr, from, to).await;
+                }
+                None => {
+                    report.not_attempted = prs.len() - index;
+                    progress(index + 1, pr, result.as_ref().err());
+                    break;
+                }
+            }
+        }
+
+        match result {
+            Ok(()) => {
+                report.migrated += 1;
+                progress(index + 1, pr, None);
+            }
+            Err(e) => {
+                progress(index + 1, pr, Some(&e));
+                report.failed.push((format!("{}#{}", pr.repo, pr.number), e));
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::github::{CommitPickOutcome, IssueComment, PrOverview, Result, ReviewRequest};
+
+    /// Labels per PR number, with failures queued up per PR
+    #[derive(Default)]
+    struct LabelStore {
+        labels: Mutex<BTreeMap<u64, Vec<String>>>,
+        failures: Mutex<HashMap<u64, Vec<GitHubError>>>,
+        calls: Mutex<usize>,
+    }
+
+    impl LabelStore {
+        fn with(prs: &[(u64, &[&str])]) -> Self {
+            let store = Self::default();
+            for (number, labels) in prs {
+                store
+                    .labels
+                    .lock()
+                    .unwrap()
+                    .insert(*number, labels.iter().map(|l| l.to_string()).collect());
+            }
+            store
+        }
+
+        fn fail(&self, number: u64, error: GitHubError) {
+            self.failures.lock().unwrap().entry(number).or_default().push(error);
+        }
+
+        /// Counts a request about PR `number`, failing it when a failure is queued
+        fn call(&self, number: u64) -> Result<()> {
+            *self.calls.lock().unwrap() += 1;
+            match self.failures.lock().unwrap().get_mut(&number).and_then(Vec::pop) {
+                Some(error) => Err(error),
+                None => Ok(()),
+            }
+        }
+
+        fn labels_of(&self, number: u64) -> Vec<String> {
+            self.labels.lock().unwrap()[&number].clone()
+        }
+    }
+
+    fn unused<T>() -> Result<T> {
+        Err(GitHubError::InvalidInput {
+            reason: "not used by the migration".into(),
+        })
+    }
+
+    #[async_trait]
+    impl GitHubOps for LabelStore {
+        async fn update_pr_labels(&self, _: u64) -> Result<()> {
+            unused()
+        }
+        async fn add_cherry_pick_comment(
+            &self,
+            _: u64,
+            _: &str,
+            _: &[CommitPickOutcome],
+            _: bool,
+            _: Option<&str>,
+            _: Option<&str>,
+        ) -> Result<()> {
+            unused()
+        }
+        async fn create_cherry_pick_pr(&self, _: &str, _: &str, _: &str, _: &str) -> Result<u64> {
+            unused()
+        }
+        async fn request_reviewers(&self, _: u64, _: &ReviewRequest) -> Result<()> {
+            unused()
+        }
+        async fn list_pr_comments(&self, _: u64) -> Result<Vec<IssueComment>> {
+            unused()
+        }
+        async fn create_comment(&self, _: u64, _: &str) -> Result<()> {
+            unused()
+        }
+        async fn update_comment(&self, _: u64, _: &str) -> Result<()> {
+            unused()
+        }
+        async fn pr_for_commit(&self, _: &str) -> Result<Option<u64>> {
+            unused()
+        }
+        async fn pr_overview(&self, _: u64) -> Result<PrOverview> {
+            unused()
+        }
+        async fn search_labelled_prs(&self, owner: &str, label: &str) -> Result<Vec<LabelledPr>> {
+            Ok(self
+                .labels
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, labels)| labels.iter().any(|l| l.eq_ignore_ascii_case(label)))
+                .map(|(&number, labels)| LabelledPr {
+                    repo: RepoSlug::new(owner, "app"),
+                    number,
+                    title: format!("PR {}", number),
+                    labels: labels.clone(),
+                })
+                .collect())
+        }
+        async fn add_pr_labels(&self, _: &RepoSlug, pr_number: u64, labels: &[String]) -> Result<()> {
+            self.call(pr_number)?;
+            let mut store = self.labels.lock().unwrap();
+            let current = store.entry(pr_number).or_default();
+            for label in labels {
+                if !current.iter().any(|l| same_label(l, label)) {
+                    current.push(label.clone());
+                }
+            }
+            Ok(())
+        }
+        async fn remove_pr_label(&self, _: &RepoSlug, pr_number: u64, label: &str) -> Result<()> {
+            self.call(pr_number)?;
+            self.labels
+                .lock()
+                .unwrap()
+                .entry(pr_number)
+                .or_default()
+                .retain(|l| !same_label(l, label));
+            Ok(())
+        }
+    }
+
+    const OLD: &str = "pending cherrypick";
+    const NEW: &str = "backport/pending";
+
+    fn quick() -> Pacing {
+        Pacing {
+            batch_size: 2,
+            between_batches: Duration::ZERO,
+            max_rate_limit_wait: Duration::from_secs(60),
+        }
+    }
+
+    async fn run(store: &LabelStore) -> (MigrationReport, Vec<usize>) {
+        let prs = store.search_labelled_prs("acme", OLD).await.unwrap();
+        let mut seen = Vec::new();
+        let report = migrate_labels(store, &prs, OLD, NEW, &quick(), |done, _, _| seen.push(done)).await;
+        (report, seen)
+    }
+
+    #[tokio::test]
+    async fn the_new_label_replaces_the_old_one_once() {
+        let store = LabelStore::with(&[(1, &["bug", "Pending Cherrypick"]), (2, &[OLD, "Backport/Pending"])]);
+        run(&store).await;
+        assert_eq!(store.labels_of(1), ["bug", NEW]);
+        assert_eq!(store.labels_of(2), ["Backport/Pending"]);
+    }
+
+    #[tokio::test]
+    async fn labels_changed_since_the_search_are_kept() {
+        let store = LabelStore::with(&[(1, &[OLD, "bug"])]);
+        let prs = store.search_labelled_prs("acme", OLD).await.unwrap();
+        {
+            let mut labels = store.labels.lock().unwrap();
+            let current = labels.get_mut(&1).unwrap();
+            current.retain(|l| l != "bug");
+            current.push("urgent".into());
+        }
+
+        let report = migrate_labels(&store, &prs, OLD, NEW, &quick(), |_, _, _| {}).await;
+        assert_eq!(report.migrated, 1);
+        assert_eq!(store.labels_of(1), ["urgent", NEW]);
+    }
+
+    #[tokio::test]
+    async fn a_rerun_after_a_failure_only_touches_what_is_left() {
+        let store = LabelStore::with(&[(1, &[OLD, "bug"]), (2, &[OLD]), (3, &[OLD, NEW]), (4, &["bug"])]);
+        store.fail(2, GitHubError::InvalidInput { reason: "boom".into() });
+
+        let (report, seen) = run(&store).await;
+        assert_eq!(report.migrated, 2);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "acme/app#2");
+        assert_eq!(seen, [1, 2, 3]);
+        assert_eq!(store.labels_of(1), ["bug", NEW]);
+        assert_eq!(store.labels_of(2), [OLD]);
+        assert_eq!(store.labels_of(3), [NEW]);
+
+        let (report, _) = run(&store).await;
+        assert_eq!((report.migrated, report.failed.len()), (1, 0));
+        assert_eq!(store.labels_of(2), [NEW]);
+        assert_eq!(store.labels_of(4), ["bug"]);
+        // An add and a remove per migrated PR, and the add that failed
+        assert_eq!(*store.calls.lock().unwrap(), 7);
+
+        // Nothing carries the old label any more
+        let (report, seen) = run(&store).await;
+        assert_eq!(report.migrated, 0);
+        assert!(seen.is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_rate_limit_that_resets_soon_is_waited_out() {
+        let store = LabelStore::with(&[(1, &[OLD]), (2, &[OLD])]);
+        store.fail(1, GitHubError::RateLimited { reset: Some(Utc::now()) });
+
+        let (report, _) = run(&store).await;
+        assert_eq!(report.migrated, 2);
+        assert!(report.failed.is_empty());
+        assert_eq!(*store.calls.lock().unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn a_rate_limit_that_resets_late_stops_the_run() {
+        let store = LabelStore::with(&[(1, &[OLD]), (2, &[OLD]), (3, &[OLD])]);
+        let reset = Utc::now() + chrono::Duration::hours(1);
+        store.fail(2, GitHubError::RateLimited { reset: Some(reset) });
+
+        let (report, seen) = run(&store).await;
+        assert_eq!(report.migrated, 1);
+        assert_eq!(report.not_attempted, 2);
+        assert_eq!(seen, [1, 2]);
+        assert_eq!(store.labels_of(3), [OLD]);
+    }
+}
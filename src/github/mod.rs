@@ -1,4 +1,7 @@
-use anyhow::{Context, Result};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 use octocrab::{Octocrab, Page};
 use regex::Regex;
@@ -7,9 +10,22 @@ use serde::{Deserialize, Serialize};
 
 use crate::auth::GitHubAuth;
 use crate::util::short_sha;
-use crate::config::Config;
+use crate::config::{Config, LabelFilterMode, RepoConfigFile, TagConfig};
+use crate::error::{ConfigError, GitHubError};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+mod follow_ups;
+mod metrics;
+mod migrate;
+mod simulated;
+
+pub use follow_ups::{follow_ups_of, link_follow_ups, pr_references};
+pub use metrics::{ApiCategory, ApiCounters, ApiUsage};
+pub use migrate::{migrate_labels, LabelledPr, Pacing};
+pub use simulated::SimulatedOps;
+
+type Result<T, E = GitHubError> = std::result::Result<T, E>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PrInfo {
     pub number: u64,
     pub title: String,
@@ -21,6 +37,48 @@ pub struct PrInfo {
     pub head_sha: String,
     pub base_ref: String,
     pub head_ref: String,
+    #[serde(default)]
+    pub assignees: Vec<String>,
+    #[serde(default)]
+    pub requested_reviewers: Vec<String>,
+    /// Merge or squash commit on the base branch; `None` unless the PR was merged
+    #[serde(default)]
+    pub merge_commit_sha: Option<String>,
+    #[serde(default)]
+    pub state: PrState,
+    /// PRs this one's title or body references (`#123`), first mention first.
+    /// A listing narrows these to the PRs it lists; the first is the PR this
+    /// one follows up.
+    #[serde(default)]
+    pub related_to: Vec<u64>,
+}
+
+/// Whether a PR is merged, still open, or closed without merging. Picking an
+/// unmerged PR takes its commits as they are now, which may not be what lands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrState {
+    Merged,
+    Open,
+    Closed,
+    /// Not recorded, as in PRs cached by older versions
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+impl PrState {
+    /// Merged whenever `merged_at` is set; GitHub reports merged PRs as closed
+    fn from_pull(pr: &octocrab::models::pulls::PullRequest) -> Self {
+        if pr.merged_at.is_some() {
+            return Self::Merged;
+        }
+        match pr.state {
+            Some(octocrab::models::IssueState::Open) => Self::Open,
+            Some(octocrab::models::IssueState::Closed) => Self::Closed,
+            _ => Self::Unknown,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +89,47 @@ pub struct CommitInfo {
     pub date: DateTime<Utc>,
 }
 
+/// A commit applied to the target branch, paired with the commit it was copied from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PickedCommit {
+    pub source_sha: String,
+    pub new_sha: String,
+}
+
+/// The parts of a PR a digest line shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrOverview {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub labels: Vec<String>,
+}
+
+/// What became of one of the PR's commits in a pick. A pick applies every
+/// commit it's given, so each one was picked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitOutcome {
+    PickedAs { new_sha: String },
+}
+
+/// A source commit and what became of it, for the pick comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitPickOutcome {
+    pub source_sha: String,
+    pub outcome: CommitOutcome,
+}
+
+impl From<&PickedCommit> for CommitPickOutcome {
+    fn from(commit: &PickedCommit) -> Self {
+        Self {
+            source_sha: commit.source_sha.clone(),
+            outcome: CommitOutcome::PickedAs {
+                new_sha: commit.new_sha.clone(),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrganizationInfo {
     pub login: String,
@@ -38,6 +137,16 @@ pub struct OrganizationInfo {
     pub description: String,
 }
 
+/// Extra facts about an organization, shown in the selector's detail pane
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationDetails {
+    pub public_repos: u32,
+    pub public_members: u32,
+    pub created_at: DateTime<Utc>,
+    /// Billing plan name; GitHub only reveals it to members
+    pub plan: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepositoryInfo {
     pub name: String,
@@ -50,6 +159,10 @@ pub struct RepositoryInfo {
     pub stargazers_count: u32,
     pub forks_count: u32,
     pub language: Option<String>,
+    /// `owner/name` of the upstream for forks. `GET /user/repos` leaves it out,
+    /// so a listed fork may need [`fork_parent`] to fill it in.
+    #[serde(default)]
+    pub parent_full_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,55 +172,415 @@ pub struct UserInfo {
     pub email: String,
 }
 
+/// The authenticated user's role flags on a repository, as in the `permissions`
+/// object of `GET /repos/{owner}/{repo}`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepoPermissions {
+    pub admin: bool,
+    pub maintain: bool,
+    pub push: bool,
+    pub triage: bool,
+    pub pull: bool,
+}
+
+impl From<octocrab::models::Permissions> for RepoPermissions {
+    fn from(p: octocrab::models::Permissions) -> Self {
+        Self {
+            admin: p.admin,
+            maintain: p.maintain,
+            push: p.push,
+            triage: p.triage,
+            pull: p.pull,
+        }
+    }
+}
+
+/// What the user may do on the repository. Everything is allowed until a
+/// probe says otherwise, so a failed probe never blocks work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub can_push: bool,
+    pub can_label: bool,
+    pub can_comment: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            can_push: true,
+            can_label: true,
+            can_comment: true,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Labels need triage, pushing needs write; anyone who can read can comment
+    pub fn from_permissions(p: &RepoPermissions) -> Self {
+        let write = p.push || p.maintain || p.admin;
+        Self {
+            can_push: write,
+            can_label: write || p.triage,
+            can_comment: write || p.triage || p.pull,
+        }
+    }
+}
+
+/// A repository as the API addresses it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RepoSlug {
+    pub owner: String,
+    pub name: String,
+}
+
+impl RepoSlug {
+    pub fn new(owner: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            owner: owner.into(),
+            name: name.into(),
+        }
+    }
+
+    /// `github.owner`/`github.repo`, where labels and comments are written
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(&config.github.owner, &config.github.repo)
+    }
+
+    /// The repository PRs are listed from: the configured source when it
+    /// differs from `github.owner`/`github.repo`, otherwise that repository
+    pub fn pr_source(config: &Config) -> Self {
+        config
+            .source_config()
+            .map_or_else(|| Self::from_config(config), |source| Self::from_config(&source))
+    }
+}
+
+impl std::fmt::Display for RepoSlug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.owner, self.name)
+    }
+}
+
+/// Which PRs a listing keeps: those into `base_branch` updated in the last
+/// `days_back` days, carrying the label criteria in `tags` and assigned to
+/// one of `assignees` (anyone when empty)
+#[derive(Debug, Clone)]
+pub struct PrFilter {
+    pub base_branch: String,
+    pub tags: TagConfig,
+    pub days_back: u32,
+    pub assignees: Vec<String>,
+    /// False to ignore the environment, pending and sprint labels
+    pub labels: bool,
+}
+
+impl PrFilter {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            base_branch: config.github.base_branch.clone(),
+            tags: config.tags.clone(),
+            days_back: config.ui.days_back,
+            assignees: config.ui.assignees.clone(),
+            labels: true,
+        }
+    }
+
+    /// The same filter without the label criteria, to check whether the base
+    /// branch has PRs at all
+    pub fn without_labels(&self) -> Self {
+        Self {
+            labels: false,
+            ..self.clone()
+        }
+    }
+}
+
+/// One authenticated connection to GitHub. Methods ending in `_in` act on the
+/// repository they're given; the rest are shorthands for the client's default
+/// repository and PR filter, both taken from the config it was created with.
+#[derive(Clone)]
 pub struct GitHubClient {
     octocrab: Octocrab,
-    config: Config,
+    repo: RepoSlug,
+    filter: PrFilter,
+    discovery_repo_limit: Option<usize>,
+    /// List PRs through the search API where the filter allows
+    use_search_api: bool,
+    /// Requests made through this client and its clones
+    requests: ApiCounters,
+    /// Times a rate-limited request is retried
+    max_retries: u32,
+    /// The wait before the next retry of a rate-limited request, `None` while none is waiting
+    rate_limit_waits: Arc<tokio::sync::watch::Sender<Option<Duration>>>,
 }
 
 impl GitHubClient {
+    /// Authenticates against github.com, or the Enterprise server named by
+    /// `github.api_base_url`
     pub async fn new(config: Config) -> Result<Self> {
-        let auth_method = GitHubAuth::authenticate().await?;
+        // A malformed URL is reported before any credential lookup
+        let api_url = config.github.api_url()?;
+        let host = api_url.as_ref().and_then(|url| url.host_str());
+        let auth_method = GitHubAuth::authenticate(host)
+            .await
+            .map_err(|e| GitHubError::Auth {
+                reason: format!("{:#}", e),
+            })?;
         let token = GitHubAuth::get_token(&auth_method);
 
-        let octocrab = Octocrab::builder()
+        let base_url = api_url.as_ref().map_or(GITHUB_API_URL, |url| url.as_str());
+        Self::new_with_base_url(config, base_url, token).await
+    }
+
+    /// Client for a custom API root (GitHub Enterprise, or a fake server in
+    /// tests) that uses `token` instead of the usual credential lookup
+    pub async fn new_with_base_url(config: Config, base_url: &str, token: &str) -> Result<Self> {
+        let octocrab = octocrab_builder()
+            .base_uri(base_url)
+            .api_context(format!("Invalid GitHub API URL '{}'", base_url))?
             .personal_token(token.to_string())
             .build()
-            .context("Failed to create GitHub client")?;
+            .api_context("Failed to create GitHub client")?;
+
+        Ok(Self::with_octocrab(octocrab, &config))
+    }
+
+    fn with_octocrab(octocrab: Octocrab, config: &Config) -> Self {
+        Self {
+            octocrab,
+            repo: RepoSlug::from_config(config),
+            filter: PrFilter::from_config(config),
+            discovery_repo_limit: config.ui.discovery_repo_limit,
+            use_search_api: config.github.use_search_api,
+            requests: ApiCounters::default(),
+            max_retries: config.network.max_retries,
+            rate_limit_waits: Arc::new(tokio::sync::watch::channel(None).0),
+        }
+    }
+
+    /// This connection aimed at `config`'s repository and filter, without
+    /// authenticating again; requests still count towards this client's usage
+    pub fn for_config(&self, config: &Config) -> Self {
+        Self {
+            requests: self.requests.clone(),
+            rate_limit_waits: self.rate_limit_waits.clone(),
+            ..Self::with_octocrab(self.octocrab.clone(), config)
+        }
+    }
+
+    /// Requests made so far, per category
+    pub fn api_usage(&self) -> ApiUsage {
+        self.requests.snapshot()
+    }
+
+    /// Follows the waits of rate-limited requests: `Some(wait)` before each
+    /// retry, back to `None` once it's sent
+    pub fn rate_limit_waits(&self) -> tokio::sync::watch::Receiver<Option<Duration>> {
+        self.rate_limit_waits.subscribe()
+    }
+
+    /// Sends `request` until it gets through, retrying up to `max_retries`
+    /// more times however it fails. A request that fails in transit is
+    /// retried after a short [`Backoff`]; a rate-limited one waits for the
+    /// limit to reset, and a reset further off than [`MAX_RATE_LIMIT_WAIT`]
+    /// fails the request with it straight away.
+    async fn with_retries<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.retrying(GitHubError::is_transient, request).await
+    }
+
+    /// [`Self::with_retries`] for a write that would be made twice if sent
+    /// again after GitHub got it: of the failures in transit, only a
+    /// connection that was never made is retried
+    async fn with_write_retries<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.retrying(GitHubError::is_connect_failure, request).await
+    }
+
+    /// The items of `first` and of every page after it. Each later page is
+    /// fetched with [`Self::with_retries`] and counted under `category`.
+    async fn with_later_pages<T>(&self, first: Page<T>, category: ApiCategory, context: &str) -> Result<Vec<T>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mut page = first;
+        let mut items = Vec::new();
+        loop {
+            items.append(&mut page.items);
+            let Some(next) = page.next.as_ref() else {
+                return Ok(items);
+            };
+            let next = self
+                .with_retries(|| async {
+                    self.requests.record(category);
+                    self.octocrab
+                        .get_page::<T>(&Some(next.clone()))
+                        .await
+                        .api_context(context)
+                })
+                .await?;
+            match next {
+                Some(next) => page = next,
+                None => return Ok(items),
+            }
+        }
+    }
+
+    /// Sends `request`, retrying the failures `retryable` accepts and rate
+    /// limits, with `max_retries` retries between them
+    async fn retrying<T, F, Fut>(&self, retryable: impl Fn(&GitHubError) -> bool, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let backoff = Backoff {
+            base: NETWORK_RETRY_BASE,
+        };
+        let mut attempt = 0;
+        loop {
+            match request().await {
+                Err(e) if attempt < self.max_retries && retryable(&e) => {
+                    let wait = backoff.wait(attempt, jitter());
+                    attempt += 1;
+                    tracing::warn!(
+                        attempt,
+                        max_retries = self.max_retries,
+                        wait_ms = wait.as_millis() as u64,
+                        error = %e,
+                        "Request failed in transit; retrying"
+                    );
+                    tokio::time::sleep(wait).await;
+                }
+                Err(GitHubError::RateLimited { reset }) => {
+                    let reset = match reset {
+                        Some(reset) => Some(reset),
+                        None => self.rate_limit_reset().await,
+                    };
+                    let wait = retry_wait(reset, Utc::now(), attempt).filter(|_| attempt < self.max_retries);
+                    let Some(wait) = wait else {
+                        return Err(GitHubError::RateLimited { reset });
+                    };
+                    attempt += 1;
+                    tracing::info!(
+                        attempt,
+                        max_retries = self.max_retries,
+                        wait_secs = wait.as_secs(),
+                        "Rate limited; retrying"
+                    );
+                    self.rate_limit_waits.send_replace(Some(wait));
+                    tokio::time::sleep(wait).await;
+                    self.rate_limit_waits.send_replace(None);
+                }
+                result => {
+                    if attempt > 0 {
+                        tracing::info!(attempts = attempt + 1, "Request finished after retrying");
+                    }
+                    return result;
+                }
+            }
+        }
+    }
 
-        Ok(Self { octocrab, config })
+    /// The filter [`Self::list_matching_prs`] applies
+    pub fn filter(&self) -> &PrFilter {
+        &self.filter
     }
 
     /// Lists PRs from the base branch that match the filtering criteria
     pub async fn list_matching_prs(&self) -> Result<Vec<PrInfo>> {
-        let since = Utc::now() - chrono::Duration::days(self.config.ui.days_back as i64);
+        self.list_matching_prs_in(&self.repo, &self.filter).await
+    }
+
+    /// Replaces the label criteria and date window used by later listings
+    pub fn set_criteria(&mut self, tags: TagConfig, days_back: u32) {
+        self.filter.tags = tags;
+        self.filter.days_back = days_back;
+    }
+
+    /// PRs in `repo` that `filter` keeps, most recently updated first
+    pub async fn list_matching_prs_in(&self, repo: &RepoSlug, filter: &PrFilter) -> Result<Vec<PrInfo>> {
+        Ok(self.listing_in(repo, filter).await?.prs)
+    }
+
+    /// [`Self::list_matching_prs_in`], along with the PRs it had to skip
+    pub async fn listing_in(&self, repo: &RepoSlug, filter: &PrFilter) -> Result<Listing> {
+        let result = self.fetch_matching_prs(repo, filter).await;
+        self.with_error_details(result, Some(repo)).await
+    }
+
+    /// Runs the listing, returning what matched before a failure as
+    /// [`GitHubError::PartialListing`] when anything did
+    async fn fetch_matching_prs(&self, repo: &RepoSlug, filter: &PrFilter) -> Result<Listing> {
+        let mut listing = Listing::default();
+        let since = Utc::now() - chrono::Duration::days(filter.days_back as i64);
+        let query = pr_search_query(repo, filter, since).filter(|_| self.use_search_api);
+        let result = match query {
+            Some(query) => self.search_matching_prs(repo, filter, &query, since, &mut listing).await,
+            None => self.collect_matching_prs(repo, filter, &mut listing).await,
+        };
+        listing.prs = link_follow_ups(std::mem::take(&mut listing.prs));
+        match result {
+            Ok(()) => {
+                tracing::info!(
+                    "Found {} matching PRs, skipped {}",
+                    listing.prs.len(),
+                    listing.warnings.len()
+                );
+                Ok(listing)
+            }
+            Err(e) if listing.prs.is_empty() => Err(e),
+            Err(e) => {
+                tracing::warn!("Listing failed after {} matching PRs: {}", listing.prs.len(), e);
+                Err(GitHubError::PartialListing {
+                    prs: listing.prs,
+                    source: Box::new(e),
+                })
+            }
+        }
+    }
+
+    async fn collect_matching_prs(&self, repo: &RepoSlug, filter: &PrFilter, listing: &mut Listing) -> Result<()> {
+        let since = Utc::now() - chrono::Duration::days(filter.days_back as i64);
 
         tracing::info!(
-            "Fetching PRs from {}/{} on branch {} since {}",
-            self.config.github.owner,
-            self.config.github.repo,
-            self.config.github.base_branch,
+            "Fetching PRs from {} on branch {} since {}",
+            repo,
+            filter.base_branch,
             since.format("%Y-%m-%d")
         );
 
         let mut page: Page<octocrab::models::pulls::PullRequest> = self
-            .octocrab
-            .pulls(&self.config.github.owner, &self.config.github.repo)
-            .list()
-            .state(octocrab::params::State::All)
-            .base(&self.config.github.base_branch)
-            .sort(octocrab::params::pulls::Sort::Updated)
-            .direction(octocrab::params::Direction::Descending)
-            .per_page(100)
-            .send()
-            .await
-            .context("Failed to fetch pull requests")?;
+            .with_retries(|| async {
+                self.requests.record(ApiCategory::PullsList);
+                self.octocrab
+                    .pulls(&repo.owner, &repo.name)
+                    .list()
+                    .state(octocrab::params::State::All)
+                    .base(&filter.base_branch)
+                    .sort(octocrab::params::pulls::Sort::Updated)
+                    .direction(octocrab::params::Direction::Descending)
+                    .per_page(100)
+                    .send()
+                    .await
+                    .api_context(format!("GET pulls page 1 for {}", repo))
+            })
+            .await?;
+        let mut page_no = 1;
 
-        let mut matching_prs = Vec::new();
         let sprint_regex =
-            Regex::new(&self.config.tags.sprint_pattern).context("Invalid sprint pattern regex")?;
+            Regex::new(&filter.tags.sprint_pattern)
+                .map_err(|e| ConfigError::invalid("tags.sprint_pattern", e))?;
 
         loop {
             let mut stop_due_to_date = false;
+            let mut candidates = Vec::new();
             for pr in &page {
                 // Filter by date
                 let pr_updated_at = pr.updated_at.unwrap_or(pr.created_at.unwrap_or(Utc::now()));
@@ -115,58 +588,144 @@ impl GitHubClient {
                     stop_due_to_date = true;
                     break;
                 }
-
-                // Get labels for the PR
-                let labels = self.get_pr_labels(pr.number).await?;
-
-                // Check if PR has the required tags
-                if crate::github::pr_matches_criteria(&self.config, &labels, &sprint_regex) {
-                    let commits = self.get_pr_commits(pr.number).await?;
-
-                    let pr_info = PrInfo {
-                        number: pr.number,
-                        title: pr.title.clone().unwrap_or_default(),
-                        author: pr.user.clone().map(|u| u.login).unwrap_or_default(),
-                        created_at: pr.created_at.unwrap_or(Utc::now()),
-                        updated_at: pr.updated_at.unwrap_or(pr.created_at.unwrap_or(Utc::now())),
-                        labels,
-                        commits,
-                        head_sha: pr.head.sha.clone(),
-                        base_ref: pr.base.ref_field.clone(),
-                        head_ref: pr.head.ref_field.clone(),
-                    };
-
-                    matching_prs.push(pr_info);
-                }
+                candidates.push(pr_info_from(pr, Vec::new(), Vec::new()));
             }
+            evaluate_prs(self, repo, filter, &sprint_regex, candidates, listing).await?;
 
             if stop_due_to_date {
                 break;
             }
 
             // Next page
-            if let Some(next_page) = self
-                .octocrab
-                .get_page::<octocrab::models::pulls::PullRequest>(&page.next)
-                .await?
-            {
+            page_no += 1;
+            let next_page = self
+                .with_retries(|| async {
+                    if page.next.is_some() {
+                        self.requests.record(ApiCategory::PullsList);
+                    }
+                    self.octocrab
+                        .get_page::<octocrab::models::pulls::PullRequest>(&page.next)
+                        .await
+                        .api_context(format!("GET pulls page {} for {}", page_no, repo))
+                })
+                .await?;
+            if let Some(next_page) = next_page {
                 page = next_page;
             } else {
                 break;
             }
         }
 
-        tracing::info!("Found {} matching PRs", matching_prs.len());
-        Ok(matching_prs)
+        Ok(())
+    }
+
+    /// [`Self::collect_matching_prs`] through the search API. GitHub narrows
+    /// the PRs down by `query`, and the labels come back with the hits, so
+    /// only the matches are fetched.
+    async fn search_matching_prs(
+        &self,
+        repo: &RepoSlug,
+        filter: &PrFilter,
+        query: &str,
+        since: DateTime<Utc>,
+        listing: &mut Listing,
+    ) -> Result<()> {
+        tracing::info!(query, "Searching PRs");
+
+        let sprint_regex =
+            Regex::new(&filter.tags.sprint_pattern)
+                .map_err(|e| ConfigError::invalid("tags.sprint_pattern", e))?;
+        let page = self
+            .with_retries(|| async {
+                self.requests.record(ApiCategory::Search);
+                self.octocrab
+                    .search()
+                    .issues_and_pull_requests(query)
+                    .sort("updated")
+                    .order("desc")
+                    .per_page(100)
+                    .send()
+                    .await
+                    .api_context(format!("Search PRs in {}", repo))
+            })
+            .await?;
+        if page.total_count.is_some_and(|total| total > 1000) {
+            tracing::warn!("Search matched more than 1,000 PRs in {}; only the first 1,000 are listed", repo);
+        }
+        let hits = self
+            .with_later_pages(page, ApiCategory::Search, &format!("Search PRs in {}", repo))
+            .await?;
+
+        for hit in hits {
+            let labels: Vec<String> = hit.labels.iter().map(|label| label.name.clone()).collect();
+            // Search ignores case and can't apply the sprint pattern, and dates only to the day
+            if hit.updated_at < since || !pr_matches_criteria(&filter.tags, &labels, &sprint_regex) {
+                continue;
+            }
+            match self.searched_pr(repo, filter, hit.number, labels).await {
+                Ok(Some(pr)) => listing.prs.push(pr),
+                Ok(None) => {}
+                Err(e) if e.is_item_specific() => {
+                    tracing::warn!("Skipping PR #{} in {}: {}", hit.number, repo, e);
+                    listing.warnings.push(e.to_string());
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// PR `pr_number`, found by search with `labels`, with its commits if
+    /// `filter`'s assignees keep it
+    async fn searched_pr(
+        &self,
+        repo: &RepoSlug,
+        filter: &PrFilter,
+        pr_number: u64,
+        labels: Vec<String>,
+    ) -> Result<Option<PrInfo>> {
+        let pull = self.get_pull_in(repo, pr_number).await?;
+        let mut pr = pr_info_from(&pull, labels, Vec::new());
+        if !pr_matches_assignees(&pr, &filter.assignees) {
+            return Ok(None);
+        }
+        pr.commits = self.get_pr_commits(repo, pr_number).await?;
+        Ok(Some(pr))
+    }
+
+    /// Fetches PR `pr_number` of `repo`. Unlike [`Self::list_matching_prs`]
+    /// no date, label or assignee filters apply; see [`criteria_mismatches`].
+    pub async fn get_pr_in(&self, repo: &RepoSlug, pr_number: u64) -> Result<PrInfo> {
+        let pr = self.get_pull_in(repo, pr_number).await?;
+        let labels = self.get_pr_labels(repo, pr_number).await?;
+        let commits = self.get_pr_commits(repo, pr_number).await?;
+
+        Ok(pr_info_from(&pr, labels, commits))
     }
 
-    async fn get_pr_labels(&self, pr_number: u64) -> Result<Vec<String>> {
+    async fn get_pull_in(&self, repo: &RepoSlug, pr_number: u64) -> Result<octocrab::models::pulls::PullRequest> {
+        self.with_retries(|| async {
+            self.requests.record(ApiCategory::PullGet);
+            self.octocrab
+                .pulls(&repo.owner, &repo.name)
+                .get(pr_number)
+                .await
+                .api_context(format!("PR #{}", pr_number))
+        })
+        .await
+    }
+
+    async fn get_pr_labels(&self, repo: &RepoSlug, pr_number: u64) -> Result<Vec<String>> {
         let labels = self
-            .octocrab
-            .issues(&self.config.github.owner, &self.config.github.repo)
-            .get(pr_number)
-            .await
-            .context("Failed to fetch PR labels")?
+            .with_retries(|| async {
+                self.requests.record(ApiCategory::IssueGet);
+                self.octocrab
+                    .issues(&repo.owner, &repo.name)
+                    .get(pr_number)
+                    .await
+                    .api_context(format!("GET labels of PR #{} in {}", pr_number, repo))
+            })
+            .await?
             .labels
             .into_iter()
             .map(|label| label.name)
@@ -175,51 +734,62 @@ impl GitHubClient {
         Ok(labels)
     }
 
-    async fn get_pr_commits(&self, pr_number: u64) -> Result<Vec<CommitInfo>> {
-        // Get the PR details first
-        let pr = self
-            .octocrab
-            .pulls(&self.config.github.owner, &self.config.github.repo)
-            .get(pr_number)
-            .await
-            .context("Failed to fetch PR details")?;
+    /// The commits of PR `pr_number`, in the order GitHub lists them: the
+    /// order they sit on the PR branch, oldest first, which is the order they
+    /// have to be picked in. Author dates can be out of that order after a
+    /// rebase, so they are not re-sorted.
+    async fn get_pr_commits(&self, repo: &RepoSlug, pr_number: u64) -> Result<Vec<CommitInfo>> {
+        let page: Page<octocrab::models::repos::RepoCommit> = self
+            .with_retries(|| async {
+                self.requests.record(ApiCategory::PullCommits);
+                self.octocrab
+                    .get(
+                        format!("/repos/{}/{}/pulls/{}/commits", repo.owner, repo.name, pr_number),
+                        Some(&[("per_page", 100)]),
+                    )
+                    .await
+                    .api_context(format!("GET commits of PR #{} in {}", pr_number, repo))
+            })
+            .await?;
+        let context = format!("GET commits of PR #{} in {}", pr_number, repo);
+        let commits = self.with_later_pages(page, ApiCategory::PullCommits, &context).await?;
 
-        // For now, we'll just use the head commit of the PR
-        // This is typically what you want to cherry-pick
-        let commit_info = CommitInfo {
-            sha: pr.head.sha.clone(),
-            message: pr.title.unwrap_or_else(|| format!("PR #{}", pr_number)),
-            author: pr.user.map(|u| u.login).unwrap_or_else(|| "Unknown".to_string()),
-            date: pr.created_at.unwrap_or(Utc::now()),
-        };
+        // GitHub stops listing at 250 commits
+        if commits.len() >= 250 {
+            tracing::warn!("PR #{} in {} lists {} commits; later ones may be missing", pr_number, repo, commits.len());
+        }
+        tracing::info!("PR #{} has {} commit(s)", pr_number, commits.len());
 
-        tracing::info!("Using head commit {} for PR #{}", pr.head.sha, pr_number);
-        Ok(vec![commit_info])
+        Ok(commits.into_iter().map(commit_info_from).collect())
     }
 
-    
-
     /// Updates a PR's labels after successful cherry-pick
     pub async fn update_pr_labels(&self, pr_number: u64) -> Result<()> {
-        tracing::info!("Updating labels for PR #{}", pr_number);
+        self.update_pr_labels_in(&self.repo, pr_number, &self.filter.tags)
+            .await
+    }
+
+    /// Swaps `tags.pending_tag` for `tags.completed_tag` on PR `pr_number` of `repo`
+    pub async fn update_pr_labels_in(&self, repo: &RepoSlug, pr_number: u64, tags: &TagConfig) -> Result<()> {
+        tracing::info!("Updating labels for PR #{} in {}", pr_number, repo);
 
         // Get current labels
-        let mut labels = self.get_pr_labels(pr_number).await?;
+        let labels = self.get_pr_labels(repo, pr_number).await?;
 
-        // Remove pending tag and add completed tag
-        labels.retain(|label| label != &self.config.tags.pending_tag);
-        if !labels.contains(&self.config.tags.completed_tag) {
-            labels.push(self.config.tags.completed_tag.clone());
-        }
+        let labels = swap_pick_labels(labels, tags);
 
         // Update the labels
-        self.octocrab
-            .issues(&self.config.github.owner, &self.config.github.repo)
-            .update(pr_number)
-            .labels(&labels)
-            .send()
-            .await
-            .context("Failed to update PR labels")?;
+        self.with_retries(|| async {
+            self.requests.record(ApiCategory::LabelUpdate);
+            self.octocrab
+                .issues(&repo.owner, &repo.name)
+                .update(pr_number)
+                .labels(&labels)
+                .send()
+                .await
+                .api_context("Failed to update PR labels")
+        })
+        .await?;
 
         tracing::info!("Successfully updated labels for PR #{}", pr_number);
         Ok(())
@@ -230,25 +800,193 @@ impl GitHubClient {
         &self,
         pr_number: u64,
         target_branch: &str,
-        commit_shas: &[String],
+        outcomes: &[CommitPickOutcome],
+        reordered: bool,
+        note: Option<&str>,
+        footer: Option<&str>,
     ) -> Result<()> {
-        let comment_body = {
-            let mut lines = Vec::with_capacity(commit_shas.len());
-            for sha in commit_shas {
-                lines.push(format!("- {}", short_sha(sha)));
-            }
-            format!(
-                "🍒 **Cherry-picked to `{}`**\n\nCommits:\n{}",
-                target_branch,
-                lines.join("\n")
-            )
-        };
+        let comment_body = cherry_pick_comment_body(target_branch, outcomes, reordered, note, footer);
+
+        self.with_write_retries(|| async {
+            self.requests.record(ApiCategory::Comment);
+            self.octocrab
+                .issues(&self.repo.owner, &self.repo.name)
+                .create_comment(pr_number, &comment_body)
+                .await
+                .api_context("Failed to add cherry-pick comment")
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// The PR commit `sha` was merged through, if GitHub links one
+    pub async fn pr_for_commit(&self, sha: &str) -> Result<Option<u64>> {
+        self.requests.record(ApiCategory::Commits);
+        let page = self
+            .octocrab
+            .commits(&self.repo.owner, &self.repo.name)
+            .associated_pull_requests(octocrab::commits::PullRequestTarget::Sha(sha.to_string()))
+            .send()
+            .await
+            .api_context(format!("PRs of commit {}", short_sha(sha)))?;
+        // Closed-unmerged PRs can contain the commit too; the merged one brought it in
+        Ok(page
+            .items
+            .iter()
+            .find(|pr| pr.merged_at.is_some())
+            .or(page.items.first())
+            .map(|pr| pr.number))
+    }
+
+    /// Title, author and labels of PR `pr_number`
+    pub async fn pr_overview(&self, pr_number: u64) -> Result<PrOverview> {
+        self.requests.record(ApiCategory::PullGet);
+        let pr = self
+            .octocrab
+            .pulls(&self.repo.owner, &self.repo.name)
+            .get(pr_number)
+            .await
+            .api_context(format!("PR #{}", pr_number))?;
+        Ok(PrOverview {
+            number: pr.number,
+            title: pr.title.unwrap_or_default(),
+            author: pr.user.map(|u| u.login).unwrap_or_default(),
+            labels: pr.labels.into_iter().flatten().map(|l| l.name).collect(),
+        })
+    }
+
+    /// Every comment on issue/PR `pr_number`, oldest first
+    pub async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<IssueComment>> {
+        self.list_pr_comments_in(&self.repo, pr_number).await
+    }
+
+    /// [`Self::list_pr_comments`] for issue/PR `pr_number` of `repo`
+    pub async fn list_pr_comments_in(&self, repo: &RepoSlug, pr_number: u64) -> Result<Vec<IssueComment>> {
+        let page = self
+            .with_retries(|| async {
+                self.requests.record(ApiCategory::Comment);
+                self.octocrab
+                    .issues(&repo.owner, &repo.name)
+                    .list_comments(pr_number)
+                    .per_page(100)
+                    .send()
+                    .await
+                    .api_context("Failed to list PR comments")
+            })
+            .await?;
+        let comments = self
+            .with_later_pages(page, ApiCategory::Comment, "Failed to list PR comments")
+            .await?;
 
+        Ok(comments
+            .into_iter()
+            .map(|c| IssueComment {
+                id: c.id.into_inner(),
+                body: c.body.unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    pub async fn create_comment(&self, pr_number: u64, body: &str) -> Result<()> {
+        self.create_comment_in(&self.repo, pr_number, body).await
+    }
+
+    pub async fn create_comment_in(&self, repo: &RepoSlug, pr_number: u64, body: &str) -> Result<()> {
+        self.requests.record(ApiCategory::Comment);
+        self.octocrab
+            .issues(&repo.owner, &repo.name)
+            .create_comment(pr_number, body)
+            .await
+            .api_context("Failed to add PR comment")?;
+        Ok(())
+    }
+
+    /// Replaces the body of an existing issue/PR comment
+    pub async fn update_comment(&self, comment_id: u64, body: &str) -> Result<()> {
+        self.update_comment_in(&self.repo, comment_id, body).await
+    }
+
+    pub async fn update_comment_in(&self, repo: &RepoSlug, comment_id: u64, body: &str) -> Result<()> {
+        self.requests.record(ApiCategory::Comment);
         self.octocrab
-            .issues(&self.config.github.owner, &self.config.github.repo)
-            .create_comment(pr_number, comment_body)
+            .issues(&repo.owner, &repo.name)
+            .update_comment(octocrab::models::CommentId(comment_id), body)
+            .await
+            .api_context("Failed to update PR comment")?;
+        Ok(())
+    }
+
+    /// Opens a PR from `head` into `base` and returns its number
+    pub async fn create_cherry_pick_pr(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<u64> {
+        self.create_cherry_pick_pr_in(&self.repo, head, base, title, body)
+            .await
+    }
+
+    pub async fn create_cherry_pick_pr_in(
+        &self,
+        repo: &RepoSlug,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<u64> {
+        tracing::info!("Creating PR {} -> {} in {}", head, base, repo);
+
+        self.requests.record(ApiCategory::PullCreate);
+        let pr = self
+            .octocrab
+            .pulls(&repo.owner, &repo.name)
+            .create(title, head, base)
+            .body(body)
+            .send()
+            .await
+            .api_context("Failed to create pull request")?;
+
+        tracing::info!("Created PR #{}", pr.number);
+        Ok(pr.number)
+    }
+
+    /// Requests reviews from users and/or teams on a PR
+    pub async fn request_reviewers(&self, pr_number: u64, request: &ReviewRequest) -> Result<()> {
+        self.request_reviewers_in(&self.repo, pr_number, request)
+            .await
+    }
+
+    pub async fn request_reviewers_in(
+        &self,
+        repo: &RepoSlug,
+        pr_number: u64,
+        request: &ReviewRequest,
+    ) -> Result<()> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            reviewers: &'a [String],
+            team_reviewers: &'a [String],
+        }
+
+        let route = format!(
+            "/repos/{}/{}/pulls/{}/requested_reviewers",
+            repo.owner, repo.name, pr_number
+        );
+        self.requests.record(ApiCategory::ReviewRequest);
+        let _: octocrab::models::pulls::PullRequest = self
+            .octocrab
+            .post(
+                route,
+                Some(&Body {
+                    reviewers: &request.reviewers,
+                    team_reviewers: &request.team_reviewers,
+                }),
+            )
             .await
-            .context("Failed to add cherry-pick comment")?;
+            .api_context("Failed to request reviewers")?;
 
         Ok(())
     }
@@ -257,6 +995,7 @@ impl GitHubClient {
     pub async fn list_user_organizations(&self) -> Result<Vec<OrganizationInfo>> {
         tracing::info!("Fetching user organizations");
 
+        self.requests.record(ApiCategory::Organization);
         let orgs = self
             .octocrab
             .current()
@@ -264,7 +1003,7 @@ impl GitHubClient {
             .per_page(100)
             .send()
             .await
-            .context("Failed to fetch user organizations")?;
+            .api_context("Failed to fetch user organizations")?;
 
         let mut org_infos = Vec::new();
         for org in orgs {
@@ -280,99 +1019,1308 @@ impl GitHubClient {
         Ok(org_infos)
     }
 
-    /// Fetches repositories accessible to the authenticated user
-    pub async fn list_user_repositories(&self) -> Result<Vec<RepositoryInfo>> {
-        tracing::info!("Fetching user repositories");
+    /// Fetches repo and member counts for organization `login`
+    pub async fn get_organization_details(&self, login: &str) -> Result<OrganizationDetails> {
+        #[derive(Deserialize)]
+        struct Plan {
+            name: String,
+        }
+        #[derive(Deserialize)]
+        struct Org {
+            public_repos: u32,
+            created_at: DateTime<Utc>,
+            plan: Option<Plan>,
+        }
 
-        let mut page = self
+        self.requests.record(ApiCategory::Organization);
+        let org: Org = self
             .octocrab
-            .current()
-            .list_repos_for_authenticated_user()
-            .per_page(100)
-            .send()
+            .get(format!("/orgs/{}", login), None::<&()>)
             .await
-            .context("Failed to fetch user repositories")?;
+            .api_context(format!("Failed to fetch organization {}", login))?;
 
-        let mut repo_infos = Vec::new();
-        loop {
-            for repo in &page {
-            let repo_info = RepositoryInfo {
-                    name: repo.name.clone(),
-                    full_name: repo.full_name.clone().unwrap_or_default(),
-                    owner: repo.owner.clone().map(|o| o.login).unwrap_or_default(),
-                    description: repo.description.clone().unwrap_or_default(),
-                    default_branch: repo.default_branch.clone().unwrap_or_else(|| "main".to_string()),
-                    private: repo.private.unwrap_or(false),
-                    fork: repo.fork.unwrap_or(false),
-                    stargazers_count: repo.stargazers_count.unwrap_or(0),
-                    forks_count: repo.forks_count.unwrap_or(0),
-                    language: repo
-                        .language
-                        .as_ref()
-                        .and_then(|v| v.as_str().map(|s| s.to_string())),
-            };
-            repo_infos.push(repo_info);
-            }
+        // The org payload has no member count; with one member per page the
+        // number of pages is the count
+        self.requests.record(ApiCategory::Organization);
+        let members: Page<octocrab::models::Author> = self
+            .octocrab
+            .get(format!("/orgs/{}/public_members", login), Some(&[("per_page", 1)]))
+            .await
+            .api_context(format!("Failed to fetch public members of {}", login))?;
+        let public_members = members
+            .number_of_pages()
+            .unwrap_or(members.items.len() as u32);
 
-            if let Some(next_page) = self.octocrab.get_page(&page.next).await? {
-                page = next_page;
-            } else {
-                break;
-            }
-        }
+        Ok(OrganizationDetails {
+            public_repos: org.public_repos,
+            public_members,
+            created_at: org.created_at,
+            plan: org.plan.map(|p| p.name),
+        })
+    }
 
-        tracing::info!("Found {} repositories", repo_infos.len());
-        Ok(repo_infos)
+    /// How many of organization `login`'s repositories the user can see. With
+    /// one repository per page the number of pages is the count.
+    pub async fn count_org_repos(&self, login: &str) -> Result<u32> {
+        self.requests.record(ApiCategory::Organization);
+        let repos: Page<serde::de::IgnoredAny> = self
+            .octocrab
+            .get(format!("/orgs/{}/repos", login), Some(&[("per_page", 1)]))
+            .await
+            .api_context(format!("Failed to count repositories of {}", login))?;
+        Ok(repos.number_of_pages().unwrap_or(repos.items.len() as u32))
     }
 
-    /// Gets information about the authenticated user
-    pub async fn get_authenticated_user(&self) -> Result<UserInfo> {
+    /// Fetches repositories accessible to the authenticated user, honoring
+    /// `ui.discovery_repo_limit` when set
+    pub async fn list_user_repositories(&self) -> Result<Vec<RepositoryInfo>> {
+        tracing::info!("Fetching user repositories");
+
+        let result = discover_repositories(self, self.discovery_repo_limit).await;
+        let repo_infos = self.with_error_details(result, None).await?;
+
+        tracing::info!("Found {} repositories", repo_infos.len());
+        Ok(repo_infos)
+    }
+
+    /// First page of `owner`'s repositories matching `query`, from the search
+    /// API. `user:` matches organizations too, so it covers both kinds of owner.
+    pub async fn search_repositories(&self, owner: &str, query: &str) -> Result<Vec<RepositoryInfo>> {
+        tracing::debug!(owner, query, "Searching repositories");
+
+        let q = format!("{} user:{}", query, owner);
+        self.requests.record(ApiCategory::Search);
+        let page = self
+            .octocrab
+            .search()
+            .repositories(&q)
+            .per_page(50)
+            .send()
+            .await
+            .api_context("Failed to search repositories")?;
+        Ok(page.items.iter().map(repo_info_from).collect())
+    }
+
+    /// Open PRs carrying `label` in any of `owner`'s repositories, from the
+    /// search API. Search stops at 1,000 results; a migration run again
+    /// finds the rest.
+    pub async fn search_labelled_prs(&self, owner: &str, label: &str) -> Result<Vec<LabelledPr>> {
+        let q = format!("is:pr is:open user:{} label:\"{}\"", owner, label);
+        tracing::debug!(q, "Searching PRs by label");
+
+        let page = self
+            .with_retries(|| async {
+                self.requests.record(ApiCategory::Search);
+                self.octocrab
+                    .search()
+                    .issues_and_pull_requests(&q)
+                    .per_page(100)
+                    .send()
+                    .await
+                    .api_context("Failed to search PRs by label")
+            })
+            .await?;
+        let issues = self
+            .with_later_pages(page, ApiCategory::Search, "Failed to search PRs by label")
+            .await?;
+        Ok(issues.iter().filter_map(labelled_pr_from).collect())
+    }
+
+    /// Adds `labels` to PR `pr_number` of `repo`, keeping the labels it has
+    pub async fn add_pr_labels(&self, repo: &RepoSlug, pr_number: u64, labels: &[String]) -> Result<()> {
+        self.requests.record(ApiCategory::LabelUpdate);
+        let result = self
+            .octocrab
+            .issues(&repo.owner, &repo.name)
+            .add_labels(pr_number, labels)
+            .await
+            .api_context(format!("Failed to add labels to PR #{} in {}", pr_number, repo))
+            .map(|_| ());
+        self.with_error_details(result, Some(repo)).await
+    }
+
+    /// Takes `label` off PR `pr_number` of `repo`. A label the PR no longer
+    /// has counts as removed.
+    pub async fn remove_pr_label(&self, repo: &RepoSlug, pr_number: u64, label: &str) -> Result<()> {
+        self.requests.record(ApiCategory::LabelUpdate);
+        let result = self
+            .octocrab
+            .issues(&repo.owner, &repo.name)
+            .remove_label(pr_number, label)
+            .await
+            .api_context(format!("Failed to remove label {} from PR #{} in {}", label, pr_number, repo));
+        match result {
+            Ok(_) | Err(GitHubError::NotFound { .. }) => Ok(()),
+            Err(e) => Err(self.error_details(e, Some(repo)).await),
+        }
+    }
+
+    /// Forks `owner/repo` into the authenticated user's account. GitHub creates
+    /// the fork asynchronously, so it may take a moment before it can be cloned.
+    pub async fn fork_repository(&self, owner: &str, repo: &str) -> Result<RepositoryInfo> {
+        tracing::info!("Forking {}/{}", owner, repo);
+
+        let route = format!("/repos/{}/{}/forks", owner, repo);
+        self.requests.record(ApiCategory::Repository);
+        let fork: octocrab::models::Repository = self
+            .octocrab
+            .post(route, None::<&()>)
+            .await
+            .api_context(format!("Failed to fork {}/{}", owner, repo))?;
+
+        let fork = repo_info_from(&fork);
+        tracing::info!("Created fork {}", fork.full_name);
+        Ok(fork)
+    }
+
+    /// Fills in what the failed response's body doesn't say: when a rate limit
+    /// resets, and where to authorize the token for SSO (asked of `repo`).
+    async fn with_error_details<T>(&self, result: Result<T>, repo: Option<&RepoSlug>) -> Result<T> {
+        match result {
+            Err(GitHubError::PartialListing { prs, source }) => Err(GitHubError::PartialListing {
+                prs,
+                source: Box::new(self.error_details(*source, repo).await),
+            }),
+            Err(e) => Err(self.error_details(e, repo).await),
+            ok => ok,
+        }
+    }
+
+    /// The rate-limit endpoint itself doesn't count against the quota
+    async fn error_details(&self, error: GitHubError, repo: Option<&RepoSlug>) -> GitHubError {
+        match error {
+            GitHubError::RateLimited { reset: None } => GitHubError::RateLimited {
+                reset: self.rate_limit_reset().await,
+            },
+            GitHubError::SsoRequired { org, url: None } => {
+                let url = match repo {
+                    Some(repo) => self.sso_authorization_url(repo).await,
+                    None => None,
+                };
+                GitHubError::SsoRequired {
+                    org: org
+                        .or_else(|| url.as_deref().and_then(sso_org))
+                        .or_else(|| repo.map(|r| r.owner.clone())),
+                    url,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// GitHub names the SSO authorization page only in the `X-GitHub-SSO`
+    /// header, which octocrab's errors drop, so the repository is asked again
+    async fn sso_authorization_url(&self, repo: &RepoSlug) -> Option<String> {
+        self.requests.record(ApiCategory::Repository);
+        let response = self
+            .octocrab
+            ._get(format!("/repos/{}/{}", repo.owner, repo.name))
+            .await
+            .ok()?;
+        let header = response.headers().get("x-github-sso")?.to_str().ok()?;
+        sso_header_url(header)
+    }
+
+    async fn rate_limit_reset(&self) -> Option<DateTime<Utc>> {
+        self.requests.record(ApiCategory::RateLimit);
+        self.octocrab
+            .ratelimit()
+            .get()
+            .await
+            .ok()
+            .and_then(|limits| DateTime::from_timestamp(limits.resources.core.reset as i64, 0))
+    }
+
+    /// Gets information about the authenticated user
+    pub async fn get_authenticated_user(&self) -> Result<UserInfo> {
         tracing::info!("Fetching authenticated user information");
 
+        self.requests.record(ApiCategory::User);
         let user = self
             .octocrab
             .current()
             .user()
             .await
-            .context("Failed to fetch authenticated user")?;
+            .api_context("Failed to fetch authenticated user")?;
+
+        let user_info = UserInfo {
+            login: user.login,
+            name: user.name.unwrap_or_default(),
+            email: user.email.unwrap_or_default(),
+        };
+
+        Ok(user_info)
+    }
+
+    /// The authenticated user's permissions on the configured repository;
+    /// `None` when GitHub leaves them out (e.g. unauthenticated requests)
+    pub async fn get_repo_permissions(&self) -> Result<Option<RepoPermissions>> {
+        self.get_repo_permissions_in(&self.repo).await
+    }
+
+    pub async fn get_repo_permissions_in(&self, repo: &RepoSlug) -> Result<Option<RepoPermissions>> {
+        self.requests.record(ApiCategory::Repository);
+        let repo = self
+            .octocrab
+            .repos(&repo.owner, &repo.name)
+            .get()
+            .await
+            .api_context("Failed to fetch repository permissions")?;
+        Ok(repo.permissions.map(RepoPermissions::from))
+    }
+
+    /// The repository's own gh_cherry settings on its default branch, most
+    /// specific first: `.github/gh_cherry.toml`, then a root `cherry.env`.
+    /// Both are requested at once; a file that doesn't exist is left out.
+    pub async fn get_repo_config_file(&self) -> Result<Vec<RepoConfigFile>> {
+        self.get_repo_config_file_in(&self.repo).await
+    }
+
+    pub async fn get_repo_config_file_in(&self, repo: &RepoSlug) -> Result<Vec<RepoConfigFile>> {
+        let (toml, env) = tokio::join!(
+            self.get_file_text(repo, RepoConfigFile::TOML_PATH),
+            self.get_file_text(repo, RepoConfigFile::ENV_PATH),
+        );
+        let mut files = Vec::new();
+        if let Some(text) = toml? {
+            files.push(RepoConfigFile::Toml(text));
+        }
+        if let Some(text) = env? {
+            files.push(RepoConfigFile::Env(text));
+        }
+        Ok(files)
+    }
+
+    /// Text of the file at `path` on the default branch, `None` on a 404
+    async fn get_file_text(&self, repo: &RepoSlug, path: &str) -> Result<Option<String>> {
+        self.requests.record(ApiCategory::Contents);
+        let result = self
+            .octocrab
+            .repos(&repo.owner, &repo.name)
+            .get_content()
+            .path(path)
+            .send()
+            .await
+            .api_context(format!("{} in {}", path, repo));
+        match result {
+            Ok(mut content) => Ok(content
+                .take_items()
+                .into_iter()
+                .find(|item| item.r#type == "file")
+                .and_then(|item| item.decoded_content())),
+            Err(GitHubError::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Body of the comment posted on a PR after it was cherry-picked. Commits are
+/// listed in the order they were applied, each as `source → new` for audit.
+/// The PR's triage `note` and then `footer`, when given, follow after blank lines.
+pub fn cherry_pick_comment_body(
+    target_branch: &str,
+    outcomes: &[CommitPickOutcome],
+    reordered: bool,
+    note: Option<&str>,
+    footer: Option<&str>,
+) -> String {
+    let lines: Vec<String> = outcomes
+        .iter()
+        .map(|c| {
+            let CommitOutcome::PickedAs { new_sha } = &c.outcome;
+            format!("- {} → {}", short_sha(&c.source_sha), short_sha(new_sha))
+        })
+        .collect();
+    let heading = if reordered {
+        "Commits (applied in a custom order):"
+    } else {
+        "Commits:"
+    };
+    let mut body = format!(
+        "🍒 **Cherry-picked to `{}`**\n\n{}\n{}",
+        target_branch,
+        heading,
+        lines.join("\n")
+    );
+    if let Some(note) = note {
+        body.push_str("\n\n📝 **Note:** ");
+        body.push_str(note);
+    }
+    if let Some(footer) = footer {
+        body.push_str("\n\n");
+        body.push_str(footer);
+    }
+    body
+}
+
+/// Why a PR was not backported this run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeferralReason {
+    Conflicts { files: Vec<String> },
+    Skipped,
+}
+
+/// A comment on an issue or PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueComment {
+    pub id: u64,
+    pub body: String,
+}
+
+/// Hidden marker identifying the deferral comment for one target branch, so
+/// later runs edit that comment instead of adding another
+pub fn deferral_marker(target_branch: &str) -> String {
+    format!("<!-- gh_cherry:deferred:{} -->", target_branch)
+}
+
+/// Body of the comment left on a PR whose backport to `target_branch` was deferred
+pub fn deferral_comment_body(target_branch: &str, reason: &DeferralReason, actor: &str) -> String {
+    let reason = match reason {
+        DeferralReason::Conflicts { files } => {
+            let files: Vec<String> = files.iter().map(|f| format!("`{}`", f)).collect();
+            format!("conflicts in {}", files.join(", "))
+        }
+        DeferralReason::Skipped => "skipped manually".to_string(),
+    };
+    format!(
+        "{}
+⏸️ **Cherry-pick to `{}` deferred**
+
+Reason: {}
+Run by: @{}",
+        deferral_marker(target_branch),
+        target_branch,
+        reason,
+        actor
+    )
+}
+
+/// Whether to post a new comment or edit the one already carrying the marker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentAction {
+    Create,
+    /// Edit the comment with this id
+    Update(u64),
+}
+
+/// Picks the latest comment containing `marker`, if any
+pub fn marked_comment_action(existing: &[IssueComment], marker: &str) -> CommentAction {
+    existing
+        .iter()
+        .rev()
+        .find(|c| c.body.contains(marker))
+        .map_or(CommentAction::Create, |c| CommentAction::Update(c.id))
+}
+
+/// Posts `body` on the PR, or edits the earlier comment holding `marker`
+/// so repeated runs keep a single comment up to date
+pub async fn upsert_marked_comment(
+    ops: &dyn GitHubOps,
+    pr_number: u64,
+    marker: &str,
+    body: &str,
+) -> Result<CommentAction> {
+    let existing = ops.list_pr_comments(pr_number).await?;
+    let action = marked_comment_action(&existing, marker);
+    match action {
+        CommentAction::Create => ops.create_comment(pr_number, body).await?,
+        CommentAction::Update(id) => ops.update_comment(id, body).await?,
+    }
+    Ok(action)
+}
+
+/// The authorization URL in an `X-GitHub-SSO: required; url=<url>` header.
+/// `partial-results` headers on listings carry no URL.
+pub fn sso_header_url(header: &str) -> Option<String> {
+    let mut parts = header.split(';').map(str::trim);
+    if parts.next() != Some("required") {
+        return None;
+    }
+    parts
+        .find_map(|part| part.strip_prefix("url="))
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+}
+
+/// The organization an SSO authorization URL (`https://github.com/orgs/<org>/sso?...`) is for
+fn sso_org(url: &str) -> Option<String> {
+    let path = url.split('?').next()?;
+    let mut segments = path.split('/').skip_while(|s| *s != "orgs").skip(1);
+    let org = segments.next().filter(|org| !org.is_empty())?;
+    (segments.next() == Some("sso")).then(|| org.to_string())
+}
+
+/// Longest a rate-limited request waits for its retry
+pub const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(5 * 60);
+
+/// First wait for a limit that doesn't say when it resets, as for secondary
+/// rate limits; it doubles with each retry
+const SECONDARY_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// How long retry `attempt` (0-based) of a rate-limited request waits: until
+/// `reset` when that's still ahead, otherwise a backoff. `None` when that's
+/// longer than [`MAX_RATE_LIMIT_WAIT`]. octocrab drops the `retry-after`
+/// header, so the reset is all there is to go on.
+pub fn retry_wait(reset: Option<DateTime<Utc>>, now: DateTime<Utc>, attempt: u32) -> Option<Duration> {
+    let until_reset = reset
+        .and_then(|reset| (reset - now).to_std().ok())
+        .filter(|wait| !wait.is_zero());
+    let wait = until_reset.unwrap_or_else(|| SECONDARY_LIMIT_BACKOFF.saturating_mul(2u32.saturating_pow(attempt)));
+    (wait <= MAX_RATE_LIMIT_WAIT).then_some(wait)
+}
+
+/// API root of github.com, used when `github.api_base_url` names no other
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Octocrab's builder without its own retries, which would resend writes
+/// and multiply [`GitHubClient`]'s retries
+fn octocrab_builder() -> octocrab::OctocrabBuilder<
+    octocrab::NoSvc,
+    octocrab::DefaultOctocrabBuilderConfig,
+    octocrab::NoAuth,
+    octocrab::NotLayerReady,
+> {
+    let mut builder = Octocrab::builder();
+    builder.add_retry_config(octocrab::service::middleware::retry::RetryConfig::None);
+    builder
+}
+
+/// First wait before retrying a request that failed in transit
+const NETWORK_RETRY_BASE: Duration = Duration::from_millis(500);
+
+/// How retries of a request that failed in transit are spaced
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    /// Wait before the first retry; it doubles with each one after
+    pub base: Duration,
+}
+
+impl Backoff {
+    /// How long retry `attempt` (0-based) waits: `base` doubled per attempt,
+    /// stretched by `jitter` (0 to 1) so clients that failed together don't
+    /// all come back at once
+    pub fn wait(&self, attempt: u32, jitter: f64) -> Duration {
+        self.base
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .mul_f64(1.0 + jitter.clamp(0.0, 1.0))
+    }
+}
+
+/// A fraction from 0 to 1 that differs between calls
+fn jitter() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+    let random = std::collections::hash_map::RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Maps an HTTP failure onto the variants callers branch on; `None` means a
+/// generic API error.
+pub(crate) fn classify_status(status: u16, message: &str, context: &str) -> Option<GitHubError> {
+    let lower = message.to_ascii_lowercase();
+    let rate_limited = lower.contains("rate limit");
+    let sso = lower.contains("saml enforcement") || lower.contains("single sign-on");
+    match status {
+        429 => Some(GitHubError::RateLimited { reset: None }),
+        403 if rate_limited => Some(GitHubError::RateLimited { reset: None }),
+        403 if sso => Some(GitHubError::SsoRequired { org: None, url: None }),
+        401 => Some(GitHubError::Unauthorized {
+            message: message.to_string(),
+        }),
+        404 => Some(GitHubError::NotFound {
+            what: context.to_string(),
+        }),
+        _ => None,
+    }
+}
+
+/// Wraps octocrab failures with context, classifying rate limits, auth and 404s
+trait ApiContext<T> {
+    fn api_context(self, context: impl Into<String>) -> Result<T>;
+}
+
+impl<T> ApiContext<T> for std::result::Result<T, octocrab::Error> {
+    fn api_context(self, context: impl Into<String>) -> Result<T> {
+        self.map_err(|source| {
+            let context = context.into();
+            if let octocrab::Error::GitHub { source: github, .. } = &source {
+                if let Some(e) = classify_status(github.status_code.as_u16(), &github.message, &context) {
+                    return e;
+                }
+            }
+            GitHubError::Api {
+                context,
+                source: Box::new(source),
+            }
+        })
+    }
+}
+
+fn repo_info_from(repo: &octocrab::models::Repository) -> RepositoryInfo {
+    RepositoryInfo {
+        name: repo.name.clone(),
+        full_name: repo.full_name.clone().unwrap_or_default(),
+        owner: repo.owner.clone().map(|o| o.login).unwrap_or_default(),
+        description: repo.description.clone().unwrap_or_default(),
+        default_branch: repo.default_branch.clone().unwrap_or_else(|| "main".to_string()),
+        private: repo.private.unwrap_or(false),
+        fork: repo.fork.unwrap_or(false),
+        stargazers_count: repo.stargazers_count.unwrap_or(0),
+        forks_count: repo.forks_count.unwrap_or(0),
+        language: repo
+            .language
+            .as_ref()
+            .and_then(|v| v.as_str().map(|s| s.to_string())),
+        parent_full_name: repo.parent.as_ref().and_then(|p| p.full_name.clone()),
+    }
+}
+
+fn pr_info_from(
+    pr: &octocrab::models::pulls::PullRequest,
+    labels: Vec<String>,
+    commits: Vec<CommitInfo>,
+) -> PrInfo {
+    PrInfo {
+        number: pr.number,
+        title: pr.title.clone().unwrap_or_default(),
+        author: pr.user.clone().map(|u| u.login).unwrap_or_default(),
+        created_at: pr.created_at.unwrap_or(Utc::now()),
+        updated_at: pr.updated_at.unwrap_or(pr.created_at.unwrap_or(Utc::now())),
+        labels,
+        commits,
+        head_sha: pr.head.sha.clone(),
+        base_ref: pr.base.ref_field.clone(),
+        head_ref: pr.head.ref_field.clone(),
+        assignees: logins(&pr.assignees),
+        requested_reviewers: logins(&pr.requested_reviewers),
+        merge_commit_sha: pr.merged_at.and(pr.merge_commit_sha.clone()),
+        state: PrState::from_pull(pr),
+        related_to: pr_references(
+            pr.number,
+            pr.title.as_deref().unwrap_or_default(),
+            pr.body.as_deref().unwrap_or_default(),
+        ),
+    }
+}
+
+fn commit_info_from(commit: octocrab::models::repos::RepoCommit) -> CommitInfo {
+    let git_author = commit.commit.author.as_ref();
+    CommitInfo {
+        author: commit
+            .author
+            .map(|a| a.login)
+            .or_else(|| git_author.map(|a| a.user.name.clone()))
+            .unwrap_or_else(|| "Unknown".to_string()),
+        date: git_author
+            .and_then(|a| a.date)
+            .or_else(|| commit.commit.committer.as_ref().and_then(|c| c.date))
+            .unwrap_or(Utc::now()),
+        message: commit.commit.message,
+        sha: commit.sha,
+    }
+}
+
+fn logins(users: &Option<Vec<octocrab::models::Author>>) -> Vec<String> {
+    users
+        .iter()
+        .flatten()
+        .map(|u| u.login.clone())
+        .collect()
+}
+
+/// Client-side assignee filter with OR semantics; an empty filter matches everything.
+/// Logins compare case-insensitively, as GitHub treats them.
+pub fn pr_matches_assignees(pr: &PrInfo, assignees: &[String]) -> bool {
+    assignees.is_empty()
+        || pr
+            .assignees
+            .iter()
+            .any(|a| assignees.iter().any(|f| f.eq_ignore_ascii_case(a)))
+}
+
+/// Replaces the `@me` alias with the authenticated user's login.
+pub fn resolve_assignee_aliases(assignees: &[String], me: &str) -> Vec<String> {
+    assignees
+        .iter()
+        .map(|a| {
+            if a.eq_ignore_ascii_case("@me") {
+                me.to_string()
+            } else {
+                a.trim_start_matches('@').to_string()
+            }
+        })
+        .collect()
+}
+
+/// A search hit as a [`LabelledPr`]; the repository is only given as its API URL
+fn labelled_pr_from(issue: &octocrab::models::issues::Issue) -> Option<LabelledPr> {
+    let mut segments = issue.repository_url.path_segments()?.rev();
+    let name = segments.next()?;
+    let owner = segments.next()?;
+    Some(LabelledPr {
+        repo: RepoSlug::new(owner, name),
+        number: issue.number,
+        title: issue.title.clone(),
+        labels: issue.labels.iter().map(|label| label.name.clone()).collect(),
+    })
+}
+
+/// `labels` without the pending tag and with the completed tag, added in its
+/// configured casing unless some casing of it is already there
+fn swap_pick_labels(mut labels: Vec<String>, tags: &TagConfig) -> Vec<String> {
+    labels.retain(|label| !tags.label_is(label, &tags.pending_tag));
+    if !tags.has_label(&labels, &tags.completed_tag) {
+        labels.push(tags.completed_tag.clone());
+    }
+    labels
+}
+
+/// The search API query for the PRs `filter` keeps in `repo` since `since`,
+/// so GitHub narrows them down instead of the listing fetching each PR's
+/// labels. `None` when the labels can't be required on the server: they're
+/// off, or one has a quote in it. The sprint label is only searched for when
+/// the pattern is an exact label like `^S12$`; the pattern is checked against
+/// the labels found either way.
+pub fn pr_search_query(repo: &RepoSlug, filter: &PrFilter, since: DateTime<Utc>) -> Option<String> {
+    let tags = &filter.tags;
+    if !filter.labels {
+        return None;
+    }
+    let mut labels = vec![tags.pending_tag.trim(), tags.environment.trim()];
+    labels.extend(exact_label(&tags.sprint_pattern));
+    labels.retain(|label| !label.is_empty());
+    if labels.iter().any(|label| label.contains('"')) {
+        return None;
+    }
+
+    let mut query = format!("repo:{} is:pr base:{}", repo, filter.base_branch);
+    for label in labels {
+        query.push_str(&format!(" label:\"{}\"", label));
+    }
+    query.push_str(&format!(" updated:>={}", since.format("%Y-%m-%d")));
+    Some(query)
+}
+
+/// The one label `pattern` matches, when it's a plain label anchored at both ends
+fn exact_label(pattern: &str) -> Option<&str> {
+    let label = pattern.strip_prefix('^')?.strip_suffix('$')?;
+    (!label.is_empty() && regex::escape(label) == label).then_some(label)
+}
+
+pub(crate) fn pr_matches_criteria(tags: &TagConfig, labels: &[String], sprint_regex: &Regex) -> bool {
+    has_sprint_tag(tags, labels, sprint_regex)
+        && tags.has_label(labels, &tags.environment)
+        && tags.has_label(labels, &tags.pending_tag)
+}
+
+/// Whether `labels` carry the sprints `sprint_regex` names: in `all` mode a
+/// label for each of its alternatives (`S12|S13` needs both), in `any` mode
+/// a label for one of them
+fn has_sprint_tag(tags: &TagConfig, labels: &[String], sprint_regex: &Regex) -> bool {
+    let has_match = |re: &Regex| labels.iter().any(|label| re.is_match(label));
+    match tags.sprint_filter_mode {
+        LabelFilterMode::Any => has_match(sprint_regex),
+        LabelFilterMode::All => pattern_alternatives(sprint_regex.as_str())
+            .into_iter()
+            .all(|alternative| match Regex::new(alternative) {
+                Ok(re) => has_match(&re),
+                // One that only parses as part of the whole, e.g. after a flag group
+                Err(_) => has_match(sprint_regex),
+            }),
+    }
+}
+
+/// The top-level alternatives of a regex (`S12|S13` gives `S12` and `S13`),
+/// leaving `|` inside groups, classes and escapes alone
+fn pattern_alternatives(pattern: &str) -> Vec<&str> {
+    let mut alternatives = Vec::new();
+    let (mut depth, mut in_class, mut escaped, mut start) = (0usize, false, false, 0);
+    for (i, c) in pattern.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '(' if !in_class => depth += 1,
+            ')' if !in_class => depth = depth.saturating_sub(1),
+            '|' if !in_class && depth == 0 => {
+                alternatives.push(&pattern[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    alternatives.push(&pattern[start..]);
+    alternatives
+}
+
+/// A filter that would have kept an explicitly requested PR out of the listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CriteriaMismatch {
+    MissingPendingTag { tag: String },
+    MissingEnvironmentTag { tag: String },
+    MissingSprintTag { pattern: String },
+    OutsideWindow { days_back: u32, updated_at: DateTime<Utc> },
+    DifferentBase { expected: String, actual: String },
+    NotAssigned { assignees: Vec<String> },
+}
+
+/// Every listing filter `pr` fails, as of `now`. The sprint check honours
+/// `tags.sprint_filter_mode`.
+pub fn criteria_mismatches(
+    config: &Config,
+    pr: &PrInfo,
+    sprint_regex: &Regex,
+    now: DateTime<Utc>,
+) -> Vec<CriteriaMismatch> {
+    let mut mismatches = Vec::new();
+
+    let tags = &config.tags;
+    if !tags.has_label(&pr.labels, &tags.pending_tag) {
+        mismatches.push(CriteriaMismatch::MissingPendingTag {
+            tag: tags.pending_tag.clone(),
+        });
+    }
+    if !tags.has_label(&pr.labels, &tags.environment) {
+        mismatches.push(CriteriaMismatch::MissingEnvironmentTag {
+            tag: tags.environment.clone(),
+        });
+    }
+    if !has_sprint_tag(tags, &pr.labels, sprint_regex) {
+        mismatches.push(CriteriaMismatch::MissingSprintTag {
+            pattern: tags.sprint_pattern.clone(),
+        });
+    }
+
+    let since = now - chrono::Duration::days(config.ui.days_back as i64);
+    if pr.updated_at < since {
+        mismatches.push(CriteriaMismatch::OutsideWindow {
+            days_back: config.ui.days_back,
+            updated_at: pr.updated_at,
+        });
+    }
+    if pr.base_ref != config.github.base_branch {
+        mismatches.push(CriteriaMismatch::DifferentBase {
+            expected: config.github.base_branch.clone(),
+            actual: pr.base_ref.clone(),
+        });
+    }
+    if !pr_matches_assignees(pr, &config.ui.assignees) {
+        mismatches.push(CriteriaMismatch::NotAssigned {
+            assignees: config.ui.assignees.clone(),
+        });
+    }
+
+    mismatches
+}
+
+/// Users and teams to ask for review on a backport PR
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReviewRequest {
+    pub reviewers: Vec<String>,
+    pub team_reviewers: Vec<String>,
+}
+
+impl ReviewRequest {
+    pub fn is_empty(&self) -> bool {
+        self.reviewers.is_empty() && self.team_reviewers.is_empty()
+    }
+}
+
+/// Builds the review request for a backport: the original PR author, unless that is
+/// the authenticated user (GitHub rejects self-review requests), plus the configured
+/// team. `review_team` may be given as `org/slug` or just `slug`.
+pub fn backport_review_request(
+    original_author: &str,
+    authenticated_login: &str,
+    review_team: Option<&str>,
+) -> ReviewRequest {
+    let mut request = ReviewRequest::default();
+    if !original_author.is_empty() && !original_author.eq_ignore_ascii_case(authenticated_login)
+    {
+        request.reviewers.push(original_author.to_string());
+    }
+    if let Some(team) = review_team.map(str::trim).filter(|t| !t.is_empty()) {
+        let slug = team.rsplit('/').next().unwrap_or(team);
+        request.team_reviewers.push(slug.to_string());
+    }
+    request
+}
+
+/// Outcome of opening a backport PR. Reviewer-request failures don't fail the
+/// operation; they are reported back for the UI to warn about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackportPr {
+    pub number: u64,
+    /// Why asking for reviews failed, if it did
+    pub review_error: Option<String>,
+}
+
+/// Title and description of the backport of PR `number` onto `base`
+pub fn backport_pr_text(number: u64, title: &str, base: &str) -> (String, String) {
+    (
+        format!("[{}] {}", base, title),
+        format!("Backport of #{} onto `{}`.", number, base),
+    )
+}
+
+/// The teams owning a backport's paths on its base branch, by that branch's CODEOWNERS
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CodeOwnerTeams {
+    /// `@org/team` owners, as [`crate::codeowners::CodeOwners::teams_for`] lists them
+    pub teams: Vec<String>,
+    /// Ask them to review as well (`github.request_code_owners`)
+    pub request_review: bool,
+}
+
+/// `body` with the code owner teams of `base` listed under it, so reviewers
+/// can see who has to approve
+pub fn backport_pr_body(body: &str, base: &str, teams: &[String]) -> String {
+    if teams.is_empty() {
+        return body.to_string();
+    }
+    let owners = format!("**Code owners on `{}`:** {}", base, teams.join(", "));
+    if body.is_empty() {
+        owners
+    } else {
+        format!("{}\n\n{}", body, owners)
+    }
+}
+
+/// Opens the backport PR and asks the original author (and review team) to
+/// review it. The code owner teams are listed in the body and, if
+/// `code_owners.request_review`, asked to review too.
+#[allow(clippy::too_many_arguments)]
+pub async fn open_backport_pr(
+    ops: &dyn GitHubOps,
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+    original_author: &str,
+    authenticated_login: &str,
+    review_team: Option<&str>,
+    code_owners: &CodeOwnerTeams,
+) -> Result<BackportPr> {
+    let body = backport_pr_body(body, base, &code_owners.teams);
+    let number = ops.create_cherry_pick_pr(head, base, title, &body).await?;
+
+    let mut request = backport_review_request(original_author, authenticated_login, review_team);
+    if code_owners.request_review {
+        for team in &code_owners.teams {
+            let slug = crate::codeowners::team_slug(team).to_string();
+            if !request.team_reviewers.contains(&slug) {
+                request.team_reviewers.push(slug);
+            }
+        }
+    }
+    let review_error = if request.is_empty() {
+        None
+    } else {
+        match ops.request_reviewers(number, &request).await {
+            Ok(()) => None,
+            Err(e) => {
+                tracing::warn!(pr = number, error = %e, "Failed to request reviewers");
+                Some(e.to_string())
+            }
+        }
+    };
+
+    Ok(BackportPr { number, review_error })
+}
+
+/// GitHub operations used by the cherry-pick flow and `--digest`, abstracted
+/// so the orchestration can be tested without network access.
+#[async_trait]
+#[allow(dead_code)]
+pub trait GitHubOps: Send + Sync {
+    async fn update_pr_labels(&self, pr_number: u64) -> Result<()>;
+    async fn add_cherry_pick_comment(
+        &self,
+        pr_number: u64,
+        target_branch: &str,
+        outcomes: &[CommitPickOutcome],
+        reordered: bool,
+        note: Option<&str>,
+        footer: Option<&str>,
+    ) -> Result<()>;
+    async fn create_cherry_pick_pr(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<u64>;
+    async fn request_reviewers(&self, pr_number: u64, request: &ReviewRequest) -> Result<()>;
+    async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<IssueComment>>;
+    async fn create_comment(&self, pr_number: u64, body: &str) -> Result<()>;
+    async fn update_comment(&self, comment_id: u64, body: &str) -> Result<()>;
+    async fn pr_for_commit(&self, sha: &str) -> Result<Option<u64>>;
+    async fn pr_overview(&self, pr_number: u64) -> Result<PrOverview>;
+    async fn search_labelled_prs(&self, owner: &str, label: &str) -> Result<Vec<LabelledPr>>;
+    async fn add_pr_labels(&self, repo: &RepoSlug, pr_number: u64, labels: &[String]) -> Result<()>;
+    async fn remove_pr_label(&self, repo: &RepoSlug, pr_number: u64, label: &str) -> Result<()>;
+    /// Requests sent to GitHub so far; nothing for implementations that don't
+    fn api_usage(&self) -> ApiUsage {
+        ApiUsage::default()
+    }
+}
+
+#[async_trait]
+impl GitHubOps for GitHubClient {
+    async fn update_pr_labels(&self, pr_number: u64) -> Result<()> {
+        GitHubClient::update_pr_labels(self, pr_number).await
+    }
+    async fn add_cherry_pick_comment(
+        &self,
+        pr_number: u64,
+        target_branch: &str,
+        outcomes: &[CommitPickOutcome],
+        reordered: bool,
+        note: Option<&str>,
+        footer: Option<&str>,
+    ) -> Result<()> {
+        GitHubClient::add_cherry_pick_comment(self, pr_number, target_branch, outcomes, reordered, note, footer)
+            .await
+    }
+    async fn create_cherry_pick_pr(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<u64> {
+        GitHubClient::create_cherry_pick_pr(self, head, base, title, body).await
+    }
+    async fn request_reviewers(&self, pr_number: u64, request: &ReviewRequest) -> Result<()> {
+        GitHubClient::request_reviewers(self, pr_number, request).await
+    }
+    async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<IssueComment>> {
+        GitHubClient::list_pr_comments(self, pr_number).await
+    }
+    async fn create_comment(&self, pr_number: u64, body: &str) -> Result<()> {
+        GitHubClient::create_comment(self, pr_number, body).await
+    }
+    async fn update_comment(&self, comment_id: u64, body: &str) -> Result<()> {
+        GitHubClient::update_comment(self, comment_id, body).await
+    }
+    async fn pr_for_commit(&self, sha: &str) -> Result<Option<u64>> {
+        GitHubClient::pr_for_commit(self, sha).await
+    }
+    async fn pr_overview(&self, pr_number: u64) -> Result<PrOverview> {
+        GitHubClient::pr_overview(self, pr_number).await
+    }
+    async fn search_labelled_prs(&self, owner: &str, label: &str) -> Result<Vec<LabelledPr>> {
+        GitHubClient::search_labelled_prs(self, owner, label).await
+    }
+    async fn add_pr_labels(&self, repo: &RepoSlug, pr_number: u64, labels: &[String]) -> Result<()> {
+        GitHubClient::add_pr_labels(self, repo, pr_number, labels).await
+    }
+    async fn remove_pr_label(&self, repo: &RepoSlug, pr_number: u64, label: &str) -> Result<()> {
+        GitHubClient::remove_pr_label(self, repo, pr_number, label).await
+    }
+    fn api_usage(&self) -> ApiUsage {
+        GitHubClient::api_usage(self)
+    }
+}
+
+const REPOS_PER_PAGE: usize = 100;
+
+/// One page of `GET /user/repos`
+pub struct RepoPage {
+    pub repos: Vec<RepositoryInfo>,
+    pub has_next: bool,
+}
+
+/// Repository lookups used during discovery, abstracted so paging and the
+/// exact-name shortcut can be tested without network access.
+#[async_trait]
+pub trait RepoDiscovery: Send + Sync {
+    /// Fetches page `page` (1-based) of the authenticated user's repositories
+    async fn repo_page(&self, page: u32) -> Result<RepoPage>;
+    /// Looks up a single repository via `GET /repos/{owner}/{name}`
+    async fn find_repository(&self, owner: &str, name: &str) -> Result<RepositoryInfo>;
+    /// The account the token belongs to
+    async fn authenticated_user(&self) -> Result<UserInfo>;
+}
+
+#[async_trait]
+impl RepoDiscovery for GitHubClient {
+    async fn repo_page(&self, page: u32) -> Result<RepoPage> {
+        let page_param = page.to_string();
+        let per_page = REPOS_PER_PAGE.to_string();
+        self.requests.record(ApiCategory::Repository);
+        let repos: Vec<octocrab::models::Repository> = self
+            .octocrab
+            .get(
+                "/user/repos",
+                Some(&[("per_page", per_page.as_str()), ("page", page_param.as_str())]),
+            )
+            .await
+            .api_context("Failed to fetch user repositories")?;
+
+        Ok(RepoPage {
+            has_next: repos.len() == REPOS_PER_PAGE,
+            repos: repos.iter().map(repo_info_from).collect(),
+        })
+    }
+
+    async fn find_repository(&self, owner: &str, name: &str) -> Result<RepositoryInfo> {
+        self.requests.record(ApiCategory::Repository);
+        let repo = self
+            .octocrab
+            .repos(owner, name)
+            .get()
+            .await
+            .api_context(format!("Repository {}/{}", owner, name))?;
+        Ok(repo_info_from(&repo))
+    }
+
+    async fn authenticated_user(&self) -> Result<UserInfo> {
+        self.get_authenticated_user().await
+    }
+}
+
+/// The authenticated user, looked up only when discovery has to know who it
+/// runs as: to fall back to their own account when no owner is set, or to
+/// tell whether a repository about to be forked is already theirs. `None`
+/// otherwise, including whenever `github` names both owner and repo.
+pub async fn discovery_user(
+    source: &dyn RepoDiscovery,
+    github: &crate::config::GitHubConfig,
+    create_fork: bool,
+) -> Result<Option<UserInfo>> {
+    let needed = github.owner.is_empty() || (github.repo.is_empty() && create_fork);
+    if !needed {
+        return Ok(None);
+    }
+    source.authenticated_user().await.map(Some)
+}
+
+/// How long the organization selector waits for [`probe_org_repos`]
+pub const ORG_PROBE_TIME_BOX: Duration = Duration::from_secs(3);
+
+/// Repository counts of `orgs` by login, probed concurrently. Orgs whose
+/// probe fails or is still running after `time_box` are left out, and the
+/// probes still running are dropped.
+pub async fn probe_org_repos(
+    client: &GitHubClient,
+    orgs: &[OrganizationInfo],
+    time_box: Duration,
+) -> std::collections::HashMap<String, u32> {
+    let mut probes = tokio::task::JoinSet::new();
+    for org in orgs {
+        let client = client.clone();
+        let login = org.login.clone();
+        probes.spawn(async move {
+            let count = client.count_org_repos(&login).await;
+            (login, count)
+        });
+    }
+
+    let deadline = tokio::time::Instant::now() + time_box;
+    let mut counts = std::collections::HashMap::new();
+    loop {
+        match tokio::time::timeout_at(deadline, probes.join_next()).await {
+            Ok(Some(Ok((login, Ok(count))))) => {
+                counts.insert(login, count);
+            }
+            Ok(Some(Ok((login, Err(e))))) => tracing::debug!("Couldn't count repositories of {}: {}", login, e),
+            Ok(Some(Err(e))) => tracing::debug!("Organization probe failed: {}", e),
+            Ok(None) => break,
+            Err(_) => {
+                tracing::info!(
+                    probed = counts.len(),
+                    total = orgs.len(),
+                    "Organization probes timed out; leaving the rest unannotated"
+                );
+                break;
+            }
+        }
+    }
+    counts
+}
+
+/// Pages through the user's repositories, stopping early once `limit` repos
+/// have been collected.
+pub async fn discover_repositories(
+    source: &dyn RepoDiscovery,
+    limit: Option<usize>,
+) -> Result<Vec<RepositoryInfo>> {
+    let mut repos = Vec::new();
+    let mut page = 1;
+    loop {
+        let RepoPage { repos: batch, has_next } = source.repo_page(page).await?;
+        repos.extend(batch);
+
+        if let Some(limit) = limit {
+            if repos.len() >= limit {
+                repos.truncate(limit);
+                tracing::info!("Stopped repository discovery at the configured limit of {}", limit);
+                break;
+            }
+        }
+        if !has_next {
+            break;
+        }
+        page += 1;
+    }
+    Ok(repos)
+}
+
+/// Validates a typed `name` or `owner/name` with a single lookup instead of
+/// listing every repository. Bare names resolve against `default_owner`.
+pub async fn resolve_exact_repo(
+    source: &dyn RepoDiscovery,
+    default_owner: &str,
+    input: &str,
+) -> Result<RepositoryInfo> {
+    let input = input.trim();
+    let (owner, name) = match input.split_once('/') {
+        Some((owner, name)) => (owner.trim(), name.trim()),
+        None => (default_owner, input),
+    };
+    if owner.is_empty() || name.is_empty() {
+        return Err(GitHubError::InvalidInput {
+            reason: format!("Expected a repository name or owner/name, got '{}'", input),
+        });
+    }
+    source.find_repository(owner, name).await
+}
+
+/// Upstream `owner/name` of a fork, looked up via `GET /repos/...` when the
+/// listing didn't include it. `None` for repositories that aren't forks.
+pub async fn fork_parent(
+    source: &dyn RepoDiscovery,
+    repo: &RepositoryInfo,
+) -> Result<Option<String>> {
+    if !repo.fork {
+        return Ok(None);
+    }
+    if let Some(parent) = &repo.parent_full_name {
+        return Ok(Some(parent.clone()));
+    }
+    let detailed = source.find_repository(&repo.owner, &repo.name).await?;
+    Ok(detailed.parent_full_name)
+}
+
+/// Which repository PR discovery should query after a fork was selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrTarget {
+    Fork,
+    Parent,
+}
+
+/// Points `github.owner/repo` at the fork's parent when `target` is
+/// [`PrTarget::Parent`]. Git operations are unaffected; they use the local clone.
+pub fn retarget_pr_discovery(
+    github: &mut crate::config::GitHubConfig,
+    parent_full_name: &str,
+    target: PrTarget,
+) -> Result<()> {
+    if target == PrTarget::Fork {
+        return Ok(());
+    }
+    match parent_full_name.split_once('/') {
+        Some((owner, name)) if !owner.is_empty() && !name.is_empty() => {
+            github.owner = owner.to_string();
+            github.repo = name.to_string();
+            Ok(())
+        }
+        _ => Err(GitHubError::InvalidInput {
+            reason: format!("Expected parent repository as owner/name, got '{}'", parent_full_name),
+        }),
+    }
+}
+
+/// What a listing found: the matching PRs, and a line for each PR it skipped
+/// because its details couldn't be fetched
+#[derive(Debug, Clone, Default)]
+pub struct Listing {
+    pub prs: Vec<PrInfo>,
+    pub warnings: Vec<String>,
+}
+
+/// The per-PR lookups a listing makes, so evaluating its pages can be tested
+/// without network calls
+#[async_trait]
+pub trait PrDetails: Send + Sync {
+    async fn pr_labels(&self, repo: &RepoSlug, pr_number: u64) -> Result<Vec<String>>;
+    async fn pr_commits(&self, repo: &RepoSlug, pr_number: u64) -> Result<Vec<CommitInfo>>;
+}
 
-        let user_info = UserInfo {
-            login: user.login,
-            name: user.name.unwrap_or_default(),
-            email: user.email.unwrap_or_default(),
-        };
+#[async_trait]
+impl PrDetails for GitHubClient {
+    async fn pr_labels(&self, repo: &RepoSlug, pr_number: u64) -> Result<Vec<String>> {
+        self.get_pr_labels(repo, pr_number).await
+    }
 
-        Ok(user_info)
+    async fn pr_commits(&self, repo: &RepoSlug, pr_number: u64) -> Result<Vec<CommitInfo>> {
+        self.get_pr_commits(repo, pr_number).await
     }
 }
 
-pub(crate) fn pr_matches_criteria(config: &Config, labels: &[String], sprint_regex: &Regex) -> bool {
-    let has_sprint_tag = labels.iter().any(|label| sprint_regex.is_match(label));
-    let has_env_tag = labels.iter().any(|label| label == &config.tags.environment);
-    let has_pending_tag = labels.iter().any(|label| label == &config.tags.pending_tag);
-    has_sprint_tag && has_env_tag && has_pending_tag
+/// Fetches the labels of each of `candidates` and, for those `filter` keeps,
+/// their commits, adding the matches to `listing`. A PR whose details fail on
+/// their own (it's gone, or GitHub errors on it) is skipped with a warning;
+/// failures every other PR would hit too, like rate limits, end the listing.
+pub async fn evaluate_prs(
+    source: &dyn PrDetails,
+    repo: &RepoSlug,
+    filter: &PrFilter,
+    sprint_regex: &Regex,
+    candidates: Vec<PrInfo>,
+    listing: &mut Listing,
+) -> Result<()> {
+    for candidate in candidates {
+        let number = candidate.number;
+        match evaluate_pr(source, repo, filter, sprint_regex, candidate).await {
+            Ok(Some(pr)) => listing.prs.push(pr),
+            Ok(None) => {}
+            Err(e) if e.is_item_specific() => {
+                tracing::warn!("Skipping PR #{} in {}: {}", number, repo, e);
+                listing.warnings.push(e.to_string());
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// `candidate` with its labels and commits, if `filter` keeps it
+async fn evaluate_pr(
+    source: &dyn PrDetails,
+    repo: &RepoSlug,
+    filter: &PrFilter,
+    sprint_regex: &Regex,
+    mut candidate: PrInfo,
+) -> Result<Option<PrInfo>> {
+    candidate.labels = source.pr_labels(repo, candidate.number).await?;
+    if filter.labels && !pr_matches_criteria(&filter.tags, &candidate.labels, sprint_regex) {
+        return Ok(None);
+    }
+    candidate.commits = source.pr_commits(repo, candidate.number).await?;
+    Ok(pr_matches_assignees(&candidate, &filter.assignees).then_some(candidate))
 }
 
 /// Trait abstraction to allow mocking PR listing in tests without network calls.
 #[async_trait]
 #[allow(dead_code)]
 pub trait PrLister: Send + Sync {
-    async fn list_matching_prs(&self) -> Result<Vec<PrInfo>>;
-    fn config(&self) -> &Config;
+    async fn list_matching_prs(&self, repo: &RepoSlug, filter: &PrFilter) -> Result<Vec<PrInfo>>;
 }
 
 #[async_trait]
 impl PrLister for GitHubClient {
-    async fn list_matching_prs(&self) -> Result<Vec<PrInfo>> {
-        // Call inherent async method
-        GitHubClient::list_matching_prs(self).await
+    async fn list_matching_prs(&self, repo: &RepoSlug, filter: &PrFilter) -> Result<Vec<PrInfo>> {
+        self.list_matching_prs_in(repo, filter).await
     }
-    fn config(&self) -> &Config { &self.config }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+    use std::collections::HashMap;
 
     fn test_config_with(env: &str, pending: &str, sprint: &str) -> Config {
         Config {
@@ -383,14 +2331,25 @@ mod tests {
                 target_branch: "main".into(),
                 cherry_pick_source_branch: "main".into(),
                 branch_name_template: "ch/{task_id}".into(),
+                ..Default::default()
             },
             tags: crate::config::TagConfig {
                 sprint_pattern: sprint.into(),
                 environment: env.into(),
                 pending_tag: pending.into(),
                 completed_tag: "done".into(),
+                ..Default::default()
             },
-            ui: crate::config::UiConfig { days_back: 7, page_size: 20, only_forked_repos: false },
+            ui: crate::config::UiConfig {
+                days_back: 7,
+                page_size: 20,
+                only_forked_repos: false,
+                ..Default::default()
+            },
+            storage: crate::config::StorageConfig::default(),
+            network: crate::config::NetworkConfig::default(),
+            env_warnings: Vec::new(),
+            sources: Default::default(),
         }
     }
 
@@ -403,18 +2362,84 @@ mod tests {
             "DEV".to_string(),
             "pending cherrypick".to_string(),
         ];
-    assert!(crate::github::pr_matches_criteria(&cfg, &labels, &re));
+    assert!(crate::github::pr_matches_criteria(&cfg.tags, &labels, &re));
 
     let labels2 = vec!["S12".to_string(), "QA".to_string(), "pending cherrypick".to_string()];
-    assert!(!crate::github::pr_matches_criteria(&cfg, &labels2, &re));
+    assert!(!crate::github::pr_matches_criteria(&cfg.tags, &labels2, &re));
+    }
+
+    #[test]
+    fn pr_label_matching_any_mode_relaxes_only_the_sprint_check() {
+        let mut cfg = test_config_with("DEV", "pending cherrypick", "S12|S13");
+        cfg.tags.sprint_filter_mode = LabelFilterMode::Any;
+        let re = Regex::new(&cfg.tags.sprint_pattern).unwrap();
+        let labels = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+
+        let one_sprint = labels(&["S13", "DEV", "pending cherrypick"]);
+        let both_sprints = labels(&["S12", "S13", "DEV", "pending cherrypick"]);
+        assert!(pr_matches_criteria(&cfg.tags, &one_sprint, &re));
+        assert!(pr_matches_criteria(&cfg.tags, &both_sprints, &re));
+        // A sprint label alone, or the other labels alone, isn't a pending backport
+        assert!(!pr_matches_criteria(&cfg.tags, &labels(&["S12", "QA"]), &re));
+        assert!(!pr_matches_criteria(&cfg.tags, &labels(&["DEV"]), &re));
+        assert!(!pr_matches_criteria(&cfg.tags, &labels(&["pending cherrypick", "DEV"]), &re));
+
+        cfg.tags.sprint_filter_mode = LabelFilterMode::All;
+        assert!(!pr_matches_criteria(&cfg.tags, &one_sprint, &re));
+        assert!(pr_matches_criteria(&cfg.tags, &both_sprints, &re));
+    }
+
+    #[test]
+    fn pattern_alternatives_split_only_at_the_top_level() {
+        assert_eq!(pattern_alternatives(r"S\d+"), [r"S\d+"]);
+        assert_eq!(pattern_alternatives("^S12$|^S13$"), ["^S12$", "^S13$"]);
+        assert_eq!(pattern_alternatives(r"S(1|2)|[|]|a\|b"), ["S(1|2)", "[|]", r"a\|b"]);
+    }
+
+    #[test]
+    fn label_matching_ignores_case_and_padding_unless_told_not_to() {
+        let mut cfg = test_config_with("DEV", "pending cherrypick", r"S\d+");
+        let re = Regex::new(&cfg.tags.sprint_pattern).unwrap();
+        let labels = vec!["S12".to_string(), "dev".to_string(), " Pending Cherrypick ".to_string()];
+        assert!(pr_matches_criteria(&cfg.tags, &labels, &re));
+
+        let mut pr = pr_assigned_to(1, &[]);
+        pr.labels = labels.clone();
+        pr.updated_at = Utc::now();
+        assert!(criteria_mismatches(&cfg, &pr, &re, Utc::now()).is_empty());
+
+        cfg.tags.case_sensitive_labels = true;
+        assert!(!pr_matches_criteria(&cfg.tags, &labels, &re));
+        assert_eq!(criteria_mismatches(&cfg, &pr, &re, Utc::now()).len(), 2);
+    }
+
+    #[test]
+    fn the_pick_label_swap_matches_any_casing_and_adds_the_configured_one() {
+        let mut cfg = test_config_with("DEV", "Pending Cherrypick", r"S\d+");
+        cfg.tags.completed_tag = "Cherry Picked".into();
+        let swapped = swap_pick_labels(
+            vec!["pending cherrypick".into(), "S12".into(), "PENDING CHERRYPICK".into()],
+            &cfg.tags,
+        );
+        assert_eq!(swapped, ["S12", "Cherry Picked"]);
+
+        // Already completed in another casing: nothing is added twice
+        let swapped = swap_pick_labels(vec!["cherry picked".into()], &cfg.tags);
+        assert_eq!(swapped, ["cherry picked"]);
+
+        cfg.tags.case_sensitive_labels = true;
+        let swapped = swap_pick_labels(vec!["pending cherrypick".into()], &cfg.tags);
+        assert_eq!(swapped, ["pending cherrypick", "Cherry Picked"]);
     }
 
-    struct MockLister { #[allow(dead_code)] cfg: Config, prs: Vec<PrInfo> }
+    /// Serves each repository's PRs, whatever the filter
+    struct MockLister { prs: HashMap<RepoSlug, Vec<PrInfo>> }
 
     #[async_trait]
     impl super::PrLister for MockLister {
-        async fn list_matching_prs(&self) -> Result<Vec<PrInfo>> { Ok(self.prs.clone()) }
-        fn config(&self) -> &Config { &self.cfg }
+        async fn list_matching_prs(&self, repo: &RepoSlug, _: &PrFilter) -> Result<Vec<PrInfo>> {
+            Ok(self.prs.get(repo).cloned().unwrap_or_default())
+        }
     }
 
     #[tokio::test]
@@ -424,16 +2449,738 @@ mod tests {
             number: 1,
             title: "Test".into(),
             author: "alice".into(),
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
             labels: vec!["S1".into(), "DEV".into(), "pending cherrypick".into()],
-            commits: vec![],
             head_sha: "abcd1234".into(),
             base_ref: "main".into(),
             head_ref: "feature".into(),
+            state: PrState::Open,
+            ..Default::default()
         }];
-        let mock = MockLister { cfg, prs: prs.clone() };
-        let got = mock.list_matching_prs().await.unwrap();
+        let repo = RepoSlug::new("acme", "widgets");
+        let mock = MockLister { prs: HashMap::from([(repo.clone(), prs.clone())]) };
+        let filter = PrFilter::from_config(&cfg);
+        let got = mock.list_matching_prs(&repo, &filter).await.unwrap();
         assert_eq!(got.len(), prs.len());
+        let other = mock.list_matching_prs(&RepoSlug::new("acme", "gadgets"), &filter).await.unwrap();
+        assert!(other.is_empty());
+    }
+
+    fn pr_assigned_to(number: u64, assignees: &[&str]) -> PrInfo {
+        PrInfo {
+            number,
+            title: format!("PR {}", number),
+            author: "alice".into(),
+            head_sha: "abcd1234".into(),
+            base_ref: "main".into(),
+            head_ref: "feature".into(),
+            assignees: assignees.iter().map(|s| s.to_string()).collect(),
+            state: PrState::Open,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn assignee_filter_uses_or_semantics() {
+        let cfg = test_config_with("DEV", "pending cherrypick", r"S\d+");
+        let repo = RepoSlug::from_config(&cfg);
+        let mock = MockLister {
+            prs: HashMap::from([(
+                repo.clone(),
+                vec![
+                    pr_assigned_to(1, &["bob"]),
+                    pr_assigned_to(2, &["carol", "Dave"]),
+                    pr_assigned_to(3, &[]),
+                ],
+            )]),
+        };
+        let prs = mock.list_matching_prs(&repo, &PrFilter::from_config(&cfg)).await.unwrap();
+        let filter = vec!["bob".to_string(), "dave".to_string()];
+        let kept: Vec<u64> = prs
+            .iter()
+            .filter(|pr| pr_matches_assignees(pr, &filter))
+            .map(|pr| pr.number)
+            .collect();
+        assert_eq!(kept, vec![1, 2]);
+
+        // No filter keeps everything, including unassigned PRs
+        assert!(prs.iter().all(|pr| pr_matches_assignees(pr, &[])));
+    }
+
+    /// Serves the labels of the PRs it knows; the others are gone. Rate
+    /// limited, it refuses everything.
+    struct MockDetails {
+        labels: HashMap<u64, Vec<String>>,
+        rate_limited: bool,
+    }
+
+    #[async_trait]
+    impl super::PrDetails for MockDetails {
+        async fn pr_labels(&self, _: &RepoSlug, pr_number: u64) -> Result<Vec<String>> {
+            if self.rate_limited {
+                return Err(GitHubError::RateLimited { reset: None });
+            }
+            self.labels.get(&pr_number).cloned().ok_or_else(|| GitHubError::NotFound {
+                what: format!("GET labels of PR #{}", pr_number),
+            })
+        }
+
+        async fn pr_commits(&self, _: &RepoSlug, _: u64) -> Result<Vec<CommitInfo>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn a_pr_whose_details_fail_is_skipped_with_a_warning() {
+        let cfg = test_config_with("DEV", "pending cherrypick", r"S\d+");
+        let filter = PrFilter::from_config(&cfg);
+        let sprint_regex = Regex::new(&filter.tags.sprint_pattern).unwrap();
+        let repo = RepoSlug::from_config(&cfg);
+        let matching: Vec<String> = vec!["S1".into(), "DEV".into(), "pending cherrypick".into()];
+        let mut source = MockDetails {
+            labels: HashMap::from([(1, matching.clone()), (3, matching), (4, vec!["S1".into()])]),
+            rate_limited: false,
+        };
+        let candidates = || (1..=4).map(|n| pr_assigned_to(n, &[])).collect::<Vec<_>>();
+
+        let mut listing = Listing::default();
+        evaluate_prs(&source, &repo, &filter, &sprint_regex, candidates(), &mut listing)
+            .await
+            .unwrap();
+        let numbers: Vec<u64> = listing.prs.iter().map(|pr| pr.number).collect();
+        assert_eq!(numbers, vec![1, 3]);
+        assert_eq!(listing.warnings.len(), 1);
+        assert!(listing.warnings[0].contains("PR #2"), "unexpected warning: {}", listing.warnings[0]);
+
+        // A failure every PR would hit ends the listing instead
+        source.rate_limited = true;
+        let mut listing = Listing::default();
+        let err = evaluate_prs(&source, &repo, &filter, &sprint_regex, candidates(), &mut listing)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GitHubError::RateLimited { .. }), "got {:?}", err);
+        assert!(listing.warnings.is_empty());
+    }
+
+    fn picked_as(source_sha: &str, new_sha: &str) -> CommitPickOutcome {
+        CommitPickOutcome::from(&PickedCommit {
+            source_sha: source_sha.into(),
+            new_sha: new_sha.into(),
+        })
+    }
+
+    #[test]
+    fn comment_body_notes_custom_order() {
+        let picked = vec![picked_as("bbbbbbbbbb", "1111111111"), picked_as("aaaaaaaaaa", "2222222222")];
+        assert_eq!(
+            cherry_pick_comment_body("release", &picked, false, None, None),
+            "🍒 **Cherry-picked to `release`**\n\nCommits:\n- bbbbbbbb → 11111111\n- aaaaaaaa → 22222222"
+        );
+        assert!(cherry_pick_comment_body("release", &picked, true, None, None)
+            .contains("Commits (applied in a custom order):\n- bbbbbbbb → 11111111\n- aaaaaaaa → 22222222"));
+    }
+
+    #[test]
+    fn comment_body_ends_with_the_footer() {
+        let picked = vec![picked_as("aaaaaaaaaa", "1111111111")];
+        assert!(cherry_pick_comment_body("release", &picked, false, None, Some("<sub>— gh_cherry v0.3.1</sub>"))
+            .ends_with("- aaaaaaaa → 11111111\n\n<sub>— gh_cherry v0.3.1</sub>"));
+    }
+
+    #[test]
+    fn comment_body_carries_the_note_before_the_footer() {
+        let picked = vec![picked_as("aaaaaaaaaa", "1111111111")];
+        let body = cherry_pick_comment_body(
+            "release",
+            &picked,
+            false,
+            Some("needs DB migration first"),
+            Some("<sub>— gh_cherry v0.3.1</sub>"),
+        );
+        assert!(body.ends_with(
+            "- aaaaaaaa → 11111111\n\n📝 **Note:** needs DB migration first\n\n<sub>— gh_cherry v0.3.1</sub>"
+        ));
+    }
+
+    #[test]
+    fn review_request_skips_self_review() {
+        let req = backport_review_request("alice", "Alice", None);
+        assert!(req.is_empty());
+
+        let req = backport_review_request("bob", "alice", Some("org/release-captains"));
+        assert_eq!(req.reviewers, vec!["bob"]);
+        assert_eq!(req.team_reviewers, vec!["release-captains"]);
+
+        let req = backport_review_request("alice", "alice", Some("captains"));
+        assert!(req.reviewers.is_empty());
+        assert_eq!(req.team_reviewers, vec!["captains"]);
+    }
+
+    #[derive(Default)]
+    struct MockOps {
+        fail_reviewers: bool,
+        review_requests: std::sync::Mutex<Vec<(u64, ReviewRequest)>>,
+        comments: std::sync::Mutex<Vec<IssueComment>>,
+        pr_bodies: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl GitHubOps for MockOps {
+        async fn update_pr_labels(&self, _pr_number: u64) -> Result<()> {
+            Ok(())
+        }
+        async fn add_cherry_pick_comment(
+            &self,
+            _: u64,
+            _: &str,
+            _: &[CommitPickOutcome],
+            _: bool,
+            _: Option<&str>,
+            _: Option<&str>,
+        ) -> Result<()> {
+            Ok(())
+        }
+        async fn create_cherry_pick_pr(&self, _: &str, _: &str, _: &str, body: &str) -> Result<u64> {
+            self.pr_bodies.lock().unwrap().push(body.to_string());
+            Ok(42)
+        }
+        async fn request_reviewers(&self, pr_number: u64, request: &ReviewRequest) -> Result<()> {
+            if self.fail_reviewers {
+                return Err(GitHubError::InvalidInput {
+                    reason: "422 Unprocessable Entity".into(),
+                });
+            }
+            self.review_requests
+                .lock()
+                .unwrap()
+                .push((pr_number, request.clone()));
+            Ok(())
+        }
+        async fn list_pr_comments(&self, _: u64) -> Result<Vec<IssueComment>> {
+            Ok(self.comments.lock().unwrap().clone())
+        }
+        async fn create_comment(&self, _: u64, body: &str) -> Result<()> {
+            let mut comments = self.comments.lock().unwrap();
+            let id = comments.len() as u64 + 1;
+            comments.push(IssueComment { id, body: body.into() });
+            Ok(())
+        }
+        async fn update_comment(&self, comment_id: u64, body: &str) -> Result<()> {
+            let mut comments = self.comments.lock().unwrap();
+            let comment = comments.iter_mut().find(|c| c.id == comment_id).unwrap();
+            comment.body = body.into();
+            Ok(())
+        }
+        async fn pr_for_commit(&self, _: &str) -> Result<Option<u64>> {
+            Ok(None)
+        }
+        async fn pr_overview(&self, pr_number: u64) -> Result<PrOverview> {
+            Err(GitHubError::NotFound {
+                what: format!("PR #{}", pr_number),
+            })
+        }
+        async fn search_labelled_prs(&self, _: &str, _: &str) -> Result<Vec<LabelledPr>> {
+            Ok(Vec::new())
+        }
+        async fn add_pr_labels(&self, _: &RepoSlug, _: u64, _: &[String]) -> Result<()> {
+            Ok(())
+        }
+        async fn remove_pr_label(&self, _: &RepoSlug, _: u64, _: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn deferral_comment_states_branch_reason_and_actor() {
+        let conflicts = DeferralReason::Conflicts {
+            files: vec!["src/a.rs".into(), "b.txt".into()],
+        };
+        assert_eq!(
+            deferral_comment_body("release/1.4", &conflicts, "alice"),
+            "<!-- gh_cherry:deferred:release/1.4 -->\n⏸️ **Cherry-pick to `release/1.4` deferred**\n\n\
+             Reason: conflicts in `src/a.rs`, `b.txt`\nRun by: @alice"
+        );
+        assert!(deferral_comment_body("release/1.4", &DeferralReason::Skipped, "bob")
+            .contains("Reason: skipped manually\nRun by: @bob"));
+    }
+
+    #[test]
+    fn marked_comment_is_edited_instead_of_duplicated() {
+        let marker = deferral_marker("release");
+        let comments = vec![
+            IssueComment { id: 1, body: "LGTM".into() },
+            IssueComment { id: 2, body: format!("{}\nold", marker) },
+            IssueComment { id: 3, body: deferral_marker("release/2.0") },
+        ];
+        assert_eq!(marked_comment_action(&comments, &marker), CommentAction::Update(2));
+        assert_eq!(marked_comment_action(&comments[..1], &marker), CommentAction::Create);
+    }
+
+    #[tokio::test]
+    async fn repeated_deferrals_keep_one_comment() {
+        let ops = MockOps::default();
+        let marker = deferral_marker("release");
+        let first = deferral_comment_body("release", &DeferralReason::Skipped, "alice");
+        let second = deferral_comment_body(
+            "release",
+            &DeferralReason::Conflicts { files: vec!["a.txt".into()] },
+            "alice",
+        );
+
+        let action = upsert_marked_comment(&ops, 7, &marker, &first).await.unwrap();
+        assert_eq!(action, CommentAction::Create);
+        let action = upsert_marked_comment(&ops, 7, &marker, &second).await.unwrap();
+        assert_eq!(action, CommentAction::Update(1));
+
+        let comments = ops.comments.lock().unwrap();
+        assert_eq!(comments.len(), 1);
+        assert!(comments[0].body.contains("conflicts in `a.txt`"));
+    }
+
+    #[tokio::test]
+    async fn open_backport_pr_requests_author_review() {
+        let ops = MockOps::default();
+        let pr = open_backport_pr(&ops, "cp/X-1", "release", "t", "b", "bob", "alice", None, &CodeOwnerTeams::default())
+            .await
+            .unwrap();
+        assert_eq!(pr, BackportPr { number: 42, review_error: None });
+        let calls = ops.review_requests.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].1.reviewers, vec!["bob"]);
+    }
+
+    #[tokio::test]
+    async fn open_backport_pr_skips_request_for_own_pr() {
+        let ops = MockOps::default();
+        let pr = open_backport_pr(&ops, "cp/X-1", "release", "t", "b", "alice", "alice", None, &CodeOwnerTeams::default())
+            .await
+            .unwrap();
+        assert!(pr.review_error.is_none());
+        assert!(ops.review_requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn open_backport_pr_lists_code_owners_and_requests_them_when_asked() {
+        let teams = vec!["@acme/core".to_string(), "@acme/web".to_string()];
+        let ops = MockOps::default();
+        let listed_only = CodeOwnerTeams {
+            teams: teams.clone(),
+            request_review: false,
+        };
+        open_backport_pr(&ops, "cp/X-1", "release", "t", "Backport of #7", "bob", "alice", Some("acme/core"), &listed_only)
+            .await
+            .unwrap();
+        assert_eq!(
+            ops.pr_bodies.lock().unwrap()[0],
+            "Backport of #7\n\n**Code owners on `release`:** @acme/core, @acme/web"
+        );
+        assert_eq!(ops.review_requests.lock().unwrap()[0].1.team_reviewers, ["core"]);
+
+        let ops = MockOps::default();
+        let requested = CodeOwnerTeams {
+            teams,
+            request_review: true,
+        };
+        open_backport_pr(&ops, "cp/X-1", "release", "t", "", "bob", "alice", Some("acme/core"), &requested)
+            .await
+            .unwrap();
+        assert_eq!(ops.pr_bodies.lock().unwrap()[0], "**Code owners on `release`:** @acme/core, @acme/web");
+        assert_eq!(ops.review_requests.lock().unwrap()[0].1.team_reviewers, ["core", "web"]);
+    }
+
+    #[tokio::test]
+    async fn reviewer_failure_downgrades_to_warning() {
+        let ops = MockOps {
+            fail_reviewers: true,
+            ..Default::default()
+        };
+        let pr = open_backport_pr(&ops, "cp/X-1", "release", "t", "b", "bob", "alice", None, &CodeOwnerTeams::default())
+            .await
+            .unwrap();
+        assert_eq!(pr.number, 42);
+        assert!(pr.review_error.unwrap().contains("422"));
+    }
+
+    #[test]
+    fn rate_limit_responses_become_rate_limited() {
+        assert!(matches!(
+            classify_status(403, "API rate limit exceeded for user ID 1.", "Failed to fetch PR labels"),
+            Some(GitHubError::RateLimited { reset: None })
+        ));
+        assert!(matches!(
+            classify_status(429, "Too Many Requests", "Failed to fetch PR labels"),
+            Some(GitHubError::RateLimited { .. })
+        ));
+        // A plain permission error is not a rate limit
+        assert!(classify_status(403, "Resource not accessible by integration", "x").is_none());
+
+        let reset = DateTime::from_timestamp(1_700_000_000, 0);
+        let message = GitHubError::RateLimited { reset }.to_string();
+        assert!(message.contains("resets at 22:13:20 UTC"), "{}", message);
+    }
+
+    #[test]
+    fn rate_limit_retries_wait_for_the_reset_or_back_off() {
+        let now = Utc::now();
+        let soon = now + chrono::Duration::seconds(30);
+        assert_eq!(retry_wait(Some(soon), now, 0), Some(Duration::from_secs(30)));
+        // A reset that has passed or isn't known backs off, doubling each time
+        assert_eq!(retry_wait(Some(now), now, 0), Some(Duration::from_secs(60)));
+        assert_eq!(retry_wait(None, now, 2), Some(Duration::from_secs(240)));
+        // Waits past the cap aren't taken
+        assert_eq!(retry_wait(None, now, 3), None);
+        assert_eq!(retry_wait(Some(now + chrono::Duration::hours(1)), now, 0), None);
+    }
+
+    #[test]
+    fn network_backoff_doubles_with_jitter() {
+        let backoff = Backoff {
+            base: NETWORK_RETRY_BASE,
+        };
+        assert_eq!(backoff.wait(0, 0.0), Duration::from_millis(500));
+        assert_eq!(backoff.wait(2, 0.0), Duration::from_secs(2));
+        assert_eq!(backoff.wait(1, 0.5), Duration::from_millis(1500));
+        assert_eq!(backoff.wait(0, 7.0), Duration::from_secs(1));
+        let random = jitter();
+        assert!((0.0..1.0).contains(&random), "jitter out of range: {}", random);
+    }
+
+    #[test]
+    fn sso_protected_organizations_are_classified() {
+        let body: serde_json::Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/github/sso_required.json")).unwrap();
+        assert!(matches!(
+            classify_status(403, body["message"].as_str().unwrap(), "GET PR #7 in acme/widgets"),
+            Some(GitHubError::SsoRequired { org: None, url: None })
+        ));
+
+        let url = "https://github.com/orgs/acme/sso?authorization_request=A1B2C3";
+        assert_eq!(sso_header_url(&format!("required; url={}", url)).as_deref(), Some(url));
+        assert_eq!(sso_header_url("partial-results; organizations=21955855,20582480"), None);
+        assert_eq!(sso_org(url).as_deref(), Some("acme"));
+        assert_eq!(sso_org("https://github.com/settings/tokens"), None);
+
+        let message = GitHubError::SsoRequired {
+            org: Some("acme".into()),
+            url: Some(url.into()),
+        }
+        .to_string();
+        assert_eq!(message, format!("Your token needs SSO authorization for acme — visit {}", url));
+    }
+
+    #[test]
+    fn auth_and_missing_resources_are_classified() {
+        assert!(matches!(
+            classify_status(401, "Bad credentials", "x"),
+            Some(GitHubError::Unauthorized { .. })
+        ));
+        match classify_status(404, "Not Found", "Repository acme/widgets") {
+            Some(GitHubError::NotFound { what }) => assert_eq!(what, "Repository acme/widgets"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(classify_status(500, "Server Error", "x").is_none());
+    }
+
+    #[test]
+    fn at_me_resolves_to_authenticated_login() {
+        let resolved = resolve_assignee_aliases(
+            &["@me".to_string(), "@bob".to_string(), "carol".to_string()],
+            "alice",
+        );
+        assert_eq!(resolved, vec!["alice", "bob", "carol"]);
+    }
+
+    fn repo(owner: &str, name: &str) -> RepositoryInfo {
+        RepositoryInfo {
+            name: name.into(),
+            full_name: format!("{}/{}", owner, name),
+            owner: owner.into(),
+            description: String::new(),
+            default_branch: "main".into(),
+            private: false,
+            fork: false,
+            stargazers_count: 0,
+            forks_count: 0,
+            language: None,
+            parent_full_name: None,
+        }
+    }
+
+    fn fork(owner: &str, name: &str, parent: Option<&str>) -> RepositoryInfo {
+        RepositoryInfo {
+            fork: true,
+            parent_full_name: parent.map(String::from),
+            ..repo(owner, name)
+        }
+    }
+
+    /// Serves `pages` pages of `per_page` repos each and records every request.
+    #[derive(Default)]
+    struct MockRepos {
+        pages: u32,
+        per_page: usize,
+        page_requests: std::sync::Mutex<Vec<u32>>,
+        lookups: std::sync::Mutex<Vec<(String, String)>>,
+        user_requests: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl RepoDiscovery for MockRepos {
+        async fn repo_page(&self, page: u32) -> Result<RepoPage> {
+            self.page_requests.lock().unwrap().push(page);
+            let repos = (0..self.per_page)
+                .map(|i| repo("acme", &format!("repo-{}-{}", page, i)))
+                .collect();
+            Ok(RepoPage { repos, has_next: page < self.pages })
+        }
+        async fn find_repository(&self, owner: &str, name: &str) -> Result<RepositoryInfo> {
+            self.lookups.lock().unwrap().push((owner.into(), name.into()));
+            if name == "missing" {
+                return Err(GitHubError::NotFound {
+                    what: format!("Repository {}/{}", owner, name),
+                });
+            }
+            if name.starts_with("fork-") {
+                return Ok(fork(owner, name, Some("upstream-org/widgets")));
+            }
+            Ok(repo(owner, name))
+        }
+        async fn authenticated_user(&self) -> Result<UserInfo> {
+            self.user_requests.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(UserInfo { login: "me".into(), name: String::new(), email: String::new() })
+        }
+    }
+
+    #[tokio::test]
+    async fn discovery_fetches_every_page_without_limit() {
+        let source = MockRepos { pages: 3, per_page: 5, ..Default::default() };
+        let repos = discover_repositories(&source, None).await.unwrap();
+        assert_eq!(repos.len(), 15);
+        assert_eq!(*source.page_requests.lock().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn discovery_stops_paginating_at_limit() {
+        let source = MockRepos { pages: 10, per_page: 5, ..Default::default() };
+        let repos = discover_repositories(&source, Some(7)).await.unwrap();
+        assert_eq!(repos.len(), 7);
+        assert_eq!(*source.page_requests.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn discovery_looks_up_the_user_only_when_it_needs_them() {
+        let source = MockRepos::default();
+        let users = || source.user_requests.load(std::sync::atomic::Ordering::SeqCst);
+        let github = |owner: &str, repo: &str| crate::config::GitHubConfig {
+            owner: owner.into(),
+            repo: repo.into(),
+            ..Default::default()
+        };
+
+        // A complete config makes no discovery request at all, even with --create-fork
+        for create_fork in [false, true] {
+            assert!(discovery_user(&source, &github("acme", "app"), create_fork).await.unwrap().is_none());
+        }
+        assert!(discovery_user(&source, &github("acme", ""), false).await.unwrap().is_none());
+        assert_eq!(users(), 0);
+        assert!(source.page_requests.lock().unwrap().is_empty());
+        assert!(source.lookups.lock().unwrap().is_empty());
+
+        let user = discovery_user(&source, &github("", ""), false).await.unwrap();
+        assert_eq!(user.map(|u| u.login).as_deref(), Some("me"));
+        assert!(discovery_user(&source, &github("acme", ""), true).await.unwrap().is_some());
+        assert_eq!(users(), 2);
+    }
+
+    #[tokio::test]
+    async fn exact_repo_resolves_with_single_lookup() {
+        let source = MockRepos::default();
+
+        let found = resolve_exact_repo(&source, "acme", " widgets ").await.unwrap();
+        assert_eq!(found.full_name, "acme/widgets");
+
+        let found = resolve_exact_repo(&source, "acme", "other-org/gadgets").await.unwrap();
+        assert_eq!(found.owner, "other-org");
+
+        assert_eq!(
+            *source.lookups.lock().unwrap(),
+            vec![
+                ("acme".to_string(), "widgets".to_string()),
+                ("other-org".to_string(), "gadgets".to_string())
+            ]
+        );
+        assert!(source.page_requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn exact_repo_rejects_bad_input_and_missing_repos() {
+        let source = MockRepos::default();
+        assert!(resolve_exact_repo(&source, "acme", "").await.is_err());
+        assert!(resolve_exact_repo(&source, "acme", "acme/").await.is_err());
+        assert!(source.lookups.lock().unwrap().is_empty());
+
+        let err = resolve_exact_repo(&source, "acme", "missing").await.unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn fork_parent_looks_up_only_when_listing_omitted_it() {
+        let source = MockRepos::default();
+
+        assert_eq!(fork_parent(&source, &repo("me", "plain")).await.unwrap(), None);
+
+        let known = fork("me", "widgets", Some("acme/widgets"));
+        assert_eq!(
+            fork_parent(&source, &known).await.unwrap().as_deref(),
+            Some("acme/widgets")
+        );
+        assert!(source.lookups.lock().unwrap().is_empty());
+
+        let listed = fork("me", "fork-widgets", None);
+        assert_eq!(
+            fork_parent(&source, &listed).await.unwrap().as_deref(),
+            Some("upstream-org/widgets")
+        );
+        assert_eq!(
+            *source.lookups.lock().unwrap(),
+            vec![("me".to_string(), "fork-widgets".to_string())]
+        );
+    }
+
+    #[test]
+    fn retarget_switches_owner_and_repo_only_for_parent() {
+        let mut github = Config::default().github;
+        github.owner = "me".into();
+        github.repo = "widgets".into();
+
+        retarget_pr_discovery(&mut github, "acme/widgets", PrTarget::Fork).unwrap();
+        assert_eq!((github.owner.as_str(), github.repo.as_str()), ("me", "widgets"));
+
+        retarget_pr_discovery(&mut github, "acme/widgets-upstream", PrTarget::Parent).unwrap();
+        assert_eq!(
+            (github.owner.as_str(), github.repo.as_str()),
+            ("acme", "widgets-upstream")
+        );
+
+        assert!(retarget_pr_discovery(&mut github, "no-slash", PrTarget::Parent).is_err());
+    }
+
+    #[test]
+    fn search_queries_require_the_labels_the_server_can_match() {
+        let repo = RepoSlug::new("acme", "widgets");
+        let since = Utc.with_ymd_and_hms(2025, 3, 4, 12, 0, 0).unwrap();
+        let mut config = Config::default();
+        config.github.base_branch = "main".into();
+        config.tags.pending_tag = "pending cherrypick".into();
+        config.tags.environment = "DEV".into();
+        config.tags.sprint_pattern = r"S\d+".into();
+        let query = |config: &Config| pr_search_query(&repo, &PrFilter::from_config(config), since);
+
+        assert_eq!(
+            query(&config).as_deref(),
+            Some(r#"repo:acme/widgets is:pr base:main label:"pending cherrypick" label:"DEV" updated:>=2025-03-04"#)
+        );
+        config.tags.sprint_pattern = "^S12$".into();
+        assert!(query(&config).unwrap().contains(r#"label:"DEV" label:"S12" updated"#));
+        config.tags.sprint_pattern = "S12".into();
+        assert!(!query(&config).unwrap().contains("S12"));
+
+        // Either mode needs the pending and environment labels
+        config.tags.sprint_filter_mode = LabelFilterMode::Any;
+        assert!(query(&config).unwrap().contains(r#"label:"pending cherrypick" label:"DEV""#));
+        config.tags.sprint_filter_mode = LabelFilterMode::All;
+        config.tags.environment = "say \"hi\"".into();
+        assert_eq!(query(&config), None);
+        config.tags.environment = "DEV".into();
+        let unlabelled = PrFilter::from_config(&config).without_labels();
+        assert_eq!(pr_search_query(&repo, &unlabelled, since), None);
+    }
+
+    #[test]
+    fn criteria_mismatches_lists_every_failed_filter() {
+        let mut cfg = test_config_with("DEV", "pending cherrypick", r"S\d+");
+        cfg.ui.assignees = vec!["bob".into()];
+        let re = Regex::new(&cfg.tags.sprint_pattern).unwrap();
+        let now = Utc::now();
+
+        let mut pr = pr_assigned_to(1, &["bob"]);
+        pr.labels = vec!["S1".into(), "DEV".into(), "pending cherrypick".into()];
+        pr.updated_at = now - chrono::Duration::days(1);
+        assert!(criteria_mismatches(&cfg, &pr, &re, now).is_empty());
+
+        pr.labels = vec!["S1".into()];
+        pr.updated_at = now - chrono::Duration::days(30);
+        pr.base_ref = "develop".into();
+        pr.assignees = vec![];
+        assert_eq!(
+            criteria_mismatches(&cfg, &pr, &re, now),
+            vec![
+                CriteriaMismatch::MissingPendingTag { tag: "pending cherrypick".into() },
+                CriteriaMismatch::MissingEnvironmentTag { tag: "DEV".into() },
+                CriteriaMismatch::OutsideWindow { days_back: 7, updated_at: pr.updated_at },
+                CriteriaMismatch::DifferentBase { expected: "main".into(), actual: "develop".into() },
+                CriteriaMismatch::NotAssigned { assignees: vec!["bob".into()] },
+            ]
+        );
+    }
+
+    #[test]
+    fn criteria_mismatches_any_mode_still_needs_pending_and_environment() {
+        let mut cfg = test_config_with("DEV", "pending cherrypick", r"S\d+");
+        cfg.tags.sprint_filter_mode = LabelFilterMode::Any;
+        let re = Regex::new(&cfg.tags.sprint_pattern).unwrap();
+        let now = Utc::now();
+
+        let mut pr = pr_assigned_to(1, &[]);
+        pr.updated_at = now;
+        pr.labels = vec!["S1".into(), "DEV".into(), "pending cherrypick".into()];
+        assert!(criteria_mismatches(&cfg, &pr, &re, now).is_empty());
+
+        pr.labels = vec!["DEV".into()];
+        assert_eq!(
+            criteria_mismatches(&cfg, &pr, &re, now),
+            vec![
+                CriteriaMismatch::MissingPendingTag { tag: "pending cherrypick".into() },
+                CriteriaMismatch::MissingSprintTag { pattern: r"S\d+".into() },
+            ]
+        );
+    }
+
+    fn capabilities_from(payload: &str) -> Capabilities {
+        let permissions: RepoPermissions = serde_json::from_str(payload).unwrap();
+        Capabilities::from_permissions(&permissions)
+    }
+
+    #[test]
+    fn read_only_users_can_only_comment() {
+        let caps = capabilities_from(r#"{"admin":false,"push":false,"pull":true}"#);
+        assert_eq!(
+            caps,
+            Capabilities { can_push: false, can_label: false, can_comment: true }
+        );
+    }
+
+    #[test]
+    fn triage_allows_labels_but_not_pushing() {
+        let caps = capabilities_from(
+            r#"{"admin":false,"maintain":false,"push":false,"triage":true,"pull":true}"#,
+        );
+        assert!(caps.can_label && caps.can_comment && !caps.can_push);
+    }
+
+    #[test]
+    fn any_write_role_allows_everything() {
+        for payload in [
+            r#"{"push":true,"pull":true}"#,
+            r#"{"maintain":true,"push":false,"pull":true}"#,
+            r#"{"admin":true,"push":false,"pull":false}"#,
+        ] {
+            assert_eq!(capabilities_from(payload), Capabilities::default(), "{}", payload);
+        }
+    }
+
+    #[test]
+    fn empty_permissions_allow_nothing() {
+        let caps = capabilities_from("{}");
+        assert!(!caps.can_push && !caps.can_label && !caps.can_comment);
     }
 }
@@ -0,0 +1,278 @@
+//! `--no-mutate-github`: reads go to GitHub as usual, while label swaps,
+//! comments, PRs and review requests are only logged and kept for the summary.
+//! Independent of whether the git side of a pick runs.
+
+use std::fmt;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use super::{
+    cherry_pick_comment_body, ApiUsage, CommitPickOutcome, GitHubOps, IssueComment, LabelledPr, PrOverview, RepoSlug,
+    ReviewRequest, Result,
+};
+
+/// A write that would have been sent to GitHub
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulatedMutation {
+    Labels { pr_number: u64 },
+    AddLabels { repo: RepoSlug, pr_number: u64, labels: Vec<String> },
+    RemoveLabel { repo: RepoSlug, pr_number: u64, label: String },
+    Comment { pr_number: u64, body: String },
+    EditComment { comment_id: u64, body: String },
+    PullRequest { head: String, base: String, title: String },
+    Reviewers { pr_number: u64, request: ReviewRequest },
+}
+
+impl fmt::Display for SimulatedMutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulatedMutation::Labels { pr_number } => write!(f, "swap pick labels on PR #{}", pr_number),
+            SimulatedMutation::AddLabels { repo, pr_number, labels } => {
+                write!(f, "add labels {} to {}#{}", labels.join(", "), repo, pr_number)
+            }
+            SimulatedMutation::RemoveLabel { repo, pr_number, label } => {
+                write!(f, "remove label {} from {}#{}", label, repo, pr_number)
+            }
+            SimulatedMutation::Comment { pr_number, .. } => write!(f, "comment on PR #{}", pr_number),
+            SimulatedMutation::EditComment { comment_id, .. } => write!(f, "edit comment {}", comment_id),
+            SimulatedMutation::PullRequest { head, base, .. } => write!(f, "open PR {} → {}", head, base),
+            SimulatedMutation::Reviewers { pr_number, request } => write!(
+                f,
+                "request review on PR #{} from {}",
+                pr_number,
+                request
+                    .reviewers
+                    .iter()
+                    .chain(&request.team_reviewers)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Wraps `inner`, passing reads through and recording writes instead of
+/// sending them. Simulated writes always succeed; a simulated PR is number 0.
+pub struct SimulatedOps<O> {
+    inner: O,
+    recorded: Mutex<Vec<SimulatedMutation>>,
+}
+
+impl<O: GitHubOps> SimulatedOps<O> {
+    pub fn new(inner: O) -> Self {
+        Self {
+            inner,
+            recorded: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The writes recorded since the last call, oldest first
+    pub fn take_recorded(&self) -> Vec<SimulatedMutation> {
+        std::mem::take(&mut *self.recorded.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn record(&self, mutation: SimulatedMutation) {
+        tracing::info!("Simulated GitHub change: {}", mutation);
+        self.recorded
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(mutation);
+    }
+}
+
+#[async_trait]
+impl<O: GitHubOps> GitHubOps for SimulatedOps<O> {
+    async fn update_pr_labels(&self, pr_number: u64) -> Result<()> {
+        self.record(SimulatedMutation::Labels { pr_number });
+        Ok(())
+    }
+    async fn add_cherry_pick_comment(
+        &self,
+        pr_number: u64,
+        target_branch: &str,
+        outcomes: &[CommitPickOutcome],
+        reordered: bool,
+        note: Option<&str>,
+        footer: Option<&str>,
+    ) -> Result<()> {
+        let body = cherry_pick_comment_body(target_branch, outcomes, reordered, note, footer);
+        self.record(SimulatedMutation::Comment { pr_number, body });
+        Ok(())
+    }
+    async fn create_cherry_pick_pr(&self, head: &str, base: &str, title: &str, _body: &str) -> Result<u64> {
+        self.record(SimulatedMutation::PullRequest {
+            head: head.to_string(),
+            base: base.to_string(),
+            title: title.to_string(),
+        });
+        Ok(0)
+    }
+    async fn request_reviewers(&self, pr_number: u64, request: &ReviewRequest) -> Result<()> {
+        self.record(SimulatedMutation::Reviewers {
+            pr_number,
+            request: request.clone(),
+        });
+        Ok(())
+    }
+    async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<IssueComment>> {
+        self.inner.list_pr_comments(pr_number).await
+    }
+    async fn create_comment(&self, pr_number: u64, body: &str) -> Result<()> {
+        self.record(SimulatedMutation::Comment {
+            pr_number,
+            body: body.to_string(),
+        });
+        Ok(())
+    }
+    async fn update_comment(&self, comment_id: u64, body: &str) -> Result<()> {
+        self.record(SimulatedMutation::EditComment {
+            comment_id,
+            body: body.to_string(),
+        });
+        Ok(())
+    }
+    async fn pr_for_commit(&self, sha: &str) -> Result<Option<u64>> {
+        self.inner.pr_for_commit(sha).await
+    }
+    async fn pr_overview(&self, pr_number: u64) -> Result<PrOverview> {
+        self.inner.pr_overview(pr_number).await
+    }
+    async fn search_labelled_prs(&self, owner: &str, label: &str) -> Result<Vec<LabelledPr>> {
+        self.inner.search_labelled_prs(owner, label).await
+    }
+    async fn add_pr_labels(&self, repo: &RepoSlug, pr_number: u64, labels: &[String]) -> Result<()> {
+        self.record(SimulatedMutation::AddLabels {
+            repo: repo.clone(),
+            pr_number,
+            labels: labels.to_vec(),
+        });
+        Ok(())
+    }
+    async fn remove_pr_label(&self, repo: &RepoSlug, pr_number: u64, label: &str) -> Result<()> {
+        self.record(SimulatedMutation::RemoveLabel {
+            repo: repo.clone(),
+            pr_number,
+            label: label.to_string(),
+        });
+        Ok(())
+    }
+    fn api_usage(&self) -> ApiUsage {
+        self.inner.api_usage()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::github::{open_backport_pr, upsert_marked_comment, CodeOwnerTeams, CommentAction, GitHubError};
+
+    /// Counts every write that reaches it; reads serve one marked comment
+    #[derive(Default)]
+    struct CountingOps {
+        writes: AtomicUsize,
+    }
+
+    impl CountingOps {
+        fn write(&self) -> Result<()> {
+            self.writes.fetch_add(1, Ordering::SeqCst);
+            Err(GitHubError::InvalidInput {
+                reason: "writes must not reach the client".into(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl GitHubOps for CountingOps {
+        async fn update_pr_labels(&self, _: u64) -> Result<()> {
+            self.write()
+        }
+        async fn add_cherry_pick_comment(
+            &self,
+            _: u64,
+            _: &str,
+            _: &[CommitPickOutcome],
+            _: bool,
+            _: Option<&str>,
+            _: Option<&str>,
+        ) -> Result<()> {
+            self.write()
+        }
+        async fn create_cherry_pick_pr(&self, _: &str, _: &str, _: &str, _: &str) -> Result<u64> {
+            self.write().map(|_| 1)
+        }
+        async fn request_reviewers(&self, _: u64, _: &ReviewRequest) -> Result<()> {
+            self.write()
+        }
+        async fn list_pr_comments(&self, _: u64) -> Result<Vec<IssueComment>> {
+            Ok(vec![IssueComment {
+                id: 9,
+                body: "<!-- marker -->\nold".into(),
+            }])
+        }
+        async fn create_comment(&self, _: u64, _: &str) -> Result<()> {
+            self.write()
+        }
+        async fn update_comment(&self, _: u64, _: &str) -> Result<()> {
+            self.write()
+        }
+        async fn pr_for_commit(&self, _: &str) -> Result<Option<u64>> {
+            Ok(None)
+        }
+        async fn pr_overview(&self, _: u64) -> Result<PrOverview> {
+            Err(GitHubError::NotFound { what: "PR".into() })
+        }
+        async fn search_labelled_prs(&self, _: &str, _: &str) -> Result<Vec<LabelledPr>> {
+            Ok(Vec::new())
+        }
+        async fn add_pr_labels(&self, _: &RepoSlug, _: u64, _: &[String]) -> Result<()> {
+            self.write()
+        }
+        async fn remove_pr_label(&self, _: &RepoSlug, _: u64, _: &str) -> Result<()> {
+            self.write()
+        }
+    }
+
+    #[tokio::test]
+    async fn no_write_reaches_the_wrapped_client() {
+        let ops = SimulatedOps::new(CountingOps::default());
+        ops.update_pr_labels(7).await.unwrap();
+        ops.add_cherry_pick_comment(7, "release", &[], false, None, None).await.unwrap();
+        let action = upsert_marked_comment(&ops, 7, "<!-- marker -->", "new").await.unwrap();
+        assert_eq!(action, CommentAction::Update(9));
+        let pr = open_backport_pr(&ops, "pick/7", "release", "Backport #7", "", "bob", "alice", None, &CodeOwnerTeams::default())
+            .await
+            .unwrap();
+        assert_eq!(pr.review_error, None);
+        let repo = RepoSlug::new("acme", "app");
+        ops.add_pr_labels(&repo, 8, &["backport/pending".into()]).await.unwrap();
+        ops.remove_pr_label(&repo, 8, "pending cherrypick").await.unwrap();
+
+        assert_eq!(ops.inner.writes.load(Ordering::SeqCst), 0);
+        let recorded = ops.take_recorded();
+        let described: Vec<String> = recorded.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            described,
+            [
+                "swap pick labels on PR #7",
+                "comment on PR #7",
+                "edit comment 9",
+                "open PR pick/7 → release",
+                "request review on PR #0 from bob",
+                "add labels backport/pending to acme/app#8",
+                "remove label pending cherrypick from acme/app#8",
+            ]
+        );
+        assert_eq!(
+            recorded[2],
+            SimulatedMutation::EditComment {
+                comment_id: 9,
+                body: "new".into()
+            }
+        );
+        assert!(ops.take_recorded().is_empty());
+    }
+}
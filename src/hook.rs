@@ -0,0 +1,94 @@
+//! `post_pick_hook`: a command run in the working tree after each pick
+//! lands and before it's pushed or the PR is labelled, so a build or test
+//! run can catch a pick that applied cleanly but broke something.
+
+use std::path::Path;
+use std::process::{ExitStatus, Stdio};
+
+use anyhow::{Context, Result};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+/// What the hook is told about the pick, as `GH_CHERRY_*` environment variables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookContext {
+    pub pr_number: u64,
+    pub target_branch: String,
+    /// Commits the pick created, oldest first
+    pub new_shas: Vec<String>,
+}
+
+impl HookContext {
+    pub fn env(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("GH_CHERRY_PR_NUMBER", self.pr_number.to_string()),
+            ("GH_CHERRY_TARGET_BRANCH", self.target_branch.clone()),
+            ("GH_CHERRY_NEW_SHAS", self.new_shas.join(" ")),
+        ]
+    }
+}
+
+/// `command` as the platform's shell runs it
+fn shell_command(command: &str) -> Command {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command);
+    shell
+}
+
+/// Runs `command` in `dir`, handing each line it prints to `on_line` as it
+/// comes, stdout and stderr alike. Its stdin is closed, as the TUI owns the
+/// terminal.
+pub async fn run(command: &str, dir: &Path, context: &HookContext, mut on_line: impl FnMut(String)) -> Result<ExitStatus> {
+    let mut child = shell_command(command)
+        .current_dir(dir)
+        .envs(context.env())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start post-pick hook '{}'", command))?;
+    let mut stdout = child.stdout.take().map(|out| BufReader::new(out).lines());
+    let mut stderr = child.stderr.take().map(|err| BufReader::new(err).lines());
+
+    while stdout.is_some() || stderr.is_some() {
+        tokio::select! {
+            line = async { stdout.as_mut()?.next_line().await.transpose() }, if stdout.is_some() => match line {
+                Some(line) => on_line(line.context("Failed to read the hook's output")?),
+                None => stdout = None,
+            },
+            line = async { stderr.as_mut()?.next_line().await.transpose() }, if stderr.is_some() => match line {
+                Some(line) => on_line(line.context("Failed to read the hook's output")?),
+                None => stderr = None,
+            },
+        }
+    }
+
+    child
+        .wait()
+        .await
+        .with_context(|| format!("Failed to wait for post-pick hook '{}'", command))
+}
+
+/// Opens the user's shell in `dir` with the hook's environment, returning
+/// once it exits. The caller hands the terminal over first.
+pub fn open_shell(dir: &Path, context: &HookContext) -> Result<ExitStatus> {
+    let shell = if cfg!(windows) {
+        std::env::var("COMSPEC").unwrap_or_else(|_| "cmd".to_string())
+    } else {
+        std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string())
+    };
+    std::process::Command::new(&shell)
+        .current_dir(dir)
+        .envs(context.env())
+        .status()
+        .with_context(|| format!("Failed to open {}", shell))
+}
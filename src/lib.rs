@@ -1,6 +1,19 @@
 pub mod auth;
+pub mod codeowners;
 pub mod config;
+pub mod digest;
+pub mod error;
 pub mod git;
 pub mod github;
+pub mod hook;
+pub mod messages;
+pub mod outcome;
+pub mod pr_notes;
+pub mod run_info;
+pub mod session_log;
+pub mod stats;
+pub mod storage;
+pub mod target_history;
 pub mod ui;
 pub mod util;
+pub mod workflow;
@@ -1,42 +1,84 @@
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
 use anyhow::Result;
 use clap::Parser;
 
 mod auth;
+mod codeowners;
 mod config;
+mod digest;
+mod error;
 mod git;
 mod github;
+mod hook;
+mod messages;
+mod outcome;
+mod pr_notes;
+mod run_info;
+mod session_log;
+mod stats;
+mod storage;
+mod target_history;
 mod ui;
 mod util;
+mod workflow;
 
-use config::Config;
-use github::GitHubClient;
+use config::{Config, ConfigSource};
+use digest::DigestGrouping;
+use github::{CriteriaMismatch, GitHubClient, PrState, PrTarget};
 use ui::app::App;
 use ui::config_selector::ConfigSelectorApp;
-use ui::selector::SelectorApp;
+use messages::{t, tf};
+use outcome::{NothingMatched, Outcome};
+use util::PromptPolicy;
+use workflow::ExistingBranchPolicy;
+use ui::selector::{RepoSelection, SelectorApp};
 use ui::simple_input::SimpleInput;
 
 #[derive(Parser)]
-#[command(author, version, about = "A TUI application for cherry-picking GitHub PRs to target branches. Auto-discovers organizations and repositories when not specified.", long_about = None)]
+#[command(
+    author,
+    version,
+    about = "A TUI application for cherry-picking GitHub PRs to target branches. Auto-discovers organizations and repositories when not specified.",
+    long_about = None,
+    after_help = "Exit codes:\n  \
+        0  success\n  \
+        1  error\n  \
+        2  a cherry-pick was left with unresolved conflicts\n  \
+        3  authentication failed\n  \
+        4  nothing matched (no PRs or repositories)"
+)]
 struct Cli {
     /// GitHub repository owner (auto-discovered if not provided)
     #[arg(short, long)]
     owner: Option<String>,
 
-    /// GitHub repository name (auto-discovered if not provided)
+    /// GitHub repository name or owner/name (auto-discovered if not provided)
     #[arg(short, long)]
     repo: Option<String>,
 
-    /// Path to configuration file
+    /// Path to configuration file (default: $CHERRY_CONFIG, then the user config directory)
     #[arg(short, long)]
     config: Option<String>,
 
+    /// Local clone to pick in, whose cherry.env is read (default: the repository containing the current directory)
+    #[arg(long, value_name = "DIR")]
+    repo_path: Option<std::path::PathBuf>,
+
+    /// REST API root of a GitHub Enterprise Server, e.g. https://github.example.com/api/v3
+    #[arg(long, value_name = "URL")]
+    api_url: Option<String>,
+
     /// Base branch to cherry-pick from
     #[arg(short, long)]
     base_branch: Option<String>,
 
-    /// Target branch to cherry-pick to
-    #[arg(short, long)]
-    target_branch: Option<String>,
+    /// Target branch to cherry-pick to; repeat it to pick onto several in turn
+    #[arg(short, long, num_args = 1..)]
+    target_branch: Vec<String>,
 
     /// Number of days to look back for PRs
     #[arg(short, long)]
@@ -58,25 +100,228 @@ struct Cli {
     #[arg(long)]
     save_config: bool,
 
-    /// Skip interactive configuration loading prompt
+    /// Interactively create a cherry.env file for this project, then exit
+    #[arg(long)]
+    config_init: bool,
+
+    /// Print the final settings and where each came from, then exit
+    #[arg(long)]
+    show_config: bool,
+
+    /// Print the pending PRs counted by author, then exit
+    #[arg(long)]
+    stats: bool,
+
+    /// Print a commented config.toml with every setting at its default, then exit
+    #[arg(long)]
+    print_config_template: bool,
+
+    /// Check a config file and list its problems, then exit (1 if there are any)
+    #[arg(long, value_name = "PATH")]
+    validate_config: Option<std::path::PathBuf>,
+
+    /// Print a Markdown digest of what was cherry-picked to the target branch, then exit
+    #[arg(long)]
+    digest: bool,
+
+    /// Window the digest covers: a duration such as 7d, 2w or 12h, or a date (YYYY-MM-DD)
+    #[arg(long, value_name = "WINDOW", default_value = "7d", requires = "digest")]
+    since: String,
+
+    /// Group the digest by `day` or by `sprint` label
+    #[arg(long, value_name = "GROUPING", default_value = "day", requires = "digest")]
+    group_by: DigestGrouping,
+
+    /// Build the digest from commit trailers alone, without asking GitHub for PR details
+    #[arg(long, requires = "digest")]
+    no_api: bool,
+
+    /// Move every open PR in the owner's repositories from one label to another, then exit
+    #[arg(long, requires_all = ["from", "to"])]
+    migrate_labels: bool,
+
+    /// Label to migrate away from
+    #[arg(long, value_name = "LABEL", requires = "migrate_labels")]
+    from: Option<String>,
+
+    /// Label to migrate to
+    #[arg(long, value_name = "LABEL", requires = "migrate_labels")]
+    to: Option<String>,
+
+    /// Show the commits each pick would apply without running git or writing to GitHub (with --migrate-labels: list the PRs it would change)
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Squash each PR's commits into one cherry-picked commit
+    #[arg(long)]
+    squash: bool,
+
+    /// When the backport branch already exists: `reuse` it, `recreate` it from the source branch, or `fail` (default: ask)
+    #[arg(long, value_name = "POLICY")]
+    on_existing_branch: Option<ExistingBranchPolicy>,
+
+    /// Open this PR directly, bypassing the date/label filters
+    #[arg(long, value_name = "NUMBER")]
+    pr: Option<u64>,
+
+    /// Fork the auto-discovered repository into your account and use the fork
+    #[arg(long)]
+    create_fork: bool,
+
+    /// Never stop to ask during startup; use flags and defaults instead (implied when stdin is not a terminal)
     #[arg(long)]
     no_prompt: bool,
+
+    /// Only show PRs assigned to this login (repeatable, any match; `@me` = yourself)
+    #[arg(long = "assignee", value_name = "LOGIN")]
+    assignees: Vec<String>,
+
+    /// Delete all cached data and expired session logs, then exit
+    #[arg(long)]
+    clean_cache: bool,
+
+    /// Open the SSO authorization page in the browser when the token isn't authorized for the organization
+    #[arg(long)]
+    open_sso: bool,
+
+    /// Log label, comment and PR changes instead of sending them to GitHub (git still runs)
+    #[arg(long)]
+    no_mutate_github: bool,
+
+    /// Print extra diagnostics (e.g. files removed by the startup prune, GitHub requests per load)
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Only print errors; progress messages and info logs are suppressed
+    #[arg(short, long, conflicts_with = "verbose")]
+    quiet: bool,
+}
+
+/// Set by `--quiet`; checked by [`say`]
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Prints a progress/info line to stdout unless `--quiet` was given
+fn say(message: impl std::fmt::Display) {
+    if !QUIET.load(Ordering::Relaxed) {
+        println!("{}", message);
+    }
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt::init();
+async fn main() -> ExitCode {
+    let launched = Instant::now();
 
     // Parse command line arguments
     let cli = Cli::parse();
 
+    // Initialize tracing
+    if cli.quiet {
+        QUIET.store(true, Ordering::Relaxed);
+        tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::WARN)
+            .with_writer(std::io::stderr)
+            .init();
+    } else {
+        tracing_subscriber::fmt::init();
+    }
+
+    let open_sso = cli.open_sso;
+    let outcome = match run(cli, launched).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            if let Some(url) = sso_url(&e) {
+                offer_sso(url, open_sso);
+            }
+            Outcome::from_error(&e)
+        }
+    };
+    outcome.into()
+}
+
+/// `--validate-config`: every problem in the file, one per line on stderr
+fn validate_config_file(path: &std::path::Path) -> Result<Outcome> {
+    let errors = Config::check_file(path)?;
+    if errors.is_empty() {
+        say(tf("cli.config_valid", &[("path", &path.display())]));
+        return Ok(Outcome::Success);
+    }
+    eprintln!(
+        "{}",
+        tf("cli.config_invalid", &[("path", &path.display()), ("count", &errors.len())])
+    );
+    for error in &errors {
+        eprintln!("  {}", error);
+    }
+    Ok(Outcome::Error)
+}
+
+/// The SSO authorization page an error asks the user to visit, if any
+fn sso_url(err: &anyhow::Error) -> Option<&str> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<error::GitHubError>())
+        .and_then(|e| e.sso_url())
+}
+
+/// Opens the SSO page with `--open-sso`, otherwise says how to
+fn offer_sso(url: &str, open: bool) {
+    if !open {
+        eprintln!("{}", t("cli.sso_hint"));
+        return;
+    }
+    match util::open_in_browser(url) {
+        Ok(()) => eprintln!("{}", tf("status.sso_opened", &[("url", &url)])),
+        Err(e) => eprintln!("{}", tf("status.sso_open_failed", &[("url", &url), ("error", &e)])),
+    }
+}
+
+async fn run(cli: Cli, launched: Instant) -> Result<Outcome> {
+    if cli.clean_cache {
+        // Session logs are kept for as long as the config says, even here
+        let retention_days = Config::load(cli.config.as_deref(), cli.repo_path.as_deref())
+            .map(|config| config.storage.retention_days)
+            .unwrap_or_else(|_| config::StorageConfig::default().retention_days);
+        let removed = storage::clean_cache(retention_days)?;
+        report_removed(&removed, cli.verbose);
+        say(tf("cli.cache_cleaned", &[("count", &removed.len())]));
+        return Ok(Outcome::Success);
+    }
+
+    if cli.print_config_template {
+        print!("{}", config::TEMPLATE);
+        return Ok(Outcome::Success);
+    }
+
+    if let Some(path) = &cli.validate_config {
+        return validate_config_file(path);
+    }
+
+    if cli.config_init {
+        if Config::init_env_file(cli.config.as_deref(), cli.repo_path.as_deref())? {
+            say(t("cli.config_saved"));
+        }
+        return Ok(Outcome::Success);
+    }
+
+    // Every question below is skipped with a logged assumption unless this allows it
+    let prompts = PromptPolicy::detect(cli.no_prompt);
+    if prompts == PromptPolicy::NoTerminal {
+        tracing::warn!("stdin is not a terminal; not prompting and using defaults");
+    }
+
     // Load configuration with optional interactive prompt
-    let mut config = if cli.no_prompt {
-        Config::load(cli.config.as_deref())?
+    let mut config = if !prompts.can_ask() {
+        Config::load(cli.config.as_deref(), cli.repo_path.as_deref())?
     } else {
-        Config::load_with_prompt(cli.config.as_deref())?
+        Config::load_with_prompt(cli.config.as_deref(), cli.repo_path.as_deref())?
     };
+    messages::init(config.ui.language.as_deref());
+
+    // Drop stale caches/session logs; never fatal
+    match storage::prune_expired(config.storage.retention_days) {
+        Ok(removed) => report_removed(&removed, cli.verbose),
+        Err(e) => tracing::warn!("Failed to prune expired session data: {}", e),
+    }
 
     // Override config with CLI arguments
     config = config.with_overrides(
@@ -89,13 +334,61 @@ async fn main() -> Result<()> {
         cli.source_branch,
     );
 
-    // Handle task ID for branch naming
+    config = config.with_repo_path(cli.repo_path.clone()).with_api_url(cli.api_url);
+
+    if !cli.assignees.is_empty() {
+        config.ui.assignees = cli.assignees;
+        config.set_source("ui.assignees", ConfigSource::CommandLine);
+    }
+    if cli.squash {
+        config.github.squash_picks = true;
+        config.set_source("github.squash_picks", ConfigSource::CommandLine);
+    }
+
+    // Trailers alone need neither GitHub nor the repository's identity
+    if cli.digest && cli.no_api {
+        return run_digest(&config, &cli.since, cli.group_by, None).await;
+    }
+
+    // Authenticated once; re-aimed with `for_config` as the config changes
+    let mut github_client = GitHubClient::new(config.clone()).await?;
+
+    // Handle auto-discovery if needed; a complete config makes no discovery requests
+    let mut user = None;
+    if config.needs_auto_discovery() {
+        say(t("cli.discovering"));
+        (config, user) = handle_auto_discovery(config, &github_client, cli.create_fork, prompts).await?;
+        github_client = github_client.for_config(&config);
+    } else if cli.create_fork {
+        tracing::warn!("--create-fork only applies when the repository is auto-discovered");
+    }
+
+    if let (true, Some(from), Some(to)) = (cli.migrate_labels, &cli.from, &cli.to) {
+        return run_label_migration(&config, &github_client, from, to, cli.dry_run).await;
+    }
+
+    // The repository's own conventions, beneath cherry.env and the flags above
+    apply_repo_config(&mut config, &github_client).await?;
+    github_client = github_client.for_config(&config);
+
+    if cli.digest {
+        return run_digest(&config, &cli.since, cli.group_by, Some(&github_client)).await;
+    }
+
+    // Handle task ID for branch naming; after the repository config, which may set the template
     if let Some(task_id) = cli.task_id {
         // Replace {task_id} placeholder in branch name template
         config.github.branch_name_template = config
             .github
             .branch_name_template
             .replace("{task_id}", &task_id);
+    } else if !prompts.can_ask() {
+        if config.github.branch_name_template.contains("{task_id}") {
+            tracing::warn!(
+                "No --task-id given; keeping the branch name template {} as is",
+                config.github.branch_name_template
+            );
+        }
     } else {
         // If no task ID provided, prompt user for it
         if config.github.branch_name_template.contains("{task_id}") {
@@ -108,18 +401,32 @@ async fn main() -> Result<()> {
         }
     }
 
-    // Handle auto-discovery if needed
-    if config.needs_auto_discovery() {
-        println!("No owner/repo specified, discovering available options...");
-        config = handle_auto_discovery(config).await?;
+    // Resolve `@me` in the assignee filter to the authenticated login
+    if config
+        .ui
+        .assignees
+        .iter()
+        .any(|a| a.eq_ignore_ascii_case("@me"))
+    {
+        let me = match user.take() {
+            Some(user) => user,
+            None => github_client.get_authenticated_user().await?,
+        };
+        config.ui.assignees = github::resolve_assignee_aliases(&config.ui.assignees, &me.login);
+        user = Some(me);
     }
 
     // If source branch is default or not set, prompt user for customization via TUI input (no boxes)
-    if config.github.cherry_pick_source_branch == "master"
+    if !prompts.can_ask() {
+        tracing::info!(
+            "Not prompting for a source branch; using '{}'",
+            config.github.cherry_pick_source_branch
+        );
+    } else if config.github.cherry_pick_source_branch == "master"
         || config.github.cherry_pick_source_branch.is_empty()
     {
-        let title = "Source branch for cherry-pick";
-        let placeholder = "e.g., main or release/2025.08 (Enter to accept current)";
+        let title = t("cli.source_branch_title");
+        let placeholder = t("cli.source_branch_placeholder");
         if let Some(input) =
             SimpleInput::prompt(title, &config.github.cherry_pick_source_branch, placeholder)?
         {
@@ -131,27 +438,346 @@ async fn main() -> Result<()> {
 
     // Validate final configuration
     config.validate()?;
+    let github_client = github_client.for_config(&config);
+
+    if cli.show_config {
+        print!("{}", config.describe_sources());
+        return Ok(Outcome::Success);
+    }
+
+    if cli.stats {
+        return print_stats(&github_client).await;
+    }
 
     // Save config to cherry.env if requested
     if cli.save_config {
-        config.save_env_overrides()?;
-        println!("Configuration saved to cherry.env");
+        config.save_env_overrides(cli.repo_path.as_deref())?;
+        say(t("cli.config_saved"));
+    }
+
+    if let Some(number) = cli.pr {
+        // The notice waits for Enter, so it is skipped along with other output
+        if !cli.quiet {
+            report_pr_mismatches(&config, &github_client, number, prompts).await?;
+        }
     }
 
     // Create and run the TUI application
-    let mut app = App::new(config).await?;
-    app.run().await?;
+    let watch_env = config.ui.watch_env;
+    let mut app = App::new(config, github_client)?;
+    app.trace_first_frame(launched);
+    // Otherwise looked up in the background once the first frame is up
+    if let Some(user) = &user {
+        app.know_user(user);
+    }
+    if let Some(number) = cli.pr {
+        app.focus_pr(number);
+    }
+    if cli.dry_run {
+        app.dry_run();
+    } else if cli.no_mutate_github {
+        app.simulate_github_mutations();
+    }
+    if cli.open_sso {
+        app.open_sso_automatically();
+    }
+    if let Some(policy) = cli.on_existing_branch {
+        app.on_existing_branch(policy);
+    }
+    if cli.verbose {
+        app.report_api_usage();
+    }
+    if watch_env {
+        app.watch_env(cli.repo_path.clone());
+    }
+    let outcome = app.run().await;
+    if let Some(note) = app.exit_note() {
+        say(note);
+    }
+    say(tf("cli.api_summary", &[("usage", &app.api_usage())]));
+    // The runtime's shutdown would wait for the git step still running
+    if app.abandoned_pick() {
+        let outcome = outcome.as_ref().map_or(Outcome::Error, |outcome| *outcome);
+        std::process::exit(outcome.code().into());
+    }
+    outcome
+}
+
+/// Prints the Markdown digest of picks onto the target branch since `since`.
+/// Without `client` the PRs are named from the commit trailers only.
+async fn run_digest(
+    config: &Config,
+    since: &str,
+    grouping: DigestGrouping,
+    client: Option<&GitHubClient>,
+) -> Result<Outcome> {
+    let branch = &config.github.target_branch;
+    if branch.is_empty() {
+        anyhow::bail!(t("cli.digest_needs_target"));
+    }
+    let since = digest::parse_since(since, chrono::Utc::now())?;
+    let sprint_regex = regex::Regex::new(&config.tags.sprint_pattern)?;
+
+    let git_ops = git::GitOperations::open_configured(config.github.repo_path.as_deref())?;
+    let backports: Vec<_> = git_ops
+        .commits_since(branch, since)?
+        .iter()
+        .filter_map(digest::backport_from)
+        .collect();
+    let entries = match client {
+        Some(client) => digest::resolve(client, backports).await,
+        None => digest::offline_entries(backports),
+    };
+    print!("{}", digest::render_digest(branch, since, &entries, grouping, &sprint_regex));
+    Ok(Outcome::Success)
+}
+
+/// `--stats`: the PRs the TUI would list, counted by author
+async fn print_stats(client: &GitHubClient) -> Result<Outcome> {
+    let prs = client.list_matching_prs().await?;
+    if prs.is_empty() {
+        return Err(NothingMatched(t("stats.empty").to_string()).into());
+    }
+    for line in stats::stats_lines(&stats::author_stats(&prs), ui::components::stats_header()) {
+        println!("{}", line);
+    }
+    Ok(Outcome::Success)
+}
+
+/// `--migrate-labels`: moves the owner's open PRs from `from` to `to`,
+/// or with `dry_run` lists the PRs that would move
+async fn run_label_migration(
+    config: &Config,
+    client: &GitHubClient,
+    from: &str,
+    to: &str,
+    dry_run: bool,
+) -> Result<Outcome> {
+    let owner = &config.github.owner;
+    let prs = client.search_labelled_prs(owner, from).await?;
+    if prs.is_empty() {
+        say(tf("cli.migrate_none", &[("label", &from), ("owner", owner)]));
+        return Ok(Outcome::Success);
+    }
+    say(tf("cli.migrate_found", &[("count", &prs.len()), ("label", &from)]));
+    if dry_run {
+        for pr in &prs {
+            println!("{}#{}  {}", pr.repo, pr.number, pr.title);
+        }
+        return Ok(Outcome::Success);
+    }
 
+    let total = prs.len();
+    let report = github::migrate_labels(client, &prs, from, to, &github::Pacing::default(), |done, pr, error| {
+        let pr_ref = format!("{}#{}", pr.repo, pr.number);
+        match error {
+            None => say(tf("cli.migrate_progress", &[("done", &done), ("total", &total), ("pr", &pr_ref)])),
+            Some(e) => eprintln!("{}", tf("cli.migrate_failed", &[("pr", &pr_ref), ("error", e)])),
+        }
+    })
+    .await;
+
+    say(tf("cli.migrate_done", &[("migrated", &report.migrated), ("total", &total), ("label", &to)]));
+    if report.not_attempted > 0 {
+        eprintln!("{}", tf("cli.migrate_stopped", &[("count", &report.not_attempted)]));
+    }
+    let tags = &config.tags;
+    if tags.label_is(from, &tags.pending_tag) || tags.label_is(from, &tags.completed_tag) {
+        say(tf("cli.migrate_update_config", &[("from", &from), ("to", &to)]));
+    }
+    Ok(if report.failed.is_empty() && report.not_attempted == 0 {
+        Outcome::Success
+    } else {
+        Outcome::Error
+    })
+}
+
+/// Overlays `.github/gh_cherry.toml` or `cherry.env` from the repository's
+/// default branch. Failing to fetch them only costs the repository's defaults.
+async fn apply_repo_config(config: &mut Config, github_client: &GitHubClient) -> Result<()> {
+    let files = match github_client.get_repo_config_file().await {
+        Ok(files) => files,
+        Err(e) => {
+            tracing::warn!("Could not fetch the repository's config: {}", e);
+            return Ok(());
+        }
+    };
+    if files.is_empty() {
+        return Ok(());
+    }
+    let warnings = config.apply_repo_config(&files);
+    for warning in &warnings {
+        tracing::warn!("Repository config: {}", warning);
+    }
+    say(tf(
+        "cli.repo_config_applied",
+        &[("owner", &config.github.owner), ("repo", &config.github.repo)],
+    ));
+    Ok(())
+}
+
+/// Tells the user which configured filters an explicitly requested PR fails,
+/// and whether it isn't merged, then waits for them to confirm before
+/// continuing when they can be asked
+async fn report_pr_mismatches(
+    config: &Config,
+    client: &GitHubClient,
+    number: u64,
+    prompts: PromptPolicy,
+) -> Result<()> {
+    let pr = client.get_pr_in(&github::RepoSlug::pr_source(config), number).await?;
+    let sprint_regex = regex::Regex::new(&config.tags.sprint_pattern)?;
+
+    let mismatches = github::criteria_mismatches(config, &pr, &sprint_regex, chrono::Utc::now());
+    let unmerged = match pr.state {
+        PrState::Open => Some("cli.pr_open"),
+        PrState::Closed => Some("cli.pr_closed"),
+        PrState::Merged | PrState::Unknown => None,
+    };
+    if mismatches.is_empty() && unmerged.is_none() {
+        return Ok(());
+    }
+
+    // Warnings, so they reach the terminal even when stdout is redirected
+    if let Some(key) = unmerged {
+        eprintln!("{}", tf(key, &[("number", &number)]));
+    }
+    if !mismatches.is_empty() {
+        eprintln!("{}", tf("cli.pr_mismatch", &[("number", &number)]));
+        for mismatch in &mismatches {
+            eprintln!("  • {}", mismatch_message(mismatch));
+        }
+    }
+    if !prompts.can_ask() {
+        tracing::info!("Not waiting for confirmation; opening PR #{} anyway", number);
+        return Ok(());
+    }
+    say(t("cli.pr_mismatch_continue"));
+    use tokio::io::AsyncBufReadExt;
+    tokio::io::BufReader::new(tokio::io::stdin())
+        .read_line(&mut String::new())
+        .await?;
     Ok(())
 }
 
-async fn handle_auto_discovery(mut config: Config) -> Result<Config> {
-    // Create a temporary GitHub client for discovery
-    let github_client = GitHubClient::new(config.clone()).await?;
+fn mismatch_message(mismatch: &CriteriaMismatch) -> String {
+    match mismatch {
+        CriteriaMismatch::MissingPendingTag { tag } => {
+            tf("cli.mismatch_pending_tag", &[("tag", tag)])
+        }
+        CriteriaMismatch::MissingEnvironmentTag { tag } => {
+            tf("cli.mismatch_environment_tag", &[("tag", tag)])
+        }
+        CriteriaMismatch::MissingSprintTag { pattern } => {
+            tf("cli.mismatch_sprint_tag", &[("pattern", pattern)])
+        }
+        CriteriaMismatch::OutsideWindow { days_back, updated_at } => tf(
+            "cli.mismatch_window",
+            &[
+                ("updated", &updated_at.format("%Y-%m-%d")),
+                ("days", days_back),
+            ],
+        ),
+        CriteriaMismatch::DifferentBase { expected, actual } => tf(
+            "cli.mismatch_base",
+            &[("actual", actual), ("expected", expected)],
+        ),
+        CriteriaMismatch::NotAssigned { assignees } => {
+            tf("cli.mismatch_assignees", &[("assignees", &assignees.join(", "))])
+        }
+    }
+}
+
+fn report_removed(removed: &[std::path::PathBuf], verbose: bool) {
+    if verbose {
+        for path in removed {
+            say(tf("cli.removed_path", &[("path", &path.display())]));
+        }
+    }
+}
+
+/// Lists the owner's repositories and picks one: the only one there is, or
+/// the one chosen in the selector. Sets `config`'s owner and repo to it.
+async fn select_listed_repo(
+    config: &mut Config,
+    github_client: &GitHubClient,
+    prompts: PromptPolicy,
+) -> Result<github::RepositoryInfo> {
+    let repos = github_client.list_user_repositories().await?;
+
+    // Filter repos by owner and fork preference
+    let owner_repos: Vec<_> = repos
+        .iter()
+        .filter(|r| r.owner == config.github.owner && (!config.ui.only_forked_repos || r.fork))
+        .cloned()
+        .collect();
+
+    if owner_repos.is_empty() {
+        let mut message = tf("cli.no_repos", &[("owner", &config.github.owner)]);
+        if config.ui.only_forked_repos {
+            message.push_str(t("cli.no_repos_forks_only"));
+        }
+        if let Some(limit) = config.ui.discovery_repo_limit {
+            if repos.len() >= limit {
+                message.push_str(&tf("cli.no_repos_limit_hint", &[("limit", &limit)]));
+            }
+        }
+        return Err(NothingMatched(message).into());
+    }
+
+    Ok(if owner_repos.len() == 1 {
+        // Only one repo available
+        config.github.repo = owner_repos[0].name.clone();
+        say(tf("cli.using_repo", &[("repo", &config.github.repo)]));
+        owner_repos[0].clone()
+    } else if !prompts.can_ask() {
+        anyhow::bail!(tf("cli.repo_required", &[("owner", &config.github.owner)]));
+    } else {
+        // Multiple repos available - use TUI selector
+        say(t("cli.opening_repo_selector"));
+        // Like the organization details, searches run on this worker thread
+        let owner = config.github.owner.clone();
+        let only_forks = config.ui.only_forked_repos;
+        let mut search = |query: &str| {
+            let lookup = github_client.search_repositories(&owner, query);
+            match tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(lookup)) {
+                Ok(found) => found.into_iter().filter(|r| !only_forks || r.fork).collect(),
+                Err(e) => {
+                    tracing::debug!("Repository search for '{}' failed: {}", query, e);
+                    Vec::new()
+                }
+            }
+        };
+        let selected = match SelectorApp::run_repository_selector(&owner_repos, &mut search, config.ui.text_match())? {
+            RepoSelection::Listed(repo) => repo,
+            RepoSelection::Typed(input) => {
+                github::resolve_exact_repo(github_client, &config.github.owner, &input).await?
+            }
+        };
+        config.github.owner = selected.owner.clone();
+        config.github.repo = selected.name.clone();
+        say(tf(
+            "cli.selected_repo",
+            &[("owner", &config.github.owner), ("repo", &config.github.repo)],
+        ));
+        selected
+    })
+}
 
-    // Fetch user info for context
-    let user = github_client.get_authenticated_user().await?;
-    println!("Authenticated as: {} ({})", user.name, user.login);
+/// Fills in the missing owner and/or repo, returning the authenticated user
+/// when discovery had to look them up
+async fn handle_auto_discovery(
+    mut config: Config,
+    github_client: &GitHubClient,
+    create_fork: bool,
+    prompts: PromptPolicy,
+) -> Result<(Config, Option<github::UserInfo>)> {
+    // Only asked for when the owner or a fork depends on who we are
+    let user = github::discovery_user(github_client, &config.github, create_fork).await?;
+    if let Some(user) = &user {
+        say(tf("cli.authenticated", &[("name", &user.name), ("login", &user.login)]));
+    }
+    let login = user.as_ref().map_or("", |u| u.login.as_str());
 
     // If no owner specified, try to discover
     if config.github.owner.is_empty() {
@@ -159,49 +785,94 @@ async fn handle_auto_discovery(mut config: Config) -> Result<Config> {
 
         if orgs.is_empty() {
             // Only user account available
-            config.github.owner = user.login.clone();
-            println!("Using owner: {}", config.github.owner);
+            config.github.owner = login.to_string();
+            say(tf("cli.using_owner", &[("owner", &config.github.owner)]));
+        } else if !prompts.can_ask() {
+            anyhow::bail!(t("cli.owner_required"));
         } else {
+            let repo_counts = if config.ui.probe_orgs {
+                say(t("cli.probing_orgs"));
+                github::probe_org_repos(github_client, &orgs, github::ORG_PROBE_TIME_BOX).await
+            } else {
+                HashMap::new()
+            };
             // Multiple options available - use TUI selector
-            println!("Opening organization selector...");
-            config.github.owner = SelectorApp::run_organization_selector(&user.login, &orgs)?;
-            println!("Selected owner: {}", config.github.owner);
+            say(t("cli.opening_org_selector"));
+            // The selector is synchronous; fetch details on this worker thread as orgs are highlighted
+            let mut load_details = |login: &str| {
+                let lookup = github_client.get_organization_details(login);
+                tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(lookup))
+                    .map_err(|e| tracing::debug!("No details for organization {}: {}", login, e))
+                    .ok()
+            };
+            let text_match = config.ui.text_match();
+            config.github.owner =
+                SelectorApp::run_organization_selector(login, &orgs, &repo_counts, &mut load_details, text_match)?;
+            say(tf("cli.selected_owner", &[("owner", &config.github.owner)]));
         }
     }
 
-    // If no repo specified, try to find repos for the owner
+    // If no repo specified, ask for one or pick from the owner's repos
     if config.github.repo.is_empty() {
-        let repos = github_client.list_user_repositories().await?;
-
-        // Filter repos by owner and fork preference
-        let owner_repos: Vec<_> = repos
-            .iter()
-            .filter(|r| r.owner == config.github.owner && (!config.ui.only_forked_repos || r.fork))
-            .cloned()
-            .collect();
-
-        if owner_repos.is_empty() {
-            let filter_msg = if config.ui.only_forked_repos {
-                " (forked repositories only)"
+        // Someone who knows the name needn't wait for every repository to be listed
+        let typed = if prompts.can_ask() {
+            let title = tf("cli.repo_quick_entry", &[("owner", &config.github.owner)]);
+            SimpleInput::prompt(&title, "", t("cli.repo_quick_entry_placeholder"))?
+                .ok_or_else(|| anyhow::anyhow!("Selection cancelled"))?
+        } else {
+            String::new()
+        };
+        let selected = if typed.is_empty() {
+            select_listed_repo(&mut config, github_client, prompts).await?
+        } else {
+            let selected = github::resolve_exact_repo(github_client, &config.github.owner, &typed).await?;
+            config.github.owner = selected.owner.clone();
+            config.github.repo = selected.name.clone();
+            say(tf(
+                "cli.selected_repo",
+                &[("owner", &config.github.owner), ("repo", &config.github.repo)],
+            ));
+            selected
+        };
+
+        let selected = if create_fork && selected.owner != login {
+            say(tf("cli.forking", &[("repo", &selected.full_name)]));
+            let fork = github_client
+                .fork_repository(&selected.owner, &selected.name)
+                .await?;
+            config.github.owner = fork.owner.clone();
+            config.github.repo = fork.name.clone();
+            say(tf(
+                "cli.selected_repo",
+                &[("owner", &config.github.owner), ("repo", &config.github.repo)],
+            ));
+            fork
+        } else {
+            if create_fork {
+                say(tf("cli.fork_own_repo", &[("repo", &selected.full_name)]));
+            }
+            selected
+        };
+
+        // PRs for a fork usually live upstream; let the user pick which one to query
+        if let Some(parent) = github::fork_parent(github_client, &selected).await? {
+            say(tf("cli.fork_of", &[("repo", &selected.full_name), ("parent", &parent)]));
+            let target = if prompts.can_ask() {
+                SelectorApp::run_fork_target_selector(&selected.full_name, &parent)?
             } else {
-                ""
+                // The selector's default choice
+                tracing::info!("Not prompting; listing PRs from the parent {}", parent);
+                PrTarget::Parent
             };
-            anyhow::bail!(
-                "No repositories found for owner: {}{}",
-                config.github.owner,
-                filter_msg
-            );
-        } else if owner_repos.len() == 1 {
-            // Only one repo available
-            config.github.repo = owner_repos[0].name.clone();
-            println!("Using repository: {}", config.github.repo);
-        } else {
-            // Multiple repos available - use TUI selector
-            println!("Opening repository selector...");
-            config.github.repo = SelectorApp::run_repository_selector(&owner_repos)?;
-            println!("Selected repository: {}", config.github.repo);
+            github::retarget_pr_discovery(&mut config.github, &parent, target)?;
+            if target == PrTarget::Parent {
+                say(tf(
+                    "cli.listing_prs_from",
+                    &[("owner", &config.github.owner), ("repo", &config.github.repo)],
+                ));
+            }
         }
     }
 
-    Ok(config)
+    Ok((config, user))
 }
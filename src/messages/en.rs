@@ -0,0 +1,482 @@
+pub(super) static CATALOG: &[(&str, &str)] = &[
+    // Main menu
+    ("main_menu.title", "🍒 GitHub Cherry-Pick"),
+    ("main_menu.prompt", ">> Press Enter to view PRs  •  r: Refresh  •  t: Tags  •  h: History  •  b: Target branch  •  q: Quit"),
+    ("main_menu.hint", "Use numbers to select options, 'q' to quit"),
+    // PR list
+    ("pr_list.title", "📋 Pull Requests  —  showing {shown} of {total}"),
+    ("pr_list.title_short", "📋 PRs {shown}/{total}"),
+    ("pr_list.dry_run_badge", "[DRY RUN]"),
+    ("pr_list.prompt_hint", "f: Filter  •  Enter: Cherry-pick  •  Esc: Back"),
+    (
+        "pr_list.prompt_hint_filtered",
+        "f: Filter (active: '{query}')  •  Enter: Cherry-pick  •  Esc: Back",
+    ),
+    (
+        "pr_list.footer",
+        "↑/↓ Navigate  •  Enter Cherry-pick  •  d Details  •  s Skip  •  P Pick with options  •  Space Select  •  x Pick selected  •  n Note  •  # Jump to PR  •  r Refresh  •  f Filter  •  m/S/c/M Mine/Sprint/Clean/Merged  •  u By author  •  A Abort session  •  Esc Back  •  q Quit",
+    ),
+    ("pr_list.follow_up", "↳ follow-up of #{number} "),
+    ("pr_list.note", "        ✎ {note}"),
+    ("pr_list.auto_refresh", "↻ refresh in {secs}s"),
+    (
+        "pr_list.empty",
+        "No PRs found matching the criteria.\n\n\
+        📋 Search Criteria:\n\
+        • Repository: {owner}/{repo}\n\
+        • Base Branch: {base}\n\
+        • Environment: {environment}\n\
+        • Pending Tag: \"{pending}\"\n\
+        • Days Back: {days}\n\n\
+        💡 Tips:\n\
+        • Ensure PRs are tagged with \"{pending}\"\n\
+        • Check if PRs are merged to \"{base}\" branch\n\
+        • Verify the tag pattern matches: {pattern}\n\n\
+        🔄 Press 'r' to refresh, 'e' to edit the criteria, 'n' to list without the labels, or 'Esc' to go back.",
+    ),
+    ("pr_list.fetch_badge", "[!FETCH]"),
+    ("pr_list.fetch_hint", "F Fetch missing commits"),
+    ("pr_list.on_demand_badge", "[+]"),
+    ("pr_list.new_badge", "[new]"),
+    ("pr_list.updated_badge", "[updated]"),
+    ("pr_list.read_only_hint", "Enter pick (read-only: local only)"),
+    ("filter.title", "Filter PRs"),
+    (
+        "filter.hint",
+        "type to filter by #, title or author (Enter to apply, Esc to cancel)",
+    ),
+    // Quick filters
+    ("quick_filter.mine", "mine"),
+    ("quick_filter.sprint", "sprint {sprint}"),
+    ("quick_filter.conflict_free", "conflict-free"),
+    ("quick_filter.merged", "merged"),
+    ("quick_filter.login_unknown", "Couldn't tell which GitHub user you are, so 'mine' is unavailable"),
+    ("quick_filter.bad_pattern", "The sprint pattern doesn't compile: {error}"),
+    ("quick_filter.no_predictions", "No conflict predictions yet; they need the PRs' commits in the local clone"),
+    ("jump.title", "Jump to PR"),
+    ("jump.hint", "type a PR number (Enter to jump, Esc to cancel)"),
+    ("note.title", "Note for PR #{number}"),
+    ("note.hint", "one line, e.g. needs DB migration first (empty to remove)"),
+    ("branch_name.title", "Another name for the backport branch"),
+    ("branch_name.hint", "one that exists neither here nor on the remote"),
+    ("branch_name.taken", "{branch} is taken too; choose another name"),
+    ("jump.invalid", "'{input}' isn't a PR number"),
+    ("criteria.title", "Criteria {step}/{count}: {field}"),
+    ("criteria.hint", "Enter to accept and continue, Esc to cancel (this session only)"),
+    ("criteria.environment", "Environment tag"),
+    ("criteria.pending_tag", "Pending tag"),
+    ("criteria.sprint_pattern", "Sprint pattern"),
+    ("criteria.days_back", "Days back"),
+    ("criteria.empty", "A tag can't be empty"),
+    ("criteria.invalid_pattern", "Not a valid pattern: {error}"),
+    ("criteria.invalid_days", "'{input}' isn't a positive number of days"),
+    // PR state badges
+    ("pr_state.merged", "merged"),
+    ("pr_state.open", "open"),
+    ("pr_state.closed", "closed"),
+    // PR detail
+    ("pr_detail.none_selected", "No PR selected"),
+    ("pr_detail.state", "State"),
+    ("pr_detail.author", "Author"),
+    ("pr_detail.branches", "Branches"),
+    ("pr_detail.updated", "Updated"),
+    ("pr_detail.labels", "Labels"),
+    ("pr_detail.assignees", "Assignees"),
+    ("pr_detail.reviewers", "Reviewers"),
+    ("pr_detail.follows_up", "Follows up"),
+    ("pr_detail.note", "Note"),
+    ("pr_detail.none", "none"),
+    ("pr_detail.squash", "Squash"),
+    ("pr_detail.squash_on", "yes, into one commit (S to toggle)"),
+    ("pr_detail.squash_off", "no, commit by commit (S to toggle)"),
+    ("pr_detail.commits", "Commits ({count})"),
+    ("pr_detail.commits_reordered", "Commits ({count}, reordered)"),
+    (
+        "pr_detail.head_gone_merged",
+        "head commit garbage-collected — re-pick from the merge commit instead?",
+    ),
+    (
+        "pr_detail.head_gone",
+        "head commit garbage-collected and the PR was never merged; nothing left to pick",
+    ),
+    (
+        "pr_detail.footer",
+        "↑/↓ Select commit  •  Shift+↑/↓ Reorder  •  Enter Cherry-pick  •  S Squash  •  n Note  •  s Skip  •  y Copy sha  •  Esc Back",
+    ),
+    // Tag list
+    ("tag_list.title", "🏷  Tags ({count})"),
+    ("tag_list.empty", "No tags in this repository"),
+    ("tag_list.footer", "Esc Back  •  q Quit"),
+    // Pending backports by author
+    ("stats.title", "👥 Pending by author — {prs} PRs from {authors} authors"),
+    ("stats.empty", "No PRs loaded"),
+    ("stats.author", "Author"),
+    ("stats.prs", "PRs"),
+    ("stats.oldest", "Oldest"),
+    ("stats.commits", "Commits"),
+    ("stats.footer", "Esc Back  •  q Quit"),
+    // History
+    ("target.title", "🎯 Target branch (now {branch})"),
+    ("target.recent", "Recent"),
+    ("target.all", "All branches"),
+    ("target.current", "(current)"),
+    ("target.empty", "No branches found in this repository"),
+    ("target.footer", "↑/↓ Navigate  •  Enter Pick onto this branch  •  * Pin/unpin  •  Esc Back  •  q Quit"),
+    ("history.title", "📜 Session history ({count})"),
+    ("history.empty", "No sessions recorded for this repository yet"),
+    ("history.item", "{started}  {branches}  —  {picked} picked, {failed} failed"),
+    ("history.partial", "{count} incomplete"),
+    ("history.footer", "↑/↓ Navigate  •  Enter Open  •  y Copy branches  •  Esc Back  •  q Quit"),
+    ("session.none_selected", "No session selected"),
+    ("session.title", "Session {session}"),
+    ("session.record", "#{number} {title} → {branch} ({count} commits)"),
+    ("session.partial", "missing {missing}"),
+    ("session.missing_labels", "labels"),
+    ("session.missing_comment", "comment"),
+    ("session.held_back", "held back: {steps}"),
+    ("session.skipped_lines", "{count} unreadable line(s) skipped"),
+    ("session.footer", "↑/↓ Select  •  y Copy new shas  •  b Re-run GitHub bookkeeping  •  Esc Back  •  q Quit"),
+    ("session.sides_taken", "kept {files}"),
+    ("session.note", "✎ {note}"),
+    ("session.simulated", "simulated: {changes}"),
+    ("session.submodules_unverified", "submodule commit not found upstream: {submodules}"),
+    ("clipboard.copied", "Copied {value}"),
+    ("clipboard.unavailable", "No clipboard here; copy it yourself: {value}"),
+    ("clipboard.nothing", "Nothing to copy on this row"),
+    ("conflicts.title", "⚠ PR #{number} stopped on {count} conflicted file(s)"),
+    ("conflicts.binary", "binary — press o or t to keep one side"),
+    ("conflicts.text", "resolve in your editor, then stage it"),
+    ("conflicts.taken", "kept {side}"),
+    ("conflicts.ours", "ours"),
+    ("conflicts.theirs", "theirs"),
+    ("conflicts.staged", "resolved and staged"),
+    ("conflicts.leftover_title", "⚠ A cherry-pick was left stopped on {count} conflicted file(s)"),
+    ("conflicts.leftover", "Resolve and stage the files, then press c to continue or a to abort"),
+    ("conflicts.stopped", "Commit {sha} stopped on conflicts; resolve and stage them here or in another terminal, then press r to re-check."),
+    ("conflicts.footer", "↑/↓ Navigate  •  o Take ours  •  t Take theirs  •  r Re-check  •  a Abort pick  •  Esc Back  •  q Quit"),
+    ("conflicts.footer_ready", "c Continue pick  •  r Re-check  •  a Abort pick  •  Esc Back  •  q Quit"),
+    // Post-pick hook
+    ("hook.running_title", "Running the post-pick hook: {command}"),
+    ("hook.failed_title", "⚠ The post-pick hook {failure}: {command}"),
+    ("hook.exit_code", "exited with code {code}"),
+    ("hook.killed", "was killed by a signal"),
+    ("hook.footer", "c Continue anyway  •  s Open a shell  •  r Roll back the pick  •  ↑/↓ Scroll  •  q Quit"),
+    ("hook.footer_running", "Waiting for the hook to finish…"),
+    ("hook.shell_banner", "Opening a shell in {dir}; exit it to return to gh_cherry"),
+    // Terminal capability notes
+    ("terminal.mouse_off", "mouse off"),
+    ("terminal.inline_mode", "inline mode"),
+    // Confirmations
+    (
+        "confirm.reordered_pick",
+        "Commits were reordered; out-of-order picks are conflict-prone. Pick anyway? (y/n)",
+    ),
+    (
+        "confirm.merge_commit_fallback",
+        "Head commit garbage-collected — re-pick from merge commit {sha} instead? (y/n)",
+    ),
+    (
+        "confirm.abort_session",
+        "Revert {commits} commit(s) from {prs} PR(s) picked this session? (y/n)",
+    ),
+    (
+        "confirm.unpushed_commits",
+        "{count} commit(s) on {branch} aren't on origin: {commits}. y Pick on top  •  r Reset to origin (discards them)  •  n Cancel",
+    ),
+    (
+        "confirm.existing_branch",
+        "{branch} already exists {where}. y Reuse it and add the picks  •  d Delete and recreate it from {source}  •  e Use another name  •  n Cancel",
+    ),
+    ("confirm.branch_local", "locally"),
+    ("confirm.branch_remote", "on the remote"),
+    ("confirm.branch_both", "locally and on the remote"),
+    (
+        "confirm.detached_head",
+        "HEAD is detached at {sha}; commits made there will be on no branch. Check out {branch} and pick? (y/n)",
+    ),
+    ("confirm.submodule_update", "{path} to {sha}"),
+    (
+        "confirm.submodule_updates",
+        "This updates submodule {updates} — ensure that commit exists upstream. Pick anyway? (y/n)",
+    ),
+    (
+        "confirm.fetch_pr",
+        "PR #{number} isn't in the list. Fetch it from GitHub? (y/n)",
+    ),
+    (
+        "confirm.rerun_bookkeeping",
+        "Retry the failed label updates/comments for {prs} PR(s)? (y/n)",
+    ),
+    (
+        "confirm.include_follow_ups",
+        "PR #{number} has follow-ups: {follow_ups}. y Pick them after it  •  o Only #{number}  •  n Cancel",
+    ),
+    (
+        "confirm.pick_options",
+        "Pick #{number} with: {labels} l Update labels  {comment} m Post comment  •  y Pick  •  n Cancel",
+    ),
+    // Progress
+    ("progress.title", "⏳ Processing..."),
+    ("progress.working", "Working..."),
+    ("progress.wait", "Please wait..."),
+    ("progress.op_fetch", "Fetching"),
+    ("progress.op_checkout", "Checking out {branch}"),
+    ("progress.op_cherry_pick", "Cherry-picking {sha}"),
+    ("progress.op_push", "Pushing {branch}"),
+    ("status.loading_prs", "Loading PRs..."),
+    ("status.rate_limited_retrying", "Rate limited, retrying in {secs}s"),
+    ("status.env_warnings", "⚠ cherry.env: {warnings}"),
+    ("status.picking", "Cherry-picking PR #{number}: {title}"),
+    ("status.quit_pending", "Finishing the current commit, then exiting… (Ctrl+C again to exit now)"),
+    ("status.picked", "Successfully cherry-picked PR #{number}"),
+    (
+        "status.picked_rerere",
+        "Successfully cherry-picked PR #{number} (conflicts resolved via rerere)",
+    ),
+    (
+        "status.picked_submodules_unverified",
+        "Cherry-picked PR #{number}, but submodule commit(s) {submodules} weren't found upstream",
+    ),
+    (
+        "status.picked_simulated",
+        "Cherry-picked PR #{number}; labels/comment simulated, GitHub left unchanged",
+    ),
+    (
+        "status.picked_held_back",
+        "Cherry-picked PR #{number}; held-back updates are listed in History for later",
+    ),
+    (
+        "status.tracking_other_remote",
+        " • {branch} is tracking {remote}/{branch}, as the configured remote doesn't have it",
+    ),
+    ("status.dry_run_pick", "Dry run: PR #{number} would apply {count} commit(s) onto {branch}: {shas}"),
+    ("status.code_owners", " • code owners on {branch}: {teams}"),
+    (
+        "status.code_owners_changed",
+        " • code owners on {branch}: {teams}, but {base_teams} on {base}; check who has to approve",
+    ),
+    ("status.code_owners_nobody", "nobody"),
+    ("status.pushed_branch", " • pushed {branch}"),
+    ("status.push_failed", " • pushing {branch} failed: {error}"),
+    ("status.backport_pr_opened", " • opened PR #{number}"),
+    ("status.backport_pr_failed", " • opening the backport PR failed: {error}"),
+    ("status.review_request_failed", " • opened PR #{number}, but requesting reviewers failed: {error}"),
+    ("status.note_set", "Noted PR #{number}"),
+    ("status.note_cleared", "Removed the note of PR #{number}"),
+    ("status.picked_mainline", " • merge commit {sha} picked against parent {parent}"),
+    (
+        "status.picked_local_only",
+        "Cherry-picked PR #{number} locally; labels/comment skipped (read-only access)",
+    ),
+    (
+        "status.bookkeeping_not_permitted",
+        "You can't label or comment on this repository (read-only access)",
+    ),
+    ("status.nothing_to_rerun", "Every pick in this session finished its GitHub bookkeeping"),
+    (
+        "status.bookkeeping_rerun",
+        "Bookkeeping completed for {completed} PR(s); {remaining} still incomplete",
+    ),
+    ("status.skipped", "Skipped PR #{number}"),
+    ("status.target_set", "Now picking onto {branch}"),
+    ("status.target_run", " • branch {index} of {total}: {branch}"),
+    ("status.picking_targets", "Cherry-picking PR #{number} onto {count} branches in worktrees"),
+    ("status.targets_progress", " • {done} of {total} done (Esc starts no more)"),
+    ("status.targets_cancelling", "Finishing the branches already started…"),
+    ("status.targets_done", "PR #{number} picked onto {picked} of {total} branches"),
+    ("status.target_picked", " • {branch}: picked"),
+    ("status.target_conflicted", " • {branch}: {sha} conflicts in {files}, left unchanged"),
+    ("status.target_failed", " • {branch}: failed: {error}"),
+    ("status.target_cancelled", " • {branch}: not started"),
+    ("status.batch_progress", " • PR {index} of {total}"),
+    ("status.batch_queued", "Picking {count} selected PR(s) in list order"),
+    ("status.nothing_selected", "No PRs selected; press Space on a PR to select it"),
+    ("status.queue_skipped", " • {count} queued PR(s) skipped"),
+    ("status.hook_rolled_back", "Rolled back the pick of PR #{number} after its post-pick hook failed"),
+    ("status.hook_shell_closed", "Back from the shell; continue or roll back the pick"),
+    ("status.target_pinned", "Pinned {branch}"),
+    ("status.target_unpinned", "Unpinned {branch}"),
+    ("status.detached_head", "HEAD is detached at {sha}; picking will ask before checking out the target branch"),
+    ("status.listing_changed", "Since the last load: {new} new, {updated} updated"),
+    ("status.listing_gone", "{count} PR(s) no longer listed: {numbers}"),
+    ("status.auto_refresh_failed", "Automatic refresh failed: {error}"),
+    ("status.env_reloaded", "Reloaded cherry.env"),
+    ("status.env_reload_failed", "Could not reload cherry.env: {error}"),
+    ("status.sso_opened", "Opened {url} in your browser"),
+    ("status.sso_open_failed", "Couldn't open a browser ({error}); visit {url}"),
+    ("status.api_usage", "GitHub: {usage}"),
+    ("status.prs_not_evaluated", "{count} PR(s) could not be evaluated — see log"),
+    ("status.partial_listing", "Showing the {count} PR(s) fetched before the listing failed; r retries"),
+    ("status.labels_bypassed", "{count} PR(s) without the label criteria; press r to list with them again"),
+    ("status.conflict_side_taken", "Kept {side} version of {path}"),
+    ("status.conflicts_resolved", "All conflicts resolved; press c to continue the pick"),
+    ("status.conflicts_left", "{count} file(s) still conflicted"),
+    ("status.leftover_continued", "Stopped cherry-pick committed as {sha}"),
+    ("status.stopped_pick_aborted", "Pick aborted; commits it already made stay until the session is aborted (A)"),
+    ("status.conflict_not_binary", "{path} isn't binary; resolve it in your editor"),
+    ("status.fetching", "Fetching {branch}..."),
+    ("status.nothing_to_abort", "Nothing has been cherry-picked this session"),
+    (
+        "status.session_aborted",
+        "Reverted {commits} commit(s) from {prs} PR(s); deleted {branches} branch(es)",
+    ),
+    // Errors
+    ("error.unknown", "Unknown error"),
+    ("error.generic", "Error: {error}"),
+    ("error.load_prs", "Failed to load PRs: {error}"),
+    ("error.sso_open_hint", "Press o to open the authorization page in your browser, or any other key for the menu."),
+    ("error.load_prs_partial", "Failed to load PRs: {error}\n\nPress p to show the {count} PR(s) fetched before the error, or any other key for the menu."),
+    ("error.checkout_target", "Failed to checkout target branch: {error}"),
+    (
+        "error.backport_branch_unnamed",
+        "The backport branch {template} still has a {task_id} placeholder; pass --task-id",
+    ),
+    (
+        "error.backport_branch_exists",
+        "The backport branch {branch} already exists and --on-existing-branch is fail",
+    ),
+    (
+        "error.conflict_repeat_hint",
+        "This conflict already came up this session; enable rerere (git config rerere.enabled true) to auto-resolve repeats.",
+    ),
+    ("error.pick_failed", "Failed to cherry-pick commit {sha}: {error}"),
+    ("error.resolve_conflict", "Failed to resolve {path}: {error}"),
+    ("error.read_conflicts", "Failed to read the conflicted files: {error}"),
+    ("error.continue_pick", "Failed to continue the cherry-pick: {error}"),
+    ("error.abort_pick", "Failed to abort the cherry-pick: {error}"),
+    ("error.list_sessions", "Failed to list session logs: {error}"),
+    ("error.rerun_bookkeeping", "Failed to re-run bookkeeping: {error}"),
+    ("error.reset_to_origin", "Failed to reset {branch} to origin: {error}"),
+    ("error.fetch_branch", "Failed to fetch branch {branch}: {error}"),
+    (
+        "error.unpickable",
+        "PR #{number}: its commits no longer exist (branch deleted or history rewritten) and it has no merge commit to pick instead",
+    ),
+    ("error.squash", "Picked PR #{number} but could not squash its commits: {error}"),
+    ("error.fetch_pr", "Failed to fetch PR #{number}: {error}"),
+    ("error.abort_session", "Failed to abort session: {error}"),
+    (
+        "error.abort_head_moved",
+        "{branch} no longer ends at picked commit {sha}; leaving it untouched",
+    ),
+    ("error.list_tags", "Failed to list tags: {error}"),
+    ("error.hook_shell", "Could not open a shell: {error}"),
+    ("error.hook_rollback", "Could not roll back the pick: {error}"),
+    ("error.list_branches", "Failed to list branches: {error}"),
+    // Setup and selectors
+    ("config_selector.title", "Configuration Loader"),
+    ("config_selector.env_found", "Found cherry.env file. Choose how to load configuration:"),
+    ("config_selector.env_missing", "No cherry.env file found. Choose how to load configuration:"),
+    ("config_selector.setup_title", " Configuration Setup "),
+    ("config_selector.options_title", " Options "),
+    ("config_selector.instructions_title", " Instructions "),
+    ("config_selector.instructions", "↑/↓: Navigate | 1-{count}: Quick select | Enter: Confirm | Esc/q: Cancel"),
+    ("config_selector.load_env", "Load from cherry.env"),
+    ("config_selector.load_env_desc", "Use project-specific configuration from cherry.env file (recommended)"),
+    ("config_selector.defaults", "Use defaults only"),
+    ("config_selector.defaults_desc", "Start with default settings, ignore all configuration files"),
+    ("config_selector.global", "Use global config only"),
+    ("config_selector.global_desc", "Load from global config.toml file, ignore cherry.env"),
+    ("config_selector.create", "Create cherry.env for this project"),
+    ("config_selector.create_desc", "Answer a few questions and save them to a new cherry.env file"),
+    ("wizard.owner", "Repository owner"),
+    ("wizard.owner_placeholder", "empty = pick from your organizations"),
+    ("wizard.repo", "Repository name"),
+    ("wizard.repo_placeholder", "empty = pick from the owner's repositories"),
+    ("wizard.base_branch", "Base branch (PRs merged into)"),
+    ("wizard.base_branch_placeholder", "e.g., main"),
+    ("wizard.target_branch", "Target branch (cherry-pick onto)"),
+    ("wizard.target_branch_placeholder", "e.g., release/2025.08"),
+    ("wizard.source_branch", "Source branch for cherry-pick"),
+    ("wizard.source_branch_placeholder", "e.g., main"),
+    ("wizard.branch_template", "Branch name template"),
+    ("wizard.branch_template_placeholder", "e.g., cherry-pick/{task_id}"),
+    ("task_id.title", "Task ID"),
+    ("task_id.placeholder", "e.g., GH-123"),
+    ("task_id.branch", "Branch: "),
+    ("input.hint", "Enter: Confirm  •  Esc: Cancel  •  Backspace: Delete"),
+    ("selector.personal_account_option", "{login} (Your personal account)"),
+    ("selector.personal_account", "Your personal account"),
+    ("selector.no_description", "No description"),
+    ("selector.org_title", "Select Organization"),
+    ("selector.org_no_repos", "(no accessible repos)"),
+    ("selector.org_one_repo", "(1 repo)"),
+    ("selector.org_repos", "({count} repos)"),
+    ("selector.org_public_repos", "Public repos:   {count}"),
+    ("selector.org_public_members", "Public members: {count}"),
+    ("selector.org_created", "Created:        {date}"),
+    ("selector.org_plan", "Plan:           {plan}"),
+    ("selector.org_details_unavailable", "Details unavailable"),
+    ("selector.details_title", " Details "),
+    ("selector.fork_target_title", "List PRs From"),
+    ("selector.fork_target_parent", "{repo} - upstream repository (where PRs are usually opened)"),
+    ("selector.fork_target_fork", "{repo} - this fork"),
+    ("selector.repo_title", "Select Repository"),
+    ("selector.fork_of", "{name} (fork of {parent})"),
+    ("selector.fork", "{name} (fork)"),
+    ("selector.quick_entry", " Go to repo (name or owner/name): {entry} "),
+    ("selector.search", " Search: {query} "),
+    ("selector.search_hint", " Press '/' to search "),
+    ("selector.repo_search_hint", " Press '/' to search, ':' to enter a repo name "),
+    ("selector.searching", "searching…"),
+    ("selector.instructions", "↑/↓: Navigate | Enter: Select | /: Search | Esc/q: Cancel"),
+    ("selector.repo_instructions", "↑/↓: Navigate | Enter: Select | /: Search | :: Go to repo | Esc/q: Cancel"),
+    // Command line
+    ("cli.sso_hint", "Rerun with --open-sso to open the authorization page in your browser"),
+    ("cli.digest_needs_target", "--digest needs a target branch; pass --target-branch or set it in the config"),
+    ("cli.api_summary", "GitHub API this session: {usage}"),
+    ("cli.pick_interrupted", "Stopped picking PR #{number} after {picked} of {total} commits; abort the session or pick the rest next run"),
+    ("cli.pick_abandoned", "Exited while PR #{number} was being picked onto {branch}; check that branch with git status before picking again"),
+    ("cli.bookkeeping_skipped", "PR #{number} was picked but not labelled or commented on; finish it from the history screen"),
+    ("cli.config_valid", "{path} is a valid gh_cherry config"),
+    ("cli.config_invalid", "{path} has {count} problem(s):"),
+    ("cli.migrate_none", "No open PRs in {owner}'s repositories carry '{label}'"),
+    ("cli.migrate_found", "{count} open PR(s) carry '{label}'"),
+    ("cli.migrate_progress", "[{done}/{total}] {pr}"),
+    ("cli.migrate_failed", "Could not relabel {pr}: {error}"),
+    ("cli.migrate_done", "Moved {migrated} of {total} PR(s) to '{label}'"),
+    ("cli.migrate_stopped", "Stopped for the rate limit with {count} PR(s) left; run the same command again later to finish"),
+    ("cli.migrate_update_config", "'{from}' is one of your configured tags; set it to '{to}' in your config or cherry.env"),
+    ("cli.cache_cleaned", "Removed {count} cached file(s)"),
+    ("cli.removed_path", "Removed {path}"),
+    ("cli.discovering", "No owner/repo specified, discovering available options..."),
+    ("cli.source_branch_title", "Source branch for cherry-pick"),
+    (
+        "cli.source_branch_placeholder",
+        "e.g., main or release/2025.08 (Enter to accept current)",
+    ),
+    ("cli.config_saved", "Configuration saved to cherry.env"),
+    ("cli.repo_config_applied", "Applied repository settings from {owner}/{repo}"),
+    ("cli.authenticated", "Authenticated as: {name} ({login})"),
+    ("cli.using_owner", "Using owner: {owner}"),
+    ("cli.opening_org_selector", "Opening organization selector..."),
+    ("cli.probing_orgs", "Counting repositories in your organizations..."),
+    ("cli.owner_required", "Several organizations are available and prompting is off; pass --owner to choose one"),
+    ("cli.selected_owner", "Selected owner: {owner}"),
+    ("cli.using_repo", "Using repository: {repo}"),
+    ("cli.opening_repo_selector", "Opening repository selector..."),
+    ("cli.repo_quick_entry", "Repository in {owner} (name or owner/name)"),
+    ("cli.repo_quick_entry_placeholder", "Leave empty and press Enter to browse the list"),
+    ("cli.repo_required", "{owner} has several repositories and prompting is off; pass --repo to choose one"),
+    ("cli.selected_repo", "Selected repository: {owner}/{repo}"),
+    ("cli.forking", "Forking {repo}... please wait"),
+    ("cli.fork_own_repo", "{repo} already belongs to you; not forking"),
+    ("cli.fork_of", "{repo} is a fork of {parent}"),
+    ("cli.listing_prs_from", "Listing PRs from {owner}/{repo}; git operations use the local clone"),
+    ("cli.pr_open", "Note: PR #{number} is open and not merged yet; picking it takes its commits as they are now"),
+    ("cli.pr_closed", "Note: PR #{number} was closed without merging"),
+    ("cli.pr_mismatch", "PR #{number} would not appear in the normal listing:"),
+    ("cli.mismatch_pending_tag", "missing the pending tag \"{tag}\""),
+    ("cli.mismatch_environment_tag", "missing the environment tag \"{tag}\""),
+    ("cli.mismatch_sprint_tag", "no label matches the sprint pattern {pattern}"),
+    ("cli.mismatch_window", "last updated {updated}, outside the {days}-day window"),
+    ("cli.mismatch_base", "targets {actual} instead of {expected}"),
+    ("cli.mismatch_assignees", "not assigned to {assignees}"),
+    ("cli.pr_mismatch_continue", "Press Enter to continue anyway, or Ctrl+C to cancel."),
+    ("cli.no_repos", "No repositories found for owner: {owner}"),
+    ("cli.no_repos_forks_only", " (forked repositories only)"),
+    (
+        "cli.no_repos_limit_hint",
+        " within the first {limit} repositories; raise ui.discovery_repo_limit or pass --repo",
+    ),
+];
@@ -0,0 +1,491 @@
+pub(super) static CATALOG: &[(&str, &str)] = &[
+    // Menú principal
+    ("main_menu.title", "🍒 GitHub Cherry-Pick"),
+    (
+        "main_menu.prompt",
+        ">> Pulsa Enter para ver los PRs  •  r: Actualizar  •  t: Etiquetas  •  h: Historial  •  b: Rama destino  •  q: Salir",
+    ),
+    ("main_menu.hint", "Usa los números para elegir una opción, 'q' para salir"),
+    // Lista de PRs
+    ("pr_list.title", "📋 Pull Requests  —  mostrando {shown} de {total}"),
+    ("pr_list.title_short", "📋 PRs {shown}/{total}"),
+    ("pr_list.dry_run_badge", "[SIMULACRO]"),
+    ("pr_list.prompt_hint", "f: Filtrar  •  Enter: Cherry-pick  •  Esc: Volver"),
+    (
+        "pr_list.prompt_hint_filtered",
+        "f: Filtrar (activo: '{query}')  •  Enter: Cherry-pick  •  Esc: Volver",
+    ),
+    (
+        "pr_list.footer",
+        "↑/↓ Navegar  •  Enter Cherry-pick  •  d Detalles  •  s Omitir  •  P Aplicar con opciones  •  Espacio Seleccionar  •  x Aplicar seleccionados  •  n Nota  •  # Ir a PR  •  r Actualizar  •  f Filtrar  •  m/S/c/M Míos/Sprint/Limpios/Fusionados  •  u Por autor  •  A Abortar sesión  •  Esc Volver  •  q Salir",
+    ),
+    ("pr_list.follow_up", "↳ seguimiento de #{number} "),
+    ("pr_list.note", "        ✎ {note}"),
+    ("pr_list.auto_refresh", "↻ actualización en {secs}s"),
+    (
+        "pr_list.empty",
+        "No se encontraron PRs que cumplan los criterios.\n\n\
+        📋 Criterios de búsqueda:\n\
+        • Repositorio: {owner}/{repo}\n\
+        • Rama base: {base}\n\
+        • Entorno: {environment}\n\
+        • Etiqueta pendiente: \"{pending}\"\n\
+        • Días atrás: {days}\n\n\
+        💡 Consejos:\n\
+        • Asegúrate de que los PRs tengan la etiqueta \"{pending}\"\n\
+        • Comprueba que los PRs estén fusionados en la rama \"{base}\"\n\
+        • Verifica que el patrón de etiquetas coincida: {pattern}\n\n\
+        🔄 Pulsa 'r' para actualizar, 'e' para editar los criterios, 'n' para listar sin las etiquetas o 'Esc' para volver.",
+    ),
+    ("pr_list.fetch_badge", "[!FETCH]"),
+    ("pr_list.fetch_hint", "F Descargar commits que faltan"),
+    ("pr_list.on_demand_badge", "[+]"),
+    ("pr_list.new_badge", "[nuevo]"),
+    ("pr_list.updated_badge", "[actualizado]"),
+    ("pr_list.read_only_hint", "Enter cherry-pick (solo lectura: solo local)"),
+    ("filter.title", "Filtrar PRs"),
+    (
+        "filter.hint",
+        "escribe para filtrar por #, título o autor (Enter para aplicar, Esc para cancelar)",
+    ),
+    // Filtros rápidos
+    ("quick_filter.mine", "míos"),
+    ("quick_filter.sprint", "sprint {sprint}"),
+    ("quick_filter.conflict_free", "sin conflictos"),
+    ("quick_filter.merged", "fusionados"),
+    ("quick_filter.login_unknown", "No se pudo saber qué usuario de GitHub eres; 'míos' no está disponible"),
+    ("quick_filter.bad_pattern", "El patrón de sprint no compila: {error}"),
+    ("quick_filter.no_predictions", "Aún no hay predicciones de conflictos; requieren los commits de los PRs en el clon local"),
+    ("jump.title", "Ir a PR"),
+    ("jump.hint", "escribe un número de PR (Enter para ir, Esc para cancelar)"),
+    ("note.title", "Nota del PR #{number}"),
+    ("note.hint", "una línea, p. ej. necesita antes la migración de BD (vacía para quitarla)"),
+    ("branch_name.title", "Otro nombre para la rama de backport"),
+    ("branch_name.hint", "uno que no exista ni aquí ni en el remoto"),
+    ("branch_name.taken", "{branch} también existe; elige otro nombre"),
+    ("jump.invalid", "'{input}' no es un número de PR"),
+    ("criteria.title", "Criterios {step}/{count}: {field}"),
+    ("criteria.hint", "Enter para aceptar y seguir, Esc para cancelar (solo esta sesión)"),
+    ("criteria.environment", "Etiqueta de entorno"),
+    ("criteria.pending_tag", "Etiqueta pendiente"),
+    ("criteria.sprint_pattern", "Patrón de sprint"),
+    ("criteria.days_back", "Días atrás"),
+    ("criteria.empty", "Una etiqueta no puede estar vacía"),
+    ("criteria.invalid_pattern", "Patrón no válido: {error}"),
+    ("criteria.invalid_days", "'{input}' no es un número de días positivo"),
+    // Estado del PR
+    ("pr_state.merged", "fusionado"),
+    ("pr_state.open", "abierto"),
+    ("pr_state.closed", "cerrado"),
+    // Detalle de PR
+    ("pr_detail.none_selected", "Ningún PR seleccionado"),
+    ("pr_detail.state", "Estado"),
+    ("pr_detail.author", "Autor"),
+    ("pr_detail.branches", "Ramas"),
+    ("pr_detail.updated", "Actualizado"),
+    ("pr_detail.labels", "Etiquetas"),
+    ("pr_detail.assignees", "Asignados"),
+    ("pr_detail.reviewers", "Revisores"),
+    ("pr_detail.follows_up", "Sigue a"),
+    ("pr_detail.note", "Nota"),
+    ("pr_detail.none", "ninguno"),
+    ("pr_detail.squash", "Combinar"),
+    ("pr_detail.squash_on", "sí, en un solo commit (S para cambiar)"),
+    ("pr_detail.squash_off", "no, commit a commit (S para cambiar)"),
+    ("pr_detail.commits", "Commits ({count})"),
+    ("pr_detail.commits_reordered", "Commits ({count}, reordenados)"),
+    (
+        "pr_detail.head_gone_merged",
+        "el commit de cabecera fue eliminado por el recolector — ¿aplicar el commit de merge en su lugar?",
+    ),
+    (
+        "pr_detail.head_gone",
+        "el commit de cabecera fue eliminado y el PR nunca se fusionó; no queda nada que aplicar",
+    ),
+    (
+        "pr_detail.footer",
+        "↑/↓ Elegir commit  •  Shift+↑/↓ Reordenar  •  Enter Cherry-pick  •  S Combinar  •  n Nota  •  s Omitir  •  y Copiar sha  •  Esc Volver",
+    ),
+    // Lista de etiquetas
+    ("tag_list.title", "🏷  Etiquetas ({count})"),
+    ("tag_list.empty", "No hay etiquetas en este repositorio"),
+    ("tag_list.footer", "Esc Volver  •  q Salir"),
+    // Pendientes por autor
+    ("stats.title", "👥 Pendientes por autor — {prs} PRs de {authors} autores"),
+    ("stats.empty", "No hay PRs cargados"),
+    ("stats.author", "Autor"),
+    ("stats.prs", "PRs"),
+    ("stats.oldest", "Desde"),
+    ("stats.commits", "Commits"),
+    ("stats.footer", "Esc Volver  •  q Salir"),
+    // Historial
+    ("target.title", "🎯 Rama destino (ahora {branch})"),
+    ("target.recent", "Recientes"),
+    ("target.all", "Todas las ramas"),
+    ("target.current", "(actual)"),
+    ("target.empty", "No se encontraron ramas en este repositorio"),
+    ("target.footer", "↑/↓ Navegar  •  Enter Aplicar en esta rama  •  * Fijar/soltar  •  Esc Volver  •  q Salir"),
+    ("history.title", "📜 Historial de sesiones ({count})"),
+    ("history.empty", "Aún no hay sesiones registradas para este repositorio"),
+    ("history.item", "{started}  {branches}  —  {picked} aplicados, {failed} fallidos"),
+    ("history.partial", "{count} incompletos"),
+    ("history.footer", "↑/↓ Navegar  •  Enter Abrir  •  y Copiar ramas  •  Esc Volver  •  q Salir"),
+    ("session.none_selected", "Ninguna sesión seleccionada"),
+    ("session.title", "Sesión {session}"),
+    ("session.record", "#{number} {title} → {branch} ({count} commits)"),
+    ("session.partial", "falta {missing}"),
+    ("session.missing_labels", "etiquetas"),
+    ("session.missing_comment", "comentario"),
+    ("session.held_back", "retenido: {steps}"),
+    ("session.skipped_lines", "{count} línea(s) ilegibles omitidas"),
+    ("session.footer", "↑/↓ Elegir  •  y Copiar shas nuevos  •  b Repetir la gestión en GitHub  •  Esc Volver  •  q Salir"),
+    ("session.sides_taken", "se conservó {files}"),
+    ("session.note", "✎ {note}"),
+    ("session.simulated", "simulado: {changes}"),
+    ("session.submodules_unverified", "commit de submódulo no encontrado en el remoto: {submodules}"),
+    ("clipboard.copied", "Copiado {value}"),
+    ("clipboard.unavailable", "No hay portapapeles; cópialo a mano: {value}"),
+    ("clipboard.nothing", "Nada que copiar en esta fila"),
+    ("conflicts.title", "⚠ El PR #{number} se detuvo con {count} archivo(s) en conflicto"),
+    ("conflicts.binary", "binario — pulsa o o t para conservar un lado"),
+    ("conflicts.text", "resuélvelo en tu editor y añádelo al índice"),
+    ("conflicts.taken", "se conservó {side}"),
+    ("conflicts.ours", "la nuestra"),
+    ("conflicts.theirs", "la suya"),
+    ("conflicts.staged", "resuelto y en el índice"),
+    ("conflicts.leftover_title", "⚠ Quedó un cherry-pick detenido con {count} archivo(s) en conflicto"),
+    ("conflicts.leftover", "Resuelve los archivos y añádelos al índice; luego pulsa c para continuar o a para abortar"),
+    ("conflicts.stopped", "El commit {sha} se detuvo con conflictos; resuélvelos aquí o en otra terminal y pulsa r para volver a comprobar."),
+    ("conflicts.footer", "↑/↓ Navegar  •  o Conservar la nuestra  •  t Conservar la suya  •  r Comprobar  •  a Abortar  •  Esc Volver  •  q Salir"),
+    ("conflicts.footer_ready", "c Continuar  •  r Comprobar  •  a Abortar  •  Esc Volver  •  q Salir"),
+    // Hook tras el pick
+    ("hook.running_title", "Ejecutando el hook tras el pick: {command}"),
+    ("hook.failed_title", "⚠ El hook tras el pick {failure}: {command}"),
+    ("hook.exit_code", "terminó con código {code}"),
+    ("hook.killed", "fue terminado por una señal"),
+    ("hook.footer", "c Continuar igualmente  •  s Abrir una shell  •  r Deshacer el pick  •  ↑/↓ Desplazar  •  q Salir"),
+    ("hook.footer_running", "Esperando a que termine el hook…"),
+    ("hook.shell_banner", "Abriendo una shell en {dir}; sal de ella para volver a gh_cherry"),
+    // Capacidades del terminal
+    ("terminal.mouse_off", "ratón desactivado"),
+    ("terminal.inline_mode", "modo en línea"),
+    // Confirmaciones
+    (
+        "confirm.reordered_pick",
+        "Los commits se reordenaron; aplicarlos fuera de orden suele causar conflictos. ¿Continuar? (y/n)",
+    ),
+    (
+        "confirm.merge_commit_fallback",
+        "El commit de cabecera ya no existe — ¿aplicar el commit de merge {sha} en su lugar? (y/n)",
+    ),
+    (
+        "confirm.abort_session",
+        "¿Revertir {commits} commit(s) de {prs} PR(s) aplicados en esta sesión? (y/n)",
+    ),
+    (
+        "confirm.unpushed_commits",
+        "{count} commit(s) en {branch} no están en origin: {commits}. y Aplicar encima  •  r Restablecer a origin (los descarta)  •  n Cancelar",
+    ),
+    (
+        "confirm.existing_branch",
+        "{branch} ya existe {where}. y Reutilizarla y añadir los commits  •  d Borrarla y crearla de nuevo desde {source}  •  e Usar otro nombre  •  n Cancelar",
+    ),
+    ("confirm.branch_local", "localmente"),
+    ("confirm.branch_remote", "en el remoto"),
+    ("confirm.branch_both", "localmente y en el remoto"),
+    (
+        "confirm.detached_head",
+        "HEAD está desacoplado en {sha}; los commits hechos ahí no quedarán en ninguna rama. ¿Cambiar a {branch} y aplicar? (y/n)",
+    ),
+    ("confirm.submodule_update", "{path} a {sha}"),
+    (
+        "confirm.submodule_updates",
+        "Esto actualiza el submódulo {updates} — asegúrate de que ese commit existe en el remoto. ¿Aplicar de todos modos? (y/n)",
+    ),
+    (
+        "confirm.fetch_pr",
+        "El PR #{number} no está en la lista. ¿Descargarlo de GitHub? (y/n)",
+    ),
+    (
+        "confirm.rerun_bookkeeping",
+        "¿Reintentar las etiquetas/comentarios fallidos de {prs} PR(s)? (y/n)",
+    ),
+    (
+        "confirm.include_follow_ups",
+        "El PR #{number} tiene seguimientos: {follow_ups}. y Aplicarlos después  •  o Solo #{number}  •  n Cancelar",
+    ),
+    (
+        "confirm.pick_options",
+        "Aplicar #{number} con: {labels} l Actualizar etiquetas  {comment} m Publicar comentario  •  y Aplicar  •  n Cancelar",
+    ),
+    // Progreso
+    ("progress.title", "⏳ Procesando..."),
+    ("progress.working", "Trabajando..."),
+    ("progress.wait", "Espera, por favor..."),
+    ("progress.op_fetch", "Descargando"),
+    ("progress.op_checkout", "Cambiando a {branch}"),
+    ("progress.op_cherry_pick", "Aplicando {sha}"),
+    ("progress.op_push", "Subiendo {branch}"),
+    ("status.env_warnings", "⚠ cherry.env: {warnings}"),
+    ("status.loading_prs", "Cargando PRs..."),
+    ("status.rate_limited_retrying", "Límite de peticiones alcanzado, reintentando en {secs}s"),
+    ("status.picking", "Aplicando cherry-pick del PR #{number}: {title}"),
+    ("status.quit_pending", "Terminando el commit actual y saliendo… (Ctrl+C otra vez para salir ya)"),
+    ("status.picked", "Cherry-pick del PR #{number} completado"),
+    (
+        "status.picked_rerere",
+        "Cherry-pick del PR #{number} completado (conflictos resueltos con rerere)",
+    ),
+    (
+        "status.picked_submodules_unverified",
+        "Cherry-pick del PR #{number} completado, pero los commits de submódulo {submodules} no están en el remoto",
+    ),
+    (
+        "status.picked_simulated",
+        "Cherry-pick del PR #{number} completado; etiquetas/comentario simulados, GitHub sin cambios",
+    ),
+    (
+        "status.picked_held_back",
+        "Cherry-pick del PR #{number} completado; lo retenido queda en el Historial para después",
+    ),
+    (
+        "status.tracking_other_remote",
+        " • {branch} sigue a {remote}/{branch}, porque el remoto configurado no la tiene",
+    ),
+    ("status.dry_run_pick", "Simulacro: el PR #{number} aplicaría {count} commit(s) en {branch}: {shas}"),
+    ("status.code_owners", " • propietarios del código en {branch}: {teams}"),
+    (
+        "status.code_owners_changed",
+        " • propietarios del código en {branch}: {teams}, pero {base_teams} en {base}; revisa quién debe aprobar",
+    ),
+    ("status.code_owners_nobody", "nadie"),
+    ("status.pushed_branch", " • {branch} enviada"),
+    ("status.push_failed", " • no se pudo enviar {branch}: {error}"),
+    ("status.backport_pr_opened", " • PR #{number} abierto"),
+    ("status.backport_pr_failed", " • no se pudo abrir el PR del backport: {error}"),
+    ("status.review_request_failed", " • PR #{number} abierto, pero no se pudieron pedir revisores: {error}"),
+    ("status.note_set", "Nota guardada para el PR #{number}"),
+    ("status.note_cleared", "Se quitó la nota del PR #{number}"),
+    ("status.picked_mainline", " • commit de merge {sha} aplicado contra el padre {parent}"),
+    (
+        "status.picked_local_only",
+        "Cherry-pick del PR #{number} hecho en local; etiquetas/comentario omitidos (acceso de solo lectura)",
+    ),
+    (
+        "status.bookkeeping_not_permitted",
+        "No puedes etiquetar ni comentar en este repositorio (acceso de solo lectura)",
+    ),
+    ("status.nothing_to_rerun", "Todos los cherry-picks de esta sesión completaron su gestión en GitHub"),
+    (
+        "status.bookkeeping_rerun",
+        "Gestión completada para {completed} PR(s); {remaining} siguen incompletos",
+    ),
+    ("status.skipped", "PR #{number} omitido"),
+    ("status.target_set", "Ahora se aplica en {branch}"),
+    ("status.target_run", " • rama {index} de {total}: {branch}"),
+    ("status.picking_targets", "Aplicando cherry-pick del PR #{number} en {count} ramas con worktrees"),
+    ("status.targets_progress", " • {done} de {total} listas (Esc no inicia más)"),
+    ("status.targets_cancelling", "Terminando las ramas ya iniciadas…"),
+    ("status.targets_done", "PR #{number} aplicado en {picked} de {total} ramas"),
+    ("status.target_picked", " • {branch}: aplicado"),
+    ("status.target_conflicted", " • {branch}: {sha} tiene conflictos en {files}, sin cambios"),
+    ("status.target_failed", " • {branch}: falló: {error}"),
+    ("status.target_cancelled", " • {branch}: no iniciada"),
+    ("status.batch_progress", " • PR {index} de {total}"),
+    ("status.batch_queued", "Aplicando {count} PR(s) seleccionado(s) en el orden de la lista"),
+    ("status.nothing_selected", "No hay PRs seleccionados; pulsa Espacio sobre un PR para seleccionarlo"),
+    ("status.queue_skipped", " • se omitieron {count} PR(s) en cola"),
+    ("status.hook_rolled_back", "Se deshizo el pick del PR #{number} tras fallar su hook"),
+    ("status.hook_shell_closed", "De vuelta de la shell; continúa o deshaz el pick"),
+    ("status.target_pinned", "{branch} fijada"),
+    ("status.target_unpinned", "{branch} ya no está fijada"),
+    ("status.detached_head", "HEAD está desacoplado en {sha}; al aplicar se pedirá confirmación antes de cambiar a la rama destino"),
+    ("status.listing_changed", "Desde la última carga: {new} nuevo(s), {updated} actualizado(s)"),
+    ("status.listing_gone", "{count} PR(s) ya no aparecen: {numbers}"),
+    ("status.auto_refresh_failed", "Falló la actualización automática: {error}"),
+    ("status.env_reloaded", "cherry.env recargado"),
+    ("status.env_reload_failed", "No se pudo recargar cherry.env: {error}"),
+    ("status.sso_opened", "Se abrió {url} en el navegador"),
+    ("status.sso_open_failed", "No se pudo abrir un navegador ({error}); visita {url}"),
+    ("status.api_usage", "GitHub: {usage}"),
+    ("status.prs_not_evaluated", "No se pudieron evaluar {count} PR(s) — ver el registro"),
+    ("status.partial_listing", "Mostrando los {count} PR(s) obtenidos antes del fallo; r reintenta"),
+    ("status.labels_bypassed", "{count} PR(s) sin los criterios de etiquetas; pulsa r para volver a aplicarlos"),
+    ("status.conflict_side_taken", "{path}: se conservó {side}"),
+    ("status.conflicts_resolved", "Conflictos resueltos; pulsa c para continuar el pick"),
+    ("status.conflicts_left", "Quedan {count} archivo(s) en conflicto"),
+    ("status.leftover_continued", "Cherry-pick detenido confirmado como {sha}"),
+    ("status.stopped_pick_aborted", "Pick abortado; los commits que ya hizo quedan hasta abortar la sesión (A)"),
+    ("status.conflict_not_binary", "{path} no es binario; resuélvelo en tu editor"),
+    ("status.fetching", "Descargando {branch}..."),
+    ("status.nothing_to_abort", "No se ha aplicado ningún cherry-pick en esta sesión"),
+    (
+        "status.session_aborted",
+        "Revertidos {commits} commit(s) de {prs} PR(s); eliminadas {branches} rama(s)",
+    ),
+    // Errores
+    ("error.unknown", "Error desconocido"),
+    ("error.generic", "Error: {error}"),
+    ("error.load_prs", "No se pudieron cargar los PRs: {error}"),
+    ("error.sso_open_hint", "Pulsa o para abrir la página de autorización en el navegador, o cualquier otra tecla para ir al menú."),
+    ("error.load_prs_partial", "No se pudieron cargar los PRs: {error}\n\nPulsa p para ver los {count} PR(s) obtenidos antes del error, o cualquier otra tecla para ir al menú."),
+    ("error.checkout_target", "No se pudo cambiar a la rama destino: {error}"),
+    (
+        "error.backport_branch_unnamed",
+        "La rama de backport {template} aún tiene el marcador {task_id}; usa --task-id",
+    ),
+    (
+        "error.backport_branch_exists",
+        "La rama de backport {branch} ya existe y --on-existing-branch es fail",
+    ),
+    (
+        "error.conflict_repeat_hint",
+        "Este conflicto ya apareció en esta sesión; activa rerere (git config rerere.enabled true) para resolver las repeticiones automáticamente.",
+    ),
+    ("error.pick_failed", "Falló el cherry-pick del commit {sha}: {error}"),
+    ("error.resolve_conflict", "No se pudo resolver {path}: {error}"),
+    ("error.read_conflicts", "No se pudieron leer los archivos en conflicto: {error}"),
+    ("error.continue_pick", "No se pudo continuar el cherry-pick: {error}"),
+    ("error.abort_pick", "No se pudo abortar el cherry-pick: {error}"),
+    ("error.list_sessions", "No se pudieron listar los registros de sesión: {error}"),
+    ("error.rerun_bookkeeping", "No se pudo repetir la gestión: {error}"),
+    ("error.reset_to_origin", "No se pudo restablecer {branch} a origin: {error}"),
+    ("error.fetch_branch", "No se pudo descargar la rama {branch}: {error}"),
+    (
+        "error.unpickable",
+        "PR #{number}: sus commits ya no existen (rama eliminada o historial reescrito) y no tiene commit de merge que aplicar",
+    ),
+    ("error.squash", "Se aplicó el PR #{number} pero no se pudieron combinar sus commits: {error}"),
+    ("error.fetch_pr", "No se pudo obtener el PR #{number}: {error}"),
+    ("error.abort_session", "No se pudo abortar la sesión: {error}"),
+    (
+        "error.abort_head_moved",
+        "{branch} ya no termina en el commit aplicado {sha}; no se modifica",
+    ),
+    ("error.list_tags", "No se pudieron listar las etiquetas: {error}"),
+    ("error.hook_shell", "No se pudo abrir una shell: {error}"),
+    ("error.hook_rollback", "No se pudo deshacer el pick: {error}"),
+    ("error.list_branches", "No se pudieron listar las ramas: {error}"),
+    // Configuración inicial y selectores
+    ("config_selector.title", "Cargador de configuración"),
+    ("config_selector.env_found", "Se encontró cherry.env. Elige cómo cargar la configuración:"),
+    ("config_selector.env_missing", "No se encontró cherry.env. Elige cómo cargar la configuración:"),
+    ("config_selector.setup_title", " Configuración "),
+    ("config_selector.options_title", " Opciones "),
+    ("config_selector.instructions_title", " Instrucciones "),
+    ("config_selector.instructions", "↑/↓: Navegar | 1-{count}: Selección rápida | Enter: Confirmar | Esc/q: Cancelar"),
+    ("config_selector.load_env", "Cargar desde cherry.env"),
+    ("config_selector.load_env_desc", "Usar la configuración del proyecto en cherry.env (recomendado)"),
+    ("config_selector.defaults", "Usar solo los valores por defecto"),
+    ("config_selector.defaults_desc", "Empezar con la configuración por defecto e ignorar todos los archivos de configuración"),
+    ("config_selector.global", "Usar solo la configuración global"),
+    ("config_selector.global_desc", "Cargar el config.toml global e ignorar cherry.env"),
+    ("config_selector.create", "Crear cherry.env para este proyecto"),
+    ("config_selector.create_desc", "Responder unas preguntas y guardarlas en un nuevo cherry.env"),
+    ("wizard.owner", "Owner del repositorio"),
+    ("wizard.owner_placeholder", "vacío = elegir entre tus organizaciones"),
+    ("wizard.repo", "Nombre del repositorio"),
+    ("wizard.repo_placeholder", "vacío = elegir entre los repositorios del owner"),
+    ("wizard.base_branch", "Rama base (donde se fusionan los PRs)"),
+    ("wizard.base_branch_placeholder", "p. ej., main"),
+    ("wizard.target_branch", "Rama destino (donde se aplica el cherry-pick)"),
+    ("wizard.target_branch_placeholder", "p. ej., release/2025.08"),
+    ("wizard.source_branch", "Rama origen del cherry-pick"),
+    ("wizard.source_branch_placeholder", "p. ej., main"),
+    ("wizard.branch_template", "Plantilla del nombre de rama"),
+    ("wizard.branch_template_placeholder", "p. ej., cherry-pick/{task_id}"),
+    ("task_id.title", "ID de tarea"),
+    ("task_id.placeholder", "p. ej., GH-123"),
+    ("task_id.branch", "Rama: "),
+    ("input.hint", "Enter: Confirmar  •  Esc: Cancelar  •  Retroceso: Borrar"),
+    ("selector.personal_account_option", "{login} (Tu cuenta personal)"),
+    ("selector.personal_account", "Tu cuenta personal"),
+    ("selector.no_description", "Sin descripción"),
+    ("selector.org_title", "Seleccionar organización"),
+    ("selector.org_no_repos", "(sin repositorios accesibles)"),
+    ("selector.org_one_repo", "(1 repositorio)"),
+    ("selector.org_repos", "({count} repositorios)"),
+    ("selector.org_public_repos", "Repositorios públicos: {count}"),
+    ("selector.org_public_members", "Miembros públicos:     {count}"),
+    ("selector.org_created", "Creada:                {date}"),
+    ("selector.org_plan", "Plan:                  {plan}"),
+    ("selector.org_details_unavailable", "Detalles no disponibles"),
+    ("selector.details_title", " Detalles "),
+    ("selector.fork_target_title", "Listar PRs de"),
+    ("selector.fork_target_parent", "{repo} - repositorio upstream (donde se suelen abrir los PRs)"),
+    ("selector.fork_target_fork", "{repo} - este fork"),
+    ("selector.repo_title", "Seleccionar repositorio"),
+    ("selector.fork_of", "{name} (fork de {parent})"),
+    ("selector.fork", "{name} (fork)"),
+    ("selector.quick_entry", " Ir al repositorio (nombre u owner/nombre): {entry} "),
+    ("selector.search", " Buscar: {query} "),
+    ("selector.search_hint", " Pulsa '/' para buscar "),
+    ("selector.repo_search_hint", " Pulsa '/' para buscar, ':' para escribir un repositorio "),
+    ("selector.searching", "buscando…"),
+    ("selector.instructions", "↑/↓: Navegar | Enter: Seleccionar | /: Buscar | Esc/q: Cancelar"),
+    ("selector.repo_instructions", "↑/↓: Navegar | Enter: Seleccionar | /: Buscar | :: Ir al repositorio | Esc/q: Cancelar"),
+    // Línea de comandos
+    ("cli.sso_hint", "Vuelve a ejecutar con --open-sso para abrir la página de autorización en el navegador"),
+    ("cli.digest_needs_target", "--digest necesita una rama destino; usa --target-branch o configúrala"),
+    ("cli.api_summary", "API de GitHub en esta sesión: {usage}"),
+    ("cli.pick_interrupted", "Se detuvo el cherry-pick del PR #{number} tras {picked} de {total} commits; aborta la sesión o aplica el resto en la próxima ejecución"),
+    ("cli.pick_abandoned", "Se salió mientras se aplicaba el PR #{number} en {branch}; revisa esa rama con git status antes de volver a aplicarlo"),
+    ("cli.bookkeeping_skipped", "El PR #{number} se aplicó pero sin etiquetas ni comentario; complétalo desde la pantalla de historial"),
+    ("cli.config_valid", "{path} es una configuración válida de gh_cherry"),
+    ("cli.config_invalid", "{path} tiene {count} problema(s):"),
+    ("cli.migrate_none", "Ningún PR abierto en los repositorios de {owner} tiene '{label}'"),
+    ("cli.migrate_found", "{count} PR(s) abiertos tienen '{label}'"),
+    ("cli.migrate_progress", "[{done}/{total}] {pr}"),
+    ("cli.migrate_failed", "No se pudieron cambiar las etiquetas de {pr}: {error}"),
+    ("cli.migrate_done", "Se movieron {migrated} de {total} PR(s) a '{label}'"),
+    ("cli.migrate_stopped", "Detenido por el límite de peticiones con {count} PR(s) pendientes; vuelve a ejecutar el mismo comando más tarde"),
+    ("cli.migrate_update_config", "'{from}' es una de tus etiquetas configuradas; cámbiala a '{to}' en tu configuración o cherry.env"),
+    ("cli.cache_cleaned", "Se eliminaron {count} archivo(s) de caché"),
+    ("cli.removed_path", "Eliminado {path}"),
+    (
+        "cli.discovering",
+        "No se indicó owner/repo, buscando las opciones disponibles...",
+    ),
+    ("cli.source_branch_title", "Rama de origen para el cherry-pick"),
+    (
+        "cli.source_branch_placeholder",
+        "p. ej., main o release/2025.08 (Enter para mantener la actual)",
+    ),
+    ("cli.config_saved", "Configuración guardada en cherry.env"),
+    ("cli.repo_config_applied", "Se aplicó la configuración del repositorio {owner}/{repo}"),
+    ("cli.authenticated", "Autenticado como: {name} ({login})"),
+    ("cli.using_owner", "Usando owner: {owner}"),
+    ("cli.opening_org_selector", "Abriendo el selector de organizaciones..."),
+    ("cli.probing_orgs", "Contando los repositorios de tus organizaciones..."),
+    ("cli.owner_required", "Hay varias organizaciones y las preguntas están desactivadas; usa --owner para elegir una"),
+    ("cli.selected_owner", "Owner seleccionado: {owner}"),
+    ("cli.using_repo", "Usando el repositorio: {repo}"),
+    ("cli.opening_repo_selector", "Abriendo el selector de repositorios..."),
+    ("cli.repo_quick_entry", "Repositorio de {owner} (nombre u owner/nombre)"),
+    ("cli.repo_quick_entry_placeholder", "Déjalo vacío y pulsa Enter para ver la lista"),
+    ("cli.repo_required", "{owner} tiene varios repositorios y las preguntas están desactivadas; usa --repo para elegir uno"),
+    ("cli.selected_repo", "Repositorio seleccionado: {owner}/{repo}"),
+    ("cli.forking", "Creando fork de {repo}... espera, por favor"),
+    ("cli.fork_own_repo", "{repo} ya te pertenece; no se crea un fork"),
+    ("cli.fork_of", "{repo} es un fork de {parent}"),
+    (
+        "cli.listing_prs_from",
+        "Listando PRs de {owner}/{repo}; las operaciones de git usan el clon local",
+    ),
+    ("cli.pr_open", "Nota: el PR #{number} sigue abierto y sin fusionar; se tomarán sus commits tal como están ahora"),
+    ("cli.pr_closed", "Nota: el PR #{number} se cerró sin fusionarse"),
+    ("cli.pr_mismatch", "El PR #{number} no aparecería en el listado normal:"),
+    ("cli.mismatch_pending_tag", "le falta la etiqueta pendiente \"{tag}\""),
+    ("cli.mismatch_environment_tag", "le falta la etiqueta de entorno \"{tag}\""),
+    ("cli.mismatch_sprint_tag", "ninguna etiqueta coincide con el patrón de sprint {pattern}"),
+    ("cli.mismatch_window", "actualizado por última vez el {updated}, fuera de la ventana de {days} días"),
+    ("cli.mismatch_base", "apunta a {actual} en lugar de {expected}"),
+    ("cli.mismatch_assignees", "no está asignado a {assignees}"),
+    ("cli.pr_mismatch_continue", "Pulsa Enter para continuar de todos modos, o Ctrl+C para cancelar."),
+    ("cli.no_repos", "No se encontraron repositorios para el owner: {owner}"),
+    ("cli.no_repos_forks_only", " (solo repositorios fork)"),
+    (
+        "cli.no_repos_limit_hint",
+        " entre los primeros {limit} repositorios; aumenta ui.discovery_repo_limit o usa --repo",
+    ),
+];
@@ -0,0 +1,111 @@
+//! User-facing message catalog. UI text is looked up by key so it can be
+//! translated; log/tracing output stays English and does not go through here.
+//!
+//! Templates use `{name}` placeholders filled by [`tf`]. A key missing from the
+//! active locale falls back to English, and a key missing from English renders
+//! as the key itself so gaps are visible instead of panicking.
+
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+mod en;
+mod es;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Es,
+}
+
+impl Locale {
+    /// Parses a language setting such as `es`, `es_ES.UTF-8` or `en-US`
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let lang = tag
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match lang.as_str() {
+            "en" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            _ => None,
+        }
+    }
+
+    pub fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => en::CATALOG,
+            Locale::Es => es::CATALOG,
+        }
+    }
+}
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// Picks the locale from the configured language, then `LANG`, then English.
+/// Only the first call has an effect.
+pub fn init(configured: Option<&str>) -> Locale {
+    *LOCALE.get_or_init(|| detect(configured, std::env::var("LANG").ok().as_deref()))
+}
+
+/// Locale resolution used by [`init`], exposed for tests
+pub fn detect(configured: Option<&str>, lang_env: Option<&str>) -> Locale {
+    configured
+        .and_then(Locale::from_tag)
+        .or_else(|| lang_env.and_then(Locale::from_tag))
+        .unwrap_or(Locale::En)
+}
+
+/// Active locale; falls back to `LANG` detection if [`init`] was never called
+pub fn locale() -> Locale {
+    init(None)
+}
+
+/// Template for `key` in `locale`, without English fallback
+pub fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    locale
+        .catalog()
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| *v)
+}
+
+/// Message for `key` in the active locale
+pub fn t(key: &'static str) -> &'static str {
+    lookup(locale(), key)
+        .or_else(|| lookup(Locale::En, key))
+        .unwrap_or(key)
+}
+
+/// Message for `key` with `{name}` placeholders replaced from `args`
+pub fn tf(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    fill(t(key), args)
+}
+
+/// Replaces `{name}` placeholders in `template` in one left-to-right pass, so
+/// text substituted for one placeholder is never scanned for another. Braces
+/// that don't name an argument are kept as written.
+pub fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let arg = after.find('}').and_then(|close| {
+            let name = &after[..close];
+            args.iter().find(|(n, _)| *n == name).map(|(_, value)| (close, value))
+        });
+        match arg {
+            Some((close, value)) => {
+                out.push_str(&value.to_string());
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,62 @@
+//! How a run ended, and the process exit code for it. `main` maps the final
+//! result through [`Outcome`] once instead of picking codes at each failure.
+
+use std::process::ExitCode;
+
+use crate::error::{GitError, GitHubError};
+
+/// Exit codes, as listed in `--help`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// 0: finished normally
+    Success,
+    /// 1: any failure not covered below
+    Error,
+    /// 2: a cherry-pick stopped on conflicts that are still unresolved
+    Conflicts,
+    /// 3: no usable GitHub credentials, or GitHub rejected them
+    AuthFailure,
+    /// 4: no PR or repository matched the given criteria
+    NothingMatched,
+}
+
+/// Error for runs that found nothing to work on; maps to [`Outcome::NothingMatched`]
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct NothingMatched(pub String);
+
+impl Outcome {
+    pub fn code(self) -> u8 {
+        match self {
+            Outcome::Success => 0,
+            Outcome::Error => 1,
+            Outcome::Conflicts => 2,
+            Outcome::AuthFailure => 3,
+            Outcome::NothingMatched => 4,
+        }
+    }
+
+    /// Classifies the error a run ended with by the typed errors in its chain
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        for cause in err.chain() {
+            if let Some(e) = cause.downcast_ref::<GitHubError>() {
+                if matches!(e, GitHubError::Unauthorized { .. } | GitHubError::Auth { .. }) {
+                    return Outcome::AuthFailure;
+                }
+            }
+            if let Some(GitError::Conflicts { .. }) = cause.downcast_ref::<GitError>() {
+                return Outcome::Conflicts;
+            }
+            if cause.is::<NothingMatched>() {
+                return Outcome::NothingMatched;
+            }
+        }
+        Outcome::Error
+    }
+}
+
+impl From<Outcome> for ExitCode {
+    fn from(outcome: Outcome) -> Self {
+        ExitCode::from(outcome.code())
+    }
+}
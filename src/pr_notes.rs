@@ -0,0 +1,76 @@
+//! Free-text notes attached to PRs during triage ("needs DB migration
+//! first"), kept under [`crate::storage::notes_file`] so they outlive the
+//! session. A PR's note goes into its pick comment and session log record.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrNotes {
+    /// Keyed by PR number
+    #[serde(default)]
+    pub notes: BTreeMap<u64, String>,
+}
+
+impl PrNotes {
+    /// Reads the notes; a missing or unreadable file has none
+    pub fn load(path: &Path) -> Self {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                tracing::warn!("Failed to read {}: {}", path.display(), e);
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring unreadable PR notes {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text = serde_json::to_string_pretty(self).context("Failed to serialize PR notes")?;
+        std::fs::write(path, text).with_context(|| format!("Failed to write PR notes {}", path.display()))
+    }
+
+    pub fn get(&self, number: u64) -> Option<&str> {
+        self.notes.get(&number).map(String::as_str)
+    }
+
+    /// Sets the note of PR `number`, keeping only its first line. Blank text
+    /// removes the note. Returns whether the PR has a note now.
+    pub fn set(&mut self, number: u64, text: &str) -> bool {
+        let note = text.lines().next().unwrap_or_default().trim();
+        if note.is_empty() {
+            self.notes.remove(&number);
+            false
+        } else {
+            self.notes.insert(number, note.to_string());
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_are_single_trimmed_lines_and_blank_removes() {
+        let mut notes = PrNotes::default();
+        assert!(notes.set(42, "  needs DB migration first \nsecond line"));
+        assert_eq!(notes.get(42), Some("needs DB migration first"));
+        assert!(notes.set(42, "migration merged"));
+        assert_eq!(notes.get(42), Some("migration merged"));
+        assert!(!notes.set(42, "   "));
+        assert_eq!(notes.get(42), None);
+    }
+}
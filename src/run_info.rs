@@ -0,0 +1,85 @@
+//! Which build of gh_cherry made a pick, for whom and on which machine, so a
+//! bad backport can be traced weeks later. Stamped into session log records
+//! and, as a footer, into the cherry-pick comment.
+
+use serde::{Deserialize, Serialize};
+
+use crate::github::UserInfo;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunInfo {
+    pub version: String,
+    /// GitHub login the run was authenticated as
+    #[serde(default)]
+    pub login: Option<String>,
+    #[serde(default)]
+    pub hostname: Option<String>,
+}
+
+impl RunInfo {
+    pub fn new(version: &str, user: Option<&UserInfo>, hostname: Option<&str>) -> Self {
+        let non_empty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        Self {
+            version: version.to_string(),
+            login: user.and_then(|u| non_empty(&u.login)),
+            hostname: hostname.and_then(non_empty),
+        }
+    }
+
+    /// This build, on this machine
+    pub fn current(user: Option<&UserInfo>) -> Self {
+        Self::new(env!("CARGO_PKG_VERSION"), user, hostname().as_deref())
+    }
+
+    /// `— gh_cherry v0.3.1 by @login` in GitHub's small print
+    pub fn comment_footer(&self) -> String {
+        let by = self
+            .login
+            .as_ref()
+            .map(|login| format!(" by @{}", login))
+            .unwrap_or_default();
+        format!("<sub>— gh_cherry v{}{}</sub>", self.version, by)
+    }
+}
+
+/// The machine's name, from the environment or `/etc/hostname`
+fn hostname() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(login: &str) -> UserInfo {
+        UserInfo {
+            login: login.into(),
+            name: String::new(),
+            email: String::new(),
+        }
+    }
+
+    #[test]
+    fn footer_names_version_and_login() {
+        let run = RunInfo::new("0.3.1", Some(&user("alice")), Some("build-7\n"));
+        assert_eq!(run.comment_footer(), "<sub>— gh_cherry v0.3.1 by @alice</sub>");
+        assert_eq!(run.hostname.as_deref(), Some("build-7"));
+    }
+
+    #[test]
+    fn unknown_user_and_host_are_left_out() {
+        let run = RunInfo::new("0.3.1", Some(&user("")), Some("  "));
+        assert_eq!(run.login, None);
+        assert_eq!(run.hostname, None);
+        assert_eq!(run.comment_footer(), "<sub>— gh_cherry v0.3.1</sub>");
+        assert_eq!(RunInfo::new("0.3.1", None, None).login, None);
+    }
+
+    #[test]
+    fn current_uses_the_crate_version() {
+        assert_eq!(RunInfo::current(None).version, env!("CARGO_PKG_VERSION"));
+    }
+}
@@ -0,0 +1,242 @@
+//! Per-session log of cherry-picks, one JSON [`PickRecord`] per line, kept
+//! under [`crate::storage::sessions_dir`]. Read back by the history screen,
+//! which also re-runs the GitHub bookkeeping a pick didn't finish.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::git::{ConflictSide, SubmoduleUpdate};
+use crate::github::{ApiUsage, PickedCommit};
+use crate::run_info::RunInfo;
+
+/// How a pick ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PickStatus {
+    /// Every commit applied
+    Picked,
+    /// Stopped on conflicts
+    Conflicted,
+    /// Stopped on any other error
+    Failed,
+    /// Deferred by the user without picking
+    Skipped,
+    /// Stopped between commits because the user quit
+    Interrupted,
+    /// A status written by a newer version
+    #[serde(other)]
+    Unknown,
+}
+
+/// One line of a session log. Fields added after the first version carry
+/// serde defaults so older logs still parse; unknown fields are ignored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickRecord {
+    pub pr_number: u64,
+    pub status: PickStatus,
+    #[serde(default)]
+    pub recorded_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub pr_title: String,
+    #[serde(default)]
+    pub target_branch: String,
+    /// Commits applied, including those applied before a pick stopped
+    #[serde(default)]
+    pub commits: Vec<PickedCommit>,
+    #[serde(default)]
+    pub reordered: bool,
+    /// Missing in old logs, which didn't track it; assume the step succeeded
+    #[serde(default = "assume_done")]
+    pub labels_updated: bool,
+    #[serde(default = "assume_done")]
+    pub comment_added: bool,
+    /// Version, login and machine that wrote the record
+    #[serde(default)]
+    pub run: Option<RunInfo>,
+    /// Binary conflicts settled by keeping one side, in the order they were taken
+    #[serde(default)]
+    pub sides_taken: Vec<SideTaken>,
+    /// GitHub writes `--no-mutate-github` only logged, described for the summary
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub simulated: Vec<String>,
+    /// Submodule pointers the pick moved to commits that couldn't be found upstream
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unverified_submodules: Vec<SubmoduleUpdate>,
+    /// GitHub requests the session had made when the record was written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_requests: Option<ApiUsage>,
+    /// Bookkeeping the user chose to leave out of this pick
+    #[serde(default, skip_serializing_if = "HeldBack::is_none")]
+    pub held_back: HeldBack,
+    /// The PR's triage note when it was picked or skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// GitHub updates left out of a pick on purpose, e.g. until QA signs off.
+/// They still count as outstanding, so the history screen can finish them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeldBack {
+    #[serde(default)]
+    pub labels: bool,
+    #[serde(default)]
+    pub comment: bool,
+}
+
+impl HeldBack {
+    pub fn is_none(&self) -> bool {
+        !self.labels && !self.comment
+    }
+}
+
+/// A conflicted file resolved by keeping one version whole
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SideTaken {
+    pub path: String,
+    pub side: ConflictSide,
+}
+
+fn assume_done() -> bool {
+    true
+}
+
+impl PickRecord {
+    /// The commits landed but the labels or the comment didn't
+    pub fn is_partial(&self) -> bool {
+        self.status == PickStatus::Picked && !(self.labels_updated && self.comment_added)
+    }
+
+    /// The labels still need swapping and weren't held back
+    pub fn labels_due(&self) -> bool {
+        !self.labels_updated && !self.held_back.labels
+    }
+
+    /// The pick comment still needs posting and wasn't held back
+    pub fn comment_due(&self) -> bool {
+        !self.comment_added && !self.held_back.comment
+    }
+}
+
+/// Records parsed from a session log, plus how many lines couldn't be
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionRecords {
+    pub records: Vec<PickRecord>,
+    pub skipped_lines: usize,
+}
+
+impl SessionRecords {
+    /// Parses JSONL, skipping blank lines and lines that aren't a record
+    pub fn parse(text: &str) -> Self {
+        let mut parsed = SessionRecords::default();
+        for (number, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(record) => parsed.records.push(record),
+                Err(e) => {
+                    tracing::warn!(line = number + 1, error = %e, "Skipping unreadable session log line");
+                    parsed.skipped_lines += 1;
+                }
+            }
+        }
+        parsed
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read session log {}", path.display()))?;
+        Ok(Self::parse(&text))
+    }
+
+    /// The last record for each PR and target branch, by PR number. A later
+    /// record (a bookkeeping re-run, or a second pick) supersedes earlier ones.
+    pub fn latest(&self) -> Vec<&PickRecord> {
+        let mut latest: HashMap<(u64, &str), &PickRecord> = HashMap::new();
+        for record in &self.records {
+            latest.insert((record.pr_number, record.target_branch.as_str()), record);
+        }
+        let mut records: Vec<&PickRecord> = latest.into_values().collect();
+        records.sort_by_key(|r| (r.pr_number, r.target_branch.clone()));
+        records
+    }
+
+    /// Picks whose GitHub bookkeeping is still outstanding
+    pub fn partial(&self) -> Vec<&PickRecord> {
+        self.latest().into_iter().filter(|r| r.is_partial()).collect()
+    }
+}
+
+/// One line per session on the history screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSummary {
+    pub path: PathBuf,
+    /// Time of the first record, if it has one
+    pub started_at: Option<DateTime<Utc>>,
+    pub target_branches: Vec<String>,
+    /// Counts over the last record per PR and branch, so re-runs aren't counted twice
+    pub picked: usize,
+    pub failed: usize,
+    pub partial: usize,
+}
+
+impl SessionSummary {
+    pub fn new(path: PathBuf, session: &SessionRecords) -> Self {
+        let mut target_branches: Vec<String> = Vec::new();
+        for record in &session.records {
+            if !record.target_branch.is_empty() && !target_branches.contains(&record.target_branch) {
+                target_branches.push(record.target_branch.clone());
+            }
+        }
+        let latest = session.latest();
+        Self {
+            path,
+            started_at: session.records.iter().find_map(|r| r.recorded_at),
+            target_branches,
+            picked: latest.iter().filter(|r| r.status == PickStatus::Picked).count(),
+            failed: latest
+                .iter()
+                .filter(|r| {
+                    matches!(
+                        r.status,
+                        PickStatus::Conflicted | PickStatus::Failed | PickStatus::Interrupted
+                    )
+                })
+                .count(),
+            partial: latest.iter().filter(|r| r.is_partial()).count(),
+        }
+    }
+}
+
+/// Appends records to one session log. The file is created on the first
+/// append, so runs that pick nothing leave no empty logs behind.
+#[derive(Debug, Clone)]
+pub struct SessionLog {
+    path: PathBuf,
+}
+
+impl SessionLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn append(&self, record: &PickRecord) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let mut line = serde_json::to_string(record).context("Failed to serialize pick record")?;
+        line.push('\n');
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .with_context(|| format!("Failed to write session log {}", self.path.display()))
+    }
+}
@@ -0,0 +1,141 @@
+//! Who has backports waiting: the loaded PRs grouped by author, for the stats
+//! screen and `--stats`.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::github::PrInfo;
+
+/// One author's pending PRs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorStats {
+    pub author: String,
+    pub prs: usize,
+    /// When the longest-waiting of them was opened
+    pub oldest: DateTime<Utc>,
+    pub commits: usize,
+}
+
+/// Authors with the most pending PRs first. Ties go to whoever has waited
+/// longest, then by name, so the order is the same on every load.
+pub fn author_stats(prs: &[PrInfo]) -> Vec<AuthorStats> {
+    let mut by_author: HashMap<&str, AuthorStats> = HashMap::new();
+    for pr in prs {
+        let entry = by_author.entry(&pr.author).or_insert_with(|| AuthorStats {
+            author: pr.author.clone(),
+            prs: 0,
+            oldest: pr.created_at,
+            commits: 0,
+        });
+        entry.prs += 1;
+        entry.oldest = entry.oldest.min(pr.created_at);
+        entry.commits += pr.commits.len();
+    }
+
+    let mut stats: Vec<AuthorStats> = by_author.into_values().collect();
+    stats.sort_by(|a, b| {
+        b.prs
+            .cmp(&a.prs)
+            .then(a.oldest.cmp(&b.oldest))
+            .then_with(|| a.author.cmp(&b.author))
+    });
+    stats
+}
+
+/// The table as aligned plain text, headed by `header` (author, PRs, oldest,
+/// commits), for printing or drawing one line per row
+pub fn stats_lines(stats: &[AuthorStats], header: [&str; 4]) -> Vec<String> {
+    let width = stats
+        .iter()
+        .map(|s| s.author.chars().count())
+        .chain([header[0].chars().count()])
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![format!(
+        "{:<width$}  {:>5}  {:<10}  {:>7}",
+        header[0],
+        header[1],
+        header[2],
+        header[3],
+        width = width
+    )];
+    lines.extend(stats.iter().map(|s| {
+        format!(
+            "{:<width$}  {:>5}  {:<10}  {:>7}",
+            s.author,
+            s.prs,
+            s.oldest.format("%Y-%m-%d"),
+            s.commits,
+            width = width
+        )
+    }));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{CommitInfo, PrState};
+    use chrono::TimeZone;
+
+    fn day(n: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, n, 12, 0, 0).unwrap()
+    }
+
+    fn pr(author: &str, created: u32, commits: usize) -> PrInfo {
+        PrInfo {
+            number: 1,
+            author: author.into(),
+            created_at: day(created),
+            updated_at: day(created),
+            commits: (0..commits)
+                .map(|i| CommitInfo {
+                    sha: format!("{:040}", i),
+                    message: String::new(),
+                    author: author.into(),
+                    date: day(created),
+                })
+                .collect(),
+            base_ref: "main".into(),
+            head_ref: "feature".into(),
+            state: PrState::Merged,
+            ..Default::default()
+        }
+    }
+
+    fn authors(stats: &[AuthorStats]) -> Vec<&str> {
+        stats.iter().map(|s| s.author.as_str()).collect()
+    }
+
+    #[test]
+    fn no_prs_means_no_rows_but_still_a_header() {
+        let stats = author_stats(&[]);
+        assert!(stats.is_empty());
+        assert_eq!(stats_lines(&stats, ["Author", "PRs", "Oldest", "Commits"]).len(), 1);
+    }
+
+    #[test]
+    fn authors_are_counted_and_ranked_by_pending_prs() {
+        let prs = [
+            pr("bob", 9, 1),
+            pr("alice", 5, 2),
+            pr("bob", 3, 4),
+        ];
+        let stats = author_stats(&prs);
+        assert_eq!(authors(&stats), ["bob", "alice"]);
+        assert_eq!(stats[0].prs, 2);
+        assert_eq!(stats[0].oldest, day(3));
+        assert_eq!(stats[0].commits, 5);
+
+        let lines = stats_lines(&stats, ["Author", "PRs", "Oldest", "Commits"]);
+        assert_eq!(lines[0], "Author    PRs  Oldest      Commits");
+        assert_eq!(lines[1], "bob         2  2025-03-03        5");
+    }
+
+    #[test]
+    fn ties_go_to_the_longest_waiting_then_by_name() {
+        let prs = [pr("carol", 4, 1), pr("bob", 2, 1), pr("alice", 4, 1)];
+        assert_eq!(authors(&author_stats(&prs)), ["bob", "alice", "carol"]);
+    }
+}
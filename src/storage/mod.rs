@@ -0,0 +1,177 @@
+//! Central place for every on-disk path the tool uses.
+//!
+//! Nothing outside this module should build paths under the user's config/cache/state
+//! directories by hand; per-repository data lives in a subdirectory named by [`repo_slug`].
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const APP_DIR: &str = "gh_cherry";
+const SESSIONS_DIR: &str = "sessions";
+
+/// Directory holding the global `config.toml`
+pub fn config_dir() -> Result<PathBuf> {
+    Ok(dirs::config_dir()
+        .context("Failed to get config directory")?
+        .join(APP_DIR))
+}
+
+/// Default location of the global config file
+pub fn config_file() -> Result<PathBuf> {
+    Ok(config_dir()?.join("config.toml"))
+}
+
+/// Root for disposable data (API response caches). Safe to delete at any time.
+pub fn cache_dir() -> Result<PathBuf> {
+    Ok(dirs::cache_dir()
+        .context("Failed to get cache directory")?
+        .join(APP_DIR))
+}
+
+/// Root for data that should survive restarts (saved state, resume files, session logs).
+pub fn state_dir() -> Result<PathBuf> {
+    // `dirs::state_dir` is Linux-only; fall back to the local data dir elsewhere
+    let base = dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .context("Failed to get state directory")?;
+    Ok(base.join(APP_DIR))
+}
+
+/// Filesystem-safe key for a repository, used to namespace per-repo subdirectories
+pub fn repo_slug(owner: &str, repo: &str) -> String {
+    let sanitize = |s: &str| -> String {
+        s.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    };
+    format!("{}__{}", sanitize(owner), sanitize(repo))
+}
+
+/// Per-repository state directory
+pub fn repo_state_dir(owner: &str, repo: &str) -> Result<PathBuf> {
+    Ok(state_dir()?.join(repo_slug(owner, repo)))
+}
+
+/// Per-repository session log directory
+pub fn sessions_dir(owner: &str, repo: &str) -> Result<PathBuf> {
+    Ok(repo_state_dir(owner, repo)?.join(SESSIONS_DIR))
+}
+
+/// Target branches this repository was picked onto, for the target selector
+pub fn targets_file(owner: &str, repo: &str) -> Result<PathBuf> {
+    Ok(repo_state_dir(owner, repo)?.join("targets.json"))
+}
+
+/// Notes attached to this repository's PRs
+pub fn notes_file(owner: &str, repo: &str) -> Result<PathBuf> {
+    Ok(repo_state_dir(owner, repo)?.join("notes.json"))
+}
+
+/// Log file for a session started at `started`. Names sort in start order.
+pub fn session_file(owner: &str, repo: &str, started: DateTime<Utc>) -> Result<PathBuf> {
+    Ok(sessions_dir(owner, repo)?.join(format!("{}.jsonl", started.format("%Y-%m-%dT%H-%M-%SZ"))))
+}
+
+/// Session logs recorded for a repository, newest first
+pub fn list_sessions(owner: &str, repo: &str) -> Result<Vec<PathBuf>> {
+    list_session_files(&sessions_dir(owner, repo)?)
+}
+
+/// The `.jsonl` files directly in `dir`, newest name first. A missing `dir` is not an error.
+pub fn list_session_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "jsonl") {
+            files.push(path);
+        }
+    }
+    files.sort_by(|a, b| b.cmp(a));
+    Ok(files)
+}
+
+/// Removes expired cache entries and session logs for every repository.
+/// Saved state and resume files are never pruned. Returns the removed paths.
+pub fn prune_expired(retention_days: u32) -> Result<Vec<PathBuf>> {
+    let cutoff = retention_cutoff(retention_days);
+    let mut removed = prune_older_than(&cache_dir()?, cutoff)?;
+    removed.extend(prune_sessions(cutoff)?);
+    Ok(removed)
+}
+
+/// Deletes the whole cache directory and the session logs older than
+/// `retention_days` (`--clean-cache`). Returns the removed paths.
+pub fn clean_cache(retention_days: u32) -> Result<Vec<PathBuf>> {
+    let mut removed = prune_older_than(&cache_dir()?, SystemTime::now() + Duration::from_secs(60))?;
+    removed.extend(prune_sessions(retention_cutoff(retention_days))?);
+    Ok(removed)
+}
+
+fn retention_cutoff(retention_days: u32) -> SystemTime {
+    let retention = Duration::from_secs(u64::from(retention_days) * 24 * 60 * 60);
+    SystemTime::now()
+        .checked_sub(retention)
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// Session logs of every repository last modified before `cutoff`
+fn prune_sessions(cutoff: SystemTime) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+    let state_root = state_dir()?;
+    if state_root.is_dir() {
+        for entry in std::fs::read_dir(&state_root)
+            .with_context(|| format!("Failed to read {}", state_root.display()))?
+        {
+            let sessions = entry?.path().join(SESSIONS_DIR);
+            removed.extend(prune_older_than(&sessions, cutoff)?);
+        }
+    }
+    Ok(removed)
+}
+
+/// Removes every file under `root` last modified before `cutoff`, then any directories
+/// left empty. `root` itself is kept. A missing `root` is not an error.
+pub fn prune_older_than(root: &Path, cutoff: SystemTime) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+    if root.is_dir() {
+        prune_dir(root, cutoff, &mut removed)?;
+    }
+    Ok(removed)
+}
+
+fn prune_dir(dir: &Path, cutoff: SystemTime, removed: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            prune_dir(&path, cutoff, removed)?;
+            if std::fs::read_dir(&path)?.next().is_none() {
+                std::fs::remove_dir(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+        } else {
+            let modified = entry.metadata()?.modified()?;
+            if modified < cutoff {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+                removed.push(path);
+            }
+        }
+    }
+    Ok(())
+}
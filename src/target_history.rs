@@ -0,0 +1,218 @@
+//! Target branches this repository was picked onto before, kept under
+//! [`crate::storage::targets_file`] so the target selector can offer them
+//! ahead of the full branch list. Pinned branches always come first.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How many unpinned branches the "Recent" section keeps
+pub const RECENT_LIMIT: usize = 8;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TargetHistory {
+    #[serde(default)]
+    pub targets: Vec<TargetUse>,
+}
+
+/// One branch picked onto, or pinned, before
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetUse {
+    pub branch: String,
+    /// `None` for a branch pinned before it was ever used
+    #[serde(default)]
+    pub last_used: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+impl TargetHistory {
+    /// Reads the history; a missing or unreadable file is an empty history
+    pub fn load(path: &Path) -> Self {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                tracing::warn!("Failed to read {}: {}", path.display(), e);
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring unreadable target history {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text = serde_json::to_string_pretty(self).context("Failed to serialize target history")?;
+        std::fs::write(path, text)
+            .with_context(|| format!("Failed to write target history {}", path.display()))
+    }
+
+    /// Notes that `branch` was picked onto at `at`. Unpinned branches beyond
+    /// the recent limit are forgotten, oldest first.
+    pub fn record_use(&mut self, branch: &str, at: DateTime<Utc>) {
+        match self.targets.iter_mut().find(|t| t.branch == branch) {
+            Some(target) => target.last_used = Some(at),
+            None => self.targets.push(TargetUse {
+                branch: branch.to_string(),
+                last_used: Some(at),
+                pinned: false,
+            }),
+        }
+        let keep: Vec<String> = self
+            .ranked()
+            .into_iter()
+            .filter(|t| !t.pinned)
+            .take(RECENT_LIMIT)
+            .map(|t| t.branch.clone())
+            .collect();
+        self.targets.retain(|t| t.pinned || keep.contains(&t.branch));
+    }
+
+    /// Pins `branch`, or unpins it if it was pinned. Returns whether it's pinned now.
+    pub fn toggle_pin(&mut self, branch: &str) -> bool {
+        match self.targets.iter_mut().find(|t| t.branch == branch) {
+            Some(target) => {
+                target.pinned = !target.pinned;
+                target.pinned
+            }
+            None => {
+                self.targets.push(TargetUse {
+                    branch: branch.to_string(),
+                    last_used: None,
+                    pinned: true,
+                });
+                true
+            }
+        }
+    }
+
+    pub fn is_pinned(&self, branch: &str) -> bool {
+        self.targets.iter().any(|t| t.pinned && t.branch == branch)
+    }
+
+    /// Pinned branches, then the rest; most recently used first within each,
+    /// never-used pins last in name order
+    pub fn ranked(&self) -> Vec<&TargetUse> {
+        let mut ranked: Vec<&TargetUse> = self.targets.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then_with(|| b.last_used.cmp(&a.last_used))
+                .then_with(|| a.branch.cmp(&b.branch))
+        });
+        ranked
+    }
+}
+
+/// A row of the target selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetRow {
+    /// Section title; can't be selected
+    Header(TargetSection),
+    Branch(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetSection {
+    Recent,
+    All,
+}
+
+/// The selector's rows: a "Recent" section with the history's ranked
+/// branches, then every other branch in `branches` under "All branches".
+/// A section with no branches is left out.
+pub fn target_rows(history: &TargetHistory, branches: &[String]) -> Vec<TargetRow> {
+    let recent: Vec<&str> = history.ranked().into_iter().map(|t| t.branch.as_str()).collect();
+    let others: Vec<&String> = branches.iter().filter(|b| !recent.contains(&b.as_str())).collect();
+
+    let mut rows = Vec::new();
+    if !recent.is_empty() {
+        rows.push(TargetRow::Header(TargetSection::Recent));
+        rows.extend(recent.iter().map(|b| TargetRow::Branch(b.to_string())));
+    }
+    if !others.is_empty() {
+        rows.push(TargetRow::Header(TargetSection::All));
+        rows.extend(others.into_iter().map(|b| TargetRow::Branch(b.clone())));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+
+    fn day(n: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 8, 1, 12, 0, 0).unwrap() + Duration::days(n)
+    }
+
+    fn branches(history: &TargetHistory) -> Vec<&str> {
+        history.ranked().into_iter().map(|t| t.branch.as_str()).collect()
+    }
+
+    #[test]
+    fn most_recent_first_with_pins_ahead() {
+        let mut history = TargetHistory::default();
+        history.record_use("release/2025.07", day(0));
+        history.record_use("release/2025.08", day(2));
+        history.record_use("hotfix", day(1));
+        assert_eq!(branches(&history), ["release/2025.08", "hotfix", "release/2025.07"]);
+
+        assert!(history.toggle_pin("release/2025.07"));
+        assert!(history.toggle_pin("release/lts"));
+        assert_eq!(
+            branches(&history),
+            ["release/2025.07", "release/lts", "release/2025.08", "hotfix"]
+        );
+
+        // Using a branch again moves it up; unpinning drops it back among the rest
+        history.record_use("hotfix", day(3));
+        assert!(!history.toggle_pin("release/2025.07"));
+        assert_eq!(
+            branches(&history),
+            ["release/lts", "hotfix", "release/2025.08", "release/2025.07"]
+        );
+    }
+
+    #[test]
+    fn old_unpinned_branches_are_forgotten() {
+        let mut history = TargetHistory::default();
+        history.toggle_pin("release/0");
+        for n in 0..=RECENT_LIMIT as i64 + 1 {
+            history.record_use(&format!("release/{}", n), day(n));
+        }
+        assert_eq!(history.targets.len(), RECENT_LIMIT + 1);
+        // The pinned branch stays even though it's the oldest
+        assert!(history.is_pinned("release/0"));
+        assert!(!branches(&history).contains(&"release/1"));
+    }
+
+    #[test]
+    fn recent_branches_come_before_the_rest_under_headers() {
+        let mut history = TargetHistory::default();
+        history.record_use("release/2025.08", day(0));
+        let all = vec!["main".to_string(), "release/2025.08".to_string()];
+        assert_eq!(
+            target_rows(&history, &all),
+            [
+                TargetRow::Header(TargetSection::Recent),
+                TargetRow::Branch("release/2025.08".into()),
+                TargetRow::Header(TargetSection::All),
+                TargetRow::Branch("main".into()),
+            ]
+        );
+        assert_eq!(
+            target_rows(&TargetHistory::default(), &all[..1]),
+            [TargetRow::Header(TargetSection::All), TargetRow::Branch("main".into())]
+        );
+    }
+}
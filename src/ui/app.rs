@@ -1,94 +1,343 @@
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+use chrono::Utc;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::Frame;
+use regex::Regex;
+
+use crate::codeowners::{self, CodeOwners};
+use crate::config::{Config, GitHubConfig};
+use crate::error::GitHubError;
+use crate::git::{ConflictSide, GitOperations, HeadState, SubmoduleUpdate, UnpushedCommit, WorktreePick, WorktreePicker};
+use crate::hook::{self, HookContext};
+use crate::github::{
+    self as gh, ApiUsage, Capabilities, CommitPickOutcome, DeferralReason, GitHubClient, GitHubOps, Listing, PickedCommit, PrInfo, RepoSlug,
+    SimulatedOps, UserInfo,
+};
+use crate::messages::{t, tf};
+use crate::outcome::Outcome;
+use crate::pr_notes::PrNotes;
+use crate::run_info::RunInfo;
+use crate::session_log::{HeldBack, PickRecord, PickStatus, SessionLog, SessionRecords, SessionSummary};
+use crate::storage;
+use crate::target_history::{target_rows, TargetHistory};
+use crate::util::{open_in_browser, short_sha};
+use crate::workflow::{
+    self, BranchPlan, BranchPresence, CancelFlag, ExistingBranchChoice, ExistingBranchPolicy, PickSource, TargetOutcome,
+};
+
+use super::components::{
+    ConflictList, ErrorView, History, HookOutputView, MainMenu, PrDetail, PrList, ProgressView, SessionDetail, StatsView, TagList,
+    TargetSelector,
+};
+use super::auto_refresh::AutoRefresh;
+use super::env_watch::EnvWatch;
+use super::clipboard::{self, Clipboard, SystemClipboard};
+use super::criteria::Criteria;
+use super::keymap::{self, COPY_KEY};
+use super::listing_changes::ListingChanges;
+use super::shutdown::QuitAction;
+use super::state::{
+    AppState, GitOperation, HookPanel, JumpTarget, PendingConfirmation, PromptKind, Screen, SessionPick, TargetRun,
 };
-use ratatui::{backend::CrosstermBackend, Frame, Terminal};
-use std::io;
+use super::terminal::TerminalSession;
 
-use crate::config::Config;
-use crate::git::GitOperations;
-use crate::github::GitHubClient;
-use crate::util::short_sha;
+/// What the background check learned about the listed PRs, keyed by head sha
+#[derive(Debug, Default)]
+struct LocalCheck {
+    /// Whether the head commit exists in the local clone
+    available: HashMap<String, bool>,
+    /// Whether the PR's commits would pick onto the target branch without conflicts
+    clean: HashMap<String, bool>,
+}
 
-use super::components::{MainMenu, PrList, ProgressView};
-use super::state::{AppState, Screen};
+/// The authenticated user; a failed lookup only leaves records without a login
+async fn lookup_user(client: GitHubClient) -> Option<UserInfo> {
+    client
+        .get_authenticated_user()
+        .await
+        .map_err(|e| tracing::warn!("Could not look up the authenticated user: {}", e))
+        .ok()
+}
 
 pub struct App {
     state: AppState,
     github_client: GitHubClient,
+    /// Set by `--no-mutate-github`: label, comment and PR writes are recorded here instead of sent
+    simulated: Option<SimulatedOps<GitHubClient>>,
+    /// Repository PRs are listed from; an upstream when `config.github` is a fork
+    pr_source: RepoSlug,
     git_ops: GitOperations,
+    /// Background lookup of which PR head commits exist locally
+    availability_check: Option<tokio::task::JoinHandle<LocalCheck>>,
+    /// PR requested with `--pr`; loaded directly instead of the filtered listing
+    explicit_pr: Option<u64>,
+    /// The last successful PR load came back empty
+    listing_empty: bool,
+    /// Branch checked out when the app started; `abort_session` returns to it
+    session_start_branch: Option<String>,
+    /// Where this session's picks are recorded; `None` if there's no state directory
+    session_log: Option<SessionLog>,
+    /// Version, login and host stamped on records and comments; the login is
+    /// looked up in the background once the first frame is drawn
+    run_info: Option<RunInfo>,
+    /// Background lookup of the authenticated user behind `run_info`
+    user_lookup: Option<tokio::task::JoinHandle<Option<UserInfo>>>,
+    /// When startup began, until the first frame is drawn
+    first_frame: Option<Instant>,
+    /// The terminal while the TUI is up
+    session: Option<TerminalSession>,
+    /// Where the copy key puts identifiers
+    clipboard: Box<dyn Clipboard>,
+    /// Set by `--open-sso`: open the SSO authorization page as soon as GitHub asks for it
+    open_sso: bool,
+    /// Set by `--verbose`: note the GitHub requests each load made
+    report_api_usage: bool,
+    /// Keys typed while a pick ran, handled once it's done
+    held_keys: VecDeque<KeyEvent>,
+    /// Printed after the terminal is restored when quitting cut a pick short
+    exit_note: Option<String>,
+    /// A second Ctrl+C quit while a pick's git step was still running
+    abandoned_pick: bool,
+    /// Set with `ui.watch_env`: cherry.env is read again when it changes
+    env_watch: Option<EnvWatch>,
+    /// Set by `--on-existing-branch`: what to do with a backport branch that
+    /// already exists instead of asking
+    on_existing_branch: Option<ExistingBranchPolicy>,
     config: Config,
     should_quit: bool,
 }
 
 impl App {
-    pub async fn new(config: Config) -> Result<Self> {
+    /// The app for `config`, talking to GitHub through `github_client`
+    pub fn new(config: Config, github_client: GitHubClient) -> Result<Self> {
         // Validate configuration
         config.validate()?;
 
-        // Initialize GitHub client
-        let github_client = GitHubClient::new(config.clone()).await?;
+        let pr_source = RepoSlug::pr_source(&config);
 
-        // Initialize Git operations
-        let git_ops = GitOperations::discover()?;
+        let git_ops = configured_git_ops(&config)?;
+        // A detached HEAD has no branch to return to when the session is aborted
+        let session_start_branch = match git_ops.head_state() {
+            Ok(HeadState::Branch(name)) => Some(name),
+            _ => None,
+        };
+        let session_log = match storage::session_file(&config.github.owner, &config.github.repo, Utc::now()) {
+            Ok(path) => Some(SessionLog::new(path)),
+            Err(e) => {
+                tracing::warn!("Session log disabled: {}", e);
+                None
+            }
+        };
+
+        let mut state = AppState::new();
+        state.auto_refresh = AutoRefresh::new(config.ui.auto_refresh_secs, Instant::now());
+        state.text_match = config.ui.text_match();
+        if config.github.repo_path.is_some() {
+            state.repo_path = git_ops.workdir_elsewhere().map(|p| p.display().to_string());
+        }
+        if let Ok(path) = storage::targets_file(&config.github.owner, &config.github.repo) {
+            state.target_history = TargetHistory::load(&path);
+        }
+        if let Ok(path) = storage::notes_file(&config.github.owner, &config.github.repo) {
+            state.pr_notes = PrNotes::load(&path);
+        }
 
         Ok(Self {
-            state: AppState::new(),
+            state,
             github_client,
+            simulated: None,
+            pr_source,
             git_ops,
+            availability_check: None,
+            explicit_pr: None,
+            listing_empty: false,
+            session_start_branch,
+            session_log,
+            run_info: None,
+            user_lookup: None,
+            first_frame: Some(Instant::now()),
+            session: None,
+            clipboard: Box::new(SystemClipboard),
+            open_sso: false,
+            report_api_usage: false,
+            held_keys: VecDeque::new(),
+            exit_note: None,
+            abandoned_pick: false,
+            env_watch: None,
+            on_existing_branch: None,
             config,
             should_quit: false,
         })
     }
 
-    pub async fn run(&mut self) -> Result<()> {
-        // Setup terminal
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+    /// Measures time to first frame from `launched` rather than from `new`
+    pub fn trace_first_frame(&mut self, launched: Instant) {
+        self.first_frame = Some(launched);
+    }
+
+    /// Stamps records and comments with `user`, already looked up during startup
+    pub fn know_user(&mut self, user: &UserInfo) {
+        self.run_info = Some(RunInfo::current(Some(user)));
+    }
+
+    /// What a quit during a pick left undone, for after the terminal is restored
+    pub fn exit_note(&self) -> Option<&str> {
+        self.exit_note.as_deref()
+    }
+
+    /// Shows only PR `number`, fetched directly so the listing filters don't hide it
+    pub fn focus_pr(&mut self, number: u64) {
+        self.explicit_pr = Some(number);
+    }
+
+    /// Opens the SSO authorization page without waiting for `o` on the error screen
+    pub fn open_sso_automatically(&mut self) {
+        self.open_sso = true;
+    }
+
+    /// Handles a backport branch that already exists as `policy` says
+    /// instead of asking
+    pub fn on_existing_branch(&mut self, policy: ExistingBranchPolicy) {
+        self.on_existing_branch = Some(policy);
+    }
+
+    /// Reads the cherry.env in `project_dir` again whenever it changes
+    pub fn watch_env(&mut self, project_dir: Option<PathBuf>) {
+        self.env_watch = Some(EnvWatch::new(project_dir, Instant::now()));
+    }
+
+    /// Notes the GitHub requests each load made next to its status
+    pub fn report_api_usage(&mut self) {
+        self.report_api_usage = true;
+    }
+
+    /// GitHub requests made so far this session, per category
+    pub fn api_usage(&self) -> ApiUsage {
+        self.github_client.api_usage()
+    }
+
+    /// Logs GitHub writes instead of sending them; git operations are unaffected
+    pub fn simulate_github_mutations(&mut self) {
+        self.simulated = Some(SimulatedOps::new(self.github_client.clone()));
+    }
+
+    /// Picks only report the commits they would apply: no git operation runs,
+    /// and any other GitHub write is simulated as with [`Self::simulate_github_mutations`]
+    pub fn dry_run(&mut self) {
+        self.state.dry_run = true;
+        self.simulate_github_mutations();
+    }
+
+    /// Where label, comment and PR writes go
+    fn mutations(&self) -> &dyn GitHubOps {
+        match &self.simulated {
+            Some(simulated) => simulated,
+            None => &self.github_client,
+        }
+    }
+
+    /// Descriptions of the writes simulated since the last call; empty when
+    /// writes are really sent
+    fn take_simulated(&self) -> Vec<String> {
+        self.simulated
+            .as_ref()
+            .map(|simulated| simulated.take_recorded().iter().map(ToString::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    /// Runs the TUI until the user quits. The outcome is `Conflicts` when a pick
+    /// is still stopped on conflicts and `NothingMatched` when no PR was listed.
+    pub async fn run(&mut self) -> Result<Outcome> {
+        let session = TerminalSession::start()?;
+        self.state.terminal_caps = session.capabilities();
+        self.session = Some(session);
 
         // Load initial data
+        self.probe_capabilities().await;
         self.load_prs().await?;
+        if !self.config.env_warnings.is_empty() {
+            let warnings = self.config.env_warnings.join("; ");
+            self.state
+                .set_success(&tf("status.env_warnings", &[("warnings", &warnings)]));
+        }
+        if let Ok(HeadState::Detached(sha)) = self.git_ops.head_state() {
+            tracing::warn!(sha = %sha, "Started with a detached HEAD");
+            self.state
+                .set_success(&tf("status.detached_head", &[("sha", &short_sha(&sha))]));
+        }
+        self.show_leftover_pick();
 
         // Main loop
-        let result = self.run_app(&mut terminal).await;
+        let result = self.run_app().await;
 
-        // Restore terminal
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
+        if let Some(mut session) = self.session.take() {
+            session.restore()?;
+        }
 
-        result
+        result?;
+        Ok(if self.git_ops.cherry_pick_in_progress() {
+            Outcome::Conflicts
+        } else if self.listing_empty {
+            Outcome::NothingMatched
+        } else {
+            Outcome::Success
+        })
     }
 
-    async fn run_app<B: ratatui::backend::Backend>(
-        &mut self,
-        terminal: &mut Terminal<B>,
-    ) -> Result<()> {
+    async fn run_app(&mut self) -> Result<()> {
         loop {
-            terminal.draw(|f| self.ui(f))?;
-
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match self.handle_key_event(key).await {
-                        Ok(should_continue) => {
-                            if !should_continue {
-                                break;
-                            }
-                        }
-                        Err(e) => {
-                            self.state.set_error(format!("Error: {}", e));
-                        }
+            self.collect_availability().await;
+            self.collect_user_lookup().await;
+            if let Err(e) = self.advance_target_run().await {
+                self.state.set_error(tf("error.generic", &[("error", &e)]));
+            }
+            if let Err(e) = self.advance_pick_queue().await {
+                self.state.set_error(tf("error.generic", &[("error", &e)]));
+            }
+            // A queued pick may have been told to quit
+            if self.should_quit {
+                break;
+            }
+            self.auto_refresh_if_due().await;
+            self.reload_env_if_changed().await;
+            self.draw()?;
+            if let Some(launched) = self.first_frame.take() {
+                tracing::info_span!("time_to_first_frame").in_scope(|| {
+                    tracing::info!(elapsed_ms = launched.elapsed().as_millis() as u64, "First frame drawn");
+                });
+                self.spawn_user_lookup();
+            }
+
+            let key = match self.held_keys.pop_front() {
+                Some(key) => key,
+                None => {
+                    // Poll so background results and the refresh countdown get drawn
+                    if !event::poll(Duration::from_millis(100))? {
+                        continue;
+                    }
+                    match event::read()? {
+                        Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                        _ => continue,
+                    }
+                }
+            };
+            self.state.auto_refresh.note_input(Instant::now());
+            match self.handle_key_event(key).await {
+                Ok(should_continue) => {
+                    if !should_continue {
+                        break;
                     }
                 }
+                Err(e) => {
+                    self.state.set_error(tf("error.generic", &[("error", &e)]));
+                }
             }
 
             if self.should_quit {
@@ -99,6 +348,17 @@ impl App {
         Ok(())
     }
 
+    /// Draws the current screen, outside the main loop too, e.g. while a
+    /// listing waits out a rate limit
+    fn draw(&mut self) -> Result<()> {
+        if let Some(mut session) = self.session.take() {
+            let drawn = session.terminal().draw(|f| self.ui(f)).map(|_| ());
+            self.session = Some(session);
+            drawn?;
+        }
+        Ok(())
+    }
+
     fn ui(&self, f: &mut Frame) {
         match &self.state.current_screen {
             Screen::MainMenu => {
@@ -107,45 +367,63 @@ impl App {
             Screen::PrList => {
                 PrList::render(f, &self.state, &self.config);
             }
+            Screen::PrDetail => {
+                PrDetail::render(f, &self.state, &self.config);
+            }
             Screen::Progress => {
                 ProgressView::render(f, &self.state);
             }
             Screen::Error => {
-                self.render_error(f);
+                ErrorView::render(f, &self.state);
+            }
+            Screen::TagList => {
+                TagList::render(f, &self.state);
+            }
+            Screen::History => {
+                History::render(f, &self.state);
+            }
+            Screen::SessionDetail => {
+                SessionDetail::render(f, &self.state);
+            }
+            Screen::Conflicts => {
+                ConflictList::render(f, &self.state);
+            }
+            Screen::HookOutput => {
+                HookOutputView::render(f, &self.state);
+            }
+            Screen::TargetSelect => {
+                TargetSelector::render(f, &self.state, &self.config);
+            }
+            Screen::Stats => {
+                StatsView::render(f, &self.state);
             }
         }
     }
 
-    fn render_error(&self, f: &mut Frame) {
-        use ratatui::{
-            layout::{Constraint, Direction, Layout},
-            style::{Color, Style},
-            widgets::{Paragraph, Wrap},
-        };
-
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(2)
-            .constraints([Constraint::Percentage(100)].as_ref())
-            .split(f.area());
-
-        let error_message = self
-            .state
-            .error_message
-            .as_deref()
-            .unwrap_or("Unknown error");
-        let paragraph = Paragraph::new(error_message)
-            .style(Style::default().fg(Color::Red))
-            .wrap(Wrap { trim: true });
-
-        f.render_widget(paragraph, chunks[0]);
-    }
-
-    async fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) -> Result<bool> {
+    async fn handle_key_event(&mut self, key: KeyEvent) -> Result<bool> {
         let code = key.code;
+        // Raw mode turns Ctrl+C into a key, so it has to quit by hand
+        if quit_key(&key) == Some(true) {
+            self.should_quit = true;
+            return Ok(false);
+        }
         if self.state.input_active {
             // Inline prompt editing
             match code {
+                KeyCode::Enter if self.state.prompt_kind == PromptKind::JumpToPr => {
+                    self.submit_jump();
+                }
+                KeyCode::Enter if matches!(self.state.prompt_kind, PromptKind::Criteria(_)) => {
+                    if let Some(criteria) = self.state.submit_criterion() {
+                        self.apply_criteria(criteria).await?;
+                    }
+                }
+                KeyCode::Enter if matches!(self.state.prompt_kind, PromptKind::Note(_)) => {
+                    self.submit_note();
+                }
+                KeyCode::Enter if self.state.prompt_kind == PromptKind::BranchName => {
+                    self.submit_branch_rename().await?;
+                }
                 KeyCode::Enter => {
                     let value = self.state.confirm_prompt();
                     // For now used as filter input when on PR list
@@ -172,6 +450,63 @@ impl App {
             return Ok(true);
         }
 
+        if let Some(confirmation) = self.state.confirmation.clone() {
+            match code {
+                KeyCode::Char('r') | KeyCode::Char('R')
+                    if matches!(confirmation, PendingConfirmation::UnpushedCommits { .. }) =>
+                {
+                    self.state.confirmation = None;
+                    self.reset_and_pick(confirmation).await?;
+                }
+                KeyCode::Char('d') | KeyCode::Char('D')
+                    if matches!(confirmation, PendingConfirmation::ExistingBranch { .. }) =>
+                {
+                    self.state.confirmation = None;
+                    self.pick_onto_existing_branch(confirmation, ExistingBranchChoice::Recreate).await?;
+                }
+                KeyCode::Char('e') | KeyCode::Char('E')
+                    if matches!(confirmation, PendingConfirmation::ExistingBranch { .. }) =>
+                {
+                    self.state.confirmation = None;
+                    self.start_branch_rename(confirmation);
+                }
+                KeyCode::Char(c @ ('l' | 'L' | 'm' | 'M')) => {
+                    self.state.toggle_pick_option(c);
+                }
+                KeyCode::Char('o') | KeyCode::Char('O') => {
+                    if let PendingConfirmation::IncludeFollowUps { pr_index, .. } = confirmation {
+                        self.state.confirmation = None;
+                        self.request_single_pick(pr_index).await?;
+                    }
+                }
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.state.confirmation = None;
+                    self.run_confirmed(confirmation).await?;
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.state.confirmation = None;
+                    // Declining any step of a queued run ends the run
+                    self.state.pick_queue.clear();
+                    self.end_target_run();
+                    self.state.pick_held_back = HeldBack::default();
+                }
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        let abort_requested = code == KeyCode::Char('A')
+            || (code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL));
+        if abort_requested
+            && matches!(
+                self.state.current_screen,
+                Screen::MainMenu | Screen::PrList | Screen::PrDetail
+            )
+        {
+            self.request_abort_session();
+            return Ok(true);
+        }
+
         match code {
             KeyCode::Char('q') => {
                 self.should_quit = true;
@@ -182,19 +517,49 @@ impl App {
                     self.should_quit = true;
                     return Ok(false);
                 }
+                Screen::PrDetail | Screen::Stats => {
+                    self.state.current_screen = Screen::PrList;
+                }
+                // With PRs selected, Esc drops the selection before leaving the list
+                Screen::PrList if !self.state.selected_prs.is_empty() => {
+                    self.state.clear_selection();
+                }
+                Screen::SessionDetail => {
+                    self.state.current_screen = Screen::History;
+                }
+                // A held pick is continued or rolled back, not left behind
+                Screen::HookOutput => {}
                 _ => {
                     self.state.current_screen = Screen::MainMenu;
                 }
             },
+            COPY_KEY if keymap::copy_target(&self.state.current_screen).is_some() => {
+                self.copy_highlighted();
+            }
             _ => {
                 match &self.state.current_screen {
                     Screen::MainMenu => self.handle_main_menu_input(code).await?,
                     Screen::PrList => self.handle_pr_list_input(code).await?,
+                    Screen::PrDetail => self.handle_pr_detail_input(key).await?,
                     Screen::Progress => self.handle_progress_input(code).await?,
+                    Screen::Error if code == KeyCode::Char('p') && self.state.partial_listing.is_some() => {
+                        self.show_partial_listing();
+                    }
+                    Screen::Error if code == KeyCode::Char('o') && self.state.sso_url.is_some() => {
+                        self.open_sso_page();
+                    }
                     Screen::Error => {
-                        // Any key from error screen goes back to main menu
+                        // Any other key from error screen goes back to main menu
+                        self.state.partial_listing = None;
+                        self.state.sso_url = None;
                         self.state.current_screen = Screen::MainMenu;
                     }
+                    Screen::TagList | Screen::Stats => {}
+                    Screen::History => self.handle_history_input(code)?,
+                    Screen::SessionDetail => self.handle_session_detail_input(code),
+                    Screen::Conflicts => self.handle_conflicts_input(code).await?,
+                    Screen::HookOutput => self.handle_hook_input(code).await?,
+                    Screen::TargetSelect => self.handle_target_select_input(code).await?,
                 }
             }
         }
@@ -202,6 +567,19 @@ impl App {
         Ok(true)
     }
 
+    /// Copies what the copy key means on the current screen from its highlighted row
+    fn copy_highlighted(&mut self) {
+        let value = keymap::copy_target(&self.state.current_screen)
+            .and_then(|target| target.value(&self.state));
+        match value {
+            Some(value) => {
+                let toast = clipboard::copy_or_show(self.clipboard.as_mut(), &value);
+                self.state.set_success(&toast);
+            }
+            None => self.state.set_success(t("clipboard.nothing")),
+        }
+    }
+
     async fn handle_main_menu_input(&mut self, key: KeyCode) -> Result<()> {
         match key {
             KeyCode::Char('1') | KeyCode::Enter => {
@@ -210,149 +588,2828 @@ impl App {
             KeyCode::Char('r') => {
                 self.load_prs().await?;
             }
+            KeyCode::Char('t') => self.show_tags(),
+            KeyCode::Char('h') => self.show_history(),
+            KeyCode::Char('b') => self.show_target_selector(),
             _ => {}
         }
         Ok(())
     }
 
-    async fn handle_pr_list_input(&mut self, key: KeyCode) -> Result<()> {
-        match key {
-            KeyCode::Up | KeyCode::Char('k') => {
-                self.state.pr_list_state.select_previous();
+    /// Lists branches to pick onto: recently used and pinned ones, then the rest
+    fn show_target_selector(&mut self) {
+        match self.git_ops.branch_names() {
+            Ok(branches) => {
+                let rows = target_rows(&self.state.target_history, &branches);
+                self.state.branches = branches;
+                let current = self.config.github.target_branch.clone();
+                self.state.set_target_rows(rows, Some(&current));
+                self.state.current_screen = Screen::TargetSelect;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                self.state.pr_list_state.select_next();
+            Err(e) => {
+                self.state.set_error(tf("error.list_branches", &[("error", &e)]));
+                self.state.current_screen = Screen::Error;
             }
+        }
+    }
+
+    async fn handle_target_select_input(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => self.state.target_list_state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.state.target_list_state.select_next(),
+            KeyCode::Char('*') => self.toggle_target_pin(),
             KeyCode::Enter => {
-                if let Some(selected) = self.state.pr_list_state.selected() {
-                    // map from visible selection to actual PR index
-                    if let Some(&actual_idx) = self.state.display_indices.get(selected) {
-                        self.cherry_pick_pr(actual_idx).await?;
-                    }
+                if let Some(branch) = self.state.selected_target().map(str::to_string) {
+                    self.switch_target(branch).await?;
                 }
             }
-            KeyCode::Char('r') => {
-                self.load_prs().await?;
-            }
-            KeyCode::Char('f') => {
-                // Activate inline filter prompt
-                let hint = "type to filter by #, title or author (Enter to apply, Esc to cancel)";
-                let initial_owned = {
-                    let initial = self.state.filter_query.as_deref().unwrap_or("");
-                    initial.to_string()
-                };
-                self.state.start_prompt("Filter PRs", hint, &initial_owned);
-            }
             _ => {}
         }
         Ok(())
     }
 
-    async fn handle_progress_input(&mut self, _key: KeyCode) -> Result<()> {
-        // Progress screen doesn't handle input
+    fn toggle_target_pin(&mut self) {
+        let Some(branch) = self.state.selected_target().map(str::to_string) else {
+            return;
+        };
+        let pinned = self.state.target_history.toggle_pin(&branch);
+        self.save_target_history();
+        let rows = target_rows(&self.state.target_history, &self.state.branches);
+        self.state.set_target_rows(rows, Some(&branch));
+        let key = if pinned { "status.target_pinned" } else { "status.target_unpinned" };
+        self.state.set_success(&tf(key, &[("branch", &branch)]));
+    }
+
+    /// Picks onto `branch` from now on, reloading the PRs since conflict
+    /// predictions depend on the target
+    async fn switch_target(&mut self, branch: String) -> Result<()> {
+        self.config.github.target_branch = branch.clone();
+        self.config.github.target_branches.clear();
+        self.note_target_use(&branch);
+        self.load_prs().await?;
+        if matches!(self.state.current_screen, Screen::PrList) {
+            self.state.set_success(&tf("status.target_set", &[("branch", &branch)]));
+        }
         Ok(())
     }
 
-    async fn load_prs(&mut self) -> Result<()> {
-        self.state.set_loading("Loading PRs...");
-        self.state.current_screen = Screen::Progress;
+    /// The branch picks land on: the one named by `branch_name_template` when
+    /// `create_backport_branch` is set, otherwise `target_branch` itself
+    fn pick_branch(&self) -> String {
+        let github = &self.config.github;
+        if github.create_backport_branch {
+            github.branch_name_template.clone()
+        } else {
+            github.target_branch.clone()
+        }
+    }
 
-        match self.github_client.list_matching_prs().await {
-            Ok(prs) => {
-                self.state.set_prs(prs);
-                self.state.current_screen = Screen::PrList;
-            }
-            Err(e) => {
-                self.state.set_error(format!("Failed to load PRs: {}", e));
-                self.state.current_screen = Screen::Error;
-            }
+    /// How to get onto `branch` for a pick: the target branch is checked out
+    /// as it is, and a backport branch is created from `cherry_pick_source_branch`.
+    /// A backport branch that already exists, and that this session didn't
+    /// create or pick onto, is handled as `choice`, or else `--on-existing-branch`,
+    /// says. `Ok(None)` when neither says, with where the branch exists, so the user can choose.
+    fn plan_pick_branch(
+        &self,
+        branch: &str,
+        choice: Option<ExistingBranchChoice>,
+    ) -> Result<Result<BranchPlan, BranchPresence>> {
+        let ours = self.state.created_branches.iter().any(|b| b == branch)
+            || self.state.recently_cherry_picked.iter().any(|pick| pick.branch == branch);
+        if !self.config.github.create_backport_branch || ours {
+            return Ok(Ok(BranchPlan::Reuse {
+                name: branch.to_string(),
+                from_remote: false,
+            }));
+        }
+        if branch.contains("{task_id}") {
+            anyhow::bail!("{}", tf("error.backport_branch_unnamed", &[("template", &branch)]));
         }
+        let presence = self.branch_presence(branch);
+        let choice = match (choice, self.on_existing_branch) {
+            (Some(choice), _) => Some(choice),
+            (None, Some(policy)) if presence.exists() => Some(
+                policy
+                    .choice()
+                    .ok_or_else(|| anyhow::anyhow!("{}", tf("error.backport_branch_exists", &[("branch", &branch)])))?,
+            ),
+            (None, _) => None,
+        };
+        Ok(workflow::plan_branch(branch, presence, choice.as_ref(), |name| self.branch_presence(name)).ok_or(presence))
+    }
 
-        Ok(())
+    /// Where branch `name` exists. An unreachable remote is treated as not
+    /// having it; the push says otherwise.
+    fn branch_presence(&self, name: &str) -> BranchPresence {
+        self.git_ops.branch_presence(name).unwrap_or_else(|e| {
+            tracing::warn!("Could not ask the remote for {}: {}", name, e);
+            BranchPresence {
+                local: self.git_ops.branch_exists(name),
+                remote: false,
+            }
+        })
     }
 
-    async fn cherry_pick_pr(&mut self, pr_index: usize) -> Result<()> {
-        // Get PR details before borrowing mutably
-        let pr = if let Some(pr) = self.state.prs.get(pr_index) {
-            pr.clone()
-        } else {
-            return Ok(());
+    /// Asks what to do with backport branch `branch`, which exists at `presence`,
+    /// before picking `shas` of `pr` onto it
+    fn ask_about_existing_branch(&mut self, pr: &PrInfo, shas: Vec<String>, reordered: bool, branch: String, presence: BranchPresence) {
+        let Some(pr_index) = self.state.prs.iter().position(|p| p.number == pr.number) else {
+            return;
         };
+        self.state.current_screen = Screen::PrList;
+        self.state.confirmation = Some(PendingConfirmation::ExistingBranch {
+            pr_index,
+            shas,
+            reordered,
+            branch,
+            presence,
+            source: self.config.github.cherry_pick_source_branch.clone(),
+        });
+    }
 
+    /// Opens the prompt for another name for the existing backport branch in
+    /// `confirmation`, pre-filled with the next free `-N` suffix
+    fn start_branch_rename(&mut self, confirmation: PendingConfirmation) {
+        let PendingConfirmation::ExistingBranch { branch, .. } = &confirmation else {
+            return;
+        };
+        let suggested = workflow::suggested_branch_name(branch, |name| self.branch_presence(name).exists());
         self.state
-            .set_loading(&format!("Cherry-picking PR #{}: {}", pr.number, pr.title));
-        self.state.current_screen = Screen::Progress;
+            .start_prompt(PromptKind::BranchName, t("branch_name.title"), t("branch_name.hint"), &suggested);
+        self.state.renaming_branch = Some(confirmation);
+    }
 
-        // Switch to target branch
-        if let Err(e) = self
-            .git_ops
-            .checkout_branch(&self.config.github.target_branch)
-        {
-            self.state
-                .set_error(format!("Failed to checkout target branch: {}", e));
-            self.state.current_screen = Screen::Error;
+    /// Picks onto the name typed into the rename prompt, which must be free;
+    /// a taken one keeps the prompt open with a message
+    async fn submit_branch_rename(&mut self) -> Result<()> {
+        let name = self.state.input_buffer.trim().to_string();
+        if name.is_empty() || self.branch_presence(&name).exists() {
+            self.state.prompt_error = Some(tf("branch_name.taken", &[("branch", &name)]));
             return Ok(());
         }
+        self.state.confirm_prompt();
+        let Some(PendingConfirmation::ExistingBranch { pr_index, shas, reordered, branch, .. }) =
+            self.state.renaming_branch.take()
+        else {
+            return Ok(());
+        };
+        self.state.branch_choice = Some((branch, ExistingBranchChoice::Rename(name)));
+        let Some(pr) = self.state.prs.get(pr_index).cloned() else {
+            return Ok(());
+        };
+        self.pick_commits(&pr, shas, reordered).await
+    }
 
-        let mut success = true;
-        let mut cherry_picked_commits = Vec::new();
+    /// Pushes the branch a pick completed on, when `auto_push` is set. The
+    /// note to append to the status message says how it went, and is an
+    /// error when the push failed.
+    fn push_pick_branch(&mut self, branch: &str) -> Option<Result<String, String>> {
+        if !self.config.ui.auto_push {
+            return None;
+        }
+        self.state.current_git_op = GitOperation::Push(branch.to_string());
+        let force = self.state.force_push_branches.contains(branch);
+        let pushed = self.git_ops.push_branch(branch, force);
+        self.state.current_git_op = GitOperation::Idle;
+        Some(match pushed {
+            Ok(()) => Ok(tf("status.pushed_branch", &[("branch", &branch)])),
+            Err(e) => {
+                tracing::warn!("Failed to push {}: {}", branch, e);
+                Err(tf("status.push_failed", &[("branch", &branch), ("error", &e)]))
+            }
+        })
+    }
 
-        // Cherry-pick each commit in the PR
-        for commit in &pr.commits {
-            match self.git_ops.cherry_pick(&commit.sha) {
-                Ok(result) => {
-                    if result.success {
-                        if let Some(sha) = result.commit_sha {
-                            cherry_picked_commits.push(sha);
-                        }
-                    } else {
-                        // Handle conflicts
-                        let short = short_sha(&commit.sha);
-                        self.state.set_error(format!(
-                            "Conflicts in commit {}: {:?}. Please resolve manually and press any key to continue.",
-                            short,
-                            result.conflicts
-                        ));
-                        self.state.current_screen = Screen::Error;
-                        success = false;
-                        break;
+    /// Pushes the branch a pick landed on and, unless `open_pr` is false,
+    /// opens its backport PR, as far as the config asks. Simulated writes are
+    /// added to `record`. Returns the notes to append to the status message.
+    async fn publish_pick(&mut self, record: &mut PickRecord, open_pr: bool) -> String {
+        let mut notes = String::new();
+        match self.push_pick_branch(&record.target_branch) {
+            Some(Ok(note)) => {
+                notes.push_str(&note);
+                if open_pr {
+                    if let Some(note) = self.open_pick_pr(record).await {
+                        notes.push_str(&note);
                     }
-                }
-                Err(e) => {
-                    let short = short_sha(&commit.sha);
-                    self.state
-                        .set_error(format!("Failed to cherry-pick commit {}: {}", short, e));
-                    self.state.current_screen = Screen::Error;
-                    success = false;
-                    break;
+                    record.simulated.extend(self.take_simulated());
                 }
             }
+            Some(Err(note)) => notes.push_str(&note),
+            None => {}
         }
+        notes
+    }
 
-        if success {
-            // Update PR labels
-            if let Err(e) = self.github_client.update_pr_labels(pr.number).await {
-                tracing::warn!("Failed to update PR labels: {}", e);
+    /// Opens the PR of a pushed backport branch when `open_backport_pr` is
+    /// set, asking the original author and `review_team` to review it. The
+    /// target's code owner teams of the picked files are listed in it and,
+    /// with `request_code_owners`, asked too. The note to append to the
+    /// status message says how it went.
+    async fn open_pick_pr(&mut self, record: &PickRecord) -> Option<String> {
+        let github = &self.config.github;
+        if !github.open_backport_pr || !github.create_backport_branch {
+            return None;
+        }
+        let base = github.target_branch.clone();
+        let review_team = github.review_team.clone();
+        let shas: Vec<String> = record.commits.iter().map(|c| c.source_sha.clone()).collect();
+        let code_owners = gh::CodeOwnerTeams {
+            teams: self.code_owner_teams(&base, &self.changed_paths(&shas)),
+            request_review: github.request_code_owners,
+        };
+        let author = self
+            .state
+            .prs
+            .iter()
+            .find(|pr| pr.number == record.pr_number)
+            .map(|pr| pr.author.clone())
+            .unwrap_or_default();
+        let login = self.run_info().await.login.unwrap_or_default();
+        let (title, body) = gh::backport_pr_text(record.pr_number, &record.pr_title, &base);
+        let opened = gh::open_backport_pr(
+            self.mutations(),
+            &record.target_branch,
+            &base,
+            &title,
+            &body,
+            &author,
+            &login,
+            review_team.as_deref(),
+            &code_owners,
+        )
+        .await;
+        Some(match opened {
+            Ok(pr) => match pr.review_error {
+                None => tf("status.backport_pr_opened", &[("number", &pr.number)]),
+                Some(error) => tf("status.review_request_failed", &[("number", &pr.number), ("error", &error)]),
+            },
+            Err(e) => {
+                tracing::warn!("Failed to open the backport PR for #{}: {}", record.pr_number, e);
+                tf("status.backport_pr_failed", &[("error", &e)])
             }
+        })
+    }
 
-            // Add comment to PR
-            if let Err(e) = self
-                .github_client
-                .add_cherry_pick_comment(
-                    pr.number,
-                    &self.config.github.target_branch,
-                    &cherry_picked_commits,
-                )
-                .await
-            {
-                tracing::warn!("Failed to add cherry-pick comment: {}", e);
-            }
+    /// Sets the note the prompt was editing, or removes it when left blank
+    fn submit_note(&mut self) {
+        let PromptKind::Note(number) = self.state.prompt_kind else {
+            return;
+        };
+        let text = self.state.confirm_prompt();
+        let key = if self.state.pr_notes.set(number, &text) {
+            "status.note_set"
+        } else {
+            "status.note_cleared"
+        };
+        let saved = storage::notes_file(&self.config.github.owner, &self.config.github.repo)
+            .and_then(|path| self.state.pr_notes.save(&path));
+        if let Err(e) = saved {
+            tracing::warn!("Failed to save PR notes: {:#}", e);
+        }
+        self.state.set_success(&tf(key, &[("number", &number)]));
+    }
 
-            self.state
-                .set_success(&format!("Successfully cherry-picked PR #{}", pr.number));
-            self.state.current_screen = Screen::PrList;
+    /// Moves `branch` to the top of the selector's recent targets
+    fn note_target_use(&mut self, branch: &str) {
+        self.state.target_history.record_use(branch, Utc::now());
+        self.save_target_history();
+    }
+
+    /// Writes the target history; failing only costs the ranking next time
+    fn save_target_history(&self) {
+        let saved = storage::targets_file(&self.config.github.owner, &self.config.github.repo)
+            .and_then(|path| self.state.target_history.save(&path));
+        if let Err(e) = saved {
+            tracing::warn!("Failed to save target history: {:#}", e);
+        }
+    }
+
+    /// Lists this repository's earlier session logs, newest first
+    fn show_history(&mut self) {
+        match storage::list_sessions(&self.config.github.owner, &self.config.github.repo) {
+            Ok(paths) => {
+                let sessions = paths
+                    .into_iter()
+                    .filter_map(|path| match SessionRecords::read(&path) {
+                        Ok(records) => Some(SessionSummary::new(path, &records)),
+                        Err(e) => {
+                            tracing::warn!("Skipping session log: {:#}", e);
+                            None
+                        }
+                    })
+                    .collect();
+                self.state.set_sessions(sessions);
+                self.state.current_screen = Screen::History;
+            }
+            Err(e) => {
+                self.state.set_error(tf("error.list_sessions", &[("error", &e)]));
+                self.state.current_screen = Screen::Error;
+            }
         }
+    }
 
+    fn handle_history_input(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => self.state.session_list_state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.state.session_list_state.select_next(),
+            KeyCode::Enter => {
+                if let Some(path) = self.state.selected_session().map(|s| s.path.clone()) {
+                    self.state.view_session(SessionRecords::read(&path)?);
+                    self.state.current_screen = Screen::SessionDetail;
+                }
+            }
+            _ => {}
+        }
         Ok(())
     }
+
+    fn handle_session_detail_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => self.state.session_record_state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.state.session_record_state.select_next(),
+            KeyCode::Char('b') => self.request_bookkeeping_rerun(),
+            _ => {}
+        }
+    }
+
+    fn request_bookkeeping_rerun(&mut self) {
+        let prs = self
+            .state
+            .viewed_session
+            .as_ref()
+            .map_or(0, |session| session.partial().len());
+        let caps = self.state.capabilities;
+        if prs == 0 {
+            self.state.set_success(t("status.nothing_to_rerun"));
+        } else if !caps.can_label && !caps.can_comment {
+            self.state.set_success(t("status.bookkeeping_not_permitted"));
+        } else {
+            self.state.confirmation = Some(PendingConfirmation::RerunBookkeeping { prs });
+        }
+    }
+
+    async fn handle_conflicts_input(&mut self, key: KeyCode) -> Result<()> {
+        let side = match key {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.conflict_list_state.select_previous();
+                return Ok(());
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.conflict_list_state.select_next();
+                return Ok(());
+            }
+            KeyCode::Char('r') => {
+                self.recheck_conflicts();
+                return Ok(());
+            }
+            KeyCode::Char('c') => return self.continue_stopped_pick().await,
+            KeyCode::Char('a') => {
+                self.abort_stopped_pick();
+                return Ok(());
+            }
+            KeyCode::Char('o') => ConflictSide::Ours,
+            KeyCode::Char('t') => ConflictSide::Theirs,
+            _ => return Ok(()),
+        };
+        let Some(file) = self.state.selected_conflict().cloned() else {
+            return Ok(());
+        };
+        if !file.binary {
+            self.state
+                .set_success(&tf("status.conflict_not_binary", &[("path", &file.path)]));
+            return Ok(());
+        }
+        if file.taken.is_some() {
+            return Ok(());
+        }
+        if let Err(e) = self.git_ops.resolve_conflict(&file.path, side) {
+            self.state.set_error(tf(
+                "error.resolve_conflict",
+                &[("path", &file.path), ("error", &e)],
+            ));
+            self.state.current_screen = Screen::Error;
+            return Ok(());
+        }
+
+        if let Some(record) = self.state.take_conflict_side(&file.path, side).cloned() {
+            self.log_pick(&record);
+        }
+        if self.state.conflicts_settled() {
+            self.state.set_success(t("status.conflicts_resolved"));
+        } else {
+            let side = match side {
+                ConflictSide::Ours => t("conflicts.ours"),
+                ConflictSide::Theirs => t("conflicts.theirs"),
+            };
+            self.state.set_success(&tf(
+                "status.conflict_side_taken",
+                &[("side", &side), ("path", &file.path)],
+            ));
+        }
+        Ok(())
+    }
+
+    /// Opens the conflicts screen on a cherry-pick an earlier run (or plain
+    /// git) left stopped, so it's continued or aborted before anything else
+    fn show_leftover_pick(&mut self) {
+        if !self.git_ops.cherry_pick_in_progress() {
+            return;
+        }
+        match self.git_ops.conflicted_files() {
+            Ok(conflicts) => {
+                self.state.set_leftover_conflicts(&conflicts);
+                self.state.refresh_conflicts(&conflicts);
+                self.state.set_success(t("conflicts.leftover"));
+                self.state.current_screen = Screen::Conflicts;
+            }
+            Err(e) => tracing::warn!("Couldn't read the stopped cherry-pick: {}", e),
+        }
+    }
+
+    /// Re-reads the index, picking up files resolved and staged in another
+    /// terminal
+    fn recheck_conflicts(&mut self) {
+        match self.git_ops.conflicted_files() {
+            Ok(still_conflicted) => {
+                self.state.refresh_conflicts(&still_conflicted);
+                if self.state.conflicts_settled() {
+                    self.state.set_success(t("status.conflicts_resolved"));
+                } else {
+                    self.state.set_success(&tf(
+                        "status.conflicts_left",
+                        &[("count", &still_conflicted.len())],
+                    ));
+                }
+            }
+            Err(e) => {
+                self.state
+                    .set_error(tf("error.read_conflicts", &[("error", &e)]));
+                self.state.current_screen = Screen::Error;
+            }
+        }
+    }
+
+    /// Commits the stopped pick once nothing is left conflicted, then applies
+    /// the rest of its PR's commits. A pick left over from an earlier run is
+    /// only committed; there's no record of which commits were meant to follow.
+    async fn continue_stopped_pick(&mut self) -> Result<()> {
+        self.recheck_conflicts();
+        if !self.state.conflicts_settled() || self.state.error_message.is_some() {
+            return Ok(());
+        }
+        let source_sha = self.git_ops.cherry_pick_head();
+        let new_sha = match self.git_ops.resume_cherry_pick() {
+            Ok(sha) => sha,
+            Err(e) => {
+                self.state
+                    .set_error(tf("error.continue_pick", &[("error", &e)]));
+                self.state.current_screen = Screen::Error;
+                return Ok(());
+            }
+        };
+
+        let remaining = std::mem::take(&mut self.state.conflict_remaining);
+        let Some(mut record) = self.state.conflict_record.take() else {
+            self.state.clear_conflicts();
+            self.state.set_success(&tf(
+                "status.leftover_continued",
+                &[("sha", &short_sha(&new_sha))],
+            ));
+            self.state.current_screen = Screen::MainMenu;
+            return Ok(());
+        };
+        self.state.clear_conflicts();
+        if let Some(source_sha) = source_sha {
+            self.state
+                .note_session_commit(record.pr_number, &record.target_branch, new_sha.clone());
+            record.commits.push(PickedCommit { source_sha, new_sha });
+        }
+
+        let mut remaining = remaining.into_iter();
+        while let Some(sha) = remaining.next() {
+            self.state.current_git_op = GitOperation::CherryPick(short_sha(&sha).to_string());
+            match self.git_ops.cherry_pick(&sha) {
+                Ok(result) if result.success => {
+                    if let Some(new_sha) = result.commit_sha {
+                        self.state
+                            .note_session_commit(record.pr_number, &record.target_branch, new_sha.clone());
+                        record.commits.push(PickedCommit {
+                            source_sha: sha,
+                            new_sha,
+                        });
+                    }
+                }
+                Ok(result) => {
+                    // Stopped again: back to the conflicts screen for this one
+                    record.recorded_at = Some(Utc::now());
+                    self.log_pick(&record);
+                    self.state
+                        .set_conflicts(&result.conflicts, &result.binary_conflicts, record);
+                    self.state.conflict_remaining = remaining.collect();
+                    self.state
+                        .set_success(&tf("conflicts.stopped", &[("sha", &short_sha(&sha))]));
+                    return Ok(());
+                }
+                Err(e) => {
+                    record.status = PickStatus::Failed;
+                    record.recorded_at = Some(Utc::now());
+                    self.log_pick(&record);
+                    self.state.set_error(tf(
+                        "error.pick_failed",
+                        &[("sha", &short_sha(&sha)), ("error", &e)],
+                    ));
+                    self.state.current_screen = Screen::Error;
+                    return Ok(());
+                }
+            }
+        }
+
+        record.status = PickStatus::Picked;
+        if !self.post_pick_hook_passed(&record).await {
+            self.hold_for_hook(record);
+            return Ok(());
+        }
+        self.complete_pick(record).await;
+        Ok(())
+    }
+
+    /// Labels and comments on a pick that landed, pushes its branch, opens
+    /// its backport PR and logs it
+    async fn complete_pick(&mut self, mut record: PickRecord) {
+        let not_permitted = self.finish_bookkeeping(&mut record).await;
+        let notes = self.publish_pick(&mut record, true).await;
+        record.recorded_at = Some(Utc::now());
+        self.log_pick(&record);
+        self.note_target_use(&self.config.github.target_branch.clone());
+        let key = if not_permitted {
+            "status.picked_local_only"
+        } else if !record.simulated.is_empty() {
+            "status.picked_simulated"
+        } else if !record.held_back.is_none() {
+            "status.picked_held_back"
+        } else {
+            "status.picked"
+        };
+        let mut message = tf(key, &[("number", &record.pr_number)]);
+        message.push_str(&notes);
+        if let Some(run) = &self.state.target_run {
+            message.push_str(&run.progress());
+        }
+        message.push_str(&self.state.batch_progress());
+        self.state.set_success(&message);
+        self.state.current_screen = Screen::PrList;
+    }
+
+    /// Runs `post_pick_hook` on the pick in `record`, showing its output as it
+    /// comes. True when there's no hook or it exited successfully.
+    async fn post_pick_hook_passed(&mut self, record: &PickRecord) -> bool {
+        let Some(command) = self.config.github.post_pick_hook.clone() else {
+            return true;
+        };
+        let Some(dir) = self.git_ops.workdir().map(Path::to_path_buf) else {
+            tracing::warn!("Skipping the post-pick hook; the repository has no working tree");
+            return true;
+        };
+        let context = hook_context(record);
+        self.state.hook = Some(HookPanel::new(&command));
+        self.state.current_screen = Screen::HookOutput;
+        if let Err(e) = self.draw() {
+            tracing::warn!("Failed to draw the hook output: {}", e);
+        }
+
+        let result = hook::run(&command, &dir, &context, |line| {
+            if let Some(panel) = &mut self.state.hook {
+                panel.lines.push(line);
+            }
+            if let Err(e) = self.draw() {
+                tracing::warn!("Failed to draw the hook output: {}", e);
+            }
+        })
+        .await;
+        let failure = match result {
+            Ok(status) if status.success() => None,
+            Ok(status) => Some(match status.code() {
+                Some(code) => tf("hook.exit_code", &[("code", &code)]),
+                None => t("hook.killed").to_string(),
+            }),
+            Err(e) => Some(format!("{:#}", e)),
+        };
+        tracing::info!(command = %command, pr = record.pr_number, failure = ?failure, "Ran the post-pick hook");
+
+        match failure {
+            None => {
+                self.state.hook = None;
+                self.state.current_screen = Screen::Progress;
+                true
+            }
+            Some(failure) => {
+                if let Some(panel) = &mut self.state.hook {
+                    panel.failure = Some(failure);
+                }
+                false
+            }
+        }
+    }
+
+    /// Keeps the pick whose hook failed on the hook screen, unpushed and
+    /// unlogged, until it's continued or rolled back
+    fn hold_for_hook(&mut self, record: PickRecord) {
+        if let Some(panel) = &mut self.state.hook {
+            panel.held_pick = Some(record);
+        }
+        self.state.loading_message = None;
+        self.state.current_git_op = GitOperation::Idle;
+        self.state.current_screen = Screen::HookOutput;
+    }
+
+    async fn handle_hook_input(&mut self, key: KeyCode) -> Result<()> {
+        let Some(panel) = &mut self.state.hook else {
+            return Ok(());
+        };
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => panel.scroll(true, 1),
+            KeyCode::Down | KeyCode::Char('j') => panel.scroll(false, 1),
+            KeyCode::PageUp => panel.scroll(true, 10),
+            KeyCode::PageDown => panel.scroll(false, 10),
+            KeyCode::Char('c') => {
+                if let Some(record) = panel.held_pick.take() {
+                    self.state.hook = None;
+                    self.state.current_screen = Screen::Progress;
+                    self.complete_pick(record).await;
+                }
+            }
+            KeyCode::Char('s') => self.open_hook_shell()?,
+            KeyCode::Char('r') => self.roll_back_held_pick(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Hands the terminal to a shell in the working tree, with the hook's
+    /// environment, to look into the failure
+    fn open_hook_shell(&mut self) -> Result<()> {
+        let Some(record) = self.state.hook.as_ref().and_then(|panel| panel.held_pick.as_ref()) else {
+            return Ok(());
+        };
+        let Some(dir) = self.git_ops.workdir().map(Path::to_path_buf) else {
+            return Ok(());
+        };
+        let context = hook_context(record);
+        let had_session = match self.session.take() {
+            Some(mut session) => {
+                session.restore()?;
+                true
+            }
+            None => false,
+        };
+        println!("{}", tf("hook.shell_banner", &[("dir", &dir.display())]));
+        let result = hook::open_shell(&dir, &context);
+        if had_session {
+            let session = TerminalSession::start()?;
+            self.state.terminal_caps = session.capabilities();
+            self.session = Some(session);
+        }
+        match result {
+            Ok(_) => self.state.set_success(t("status.hook_shell_closed")),
+            Err(e) => self
+                .state
+                .set_error(tf("error.hook_shell", &[("error", &format!("{:#}", e))])),
+        }
+        Ok(())
+    }
+
+    /// Undoes the commits of the pick the hook failed on and logs it as failed.
+    /// Stops if the branch has moved on since, as aborting the session does.
+    fn roll_back_held_pick(&mut self) {
+        let Some(mut record) = self.state.hook.as_mut().and_then(|panel| panel.held_pick.take()) else {
+            return;
+        };
+        let mut commits: Vec<String> = record.commits.iter().map(|c| c.new_sha.clone()).collect();
+        commits.dedup();
+        for sha in commits.iter().rev() {
+            let undone = match self.git_ops.head_sha() {
+                Ok(head) if head == *sha => self.git_ops.undo_last_commit().map_err(|e| e.to_string()),
+                Ok(_) => Err(tf(
+                    "error.abort_head_moved",
+                    &[("branch", &record.target_branch), ("sha", &short_sha(sha))],
+                )),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = undone {
+                self.state.set_success(&tf("error.hook_rollback", &[("error", &e)]));
+                if let Some(panel) = &mut self.state.hook {
+                    panel.held_pick = Some(record);
+                }
+                return;
+            }
+            if let Some(pick) = self.state.recently_cherry_picked.iter_mut().rev().find(|pick| {
+                pick.pr_number == record.pr_number && pick.branch == record.target_branch
+            }) {
+                pick.commits.retain(|commit| commit != sha);
+            }
+        }
+        self.state.recently_cherry_picked.retain(|pick| !pick.commits.is_empty());
+
+        record.status = PickStatus::Failed;
+        record.commits.clear();
+        record.recorded_at = Some(Utc::now());
+        self.log_pick(&record);
+        self.state.pick_queue.clear();
+        self.end_target_run();
+        self.state.hook = None;
+        self.state
+            .set_success(&tf("status.hook_rolled_back", &[("number", &record.pr_number)]));
+        self.state.current_screen = Screen::PrList;
+    }
+
+    /// Gives up on the stopped pick, leaving the branch as it was before the
+    /// conflicted commit. Commits of the PR already picked stay; aborting the
+    /// session removes them.
+    fn abort_stopped_pick(&mut self) {
+        if let Err(e) = self.git_ops.abort_cherry_pick() {
+            self.state
+                .set_error(tf("error.abort_pick", &[("error", &e)]));
+            self.state.current_screen = Screen::Error;
+            return;
+        }
+        self.state.clear_conflicts();
+        self.state.set_success(t("status.stopped_pick_aborted"));
+        self.state.current_screen = if self.state.prs.is_empty() {
+            Screen::MainMenu
+        } else {
+            Screen::PrList
+        };
+    }
+
+    /// Retries the labels and comment of each partial pick in the viewed session,
+    /// appending the outcome to that session's log
+    async fn rerun_bookkeeping(&mut self) -> Result<()> {
+        let Some(path) = self.state.selected_session().map(|s| s.path.clone()) else {
+            return Ok(());
+        };
+        let partial: Vec<PickRecord> = match &self.state.viewed_session {
+            Some(session) => session.partial().into_iter().cloned().collect(),
+            None => return Ok(()),
+        };
+
+        let log = SessionLog::new(path.clone());
+        let mut completed = 0;
+        let run = self.run_info().await;
+        for mut record in partial {
+            // The re-run is credited to whoever is running it now
+            record.run = Some(run.clone());
+            // Asking for the re-run releases anything held back at pick time
+            record.held_back = HeldBack::default();
+            self.finish_bookkeeping(&mut record).await;
+            record.recorded_at = Some(Utc::now());
+            if !record.is_partial() {
+                completed += 1;
+            }
+            log.append(&record)?;
+        }
+
+        let session = SessionRecords::read(&path)?;
+        let remaining = session.partial().len();
+        if let Some(i) = self.state.session_list_state.selected() {
+            self.state.sessions[i] = SessionSummary::new(path, &session);
+        }
+        self.state.session_record_state.set_items_count(session.records.len());
+        self.state.viewed_session = Some(session);
+        self.state.set_success(&tf(
+            "status.bookkeeping_rerun",
+            &[("completed", &completed), ("remaining", &remaining)],
+        ));
+        Ok(())
+    }
+
+    /// Reads the user's repository permissions so actions they can't take are
+    /// skipped up front. A failed probe leaves everything allowed.
+    async fn probe_capabilities(&mut self) {
+        match self.github_client.get_repo_permissions().await {
+            Ok(Some(permissions)) => {
+                self.state.capabilities = Capabilities::from_permissions(&permissions);
+                tracing::info!(capabilities = ?self.state.capabilities, "Probed repository permissions");
+            }
+            Ok(None) => tracing::info!("Repository permissions not reported; assuming full access"),
+            Err(e) => tracing::warn!("Could not probe repository permissions: {}", e),
+        }
+    }
+
+    /// Swaps the PR's labels and posts the pick comment, skipping the steps
+    /// `record` already has done or holds back. Failures are logged and left unset in `record`,
+    /// as are simulated steps, which are listed in `record.simulated` instead.
+    /// Returns whether a step was skipped because the user isn't allowed to do it.
+    async fn finish_bookkeeping(&self, record: &mut PickRecord) -> bool {
+        let caps = self.state.capabilities;
+        let sent = self.simulated.is_none();
+        let (labels_due, comment_due) = (record.labels_due(), record.comment_due());
+        let not_permitted = (labels_due && !caps.can_label) || (comment_due && !caps.can_comment);
+        if labels_due && caps.can_label {
+            match self.mutations().update_pr_labels(record.pr_number).await {
+                Ok(()) => record.labels_updated = sent,
+                Err(e) => tracing::warn!("Failed to update PR labels: {}", e),
+            }
+        }
+        if comment_due && caps.can_comment {
+            let footer = record
+                .run
+                .as_ref()
+                .filter(|_| self.config.github.comment_footer)
+                .map(RunInfo::comment_footer);
+            let outcomes: Vec<CommitPickOutcome> = record.commits.iter().map(Into::into).collect();
+            match self
+                .mutations()
+                .add_cherry_pick_comment(
+                    record.pr_number,
+                    &record.target_branch,
+                    &outcomes,
+                    record.reordered,
+                    record.note.as_deref(),
+                    footer.as_deref(),
+                )
+                .await
+            {
+                Ok(()) => record.comment_added = sent,
+                Err(e) => tracing::warn!("Failed to add cherry-pick comment: {}", e),
+            }
+        }
+        record.simulated.extend(self.take_simulated());
+        not_permitted
+    }
+
+    /// Defers `pr` for this session, leaving a comment on it when `comment_on_skip` is set
+    async fn skip_pr(&mut self, pr_index: usize) {
+        let Some(pr) = self.state.prs.get(pr_index).cloned() else {
+            return;
+        };
+        self.state.skipped_prs.insert(pr.number);
+
+        let target_branch = self.config.github.target_branch.clone();
+        let commented = self.config.github.comment_on_skip
+            && self.state.capabilities.can_comment
+            && self
+                .comment_deferral(pr.number, &target_branch, &DeferralReason::Skipped)
+                .await;
+        let run = self.run_info().await;
+        self.log_pick(&PickRecord {
+            pr_number: pr.number,
+            status: PickStatus::Skipped,
+            recorded_at: Some(Utc::now()),
+            pr_title: pr.title.clone(),
+            target_branch,
+            commits: Vec::new(),
+            reordered: false,
+            labels_updated: false,
+            comment_added: commented,
+            run: Some(run),
+            sides_taken: Vec::new(),
+            simulated: self.take_simulated(),
+            unverified_submodules: Vec::new(),
+            api_requests: None,
+            held_back: HeldBack::default(),
+            note: self.state.pr_notes.get(pr.number).map(str::to_string),
+        });
+        self.state
+            .set_success(&tf("status.skipped", &[("number", &pr.number)]));
+    }
+
+    /// Who is running this session, waiting for the background lookup of the
+    /// login or making it here when none was started
+    async fn run_info(&mut self) -> RunInfo {
+        if let Some(run) = &self.run_info {
+            return run.clone();
+        }
+        let user = match self.user_lookup.take() {
+            Some(lookup) => lookup.await.ok().flatten(),
+            None => lookup_user(self.github_client.clone()).await,
+        };
+        let run = RunInfo::current(user.as_ref());
+        self.run_info = Some(run.clone());
+        run
+    }
+
+    /// Posts or refreshes the deferral comment on the original PR. Returns
+    /// whether it was written, which a simulated write wasn't; failures are only logged.
+    async fn comment_deferral(&mut self, pr_number: u64, target_branch: &str, reason: &DeferralReason) -> bool {
+        let actor = self
+            .run_info()
+            .await
+            .login
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let body = gh::deferral_comment_body(target_branch, reason, &actor);
+        let marker = gh::deferral_marker(target_branch);
+        match gh::upsert_marked_comment(self.mutations(), pr_number, &marker, &body).await {
+            Ok(action) => {
+                tracing::info!(pr = pr_number, ?action, "Wrote deferral comment");
+                self.simulated.is_none()
+            }
+            Err(e) => {
+                tracing::warn!("Failed to comment on PR #{}: {}", pr_number, e);
+                false
+            }
+        }
+    }
+
+    /// Appends to this session's log, stamped with the requests made so far;
+    /// a failed write only costs the history entry
+    fn log_pick(&self, record: &PickRecord) {
+        if let Some(log) = &self.session_log {
+            let record = PickRecord {
+                api_requests: Some(self.api_usage()),
+                ..record.clone()
+            };
+            if let Err(e) = log.append(&record) {
+                tracing::warn!("Failed to record pick in session log: {:#}", e);
+            }
+        }
+    }
+
+    /// Flips the "mine" quick filter, which needs to know who "mine" is
+    async fn toggle_mine(&mut self) {
+        if self.state.quick_filters.mine.is_some() {
+            self.state.quick_filters.mine = None;
+            self.state.recompute_display_indices();
+            return;
+        }
+        match self.run_info().await.login {
+            Some(login) => self.state.toggle_mine(&login),
+            None => self.state.set_success(t("quick_filter.login_unknown")),
+        }
+    }
+
+    fn show_tags(&mut self) {
+        match self.git_ops.list_tags(None) {
+            Ok(tags) => {
+                self.state.tags = tags;
+                self.state.current_screen = Screen::TagList;
+            }
+            Err(e) => {
+                self.state.set_error(tf("error.list_tags", &[("error", &e)]));
+                self.state.current_screen = Screen::Error;
+            }
+        }
+    }
+
+    async fn handle_pr_list_input(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.pr_list_state.select_previous();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.pr_list_state.select_next();
+            }
+            KeyCode::Enter => {
+                if let Some(actual_idx) = self.state.selected_pr_index() {
+                    self.request_cherry_pick(actual_idx).await?;
+                }
+            }
+            KeyCode::Char('P') => {
+                if let Some(actual_idx) = self.state.selected_pr_index() {
+                    self.state.open_pick_options(actual_idx);
+                }
+            }
+            KeyCode::Char(' ') => {
+                if let Some(actual_idx) = self.state.selected_pr_index() {
+                    self.state.toggle_selection(actual_idx);
+                }
+            }
+            KeyCode::Char('x') => {
+                // The run loop picks the queued batch one PR at a time
+                match self.state.queue_selection() {
+                    0 => self.state.set_success(t("status.nothing_selected")),
+                    count => self
+                        .state
+                        .set_success(&tf("status.batch_queued", &[("count", &count)])),
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(actual_idx) = self.state.selected_pr_index() {
+                    let commit_count = self.state.prs[actual_idx].commits.len();
+                    self.state.detail_index = Some(actual_idx);
+                    self.state.detail_commit_state = Default::default();
+                    self.state.detail_commit_state.set_items_count(commit_count);
+                    self.state.current_screen = Screen::PrDetail;
+                }
+            }
+            KeyCode::Char('r') => {
+                self.load_prs().await?;
+            }
+            KeyCode::Char('s') => {
+                if let Some(actual_idx) = self.state.selected_pr_index() {
+                    self.skip_pr(actual_idx).await;
+                }
+            }
+            KeyCode::Char('F') => {
+                if let Some(actual_idx) = self
+                    .state
+                    .selected_pr_index()
+                    .filter(|&idx| self.state.needs_fetch(&self.state.prs[idx]))
+                {
+                    if self.fetch_pr_head(actual_idx) {
+                        self.state.current_screen = Screen::PrList;
+                    }
+                }
+            }
+            KeyCode::Char('f') => {
+                // Activate inline filter prompt
+                let hint = t("filter.hint");
+                let initial_owned = {
+                    let initial = self.state.filter_query.as_deref().unwrap_or("");
+                    initial.to_string()
+                };
+                self.state
+                    .start_prompt(PromptKind::Filter, t("filter.title"), hint, &initial_owned);
+            }
+            KeyCode::Char('#') | KeyCode::Char(':') => {
+                self.state
+                    .start_prompt(PromptKind::JumpToPr, t("jump.title"), t("jump.hint"), "");
+            }
+            KeyCode::Char('m') => self.toggle_mine().await,
+            // `s` skips, so the sprint toggle is on the shifted key
+            KeyCode::Char('S') => match Regex::new(&self.config.tags.sprint_pattern) {
+                Ok(pattern) => self.state.toggle_latest_sprint(pattern),
+                Err(e) => self
+                    .state
+                    .set_success(&tf("quick_filter.bad_pattern", &[("error", &e)])),
+            },
+            KeyCode::Char('M') => self.state.toggle_merged_only(),
+            KeyCode::Char('u') => self.state.current_screen = Screen::Stats,
+            KeyCode::Char('c') => {
+                let toggled = self.state.toggle_conflict_free();
+                if !toggled {
+                    self.state.set_success(t("quick_filter.no_predictions"));
+                }
+            }
+            // Only from the empty listing: adjust the criteria, or check that PRs exist at all
+            KeyCode::Char('e') if self.state.prs.is_empty() && self.explicit_pr.is_none() => {
+                self.state
+                    .start_criteria_edit(Criteria::from_config(&self.config));
+            }
+            KeyCode::Char('n') if self.state.prs.is_empty() && self.explicit_pr.is_none() => {
+                self.state.bypass_labels_once = true;
+                self.load_prs().await?;
+            }
+            KeyCode::Char('n') => {
+                if let Some(idx) = self.state.selected_pr_index() {
+                    self.state.start_note_edit(self.state.prs[idx].number);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn handle_pr_detail_input(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(idx) = self.state.detail_index else {
+            return Ok(());
+        };
+        let pr_number = self.state.prs[idx].number;
+        let commit_count = self.state.prs[idx].commits.len();
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+
+        match key.code {
+            // Shift+arrows reorder; K/J cover terminals that don't report shifted arrows
+            KeyCode::Up if shift => self.move_detail_commit(pr_number, commit_count, true),
+            KeyCode::Down if shift => self.move_detail_commit(pr_number, commit_count, false),
+            KeyCode::Char('K') => self.move_detail_commit(pr_number, commit_count, true),
+            KeyCode::Char('J') => self.move_detail_commit(pr_number, commit_count, false),
+            KeyCode::Up | KeyCode::Char('k') => self.state.detail_commit_state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.state.detail_commit_state.select_next(),
+            KeyCode::Enter => self.request_cherry_pick(idx).await?,
+            KeyCode::Char('S') => self.state.toggle_squash(pr_number, self.config.github.squash_picks),
+            KeyCode::Char('n') => self.state.start_note_edit(pr_number),
+            KeyCode::Char('s') => {
+                self.skip_pr(idx).await;
+                self.state.current_screen = Screen::PrList;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Picks the PR of a run over several targets onto the next one once its
+    /// pick onto the current one is back on the list with nothing to confirm.
+    /// A pick that stopped ends the run, leaving its branch the target so the
+    /// pick can be continued.
+    async fn advance_target_run(&mut self) -> Result<()> {
+        if self.state.target_run.is_none() {
+            return Ok(());
+        }
+        if matches!(self.state.current_screen, Screen::Error | Screen::Conflicts) {
+            self.state.target_run = None;
+            return Ok(());
+        }
+        if !matches!(self.state.current_screen, Screen::PrList)
+            || self.state.confirmation.is_some()
+            || self.state.input_active
+        {
+            return Ok(());
+        }
+        let Some(run) = self.state.target_run.as_mut() else {
+            return Ok(());
+        };
+        let number = run.pr_number;
+        let held_back = run.held_back;
+        let Some(branch) = run.advance().map(str::to_string) else {
+            self.end_target_run();
+            return Ok(());
+        };
+        self.config.github.target_branch = branch;
+        self.state.pick_held_back = held_back;
+        match self.state.prs.iter().position(|pr| pr.number == number) {
+            Some(pr_index) => self.cherry_pick_pr(pr_index).await,
+            None => {
+                self.end_target_run();
+                Ok(())
+            }
+        }
+    }
+
+    /// Drops the run over several targets, going back to its first branch
+    fn end_target_run(&mut self) {
+        if let Some(run) = self.state.target_run.take() {
+            self.config.github.target_branch = run.branches[0].clone();
+        }
+    }
+
+    /// Starts the next queued follow-up once the previous pick is back on the
+    /// list with nothing to confirm. A pick that stopped drops the rest.
+    async fn advance_pick_queue(&mut self) -> Result<()> {
+        if self.state.pick_queue.is_empty() {
+            // A batch is over once its last pick is done with
+            if !matches!(self.state.current_screen, Screen::Progress | Screen::HookOutput)
+                && self.state.confirmation.is_none()
+            {
+                self.state.batch_total = None;
+            }
+            return Ok(());
+        }
+        if self.state.target_run.is_some() {
+            return Ok(());
+        }
+        if matches!(self.state.current_screen, Screen::Error | Screen::Conflicts) {
+            let skipped = std::mem::take(&mut self.state.pick_queue);
+            tracing::warn!(prs = ?skipped, "Skipping the queued picks after a pick stopped");
+            let note = tf("status.queue_skipped", &[("count", &skipped.len())]);
+            if let Some(message) = self.state.error_message.as_mut().or(self.state.success_message.as_mut()) {
+                message.push_str(&note);
+            }
+            self.state.batch_total = None;
+            return Ok(());
+        }
+        if !matches!(self.state.current_screen, Screen::PrList)
+            || self.state.confirmation.is_some()
+            || self.state.input_active
+        {
+            return Ok(());
+        }
+        let number = self.state.pick_queue.remove(0);
+        self.state.pick_held_back = HeldBack::default();
+        match self.state.prs.iter().position(|pr| pr.number == number) {
+            Some(pr_index) => self.request_single_pick(pr_index).await,
+            None => Ok(()),
+        }
+    }
+
+    fn move_detail_commit(&mut self, pr_number: u64, commit_count: usize, up: bool) {
+        if let Some(position) = self.state.detail_commit_state.selected() {
+            if let Some(new_position) =
+                self.state
+                    .move_commit(pr_number, commit_count, position, up)
+            {
+                self.state.detail_commit_state.select(Some(new_position));
+            }
+        }
+    }
+
+    /// Starts a pick, first offering to queue the PR's listed follow-ups after it
+    async fn request_cherry_pick(&mut self, pr_index: usize) -> Result<()> {
+        self.state.pick_held_back = HeldBack::default();
+        if let Some(number) = self.state.prs.get(pr_index).map(|pr| pr.number) {
+            let follow_ups = self.state.pending_follow_ups(number);
+            if !follow_ups.is_empty() {
+                self.state.confirmation = Some(PendingConfirmation::IncludeFollowUps {
+                    pr_index,
+                    number,
+                    follow_ups,
+                });
+                return Ok(());
+            }
+        }
+        self.request_single_pick(pr_index).await
+    }
+
+    /// Starts a pick, asking for confirmation first when the commit order was changed
+    async fn request_single_pick(&mut self, pr_index: usize) -> Result<()> {
+        let reordered = self
+            .state
+            .prs
+            .get(pr_index)
+            .is_some_and(|pr| self.state.is_reordered(pr.number));
+        if reordered {
+            self.state.confirmation = Some(PendingConfirmation::ReorderedPick { pr_index });
+            return Ok(());
+        }
+        self.cherry_pick_pr(pr_index).await
+    }
+
+    async fn run_confirmed(&mut self, confirmation: PendingConfirmation) -> Result<()> {
+        match confirmation {
+            PendingConfirmation::ReorderedPick { pr_index } => self.cherry_pick_pr(pr_index).await,
+            PendingConfirmation::MergeCommitFallback { pr_index, sha } => {
+                let Some(pr) = self.state.prs.get(pr_index).cloned() else {
+                    return Ok(());
+                };
+                self.pick_commits(&pr, vec![sha], false).await
+            }
+            PendingConfirmation::UnpushedCommits { pr_index, shas, reordered, branch, .. } => {
+                self.state.accepted_unpushed.insert(branch);
+                let Some(pr) = self.state.prs.get(pr_index).cloned() else {
+                    return Ok(());
+                };
+                self.pick_commits(&pr, shas, reordered).await
+            }
+            PendingConfirmation::ExistingBranch { .. } => {
+                self.pick_onto_existing_branch(confirmation, ExistingBranchChoice::Reuse).await
+            }
+            PendingConfirmation::DetachedHead { pr_index, shas, reordered, branch, .. } => {
+                if let Err(e) = self.git_ops.checkout_branch(&branch) {
+                    self.state
+                        .set_error(tf("error.checkout_target", &[("error", &e)]));
+                    self.state.current_screen = Screen::Error;
+                    return Ok(());
+                }
+                let Some(pr) = self.state.prs.get(pr_index).cloned() else {
+                    return Ok(());
+                };
+                self.pick_commits(&pr, shas, reordered).await
+            }
+            PendingConfirmation::SubmoduleUpdates { pr_index, shas, reordered, .. } => {
+                let Some(pr) = self.state.prs.get(pr_index).cloned() else {
+                    return Ok(());
+                };
+                self.state.accepted_submodules.insert(pr.number);
+                self.pick_commits(&pr, shas, reordered).await
+            }
+            PendingConfirmation::PickOptions { pr_index, held_back, .. } => {
+                self.state.pick_held_back = held_back;
+                self.request_single_pick(pr_index).await
+            }
+            PendingConfirmation::FetchPr { number } => {
+                self.fetch_pr_on_demand(number).await;
+                Ok(())
+            }
+            PendingConfirmation::RerunBookkeeping { .. } => {
+                if let Err(e) = self.rerun_bookkeeping().await {
+                    self.state
+                        .set_error(tf("error.rerun_bookkeeping", &[("error", &e)]));
+                    self.state.current_screen = Screen::Error;
+                }
+                Ok(())
+            }
+            PendingConfirmation::IncludeFollowUps { pr_index, follow_ups, .. } => {
+                self.state.pick_queue = follow_ups;
+                self.request_single_pick(pr_index).await
+            }
+            PendingConfirmation::AbortSession { .. } => {
+                if let Err(e) = self.abort_session() {
+                    self.state
+                        .set_error(tf("error.abort_session", &[("error", &e)]));
+                    self.state.current_screen = Screen::Error;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs the pick the existing backport branch in `confirmation` was asked
+    /// about, handling the branch as `choice` says
+    async fn pick_onto_existing_branch(&mut self, confirmation: PendingConfirmation, choice: ExistingBranchChoice) -> Result<()> {
+        let PendingConfirmation::ExistingBranch { pr_index, shas, reordered, branch, .. } = confirmation else {
+            return Ok(());
+        };
+        self.state.branch_choice = Some((branch, choice));
+        let Some(pr) = self.state.prs.get(pr_index).cloned() else {
+            return Ok(());
+        };
+        self.pick_commits(&pr, shas, reordered).await
+    }
+
+    /// Resets the target branch to origin, dropping its unpushed commits, then runs the pick
+    async fn reset_and_pick(&mut self, confirmation: PendingConfirmation) -> Result<()> {
+        let PendingConfirmation::UnpushedCommits { pr_index, shas, reordered, branch, .. } = confirmation else {
+            return Ok(());
+        };
+        if let Err(e) = self.git_ops.reset_branch_to_remote(&branch) {
+            self.state
+                .set_error(tf("error.reset_to_origin", &[("branch", &branch), ("error", &e)]));
+            self.state.current_screen = Screen::Error;
+            return Ok(());
+        }
+        // Anything this session picked onto the branch went with the reset
+        self.state.recently_cherry_picked.retain(|pick| pick.branch != branch);
+
+        let Some(pr) = self.state.prs.get(pr_index).cloned() else {
+            return Ok(());
+        };
+        self.pick_commits(&pr, shas, reordered).await
+    }
+
+    /// Commits on `branch` that the remote doesn't have and that this session
+    /// didn't pick, after refreshing `<remote>/<branch>`
+    fn foreign_unpushed_commits(&self, branch: &str) -> Vec<UnpushedCommit> {
+        if let Err(e) = self.git_ops.fetch_branch(branch) {
+            tracing::warn!("Could not refresh {}/{}: {}", self.config.github.remote_name, branch, e);
+        }
+        let picked: Vec<&String> = self
+            .state
+            .recently_cherry_picked
+            .iter()
+            .filter(|pick| pick.branch == branch)
+            .flat_map(|pick| &pick.commits)
+            .collect();
+        match self.git_ops.unpushed_commits(branch) {
+            Ok(commits) => commits
+                .into_iter()
+                .filter(|c| !picked.contains(&&c.sha))
+                .collect(),
+            Err(e) => {
+                tracing::warn!("Could not compare {} with {}: {}", branch, self.config.github.remote_name, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Records the conflict in `paths` and reports whether this session has seen it before
+    fn is_repeated_conflict(&mut self, paths: &[String]) -> bool {
+        self.git_ops
+            .conflict_signature(paths)
+            .is_some_and(|sig| self.state.conflict_history.record(sig) > 1)
+    }
+
+    /// Asks before reverting the session's picks, if there are any
+    fn request_abort_session(&mut self) {
+        let picks = &self.state.recently_cherry_picked;
+        if picks.is_empty() {
+            self.state.set_success(t("status.nothing_to_abort"));
+            return;
+        }
+        let commits = picks.iter().map(|p| p.commits.len()).sum();
+        self.state.confirmation = Some(PendingConfirmation::AbortSession {
+            prs: picks.len(),
+            commits,
+        });
+    }
+
+    /// Reverts every cherry-pick made in this session, newest first, then
+    /// returns to the starting branch and deletes the branches the session created.
+    /// Stops without touching anything further if a branch has moved on since
+    /// the pick, so commits made outside the app are never thrown away.
+    pub fn abort_session(&mut self) -> Result<()> {
+        if self.git_ops.cherry_pick_in_progress() {
+            self.git_ops.abort_cherry_pick()?;
+        }
+
+        let mut reverted_prs = Vec::new();
+        let mut reverted_commits = 0;
+        while let Some(pick) = self.state.recently_cherry_picked.last().cloned() {
+            self.git_ops.checkout_branch(&pick.branch)?;
+            for sha in pick.commits.iter().rev() {
+                let head = self.git_ops.head_sha()?;
+                if head != *sha {
+                    anyhow::bail!(tf(
+                        "error.abort_head_moved",
+                        &[("branch", &pick.branch), ("sha", &short_sha(sha))]
+                    ));
+                }
+                self.git_ops.undo_last_commit()?;
+                if let Some(last) = self.state.recently_cherry_picked.last_mut() {
+                    last.commits.pop();
+                }
+                reverted_commits += 1;
+            }
+            self.state.recently_cherry_picked.pop();
+            reverted_prs.push(pick.pr_number);
+        }
+
+        if let Some(start) = &self.session_start_branch {
+            self.git_ops.checkout_branch(start)?;
+        }
+        let current = self.git_ops.current_branch().ok();
+        let mut deleted = Vec::new();
+        for branch in std::mem::take(&mut self.state.created_branches) {
+            if current.as_deref() == Some(branch.as_str()) {
+                tracing::warn!(branch = %branch, "Keeping created branch; it is checked out");
+                continue;
+            }
+            self.git_ops.delete_branch(&branch)?;
+            deleted.push(branch);
+        }
+
+        tracing::info!(
+            prs = ?reverted_prs,
+            commits = reverted_commits,
+            deleted_branches = ?deleted,
+            "Aborted cherry-pick session"
+        );
+        self.state.set_success(&tf(
+            "status.session_aborted",
+            &[("commits", &reverted_commits), ("prs", &reverted_prs.len()), ("branches", &deleted.len())],
+        ));
+        Ok(())
+    }
+
+    async fn handle_progress_input(&mut self, _key: KeyCode) -> Result<()> {
+        // Progress screen doesn't handle input
+        Ok(())
+    }
+
+    async fn load_prs(&mut self) -> Result<()> {
+        self.state.set_loading(t("status.loading_prs"));
+        self.state.current_git_op = GitOperation::Idle;
+        self.state.current_screen = Screen::Progress;
+
+        let bypass_labels = self.state.take_label_bypass();
+        let requests_before = self.api_usage();
+        let result = self.showing_rate_limit_waits(self.fetch_listing(bypass_labels)).await;
+        self.state.auto_refresh.refreshed(Instant::now());
+        let sso_url = result.as_ref().err().and_then(GitHubError::sso_url).map(str::to_string);
+        match result {
+            Ok(Listing { prs, warnings }) => {
+                // PRs found without the labels don't mean the criteria matched anything
+                if !bypass_labels {
+                    self.listing_empty = prs.is_empty();
+                }
+                let count = prs.len();
+                let changes = self.state.set_prs(prs);
+                self.spawn_availability_check();
+                self.state.current_screen = Screen::PrList;
+                if bypass_labels {
+                    self.state
+                        .set_success(&tf("status.labels_bypassed", &[("count", &count)]));
+                } else {
+                    self.note_listing_changes(&changes);
+                }
+                self.note_skipped_prs(&warnings);
+                if self.report_api_usage {
+                    self.note_api_usage(&requests_before);
+                }
+            }
+            Err(GitHubError::PartialListing { prs, source }) => {
+                self.state.set_error(tf(
+                    "error.load_prs_partial",
+                    &[("error", &source), ("count", &prs.len())],
+                ));
+                self.state.partial_listing = Some(prs);
+                self.state.current_screen = Screen::Error;
+            }
+            Err(e) => {
+                self.state.set_error(tf("error.load_prs", &[("error", &e)]));
+                self.state.current_screen = Screen::Error;
+            }
+        }
+        if let Some(url) = sso_url {
+            self.offer_sso(url);
+        }
+
+        Ok(())
+    }
+
+    /// Says what a reload changed: how many PRs are new or updated, and which
+    /// ones dropped off the list
+    fn note_listing_changes(&mut self, changes: &ListingChanges) {
+        if changes.is_empty() {
+            return;
+        }
+        let mut notes = Vec::new();
+        if !changes.added.is_empty() || !changes.updated.is_empty() {
+            notes.push(tf(
+                "status.listing_changed",
+                &[("new", &changes.added.len()), ("updated", &changes.updated.len())],
+            ));
+        }
+        if !changes.gone.is_empty() {
+            let numbers: Vec<String> = changes.gone.iter().map(|n| format!("#{}", n)).collect();
+            notes.push(tf(
+                "status.listing_gone",
+                &[("count", &changes.gone.len()), ("numbers", &numbers.join(", "))],
+            ));
+        }
+        self.state.set_success(&notes.join("; "));
+    }
+
+    /// Adds the requests made since `before` to the status line, after any
+    /// message already there
+    /// Says how many PRs the listing skipped; the log has why
+    fn note_skipped_prs(&mut self, warnings: &[String]) {
+        if warnings.is_empty() {
+            return;
+        }
+        let note = tf("status.prs_not_evaluated", &[("count", &warnings.len())]);
+        let message = match self.state.success_message.take() {
+            Some(message) => format!("{}; {}", message, note),
+            None => note,
+        };
+        self.state.set_success(&message);
+    }
+
+    fn note_api_usage(&mut self, before: &ApiUsage) {
+        let usage = self.api_usage().since(before);
+        let note = tf("status.api_usage", &[("usage", &usage)]);
+        let message = match self.state.success_message.take() {
+            Some(message) => format!("{}; {}", message, note),
+            None => note,
+        };
+        self.state.set_success(&message);
+    }
+
+    /// Adds the SSO authorization page to the error screen, opening it right
+    /// away with `--open-sso`
+    fn offer_sso(&mut self, url: String) {
+        if let Some(message) = &mut self.state.error_message {
+            message.push_str("\n\n");
+            message.push_str(t("error.sso_open_hint"));
+        }
+        self.state.sso_url = Some(url);
+        if self.open_sso {
+            self.open_sso_page();
+        }
+    }
+
+    /// Opens the SSO page in the browser, noting the result under the error
+    fn open_sso_page(&mut self) {
+        let Some(url) = self.state.sso_url.clone() else {
+            return;
+        };
+        let note = match open_in_browser(&url) {
+            Ok(()) => tf("status.sso_opened", &[("url", &url)]),
+            Err(e) => tf("status.sso_open_failed", &[("url", &url), ("error", &e)]),
+        };
+        if let Some(message) = &mut self.state.error_message {
+            message.push('\n');
+            message.push_str(&note);
+        }
+    }
+
+    /// Lists the PRs a failed load matched before it failed
+    fn show_partial_listing(&mut self) {
+        let Some(prs) = self.state.partial_listing.take() else {
+            return;
+        };
+        let count = prs.len();
+        self.state.set_prs(prs);
+        self.spawn_availability_check();
+        self.state.current_screen = Screen::PrList;
+        self.state
+            .set_success(&tf("status.partial_listing", &[("count", &count)]));
+    }
+
+    /// The PRs to list: the one asked for with `--pr`, or those matching the
+    /// criteria. The request doesn't borrow the app, which can redraw meanwhile.
+    fn fetch_listing(&self, bypass_labels: bool) -> impl Future<Output = Result<Listing, GitHubError>> {
+        let client = self.github_client.clone();
+        let pr_source = self.pr_source.clone();
+        let explicit_pr = self.explicit_pr;
+        async move {
+            match explicit_pr {
+                Some(number) => client.get_pr_in(&pr_source, number).await.map(|pr| Listing {
+                    prs: vec![pr],
+                    warnings: Vec::new(),
+                }),
+                None if bypass_labels => {
+                    client
+                        .listing_in(&pr_source, &client.filter().without_labels())
+                        .await
+                }
+                None => client.listing_in(&pr_source, client.filter()).await,
+            }
+        }
+    }
+
+    /// Awaits `request` on the loading screen, redrawn with how long each
+    /// rate-limited retry waits and back to the loading message once it's sent
+    async fn showing_rate_limit_waits<T>(&mut self, request: impl Future<Output = T>) -> T {
+        let mut waits = self.github_client.rate_limit_waits();
+        let loading = self.state.loading_message.clone().unwrap_or_default();
+        let mut request = std::pin::pin!(request);
+        loop {
+            tokio::select! {
+                result = &mut request => return result,
+                Ok(()) = waits.changed() => {
+                    let message = match *waits.borrow_and_update() {
+                        Some(wait) => tf("status.rate_limited_retrying", &[("secs", &wait.as_secs())]),
+                        None => loading.clone(),
+                    };
+                    self.state.set_loading(&message);
+                    if let Err(e) = self.draw() {
+                        tracing::warn!("Failed to draw the rate limit wait: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reloads the PR list in place once the auto-refresh interval is up and the
+    /// user is idle. Unlike `load_prs` it stays on the list, keeps the highlighted
+    /// PR, and reports a failure as a toast.
+    async fn auto_refresh_if_due(&mut self) {
+        let busy = self.state.auto_refresh_blocked() || self.git_ops.cherry_pick_in_progress();
+        if !self.state.auto_refresh.due(Instant::now(), busy) {
+            return;
+        }
+        self.refresh_in_place().await;
+    }
+
+    /// Reloads the PR list without leaving it, as [`Self::auto_refresh_if_due`] does
+    async fn refresh_in_place(&mut self) {
+        let result = self.fetch_listing(false).await;
+        self.state.auto_refresh.refreshed(Instant::now());
+        match result {
+            Ok(Listing { prs, warnings }) => {
+                self.listing_empty = prs.is_empty();
+                let changes = self.state.replace_prs(prs);
+                self.spawn_availability_check();
+                self.note_listing_changes(&changes);
+                self.note_skipped_prs(&warnings);
+            }
+            Err(e) => {
+                tracing::warn!("Automatic refresh failed: {:#}", e);
+                self.state
+                    .set_success(&tf("status.auto_refresh_failed", &[("error", &e)]));
+            }
+        }
+    }
+
+    /// With `ui.watch_env`, applies cherry.env again once it has changed and
+    /// nothing it would interrupt is open, then reloads the PR list if the
+    /// config came out different
+    async fn reload_env_if_changed(&mut self) {
+        let busy = self.state.auto_refresh_blocked() || self.git_ops.cherry_pick_in_progress();
+        let Some(watch) = self.env_watch.as_mut().filter(|_| !busy) else {
+            return;
+        };
+        if !watch.changed(Instant::now()) {
+            return;
+        }
+        let reloaded = self.config.reload_env(watch.project_dir()).and_then(|config| match config {
+            Some(config) => Ok(Some((configured_git_ops(&config)?, config))),
+            None => Ok(None),
+        });
+        match reloaded {
+            Ok(Some((git_ops, config))) => {
+                self.github_client = self.github_client.for_config(&config);
+                self.git_ops = git_ops;
+                self.state.auto_refresh = AutoRefresh::new(config.ui.auto_refresh_secs, Instant::now());
+                self.state.text_match = config.ui.text_match();
+                self.config = config;
+                self.refresh_in_place().await;
+                let status = if self.config.env_warnings.is_empty() {
+                    t("status.env_reloaded").to_string()
+                } else {
+                    tf("status.env_warnings", &[("warnings", &self.config.env_warnings.join("; "))])
+                };
+                self.state.set_success(&status);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("Could not reload cherry.env: {:#}", e);
+                self.state.set_success(&tf("status.env_reload_failed", &[("error", &e)]));
+            }
+        }
+    }
+
+    /// Uses the edited criteria for the rest of the session and reloads with them
+    async fn apply_criteria(&mut self, criteria: Criteria) -> Result<()> {
+        criteria.apply_to(&mut self.config);
+        self.github_client
+            .set_criteria(self.config.tags.clone(), self.config.ui.days_back);
+        // A different listing isn't news
+        self.state.listing_loaded = false;
+        self.load_prs().await
+    }
+
+    /// Resolves the jump prompt's input. Invalid input keeps the prompt open
+    /// with a message; a PR that isn't loaded is offered for fetching.
+    fn submit_jump(&mut self) {
+        let input = self.state.input_buffer.trim().trim_start_matches('#').to_string();
+        let Ok(number) = input.parse::<u64>() else {
+            self.state.prompt_error = Some(tf("jump.invalid", &[("input", &self.state.input_buffer)]));
+            return;
+        };
+        self.state.confirm_prompt();
+        if self.state.jump_to_pr(number) == JumpTarget::NotLoaded {
+            self.state.confirmation = Some(PendingConfirmation::FetchPr { number });
+        }
+    }
+
+    /// Fetches one PR by number and adds it to the list, selected
+    async fn fetch_pr_on_demand(&mut self, number: u64) {
+        match self.github_client.get_pr_in(&self.pr_source, number).await {
+            Ok(pr) => {
+                self.state.add_fetched_pr(pr);
+                self.spawn_availability_check();
+            }
+            Err(e) => {
+                self.state.set_error(tf(
+                    "error.fetch_pr",
+                    &[("number", &number), ("error", &e)],
+                ));
+                self.state.current_screen = Screen::Error;
+            }
+        }
+    }
+
+    /// Checks on a blocking thread which PR head commits exist in the local
+    /// clone and, for those that do, whether picking them would conflict
+    fn spawn_availability_check(&mut self) {
+        if let Some(previous) = self.availability_check.take() {
+            previous.abort();
+        }
+        let prs: Vec<(String, Vec<String>)> = self
+            .state
+            .prs
+            .iter()
+            .map(|pr| (pr.head_sha.clone(), pr.commits.iter().map(|c| c.sha.clone()).collect()))
+            .collect();
+        let target_branch = self.config.github.target_branch.clone();
+        let repo_path = self.git_ops.path().to_path_buf();
+        self.availability_check = Some(tokio::task::spawn_blocking(move || {
+            let mut check = LocalCheck::default();
+            let Ok(git_ops) = GitOperations::new(&repo_path) else {
+                return check;
+            };
+            for (head_sha, shas) in prs {
+                let exists = git_ops.commit_exists(&head_sha);
+                if exists && !shas.is_empty() {
+                    match git_ops.predict_clean_pick(&target_branch, &shas) {
+                        Ok(clean) => {
+                            check.clean.insert(head_sha.clone(), clean);
+                        }
+                        Err(e) => tracing::debug!(head_sha = %head_sha, "No conflict prediction: {}", e),
+                    }
+                }
+                check.available.insert(head_sha, exists);
+            }
+            check
+        }));
+    }
+
+    /// Starts looking up the authenticated user unless startup already did
+    fn spawn_user_lookup(&mut self) {
+        if self.run_info.is_none() && self.user_lookup.is_none() {
+            self.user_lookup = Some(tokio::spawn(lookup_user(self.github_client.clone())));
+        }
+    }
+
+    /// Keeps the background user lookup's answer once it has finished
+    async fn collect_user_lookup(&mut self) {
+        if self.user_lookup.as_ref().is_some_and(|h| h.is_finished()) {
+            self.run_info().await;
+        }
+    }
+
+    /// Merges the availability check's results into the state once it has finished
+    async fn collect_availability(&mut self) {
+        if !self.availability_check.as_ref().is_some_and(|h| h.is_finished()) {
+            return;
+        }
+        if let Some(handle) = self.availability_check.take() {
+            match handle.await {
+                Ok(check) => {
+                    self.state.commit_availability.extend(check.available);
+                    self.state.conflict_predictions.extend(check.clean);
+                    if self.state.quick_filters.conflict_free {
+                        self.state.recompute_display_indices();
+                    }
+                }
+                Err(e) => tracing::warn!("Commit availability check failed: {}", e),
+            }
+        }
+    }
+
+    /// Fetches the PR's head branch so its commits are available locally.
+    /// Returns false (with the error screen shown) if the fetch failed.
+    fn fetch_pr_head(&mut self, pr_index: usize) -> bool {
+        let Some(pr) = self.state.prs.get(pr_index) else {
+            return false;
+        };
+        let (head_ref, head_sha) = (pr.head_ref.clone(), pr.head_sha.clone());
+
+        self.state.set_loading(&tf("status.fetching", &[("branch", &head_ref)]));
+        self.state.current_git_op = GitOperation::Fetch;
+        self.state.current_screen = Screen::Progress;
+
+        if let Err(e) = self.git_ops.fetch_branch(&head_ref) {
+            self.state
+                .set_error(tf("error.fetch_branch", &[("branch", &head_ref), ("error", &e)]));
+            self.state.current_screen = Screen::Error;
+            return false;
+        }
+
+        let exists = self.git_ops.commit_exists(&head_sha);
+        self.state.commit_availability.insert(head_sha, exists);
+        self.state.loading_message = None;
+        self.state.current_git_op = GitOperation::Idle;
+        true
+    }
+
+    /// Works out what can be picked for `pr`, fetching its branches and pull ref
+    /// first if any commits are missing locally
+    fn resolve_pick_source(&mut self, pr: &PrInfo) -> PickSource {
+        let git_ops = &self.git_ops;
+        let source = workflow::resolve_pick_source(
+            pr,
+            |sha| git_ops.commit_exists(sha),
+            || {
+                for result in [
+                    git_ops.fetch_branch(&pr.head_ref),
+                    git_ops.fetch_pull_ref(pr.number),
+                    git_ops.fetch_branch(&pr.base_ref),
+                ] {
+                    if let Err(e) = result {
+                        tracing::warn!("Fetch for PR #{} failed: {}", pr.number, e);
+                    }
+                }
+            },
+        );
+
+        let head_present = self.git_ops.commit_exists(&pr.head_sha);
+        self.state
+            .commit_availability
+            .insert(pr.head_sha.clone(), head_present);
+        source
+    }
+
+    /// The merge or squash commit to pick for `pr` in place of its commits,
+    /// fetching the base branch when it isn't local yet
+    fn landed_commit(&self, pr: &PrInfo) -> Option<String> {
+        let sha = pr.merge_commit_sha.as_deref()?;
+        if !self.git_ops.commit_exists(sha) {
+            if let Err(e) = self.git_ops.fetch_branch(&pr.base_ref) {
+                tracing::warn!("Fetch of {} for PR #{} failed: {}", pr.base_ref, pr.number, e);
+            }
+        }
+        workflow::landed_commit(pr, |sha| self.git_ops.commit_header(sha).ok())
+    }
+
+    async fn cherry_pick_pr(&mut self, pr_index: usize) -> Result<()> {
+        // Get PR details before borrowing mutably
+        let pr = if let Some(pr) = self.state.prs.get(pr_index) {
+            pr.clone()
+        } else {
+            return Ok(());
+        };
+
+        // Several targets take the pick in turn, starting with the first
+        let targets = self.config.github.pick_targets();
+        if targets.len() > 1 && self.state.target_run.as_ref().is_none_or(|run| run.pr_number != pr.number) {
+            self.config.github.target_branch = targets[0].clone();
+            self.state.target_run = Some(TargetRun::new(pr.number, targets, self.state.pick_held_back));
+        }
+
+        // What landed on the base branch, unless the commit order was chosen by hand
+        if !self.state.is_reordered(pr.number) {
+            if let Some(sha) = self.landed_commit(&pr) {
+                tracing::info!("PR #{} was merged as {}; picking that", pr.number, short_sha(&sha));
+                return self.pick_commits(&pr, vec![sha], false).await;
+            }
+        }
+
+        // Make sure the commits exist before starting rather than failing mid-pick
+        let source = self.resolve_pick_source(&pr);
+        if source != PickSource::Commits {
+            self.state.record_pick_source(pr.number, source.clone());
+        }
+        match source {
+            PickSource::Commits => {
+                let reordered = self.state.is_reordered(pr.number);
+                let shas = self
+                    .state
+                    .commit_order_for(pr.number, pr.commits.len())
+                    .into_iter()
+                    .map(|i| pr.commits[i].sha.clone())
+                    .collect();
+                self.pick_commits(&pr, shas, reordered).await
+            }
+            PickSource::MergeCommit { sha } => {
+                self.state.confirmation =
+                    Some(PendingConfirmation::MergeCommitFallback { pr_index, sha });
+                Ok(())
+            }
+            PickSource::Unpickable => {
+                self.state
+                    .set_error(tf("error.unpickable", &[("number", &pr.number)]));
+                self.state.current_screen = Screen::Error;
+                Ok(())
+            }
+        }
+    }
+
+    /// Submodule pointers any of `shas` moves. A commit that can't be read is
+    /// left out; the pick itself will report it.
+    fn submodule_updates(&self, shas: &[String]) -> Vec<SubmoduleUpdate> {
+        shas.iter()
+            .flat_map(|sha| {
+                self.git_ops.submodule_updates(sha).unwrap_or_else(|e| {
+                    tracing::warn!("Could not look for submodule changes in {}: {}", short_sha(sha), e);
+                    Vec::new()
+                })
+            })
+            .collect()
+    }
+
+    /// The CODEOWNERS rules on `branch`, from the first place GitHub looks
+    /// that has them. `None` when none does or the branch can't be read.
+    fn code_owners_on(&self, branch: &str) -> Option<CodeOwners> {
+        for path in codeowners::LOCATIONS {
+            match self.git_ops.file_on_branch(branch, path) {
+                Ok(Some(text)) => {
+                    let owners = CodeOwners::parse(&text);
+                    if !owners.skipped_lines.is_empty() {
+                        tracing::warn!(branch, path, lines = ?owners.skipped_lines, "Ignoring invalid CODEOWNERS lines");
+                    }
+                    return Some(owners);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::debug!("Could not read {} on {}: {}", path, branch, e);
+                    return None;
+                }
+            }
+        }
+        None
+    }
+
+    /// The files `shas` change; a commit that can't be read adds none
+    fn changed_paths(&self, shas: &[String]) -> Vec<String> {
+        shas.iter()
+            .flat_map(|sha| {
+                self.git_ops.changed_paths(sha).unwrap_or_else(|e| {
+                    tracing::warn!("Could not list the files {} changes: {}", short_sha(sha), e);
+                    Vec::new()
+                })
+            })
+            .collect()
+    }
+
+    /// The teams `branch`'s CODEOWNERS gives `paths` to
+    fn code_owner_teams(&self, branch: &str, paths: &[String]) -> Vec<String> {
+        self.code_owners_on(branch)
+            .map(|owners| owners.teams_for(paths.iter().map(String::as_str)))
+            .unwrap_or_default()
+    }
+
+    /// Names the code owner teams of the picked files on `target`, warning
+    /// when `base` gives them to other teams, as the backport then needs
+    /// other approvals. `None` when no team owns them on either branch.
+    fn code_owners_note(&self, base: &str, target: &str, shas: &[String]) -> Option<String> {
+        let paths = self.changed_paths(shas);
+        let target_teams = self.code_owner_teams(target, &paths);
+        let base_teams = self.code_owner_teams(base, &paths);
+        let list = |teams: &[String]| {
+            if teams.is_empty() {
+                t("status.code_owners_nobody").to_string()
+            } else {
+                teams.join(", ")
+            }
+        };
+        if target_teams == base_teams {
+            (!target_teams.is_empty()).then(|| {
+                tf("status.code_owners", &[("branch", &target), ("teams", &list(&target_teams))])
+            })
+        } else {
+            Some(tf(
+                "status.code_owners_changed",
+                &[
+                    ("branch", &target),
+                    ("teams", &list(&target_teams)),
+                    ("base", &base),
+                    ("base_teams", &list(&base_teams)),
+                ],
+            ))
+        }
+    }
+
+    /// Reads keys typed while a pick runs, so `q` and Ctrl+C can stop it;
+    /// other keys wait until the pick is done
+    fn poll_quit_keys(&mut self) {
+        while event::poll(Duration::ZERO).unwrap_or(false) {
+            let Ok(event) = event::read() else { break };
+            let Event::Key(key) = event else { continue };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match quit_key(&key) {
+                Some(interrupt) => {
+                    if self.state.quit.request(true, interrupt) == QuitAction::AfterCurrentStep {
+                        self.state.set_loading(t("status.quit_pending"));
+                    }
+                }
+                None => self.held_keys.push_back(key),
+            }
+        }
+    }
+
+    /// Reads keys typed while several targets are picked at once: Esc, like
+    /// quitting, starts no more of them
+    fn poll_target_keys(&mut self, cancel: &CancelFlag) {
+        self.poll_quit_keys();
+        let before = self.held_keys.len();
+        self.held_keys.retain(|key| key.code != KeyCode::Esc);
+        if self.held_keys.len() < before || self.state.quit.is_pending() {
+            cancel.cancel();
+        }
+    }
+
+    /// Ends the session after a pick the user quit during, leaving a note on
+    /// what it didn't get to
+    fn quit_after_pick(&mut self, record: &PickRecord, total: usize, skipped_bookkeeping: bool) {
+        self.exit_note = if record.status == PickStatus::Interrupted {
+            Some(tf(
+                "cli.pick_interrupted",
+                &[
+                    ("number", &record.pr_number),
+                    ("picked", &record.commits.len()),
+                    ("total", &total),
+                ],
+            ))
+        } else if record.status == PickStatus::Picked && skipped_bookkeeping {
+            Some(tf("cli.bookkeeping_skipped", &[("number", &record.pr_number)]))
+        } else {
+            None
+        };
+        self.state.pick_queue.clear();
+        self.state.target_run = None;
+        self.held_keys.clear();
+        self.should_quit = true;
+    }
+
+    /// Quits at a second Ctrl+C without waiting for the pick of PR
+    /// `pr_number` onto `branch` still running, which is neither logged nor
+    /// labelled; the exit note says to check the branch
+    fn abandon_pick(&mut self, pr_number: u64, branch: &str) {
+        tracing::warn!(pr = pr_number, branch = %branch, "Quit without waiting for the pick under way");
+        self.exit_note = Some(tf("cli.pick_abandoned", &[("number", &pr_number), ("branch", &branch)]));
+        self.abandoned_pick = true;
+        self.state.pick_queue.clear();
+        self.state.target_run = None;
+        self.held_keys.clear();
+        self.should_quit = true;
+    }
+
+    /// Whether quitting left a pick running that the process shouldn't wait for
+    pub fn abandoned_pick(&self) -> bool {
+        self.abandoned_pick
+    }
+
+    /// Applies `shas` to the target branch in order, then labels and comments on the PR
+    async fn pick_commits(&mut self, pr: &PrInfo, shas: Vec<String>, reordered: bool) -> Result<()> {
+        // Every way into a pick ends here, so this is the one place a dry run stops
+        if self.state.dry_run {
+            let target_branch = self.pick_branch();
+            self.state.show_dry_run(pr.number, &target_branch, &shas);
+            return Ok(());
+        }
+
+        // A moved submodule pointer only builds if its commit exists upstream
+        let submodule_updates = self.submodule_updates(&shas);
+        if !submodule_updates.is_empty() && !self.state.accepted_submodules.contains(&pr.number) {
+            if let Some(pr_index) = self.state.prs.iter().position(|p| p.number == pr.number) {
+                self.state.current_screen = Screen::PrList;
+                self.state.confirmation = Some(PendingConfirmation::SubmoduleUpdates {
+                    pr_index,
+                    shas,
+                    reordered,
+                    updates: submodule_updates,
+                });
+                return Ok(());
+            }
+        }
+
+        // Several targets at once leave this working tree alone
+        if let Some(targets) = self.parallel_targets(pr.number) {
+            return self
+                .pick_onto_targets(pr, targets, shas, reordered, submodule_updates)
+                .await;
+        }
+
+        // Leaving a detached HEAD strands any commits made on it, so ask first
+        let target_branch = self.pick_branch();
+        if let Ok(HeadState::Detached(head)) = self.git_ops.head_state() {
+            if let Some(pr_index) = self.state.prs.iter().position(|p| p.number == pr.number) {
+                self.state.current_screen = Screen::PrList;
+                self.state.confirmation = Some(PendingConfirmation::DetachedHead {
+                    pr_index,
+                    shas,
+                    reordered,
+                    head,
+                    branch: target_branch,
+                });
+                return Ok(());
+            }
+        }
+
+        // A backport branch left from an earlier run would fail the push late, so settle it now
+        let choice = self
+            .state
+            .branch_choice
+            .take()
+            .filter(|(branch, _)| *branch == target_branch)
+            .map(|(_, choice)| choice);
+        let plan = match self.plan_pick_branch(&target_branch, choice) {
+            Ok(Ok(plan)) => plan,
+            Ok(Err(presence)) => {
+                self.ask_about_existing_branch(pr, shas, reordered, target_branch, presence);
+                return Ok(());
+            }
+            Err(e) => {
+                self.state
+                    .set_error(tf("error.checkout_target", &[("error", &e)]));
+                self.state.current_screen = Screen::Error;
+                return Ok(());
+            }
+        };
+        // A new name holds for the rest of the session's picks
+        let target_branch = plan.name().to_string();
+        if target_branch != self.pick_branch() {
+            self.config.github.branch_name_template = target_branch.clone();
+        }
+
+        let mut picking = tf("status.picking", &[("number", &pr.number), ("title", &pr.title)]);
+        if let Some(run) = &self.state.target_run {
+            picking.push_str(&run.progress());
+        }
+        picking.push_str(&self.state.batch_progress());
+        self.state.set_loading(&picking);
+        self.state.current_screen = Screen::Progress;
+
+        // Switch to target branch
+        let branch_created = !self.git_ops.branch_exists(&target_branch) || matches!(plan, BranchPlan::Recreate { .. });
+        self.state.current_git_op = GitOperation::Checkout(target_branch.clone());
+        let source = self.config.github.cherry_pick_source_branch.clone();
+        // Told once the pick is done, as the status line is busy until then
+        let tracking_note = match self.git_ops.checkout_planned_branch(&plan, &source) {
+            Ok(tracked_from) => tracked_from.map(|remote| {
+                tf(
+                    "status.tracking_other_remote",
+                    &[("branch", &target_branch), ("remote", &remote)],
+                )
+            }),
+            Err(e) => {
+                self.state
+                    .set_error(tf("error.checkout_target", &[("error", &e)]));
+                self.state.current_screen = Screen::Error;
+                return Ok(());
+            }
+        };
+        if branch_created {
+            self.state.created_branches.push(target_branch.clone());
+        }
+        if let BranchPlan::Recreate { force_push: true, .. } = plan {
+            self.state.force_push_branches.insert(target_branch.clone());
+        }
+
+        // Don't silently stack picks on commits from some earlier, unpushed work
+        if !self.state.accepted_unpushed.contains(&target_branch) {
+            let commits = self.foreign_unpushed_commits(&target_branch);
+            if !commits.is_empty() {
+                let pr_index = self.state.prs.iter().position(|p| p.number == pr.number);
+                self.state.loading_message = None;
+                self.state.current_git_op = GitOperation::Idle;
+                self.state.current_screen = Screen::PrList;
+                if let Some(pr_index) = pr_index {
+                    self.state.confirmation = Some(PendingConfirmation::UnpushedCommits {
+                        pr_index,
+                        shas,
+                        reordered,
+                        branch: target_branch,
+                        commits,
+                    });
+                }
+                return Ok(());
+            }
+        }
+
+        let squash = shas.len() > 1 && self.state.squashes(pr.number, self.config.github.squash_picks);
+        let base_sha = match self.git_ops.head_sha() {
+            Ok(sha) => sha,
+            Err(e) => {
+                self.state
+                    .set_error(tf("error.checkout_target", &[("error", &e)]));
+                self.state.current_screen = Screen::Error;
+                return Ok(());
+            }
+        };
+
+        // The commits are applied on a task of their own so the UI keeps
+        // drawing and reading quit keys while a slow commit lands
+        let stop = CancelFlag::default();
+        let picking = Arc::new(AtomicUsize::new(0));
+        let path = self.git_ops.path().to_path_buf();
+        let github = self.config.github.clone();
+        let squash = squash.then(|| Squash {
+            base_sha,
+            pr_number: pr.number,
+            title: pr.title.clone(),
+        });
+        let task = tokio::task::spawn_blocking({
+            let (shas, stop, picking) = (shas.clone(), stop.clone(), Arc::clone(&picking));
+            move || match GitOperations::new(&path) {
+                Ok(git_ops) => apply_commits(&with_pick_settings(git_ops, &github), &shas, squash, &picking, &stop),
+                Err(e) => AppliedCommits::failed(tf("error.generic", &[("error", &e)])),
+            }
+        });
+        let mut task = std::pin::pin!(task);
+        let applied = loop {
+            if let Some(sha) = shas.get(picking.load(Ordering::SeqCst)) {
+                self.state.current_git_op = GitOperation::CherryPick(short_sha(sha).to_string());
+            }
+            if let Err(e) = self.draw() {
+                tracing::warn!(error = %e, "Failed to draw the pick progress");
+            }
+            tokio::select! {
+                applied = &mut task => break applied.unwrap_or_else(|e| AppliedCommits::failed(tf("error.generic", &[("error", &e)]))),
+                () = tokio::time::sleep(Duration::from_millis(100)) => self.poll_quit_keys(),
+            }
+            // Quitting lets the commit under way land but starts no more;
+            // a second Ctrl+C doesn't wait for it
+            if self.state.quit.is_forced() {
+                self.abandon_pick(pr.number, &target_branch);
+                return Ok(());
+            }
+            if self.state.quit.is_pending() {
+                stop.cancel();
+            }
+        };
+        let status = applied.status;
+        let cherry_picked_commits = applied.commits;
+        let rerere_used = applied.rerere_used;
+        let mainline_notes = applied.mainline_notes;
+        if let Some(error) = applied.error {
+            self.state.set_error(error);
+            self.state.current_screen = Screen::Error;
+        }
+        let mut conflict_message = String::new();
+        let (mut conflicts, mut binary_conflicts, mut remaining) = (Vec::new(), Vec::new(), Vec::new());
+        if let Some(stopped) = applied.conflicted {
+            conflict_message = tf("conflicts.stopped", &[("sha", &short_sha(&stopped.sha))]);
+            if self.is_repeated_conflict(&stopped.conflicts) && !self.git_ops.rerere_enabled() {
+                conflict_message.push(' ');
+                conflict_message.push_str(t("error.conflict_repeat_hint"));
+            }
+            conflicts = stopped.conflicts;
+            binary_conflicts = stopped.binary_conflicts;
+            remaining = stopped.remaining;
+        }
+
+        // Record even a partial pick so the session can still be aborted
+        if !cherry_picked_commits.is_empty() {
+            self.state
+                .recently_cherry_picked
+                .push(session_pick(pr.number, &target_branch, &cherry_picked_commits));
+        }
+
+        let mut record = PickRecord {
+            pr_number: pr.number,
+            status,
+            recorded_at: None,
+            pr_title: pr.title.clone(),
+            target_branch,
+            commits: cherry_picked_commits,
+            reordered,
+            labels_updated: false,
+            comment_added: false,
+            run: Some(self.run_info().await),
+            sides_taken: Vec::new(),
+            simulated: Vec::new(),
+            unverified_submodules: Vec::new(),
+            api_requests: None,
+            held_back: std::mem::take(&mut self.state.pick_held_back),
+            note: self.state.pr_notes.get(pr.number).map(str::to_string),
+        };
+        let mut not_permitted = false;
+        self.poll_quit_keys();
+        let skip_bookkeeping = self.state.quit.is_forced();
+        if status == PickStatus::Picked {
+            for update in submodule_updates {
+                if self.git_ops.submodule_commit_exists(&update) != Some(true) {
+                    tracing::warn!(path = %update.path, sha = %update.sha, "Submodule commit not found upstream");
+                    record.unverified_submodules.push(update);
+                }
+            }
+            // A failing hook keeps the pick off origin and GitHub until the user decides
+            if !skip_bookkeeping && !self.post_pick_hook_passed(&record).await {
+                self.hold_for_hook(record);
+                return Ok(());
+            }
+            if !skip_bookkeeping {
+                not_permitted = self.finish_bookkeeping(&mut record).await;
+            }
+        } else if status == PickStatus::Conflicted
+            && !skip_bookkeeping
+            && self.config.github.comment_on_conflict
+            && self.state.capabilities.can_comment
+        {
+            let reason = DeferralReason::Conflicts {
+                files: conflicts.clone(),
+            };
+            record.comment_added = self
+                .comment_deferral(pr.number, &record.target_branch, &reason)
+                .await;
+            record.simulated = self.take_simulated();
+        }
+        let mut publish_notes = String::new();
+        if status == PickStatus::Picked {
+            publish_notes = self.publish_pick(&mut record, !skip_bookkeeping).await;
+        }
+        record.recorded_at = Some(Utc::now());
+        self.log_pick(&record);
+        let simulated = !record.simulated.is_empty();
+        let held_back = !record.held_back.is_none();
+        if status == PickStatus::Picked {
+            self.note_target_use(&self.config.github.target_branch.clone());
+        }
+        if self.state.quit.is_pending() {
+            self.quit_after_pick(&record, shas.len(), skip_bookkeeping);
+            return Ok(());
+        }
+        let unverified: Vec<String> = record
+            .unverified_submodules
+            .iter()
+            .map(|update| format!("{}@{}", update.path, short_sha(&update.sha)))
+            .collect();
+
+        // The conflicts screen waits for them to be resolved, here for binary
+        // files or outside the app, so the pick can be continued
+        if status == PickStatus::Conflicted {
+            self.state.set_conflicts(&conflicts, &binary_conflicts, record);
+            self.state.conflict_remaining = remaining;
+            self.state.set_success(&conflict_message);
+            self.state.current_screen = Screen::Conflicts;
+        }
+
+        if status == PickStatus::Picked && !unverified.is_empty() {
+            self.state.set_success(&tf(
+                "status.picked_submodules_unverified",
+                &[("number", &pr.number), ("submodules", &unverified.join(", "))],
+            ));
+            self.state.current_screen = Screen::PrList;
+        } else if status == PickStatus::Picked {
+            let key = if not_permitted {
+                "status.picked_local_only"
+            } else if simulated {
+                "status.picked_simulated"
+            } else if held_back {
+                "status.picked_held_back"
+            } else if rerere_used {
+                "status.picked_rerere"
+            } else {
+                "status.picked"
+            };
+            let mut message = tf(key, &[("number", &pr.number)]);
+            if let Some(note) = tracking_note {
+                message.push_str(&note);
+            }
+            message.push_str(&publish_notes);
+            for note in mainline_notes {
+                message.push_str(&note);
+            }
+            if let Some(note) = self.code_owners_note(&pr.base_ref, &self.config.github.target_branch, &shas) {
+                message.push_str(&note);
+            }
+            if let Some(run) = &self.state.target_run {
+                message.push_str(&run.progress());
+            }
+            message.push_str(&self.state.batch_progress());
+            self.state.set_success(&message);
+            self.state.current_screen = Screen::PrList;
+        }
+
+        Ok(())
+    }
+
+    /// The branches of `pr_number`'s run over several targets, when
+    /// `use_worktree` has them picked at once and nothing calls for the
+    /// working tree or a question first: no backport branch or post-pick
+    /// hook, none of them checked out here and none with unpushed commits
+    /// this session didn't make
+    fn parallel_targets(&self, pr_number: u64) -> Option<Vec<String>> {
+        let github = &self.config.github;
+        if !github.use_worktree || github.create_backport_branch || github.post_pick_hook.is_some() {
+            return None;
+        }
+        let run = self
+            .state
+            .target_run
+            .as_ref()
+            .filter(|run| run.pr_number == pr_number && run.current == 0)?;
+        let checked_out = self.git_ops.current_branch().ok();
+        let free = |branch: &String| {
+            checked_out.as_ref() != Some(branch)
+                && (self.state.accepted_unpushed.contains(branch) || self.foreign_unpushed_commits(branch).is_empty())
+        };
+        run.branches.iter().all(free).then(|| run.branches.clone())
+    }
+
+    /// Picks `shas` onto each of `targets` in a worktree of its own, at most
+    /// `max_parallel_targets` at a time, then labels, comments and pushes for
+    /// every target that took them. A target that conflicts is left as it
+    /// was; Esc starts no more targets.
+    async fn pick_onto_targets(
+        &mut self,
+        pr: &PrInfo,
+        targets: Vec<String>,
+        shas: Vec<String>,
+        reordered: bool,
+        submodule_updates: Vec<SubmoduleUpdate>,
+    ) -> Result<()> {
+        let mut picker = WorktreePicker::new(self.git_ops.path(), std::env::temp_dir(), &self.config.github, shas.clone());
+        if shas.len() > 1 && self.state.squashes(pr.number, self.config.github.squash_picks) {
+            picker = picker.squashed(&pr.title);
+        }
+        let picking = tf("status.picking_targets", &[("number", &pr.number), ("count", &targets.len())]);
+        self.state.current_screen = Screen::Progress;
+
+        let cancel = CancelFlag::default();
+        let done = AtomicUsize::new(0);
+        let picks = workflow::pick_targets(
+            Arc::new(picker),
+            &targets,
+            self.config.github.max_parallel_targets,
+            &cancel,
+            |_, _| {
+                done.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+        let mut picks = std::pin::pin!(picks);
+        let outcomes = loop {
+            let message = if cancel.is_cancelled() {
+                t("status.targets_cancelling").to_string()
+            } else {
+                let progress = tf(
+                    "status.targets_progress",
+                    &[("done", &done.load(Ordering::SeqCst)), ("total", &targets.len())],
+                );
+                format!("{}{}", picking, progress)
+            };
+            self.state.set_loading(&message);
+            if let Err(e) = self.draw() {
+                tracing::warn!("Failed to draw the pick progress: {}", e);
+            }
+            tokio::select! {
+                outcomes = &mut picks => break outcomes,
+                () = tokio::time::sleep(Duration::from_millis(100)) => self.poll_target_keys(&cancel),
+            }
+            if self.state.quit.is_forced() {
+                self.abandon_pick(pr.number, &targets.join(", "));
+                return Ok(());
+            }
+        };
+
+        let unverified: Vec<SubmoduleUpdate> = submodule_updates
+            .into_iter()
+            .filter(|update| self.git_ops.submodule_commit_exists(update) != Some(true))
+            .collect();
+        let skip_bookkeeping = self.state.quit.is_forced();
+        let base = PickRecord {
+            pr_number: pr.number,
+            status: PickStatus::Picked,
+            recorded_at: None,
+            pr_title: pr.title.clone(),
+            target_branch: String::new(),
+            commits: Vec::new(),
+            reordered,
+            labels_updated: false,
+            comment_added: false,
+            run: Some(self.run_info().await),
+            sides_taken: Vec::new(),
+            simulated: Vec::new(),
+            unverified_submodules: Vec::new(),
+            api_requests: None,
+            held_back: std::mem::take(&mut self.state.pick_held_back),
+            note: self.state.pr_notes.get(pr.number).map(str::to_string),
+        };
+        let mut picked = 0;
+        let mut notes = String::new();
+        for (target, outcome) in targets.iter().zip(outcomes) {
+            let mut record = PickRecord {
+                target_branch: target.clone(),
+                ..base.clone()
+            };
+            match outcome {
+                TargetOutcome::Finished(WorktreePick::Picked(commits)) => {
+                    self.state
+                        .recently_cherry_picked
+                        .push(session_pick(pr.number, target, &commits));
+                    record.commits = commits;
+                    record.unverified_submodules = unverified.clone();
+                    if !skip_bookkeeping {
+                        self.finish_bookkeeping(&mut record).await;
+                    }
+                    notes.push_str(&tf("status.target_picked", &[("branch", target)]));
+                    notes.push_str(&self.publish_pick(&mut record, !skip_bookkeeping).await);
+                    self.note_target_use(target);
+                    picked += 1;
+                }
+                TargetOutcome::Finished(WorktreePick::Conflicted { sha, files }) => {
+                    record.status = PickStatus::Conflicted;
+                    if !skip_bookkeeping && self.config.github.comment_on_conflict && self.state.capabilities.can_comment {
+                        let reason = DeferralReason::Conflicts { files: files.clone() };
+                        record.comment_added = self.comment_deferral(pr.number, target, &reason).await;
+                        record.simulated = self.take_simulated();
+                    }
+                    notes.push_str(&tf(
+                        "status.target_conflicted",
+                        &[("branch", target), ("sha", &short_sha(&sha)), ("files", &files.join(", "))],
+                    ));
+                }
+                TargetOutcome::Finished(WorktreePick::Failed(error)) => {
+                    record.status = PickStatus::Failed;
+                    notes.push_str(&tf("status.target_failed", &[("branch", target), ("error", &error)]));
+                }
+                TargetOutcome::Cancelled => {
+                    notes.push_str(&tf("status.target_cancelled", &[("branch", target)]));
+                    continue;
+                }
+            }
+            record.recorded_at = Some(Utc::now());
+            self.log_pick(&record);
+        }
+
+        self.end_target_run();
+        if self.state.quit.is_pending() {
+            self.state.pick_queue.clear();
+            self.held_keys.clear();
+            self.should_quit = true;
+            return Ok(());
+        }
+        let mut message = tf(
+            "status.targets_done",
+            &[("number", &pr.number), ("picked", &picked), ("total", &targets.len())],
+        );
+        message.push_str(&notes);
+        message.push_str(&self.state.batch_progress());
+        self.state.set_success(&message);
+        self.state.current_screen = Screen::PrList;
+        Ok(())
+    }
+}
+
+/// The session's note of a pick of PR `pr_number` onto `branch`, so it can
+/// be aborted; a squashed pick is one commit
+fn session_pick(pr_number: u64, branch: &str, picked: &[PickedCommit]) -> SessionPick {
+    let mut commits: Vec<String> = picked.iter().map(|c| c.new_sha.clone()).collect();
+    commits.dedup();
+    SessionPick {
+        pr_number,
+        branch: branch.to_string(),
+        commits,
+    }
+}
+
+/// The repository `config` points at, with its engine and pick settings
+fn configured_git_ops(config: &Config) -> Result<GitOperations> {
+    Ok(with_pick_settings(
+        GitOperations::open_configured(config.github.repo_path.as_deref())?,
+        &config.github,
+    ))
+}
+
+/// `git_ops` with the engine and pick settings of `github`
+fn with_pick_settings(git_ops: GitOperations, github: &GitHubConfig) -> GitOperations {
+    git_ops
+        .with_engine(github.git_engine)
+        .with_remote(&github.remote_name)
+        .with_only_commit_paths(github.pick_only_commit_paths)
+}
+
+/// Where applying a PR's commits stopped on conflicts
+struct StoppedPick {
+    sha: String,
+    conflicts: Vec<String>,
+    binary_conflicts: Vec<String>,
+    /// The commits after `sha`, still to be picked
+    remaining: Vec<String>,
+}
+
+/// How far applying a PR's commits got
+struct AppliedCommits {
+    status: PickStatus,
+    commits: Vec<PickedCommit>,
+    conflicted: Option<StoppedPick>,
+    /// Why the pick failed, for the error screen
+    error: Option<String>,
+    rerere_used: bool,
+    mainline_notes: Vec<String>,
+}
+
+impl AppliedCommits {
+    fn failed(error: String) -> Self {
+        Self {
+            status: PickStatus::Failed,
+            commits: Vec::new(),
+            conflicted: None,
+            error: Some(error),
+            rerere_used: false,
+            mainline_notes: Vec::new(),
+        }
+    }
+}
+
+/// The one commit a squashed pick of PR `pr_number` becomes, on `base_sha`
+struct Squash {
+    base_sha: String,
+    pr_number: u64,
+    title: String,
+}
+
+/// Cherry-picks `shas` onto the checked-out branch in order, then makes them
+/// the one commit of `squash` once every commit applied. `picking` holds the
+/// index of the commit under way; once `stop` is set the commit under way
+/// lands but no more start.
+fn apply_commits(
+    git_ops: &GitOperations,
+    shas: &[String],
+    squash: Option<Squash>,
+    picking: &AtomicUsize,
+    stop: &CancelFlag,
+) -> AppliedCommits {
+    let mut applied = AppliedCommits {
+        status: PickStatus::Picked,
+        commits: Vec::new(),
+        conflicted: None,
+        error: None,
+        rerere_used: false,
+        mainline_notes: Vec::new(),
+    };
+    for (index, sha) in shas.iter().enumerate() {
+        if index > 0 && stop.is_cancelled() {
+            applied.status = PickStatus::Interrupted;
+            break;
+        }
+        picking.store(index, Ordering::SeqCst);
+        // A held index lock was already waited out by the git layer
+        match git_ops.cherry_pick(sha) {
+            Ok(result) if result.success => {
+                applied.rerere_used |= !result.rerere_resolved.is_empty();
+                if let Some(parent) = result.mainline {
+                    applied.mainline_notes.push(tf(
+                        "status.picked_mainline",
+                        &[("sha", &short_sha(sha)), ("parent", &parent)],
+                    ));
+                }
+                if let Some(new_sha) = result.commit_sha {
+                    applied.commits.push(PickedCommit {
+                        source_sha: sha.clone(),
+                        new_sha,
+                    });
+                }
+            }
+            Ok(result) => {
+                applied.status = PickStatus::Conflicted;
+                applied.conflicted = Some(StoppedPick {
+                    sha: sha.clone(),
+                    conflicts: result.conflicts,
+                    binary_conflicts: result.binary_conflicts,
+                    remaining: shas[index + 1..].to_vec(),
+                });
+                return applied;
+            }
+            Err(e) => {
+                applied.status = PickStatus::Failed;
+                applied.error = Some(tf("error.pick_failed", &[("sha", &short_sha(sha)), ("error", &e)]));
+                return applied;
+            }
+        }
+    }
+
+    // One commit in place of the picks, only once every commit applied
+    if let Some(squash) = squash.filter(|_| applied.status == PickStatus::Picked) {
+        match git_ops.squash_picks(&squash.base_sha, &squash.title, shas) {
+            Ok(squashed) => {
+                for picked in &mut applied.commits {
+                    picked.new_sha = squashed.clone();
+                }
+            }
+            Err(e) => {
+                applied.status = PickStatus::Failed;
+                applied.error = Some(tf("error.squash", &[("number", &squash.pr_number), ("error", &e)]));
+            }
+        }
+    }
+    applied
+}
+
+/// What the post-pick hook is told about the pick in `record`
+fn hook_context(record: &PickRecord) -> HookContext {
+    let mut new_shas: Vec<String> = record.commits.iter().map(|c| c.new_sha.clone()).collect();
+    new_shas.dedup();
+    HookContext {
+        pr_number: record.pr_number,
+        target_branch: record.target_branch.clone(),
+        new_shas,
+    }
+}
+
+/// `Some(interrupt)` for the keys that quit: `q`, or Ctrl+C with `interrupt` set
+fn quit_key(key: &KeyEvent) -> Option<bool> {
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(true),
+        KeyCode::Char('q') => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn a_dry_run_never_reaches_the_cherry_pick() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        {
+            let mut cfg = repo.config().unwrap();
+            cfg.set_str("user.name", "Test").unwrap();
+            cfg.set_str("user.email", "test@example.com").unwrap();
+        }
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let commit = |content: &str, parents: &[&git2::Commit], update_ref: Option<&str>| {
+            let mut builder = repo.treebuilder(None).unwrap();
+            builder.insert("a.txt", repo.blob(content.as_bytes()).unwrap(), 0o100644).unwrap();
+            let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+            repo.commit(update_ref, &sig, &sig, content, &tree, parents).unwrap()
+        };
+        let base = repo.find_commit(commit("base\n", &[], Some("HEAD"))).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force())).unwrap();
+        let fix = commit("fix\n", &[&base], None).to_string();
+
+        let mut config = Config::default();
+        config.github.owner = "acme".into();
+        config.github.repo = "widgets".into();
+        config.github.target_branch = "main".into();
+        config.github.repo_path = Some(dir.path().to_path_buf());
+        let client = GitHubClient::new_with_base_url(config.clone(), "http://127.0.0.1:1", "test-token")
+            .await
+            .unwrap();
+        let mut app = App::new(config, client).unwrap();
+        let picks = Arc::new(AtomicUsize::new(0));
+        app.git_ops = GitOperations::new(dir.path()).unwrap().counting_picks(Arc::clone(&picks));
+        app.dry_run();
+
+        let pr = PrInfo {
+            number: 7,
+            title: "Fix".into(),
+            ..Default::default()
+        };
+        app.pick_commits(&pr, vec![fix.clone()], false).await.unwrap();
+        assert_eq!(picks.load(Ordering::SeqCst), 0);
+        assert!(matches!(app.state.current_screen, Screen::PrList));
+        assert_eq!(repo.head().unwrap().target(), Some(base.id()));
+
+        // The same git_ops does count a real pick
+        assert!(app.git_ops.cherry_pick(&fix).unwrap().success);
+        assert_eq!(picks.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn a_stopped_pick_lands_the_commit_under_way_and_starts_no_more() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        {
+            let mut cfg = repo.config().unwrap();
+            cfg.set_str("user.name", "Test").unwrap();
+            cfg.set_str("user.email", "test@example.com").unwrap();
+        }
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let commit = |name: &str, parents: &[&git2::Commit], update_ref: Option<&str>| {
+            let mut builder = repo.treebuilder(None).unwrap();
+            for parent in parents {
+                for entry in parent.tree().unwrap().iter() {
+                    builder.insert(entry.name().unwrap(), entry.id(), entry.filemode()).unwrap();
+                }
+            }
+            builder.insert(name, repo.blob(name.as_bytes()).unwrap(), 0o100644).unwrap();
+            let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+            repo.find_commit(repo.commit(update_ref, &sig, &sig, name, &tree, parents).unwrap()).unwrap()
+        };
+        let base = commit("base.txt", &[], Some("HEAD"));
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force())).unwrap();
+        let first = commit("first.txt", &[&base], None);
+        let second = commit("second.txt", &[&first], None);
+        let shas = vec![first.id().to_string(), second.id().to_string()];
+
+        let git_ops = GitOperations::new(dir.path()).unwrap();
+        let stop = CancelFlag::default();
+        stop.cancel();
+        let picking = AtomicUsize::new(0);
+        let applied = apply_commits(&git_ops, &shas, None, &picking, &stop);
+        assert_eq!(applied.status, PickStatus::Interrupted);
+        assert_eq!(applied.commits.len(), 1);
+        assert_eq!(applied.commits[0].source_sha, shas[0]);
+        assert_eq!(picking.load(Ordering::SeqCst), 0);
+        assert!(!dir.path().join("second.txt").exists());
+    }
 }
@@ -0,0 +1,87 @@
+//! Timed reloads of the PR list. A reload waits until the user has stopped
+//! typing for a moment, so rows don't move under the cursor mid-keystroke.
+
+use std::time::{Duration, Instant};
+
+/// How long after the last keypress the user counts as idle
+pub const IDLE_AFTER: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+pub struct AutoRefresh {
+    /// `None` when auto-refresh is off
+    interval: Option<Duration>,
+    last_refresh: Instant,
+    last_input: Instant,
+}
+
+impl AutoRefresh {
+    /// Refreshes every `secs` seconds; 0 turns it off
+    pub fn new(secs: u64, now: Instant) -> Self {
+        Self {
+            interval: Some(Duration::from_secs(secs)).filter(|d| !d.is_zero()),
+            last_refresh: now,
+            last_input: now,
+        }
+    }
+
+    pub fn note_input(&mut self, now: Instant) {
+        self.last_input = now;
+    }
+
+    /// Restarts the interval; called after any load, manual or automatic
+    pub fn refreshed(&mut self, now: Instant) {
+        self.last_refresh = now;
+    }
+
+    /// Whether to reload now. `busy` means something the reload would
+    /// interrupt is open: a prompt, a confirmation or a pick.
+    pub fn due(&self, now: Instant, busy: bool) -> bool {
+        !busy
+            && self.remaining(now) == Some(Duration::ZERO)
+            && now.saturating_duration_since(self.last_input) >= IDLE_AFTER
+    }
+
+    /// Time left in the interval, for the footer; `None` when off
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.last_refresh);
+        self.interval.map(|interval| interval.saturating_sub(elapsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(n: u64) -> Duration {
+        Duration::from_secs(n)
+    }
+
+    #[test]
+    fn zero_seconds_turns_it_off() {
+        let start = Instant::now();
+        let refresh = AutoRefresh::new(0, start);
+        assert_eq!(refresh.remaining(start + secs(3600)), None);
+        assert!(!refresh.due(start + secs(3600), false));
+    }
+
+    #[test]
+    fn refreshes_once_the_interval_is_up_and_the_user_is_idle() {
+        let start = Instant::now();
+        let mut refresh = AutoRefresh::new(60, start);
+        assert_eq!(refresh.remaining(start + secs(20)), Some(secs(40)));
+        assert!(!refresh.due(start + secs(59), false));
+        assert!(refresh.due(start + secs(60), false));
+
+        // A keypress just now holds it off until the user is idle again
+        refresh.note_input(start + secs(58));
+        assert!(!refresh.due(start + secs(60), false));
+        assert!(refresh.due(start + secs(58) + IDLE_AFTER, false));
+
+        // A prompt or pick in progress holds it off however long it's been
+        assert!(!refresh.due(start + secs(600), true));
+
+        refresh.refreshed(start + secs(600));
+        assert_eq!(refresh.remaining(start + secs(600)), Some(secs(60)));
+        assert!(!refresh.due(start + secs(630), false));
+    }
+}
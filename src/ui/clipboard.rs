@@ -0,0 +1,125 @@
+//! Copying to the system clipboard through whichever copy command the platform
+//! has. Over SSH there usually is none, so callers show the value instead.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+use crate::messages::tf;
+
+pub trait Clipboard: Send {
+    fn copy(&mut self, text: &str) -> Result<()>;
+}
+
+/// Pipes the text into the first copy command the environment supports
+#[derive(Debug, Default)]
+pub struct SystemClipboard;
+
+impl SystemClipboard {
+    /// Candidate commands in the order they're tried
+    fn commands() -> Vec<(&'static str, &'static [&'static str])> {
+        let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if cfg!(target_os = "macos") {
+            commands.push(("pbcopy", &[]));
+        }
+        if cfg!(windows) {
+            commands.push(("clip", &[]));
+        }
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            commands.push(("wl-copy", &[]));
+        }
+        if std::env::var_os("DISPLAY").is_some() {
+            commands.push(("xclip", &["-selection", "clipboard"]));
+            commands.push(("xsel", &["--clipboard", "--input"]));
+        }
+        // WSL reaches the Windows clipboard without a display server
+        if std::env::var_os("WSL_DISTRO_NAME").is_some() {
+            commands.push(("clip.exe", &[]));
+        }
+        commands
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn copy(&mut self, text: &str) -> Result<()> {
+        let mut last_error = None;
+        for (program, args) in Self::commands() {
+            match pipe_to(program, args, text) {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    tracing::debug!("Clipboard command {} failed: {:#}", program, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No clipboard command for this environment")))
+    }
+}
+
+fn pipe_to(program: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", program))?;
+    child
+        .stdin
+        .take()
+        .context("Copy command has no stdin")?
+        .write_all(text.as_bytes())?;
+    let status = child.wait()?;
+    if !status.success() {
+        anyhow::bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+/// Copies `text`, returning the toast to show: a confirmation, or the value
+/// itself when there's no clipboard to put it on
+pub fn copy_or_show(clipboard: &mut dyn Clipboard, text: &str) -> String {
+    match clipboard.copy(text) {
+        Ok(()) => tf("clipboard.copied", &[("value", &text)]),
+        Err(e) => {
+            tracing::info!("Clipboard unavailable: {:#}", e);
+            tf("clipboard.unavailable", &[("value", &text)])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeClipboard {
+        available: bool,
+        copied: Vec<String>,
+    }
+
+    impl Clipboard for FakeClipboard {
+        fn copy(&mut self, text: &str) -> Result<()> {
+            if !self.available {
+                anyhow::bail!("no clipboard");
+            }
+            self.copied.push(text.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn the_value_is_shown_when_there_is_no_clipboard() {
+        let mut clipboard = FakeClipboard { available: true, ..Default::default() };
+        let toast = copy_or_show(&mut clipboard, "abc1234");
+        assert_eq!(clipboard.copied, ["abc1234"]);
+        assert!(toast.contains("abc1234"));
+
+        let mut missing = FakeClipboard::default();
+        let fallback = copy_or_show(&mut missing, "abc1234");
+        assert!(missing.copied.is_empty());
+        assert!(fallback.contains("abc1234"));
+        assert_ne!(toast, fallback);
+    }
+}
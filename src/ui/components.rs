@@ -6,8 +6,19 @@ use ratatui::{
     Frame,
 };
 
-use crate::ui::state::AppState;
+use unicode_width::UnicodeWidthStr;
+
+use crate::ui::listing_changes::ChangeBadge;
+use crate::ui::pr_row::{pr_row_spans, state_badge, state_label, RowLayout};
+use crate::ui::state::{AppState, ConflictFile, GitOperation, PendingConfirmation};
+use crate::git::ConflictSide;
+use crate::session_log::{PickRecord, PickStatus};
+use crate::stats::{author_stats, stats_lines};
+use crate::target_history::{TargetRow, TargetSection};
+use crate::workflow::PickSource;
 use crate::config::Config;
+use crate::messages::{t, tf};
+use crate::util::{strip_markdown, truncate_display};
 
 pub struct MainMenu;
 
@@ -24,7 +35,7 @@ impl MainMenu {
             .split(f.area());
 
         // Title
-        let title = Paragraph::new("🍒 GitHub Cherry-Pick")
+        let title = Paragraph::new(t("main_menu.title"))
             .style(
                 Style::default()
                     .fg(Color::Green)
@@ -34,14 +45,14 @@ impl MainMenu {
         f.render_widget(title, chunks[0]);
 
         // Minimal prompt-like menu (no boxes)
-        let menu_text = ">> Press Enter to view PRs  •  r: Refresh  •  q: Quit";
+        let menu_text = t("main_menu.prompt");
         let menu_para = Paragraph::new(menu_text)
             .style(Style::default().fg(Color::White))
             .alignment(Alignment::Center);
         f.render_widget(menu_para, chunks[1]);
 
         // Instructions
-        let instructions = Paragraph::new("Use numbers to select options, 'q' to quit")
+        let instructions = Paragraph::new(t("main_menu.hint"))
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center);
         f.render_widget(instructions, chunks[2]);
@@ -57,7 +68,7 @@ impl PrList {
             .margin(1)
             .constraints([
         Constraint::Length(1),  // header
-        Constraint::Length(1),  // prompt bar
+        Constraint::Length(if state.input_active { 2 } else { 1 }),  // prompt bar
         Constraint::Min(8),     // list
         Constraint::Length(1),  // status/instructions
             ])
@@ -66,16 +77,58 @@ impl PrList {
         // Title
         let total = state.prs.len();
         let shown = state.display_indices.len();
-        let title = Paragraph::new(format!(
-                "📋 Pull Requests  —  showing {} of {}",
-                shown, total
-            ))
-            .style(
-                Style::default()
-                    .fg(Color::Blue)
-                    .add_modifier(Modifier::BOLD),
+        let mut title_text = tf("pr_list.title", &[("shown", &shown), ("total", &total)]);
+        // Active quick filters follow the title as chips
+        let chips: Vec<Span> = state
+            .quick_filters
+            .chips(&state.prs)
+            .into_iter()
+            .flat_map(|chip| {
+                [
+                    Span::raw(" "),
+                    Span::styled(format!(" {} ", chip), Style::default().fg(Color::Black).bg(Color::Cyan)),
+                ]
+            })
+            .collect();
+        // Nothing a dry run does touches git or GitHub, which should be hard to miss
+        let dry_run: Vec<Span> = if state.dry_run {
+            vec![
+                Span::styled(
+                    t("pr_list.dry_run_badge"),
+                    Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" "),
+            ]
+        } else {
+            Vec::new()
+        };
+        let repo_path_width = state.repo_path.as_deref().map_or(0, |path| 2 + UnicodeWidthStr::width(path));
+        let chips_width: usize = chips.iter().map(Span::width).sum::<usize>()
+            + repo_path_width
+            + dry_run.iter().map(Span::width).sum::<usize>();
+        // A wrapped header would push the list down, so fall back to the short form
+        if UnicodeWidthStr::width(title_text.as_str()) + chips_width > chunks[0].width as usize {
+            title_text = tf("pr_list.title_short", &[("shown", &shown), ("total", &total)]);
+        }
+        // The repository path gets whatever room is left, cut if need be
+        let room = (chunks[0].width as usize)
+            .saturating_sub(UnicodeWidthStr::width(title_text.as_str()) + chips_width - repo_path_width);
+        let repo_path = state.repo_path.as_deref().map(|path| {
+            Span::styled(
+                truncate_display(&format!("  {}", path), room).into_owned(),
+                Style::default().fg(Color::DarkGray),
             )
-            .alignment(Alignment::Center);
+        });
+        let mut header = dry_run;
+        header.push(Span::styled(
+            title_text,
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        ));
+        header.extend(chips);
+        header.extend(repo_path);
+        let title = Paragraph::new(Line::from(header)).alignment(Alignment::Center);
         f.render_widget(title, chunks[0]);
 
         // Inline prompt bar (minimal, no boxes)
@@ -94,14 +147,15 @@ impl PrList {
                     Span::raw(state.input_buffer.clone()),
                 ])
             };
-            Paragraph::new(vec![
-                Line::from(Span::styled(state.input_title.clone(), Style::default().fg(Color::Cyan))),
-                input,
-            ])
+            let title = match &state.prompt_error {
+                Some(error) => Span::styled(error.clone(), Style::default().fg(Color::Red)),
+                None => Span::styled(state.input_title.clone(), Style::default().fg(Color::Cyan)),
+            };
+            Paragraph::new(vec![Line::from(title), input])
         } else {
             let hint = match &state.filter_query {
-                Some(q) => format!("f: Filter (active: '{}')  •  Enter: Cherry-pick  •  Esc: Back", q),
-                None => "f: Filter  •  Enter: Cherry-pick  •  Esc: Back".to_string(),
+                Some(q) => tf("pr_list.prompt_hint_filtered", &[("query", q)]),
+                None => t("pr_list.prompt_hint").to_string(),
             };
             Paragraph::new(Line::from(vec![
                 Span::styled(">> ", Style::default().fg(Color::Yellow)),
@@ -112,28 +166,17 @@ impl PrList {
 
         // PR List
     if shown == 0 {
-            let criteria_info = format!(
-                "No PRs found matching the criteria.\n\n\
-                📋 Search Criteria:\n\
-                • Repository: {}/{}\n\
-                • Base Branch: {}\n\
-                • Environment: {}\n\
-                • Pending Tag: \"{}\"\n\
-                • Days Back: {}\n\n\
-                💡 Tips:\n\
-                • Ensure PRs are tagged with \"{}\"\n\
-                • Check if PRs are merged to \"{}\" branch\n\
-                • Verify the tag pattern matches: {}\n\n\
-                🔄 Press 'r' to refresh or 'Esc' to go back.",
-                config.github.owner,
-                config.github.repo,
-                config.github.base_branch,
-                config.tags.environment,
-                config.tags.pending_tag,
-                config.ui.days_back,
-                config.tags.pending_tag,
-                config.github.base_branch,
-                config.tags.sprint_pattern
+            let criteria_info = tf(
+                "pr_list.empty",
+                &[
+                    ("owner", &config.github.owner),
+                    ("repo", &config.github.repo),
+                    ("base", &config.github.base_branch),
+                    ("environment", &config.tags.environment),
+                    ("pending", &config.tags.pending_tag),
+                    ("days", &config.ui.days_back),
+                    ("pattern", &config.tags.sprint_pattern),
+                ],
             );
             
             let empty_message = Paragraph::new(criteria_info)
@@ -142,26 +185,75 @@ impl PrList {
                 .wrap(Wrap { trim: true });
             f.render_widget(empty_message, chunks[2]);
         } else {
+            let layout = RowLayout::from_config(&config.ui);
+            let width = chunks[2].width as usize;
             let items: Vec<ListItem> = state
                 .display_indices
                 .iter()
-                .map(|&idx| {
+                .enumerate()
+                .map(|(row, &idx)| {
                     let pr = &state.prs[idx];
-                    let style = if pr.labels.contains(&"cherry picked".to_string()) {
+                    let style = if !state.pr_list_state.is_selectable(row) {
+                        Style::default().fg(Color::DarkGray)
+                    } else if config.tags.has_label(&pr.labels, &config.tags.completed_tag) {
                         Style::default().fg(Color::Green)
                     } else {
                         Style::default().fg(Color::White)
                     };
 
-                    let content = format!(
-                        "#{} - {} (by {} - {} commits)",
-                        pr.number,
-                        pr.title,
-                        pr.author,
-                        pr.commits.len()
-                    );
+                    let mut spans: Vec<Span> = Vec::new();
+                    if state.selected_prs.contains(&idx) {
+                        spans.push(Span::styled(
+                            "[✓] ",
+                            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+                        ));
+                    }
+                    spans.extend(state_badge(pr.state));
+                    match state.change_badges.get(&pr.number) {
+                        Some(ChangeBadge::New) => spans.push(Span::styled(
+                            format!("{} ", t("pr_list.new_badge")),
+                            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                        )),
+                        Some(ChangeBadge::Updated) => spans.push(Span::styled(
+                            format!("{} ", t("pr_list.updated_badge")),
+                            Style::default().fg(Color::Yellow),
+                        )),
+                        None => {}
+                    }
+                    if state.skipped_prs.contains(&pr.number) {
+                        spans.push(Span::styled("⏭ ", Style::default().fg(Color::DarkGray)));
+                    } else if state.pick_fallbacks.contains_key(&pr.number) {
+                        spans.push(Span::styled("⚠ ", Style::default().fg(Color::Yellow)));
+                    } else if state.needs_fetch(pr) {
+                        spans.push(Span::styled(
+                            t("pr_list.fetch_badge"),
+                            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                        ));
+                        spans.push(Span::raw(" "));
+                    } else if state.fetched_on_demand.contains(&pr.number) {
+                        spans.push(Span::styled(
+                            t("pr_list.on_demand_badge"),
+                            Style::default().fg(Color::Cyan),
+                        ));
+                        spans.push(Span::raw(" "));
+                    }
+                    if let Some(parent) = state.follow_up_parent(pr) {
+                        spans.push(Span::styled(
+                            tf("pr_list.follow_up", &[("number", &parent)]),
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                    let badge_width: usize = spans.iter().map(Span::width).sum();
+                    spans.extend(pr_row_spans(pr, width.saturating_sub(badge_width), &layout));
 
-                    ListItem::new(content).style(style)
+                    let mut lines = vec![Line::from(spans)];
+                    if let Some(note) = state.pr_notes.get(pr.number) {
+                        lines.push(Line::from(Span::styled(
+                            tf("pr_list.note", &[("note", &note)]),
+                            Style::default().fg(Color::DarkGray),
+                        )));
+                    }
+                    ListItem::new(lines).style(style)
                 })
                 .collect();
 
@@ -180,17 +272,861 @@ impl PrList {
 
     // Instructions
     let mut status = String::new();
-        if let Some(message) = &state.success_message {
-            status.push_str(message);
+        if state.terminal_caps.is_degraded() {
+            status.push_str(&degraded_terminal_note(state));
+            status.push_str("   •   ");
+        }
+        if state
+            .selected_pr_index()
+            .is_some_and(|idx| state.needs_fetch(&state.prs[idx]))
+        {
+            status.push_str(t("pr_list.fetch_hint"));
+            status.push_str("   •   ");
+        }
+        if !state.capabilities.can_push {
+            status.push_str(t("pr_list.read_only_hint"));
             status.push_str("   •   ");
         }
-        status.push_str("↑/↓ Navigate  •  Enter Cherry-pick  •  r Refresh  •  f Filter  •  Esc Back  •  q Quit");
-        let instructions = Paragraph::new(status)
+        status.push_str(t("pr_list.footer"));
+        let status = with_status(state, &status, chunks[3].width);
+        let mut footer = vec![Span::styled(status, Style::default().fg(Color::Gray))];
+        if let Some(remaining) = state.auto_refresh.remaining(std::time::Instant::now()) {
+            footer.push(Span::styled(
+                format!(
+                    "   {}",
+                    tf("pr_list.auto_refresh", &[("secs", &remaining.as_secs())])
+                ),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        let instructions = match &state.confirmation {
+            Some(confirmation) => Paragraph::new(confirmation_text(confirmation))
+                .style(Style::default().fg(Color::Yellow)),
+            None => Paragraph::new(Line::from(footer)),
+        };
+        f.render_widget(instructions.alignment(Alignment::Center), chunks[3]);
+
+    // Popups removed for a cleaner, less "boxy" look
+    }
+}
+
+pub struct PrDetail;
+
+impl PrDetail {
+    pub fn render(f: &mut Frame, state: &AppState, config: &Config) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // header
+                Constraint::Min(8),    // body
+                Constraint::Length(1), // instructions
+            ])
+            .split(f.area());
+
+        let Some(pr) = state.detail_index.and_then(|i| state.prs.get(i)) else {
+            f.render_widget(Paragraph::new(t("pr_detail.none_selected")), chunks[1]);
+            return;
+        };
+
+        let title = Paragraph::new(
+            truncate_display(&format!("#{} - {}", pr.number, pr.title), chunks[0].width as usize).into_owned(),
+        )
+            .style(
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+        f.render_widget(title, chunks[0]);
+
+        let list_or_none = |items: &[String]| {
+            if items.is_empty() {
+                t("pr_detail.none").to_string()
+            } else {
+                items.join(", ")
+            }
+        };
+        let field = |name: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{:<12}", name), Style::default().fg(Color::Gray)),
+                Span::raw(value),
+            ])
+        };
+
+        let order = state.commit_order_for(pr.number, pr.commits.len());
+        let reordered = state.is_reordered(pr.number);
+        let count = pr.commits.len();
+        let commits_heading = if reordered {
+            tf("pr_detail.commits_reordered", &[("count", &count)])
+        } else {
+            tf("pr_detail.commits", &[("count", &count)])
+        };
+
+        let mut lines = vec![
+            field(
+                t("pr_detail.state"),
+                state_label(pr.state).unwrap_or(t("pr_detail.none")).to_string(),
+            ),
+            field(t("pr_detail.author"), pr.author.clone()),
+            field(t("pr_detail.branches"), format!("{} → {}", pr.head_ref, pr.base_ref)),
+            field(t("pr_detail.updated"), pr.updated_at.format("%Y-%m-%d %H:%M").to_string()),
+            field(t("pr_detail.labels"), list_or_none(&pr.labels)),
+            field(t("pr_detail.assignees"), list_or_none(&pr.assignees)),
+            field(t("pr_detail.reviewers"), list_or_none(&pr.requested_reviewers)),
+        ];
+        if let Some(parent) = state.follow_up_parent(pr) {
+            lines.push(field(t("pr_detail.follows_up"), format!("#{}", parent)));
+        }
+        if let Some(note) = state.pr_notes.get(pr.number) {
+            lines.push(field(t("pr_detail.note"), note.to_string()));
+        }
+        lines.extend([
+            field(
+                t("pr_detail.squash"),
+                if state.squashes(pr.number, config.github.squash_picks) {
+                    t("pr_detail.squash_on")
+                } else {
+                    t("pr_detail.squash_off")
+                }
+                .to_string(),
+            ),
+            Line::from(""),
+            Line::from(Span::styled(commits_heading, Style::default().fg(Color::Cyan))),
+        ]);
+        if let Some(fallback) = state.pick_fallbacks.get(&pr.number) {
+            let note = match fallback {
+                PickSource::MergeCommit { .. } => t("pr_detail.head_gone_merged"),
+                _ => t("pr_detail.head_gone"),
+            };
+            lines.push(Line::from(Span::styled(
+                format!("⚠ {}", note),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+        let cursor = state.detail_commit_state.selected();
+        for (position, commit) in order.iter().map(|&i| &pr.commits[i]).enumerate() {
+            let marker = if cursor == Some(position) { "> " } else { "  " };
+            let line = Line::from(vec![
+                Span::styled(
+                    format!("{}{} ", marker, crate::util::short_sha(&commit.sha)),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(truncate_display(commit.message.lines().next().unwrap_or(""), 72).into_owned()),
+            ]);
+            lines.push(if cursor == Some(position) {
+                line.style(Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                line
+            });
+        }
+
+        let body = Paragraph::new(lines).wrap(Wrap { trim: false });
+        f.render_widget(body, chunks[1]);
+
+        let instructions = match &state.confirmation {
+            Some(confirmation) => Paragraph::new(confirmation_text(confirmation))
+                .style(Style::default().fg(Color::Yellow)),
+            // The note prompt opened from here
+            None if state.input_active => Paragraph::new(Line::from(vec![
+                Span::styled(format!("{} ", state.input_title), Style::default().fg(Color::Cyan)),
+                Span::styled(">> ", Style::default().fg(Color::Yellow)),
+                Span::raw(state.input_buffer.clone()),
+            ])),
+            None if !state.capabilities.can_push => Paragraph::new(with_status(
+                state,
+                &format!("{}   •   {}", t("pr_list.read_only_hint"), t("pr_detail.footer")),
+                chunks[2].width,
+            ))
+            .style(Style::default().fg(Color::Gray)),
+            None => Paragraph::new(with_status(state, t("pr_detail.footer"), chunks[2].width))
+                .style(Style::default().fg(Color::Gray)),
+        };
+        f.render_widget(instructions.alignment(Alignment::Center), chunks[2]);
+    }
+}
+
+/// `footer`, after the latest status message if there is one. A long message
+/// is cut to leave `footer` room on a `width`-column line, though it always
+/// keeps at least half the line.
+fn with_status(state: &AppState, footer: &str, width: u16) -> String {
+    match &state.success_message {
+        Some(message) => {
+            let width = width as usize;
+            let budget = width
+                .saturating_sub(UnicodeWidthStr::width(footer) + STATUS_SEPARATOR.len())
+                .max(width / 2);
+            format!("{}{}{}", truncate_display(message, budget), STATUS_SEPARATOR, footer)
+        }
+        None => footer.to_string(),
+    }
+}
+
+/// Between a status message and the footer it precedes
+const STATUS_SEPARATOR: &str = "   •   ";
+
+/// Icon, description and colour for an in-flight git operation
+fn git_operation_label(op: &GitOperation) -> Option<(String, Color)> {
+    let (icon, text, color) = match op {
+        GitOperation::Idle => return None,
+        GitOperation::Fetch => ("🔄", t("progress.op_fetch").to_string(), Color::Cyan),
+        GitOperation::Checkout(branch) => (
+            "🔀",
+            tf("progress.op_checkout", &[("branch", branch)]),
+            Color::Blue,
+        ),
+        GitOperation::CherryPick(sha) => (
+            "🍒",
+            tf("progress.op_cherry_pick", &[("sha", sha)]),
+            Color::Magenta,
+        ),
+        GitOperation::Push(branch) => ("↑", tf("progress.op_push", &[("branch", branch)]), Color::Green),
+    };
+    Some((format!("{} {}", icon, text), color))
+}
+
+fn degraded_terminal_note(state: &AppState) -> String {
+    let mut notes = Vec::new();
+    if !state.terminal_caps.mouse {
+        notes.push(t("terminal.mouse_off"));
+    }
+    if !state.terminal_caps.alternate_screen {
+        notes.push(t("terminal.inline_mode"));
+    }
+    format!("[{}]", notes.join(", "))
+}
+
+fn confirmation_text(confirmation: &PendingConfirmation) -> String {
+    match confirmation {
+        PendingConfirmation::ReorderedPick { .. } => t("confirm.reordered_pick").to_string(),
+        PendingConfirmation::AbortSession { prs, commits } => {
+            tf("confirm.abort_session", &[("prs", prs), ("commits", commits)])
+        }
+        PendingConfirmation::UnpushedCommits { branch, commits, .. } => {
+            let mut listed: Vec<String> = commits
+                .iter()
+                .take(3)
+                .map(|c| format!("{} {}", crate::util::short_sha(&c.sha), truncate_display(&c.summary, 40)))
+                .collect();
+            if commits.len() > listed.len() {
+                listed.push("…".to_string());
+            }
+            tf(
+                "confirm.unpushed_commits",
+                &[("count", &commits.len()), ("branch", branch), ("commits", &listed.join("; "))],
+            )
+        }
+        PendingConfirmation::ExistingBranch { branch, presence, source, .. } => {
+            let place = match (presence.local, presence.remote) {
+                (true, true) => t("confirm.branch_both"),
+                (false, true) => t("confirm.branch_remote"),
+                _ => t("confirm.branch_local"),
+            };
+            tf(
+                "confirm.existing_branch",
+                &[("branch", branch), ("where", &place), ("source", source)],
+            )
+        }
+        PendingConfirmation::DetachedHead { head, branch, .. } => tf(
+            "confirm.detached_head",
+            &[("sha", &crate::util::short_sha(head)), ("branch", branch)],
+        ),
+        PendingConfirmation::SubmoduleUpdates { updates, .. } => {
+            let listed: Vec<String> = updates
+                .iter()
+                .map(|update| {
+                    tf(
+                        "confirm.submodule_update",
+                        &[("path", &update.path), ("sha", &crate::util::short_sha(&update.sha))],
+                    )
+                })
+                .collect();
+            tf("confirm.submodule_updates", &[("updates", &listed.join(", "))])
+        }
+        PendingConfirmation::FetchPr { number } => tf("confirm.fetch_pr", &[("number", number)]),
+        PendingConfirmation::RerunBookkeeping { prs } => {
+            tf("confirm.rerun_bookkeeping", &[("prs", prs)])
+        }
+        PendingConfirmation::MergeCommitFallback { sha, .. } => {
+            tf("confirm.merge_commit_fallback", &[("sha", &crate::util::short_sha(sha))])
+        }
+        PendingConfirmation::IncludeFollowUps { number, follow_ups, .. } => {
+            let listed: Vec<String> = follow_ups.iter().map(|n| format!("#{}", n)).collect();
+            tf(
+                "confirm.include_follow_ups",
+                &[("number", number), ("follow_ups", &listed.join(", "))],
+            )
+        }
+        PendingConfirmation::PickOptions { number, held_back, .. } => {
+            let tick = |held: bool| if held { "[ ]" } else { "[x]" };
+            tf(
+                "confirm.pick_options",
+                &[
+                    ("number", number),
+                    ("labels", &tick(held_back.labels)),
+                    ("comment", &tick(held_back.comment)),
+                ],
+            )
+        }
+    }
+}
+
+pub struct TagList;
+
+impl TagList {
+    pub fn render(f: &mut Frame, state: &AppState) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // header
+                Constraint::Min(3),    // tags
+                Constraint::Length(1), // instructions
+            ])
+            .split(f.area());
+
+        let title = Paragraph::new(tf("tag_list.title", &[("count", &state.tags.len())]))
+            .style(
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        if state.tags.is_empty() {
+            let empty = Paragraph::new(t("tag_list.empty"))
+                .style(Style::default().fg(Color::Gray))
+                .alignment(Alignment::Center);
+            f.render_widget(empty, chunks[1]);
+        } else {
+            let items: Vec<ListItem> = state
+                .tags
+                .iter()
+                .map(|tag| ListItem::new(tag.as_str()))
+                .collect();
+            f.render_widget(List::new(items), chunks[1]);
+        }
+
+        let instructions = Paragraph::new(t("tag_list.footer"))
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center);
-        f.render_widget(instructions, chunks[3]);
+        f.render_widget(instructions, chunks[2]);
+    }
+}
 
-    // Popups removed for a cleaner, less "boxy" look
+pub struct StatsView;
+
+impl StatsView {
+    pub fn render(f: &mut Frame, state: &AppState) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // header
+                Constraint::Min(3),    // authors
+                Constraint::Length(1), // instructions
+            ])
+            .split(f.area());
+
+        let stats = author_stats(&state.prs);
+        let title = Paragraph::new(tf(
+            "stats.title",
+            &[("prs", &state.prs.len()), ("authors", &stats.len())],
+        ))
+        .style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        if stats.is_empty() {
+            let empty = Paragraph::new(t("stats.empty"))
+                .style(Style::default().fg(Color::Gray))
+                .alignment(Alignment::Center);
+            f.render_widget(empty, chunks[1]);
+        } else {
+            let items: Vec<ListItem> = stats_lines(&stats, stats_header())
+                .into_iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    let style = if i == 0 {
+                        Style::default().add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    };
+                    ListItem::new(line).style(style)
+                })
+                .collect();
+            f.render_widget(List::new(items), chunks[1]);
+        }
+
+        let instructions = Paragraph::new(t("stats.footer"))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(instructions, chunks[2]);
+    }
+}
+
+/// Column headings of the author table, shared with `--stats`
+pub fn stats_header() -> [&'static str; 4] {
+    [t("stats.author"), t("stats.prs"), t("stats.oldest"), t("stats.commits")]
+}
+
+pub struct History;
+
+impl History {
+    pub fn render(f: &mut Frame, state: &AppState) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // header
+                Constraint::Min(3),    // sessions
+                Constraint::Length(1), // instructions
+            ])
+            .split(f.area());
+
+        let title = Paragraph::new(tf("history.title", &[("count", &state.sessions.len())]))
+            .style(
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        if state.sessions.is_empty() {
+            let empty = Paragraph::new(t("history.empty"))
+                .style(Style::default().fg(Color::Gray))
+                .alignment(Alignment::Center);
+            f.render_widget(empty, chunks[1]);
+        } else {
+            let items: Vec<ListItem> = state
+                .sessions
+                .iter()
+                .map(|session| {
+                    let started = match session.started_at {
+                        Some(at) => at.format("%Y-%m-%d %H:%M").to_string(),
+                        None => session
+                            .path
+                            .file_stem()
+                            .map(|s| s.to_string_lossy().into_owned())
+                            .unwrap_or_default(),
+                    };
+                    let mut spans = vec![Span::raw(tf(
+                        "history.item",
+                        &[
+                            ("started", &started),
+                            ("branches", &session.target_branches.join(", ")),
+                            ("picked", &session.picked),
+                            ("failed", &session.failed),
+                        ],
+                    ))];
+                    if session.partial > 0 {
+                        spans.push(Span::styled(
+                            format!("  ⚠ {}", tf("history.partial", &[("count", &session.partial)])),
+                            Style::default().fg(Color::Yellow),
+                        ));
+                    }
+                    ListItem::new(Line::from(spans))
+                })
+                .collect();
+
+            let list = List::new(items).highlight_style(
+                Style::default()
+                    .bg(Color::Yellow)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let mut list_state = ratatui::widgets::ListState::default();
+            list_state.select(state.session_list_state.selected());
+            f.render_stateful_widget(list, chunks[1], &mut list_state);
+        }
+
+        let instructions = Paragraph::new(with_status(state, t("history.footer"), chunks[2].width))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(instructions, chunks[2]);
+    }
+}
+
+pub struct TargetSelector;
+
+impl TargetSelector {
+    pub fn render(f: &mut Frame, state: &AppState, config: &Config) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // header
+                Constraint::Min(3),    // branches
+                Constraint::Length(1), // instructions
+            ])
+            .split(f.area());
+
+        let title = Paragraph::new(tf("target.title", &[("branch", &config.github.target_branch)]))
+            .style(
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        if state.target_rows.is_empty() {
+            let empty = Paragraph::new(t("target.empty"))
+                .style(Style::default().fg(Color::Gray))
+                .alignment(Alignment::Center);
+            f.render_widget(empty, chunks[1]);
+        } else {
+            let items: Vec<ListItem> = state
+                .target_rows
+                .iter()
+                .map(|row| match row {
+                    TargetRow::Header(section) => {
+                        let key = match section {
+                            TargetSection::Recent => "target.recent",
+                            TargetSection::All => "target.all",
+                        };
+                        ListItem::new(Span::styled(
+                            format!("── {} ──", t(key)),
+                            Style::default().fg(Color::DarkGray).add_modifier(Modifier::BOLD),
+                        ))
+                    }
+                    TargetRow::Branch(branch) => {
+                        let pin = if state.target_history.is_pinned(branch) { "★ " } else { "  " };
+                        let mut spans = vec![
+                            Span::styled(pin, Style::default().fg(Color::Yellow)),
+                            Span::raw(branch.clone()),
+                        ];
+                        if *branch == config.github.target_branch {
+                            spans.push(Span::styled(
+                                format!("  {}", t("target.current")),
+                                Style::default().fg(Color::Gray),
+                            ));
+                        }
+                        ListItem::new(Line::from(spans))
+                    }
+                })
+                .collect();
+
+            let list = List::new(items).highlight_style(
+                Style::default()
+                    .bg(Color::Yellow)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let mut list_state = ratatui::widgets::ListState::default();
+            list_state.select(state.target_list_state.selected());
+            f.render_stateful_widget(list, chunks[1], &mut list_state);
+        }
+
+        let instructions = Paragraph::new(with_status(state, t("target.footer"), chunks[2].width))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(instructions, chunks[2]);
+    }
+}
+
+pub struct SessionDetail;
+
+impl SessionDetail {
+    pub fn render(f: &mut Frame, state: &AppState) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // header
+                Constraint::Min(3),    // records
+                Constraint::Length(1), // instructions
+            ])
+            .split(f.area());
+
+        let (Some(summary), Some(session)) = (state.selected_session(), &state.viewed_session) else {
+            f.render_widget(Paragraph::new(t("session.none_selected")), chunks[1]);
+            return;
+        };
+
+        let name = summary
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let title = Paragraph::new(tf("session.title", &[("session", &name)]))
+            .style(
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+        f.render_widget(title, chunks[0]);
+
+        let partial = session.partial();
+        let cursor = state.session_record_state.selected();
+        let mut lines: Vec<Line> = session
+            .records
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let line = session_record_line(record, partial.contains(&record));
+                if cursor == Some(i) {
+                    line.style(Style::default().add_modifier(Modifier::BOLD))
+                } else {
+                    line
+                }
+            })
+            .collect();
+        if session.skipped_lines > 0 {
+            lines.push(Line::from(Span::styled(
+                tf("session.skipped_lines", &[("count", &session.skipped_lines)]),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[1]);
+
+        let instructions = match &state.confirmation {
+            Some(confirmation) => Paragraph::new(confirmation_text(confirmation))
+                .style(Style::default().fg(Color::Yellow)),
+            None => Paragraph::new(with_status(state, t("session.footer"), chunks[2].width))
+                .style(Style::default().fg(Color::Gray)),
+        };
+        f.render_widget(instructions.alignment(Alignment::Center), chunks[2]);
+    }
+}
+
+/// One row of the session detail screen; `partial` marks outstanding bookkeeping
+fn session_record_line(record: &PickRecord, partial: bool) -> Line<'static> {
+    let (icon, color) = match record.status {
+        PickStatus::Picked => ("✔", Color::Green),
+        PickStatus::Conflicted => ("✖", Color::Red),
+        PickStatus::Failed => ("✖", Color::Red),
+        PickStatus::Skipped => ("⏭", Color::Gray),
+        PickStatus::Interrupted => ("⏸", Color::Yellow),
+        PickStatus::Unknown => ("?", Color::Gray),
+    };
+    let at = record
+        .recorded_at
+        .map(|at| at.format("%H:%M").to_string())
+        .unwrap_or_default();
+    let mut spans = vec![
+        Span::styled(format!("{} ", icon), Style::default().fg(color)),
+        Span::styled(format!("{:>5} ", at), Style::default().fg(Color::Gray)),
+        Span::raw(tf(
+            "session.record",
+            &[
+                ("number", &record.pr_number),
+                ("title", &truncate_display(&strip_markdown(&record.pr_title), 60)),
+                ("branch", &record.target_branch),
+                ("count", &record.commits.len()),
+            ],
+        )),
+    ];
+    if partial {
+        // Steps held back on purpose aren't a warning
+        let (mut missing, mut held) = (Vec::new(), Vec::new());
+        if !record.labels_updated {
+            let list = if record.held_back.labels { &mut held } else { &mut missing };
+            list.push(t("session.missing_labels"));
+        }
+        if !record.comment_added {
+            let list = if record.held_back.comment { &mut held } else { &mut missing };
+            list.push(t("session.missing_comment"));
+        }
+        if !missing.is_empty() {
+            spans.push(Span::styled(
+                format!("  ⚠ {}", tf("session.partial", &[("missing", &missing.join(", "))])),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if !held.is_empty() {
+            spans.push(Span::styled(
+                format!("  {}", tf("session.held_back", &[("steps", &held.join(", "))])),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+    }
+    if !record.simulated.is_empty() {
+        spans.push(Span::styled(
+            format!("  ({})", tf("session.simulated", &[("changes", &record.simulated.join(", "))])),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    if !record.unverified_submodules.is_empty() {
+        let submodules: Vec<String> = record
+            .unverified_submodules
+            .iter()
+            .map(|update| format!("{}@{}", update.path, crate::util::short_sha(&update.sha)))
+            .collect();
+        spans.push(Span::styled(
+            format!("  ⚠ {}", tf("session.submodules_unverified", &[("submodules", &submodules.join(", "))])),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if let Some(note) = &record.note {
+        spans.push(Span::styled(
+            format!("  {}", tf("session.note", &[("note", note)])),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if !record.sides_taken.is_empty() {
+        let sides: Vec<String> = record
+            .sides_taken
+            .iter()
+            .map(|taken| format!("{} ({})", taken.path, side_label(taken.side)))
+            .collect();
+        spans.push(Span::styled(
+            format!("  {}", tf("session.sides_taken", &[("files", &sides.join(", "))])),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn side_label(side: ConflictSide) -> &'static str {
+    match side {
+        ConflictSide::Ours => t("conflicts.ours"),
+        ConflictSide::Theirs => t("conflicts.theirs"),
+    }
+}
+
+pub struct ConflictList;
+
+impl ConflictList {
+    pub fn render(f: &mut Frame, state: &AppState) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // header
+                Constraint::Min(3),    // files
+                Constraint::Length(1), // instructions
+            ])
+            .split(f.area());
+
+        let count = state.conflict_files.len();
+        let title = match &state.conflict_record {
+            Some(record) => tf(
+                "conflicts.title",
+                &[("number", &record.pr_number), ("count", &count)],
+            ),
+            None => tf("conflicts.leftover_title", &[("count", &count)]),
+        };
+        let title = Paragraph::new(title)
+        .style(
+            Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        let items: Vec<ListItem> = state.conflict_files.iter().map(conflict_item).collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(Color::Yellow)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut list_state = ratatui::widgets::ListState::default();
+        list_state.select(state.conflict_list_state.selected());
+        f.render_stateful_widget(list, chunks[1], &mut list_state);
+
+        let footer = if state.conflicts_settled() {
+            t("conflicts.footer_ready")
+        } else {
+            t("conflicts.footer")
+        };
+        let instructions = Paragraph::new(with_status(state, footer, chunks[2].width))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(instructions, chunks[2]);
+    }
+}
+
+/// One conflicted file: how it can be resolved, or which side was kept
+fn conflict_item(file: &ConflictFile) -> ListItem<'static> {
+    let (icon, color, note) = match (file.taken, file.binary) {
+        (Some(side), _) => (
+            "✔",
+            Color::Green,
+            tf("conflicts.taken", &[("side", &side_label(side))]),
+        ),
+        (None, _) if file.staged => ("✔", Color::Green, t("conflicts.staged").to_string()),
+        (None, true) => ("◆", Color::Magenta, t("conflicts.binary").to_string()),
+        (None, false) => ("✖", Color::Red, t("conflicts.text").to_string()),
+    };
+    ListItem::new(Line::from(vec![
+        Span::styled(format!("{} ", icon), Style::default().fg(color)),
+        Span::raw(file.path.clone()),
+        Span::styled(format!("  {}", note), Style::default().fg(Color::Gray)),
+    ]))
+}
+
+pub struct HookOutputView;
+
+impl HookOutputView {
+    pub fn render(f: &mut Frame, state: &AppState) {
+        let Some(panel) = &state.hook else {
+            return;
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1), // header
+                Constraint::Min(3),    // output
+                Constraint::Length(1), // instructions
+            ])
+            .split(f.area());
+
+        let (title, color) = match &panel.failure {
+            Some(failure) => (
+                tf("hook.failed_title", &[("command", &panel.command), ("failure", failure)]),
+                Color::Red,
+            ),
+            None => (tf("hook.running_title", &[("command", &panel.command)]), Color::Yellow),
+        };
+        let title = Paragraph::new(title)
+            .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        let lines: Vec<Line> = panel
+            .visible(chunks[1].height as usize)
+            .iter()
+            .map(|line| Line::raw(line.clone()))
+            .collect();
+        f.render_widget(Paragraph::new(lines), chunks[1]);
+
+        let footer = if panel.held_pick.is_some() {
+            t("hook.footer")
+        } else {
+            t("hook.footer_running")
+        };
+        let instructions = Paragraph::new(with_status(state, footer, chunks[2].width))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(instructions, chunks[2]);
+    }
+}
+
+pub struct ErrorView;
+
+impl ErrorView {
+    pub fn render(f: &mut Frame, state: &AppState) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([Constraint::Percentage(100)].as_ref())
+            .split(f.area());
+
+        let error_message = state.error_message.as_deref().unwrap_or(t("error.unknown"));
+        let paragraph = Paragraph::new(error_message)
+            .style(Style::default().fg(Color::Red))
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(paragraph, chunks[0]);
     }
 }
 
@@ -209,7 +1145,7 @@ impl ProgressView {
             .split(f.area());
 
         // Title
-        let title = Paragraph::new("⏳ Processing...")
+        let title = Paragraph::new(t("progress.title"))
             .style(
                 Style::default()
                     .fg(Color::Yellow)
@@ -218,15 +1154,19 @@ impl ProgressView {
             .alignment(Alignment::Center);
         f.render_widget(title, chunks[0]);
 
-        // Progress bar (indeterminate)
+        // Progress bar (indeterminate), labelled with the git operation if there is one
+        let (label, color) = match git_operation_label(&state.current_git_op) {
+            Some((label, color)) => (label, color),
+            None => (t("progress.working").to_string(), Color::Yellow),
+        };
         let progress = Gauge::default()
-            .gauge_style(Style::default().fg(Color::Yellow))
+            .gauge_style(Style::default().fg(color))
             .percent(50) // Static for now, could be animated
-            .label("Working...");
+            .label(Span::styled(label, Style::default().add_modifier(Modifier::BOLD)));
         f.render_widget(progress, chunks[1]);
 
         // Status message
-        let message = state.loading_message.as_deref().unwrap_or("Please wait...");
+        let message = state.loading_message.as_deref().unwrap_or(t("progress.wait"));
 
         let status = Paragraph::new(message)
             .style(Style::default().fg(Color::White))
@@ -1,21 +1,19 @@
+use crate::config::Config;
+use crate::messages::{t, tf};
 use crate::util::render_branch_name;
 use anyhow::Result;
-use crossterm::event::{
-    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind,
-};
-use crossterm::execute;
-use crossterm::terminal::{
-    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
-};
+use crate::ui::simple_input::SimpleInput;
+use crate::ui::terminal::TerminalSession;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::prelude::*;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::*;
-use std::io;
 
 pub struct ConfigSelectorApp {
     should_quit: bool,
     selected_index: usize,
     options: Vec<ConfigOption>,
+    env_exists: bool,
 }
 
 #[derive(Clone)]
@@ -25,52 +23,73 @@ pub struct ConfigOption {
     pub choice: ConfigChoice,
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ConfigChoice {
     LoadFromEnv,
     UseDefaults,
     UseGlobalConfig,
+    /// Run the init wizard and write a new cherry.env
+    CreateNew,
 }
 
 impl ConfigSelectorApp {
-    pub fn new() -> Self {
-        let options = vec![
-            ConfigOption {
-                title: "Load from cherry.env".to_string(),
-                description:
-                    "Use project-specific configuration from cherry.env file (recommended)"
-                        .to_string(),
+    /// Selector for a project with (`env_exists`) or without a cherry.env file
+    pub fn with_env_file(env_exists: bool) -> Self {
+        let options = Self::options_for(env_exists);
+        // Without cherry.env, preselect what used to happen silently: the global config
+        let selected_index = if env_exists {
+            0
+        } else {
+            options
+                .iter()
+                .position(|o| o.choice == ConfigChoice::UseGlobalConfig)
+                .unwrap_or(0)
+        };
+        Self {
+            should_quit: false,
+            selected_index,
+            options,
+            env_exists,
+        }
+    }
+
+    /// Options offered by the selector; "create" replaces "load" when there is no cherry.env
+    pub fn options_for(env_exists: bool) -> Vec<ConfigOption> {
+        let mut options = Vec::new();
+        if env_exists {
+            options.push(ConfigOption {
+                title: t("config_selector.load_env").to_string(),
+                description: t("config_selector.load_env_desc").to_string(),
                 choice: ConfigChoice::LoadFromEnv,
-            },
+            });
+        }
+        options.extend([
             ConfigOption {
-                title: "Use defaults only".to_string(),
-                description: "Start with default settings, ignore all configuration files"
-                    .to_string(),
+                title: t("config_selector.defaults").to_string(),
+                description: t("config_selector.defaults_desc").to_string(),
                 choice: ConfigChoice::UseDefaults,
             },
             ConfigOption {
-                title: "Use global config only".to_string(),
-                description: "Load from global config.toml file, ignore cherry.env".to_string(),
+                title: t("config_selector.global").to_string(),
+                description: t("config_selector.global_desc").to_string(),
                 choice: ConfigChoice::UseGlobalConfig,
             },
-        ];
-
-        Self {
-            should_quit: false,
-            selected_index: 0,
-            options,
+        ]);
+        if !env_exists {
+            options.push(ConfigOption {
+                title: t("config_selector.create").to_string(),
+                description: t("config_selector.create_desc").to_string(),
+                choice: ConfigChoice::CreateNew,
+            });
         }
+        options
     }
 
-    pub fn run_config_selector() -> Result<ConfigChoice> {
-        // Setup terminal
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+    pub fn run_config_selector(env_exists: bool) -> Result<ConfigChoice> {
+        let mut session = TerminalSession::start()?;
+        let terminal = session.terminal();
 
-        let mut app = ConfigSelectorApp::new();
+        let mut app = ConfigSelectorApp::with_env_file(env_exists);
 
         let result = loop {
             terminal.draw(|f| {
@@ -98,17 +117,12 @@ impl ConfigSelectorApp {
                                     app.selected_index += 1;
                                 }
                             }
-                            KeyCode::Char('1') => {
-                                app.selected_index = 0;
-                                break Ok(app.options[0].choice.clone());
-                            }
-                            KeyCode::Char('2') => {
-                                app.selected_index = 1;
-                                break Ok(app.options[1].choice.clone());
-                            }
-                            KeyCode::Char('3') => {
-                                app.selected_index = 2;
-                                break Ok(app.options[2].choice.clone());
+                            KeyCode::Char(c @ '1'..='9') => {
+                                let index = c as usize - '1' as usize;
+                                if index < app.options.len() {
+                                    app.selected_index = index;
+                                    break Ok(app.options[index].choice.clone());
+                                }
                             }
                             _ => {}
                         }
@@ -117,14 +131,7 @@ impl ConfigSelectorApp {
             }
         };
 
-        // Restore terminal
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
+        session.restore()?;
 
         result
     }
@@ -142,14 +149,18 @@ impl ConfigSelectorApp {
         // Title and info
         let title_text = vec![
             Line::from(vec![Span::styled(
-                "Configuration Loader",
+                t("config_selector.title"),
                 Style::default()
                     .add_modifier(Modifier::BOLD)
                     .fg(Color::Cyan),
             )]),
             Line::from(""),
             Line::from(vec![Span::styled(
-                "Found cherry.env file. Choose how to load configuration:",
+                if self.env_exists {
+                    t("config_selector.env_found")
+                } else {
+                    t("config_selector.env_missing")
+                },
                 Style::default().fg(Color::White),
             )]),
         ];
@@ -158,7 +169,7 @@ impl ConfigSelectorApp {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Configuration Setup ")
+                    .title(t("config_selector.setup_title"))
                     .title_style(Style::default().fg(Color::Cyan)),
             )
             .alignment(Alignment::Center);
@@ -208,19 +219,19 @@ impl ConfigSelectorApp {
         let list = List::new(items).block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Options ")
+                .title(t("config_selector.options_title"))
                 .title_style(Style::default().fg(Color::Yellow)),
         );
 
         f.render_widget(list, chunks[1]);
 
         // Instructions
-        let instructions = ["↑/↓: Navigate | 1-3: Quick select | Enter: Confirm | Esc/q: Cancel"];
-        let instructions_paragraph = Paragraph::new(instructions.join("\n"))
+        let instructions = tf("config_selector.instructions", &[("count", &self.options.len())]);
+        let instructions_paragraph = Paragraph::new(instructions)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Instructions ")
+                    .title(t("config_selector.instructions_title"))
                     .title_style(Style::default().fg(Color::Green)),
             )
             .style(Style::default().fg(Color::Gray))
@@ -228,14 +239,33 @@ impl ConfigSelectorApp {
         f.render_widget(instructions_paragraph, chunks[2]);
     }
 
+    /// Walks through the settings stored in cherry.env, starting from `config`.
+    /// Returns `None` if the user pressed Esc on any question.
+    pub fn run_config_init_wizard(mut config: Config) -> Result<Option<Config>> {
+        let github = &mut config.github;
+        let questions: [(&str, &str, &mut String); 6] = [
+            (t("wizard.owner"), t("wizard.owner_placeholder"), &mut github.owner),
+            (t("wizard.repo"), t("wizard.repo_placeholder"), &mut github.repo),
+            (t("wizard.base_branch"), t("wizard.base_branch_placeholder"), &mut github.base_branch),
+            (t("wizard.target_branch"), t("wizard.target_branch_placeholder"), &mut github.target_branch),
+            (t("wizard.source_branch"), t("wizard.source_branch_placeholder"), &mut github.cherry_pick_source_branch),
+            (t("wizard.branch_template"), t("wizard.branch_template_placeholder"), &mut github.branch_name_template),
+        ];
+
+        for (title, placeholder, value) in questions {
+            match SimpleInput::prompt(title, value, placeholder)? {
+                Some(answer) => *value = answer,
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(config.with_defaults_for_missing_fields()))
+    }
+
     /// TUI-based task ID input
     pub fn get_task_id_input(template: &str) -> Result<String> {
-        // Setup terminal
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+        let mut session = TerminalSession::start()?;
+        let terminal = session.terminal();
 
         let mut input = String::new();
 
@@ -269,14 +299,7 @@ impl ConfigSelectorApp {
             }
         };
 
-        // Restore terminal
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
+        session.restore()?;
 
         result
     }
@@ -296,7 +319,7 @@ impl ConfigSelectorApp {
 
         // Title
         let title = Paragraph::new(Line::from(Span::styled(
-            "Task ID",
+            t("task_id.title"),
             Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
@@ -305,7 +328,7 @@ impl ConfigSelectorApp {
         f.render_widget(title, chunks[0]);
 
         // Prompt line (no boxes)
-        let placeholder = "e.g., GH-123";
+        let placeholder = t("task_id.placeholder");
         let prompt_line = if input.is_empty() {
             Line::from(vec![
                 Span::styled(">> ", Style::default().fg(Color::Yellow)),
@@ -328,7 +351,7 @@ impl ConfigSelectorApp {
         let sample = if input.is_empty() { "GH-123" } else { input };
         let preview = render_branch_name(template, sample);
         let preview_line = Line::from(vec![
-            Span::styled("Branch: ", Style::default().fg(Color::Gray)),
+            Span::styled(t("task_id.branch"), Style::default().fg(Color::Gray)),
             Span::styled(
                 preview,
                 Style::default()
@@ -339,7 +362,7 @@ impl ConfigSelectorApp {
         f.render_widget(Paragraph::new(preview_line), chunks[2]);
 
         // Instructions/status line
-        let status = Paragraph::new("Enter: Confirm  •  Esc: Cancel  •  Backspace: Delete")
+        let status = Paragraph::new(t("input.hint"))
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Left);
         f.render_widget(status, chunks[3]);
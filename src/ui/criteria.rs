@@ -0,0 +1,170 @@
+//! Editing the listing criteria from the empty PR list. The fields are asked
+//! for one prompt at a time; the edited values replace the in-memory config
+//! for the rest of the session and are never written to cherry.env.
+
+use regex::Regex;
+
+use crate::config::Config;
+use crate::messages::{t, tf};
+
+/// One criterion, in the order the prompts ask for them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CriteriaField {
+    Environment,
+    PendingTag,
+    SprintPattern,
+    DaysBack,
+}
+
+impl CriteriaField {
+    pub const FIRST: Self = CriteriaField::Environment;
+    pub const COUNT: usize = 4;
+
+    /// 1-based position among the prompts
+    pub fn step(self) -> usize {
+        match self {
+            CriteriaField::Environment => 1,
+            CriteriaField::PendingTag => 2,
+            CriteriaField::SprintPattern => 3,
+            CriteriaField::DaysBack => 4,
+        }
+    }
+
+    /// The field asked for after this one, if any
+    pub fn next(self) -> Option<Self> {
+        match self {
+            CriteriaField::Environment => Some(CriteriaField::PendingTag),
+            CriteriaField::PendingTag => Some(CriteriaField::SprintPattern),
+            CriteriaField::SprintPattern => Some(CriteriaField::DaysBack),
+            CriteriaField::DaysBack => None,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            CriteriaField::Environment => t("criteria.environment"),
+            CriteriaField::PendingTag => t("criteria.pending_tag"),
+            CriteriaField::SprintPattern => t("criteria.sprint_pattern"),
+            CriteriaField::DaysBack => t("criteria.days_back"),
+        }
+    }
+}
+
+/// The criteria being edited, applied to the config once every field is set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Criteria {
+    pub environment: String,
+    pub pending_tag: String,
+    pub sprint_pattern: String,
+    pub days_back: u32,
+}
+
+impl Criteria {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            environment: config.tags.environment.clone(),
+            pending_tag: config.tags.pending_tag.clone(),
+            sprint_pattern: config.tags.sprint_pattern.clone(),
+            days_back: config.ui.days_back,
+        }
+    }
+
+    /// Current value of `field`, to pre-fill its prompt
+    pub fn value(&self, field: CriteriaField) -> String {
+        match field {
+            CriteriaField::Environment => self.environment.clone(),
+            CriteriaField::PendingTag => self.pending_tag.clone(),
+            CriteriaField::SprintPattern => self.sprint_pattern.clone(),
+            CriteriaField::DaysBack => self.days_back.to_string(),
+        }
+    }
+
+    /// Validates `input` for `field` and stores it, or says what's wrong with it
+    pub fn set(&mut self, field: CriteriaField, input: &str) -> Result<(), String> {
+        let input = input.trim();
+        match field {
+            CriteriaField::Environment | CriteriaField::PendingTag if input.is_empty() => {
+                Err(t("criteria.empty").to_string())
+            }
+            CriteriaField::Environment => {
+                self.environment = input.to_string();
+                Ok(())
+            }
+            CriteriaField::PendingTag => {
+                self.pending_tag = input.to_string();
+                Ok(())
+            }
+            CriteriaField::SprintPattern => match Regex::new(input) {
+                Ok(_) => {
+                    self.sprint_pattern = input.to_string();
+                    Ok(())
+                }
+                Err(e) => Err(tf("criteria.invalid_pattern", &[("error", &e)])),
+            },
+            CriteriaField::DaysBack => match input.parse::<u32>() {
+                Ok(days) if days > 0 => {
+                    self.days_back = days;
+                    Ok(())
+                }
+                _ => Err(tf("criteria.invalid_days", &[("input", &input)])),
+            },
+        }
+    }
+
+    pub fn apply_to(&self, config: &mut Config) {
+        config.tags.environment = self.environment.clone();
+        config.tags.pending_tag = self.pending_tag.clone();
+        config.tags.sprint_pattern = self.sprint_pattern.clone();
+        config.ui.days_back = self.days_back;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_are_asked_in_order() {
+        let mut fields = vec![CriteriaField::FIRST];
+        while let Some(next) = fields.last().unwrap().next() {
+            fields.push(next);
+        }
+        assert_eq!(
+            fields,
+            [
+                CriteriaField::Environment,
+                CriteriaField::PendingTag,
+                CriteriaField::SprintPattern,
+                CriteriaField::DaysBack,
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_input_leaves_the_value_unchanged() {
+        let config = Config::default();
+        let mut criteria = Criteria::from_config(&config);
+        assert!(criteria.set(CriteriaField::Environment, "  ").is_err());
+        assert!(criteria.set(CriteriaField::SprintPattern, "S(\\d+").is_err());
+        assert!(criteria.set(CriteriaField::DaysBack, "0").is_err());
+        assert!(criteria.set(CriteriaField::DaysBack, "two").is_err());
+        assert_eq!(criteria, Criteria::from_config(&config));
+    }
+
+    #[test]
+    fn edited_criteria_replace_the_config_values() {
+        let mut config = Config::default();
+        let mut criteria = Criteria::from_config(&config);
+        criteria.set(CriteriaField::Environment, " QA ").unwrap();
+        criteria.set(CriteriaField::PendingTag, "needs backport").unwrap();
+        criteria.set(CriteriaField::SprintPattern, r"^S\d+$").unwrap();
+        criteria.set(CriteriaField::DaysBack, "60").unwrap();
+        assert_eq!(criteria.value(CriteriaField::DaysBack), "60");
+
+        criteria.apply_to(&mut config);
+        assert_eq!(config.tags.environment, "QA");
+        assert_eq!(config.tags.pending_tag, "needs backport");
+        assert_eq!(config.tags.sprint_pattern, r"^S\d+$");
+        assert_eq!(config.ui.days_back, 60);
+    }
+}
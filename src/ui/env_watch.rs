@@ -0,0 +1,97 @@
+//! Watching cherry.env while the TUI runs (`ui.watch_env`). The file's
+//! modification time is polled, at most once per [`CHECK_EVERY`], so an
+//! editor's save is noticed without a file-system watcher.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often cherry.env's modification time is looked at
+pub const CHECK_EVERY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub struct EnvWatch {
+    /// Directory cherry.env is read from; `None` is the current directory
+    project_dir: Option<PathBuf>,
+    /// Modification time when last looked at; `None` while there's no file
+    modified: Option<SystemTime>,
+    last_check: Instant,
+}
+
+impl EnvWatch {
+    /// Watches the cherry.env in `project_dir`, as it is at `now`
+    pub fn new(project_dir: Option<PathBuf>, now: Instant) -> Self {
+        let modified = modified_time(project_dir.as_deref());
+        Self {
+            project_dir,
+            modified,
+            last_check: now,
+        }
+    }
+
+    pub fn project_dir(&self) -> Option<&Path> {
+        self.project_dir.as_deref()
+    }
+
+    /// Whether cherry.env was written (or created) since the last time this
+    /// said so. A deleted file is not a change: its settings stay applied.
+    pub fn changed(&mut self, now: Instant) -> bool {
+        if now.saturating_duration_since(self.last_check) < CHECK_EVERY {
+            return false;
+        }
+        self.last_check = now;
+        let modified = modified_time(self.project_dir.as_deref());
+        if modified.is_none() || modified == self.modified {
+            return false;
+        }
+        self.modified = modified;
+        true
+    }
+}
+
+fn modified_time(project_dir: Option<&Path>) -> Option<SystemTime> {
+    std::fs::metadata(crate::config::env_file(project_dir))
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use super::*;
+
+    fn touch(dir: &Path, at: SystemTime) {
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join("cherry.env"))
+            .unwrap();
+        file.set_modified(at).unwrap();
+    }
+
+    #[test]
+    fn a_new_modification_time_is_a_change_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let start = Instant::now();
+        touch(dir.path(), SystemTime::UNIX_EPOCH + Duration::from_secs(1_000));
+        let mut watch = EnvWatch::new(Some(dir.path().to_path_buf()), start);
+
+        assert!(!watch.changed(start + CHECK_EVERY));
+        touch(dir.path(), SystemTime::UNIX_EPOCH + Duration::from_secs(2_000));
+        assert!(!watch.changed(start + CHECK_EVERY), "checked again too soon");
+        assert!(watch.changed(start + CHECK_EVERY * 2));
+        assert!(!watch.changed(start + CHECK_EVERY * 3));
+    }
+
+    #[test]
+    fn a_missing_file_is_no_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let start = Instant::now();
+        let mut watch = EnvWatch::new(Some(dir.path().to_path_buf()), start);
+
+        assert!(!watch.changed(start + CHECK_EVERY));
+        touch(dir.path(), SystemTime::UNIX_EPOCH + Duration::from_secs(1_000));
+        assert!(watch.changed(start + CHECK_EVERY * 2));
+    }
+}
@@ -0,0 +1,119 @@
+//! Keys that mean the same thing on several screens, and what they act on
+//! there. Keys only one screen uses stay with that screen's input handler.
+
+use crossterm::event::KeyCode;
+
+use super::state::{AppState, Screen};
+
+/// Copies the highlighted row's identifier
+pub const COPY_KEY: KeyCode = KeyCode::Char('y');
+
+/// What the copy key takes from the highlighted row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyTarget {
+    /// The commits a session record's pick created
+    NewCommitSha,
+    /// The highlighted commit of the PR on the detail screen
+    CommitSha,
+    /// The branches a history row's session picked onto
+    BranchName,
+}
+
+/// What the copy key copies on `screen`; `None` where it does nothing
+pub fn copy_target(screen: &Screen) -> Option<CopyTarget> {
+    match screen {
+        Screen::SessionDetail => Some(CopyTarget::NewCommitSha),
+        Screen::PrDetail => Some(CopyTarget::CommitSha),
+        Screen::History => Some(CopyTarget::BranchName),
+        Screen::MainMenu
+        | Screen::PrList
+        | Screen::Progress
+        | Screen::Error
+        | Screen::TagList
+        | Screen::Conflicts
+        | Screen::HookOutput
+        | Screen::TargetSelect
+        | Screen::Stats => None,
+    }
+}
+
+impl CopyTarget {
+    /// The text to copy from the highlighted row, space-separated when the row
+    /// has several; `None` when nothing is highlighted or the row has none
+    pub fn value(self, state: &AppState) -> Option<String> {
+        let values: Vec<String> = match self {
+            CopyTarget::NewCommitSha => {
+                let session = state.viewed_session.as_ref()?;
+                let record = session.records.get(state.session_record_state.selected()?)?;
+                record.commits.iter().map(|c| c.new_sha.clone()).collect()
+            }
+            CopyTarget::CommitSha => {
+                let pr = state.prs.get(state.detail_index?)?;
+                let position = state.detail_commit_state.selected()?;
+                let order = state.commit_order_for(pr.number, pr.commits.len());
+                vec![pr.commits.get(*order.get(position)?)?.sha.clone()]
+            }
+            CopyTarget::BranchName => state.selected_session()?.target_branches.clone(),
+        };
+        Some(values.join(" ")).filter(|value| !value.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::github::{CommitInfo, PrInfo, PrState};
+    use crate::session_log::{SessionRecords, SessionSummary};
+
+    fn commit(sha: &str) -> CommitInfo {
+        CommitInfo {
+            sha: sha.into(),
+            message: "change".into(),
+            author: "dev".into(),
+            date: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn each_screen_copies_its_own_identifier() {
+        assert_eq!(copy_target(&Screen::SessionDetail), Some(CopyTarget::NewCommitSha));
+        assert_eq!(copy_target(&Screen::PrDetail), Some(CopyTarget::CommitSha));
+        assert_eq!(copy_target(&Screen::History), Some(CopyTarget::BranchName));
+        assert_eq!(copy_target(&Screen::PrList), None);
+    }
+
+    #[test]
+    fn the_highlighted_row_supplies_the_value() {
+        let mut state = AppState::new();
+        let records = SessionRecords::parse(concat!(
+            r#"{"pr_number":1,"status":"picked","target_branch":"release/1.2","commits":[{"source_sha":"aaa","new_sha":"bbb"}]}"#,
+            "\n",
+            r#"{"pr_number":2,"status":"skipped","target_branch":"release/1.2"}"#,
+        ));
+        state.set_sessions(vec![SessionSummary::new(PathBuf::from("session.jsonl"), &records)]);
+        state.view_session(records);
+        assert_eq!(CopyTarget::NewCommitSha.value(&state).as_deref(), Some("bbb"));
+        assert_eq!(CopyTarget::BranchName.value(&state).as_deref(), Some("release/1.2"));
+        state.session_record_state.select_next();
+        assert_eq!(CopyTarget::NewCommitSha.value(&state), None);
+
+        let pr = PrInfo {
+            number: 7,
+            title: "Fix".into(),
+            commits: vec![commit("111"), commit("222")],
+            head_sha: "222".into(),
+            base_ref: "main".into(),
+            head_ref: "feature".into(),
+            state: PrState::Open,
+            ..Default::default()
+        };
+        state.prs = vec![pr];
+        state.detail_index = Some(0);
+        assert_eq!(CopyTarget::CommitSha.value(&state), None);
+        state.detail_commit_state.set_items_count(2);
+        state.move_commit(7, 2, 0, false);
+        assert_eq!(CopyTarget::CommitSha.value(&state).as_deref(), Some("222"));
+    }
+}
@@ -0,0 +1,146 @@
+//! What changed between two loads of the PR list, so a refresh shows what's
+//! new since the last look. PRs are matched by number.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::github::PrInfo;
+
+/// How a PR changed since an earlier load this session, shown on its row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeBadge {
+    /// Not listed by the first load
+    New,
+    /// Edited on GitHub since it was first listed
+    Updated,
+}
+
+/// The difference between two loads
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ListingChanges {
+    /// Listed now but not before, in the new list's order
+    pub added: Vec<u64>,
+    /// Listed both times, with a different `updated_at`
+    pub updated: Vec<u64>,
+    /// Listed before but not now, in the old list's order
+    pub gone: Vec<u64>,
+}
+
+impl ListingChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.gone.is_empty()
+    }
+}
+
+/// What changed from the `before` listing to `after`
+pub fn diff_listing(before: &[PrInfo], after: &[PrInfo]) -> ListingChanges {
+    let previous: HashMap<u64, &PrInfo> = before.iter().map(|pr| (pr.number, pr)).collect();
+    let current: HashSet<u64> = after.iter().map(|pr| pr.number).collect();
+
+    let mut changes = ListingChanges::default();
+    for pr in after {
+        match previous.get(&pr.number) {
+            None => changes.added.push(pr.number),
+            Some(old) if old.updated_at != pr.updated_at => changes.updated.push(pr.number),
+            Some(_) => {}
+        }
+    }
+    changes.gone = before
+        .iter()
+        .map(|pr| pr.number)
+        .filter(|number| !current.contains(number))
+        .collect();
+    changes
+}
+
+/// Adds `changes` to the session's badges. A new PR stays new when it's
+/// updated later; one that left and came back is new again.
+pub fn apply_badges(badges: &mut HashMap<u64, ChangeBadge>, changes: &ListingChanges) {
+    for &number in &changes.added {
+        badges.insert(number, ChangeBadge::New);
+    }
+    for &number in &changes.updated {
+        badges.entry(number).or_insert(ChangeBadge::Updated);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::PrState;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 4, hour, 0, 0).unwrap()
+    }
+
+    fn pr(number: u64, updated: u32) -> PrInfo {
+        PrInfo {
+            number,
+            title: format!("PR {}", number),
+            author: "alice".into(),
+            created_at: at(0),
+            updated_at: at(updated),
+            base_ref: "main".into(),
+            head_ref: "feature".into(),
+            state: PrState::Merged,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn new_updated_and_gone_prs_are_told_apart() {
+        let before = [pr(1, 1), pr(2, 1), pr(3, 1)];
+        let after = [pr(4, 2), pr(3, 1), pr(2, 5), pr(5, 2)];
+        assert_eq!(
+            diff_listing(&before, &after),
+            ListingChanges {
+                added: vec![4, 5],
+                updated: vec![2],
+                gone: vec![1],
+            }
+        );
+        assert!(diff_listing(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn everything_is_new_after_an_empty_listing() {
+        let after = [pr(1, 1), pr(2, 1)];
+        assert_eq!(diff_listing(&[], &after).added, [1, 2]);
+        assert_eq!(diff_listing(&after, &[]).gone, [1, 2]);
+    }
+
+    #[test]
+    fn badges_last_for_the_session_and_new_wins() {
+        let mut badges = HashMap::new();
+        apply_badges(
+            &mut badges,
+            &ListingChanges {
+                added: vec![4],
+                updated: vec![2],
+                gone: vec![],
+            },
+        );
+        // A later load that only updates the new PR, with PR 2 unchanged
+        apply_badges(
+            &mut badges,
+            &ListingChanges {
+                added: vec![],
+                updated: vec![4],
+                gone: vec![],
+            },
+        );
+        assert_eq!(badges[&4], ChangeBadge::New);
+        assert_eq!(badges[&2], ChangeBadge::Updated);
+
+        // Back after being gone
+        apply_badges(
+            &mut badges,
+            &ListingChanges {
+                added: vec![2],
+                updated: vec![],
+                gone: vec![],
+            },
+        );
+        assert_eq!(badges[&2], ChangeBadge::New);
+    }
+}
@@ -1,7 +1,19 @@
 pub mod app;
+pub mod auto_refresh;
+pub mod clipboard;
 pub mod components;
+pub mod pr_row;
+pub mod quick_filters;
+pub mod repo_search;
 pub mod config_selector;
+pub mod criteria;
+pub mod env_watch;
 pub mod events;
+pub mod keymap;
+pub mod listing_changes;
+pub mod org_probe;
 pub mod selector;
+pub mod shutdown;
 pub mod simple_input;
 pub mod state;
+pub mod terminal;
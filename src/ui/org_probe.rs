@@ -0,0 +1,63 @@
+//! Repository counts for the organization selector, probed when
+//! `ui.probe_orgs` is set. Organizations the user can't see any repositories
+//! in are marked and listed last, so they aren't picked only to find nothing.
+
+use std::collections::HashMap;
+
+use crate::github::OrganizationInfo;
+use crate::messages::{t, tf};
+
+/// What a probed organization is annotated with in the selector. Orgs that
+/// weren't probed, or whose probe failed or timed out, have no note.
+pub fn repo_count_note(count: Option<u32>) -> Option<String> {
+    match count? {
+        0 => Some(t("selector.org_no_repos").to_string()),
+        1 => Some(t("selector.org_one_repo").to_string()),
+        n => Some(tf("selector.org_repos", &[("count", &n)])),
+    }
+}
+
+/// `orgs` in selector order: as listed, except orgs probed to have no
+/// repositories go last
+pub fn probed_order<'a>(orgs: &'a [OrganizationInfo], counts: &HashMap<String, u32>) -> Vec<&'a OrganizationInfo> {
+    let mut ordered: Vec<&OrganizationInfo> = orgs.iter().collect();
+    ordered.sort_by_key(|org| counts.get(&org.login) == Some(&0));
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn org(login: &str) -> OrganizationInfo {
+        OrganizationInfo {
+            login: login.into(),
+            name: String::new(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn notes_count_the_repos_or_say_there_are_none() {
+        assert_eq!(repo_count_note(None), None);
+        assert_eq!(repo_count_note(Some(0)).as_deref(), Some("(no accessible repos)"));
+        assert_eq!(repo_count_note(Some(1)).as_deref(), Some("(1 repo)"));
+        assert_eq!(repo_count_note(Some(42)).as_deref(), Some("(42 repos)"));
+    }
+
+    #[test]
+    fn empty_orgs_sink_to_the_bottom_and_unprobed_ones_stay_put() {
+        let orgs = [org("archived"), org("acme"), org("unprobed"), org("left"), org("tools")];
+        let counts = HashMap::from([
+            ("archived".to_string(), 0),
+            ("acme".to_string(), 12),
+            ("left".to_string(), 0),
+            ("tools".to_string(), 3),
+        ]);
+
+        let logins: Vec<&str> = probed_order(&orgs, &counts).iter().map(|o| o.login.as_str()).collect();
+        assert_eq!(logins, ["acme", "unprobed", "tools", "archived", "left"]);
+        let logins: Vec<&str> = probed_order(&orgs, &HashMap::new()).iter().map(|o| o.login.as_str()).collect();
+        assert_eq!(logins, ["archived", "acme", "unprobed", "left", "tools"]);
+    }
+}
@@ -0,0 +1,233 @@
+//! Width-aware composition of one PR list row. The number, commit count and
+//! date get fixed columns, the title takes what's left, and the optional
+//! author/labels columns only appear on wide enough terminals.
+
+use ratatui::style::{Color, Style};
+use ratatui::text::Span;
+use unicode_width::UnicodeWidthStr;
+
+use crate::config::UiConfig;
+use crate::github::{PrInfo, PrState};
+use crate::messages::t;
+use crate::util::{strip_markdown, truncate_display};
+
+/// `#12345 `
+const NUMBER_WIDTH: usize = 7;
+/// ` 12c`
+const COMMITS_WIDTH: usize = 4;
+/// ` 2025-03-04`
+const DATE_WIDTH: usize = 11;
+/// ` alice…`
+const AUTHOR_WIDTH: usize = 14;
+/// ` [S12, DEV]`
+const LABELS_WIDTH: usize = 24;
+/// Below this the date column is dropped to keep the title readable
+const MIN_TITLE_WIDTH: usize = 12;
+
+/// Widths at which the optional columns appear
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowLayout {
+    pub author_min_width: usize,
+    pub labels_min_width: usize,
+}
+
+impl RowLayout {
+    pub fn from_config(ui: &UiConfig) -> Self {
+        Self {
+            author_min_width: ui.author_column_min_width.into(),
+            labels_min_width: ui.labels_column_min_width.into(),
+        }
+    }
+}
+
+impl Default for RowLayout {
+    fn default() -> Self {
+        Self::from_config(&UiConfig::default())
+    }
+}
+
+/// Spans for `pr` filling exactly `width` terminal columns (less if even the
+/// fixed columns don't fit)
+pub fn pr_row_spans(pr: &PrInfo, width: usize, layout: &RowLayout) -> Vec<Span<'static>> {
+    let show_author = width >= layout.author_min_width;
+    let show_labels = width >= layout.labels_min_width;
+
+    let mut fixed = NUMBER_WIDTH + COMMITS_WIDTH;
+    if show_author {
+        fixed += AUTHOR_WIDTH;
+    }
+    if show_labels {
+        fixed += LABELS_WIDTH;
+    }
+    let show_date = width.saturating_sub(fixed + DATE_WIDTH) >= MIN_TITLE_WIDTH;
+    if show_date {
+        fixed += DATE_WIDTH;
+    }
+    let title_width = width.saturating_sub(fixed);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut spans = vec![
+        Span::styled(fit(&format!("#{}", pr.number), NUMBER_WIDTH), Style::default().fg(Color::Cyan)),
+        Span::raw(fit(&strip_markdown(&pr.title), title_width)),
+    ];
+    if show_author {
+        spans.push(Span::styled(fit(&format!(" {}", pr.author), AUTHOR_WIDTH), dim));
+    }
+    if show_labels {
+        let labels = if pr.labels.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", pr.labels.join(", "))
+        };
+        spans.push(Span::styled(fit(&labels, LABELS_WIDTH), dim));
+    }
+    spans.push(Span::styled(
+        fit_right(&format!("{}c", pr.commits.len()), COMMITS_WIDTH),
+        Style::default().fg(Color::Yellow),
+    ));
+    if show_date {
+        spans.push(Span::styled(
+            fit_right(&pr.updated_at.format("%Y-%m-%d").to_string(), DATE_WIDTH),
+            dim,
+        ));
+    }
+    spans
+}
+
+/// What `state` is called in the list and detail view; `None` when unknown
+pub fn state_label(state: PrState) -> Option<&'static str> {
+    match state {
+        PrState::Merged => Some(t("pr_state.merged")),
+        PrState::Open => Some(t("pr_state.open")),
+        PrState::Closed => Some(t("pr_state.closed")),
+        PrState::Unknown => None,
+    }
+}
+
+/// Colored badge leading a row: merged purple, open green, closed red. Padded
+/// to the longest label so the columns after it stay aligned.
+pub fn state_badge(state: PrState) -> Option<Span<'static>> {
+    let color = match state {
+        PrState::Merged => Color::Magenta,
+        PrState::Open => Color::Green,
+        PrState::Closed => Color::Red,
+        PrState::Unknown => return None,
+    };
+    let width = [PrState::Merged, PrState::Open, PrState::Closed]
+        .into_iter()
+        .filter_map(state_label)
+        .map(UnicodeWidthStr::width)
+        .max()
+        .unwrap_or(0);
+    let label = fit(state_label(state)?, width);
+    Some(Span::styled(format!("{} ", label), Style::default().fg(color)))
+}
+
+/// Truncates `s` to `width` display columns with a trailing `…` when cut,
+/// then pads with spaces to exactly `width`
+pub fn fit(s: &str, width: usize) -> String {
+    let text = truncate_display(s, width);
+    // A wide character that didn't fit leaves a one-column gap
+    let pad = width.saturating_sub(text.width());
+    format!("{}{}", text, " ".repeat(pad))
+}
+
+/// Like [`fit`] but right-aligned, for numbers and dates
+fn fit_right(s: &str, width: usize) -> String {
+    let text = s.trim_end();
+    if text.width() + 1 > width {
+        return fit(text, width);
+    }
+    format!("{}{}", " ".repeat(width - text.width()), text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn pr(title: &str) -> PrInfo {
+        PrInfo {
+            number: 4821,
+            title: title.into(),
+            author: "alice".into(),
+            created_at: Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap(),
+            updated_at: Utc.with_ymd_and_hms(2025, 3, 4, 0, 0, 0).unwrap(),
+            labels: vec!["S12".into(), "DEV".into()],
+            base_ref: "main".into(),
+            head_ref: "fix".into(),
+            state: PrState::Open,
+            ..Default::default()
+        }
+    }
+
+    fn render(pr: &PrInfo, width: usize) -> String {
+        pr_row_spans(pr, width, &RowLayout::default())
+            .iter()
+            .map(|s| s.content.to_string())
+            .collect()
+    }
+
+    const LONG: &str = "Fix the race between the session watcher and the config reload when the network drops mid-fetch";
+
+    #[test]
+    fn rows_fill_the_width_exactly() {
+        for width in [60, 80, 120, 200] {
+            let row = render(&pr(LONG), width);
+            assert_eq!(row.width(), width, "width {}: {:?}", width, row);
+            assert!(row.starts_with("#4821  "));
+            assert!(row.trim_end().ends_with("0c 2025-03-04"), "width {}: {:?}", width, row);
+        }
+    }
+
+    #[test]
+    fn long_titles_are_truncated_with_an_ellipsis() {
+        assert!(render(&pr(LONG), 80).contains("…"));
+        assert!(!render(&pr("Short"), 80).contains("…"));
+    }
+
+    #[test]
+    fn optional_columns_collapse_on_narrow_terminals() {
+        let narrow = render(&pr(LONG), 80);
+        assert!(!narrow.contains("alice") && !narrow.contains("[S12"));
+
+        let medium = render(&pr(LONG), 120);
+        assert!(medium.contains(" alice") && !medium.contains("[S12"));
+
+        let wide = render(&pr(LONG), 200);
+        assert!(wide.contains(" alice") && wide.contains("[S12, DEV]"));
+    }
+
+    #[test]
+    fn date_goes_before_the_title_becomes_unreadable() {
+        let row = render(&pr(LONG), 30);
+        assert_eq!(row.width(), 30);
+        assert!(!row.contains("2025"));
+    }
+
+    #[test]
+    fn wide_unicode_titles_keep_the_columns_aligned() {
+        let title = "修复会话记录在网络断开时丢失的问题以及相关的配置重新加载错误";
+        for width in [60, 80, 120, 200] {
+            let row = render(&pr(title), width);
+            assert_eq!(row.width(), width, "width {}: {:?}", width, row);
+            assert!(row.trim_end().ends_with("2025-03-04"));
+        }
+        // 2-column characters can't be split; a gap fills the odd column
+        assert_eq!(fit("修复问题", 6), "修复… ");
+        assert_eq!(fit("修复", 4), "修复");
+    }
+
+    #[test]
+    fn state_badges_are_colored_and_equally_wide() {
+        let badges: Vec<Span> = [PrState::Merged, PrState::Open, PrState::Closed]
+            .into_iter()
+            .filter_map(state_badge)
+            .collect();
+        let colors: Vec<_> = badges.iter().map(|b| b.style.fg).collect();
+        assert_eq!(colors, [Some(Color::Magenta), Some(Color::Green), Some(Color::Red)]);
+        assert!(badges.iter().all(|b| b.width() == badges[0].width()));
+        assert_eq!(badges[1].content.trim_end(), "open");
+        assert!(state_badge(PrState::Unknown).is_none());
+    }
+}
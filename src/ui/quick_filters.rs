@@ -0,0 +1,125 @@
+//! One-key toggles narrowing the PR list: the user's own PRs, the latest
+//! sprint, PRs whose dry-run pick came out clean, and merged PRs. They
+//! combine with each other and with the text filter.
+
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::github::{PrInfo, PrState};
+use crate::messages::{t, tf};
+
+#[derive(Debug, Clone, Default)]
+pub struct QuickFilters {
+    /// Only PRs authored by this login
+    pub mine: Option<String>,
+    /// Only PRs labelled with the latest sprint this pattern matches
+    pub sprint: Option<Regex>,
+    /// Only PRs predicted to pick without conflicts
+    pub conflict_free: bool,
+    /// Only merged PRs, leaving out open ones whose head isn't final
+    pub merged_only: bool,
+}
+
+impl QuickFilters {
+    pub fn is_empty(&self) -> bool {
+        self.mine.is_none() && self.sprint.is_none() && !self.conflict_free && !self.merged_only
+    }
+
+    /// Whether each of `prs` passes every active toggle. `clean` holds the
+    /// conflict predictions by head sha; PRs without one don't count as clean.
+    pub fn keep(&self, prs: &[PrInfo], clean: &HashMap<String, bool>) -> Vec<bool> {
+        let latest = self
+            .sprint
+            .as_ref()
+            .and_then(|pattern| latest_sprint(all_labels(prs), pattern).and_then(|l| sprint_number(l, pattern)));
+        prs.iter()
+            .map(|pr| {
+                self.mine
+                    .as_ref()
+                    .is_none_or(|login| pr.author.eq_ignore_ascii_case(login))
+                    && self.sprint.as_ref().is_none_or(|pattern| {
+                        latest.is_some()
+                            && pr
+                                .labels
+                                .iter()
+                                .any(|label| sprint_number(label, pattern) == latest)
+                    })
+                    && (!self.conflict_free || clean.get(&pr.head_sha) == Some(&true))
+                    && (!self.merged_only || pr.state == PrState::Merged)
+            })
+            .collect()
+    }
+
+    /// Labels for the header, one per active toggle
+    pub fn chips(&self, prs: &[PrInfo]) -> Vec<String> {
+        let mut chips = Vec::new();
+        if self.mine.is_some() {
+            chips.push(t("quick_filter.mine").to_string());
+        }
+        if let Some(pattern) = &self.sprint {
+            let latest = latest_sprint(all_labels(prs), pattern).unwrap_or("-");
+            chips.push(tf("quick_filter.sprint", &[("sprint", &latest)]));
+        }
+        if self.conflict_free {
+            chips.push(t("quick_filter.conflict_free").to_string());
+        }
+        if self.merged_only {
+            chips.push(t("quick_filter.merged").to_string());
+        }
+        chips
+    }
+}
+
+fn all_labels(prs: &[PrInfo]) -> impl Iterator<Item = &str> {
+    prs.iter().flat_map(|pr| pr.labels.iter().map(String::as_str))
+}
+
+/// The numbers in the part of `label` that `pattern` matches (its first
+/// capture group if it has one), in order, so `2024-S9` sorts before
+/// `2025-S1`. `None` when the label isn't a sprint or has no digits.
+pub fn sprint_number(label: &str, pattern: &Regex) -> Option<Vec<u64>> {
+    let captures = pattern.captures(label)?;
+    let text = captures.get(1).or_else(|| captures.get(0))?.as_str();
+    let numbers: Vec<u64> = text
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|run| run.parse().ok())
+        .collect();
+    Some(numbers).filter(|n| !n.is_empty())
+}
+
+/// The label among `labels` with the highest sprint number
+pub fn latest_sprint<'a>(labels: impl IntoIterator<Item = &'a str>, pattern: &Regex) -> Option<&'a str> {
+    labels
+        .into_iter()
+        .filter_map(|label| Some((sprint_number(label, pattern)?, label)))
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, label)| label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sprint_numbers_compare_numerically() {
+        let pattern = Regex::new(r"S\d+").unwrap();
+        assert_eq!(sprint_number("S9", &pattern), Some(vec![9]));
+        assert_eq!(sprint_number("bug", &pattern), None);
+        assert_eq!(
+            latest_sprint(["S9", "S12", "bug", "S10"], &pattern),
+            Some("S12")
+        );
+        assert_eq!(latest_sprint(["bug"], &pattern), None);
+    }
+
+    #[test]
+    fn sprint_numbers_use_the_capture_group_and_every_digit_run() {
+        let pattern = Regex::new(r"^sprint/(\d+-S\d+)$").unwrap();
+        assert_eq!(sprint_number("sprint/2024-S9", &pattern), Some(vec![2024, 9]));
+        assert_eq!(
+            latest_sprint(["sprint/2024-S9", "sprint/2025-S1", "sprint/2024-S12"], &pattern),
+            Some("sprint/2025-S1")
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Remote search for the repository selector. Once the query is long enough
+//! and typing has paused, the selector asks GitHub instead of only filtering
+//! the repositories it already loaded, and shows both sets of matches.
+
+use std::time::{Duration, Instant};
+
+use crate::github::RepositoryInfo;
+
+/// Shorter queries only filter the loaded repositories
+pub const MIN_QUERY_CHARS: usize = 3;
+/// Quiet time after the last keystroke before a search is sent
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Decides when the typed query is worth a search request
+#[derive(Debug, Default)]
+pub struct SearchDebounce {
+    edited_at: Option<Instant>,
+    /// The query last sent, so an unchanged query isn't searched twice
+    searched: Option<String>,
+}
+
+impl SearchDebounce {
+    pub fn edited(&mut self, now: Instant) {
+        self.edited_at = Some(now);
+    }
+
+    /// True once `query` is long enough, hasn't been searched yet and nothing
+    /// has been typed for [`DEBOUNCE`]
+    pub fn should_search(&self, query: &str, now: Instant) -> bool {
+        self.is_pending(query)
+            && self
+                .edited_at
+                .is_some_and(|edited| now.saturating_duration_since(edited) >= DEBOUNCE)
+    }
+
+    /// True while a search for `query` is waiting out the debounce
+    pub fn is_pending(&self, query: &str) -> bool {
+        query.chars().count() >= MIN_QUERY_CHARS && self.searched.as_deref() != Some(query)
+    }
+
+    pub fn searched(&mut self, query: &str) {
+        self.searched = Some(query.to_string());
+    }
+}
+
+/// Rows for a search: the `local` matches first, then repositories the API
+/// `found` that aren't already listed, compared by full name
+pub fn merge_repo_results<'a>(
+    local: Vec<&'a RepositoryInfo>,
+    found: &'a [RepositoryInfo],
+) -> Vec<&'a RepositoryInfo> {
+    let mut merged = local;
+    for repo in found {
+        if !merged
+            .iter()
+            .any(|r| r.full_name.eq_ignore_ascii_case(&repo.full_name))
+        {
+            merged.push(repo);
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(full_name: &str) -> RepositoryInfo {
+        let (owner, name) = full_name.split_once('/').unwrap();
+        RepositoryInfo {
+            name: name.into(),
+            full_name: full_name.into(),
+            owner: owner.into(),
+            description: String::new(),
+            default_branch: "main".into(),
+            private: false,
+            fork: false,
+            stargazers_count: 0,
+            forks_count: 0,
+            language: None,
+            parent_full_name: None,
+        }
+    }
+
+    fn names<'a>(repos: &[&'a RepositoryInfo]) -> Vec<&'a str> {
+        repos.iter().map(|r| r.full_name.as_str()).collect()
+    }
+
+    #[test]
+    fn search_results_follow_local_matches_without_duplicates() {
+        let local = [repo("acme/widgets"), repo("acme/widget-cli")];
+        let found = [repo("acme/widget-docs"), repo("Acme/Widgets"), repo("acme/widget-docs")];
+        let merged = merge_repo_results(local.iter().collect(), &found);
+        assert_eq!(names(&merged), ["acme/widgets", "acme/widget-cli", "acme/widget-docs"]);
+    }
+
+    #[test]
+    fn short_queries_never_search() {
+        let start = Instant::now();
+        let mut debounce = SearchDebounce::default();
+        debounce.edited(start);
+        assert!(!debounce.is_pending("wi"));
+        assert!(!debounce.should_search("wi", start + DEBOUNCE * 2));
+    }
+
+    #[test]
+    fn search_waits_for_a_pause_in_typing() {
+        let start = Instant::now();
+        let mut debounce = SearchDebounce::default();
+        debounce.edited(start);
+        assert!(debounce.is_pending("wid"));
+        assert!(!debounce.should_search("wid", start + DEBOUNCE / 2));
+
+        // Another keystroke restarts the wait
+        debounce.edited(start + DEBOUNCE / 2);
+        assert!(!debounce.should_search("widg", start + DEBOUNCE));
+        assert!(debounce.should_search("widg", start + DEBOUNCE / 2 + DEBOUNCE));
+    }
+
+    #[test]
+    fn a_searched_query_is_not_repeated() {
+        let start = Instant::now();
+        let mut debounce = SearchDebounce::default();
+        debounce.edited(start);
+        debounce.searched("widg");
+        assert!(!debounce.is_pending("widg"));
+        assert!(!debounce.should_search("widg", start + DEBOUNCE));
+        // Editing back to a different query searches again
+        assert!(debounce.should_search("widge", start + DEBOUNCE));
+    }
+}
@@ -1,16 +1,17 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
 use anyhow::Result;
-use crossterm::event::{
-    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind,
-};
-use crossterm::execute;
-use crossterm::terminal::{
-    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
-};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::prelude::*;
 use ratatui::widgets::*;
-use std::io;
 
-use crate::github::{OrganizationInfo, RepositoryInfo};
+use super::org_probe::{probed_order, repo_count_note};
+use super::repo_search::{merge_repo_results, SearchDebounce};
+use super::terminal::TerminalSession;
+use crate::github::{OrganizationDetails, OrganizationInfo, PrTarget, RepositoryInfo};
+use crate::messages::{t, tf};
+use crate::util::{truncate_display, TextMatch};
 
 pub struct SelectorApp {
     should_quit: bool,
@@ -18,35 +19,78 @@ pub struct SelectorApp {
     scroll_offset: usize,
     search_query: String,
     search_mode: bool,
+    /// Text typed after pressing `:`; `Some` while the quick-entry prompt is open
+    quick_entry: Option<String>,
+    /// How the search query is matched against the options
+    text_match: TextMatch,
+    /// Organization details by login, fetched once per org as the selection
+    /// reaches it. `None` records a failed lookup so it isn't retried every frame.
+    org_details: HashMap<String, Option<OrganizationDetails>>,
+}
+
+/// Builds the detail pane for the option at the given index
+type Preview<'a> = &'a mut dyn FnMut(&mut SelectorApp, usize) -> Vec<Line<'static>>;
+
+/// Outcome of the repository selector
+#[derive(Debug, Clone)]
+pub enum RepoSelection {
+    /// A repository picked from the list, either loaded up front or found by search
+    Listed(RepositoryInfo),
+    /// A `name` or `owner/name` typed into the quick-entry prompt, not yet validated
+    Typed(String),
 }
 
 impl SelectorApp {
-    pub fn new() -> Self {
+    pub fn new(text_match: TextMatch) -> Self {
         Self {
             should_quit: false,
             selected_index: 0,
             scroll_offset: 0,
             search_query: String::new(),
             search_mode: false,
+            quick_entry: None,
+            text_match,
+            org_details: HashMap::new(),
         }
     }
 
+    /// `load_details` is called at most once per organization, when it is first highlighted.
+    /// `repo_counts` holds the probed repository counts by login, if any were probed.
     pub fn run_organization_selector(
         user_login: &str,
         orgs: &[OrganizationInfo],
+        repo_counts: &HashMap<String, u32>,
+        load_details: &mut dyn FnMut(&str) -> Option<OrganizationDetails>,
+        text_match: TextMatch,
     ) -> Result<String> {
+        let orgs = probed_order(orgs, repo_counts);
         // Create options list (user account + organizations)
-        let mut options = vec![format!("{} (Your personal account)", user_login)];
-        for org in orgs {
+        let mut options = vec![tf("selector.personal_account_option", &[("login", &user_login)])];
+        for org in &orgs {
             let desc = if org.description.is_empty() {
-                "No description".to_string()
+                t("selector.no_description").to_string()
             } else {
-                org.description.clone()
+                truncate_display(&org.description, 60).into_owned()
             };
-            options.push(format!("{} - {}", org.login, desc));
+            options.push(match repo_count_note(repo_counts.get(&org.login).copied()) {
+                Some(note) => format!("{} {} - {}", org.login, note, desc),
+                None => format!("{} - {}", org.login, desc),
+            });
         }
 
-        let selected_index = Self::run_selector("Select Organization", &options)?;
+        let mut preview = |app: &mut SelectorApp, index: usize| {
+            if index == 0 {
+                return vec![Line::from(user_login.to_string()), Line::from(""), Line::from(t("selector.personal_account"))];
+            }
+            let org = orgs[index - 1];
+            let details = app
+                .org_details
+                .entry(org.login.clone())
+                .or_insert_with(|| load_details(&org.login));
+            org_detail_lines(org, details.as_ref())
+        };
+        let selected_index =
+            SelectorApp::new(text_match).run_list(t("selector.org_title"), &options, Some(&mut preview))?;
 
         if selected_index == 0 {
             Ok(user_login.to_string())
@@ -55,61 +99,91 @@ impl SelectorApp {
         }
     }
 
-    pub fn run_repository_selector(repos: &[RepositoryInfo]) -> Result<String> {
-        let mut app = SelectorApp::new();
-        let selected_index = app.run_repository_selector_internal(repos)?;
-        Ok(repos[selected_index].name.clone())
+    /// `search` queries GitHub for the owner's repositories matching the typed
+    /// text; it runs once typing pauses on a long enough query
+    pub fn run_repository_selector(
+        repos: &[RepositoryInfo],
+        search: &mut dyn FnMut(&str) -> Vec<RepositoryInfo>,
+        text_match: TextMatch,
+    ) -> Result<RepoSelection> {
+        let mut app = SelectorApp::new(text_match);
+        app.run_repository_selector_internal(repos, search)
     }
 
-    fn run_repository_selector_internal(&mut self, repos: &[RepositoryInfo]) -> Result<usize> {
-        // Setup terminal
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+    fn run_repository_selector_internal(
+        &mut self,
+        repos: &[RepositoryInfo],
+        search: &mut dyn FnMut(&str) -> Vec<RepositoryInfo>,
+    ) -> Result<RepoSelection> {
+        let mut session = TerminalSession::start()?;
+        let terminal = session.terminal();
 
-        let mut filtered_indices: Vec<usize> = (0..repos.len()).collect();
+        let mut debounce = SearchDebounce::default();
+        // API matches, with the query they were found for
+        let mut found: Option<(String, Vec<RepositoryInfo>)> = None;
 
         let result = loop {
-            // Filter repos based on search query
-            if self.search_mode && !self.search_query.is_empty() {
-                filtered_indices = repos
+            let query = if self.search_mode {
+                self.search_query.clone()
+            } else {
+                String::new()
+            };
+
+            // Filter repos based on search query, adding API matches once they're in
+            let local: Vec<&RepositoryInfo> = if query.is_empty() {
+                repos.iter().collect()
+            } else {
+                let folded = self.text_match.query(&query);
+                repos
                     .iter()
-                    .enumerate()
-                    .filter(|(_, repo)| {
-                        let search_text =
-                            format!("{} {}", repo.name, repo.description).to_lowercase();
-                        search_text.contains(&self.search_query.to_lowercase())
-                    })
-                    .map(|(index, _)| index)
-                    .collect();
-            } else if !self.search_mode {
-                filtered_indices = (0..repos.len()).collect();
-            }
+                    .filter(|repo| folded.matches(&format!("{} {}", repo.name, repo.description)))
+                    .collect()
+            };
+            let rows = match &found {
+                Some((searched, hits)) if *searched == query => merge_repo_results(local, hits),
+                _ => local,
+            };
 
             // Adjust selected index if it's out of bounds
-            if self.selected_index >= filtered_indices.len() && !filtered_indices.is_empty() {
-                self.selected_index = filtered_indices.len() - 1;
+            if self.selected_index >= rows.len() && !rows.is_empty() {
+                self.selected_index = rows.len() - 1;
             }
 
+            let searching = debounce.is_pending(&query);
             terminal.draw(|f| {
-                self.render_repository_selector(f, repos, &filtered_indices);
+                self.render_repository_selector(f, &rows, searching);
             })?;
 
             if event::poll(std::time::Duration::from_millis(50))? {
                 if let Event::Key(key) = event::read()? {
                     if key.kind == KeyEventKind::Press {
+                        if let Some(entry) = self.quick_entry.as_mut() {
+                            match key.code {
+                                KeyCode::Esc => self.quick_entry = None,
+                                KeyCode::Enter if !entry.trim().is_empty() => {
+                                    break Ok(RepoSelection::Typed(entry.trim().to_string()));
+                                }
+                                KeyCode::Backspace => {
+                                    entry.pop();
+                                }
+                                KeyCode::Char(c) => entry.push(c),
+                                _ => {}
+                            }
+                            continue;
+                        }
                         match key.code {
                             KeyCode::Char('q') | KeyCode::Esc => {
                                 self.should_quit = true;
                                 break Err(anyhow::anyhow!("Selection cancelled"));
                             }
                             KeyCode::Enter => {
-                                if !filtered_indices.is_empty() {
-                                    break Ok(filtered_indices[self.selected_index]);
+                                if let Some(repo) = rows.get(self.selected_index) {
+                                    break Ok(RepoSelection::Listed((*repo).clone()));
                                 }
                             }
+                            KeyCode::Char(':') if !self.search_mode => {
+                                self.quick_entry = Some(String::new());
+                            }
                             KeyCode::Up => {
                                 if self.selected_index > 0 {
                                     self.selected_index -= 1;
@@ -119,7 +193,7 @@ impl SelectorApp {
                                 }
                             }
                             KeyCode::Down => {
-                                if self.selected_index + 1 < filtered_indices.len() {
+                                if self.selected_index + 1 < rows.len() {
                                     self.selected_index += 1;
                                     // Calculate max_visible items the same way as in render function
                                     let available_height = 15; // Approximate height available for list content
@@ -135,54 +209,66 @@ impl SelectorApp {
                             }
                             KeyCode::Backspace if self.search_mode => {
                                 self.search_query.pop();
+                                debounce.edited(Instant::now());
                                 if self.search_query.is_empty() {
                                     self.search_mode = false;
                                 }
                             }
                             KeyCode::Char(c) if self.search_mode => {
                                 self.search_query.push(c);
+                                debounce.edited(Instant::now());
                             }
                             _ => {}
                         }
                     }
                 }
             }
+
+            if self.search_mode && debounce.should_search(&self.search_query, Instant::now()) {
+                let query = self.search_query.clone();
+                debounce.searched(&query);
+                let hits = search(&query);
+                found = Some((query, hits));
+            }
         };
 
-        // Restore terminal
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
+        session.restore()?;
 
         result
     }
 
+    /// Asks whether PRs for a selected fork should come from the fork or its parent
+    pub fn run_fork_target_selector(fork_full_name: &str, parent_full_name: &str) -> Result<PrTarget> {
+        let options = vec![
+            tf("selector.fork_target_parent", &[("repo", &parent_full_name)]),
+            tf("selector.fork_target_fork", &[("repo", &fork_full_name)]),
+        ];
+
+        let selected_index = Self::run_selector(t("selector.fork_target_title"), &options)?;
+
+        Ok(if selected_index == 0 { PrTarget::Parent } else { PrTarget::Fork })
+    }
+
     fn run_selector(title: &str, options: &[String]) -> Result<usize> {
-        // Setup terminal
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
-
-        let mut app = SelectorApp::new();
+        SelectorApp::new(TextMatch::default()).run_list(title, options, None)
+    }
+
+    /// Single-line list selector, with a detail pane beside the list when `preview` is given
+    fn run_list(&mut self, title: &str, options: &[String], mut preview: Option<Preview>) -> Result<usize> {
+        let mut session = TerminalSession::start()?;
+        let terminal = session.terminal();
+
+        let app = self;
         let mut filtered_indices: Vec<usize> = (0..options.len()).collect();
 
         let result = loop {
             // Filter options based on search query
             if app.search_mode && !app.search_query.is_empty() {
+                let query = app.text_match.query(&app.search_query);
                 filtered_indices = options
                     .iter()
                     .enumerate()
-                    .filter(|(_, option)| {
-                        option
-                            .to_lowercase()
-                            .contains(&app.search_query.to_lowercase())
-                    })
+                    .filter(|(_, option)| query.matches(option))
                     .map(|(index, _)| index)
                     .collect();
             } else if !app.search_mode {
@@ -194,8 +280,13 @@ impl SelectorApp {
                 app.selected_index = filtered_indices.len() - 1;
             }
 
+            let details = match (&mut preview, filtered_indices.get(app.selected_index)) {
+                (Some(preview), Some(&index)) => Some(preview(app, index)),
+                _ => None,
+            };
+
             terminal.draw(|f| {
-                app.render_selector(f, title, options, &filtered_indices);
+                app.render_selector(f, title, options, &filtered_indices, details.as_deref());
             })?;
 
             if event::poll(std::time::Duration::from_millis(50))? {
@@ -248,24 +339,12 @@ impl SelectorApp {
             }
         };
 
-        // Restore terminal
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
+        session.restore()?;
 
         result
     }
 
-    fn render_repository_selector(
-        &self,
-        f: &mut Frame,
-        repos: &[RepositoryInfo],
-        filtered_indices: &[usize],
-    ) {
+    fn render_repository_selector(&self, f: &mut Frame, rows: &[&RepositoryInfo], searching: bool) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -277,7 +356,7 @@ impl SelectorApp {
             .split(f.area());
 
         // Title
-    let title_paragraph = Paragraph::new("Select Repository")
+    let title_paragraph = Paragraph::new(t("selector.repo_title"))
             .alignment(Alignment::Center)
             .style(Style::default().add_modifier(Modifier::BOLD));
         f.render_widget(title_paragraph, chunks[0]);
@@ -285,36 +364,37 @@ impl SelectorApp {
         // List with multi-line items
         let max_visible = (chunks[1].height.saturating_sub(2) / 3) as usize; // 3 lines per item (name + desc + separator)
 
-        // Ensure scroll_offset doesn't exceed filtered indices
+        // Ensure scroll_offset doesn't exceed the rows
         let scroll_offset = self
             .scroll_offset
-            .min(filtered_indices.len().saturating_sub(1));
-        let end_index = (scroll_offset + max_visible).min(filtered_indices.len());
-        let visible_indices = if end_index > scroll_offset {
-            &filtered_indices[scroll_offset..end_index]
+            .min(rows.len().saturating_sub(1));
+        let end_index = (scroll_offset + max_visible).min(rows.len());
+        let visible_rows = if end_index > scroll_offset {
+            &rows[scroll_offset..end_index]
         } else {
             &[]
         };
 
-        let items: Vec<ListItem> = visible_indices
+        let items: Vec<ListItem> = visible_rows
             .iter()
             .enumerate()
-            .map(|(i, &original_index)| {
-                let repo = &repos[original_index];
+            .map(|(i, &repo)| {
                 let is_selected = scroll_offset + i == self.selected_index;
 
                 // Main line - repository name with fork indication
-                let name_line = if repo.fork {
-                    format!("{} (fork)", repo.name)
+                let name_line = if let Some(parent) = &repo.parent_full_name {
+                    tf("selector.fork_of", &[("name", &repo.name), ("parent", parent)])
+                } else if repo.fork {
+                    tf("selector.fork", &[("name", &repo.name)])
                 } else {
                     repo.name.clone()
                 };
 
                 // Description line (smaller/dimmed)
                 let desc_line = if repo.description.is_empty() {
-                    "No description available".to_string()
+                    t("selector.no_description").to_string()
                 } else {
-                    repo.description.clone()
+                    truncate_display(&repo.description, 80).into_owned()
                 };
 
                 // Separator line
@@ -357,24 +437,30 @@ impl SelectorApp {
 
         f.render_widget(list, chunks[1]);
 
-        // Search bar
-        let search_title = if self.search_mode {
-            format!(" Search: {} ", self.search_query)
+        // Search bar (doubles as the quick-entry prompt)
+        let search_title = if let Some(entry) = &self.quick_entry {
+            tf("selector.quick_entry", &[("entry", entry)])
+        } else if self.search_mode {
+            tf("selector.search", &[("query", &self.search_query)])
         } else {
-            " Press '/' to search ".to_string()
+            t("selector.repo_search_hint").to_string()
         };
 
-        let search_style = if self.search_mode {
+        let search_style = if self.quick_entry.is_some() || self.search_mode {
             Style::default().fg(Color::Yellow)
         } else {
             Style::default().fg(Color::Gray)
         };
+        let mut search_line = vec![Span::styled(search_title, search_style)];
+        if searching && self.quick_entry.is_none() {
+            search_line.push(Span::styled(t("selector.searching"), Style::default().fg(Color::DarkGray)));
+        }
 
-    let search_paragraph = Paragraph::new(search_title).style(search_style);
+    let search_paragraph = Paragraph::new(Line::from(search_line));
         f.render_widget(search_paragraph, chunks[2]);
 
         // Instructions
-        let instructions = ["↑/↓: Navigate | Enter: Select | /: Search | Esc/q: Cancel"];
+        let instructions = [t("selector.repo_instructions")];
         let instructions_paragraph = Paragraph::new(instructions.join("\n"))
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center);
@@ -387,6 +473,7 @@ impl SelectorApp {
         title: &str,
         options: &[String],
         filtered_indices: &[usize],
+        details: Option<&[Line<'static>]>,
     ) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -404,8 +491,23 @@ impl SelectorApp {
             .style(Style::default().add_modifier(Modifier::BOLD));
         f.render_widget(title_paragraph, chunks[0]);
 
+        let list_area = match details {
+            Some(lines) => {
+                let columns = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(chunks[1]);
+                let pane = Paragraph::new(lines.to_vec())
+                    .block(Block::default().borders(Borders::LEFT).title(t("selector.details_title")))
+                    .wrap(Wrap { trim: true });
+                f.render_widget(pane, columns[1]);
+                columns[0]
+            }
+            None => chunks[1],
+        };
+
         // List
-        let max_visible = chunks[1].height.saturating_sub(2) as usize; // Account for borders
+        let max_visible = list_area.height.saturating_sub(2) as usize; // Account for borders
         let end_index = (self.scroll_offset + max_visible).min(filtered_indices.len());
         let visible_indices = &filtered_indices[self.scroll_offset..end_index];
 
@@ -428,13 +530,13 @@ impl SelectorApp {
 
     let list = List::new(items).style(Style::default().fg(Color::White));
 
-        f.render_widget(list, chunks[1]);
+        f.render_widget(list, list_area);
 
         // Search bar
         let search_title = if self.search_mode {
-            format!(" Search: {} ", self.search_query)
+            tf("selector.search", &[("query", &self.search_query)])
         } else {
-            " Press '/' to search ".to_string()
+            t("selector.search_hint").to_string()
         };
 
         let search_style = if self.search_mode {
@@ -447,10 +549,48 @@ impl SelectorApp {
         f.render_widget(search_paragraph, chunks[2]);
 
         // Instructions
-        let instructions = ["↑/↓: Navigate | Enter: Select | /: Search | Esc/q: Cancel"];
+        let instructions = [t("selector.instructions")];
         let instructions_paragraph = Paragraph::new(instructions.join("\n"))
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center);
         f.render_widget(instructions_paragraph, chunks[3]);
     }
 }
+
+fn org_detail_lines(org: &OrganizationInfo, details: Option<&OrganizationDetails>) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::styled(
+        org.login.clone(),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    if !org.name.is_empty() {
+        lines.push(Line::from(org.name.clone()));
+    }
+    lines.push(Line::from(""));
+
+    match details {
+        Some(details) => {
+            lines.push(Line::from(tf("selector.org_public_repos", &[("count", &details.public_repos)])));
+            lines.push(Line::from(tf("selector.org_public_members", &[("count", &details.public_members)])));
+            lines.push(Line::from(tf(
+                "selector.org_created",
+                &[("date", &details.created_at.format("%Y-%m-%d"))],
+            )));
+            if let Some(plan) = &details.plan {
+                lines.push(Line::from(tf("selector.org_plan", &[("plan", plan)])));
+            }
+        }
+        None => lines.push(Line::from(Span::styled(
+            t("selector.org_details_unavailable"),
+            Style::default().fg(Color::DarkGray),
+        ))),
+    }
+
+    if !org.description.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            org.description.clone(),
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    lines
+}
@@ -0,0 +1,94 @@
+//! Quitting while a pick is running. The first `q` or Ctrl+C lets the commit
+//! being applied land and stops before the next one, so the repository is
+//! never left half way through a cherry-pick with nothing in the session log.
+//! A second Ctrl+C exits straight away, without waiting for the commit or
+//! posting the labels and comment.
+
+/// What a quit key does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuitAction {
+    /// Nothing is running; quit now
+    Exit,
+    /// Finish the git step under way, log the pick, then quit
+    AfterCurrentStep,
+    /// Quit now, without waiting for the git step under way or doing the
+    /// GitHub bookkeeping still to come
+    Force,
+    /// Already stopping; only Ctrl+C escalates
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuitRequest {
+    #[default]
+    None,
+    /// Quit once the pick has stopped
+    Pending,
+    /// Quit now, whatever is still running
+    Forced,
+}
+
+impl QuitRequest {
+    /// Notes a quit key. `busy` means a pick is running; `interrupt` means
+    /// the key was Ctrl+C rather than `q`.
+    pub fn request(&mut self, busy: bool, interrupt: bool) -> QuitAction {
+        if !busy {
+            return QuitAction::Exit;
+        }
+        match (*self, interrupt) {
+            (QuitRequest::None, _) => {
+                *self = QuitRequest::Pending;
+                QuitAction::AfterCurrentStep
+            }
+            (QuitRequest::Pending, true) | (QuitRequest::Forced, true) => {
+                *self = QuitRequest::Forced;
+                QuitAction::Force
+            }
+            (_, false) => QuitAction::Ignore,
+        }
+    }
+
+    /// Whether the next git step should not be started
+    pub fn is_pending(&self) -> bool {
+        *self != QuitRequest::None
+    }
+
+    pub fn is_forced(&self) -> bool {
+        *self == QuitRequest::Forced
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quitting_while_idle_exits_straight_away() {
+        let mut quit = QuitRequest::default();
+        assert_eq!(quit.request(false, false), QuitAction::Exit);
+        assert_eq!(quit.request(false, true), QuitAction::Exit);
+        assert!(!quit.is_pending());
+    }
+
+    #[test]
+    fn quitting_mid_pick_waits_and_a_second_interrupt_forces() {
+        let mut quit = QuitRequest::default();
+        assert_eq!(quit.request(true, false), QuitAction::AfterCurrentStep);
+        assert!(quit.is_pending());
+        assert!(!quit.is_forced());
+
+        // Pressing q again changes nothing; Ctrl+C escalates
+        assert_eq!(quit.request(true, false), QuitAction::Ignore);
+        assert_eq!(quit.request(true, true), QuitAction::Force);
+        assert!(quit.is_forced());
+        assert_eq!(quit.request(true, true), QuitAction::Force);
+        assert_eq!(quit.request(true, false), QuitAction::Ignore);
+    }
+
+    #[test]
+    fn a_first_interrupt_still_lets_the_step_finish() {
+        let mut quit = QuitRequest::default();
+        assert_eq!(quit.request(true, true), QuitAction::AfterCurrentStep);
+        assert_eq!(quit.request(true, true), QuitAction::Force);
+    }
+}
@@ -1,9 +1,9 @@
 use anyhow::Result;
-use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind};
-use crossterm::execute;
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::{prelude::*, widgets::*};
-use std::io;
+
+use super::terminal::TerminalSession;
+use crate::messages::t;
 
 pub struct SimpleInput;
 
@@ -11,11 +11,8 @@ impl SimpleInput {
     /// Prompt for a single line of input using a minimal TUI (no boxes/borders).
     /// Returns Some(input) on Enter, None on Esc/cancel.
     pub fn prompt(title: &str, initial: &str, placeholder: &str) -> Result<Option<String>> {
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = ratatui::backend::CrosstermBackend::new(stdout);
-        let mut terminal = ratatui::Terminal::new(backend)?;
+        let mut session = TerminalSession::start()?;
+        let terminal = session.terminal();
 
         let mut input = initial.to_string();
 
@@ -39,13 +36,7 @@ impl SimpleInput {
             }
         };
 
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
+        session.restore()?;
 
         result
     }
@@ -80,7 +71,7 @@ impl SimpleInput {
         };
         f.render_widget(Paragraph::new(content), chunks[1]);
 
-        let hint = Paragraph::new("Enter: Confirm  •  Esc: Cancel  •  Backspace: Delete")
+        let hint = Paragraph::new(t("input.hint"))
             .style(Style::default().fg(Color::Gray));
         f.render_widget(hint, chunks[2]);
     }
@@ -1,11 +1,257 @@
-use crate::github::PrInfo;
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+
+use crate::git::{ConflictSide, SubmoduleUpdate, UnpushedCommit};
+use crate::github::{follow_ups_of, Capabilities, PrInfo};
+use crate::messages::{t, tf};
+use crate::pr_notes::PrNotes;
+use crate::session_log::{HeldBack, PickRecord, SessionRecords, SessionSummary, SideTaken};
+use crate::target_history::{TargetHistory, TargetRow};
+use crate::ui::auto_refresh::AutoRefresh;
+use crate::ui::criteria::{Criteria, CriteriaField};
+use crate::ui::listing_changes::{apply_badges, diff_listing, ChangeBadge, ListingChanges};
+use crate::ui::quick_filters::QuickFilters;
+use crate::ui::shutdown::QuitRequest;
+use crate::ui::terminal::TerminalCapabilities;
+use crate::util::{short_sha, TextMatch};
+use crate::workflow::{BranchPresence, ConflictHistory, ExistingBranchChoice, PickSource};
 
 #[derive(Debug, Clone)]
 pub enum Screen {
     MainMenu,
     PrList,
+    PrDetail,
     Progress,
     Error,
+    /// Informational list of the local repository's tags
+    TagList,
+    /// Earlier sessions' logs for this repository
+    History,
+    /// The records of the session selected on `History`
+    SessionDetail,
+    /// Files a pick stopped on, when some are binary and can be settled here
+    Conflicts,
+    /// Branches to pick onto, recently used and pinned ones first
+    TargetSelect,
+    /// The loaded PRs counted by author
+    Stats,
+    /// The post-pick hook's output, and what to do once it failed
+    HookOutput,
+}
+
+/// The git work in flight, shown on the progress screen
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum GitOperation {
+    Fetch,
+    /// Branch being checked out
+    Checkout(String),
+    /// Sha of the commit being picked
+    CherryPick(String),
+    /// Branch being pushed
+    Push(String),
+    #[default]
+    Idle,
+}
+
+/// What the inline prompt's input is for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PromptKind {
+    #[default]
+    Filter,
+    /// A PR number to jump to
+    JumpToPr,
+    /// One of the listing criteria, edited from the empty PR list
+    Criteria(CriteriaField),
+    /// The note of this PR
+    Note(u64),
+    /// Another name for the backport branch in `renaming_branch`
+    BranchName,
+}
+
+/// Where a PR number typed into the jump prompt was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JumpTarget {
+    /// On screen at this row of the list
+    Visible(usize),
+    /// Loaded at this index of `prs`, but hidden by the filter
+    Filtered(usize),
+    /// Not among the loaded PRs
+    NotLoaded,
+}
+
+/// Commits a pick created during this session, so the session can be undone
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionPick {
+    pub pr_number: u64,
+    pub branch: String,
+    /// Shas of the new commits on `branch`, oldest first
+    pub commits: Vec<String>,
+}
+
+/// A PR being picked onto several target branches, one after another
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetRun {
+    pub pr_number: u64,
+    pub branches: Vec<String>,
+    /// Index into `branches` of the one being picked onto
+    pub current: usize,
+    /// GitHub updates left out of the first pick, left out of every later one too
+    pub held_back: HeldBack,
+}
+
+impl TargetRun {
+    /// A run starting on the first of `branches`, which must not be empty
+    pub fn new(pr_number: u64, branches: Vec<String>, held_back: HeldBack) -> Self {
+        Self {
+            pr_number,
+            branches,
+            current: 0,
+            held_back,
+        }
+    }
+
+    pub fn branch(&self) -> &str {
+        &self.branches[self.current]
+    }
+
+    /// Moves on to the next branch; `None` once the last one had its pick
+    pub fn advance(&mut self) -> Option<&str> {
+        if self.current + 1 >= self.branches.len() {
+            return None;
+        }
+        self.current += 1;
+        Some(self.branch())
+    }
+
+    /// Which of the branches this is, as a note for the pick's status
+    pub fn progress(&self) -> String {
+        tf(
+            "status.target_run",
+            &[
+                ("index", &(self.current + 1)),
+                ("total", &self.branches.len()),
+                ("branch", &self.branch()),
+            ],
+        )
+    }
+}
+
+/// The post-pick hook's output as it runs and, once it failed, the pick it
+/// holds back from origin and GitHub
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookPanel {
+    pub command: String,
+    pub lines: Vec<String>,
+    /// Lines scrolled back from the end; 0 follows the output
+    pub scroll_back: usize,
+    /// How the hook failed, once it has
+    pub failure: Option<String>,
+    /// The pick waiting to be continued or rolled back
+    pub held_pick: Option<PickRecord>,
+}
+
+impl HookPanel {
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            lines: Vec::new(),
+            scroll_back: 0,
+            failure: None,
+            held_pick: None,
+        }
+    }
+
+    /// Scrolls `by` lines towards the start, or back towards the end
+    pub fn scroll(&mut self, up: bool, by: usize) {
+        self.scroll_back = if up {
+            (self.scroll_back + by).min(self.lines.len().saturating_sub(1))
+        } else {
+            self.scroll_back.saturating_sub(by)
+        };
+    }
+
+    /// The lines that fit in `height` rows at the current scroll position
+    pub fn visible(&self, height: usize) -> &[String] {
+        let end = self.lines.len() - self.scroll_back.min(self.lines.len());
+        &self.lines[end.saturating_sub(height)..end]
+    }
+}
+
+/// A file the stopped pick left conflicted, listed on the conflicts screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictFile {
+    pub path: String,
+    /// Only binary files are resolved here, by keeping one side
+    pub binary: bool,
+    /// The side kept, once resolved
+    pub taken: Option<ConflictSide>,
+    /// Resolved and staged outside the app, seen when the index was re-read
+    pub staged: bool,
+}
+
+/// A question blocking further input until answered: y/n, plus the extra
+/// keys or toggles some offer
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingConfirmation {
+    /// Pick a PR whose commits were reordered away from their original order
+    ReorderedPick { pr_index: usize },
+    /// The PR's commits are gone; pick its merge/squash commit `sha` instead
+    MergeCommitFallback { pr_index: usize, sha: String },
+    /// PR `number` isn't loaded; fetch it directly and add it to the list
+    FetchPr { number: u64 },
+    /// Revert every pick made in this session
+    AbortSession { prs: usize, commits: usize },
+    /// The target branch has commits origin doesn't; `y` picks on top of them,
+    /// `r` resets the branch to origin first
+    UnpushedCommits {
+        pr_index: usize,
+        shas: Vec<String>,
+        reordered: bool,
+        branch: String,
+        commits: Vec<UnpushedCommit>,
+    },
+    /// The backport branch already exists at `presence`; `y` reuses it, `d`
+    /// recreates it from `source` and `e` asks for another name
+    ExistingBranch {
+        pr_index: usize,
+        shas: Vec<String>,
+        reordered: bool,
+        branch: String,
+        presence: BranchPresence,
+        source: String,
+    },
+    /// HEAD is detached at `head`; `y` checks out `branch` and picks
+    DetachedHead {
+        pr_index: usize,
+        shas: Vec<String>,
+        reordered: bool,
+        head: String,
+        branch: String,
+    },
+    /// The PR's commits move submodule pointers; `y` picks anyway
+    SubmoduleUpdates {
+        pr_index: usize,
+        shas: Vec<String>,
+        reordered: bool,
+        updates: Vec<SubmoduleUpdate>,
+    },
+    /// Redo the labels/comments that failed for the viewed session's partial picks
+    RerunBookkeeping { prs: usize },
+    /// PR `number` has follow-ups in the list; `y` picks them after it in list
+    /// order, `o` picks only the PR
+    IncludeFollowUps {
+        pr_index: usize,
+        number: u64,
+        follow_ups: Vec<u64>,
+    },
+    /// Pick PR `number` with only the ticked GitHub updates; `l` and `m`
+    /// toggle the labels and the comment
+    PickOptions {
+        pr_index: usize,
+        number: u64,
+        held_back: HeldBack,
+    },
 }
 
 #[derive(Debug)]
@@ -18,17 +264,128 @@ pub struct AppState {
     pub input_title: String,
     pub input_placeholder: String,
     pub input_buffer: String,
+    pub prompt_kind: PromptKind,
+    /// Validation message shown in place of the prompt title
+    pub prompt_error: Option<String>,
+    /// Criteria entered so far while the criteria prompts are open
+    pub criteria_draft: Option<Criteria>,
+    /// The next load ignores the label criteria; cleared once it's taken
+    pub bypass_labels_once: bool,
     pub filter_query: Option<String>,
+    /// One-key toggles applied on top of `filter_query`
+    pub quick_filters: QuickFilters,
+    /// How `filter_query` is matched against titles and authors
+    pub text_match: TextMatch,
     pub display_indices: Vec<usize>,
+    /// Index into `prs` of the PR shown on the detail screen
+    pub detail_index: Option<usize>,
+    /// Cursor over the commits on the detail screen
+    pub detail_commit_state: ListState,
+    /// Per-PR commit application order (indices into `PrInfo::commits`), keyed by PR number.
+    /// Absent means original order.
+    pub commit_order: HashMap<u64, Vec<usize>>,
+    /// Per-PR squash choices that differ from the configured default
+    pub squash_overrides: HashMap<u64, bool>,
+    pub confirmation: Option<PendingConfirmation>,
+    /// Whether each PR head sha exists in the local clone, keyed by sha.
+    /// Filled by a background check after `set_prs`; absent means not checked yet.
+    pub commit_availability: HashMap<String, bool>,
+    /// Whether each PR's commits would pick cleanly onto the target branch,
+    /// keyed by head sha. Filled by the same background check; absent means
+    /// no prediction could be made.
+    pub conflict_predictions: HashMap<String, bool>,
+    /// PRs found to have lost their commits, keyed by PR number, with what can
+    /// be picked instead. PRs that pick normally are not listed.
+    pub pick_fallbacks: HashMap<u64, PickSource>,
+    /// Picks made in this session, oldest first
+    pub recently_cherry_picked: Vec<SessionPick>,
+    /// PRs that appeared or changed in a reload this session
+    pub change_badges: HashMap<u64, ChangeBadge>,
+    /// Whether `prs` holds a listing to compare the next load against
+    pub listing_loaded: bool,
+    /// Local branches this session created by checking them out from the remote
+    pub created_branches: Vec<String>,
+    /// PRs fetched by number rather than by the listing
+    pub fetched_on_demand: HashSet<u64>,
+    /// PRs the user skipped this session
+    pub skipped_prs: HashSet<u64>,
+    /// PRs to pick, in order, once the pick in progress lands
+    pub pick_queue: Vec<u64>,
+    /// Indices into `prs` marked for a batch pick
+    pub selected_prs: HashSet<usize>,
+    /// How many PRs the batch being picked from `pick_queue` started with
+    pub batch_total: Option<usize>,
+    /// The PR going onto each of several target branches, if one is
+    pub target_run: Option<TargetRun>,
+    /// GitHub updates left out of the next pick, from the pick options prompt
+    pub pick_held_back: HeldBack,
+    /// What the user may do on the repository, probed at startup
+    pub capabilities: Capabilities,
+    /// Target branches whose unpushed commits the user chose to pick on top of
+    pub accepted_unpushed: HashSet<String>,
+    /// What the user chose for the existing backport branch the next pick goes on
+    pub branch_choice: Option<(String, ExistingBranchChoice)>,
+    /// The pick waiting for the backport branch's new name, as it was asked about
+    pub renaming_branch: Option<PendingConfirmation>,
+    /// Backport branches recreated this session, whose push replaces the remote's
+    pub force_push_branches: HashSet<String>,
+    /// PRs picked despite the warning that they move a submodule pointer
+    pub accepted_submodules: HashSet<u64>,
+    /// Conflicts hit this session, to suggest rerere when one repeats
+    pub conflict_history: ConflictHistory,
+    /// Files of the pick stopped on conflicts, for the conflicts screen
+    pub conflict_files: Vec<ConflictFile>,
+    pub conflict_list_state: ListState,
+    /// Log record of that pick, logged again as binary conflicts are settled.
+    /// `None` for a pick an earlier run left stopped.
+    pub conflict_record: Option<PickRecord>,
+    /// Commits of that pick still to apply once it's continued
+    pub conflict_remaining: Vec<String>,
+    /// The last post-pick hook run, for the hook screen
+    pub hook: Option<HookPanel>,
+    pub tags: Vec<String>,
+    /// Session logs for the history screen, newest first
+    pub sessions: Vec<SessionSummary>,
+    pub session_list_state: ListState,
+    /// Records of the session open on the detail screen
+    pub viewed_session: Option<SessionRecords>,
+    pub session_record_state: ListState,
+    /// Target branches used before in this repository, for the target selector
+    pub target_history: TargetHistory,
+    /// Notes attached to PRs, shown under their rows and carried into picks
+    pub pr_notes: PrNotes,
+    /// Every local and origin branch, listed under the selector's recent ones
+    pub branches: Vec<String>,
+    /// Rows of the target selector; headers can't be selected
+    pub target_rows: Vec<TargetRow>,
+    pub target_list_state: ListState,
+    pub terminal_caps: TerminalCapabilities,
+    /// Timer for reloading the PR list while the user is idle
+    pub auto_refresh: AutoRefresh,
     pub error_message: Option<String>,
+    /// PRs a failed load matched before failing, offered from the error screen
+    pub partial_listing: Option<Vec<PrInfo>>,
+    /// SSO authorization page for the token, offered from the error screen
+    pub sso_url: Option<String>,
+    /// Clone picked in, shown in the header when it isn't the current directory
+    pub repo_path: Option<String>,
+    /// `--dry-run`: picks report what they would apply instead of running
+    pub dry_run: bool,
     pub loading_message: Option<String>,
     pub success_message: Option<String>,
+    /// What `loading_message` is waiting on, if it's git work
+    pub current_git_op: GitOperation,
+    /// Quit asked for while a pick was running; it stops between commits
+    pub quit: QuitRequest,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct ListState {
     selected: Option<usize>,
     items_count: usize,
+    /// Which rows the cursor may land on; `None`, or rows past the end of the
+    /// mask, are selectable
+    selectable: Option<Vec<bool>>,
 }
 
 impl ListState {
@@ -36,6 +393,7 @@ impl ListState {
         Self {
             selected: None,
             items_count: 0,
+            selectable: None,
         }
     }
 
@@ -47,59 +405,98 @@ impl ListState {
         self.selected
     }
 
-    pub fn select_next(&mut self) {
-        if self.items_count == 0 {
-            return;
-        }
+    /// Marks which rows can be selected. The current selection is left alone
+    /// so a row that just became unselectable doesn't move the cursor under the
+    /// user; [`ListState::set_items_count`] clamps it.
+    pub fn set_selectable(&mut self, mask: Option<Vec<bool>>) {
+        self.selectable = mask;
+    }
 
-        let i = match self.selected {
-            Some(i) => {
-                if i >= self.items_count - 1 {
-                    0
-                } else {
-                    i + 1
-                }
-            }
-            None => 0,
-        };
-        self.select(Some(i));
+    pub fn is_selectable(&self, index: usize) -> bool {
+        index < self.items_count
+            && self
+                .selectable
+                .as_ref()
+                .and_then(|mask| mask.get(index))
+                .copied()
+                .unwrap_or(true)
     }
 
+    /// Moves to the next selectable row, wrapping; no selection if there is none
+    pub fn select_next(&mut self) {
+        let start = self.selected.map_or(0, |i| i + 1);
+        self.selected = self.find_selectable(start, true);
+    }
+
+    /// Moves to the previous selectable row, wrapping; no selection if there is none
     pub fn select_previous(&mut self) {
-        if self.items_count == 0 {
-            return;
-        }
+        self.selected = match self.selected {
+            Some(i) => self.find_selectable((i + self.items_count).saturating_sub(1), false),
+            None => self.find_selectable(0, true),
+        };
+    }
 
-        let i = match self.selected {
-            Some(i) => {
-                if i == 0 {
-                    self.items_count - 1
+    /// First selectable row from `start` (taken modulo the count), wrapping
+    /// around once in the given direction
+    fn find_selectable(&self, start: usize, forward: bool) -> Option<usize> {
+        let count = self.items_count;
+        (0..count)
+            .map(|step| {
+                if forward {
+                    (start + step) % count
                 } else {
-                    i - 1
+                    (start % count + count - step) % count
                 }
-            }
-            None => 0,
-        };
-        self.select(Some(i));
+            })
+            .find(|&i| self.is_selectable(i))
     }
 
     pub fn set_items_count(&mut self, count: usize) {
         self.items_count = count;
-        if count == 0 {
-            self.selected = None;
-        } else if self.selected.is_none() {
-            self.selected = Some(0);
-        } else if let Some(selected) = self.selected {
-            if selected >= count {
-                self.selected = Some(count - 1);
-            }
-        }
+        let wanted = match self.selected {
+            _ if count == 0 => None,
+            None => Some(0),
+            Some(selected) => Some(selected.min(count - 1)),
+        };
+        self.selected = wanted.and_then(|i| self.nearest_selectable(i));
+    }
+
+    /// `index` if selectable, else the closest selectable row, preferring the
+    /// one after it on a tie
+    fn nearest_selectable(&self, index: usize) -> Option<usize> {
+        (0..self.items_count).find_map(|distance| {
+            [index.checked_add(distance), index.checked_sub(distance)]
+                .into_iter()
+                .flatten()
+                .find(|&i| self.is_selectable(i))
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ListState;
+    use super::{
+        AppState, GitOperation, HookPanel, JumpTarget, ListState, PendingConfirmation, PromptKind, Screen, TargetRow,
+        TargetRun,
+    };
+    use crate::config::Config;
+    use crate::ui::criteria::{Criteria, CriteriaField};
+    use crate::git::ConflictSide;
+    use regex::Regex;
+    use crate::github::{PrInfo, PrState};
+    use crate::messages::tf;
+    use crate::session_log::{HeldBack, PickRecord, PickStatus, SessionLog, SessionRecords, SessionSummary, SideTaken};
+
+    fn pr_numbered(number: u64, title: &str) -> PrInfo {
+        PrInfo {
+            number,
+            title: title.into(),
+            base_ref: "main".into(),
+            head_ref: "feature".into(),
+            state: PrState::Open,
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn selection_wraps_and_initializes() {
@@ -129,6 +526,667 @@ mod tests {
         ls.set_items_count(0);
         assert_eq!(ls.selected(), None);
     }
+
+    #[test]
+    fn navigation_skips_unselectable_rows_and_wraps() {
+        let mut ls = ListState::new();
+        ls.set_selectable(Some(vec![true, false, true, false, false]));
+        ls.set_items_count(5);
+        assert_eq!(ls.selected(), Some(0));
+
+        ls.select_next();
+        assert_eq!(ls.selected(), Some(2));
+        ls.select_next(); // 3 and 4 are skipped on the way round
+        assert_eq!(ls.selected(), Some(0));
+        ls.select_previous();
+        assert_eq!(ls.selected(), Some(2));
+        ls.select_previous();
+        assert_eq!(ls.selected(), Some(0));
+    }
+
+    #[test]
+    fn nothing_selectable_means_no_selection() {
+        let mut ls = ListState::new();
+        ls.set_selectable(Some(vec![false; 3]));
+        ls.set_items_count(3);
+        assert_eq!(ls.selected(), None);
+        ls.select_next();
+        assert_eq!(ls.selected(), None);
+        ls.select_previous();
+        assert_eq!(ls.selected(), None);
+    }
+
+    #[test]
+    fn a_single_selectable_row_holds_the_cursor() {
+        let mut ls = ListState::new();
+        ls.set_selectable(Some(vec![false, false, true]));
+        ls.set_items_count(3);
+        assert_eq!(ls.selected(), Some(2));
+        ls.select_next();
+        assert_eq!(ls.selected(), Some(2));
+        ls.select_previous();
+        assert_eq!(ls.selected(), Some(2));
+    }
+
+    #[test]
+    fn item_count_changes_clamp_to_the_nearest_selectable_row() {
+        let mut ls = ListState::new();
+        ls.set_items_count(5);
+        ls.select(Some(2));
+        ls.set_selectable(Some(vec![true, true, false, true, false]));
+        // Marking the current row doesn't move the cursor by itself
+        assert_eq!(ls.selected(), Some(2));
+        assert!(!ls.is_selectable(2));
+
+        // On a tie the later row wins
+        ls.set_items_count(5);
+        assert_eq!(ls.selected(), Some(3));
+
+        ls.select(Some(4));
+        ls.set_items_count(5);
+        assert_eq!(ls.selected(), Some(3));
+
+        // Rows past the end of the mask are selectable
+        ls.set_items_count(7);
+        ls.select(Some(6));
+        assert!(ls.is_selectable(6));
+        ls.set_selectable(None);
+        assert!(ls.is_selectable(2));
+    }
+
+    #[test]
+    fn commit_order_moves_and_resets() {
+        let mut state = AppState::new();
+        assert_eq!(state.commit_order_for(7, 3), vec![0, 1, 2]);
+        assert!(!state.is_reordered(7));
+
+        // Move the last commit to the front
+        assert_eq!(state.move_commit(7, 3, 2, true), Some(1));
+        assert_eq!(state.move_commit(7, 3, 1, true), Some(0));
+        assert_eq!(state.commit_order_for(7, 3), vec![2, 0, 1]);
+        assert!(state.is_reordered(7));
+
+        // Edges are no-ops
+        assert_eq!(state.move_commit(7, 3, 0, true), None);
+        assert_eq!(state.move_commit(7, 3, 2, false), None);
+
+        // Moving back to the original order clears the override
+        state.move_commit(7, 3, 0, false);
+        state.move_commit(7, 3, 1, false);
+        assert!(!state.is_reordered(7));
+
+        state.move_commit(7, 3, 0, false);
+        state.set_prs(Vec::new());
+        assert!(!state.is_reordered(7));
+    }
+
+    #[test]
+    fn needs_fetch_only_for_known_missing_commits() {
+        let pr = |sha: &str| PrInfo {
+            number: 1,
+            head_sha: sha.into(),
+            base_ref: "main".into(),
+            head_ref: "feature".into(),
+            state: PrState::Open,
+            ..Default::default()
+        };
+        let mut state = AppState::new();
+        state.commit_availability.insert("present".into(), true);
+        state.commit_availability.insert("missing".into(), false);
+
+        assert!(!state.needs_fetch(&pr("present")));
+        assert!(state.needs_fetch(&pr("missing")));
+        // Not checked yet: no badge until the background check reports
+        assert!(!state.needs_fetch(&pr("unknown")));
+    }
+
+    #[test]
+    fn stale_commit_order_falls_back_to_original() {
+        let mut state = AppState::new();
+        state.move_commit(1, 2, 0, false);
+        // Commit count changed since the order was recorded
+        assert_eq!(state.commit_order_for(1, 3), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn jump_selects_a_visible_pr() {
+        let mut state = AppState::new();
+        state.set_prs(vec![pr_numbered(10, "Alpha"), pr_numbered(20, "Beta"), pr_numbered(30, "Gamma")]);
+        state.set_filter_query(Some("a".into()));
+
+        assert_eq!(state.jump_to_pr(30), JumpTarget::Visible(2));
+        assert_eq!(state.selected_pr_index(), Some(2));
+        assert_eq!(state.filter_query.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn jump_clears_a_filter_hiding_the_pr() {
+        let mut state = AppState::new();
+        state.set_prs(vec![pr_numbered(10, "Alpha"), pr_numbered(20, "Beta"), pr_numbered(30, "Gamma")]);
+        state.set_filter_query(Some("gamma".into()));
+
+        assert_eq!(state.jump_to_pr(20), JumpTarget::Filtered(1));
+        assert_eq!(state.filter_query, None);
+        assert_eq!(state.selected_pr_index(), Some(1));
+    }
+
+    #[test]
+    fn jump_to_an_unloaded_pr_changes_nothing_until_it_is_fetched() {
+        let mut state = AppState::new();
+        state.set_prs(vec![pr_numbered(10, "Alpha")]);
+        state.set_filter_query(Some("alpha".into()));
+        state.pr_list_state.select(Some(0));
+
+        assert_eq!(state.jump_to_pr(99), JumpTarget::NotLoaded);
+        assert_eq!(state.selected_pr_index(), Some(0));
+        assert_eq!(state.filter_query.as_deref(), Some("alpha"));
+
+        state.add_fetched_pr(pr_numbered(99, "Omega"));
+        assert_eq!(state.selected_pr_index(), Some(1));
+        assert!(state.fetched_on_demand.contains(&99));
+        assert_eq!(state.filter_query, None);
+    }
+
+    #[test]
+    fn squash_toggles_against_the_configured_default() {
+        let mut state = AppState::new();
+        assert!(!state.squashes(7, false));
+        assert!(state.squashes(7, true));
+
+        state.toggle_squash(7, false);
+        assert!(state.squashes(7, false));
+        assert!(!state.squashes(8, false));
+        state.toggle_squash(7, false);
+        assert!(state.squash_overrides.is_empty());
+
+        state.toggle_squash(7, true);
+        assert!(!state.squashes(7, true));
+        state.set_prs(Vec::new());
+        assert!(state.squashes(7, true));
+    }
+
+    #[test]
+    fn finishing_clears_the_git_operation() {
+        let mut state = AppState::new();
+        state.set_loading("Cherry-picking");
+        state.current_git_op = GitOperation::CherryPick("abc1234".into());
+        state.set_success("done");
+        assert_eq!(state.current_git_op, GitOperation::Idle);
+
+        state.current_git_op = GitOperation::Checkout("release".into());
+        state.set_error("failed".into());
+        assert_eq!(state.current_git_op, GitOperation::Idle);
+    }
+
+    #[test]
+    fn criteria_prompts_walk_every_field() {
+        let mut state = AppState::new();
+        state.start_criteria_edit(Criteria::from_config(&Config::default()));
+        assert_eq!(state.prompt_kind, PromptKind::Criteria(CriteriaField::Environment));
+
+        state.input_buffer = "QA".into();
+        assert_eq!(state.submit_criterion(), None);
+        assert_eq!(state.prompt_kind, PromptKind::Criteria(CriteriaField::PendingTag));
+        assert!(state.input_active);
+
+        // Invalid input keeps the prompt on the same field
+        state.input_buffer.clear();
+        assert_eq!(state.submit_criterion(), None);
+        assert!(state.prompt_error.is_some());
+        assert_eq!(state.prompt_kind, PromptKind::Criteria(CriteriaField::PendingTag));
+
+        state.input_buffer = "backport".into();
+        state.submit_criterion();
+        state.submit_criterion(); // sprint pattern left as it was
+        state.input_buffer = "45".into();
+        let criteria = state.submit_criterion().expect("finished criteria");
+        assert_eq!(criteria.environment, "QA");
+        assert_eq!(criteria.pending_tag, "backport");
+        assert_eq!(criteria.days_back, 45);
+        assert!(!state.input_active);
+        assert_eq!(state.criteria_draft, None);
+    }
+
+    #[test]
+    fn cancelling_drops_the_criteria_draft() {
+        let mut state = AppState::new();
+        state.start_criteria_edit(Criteria::from_config(&Config::default()));
+        state.cancel_prompt();
+        assert_eq!(state.criteria_draft, None);
+        assert_eq!(state.submit_criterion(), None);
+    }
+
+    #[test]
+    fn label_bypass_lasts_one_load() {
+        let mut state = AppState::new();
+        assert!(!state.take_label_bypass());
+        state.bypass_labels_once = true;
+        assert!(state.take_label_bypass());
+        assert!(!state.take_label_bypass());
+    }
+
+    #[test]
+    fn the_target_cursor_skips_section_headers() {
+        use crate::target_history::TargetSection;
+
+        let rows = vec![
+            TargetRow::Header(TargetSection::Recent),
+            TargetRow::Branch("release/2025.08".into()),
+            TargetRow::Header(TargetSection::All),
+            TargetRow::Branch("main".into()),
+        ];
+        let mut state = AppState::new();
+        state.set_target_rows(rows.clone(), None);
+        assert_eq!(state.selected_target(), Some("release/2025.08"));
+        state.target_list_state.select_next();
+        assert_eq!(state.selected_target(), Some("main"));
+        state.target_list_state.select_next();
+        assert_eq!(state.selected_target(), Some("release/2025.08"));
+
+        state.set_target_rows(rows, Some("main"));
+        assert_eq!(state.selected_target(), Some("main"));
+    }
+
+    #[test]
+    fn taking_a_side_is_noted_on_the_record() {
+        let record = PickRecord {
+            pr_number: 7,
+            status: PickStatus::Conflicted,
+            recorded_at: None,
+            pr_title: "Update logo".into(),
+            target_branch: "release".into(),
+            commits: Vec::new(),
+            reordered: false,
+            labels_updated: false,
+            comment_added: false,
+            run: None,
+            sides_taken: Vec::new(),
+            simulated: Vec::new(),
+            unverified_submodules: Vec::new(),
+            api_requests: None,
+            held_back: HeldBack::default(),
+            note: None,
+        };
+        let mut state = AppState::new();
+        state.set_conflicts(
+            &["logo.png".into(), "notes.txt".into()],
+            &["logo.png".into()],
+            record,
+        );
+        assert_eq!(state.selected_conflict().map(|f| f.binary), Some(true));
+
+        let logged = state
+            .take_conflict_side("logo.png", ConflictSide::Theirs)
+            .expect("record to log");
+        assert_eq!(
+            logged.sides_taken,
+            [SideTaken {
+                path: "logo.png".into(),
+                side: ConflictSide::Theirs,
+            }]
+        );
+        assert_eq!(state.conflict_files[0].taken, Some(ConflictSide::Theirs));
+        assert_eq!(state.conflict_files[1].taken, None);
+    }
+
+    #[test]
+    fn the_text_filter_ignores_case_and_accents() {
+        let mut state = AppState::new();
+        state.set_prs(vec![
+            PrInfo {
+                author: "jürgen".into(),
+                ..pr_numbered(1, "Crème de la RELEASE")
+            },
+            pr_numbered(2, "gh_cherry: fix picker"),
+        ]);
+        let shown = |state: &AppState| -> Vec<u64> {
+            state.display_indices.iter().map(|&i| state.prs[i].number).collect()
+        };
+
+        state.set_filter_query(Some("creme".into()));
+        assert_eq!(shown(&state), [1]);
+        state.set_filter_query(Some("Release".into()));
+        assert_eq!(shown(&state), [1]);
+        state.set_filter_query(Some("JURGEN".into()));
+        assert_eq!(shown(&state), [1]);
+        state.set_filter_query(Some("ghch".into()));
+        assert!(shown(&state).is_empty());
+
+        state.text_match.subsequence = true;
+        state.recompute_display_indices();
+        assert_eq!(shown(&state), [2]);
+    }
+
+    #[test]
+    fn selection_toggles_and_clears() {
+        let mut state = AppState::new();
+        state.set_prs(vec![pr_numbered(1, "one"), pr_numbered(2, "two"), pr_numbered(3, "three")]);
+
+        assert!(state.toggle_selection(2));
+        assert!(state.toggle_selection(0));
+        assert_eq!(state.selected_prs, [0, 2].into());
+        assert!(!state.toggle_selection(2));
+        assert_eq!(state.selected_prs, [0].into());
+        state.toggle_selection(1);
+        state.clear_selection();
+        assert!(state.selected_prs.is_empty());
+    }
+
+    #[test]
+    fn selection_survives_filters_and_follows_its_prs_on_reload() {
+        let mut state = AppState::new();
+        state.set_prs(vec![pr_numbered(1, "one"), pr_numbered(2, "two"), pr_numbered(3, "three")]);
+        state.toggle_selection(0);
+        state.toggle_selection(2);
+
+        state.set_filter_query(Some("two".into()));
+        state.set_filter_query(None);
+        assert_eq!(state.selected_prs, [0, 2].into());
+
+        state.set_prs(vec![pr_numbered(3, "three"), pr_numbered(2, "two")]);
+        assert_eq!(state.selected_prs, [0].into());
+    }
+
+    #[test]
+    fn a_queued_selection_counts_through_the_batch_in_list_order() {
+        let mut state = AppState::new();
+        state.set_prs(vec![pr_numbered(7, "seven"), pr_numbered(8, "eight"), pr_numbered(9, "nine")]);
+        state.toggle_selection(2);
+        state.toggle_selection(0);
+
+        assert_eq!(state.queue_selection(), 2);
+        assert_eq!(state.pick_queue, [7, 9]);
+        assert!(state.selected_prs.is_empty());
+        let progress = |index: usize| tf("status.batch_progress", &[("index", &index), ("total", &2)]);
+        state.pick_queue.remove(0);
+        assert_eq!(state.batch_progress(), progress(1));
+        state.pick_queue.remove(0);
+        assert_eq!(state.batch_progress(), progress(2));
+    }
+
+    #[test]
+    fn quick_filters_combine_with_each_other_and_the_text_filter() {
+        let pr = |number: u64, author: &str, sprint: &str| PrInfo {
+            author: author.into(),
+            labels: vec![sprint.into()],
+            head_sha: format!("sha{}", number),
+            ..pr_numbered(number, &format!("Fix {}", number))
+        };
+        let mut state = AppState::new();
+        state.set_prs(vec![
+            pr(1, "alice", "S9"),
+            pr(2, "Alice", "S10"),
+            pr(3, "bob", "S10"),
+            pr(4, "alice", "S10"),
+        ]);
+        let shown = |state: &AppState| -> Vec<u64> {
+            state.display_indices.iter().map(|&i| state.prs[i].number).collect()
+        };
+
+        state.toggle_mine("alice");
+        assert_eq!(shown(&state), [1, 2, 4]);
+        state.toggle_latest_sprint(Regex::new(r"S\d+").unwrap());
+        assert_eq!(shown(&state), [2, 4]);
+        assert_eq!(state.quick_filters.chips(&state.prs).len(), 2);
+
+        // Nothing predicted yet, so conflict-free can't be turned on
+        assert!(!state.toggle_conflict_free());
+        state.conflict_predictions.insert("sha2".into(), false);
+        state.conflict_predictions.insert("sha4".into(), true);
+        assert!(state.toggle_conflict_free());
+        assert_eq!(shown(&state), [4]);
+
+        state.set_filter_query(Some("fix 2".into()));
+        assert!(shown(&state).is_empty());
+        state.toggle_conflict_free();
+        assert_eq!(shown(&state), [2]);
+        state.toggle_mine("alice");
+        state.toggle_latest_sprint(Regex::new(r"S\d+").unwrap());
+        state.set_filter_query(None);
+        assert_eq!(shown(&state), [1, 2, 3, 4]);
+        assert!(state.quick_filters.is_empty());
+    }
+
+    #[test]
+    fn merged_only_hides_open_and_closed_prs() {
+        let pr = |number: u64, pr_state: PrState| PrInfo {
+            state: pr_state,
+            ..pr_numbered(number, &format!("Fix {}", number))
+        };
+        let mut state = AppState::new();
+        state.set_prs(vec![
+            pr(1, PrState::Merged),
+            pr(2, PrState::Open),
+            pr(3, PrState::Closed),
+            pr(4, PrState::Merged),
+        ]);
+        let shown = |state: &AppState| -> Vec<u64> {
+            state.display_indices.iter().map(|&i| state.prs[i].number).collect()
+        };
+
+        state.toggle_merged_only();
+        assert_eq!(shown(&state), [1, 4]);
+        assert_eq!(state.quick_filters.chips(&state.prs), ["merged"]);
+        state.set_filter_query(Some("fix 4".into()));
+        assert_eq!(shown(&state), [4]);
+
+        state.set_filter_query(None);
+        state.toggle_merged_only();
+        assert_eq!(shown(&state), [1, 2, 3, 4]);
+        assert!(state.quick_filters.is_empty());
+    }
+
+    #[test]
+    fn follow_ups_already_picked_or_skipped_are_not_offered() {
+        let follow_up = |number: u64| PrInfo {
+            related_to: vec![10],
+            ..pr_numbered(number, "Follow-up")
+        };
+        let mut state = AppState::new();
+        state.set_prs(vec![pr_numbered(10, "Parent"), follow_up(11), follow_up(12), follow_up(13)]);
+        assert_eq!(state.pending_follow_ups(10), [11, 12, 13]);
+        assert_eq!(state.follow_up_parent(&state.prs[1]), Some(10));
+        assert_eq!(state.follow_up_parent(&state.prs[0]), None);
+
+        state.skipped_prs.insert(12);
+        state.recently_cherry_picked.push(super::SessionPick {
+            pr_number: 13,
+            branch: "main".into(),
+            commits: vec!["abc".into()],
+        });
+        assert_eq!(state.pending_follow_ups(10), [11]);
+    }
+
+    #[test]
+    fn files_staged_outside_the_app_settle_the_conflicts() {
+        let mut state = AppState::new();
+        let files = ["logo.png".to_string(), "src/lib.rs".to_string(), "README.md".to_string()];
+        state.set_leftover_conflicts(&files);
+        assert!(state.conflict_record.is_none());
+        assert!(!state.conflicts_settled());
+
+        state.take_conflict_side("logo.png", ConflictSide::Ours);
+        state.refresh_conflicts(&files[1..]);
+        assert!(!state.conflicts_settled());
+
+        state.refresh_conflicts(&files[2..]);
+        assert!(state.conflict_files[1].staged);
+        assert!(!state.conflict_files[0].staged);
+
+        // Conflicted again after a checkout elsewhere
+        state.refresh_conflicts(&files[1..]);
+        assert!(!state.conflict_files[1].staged);
+
+        state.refresh_conflicts(&[]);
+        assert!(state.conflicts_settled());
+    }
+
+    #[test]
+    fn a_resumed_pick_adds_to_its_session_pick() {
+        let mut state = AppState::new();
+        state.note_session_commit(7, "main", "a".into());
+        state.note_session_commit(7, "main", "b".into());
+        state.note_session_commit(7, "release", "c".into());
+        assert_eq!(state.recently_cherry_picked.len(), 2);
+        assert_eq!(state.recently_cherry_picked[0].commits, ["a", "b"]);
+        assert_eq!(state.recently_cherry_picked[1].branch, "release");
+    }
+
+    #[test]
+    fn pick_options_toggle_and_start_from_what_the_token_allows() {
+        let mut state = AppState::new();
+        state.set_prs(vec![pr_numbered(7, "Fix")]);
+        state.capabilities.can_comment = false;
+        state.open_pick_options(0);
+        let held = |state: &AppState| match &state.confirmation {
+            Some(PendingConfirmation::PickOptions { number, held_back, .. }) => {
+                assert_eq!(*number, 7);
+                *held_back
+            }
+            other => panic!("expected the pick options, got {:?}", other),
+        };
+        assert_eq!(held(&state), HeldBack { labels: false, comment: true });
+
+        assert!(state.toggle_pick_option('l'));
+        assert!(state.toggle_pick_option('M'));
+        assert_eq!(held(&state), HeldBack { labels: true, comment: false });
+        assert!(!state.toggle_pick_option('x'));
+        assert!(state.toggle_pick_option('l'));
+        assert_eq!(held(&state), HeldBack::default());
+
+        // Other prompts aren't touched
+        state.confirmation = Some(PendingConfirmation::FetchPr { number: 7 });
+        assert!(!state.toggle_pick_option('l'));
+        state.confirmation = None;
+        state.open_pick_options(3);
+        assert!(state.confirmation.is_none());
+    }
+
+    #[test]
+    fn a_dry_run_lists_the_commits_and_changes_nothing_else() {
+        let mut state = AppState::new();
+        state.current_screen = Screen::Progress;
+        state.pick_held_back = HeldBack { labels: true, comment: false };
+        state.show_dry_run(7, "release", &["a".repeat(40), "b".repeat(40)]);
+
+        let message = state.success_message.as_deref().unwrap();
+        assert!(message.contains("aaaaaaaa, bbbbbbbb"), "{}", message);
+        assert!(message.contains("release"), "{}", message);
+        assert!(matches!(state.current_screen, Screen::PrList));
+        assert_eq!(state.pick_held_back, HeldBack::default());
+        assert!(state.recently_cherry_picked.is_empty());
+    }
+
+    #[test]
+    fn a_target_run_logs_a_pick_onto_each_branch_in_turn() {
+        let branches: Vec<String> = vec!["release/1.1".into(), "release/1.2".into(), "release/1.3".into()];
+        let held_back = HeldBack { labels: true, comment: false };
+        let mut run = TargetRun::new(7, branches.clone(), held_back);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let log = SessionLog::new(path.clone());
+        let picked = SessionRecords::parse(r#"{"pr_number":7,"status":"picked","labels_updated":false,"comment_added":true}"#)
+            .records[0]
+            .clone();
+        loop {
+            let progress = run.progress();
+            assert!(progress.contains(&format!("{} of 3", run.current + 1)), "{}", progress);
+            assert!(progress.contains(run.branch()), "{}", progress);
+            let record = PickRecord {
+                target_branch: run.branch().to_string(),
+                held_back: run.held_back,
+                ..picked.clone()
+            };
+            log.append(&record).unwrap();
+            if run.advance().is_none() {
+                break;
+            }
+        }
+        assert_eq!(run.branch(), "release/1.3");
+
+        let session = SessionRecords::read(&path).unwrap();
+        assert!(session.records.iter().all(|r| r.held_back == held_back));
+        let summary = SessionSummary::new(path, &session);
+        assert_eq!(summary.target_branches, branches);
+        assert_eq!(summary.picked, 3);
+    }
+
+    #[test]
+    fn the_hook_panel_follows_the_output_until_scrolled_back() {
+        let mut panel = HookPanel::new("cargo check");
+        assert!(panel.visible(3).is_empty());
+        panel.lines = (1..=5).map(|n| format!("line {}", n)).collect();
+        assert_eq!(panel.visible(3), ["line 3", "line 4", "line 5"]);
+
+        panel.scroll(true, 2);
+        assert_eq!(panel.visible(3), ["line 1", "line 2", "line 3"]);
+        // Never past the first line
+        panel.scroll(true, 10);
+        assert_eq!(panel.visible(3), ["line 1"]);
+        panel.scroll(false, 10);
+        assert_eq!(panel.visible(10).len(), 5);
+    }
+
+    #[test]
+    fn a_held_back_pick_that_conflicts_keeps_the_choice() {
+        let mut state = AppState::new();
+        let record = PickRecord {
+            held_back: HeldBack { labels: true, comment: false },
+            ..SessionRecords::parse(r#"{"pr_number":7,"status":"conflicted","labels_updated":false,"comment_added":false}"#).records[0].clone()
+        };
+        state.set_conflicts(&["notes.txt".into()], &[], record);
+        let logged = state
+            .take_conflict_side("notes.txt", ConflictSide::Ours)
+            .expect("record to log");
+        assert!(!logged.labels_due());
+        assert!(logged.comment_due());
+    }
+
+    #[test]
+    fn reloads_badge_new_and_updated_prs_but_not_the_first_load() {
+        use crate::ui::listing_changes::ChangeBadge;
+
+        let mut state = AppState::new();
+        let first = state.set_prs(vec![pr_numbered(1, "One"), pr_numbered(2, "Two"), pr_numbered(3, "Three")]);
+        assert!(first.is_empty());
+        assert!(state.change_badges.is_empty());
+
+        // 3 was picked here, so its dropping off the list isn't news
+        state.note_session_commit(3, "main", "abc".into());
+        let updated = PrInfo {
+            updated_at: state.prs[1].updated_at + chrono::Duration::minutes(5),
+            ..state.prs[1].clone()
+        };
+        let changes = state.replace_prs(vec![pr_numbered(4, "Four"), updated]);
+        assert_eq!(changes.added, [4]);
+        assert_eq!(changes.updated, [2]);
+        assert_eq!(changes.gone, [1]);
+        assert_eq!(state.change_badges.get(&4), Some(&ChangeBadge::New));
+        assert_eq!(state.change_badges.get(&2), Some(&ChangeBadge::Updated));
+
+        // Badges stay for the session
+        let unchanged = state.prs.clone();
+        assert!(state.replace_prs(unchanged).is_empty());
+        assert_eq!(state.change_badges.len(), 2);
+    }
+
+    #[test]
+    fn a_reload_keeps_the_highlighted_pr_and_waits_for_the_user() {
+        let mut state = AppState::new();
+        state.current_screen = Screen::PrList;
+        state.set_prs(vec![pr_numbered(1, "One"), pr_numbered(2, "Two"), pr_numbered(3, "Three")]);
+        state.pr_list_state.select(Some(2));
+        assert!(!state.auto_refresh_blocked());
+
+        state.replace_prs(vec![pr_numbered(4, "Four"), pr_numbered(1, "One"), pr_numbered(3, "Three")]);
+        assert_eq!(state.selected_pr_index().map(|i| state.prs[i].number), Some(3));
+
+        state.confirmation = Some(PendingConfirmation::FetchPr { number: 5 });
+        assert!(state.auto_refresh_blocked());
+        state.confirmation = None;
+        state.current_screen = Screen::PrDetail;
+        assert!(state.auto_refresh_blocked());
+    }
 }
 
 impl AppState {
@@ -141,25 +1199,427 @@ impl AppState {
             input_title: String::new(),
             input_placeholder: String::new(),
             input_buffer: String::new(),
+            prompt_kind: PromptKind::Filter,
+            prompt_error: None,
+            criteria_draft: None,
+            bypass_labels_once: false,
             filter_query: None,
+            quick_filters: QuickFilters::default(),
+            text_match: TextMatch::default(),
             display_indices: Vec::new(),
+            detail_index: None,
+            detail_commit_state: ListState::new(),
+            commit_order: HashMap::new(),
+            squash_overrides: HashMap::new(),
+            confirmation: None,
+            commit_availability: HashMap::new(),
+            conflict_predictions: HashMap::new(),
+            pick_fallbacks: HashMap::new(),
+            recently_cherry_picked: Vec::new(),
+            change_badges: HashMap::new(),
+            listing_loaded: false,
+            created_branches: Vec::new(),
+            fetched_on_demand: HashSet::new(),
+            skipped_prs: HashSet::new(),
+            pick_queue: Vec::new(),
+            selected_prs: HashSet::new(),
+            batch_total: None,
+            target_run: None,
+            pick_held_back: HeldBack::default(),
+            capabilities: Capabilities::default(),
+            accepted_unpushed: HashSet::new(),
+            branch_choice: None,
+            renaming_branch: None,
+            force_push_branches: HashSet::new(),
+            accepted_submodules: HashSet::new(),
+            conflict_history: ConflictHistory::default(),
+            conflict_files: Vec::new(),
+            conflict_list_state: ListState::new(),
+            conflict_record: None,
+            conflict_remaining: Vec::new(),
+            hook: None,
+            tags: Vec::new(),
+            sessions: Vec::new(),
+            session_list_state: ListState::new(),
+            viewed_session: None,
+            session_record_state: ListState::new(),
+            target_history: TargetHistory::default(),
+            pr_notes: PrNotes::default(),
+            branches: Vec::new(),
+            target_rows: Vec::new(),
+            target_list_state: ListState::new(),
+            terminal_caps: TerminalCapabilities::default(),
+            auto_refresh: AutoRefresh::new(0, std::time::Instant::now()),
             error_message: None,
+            partial_listing: None,
+            sso_url: None,
+            repo_path: None,
+            dry_run: false,
             loading_message: None,
+            current_git_op: GitOperation::Idle,
+            quit: QuitRequest::default(),
             success_message: None,
         }
     }
 
-    pub fn set_prs(&mut self, prs: Vec<PrInfo>) {
+    /// Lists `prs`, returning how they differ from the listing they replace.
+    /// PRs picked this session or fetched by number aren't reported as gone.
+    pub fn set_prs(&mut self, prs: Vec<PrInfo>) -> ListingChanges {
+        let mut changes = if self.listing_loaded {
+            diff_listing(&self.prs, &prs)
+        } else {
+            ListingChanges::default()
+        };
+        changes.gone.retain(|number| {
+            !self.fetched_on_demand.contains(number)
+                && !self.recently_cherry_picked.iter().any(|pick| pick.pr_number == *number)
+        });
+        apply_badges(&mut self.change_badges, &changes);
+        self.listing_loaded = true;
+
+        // The selection follows its PRs to their new rows; gone ones drop out
+        let selected: HashSet<u64> = self.selected_prs.iter().filter_map(|&i| self.prs.get(i)).map(|pr| pr.number).collect();
+        self.selected_prs = prs
+            .iter()
+            .enumerate()
+            .filter(|(_, pr)| selected.contains(&pr.number))
+            .map(|(i, _)| i)
+            .collect();
         self.prs = prs;
+        // Indices into the old commit lists are meaningless after a refresh
+        self.commit_order.clear();
+        self.squash_overrides.clear();
+        self.pick_fallbacks.clear();
+        self.fetched_on_demand.clear();
         self.recompute_display_indices();
         self.loading_message = None;
         self.error_message = None;
+        changes
+    }
+
+    /// Swaps in a reloaded list, keeping the highlighted PR highlighted if it's
+    /// still listed and visible
+    pub fn replace_prs(&mut self, prs: Vec<PrInfo>) -> ListingChanges {
+        let selected = self.selected_pr_index().map(|i| self.prs[i].number);
+        let changes = self.set_prs(prs);
+        if let Some(JumpTarget::Visible(row)) = selected.map(|number| self.resolve_jump(number)) {
+            self.pr_list_state.select(Some(row));
+        }
+        changes
+    }
+
+    /// Listed follow-ups of PR `number` that haven't been picked or skipped this session
+    pub fn pending_follow_ups(&self, number: u64) -> Vec<u64> {
+        follow_ups_of(&self.prs, number)
+            .into_iter()
+            .filter(|n| {
+                !self.skipped_prs.contains(n)
+                    && !self.recently_cherry_picked.iter().any(|pick| pick.pr_number == *n)
+            })
+            .collect()
+    }
+
+    /// Opens the pick options prompt for the PR at `pr_index`, with the
+    /// updates the token can't make already held back
+    pub fn open_pick_options(&mut self, pr_index: usize) {
+        let Some(number) = self.prs.get(pr_index).map(|pr| pr.number) else {
+            return;
+        };
+        self.confirmation = Some(PendingConfirmation::PickOptions {
+            pr_index,
+            number,
+            held_back: HeldBack {
+                labels: !self.capabilities.can_label,
+                comment: !self.capabilities.can_comment,
+            },
+        });
+    }
+
+    /// Flips one box of the open pick options prompt: `l` labels, `m` the
+    /// comment. Returns whether `key` toggled anything.
+    pub fn toggle_pick_option(&mut self, key: char) -> bool {
+        let Some(PendingConfirmation::PickOptions { held_back, .. }) = &mut self.confirmation else {
+            return false;
+        };
+        match key.to_ascii_lowercase() {
+            'l' => held_back.labels = !held_back.labels,
+            'm' => held_back.comment = !held_back.comment,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Reports a dry-run pick of PR `number`: the commits that would have
+    /// been applied onto `target_branch`, in order
+    pub fn show_dry_run(&mut self, number: u64, target_branch: &str, shas: &[String]) {
+        let shas: Vec<&str> = shas.iter().map(|sha| short_sha(sha)).collect();
+        self.pick_held_back = HeldBack::default();
+        self.set_success(&tf(
+            "status.dry_run_pick",
+            &[
+                ("number", &number),
+                ("count", &shas.len()),
+                ("branch", &target_branch),
+                ("shas", &shas.join(", ")),
+            ],
+        ));
+        self.current_screen = Screen::PrList;
+    }
+
+    /// The loaded PR that `pr` follows up, if any
+    pub fn follow_up_parent(&self, pr: &PrInfo) -> Option<u64> {
+        pr.related_to
+            .first()
+            .copied()
+            .filter(|parent| self.prs.iter().any(|p| p.number == *parent))
+    }
+
+    /// Whether an automatic reload would interrupt the user: only the PR list
+    /// with no prompt, confirmation or git work open is safe to reload under them
+    pub fn auto_refresh_blocked(&self) -> bool {
+        !matches!(self.current_screen, Screen::PrList)
+            || self.input_active
+            || self.confirmation.is_some()
+            || self.current_git_op != GitOperation::Idle
+    }
+
+    /// Marks PR `index` (into `prs`) for the batch pick, or unmarks it.
+    /// Returns whether it's marked now.
+    pub fn toggle_selection(&mut self, index: usize) -> bool {
+        if self.selected_prs.remove(&index) {
+            false
+        } else {
+            self.selected_prs.insert(index);
+            true
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected_prs.clear();
+    }
+
+    /// Queues the marked PRs in list order as a batch, clearing the marks.
+    /// Returns how many were queued.
+    pub fn queue_selection(&mut self) -> usize {
+        let mut indices: Vec<usize> = self.selected_prs.drain().filter(|&i| i < self.prs.len()).collect();
+        indices.sort_unstable();
+        self.pick_queue = indices.iter().map(|&i| self.prs[i].number).collect();
+        self.batch_total = (!self.pick_queue.is_empty()).then_some(self.pick_queue.len());
+        self.pick_queue.len()
+    }
+
+    /// Where the pick in progress stands in its batch, once it's been taken
+    /// off `pick_queue`; empty outside a batch
+    pub fn batch_progress(&self) -> String {
+        match self.batch_total {
+            Some(total) => tf(
+                "status.batch_progress",
+                &[("index", &(total - self.pick_queue.len())), ("total", &total)],
+            ),
+            None => String::new(),
+        }
+    }
+
+    /// Maps the highlighted list row to its index in `prs`
+    pub fn selected_pr_index(&self) -> Option<usize> {
+        self.pr_list_state
+            .selected()
+            .and_then(|selected| self.display_indices.get(selected).copied())
+    }
+
+    pub fn set_sessions(&mut self, sessions: Vec<SessionSummary>) {
+        self.sessions = sessions;
+        self.session_list_state.set_items_count(self.sessions.len());
+    }
+
+    /// The session highlighted on the history screen
+    pub fn selected_session(&self) -> Option<&SessionSummary> {
+        self.session_list_state
+            .selected()
+            .and_then(|i| self.sessions.get(i))
+    }
+
+    /// Fills the target selector, highlighting branch `keep` if it's listed,
+    /// else the first branch
+    pub fn set_target_rows(&mut self, rows: Vec<TargetRow>, keep: Option<&str>) {
+        let mask = rows.iter().map(|row| matches!(row, TargetRow::Branch(_))).collect();
+        let position = keep.and_then(|keep| {
+            rows.iter()
+                .position(|row| matches!(row, TargetRow::Branch(branch) if branch == keep))
+        });
+        self.target_rows = rows;
+        self.target_list_state = ListState::new();
+        self.target_list_state.set_selectable(Some(mask));
+        self.target_list_state.select(position);
+        self.target_list_state.set_items_count(self.target_rows.len());
+    }
+
+    /// The branch highlighted on the target selector
+    pub fn selected_target(&self) -> Option<&str> {
+        match self.target_rows.get(self.target_list_state.selected()?)? {
+            TargetRow::Branch(branch) => Some(branch),
+            TargetRow::Header(_) => None,
+        }
+    }
+
+    /// Opens `session` on the detail screen with its first record highlighted
+    pub fn view_session(&mut self, session: SessionRecords) {
+        self.session_record_state = ListState::new();
+        self.session_record_state.set_items_count(session.records.len());
+        self.viewed_session = Some(session);
+    }
+
+    /// Lists the files `record`'s pick stopped on, marking the `binary` ones
+    pub fn set_conflicts(&mut self, conflicts: &[String], binary: &[String], record: PickRecord) {
+        self.list_conflict_files(conflicts, binary);
+        self.conflict_record = Some(record);
+    }
+
+    /// Lists the files a cherry-pick found stopped at startup still has
+    /// conflicted. Nothing recorded it, so it can only be continued or aborted.
+    pub fn set_leftover_conflicts(&mut self, conflicts: &[String]) {
+        self.list_conflict_files(conflicts, &[]);
+        self.conflict_record = None;
+        self.conflict_remaining.clear();
+    }
+
+    fn list_conflict_files(&mut self, conflicts: &[String], binary: &[String]) {
+        self.conflict_files = conflicts
+            .iter()
+            .map(|path| ConflictFile {
+                path: path.clone(),
+                binary: binary.contains(path),
+                taken: None,
+                staged: false,
+            })
+            .collect();
+        self.conflict_list_state = ListState::new();
+        self.conflict_list_state
+            .set_items_count(self.conflict_files.len());
+    }
+
+    /// Marks the files no longer in `still_conflicted` as staged, after the
+    /// index was re-read
+    pub fn refresh_conflicts(&mut self, still_conflicted: &[String]) {
+        for file in &mut self.conflict_files {
+            file.staged = file.taken.is_none() && !still_conflicted.contains(&file.path);
+        }
+    }
+
+    /// Whether every conflicted file is settled, so the pick can be continued
+    pub fn conflicts_settled(&self) -> bool {
+        self.conflict_files
+            .iter()
+            .all(|file| file.taken.is_some() || file.staged)
+    }
+
+    /// Forgets the stopped pick once it's continued or aborted
+    pub fn clear_conflicts(&mut self) {
+        self.conflict_files.clear();
+        self.conflict_list_state = ListState::new();
+        self.conflict_record = None;
+        self.conflict_remaining.clear();
+    }
+
+    /// Notes `sha` as picked onto `branch` for `pr_number`, adding to the
+    /// latest session pick when it's the same PR and branch
+    pub fn note_session_commit(&mut self, pr_number: u64, branch: &str, sha: String) {
+        match self.recently_cherry_picked.last_mut() {
+            Some(last) if last.pr_number == pr_number && last.branch == branch => last.commits.push(sha),
+            _ => self.recently_cherry_picked.push(SessionPick {
+                pr_number,
+                branch: branch.to_string(),
+                commits: vec![sha],
+            }),
+        }
+    }
+
+    /// The conflicted file highlighted on the conflicts screen
+    pub fn selected_conflict(&self) -> Option<&ConflictFile> {
+        self.conflict_list_state
+            .selected()
+            .and_then(|i| self.conflict_files.get(i))
+    }
+
+    /// Marks `path` as settled by keeping `side` and notes it on the pick's
+    /// record, which is returned for logging
+    pub fn take_conflict_side(&mut self, path: &str, side: ConflictSide) -> Option<&PickRecord> {
+        let file = self.conflict_files.iter_mut().find(|f| f.path == path)?;
+        file.taken = Some(side);
+        let record = self.conflict_record.as_mut()?;
+        record.sides_taken.push(SideTaken {
+            path: path.to_string(),
+            side,
+        });
+        Some(record)
+    }
+
+    /// Order in which a PR's commits will be applied
+    pub fn commit_order_for(&self, pr_number: u64, commit_count: usize) -> Vec<usize> {
+        match self.commit_order.get(&pr_number) {
+            Some(order) if order.len() == commit_count => order.clone(),
+            _ => (0..commit_count).collect(),
+        }
+    }
+
+    /// Moves the commit at display `position` one step up or down, returning its new
+    /// position. Does nothing at the edges.
+    pub fn move_commit(
+        &mut self,
+        pr_number: u64,
+        commit_count: usize,
+        position: usize,
+        up: bool,
+    ) -> Option<usize> {
+        if position >= commit_count {
+            return None;
+        }
+        let target = if up {
+            position.checked_sub(1)?
+        } else if position + 1 < commit_count {
+            position + 1
+        } else {
+            return None;
+        };
+
+        let mut order = self.commit_order_for(pr_number, commit_count);
+        order.swap(position, target);
+        if order.iter().enumerate().all(|(i, &c)| i == c) {
+            self.commit_order.remove(&pr_number);
+        } else {
+            self.commit_order.insert(pr_number, order);
+        }
+        Some(target)
+    }
+
+    pub fn is_reordered(&self, pr_number: u64) -> bool {
+        self.commit_order.contains_key(&pr_number)
+    }
+
+    /// Whether PR `pr_number`'s commits are squashed into one when picked
+    pub fn squashes(&self, pr_number: u64, default: bool) -> bool {
+        self.squash_overrides.get(&pr_number).copied().unwrap_or(default)
+    }
+
+    pub fn toggle_squash(&mut self, pr_number: u64, default: bool) {
+        let squash = !self.squashes(pr_number, default);
+        if squash == default {
+            self.squash_overrides.remove(&pr_number);
+        } else {
+            self.squash_overrides.insert(pr_number, squash);
+        }
+    }
+
+    /// True once the availability check found the PR's head commit missing locally
+    pub fn needs_fetch(&self, pr: &PrInfo) -> bool {
+        self.commit_availability.get(&pr.head_sha) == Some(&false)
     }
 
     pub fn set_error(&mut self, message: String) {
         self.error_message = Some(message);
         self.loading_message = None;
         self.success_message = None;
+        self.current_git_op = GitOperation::Idle;
     }
 
     pub fn set_loading(&mut self, message: &str) {
@@ -172,6 +1632,7 @@ impl AppState {
         self.success_message = Some(message.to_string());
         self.loading_message = None;
         self.error_message = None;
+        self.current_git_op = GitOperation::Idle;
     }
 
     #[allow(dead_code)] // Useful utility method for future use
@@ -182,8 +1643,10 @@ impl AppState {
     }
 
     // Prompt helpers
-    pub fn start_prompt(&mut self, title: &str, placeholder: &str, initial: &str) {
+    pub fn start_prompt(&mut self, kind: PromptKind, title: &str, placeholder: &str, initial: &str) {
         self.input_active = true;
+        self.prompt_kind = kind;
+        self.prompt_error = None;
         self.input_title = title.to_string();
         self.input_placeholder = placeholder.to_string();
         self.input_buffer = initial.to_string();
@@ -194,6 +1657,8 @@ impl AppState {
         self.input_title.clear();
         self.input_placeholder.clear();
         self.input_buffer.clear();
+        self.prompt_error = None;
+        self.criteria_draft = None;
     }
 
     pub fn confirm_prompt(&mut self) -> String {
@@ -202,27 +1667,173 @@ impl AppState {
         res
     }
 
+    /// Opens the prompt for PR `number`'s note, pre-filled with the current one
+    pub fn start_note_edit(&mut self, number: u64) {
+        let current = self.pr_notes.get(number).unwrap_or_default().to_string();
+        self.start_prompt(
+            PromptKind::Note(number),
+            &tf("note.title", &[("number", &number)]),
+            t("note.hint"),
+            &current,
+        );
+    }
+
+    /// Opens the criteria prompts at the first field, pre-filled from `criteria`
+    pub fn start_criteria_edit(&mut self, criteria: Criteria) {
+        self.prompt_criterion(CriteriaField::FIRST, criteria);
+    }
+
+    fn prompt_criterion(&mut self, field: CriteriaField, criteria: Criteria) {
+        let title = tf(
+            "criteria.title",
+            &[
+                ("step", &field.step()),
+                ("count", &CriteriaField::COUNT),
+                ("field", &field.title()),
+            ],
+        );
+        let value = criteria.value(field);
+        self.start_prompt(PromptKind::Criteria(field), &title, t("criteria.hint"), &value);
+        self.criteria_draft = Some(criteria);
+    }
+
+    /// Takes the prompt's input for the current criterion. Invalid input keeps
+    /// the prompt open with a message, valid input moves on to the next field,
+    /// and the finished criteria are returned after the last one.
+    pub fn submit_criterion(&mut self) -> Option<Criteria> {
+        let PromptKind::Criteria(field) = self.prompt_kind else {
+            return None;
+        };
+        let mut criteria = self.criteria_draft.clone()?;
+        if let Err(message) = criteria.set(field, &self.input_buffer) {
+            self.prompt_error = Some(message);
+            return None;
+        }
+        self.confirm_prompt();
+        match field.next() {
+            Some(next) => {
+                self.prompt_criterion(next, criteria);
+                None
+            }
+            None => Some(criteria),
+        }
+    }
+
+    /// Whether this load should skip the label criteria, clearing the request
+    pub fn take_label_bypass(&mut self) -> bool {
+        std::mem::take(&mut self.bypass_labels_once)
+    }
+
+    /// Finds PR `number` among the loaded PRs
+    pub fn resolve_jump(&self, number: u64) -> JumpTarget {
+        let Some(index) = self.prs.iter().position(|pr| pr.number == number) else {
+            return JumpTarget::NotLoaded;
+        };
+        match self.display_indices.iter().position(|&i| i == index) {
+            Some(row) => JumpTarget::Visible(row),
+            None => JumpTarget::Filtered(index),
+        }
+    }
+
+    /// Selects PR `number`, clearing the filters if they hide it. Returns what
+    /// was found so the caller can offer a fetch for `NotLoaded`.
+    pub fn jump_to_pr(&mut self, number: u64) -> JumpTarget {
+        let target = self.resolve_jump(number);
+        match target {
+            JumpTarget::Visible(row) => self.pr_list_state.select(Some(row)),
+            JumpTarget::Filtered(index) => {
+                if !self.quick_filters.is_empty() {
+                    self.quick_filters = QuickFilters::default();
+                }
+                self.set_filter_query(None);
+                self.pr_list_state.select(Some(index));
+            }
+            JumpTarget::NotLoaded => {}
+        }
+        target
+    }
+
+    /// Appends a PR fetched by number and selects it
+    pub fn add_fetched_pr(&mut self, pr: PrInfo) {
+        let number = pr.number;
+        self.fetched_on_demand.insert(number);
+        self.prs.push(pr);
+        self.recompute_display_indices();
+        self.jump_to_pr(number);
+    }
+
     pub fn set_filter_query(&mut self, q: Option<String>) {
         self.filter_query = q.filter(|s| !s.trim().is_empty());
         self.recompute_display_indices();
     }
 
+    /// Shows only PRs by `login`, or everyone's again
+    pub fn toggle_mine(&mut self, login: &str) {
+        self.quick_filters.mine = match self.quick_filters.mine {
+            Some(_) => None,
+            None => Some(login.to_string()),
+        };
+        self.recompute_display_indices();
+    }
+
+    /// Shows only PRs in the latest sprint `pattern` matches, or every sprint again
+    pub fn toggle_latest_sprint(&mut self, pattern: Regex) {
+        self.quick_filters.sprint = match self.quick_filters.sprint {
+            Some(_) => None,
+            None => Some(pattern),
+        };
+        self.recompute_display_indices();
+    }
+
+    /// Shows only PRs predicted to pick cleanly, or all again. Turning it on
+    /// does nothing until some prediction is in; returns whether it changed.
+    pub fn toggle_conflict_free(&mut self) -> bool {
+        if !self.quick_filters.conflict_free && self.conflict_predictions.is_empty() {
+            return false;
+        }
+        self.quick_filters.conflict_free = !self.quick_filters.conflict_free;
+        self.recompute_display_indices();
+        true
+    }
+
+    /// Shows only merged PRs, or all again
+    pub fn toggle_merged_only(&mut self) {
+        self.quick_filters.merged_only = !self.quick_filters.merged_only;
+        self.recompute_display_indices();
+    }
+
+    /// Remembers what a PR turned out to be pickable from. PRs with nothing
+    /// left to pick can no longer be selected in the list.
+    pub fn record_pick_source(&mut self, pr_number: u64, source: PickSource) {
+        self.pick_fallbacks.insert(pr_number, source);
+        self.refresh_pr_selectable();
+    }
+
+    fn refresh_pr_selectable(&mut self) {
+        let mask = self
+            .display_indices
+            .iter()
+            .map(|&i| !matches!(self.pick_fallbacks.get(&self.prs[i].number), Some(PickSource::Unpickable)))
+            .collect();
+        self.pr_list_state.set_selectable(Some(mask));
+    }
+
     pub fn recompute_display_indices(&mut self) {
         self.display_indices.clear();
-        if let Some(q) = &self.filter_query {
-            let ql = q.to_lowercase();
-            for (i, pr) in self.prs.iter().enumerate() {
-                let n = pr.number.to_string();
-                if pr.title.to_lowercase().contains(&ql)
-                    || pr.author.to_lowercase().contains(&ql)
-                    || n.contains(&ql)
-                {
-                    self.display_indices.push(i);
+        let keep = self.quick_filters.keep(&self.prs, &self.conflict_predictions);
+        let query = self.filter_query.as_deref().map(|query| self.text_match.query(query));
+        for (i, pr) in self.prs.iter().enumerate() {
+            if !keep[i] {
+                continue;
+            }
+            if let Some(query) = &query {
+                if !(query.matches(&pr.title) || query.matches(&pr.author) || query.matches(&pr.number.to_string())) {
+                    continue;
                 }
             }
-        } else {
-            self.display_indices.extend(0..self.prs.len());
+            self.display_indices.push(i);
         }
+        self.refresh_pr_selectable();
         self.pr_list_state
             .set_items_count(self.display_indices.len());
     }
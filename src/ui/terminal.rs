@@ -0,0 +1,247 @@
+use anyhow::Result;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::{Terminal, TerminalOptions, Viewport};
+use std::io::{self, Stdout};
+
+/// What the terminal ended up supporting after setup. Renderers use this to avoid
+/// advertising features (e.g. mouse hints) that aren't available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalCapabilities {
+    pub mouse: bool,
+    /// `false` means we render inline in the normal screen buffer
+    pub alternate_screen: bool,
+}
+
+impl Default for TerminalCapabilities {
+    fn default() -> Self {
+        Self {
+            mouse: true,
+            alternate_screen: true,
+        }
+    }
+}
+
+impl TerminalCapabilities {
+    pub fn is_degraded(&self) -> bool {
+        !self.mouse || !self.alternate_screen
+    }
+}
+
+/// Individual terminal setup steps, injectable so fallback decisions can be tested
+pub trait TerminalSetup {
+    fn enable_raw_mode(&mut self) -> io::Result<()>;
+    fn enter_alternate_screen(&mut self) -> io::Result<()>;
+    fn enable_mouse_capture(&mut self) -> io::Result<()>;
+}
+
+struct CrosstermSetup;
+
+impl TerminalSetup for CrosstermSetup {
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        enable_raw_mode()
+    }
+
+    fn enter_alternate_screen(&mut self) -> io::Result<()> {
+        execute!(io::stdout(), EnterAlternateScreen)
+    }
+
+    fn enable_mouse_capture(&mut self) -> io::Result<()> {
+        execute!(io::stdout(), EnableMouseCapture)
+    }
+}
+
+/// Runs the setup steps, degrading instead of failing where possible: without mouse
+/// capture we carry on, without the alternate screen we render inline. Only a raw-mode
+/// failure is fatal since no TUI input works without it.
+pub fn initialize(setup: &mut dyn TerminalSetup) -> Result<TerminalCapabilities> {
+    if let Err(e) = setup.enable_raw_mode() {
+        anyhow::bail!(
+            "Failed to put the terminal into raw mode: {}\n\n\
+            Common causes:\n\
+            • stdin/stdout is not a TTY (output piped or redirected, CI jobs)\n\
+            • running under tmux/screen or an IDE console without terminal passthrough\n\
+            • TERM is unset or set to an unsupported value",
+            e
+        );
+    }
+
+    let alternate_screen = match setup.enter_alternate_screen() {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("Alternate screen unavailable, rendering inline: {}", e);
+            false
+        }
+    };
+
+    let mouse = match setup.enable_mouse_capture() {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("Mouse capture unavailable, continuing without it: {}", e);
+            false
+        }
+    };
+
+    Ok(TerminalCapabilities {
+        mouse,
+        alternate_screen,
+    })
+}
+
+/// A terminal prepared for TUI rendering, restored on [`TerminalSession::restore`] or drop.
+pub struct TerminalSession {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    capabilities: TerminalCapabilities,
+    restored: bool,
+}
+
+impl TerminalSession {
+    pub fn start() -> Result<Self> {
+        let capabilities = initialize(&mut CrosstermSetup)?;
+
+        let backend = CrosstermBackend::new(io::stdout());
+        let terminal = if capabilities.alternate_screen {
+            Terminal::new(backend)
+        } else {
+            let (_, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+            Terminal::with_options(
+                backend,
+                TerminalOptions {
+                    viewport: Viewport::Inline(rows),
+                },
+            )
+        };
+
+        let terminal = match terminal {
+            Ok(terminal) => terminal,
+            Err(e) => {
+                let _ = disable_raw_mode();
+                return Err(e.into());
+            }
+        };
+
+        Ok(Self {
+            terminal,
+            capabilities,
+            restored: false,
+        })
+    }
+
+    pub fn terminal(&mut self) -> &mut Terminal<CrosstermBackend<Stdout>> {
+        &mut self.terminal
+    }
+
+    pub fn capabilities(&self) -> TerminalCapabilities {
+        self.capabilities
+    }
+
+    /// Undoes only the setup steps that succeeded
+    pub fn restore(&mut self) -> Result<()> {
+        if self.restored {
+            return Ok(());
+        }
+        self.restored = true;
+
+        disable_raw_mode()?;
+        if self.capabilities.mouse {
+            execute!(self.terminal.backend_mut(), DisableMouseCapture)?;
+        }
+        if self.capabilities.alternate_screen {
+            execute!(self.terminal.backend_mut(), LeaveAlternateScreen)?;
+        }
+        self.terminal.show_cursor()?;
+        Ok(())
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeSetup {
+        fail_raw: bool,
+        fail_alternate: bool,
+        fail_mouse: bool,
+    }
+
+    fn failure() -> io::Result<()> {
+        Err(io::Error::other("not supported"))
+    }
+
+    impl TerminalSetup for FakeSetup {
+        fn enable_raw_mode(&mut self) -> io::Result<()> {
+            if self.fail_raw {
+                failure()
+            } else {
+                Ok(())
+            }
+        }
+        fn enter_alternate_screen(&mut self) -> io::Result<()> {
+            if self.fail_alternate {
+                failure()
+            } else {
+                Ok(())
+            }
+        }
+        fn enable_mouse_capture(&mut self) -> io::Result<()> {
+            if self.fail_mouse {
+                failure()
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn all_steps_succeed() {
+        let caps = initialize(&mut FakeSetup::default()).unwrap();
+        assert_eq!(caps, TerminalCapabilities::default());
+        assert!(!caps.is_degraded());
+    }
+
+    #[test]
+    fn mouse_failure_is_not_fatal() {
+        let mut setup = FakeSetup {
+            fail_mouse: true,
+            ..Default::default()
+        };
+        let caps = initialize(&mut setup).unwrap();
+        assert!(!caps.mouse);
+        assert!(caps.alternate_screen);
+        assert!(caps.is_degraded());
+    }
+
+    #[test]
+    fn alternate_screen_failure_falls_back_to_inline() {
+        let mut setup = FakeSetup {
+            fail_alternate: true,
+            ..Default::default()
+        };
+        let caps = initialize(&mut setup).unwrap();
+        assert!(!caps.alternate_screen);
+        assert!(caps.mouse);
+    }
+
+    #[test]
+    fn raw_mode_failure_is_fatal_with_hints() {
+        let mut setup = FakeSetup {
+            fail_raw: true,
+            ..Default::default()
+        };
+        let err = initialize(&mut setup).unwrap_err().to_string();
+        assert!(err.contains("raw mode"));
+        assert!(err.contains("not a TTY"));
+        assert!(err.contains("tmux"));
+    }
+}
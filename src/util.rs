@@ -1,3 +1,8 @@
+use std::borrow::Cow;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
 /// Returns a short prefix of a SHA (up to 8 chars) without panicking on short inputs.
 pub fn short_sha(sha: &str) -> &str {
     if sha.len() >= 8 {
@@ -7,9 +12,295 @@ pub fn short_sha(sha: &str) -> &str {
     }
 }
 
+/// Whether startup may stop to ask the user something
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptPolicy {
+    Ask,
+    /// `--no-prompt` was given
+    Disabled,
+    /// stdin isn't a terminal (piped input, CI), so a question would never be answered
+    NoTerminal,
+}
+
+impl PromptPolicy {
+    /// `--no-prompt` wins; otherwise prompts need a terminal on stdin
+    pub fn decide(no_prompt: bool, stdin_is_terminal: bool) -> Self {
+        if no_prompt {
+            Self::Disabled
+        } else if !stdin_is_terminal {
+            Self::NoTerminal
+        } else {
+            Self::Ask
+        }
+    }
+
+    /// [`Self::decide`] for this process's stdin
+    pub fn detect(no_prompt: bool) -> Self {
+        use std::io::IsTerminal;
+        Self::decide(no_prompt, std::io::stdin().is_terminal())
+    }
+
+    pub fn can_ask(self) -> bool {
+        self == Self::Ask
+    }
+}
+
+/// Host, owner and repository name of a GitHub (or GitHub Enterprise) repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubCoords {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+impl GitHubCoords {
+    /// `owner/repo`
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+}
+
+/// Parses a repository URL in any of the forms git and GitHub use:
+/// `https://github.com/o/r(.git)`, `ssh://git@github.com/o/r.git`,
+/// `git@github.com:o/r.git`, `git://...`, and the same on Enterprise hosts
+/// (including ports and credentials). Extra path segments such as
+/// `/pull/12` are ignored. Returns `None` for local paths or missing owner/repo.
+pub fn parse_github_url(url: &str) -> Option<GitHubCoords> {
+    let url = url.trim();
+    let (host, path) = if url.contains("://") {
+        let parsed = url::Url::parse(url).ok()?;
+        (parsed.host_str()?.to_string(), parsed.path().to_string())
+    } else {
+        // scp-like syntax: [user@]host:owner/repo
+        let (authority, path) = url.split_once(':')?;
+        let host = authority.rsplit('@').next().unwrap_or(authority);
+        // A one-letter "host" is a Windows drive (`C:/src/repo`), not a remote
+        if host.len() < 2 || host.contains('/') {
+            return None;
+        }
+        (host.to_string(), path.to_string())
+    };
+
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let owner = segments.next()?;
+    let repo = segments.next()?;
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+    if repo.is_empty() {
+        return None;
+    }
+
+    Some(GitHubCoords {
+        host: host.to_ascii_lowercase(),
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+    })
+}
+
+/// `text` cut to at most `max_cols` terminal columns, ending in "…" only when
+/// something was cut. Wide characters take two columns, and grapheme clusters
+/// (flags, ZWJ emoji, letters with combining marks) are never split.
+pub fn truncate_display(text: &str, max_cols: usize) -> Cow<'_, str> {
+    if text.width() <= max_cols {
+        return Cow::Borrowed(text);
+    }
+    if max_cols == 0 {
+        return Cow::Borrowed("");
+    }
+    // One column is kept for the ellipsis
+    let budget = max_cols - 1;
+    let mut end = 0;
+    let mut used = 0;
+    for (start, grapheme) in text.grapheme_indices(true) {
+        let width = grapheme.width();
+        if used + width > budget {
+            break;
+        }
+        used += width;
+        end = start + grapheme.len();
+    }
+    Cow::Owned(format!("{}…", &text[..end]))
+}
+
+/// How typed search text is matched against names and titles; every search
+/// box matches through this. Case is always folded, so "RELEASE" and
+/// "release" find the same things.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextMatch {
+    /// Ignore accents and similar marks, so "muller" finds "Müller"
+    pub ignore_diacritics: bool,
+    /// Match the query's characters in order with anything between them, so
+    /// "ghch" finds "gh_cherry"
+    pub subsequence: bool,
+}
+
+impl Default for TextMatch {
+    fn default() -> Self {
+        Self {
+            ignore_diacritics: true,
+            subsequence: false,
+        }
+    }
+}
+
+impl TextMatch {
+    /// Whether `needle` is found in `haystack`; an empty needle matches anything
+    pub fn matches(self, haystack: &str, needle: &str) -> bool {
+        self.query(needle).matches(haystack)
+    }
+
+    /// `needle` folded once, for matching against many rows
+    pub fn query(self, needle: &str) -> FoldedQuery {
+        FoldedQuery {
+            needle: fold_text(needle, self.ignore_diacritics),
+            how: self,
+        }
+    }
+}
+
+/// A search query already folded by [`TextMatch::query`]
+#[derive(Debug, Clone)]
+pub struct FoldedQuery {
+    needle: String,
+    how: TextMatch,
+}
+
+impl FoldedQuery {
+    /// Whether the query is found in `haystack`; an empty query matches anything
+    pub fn matches(&self, haystack: &str) -> bool {
+        let haystack = fold_text(haystack, self.how.ignore_diacritics);
+        if self.how.subsequence {
+            let mut rest = haystack.chars();
+            self.needle.chars().all(|wanted| rest.any(|c| c == wanted))
+        } else {
+            haystack.contains(&self.needle)
+        }
+    }
+}
+
+/// `text` case-folded and compatibility-normalized, so full-width letters and
+/// ligatures compare like their plain forms; with `ignore_diacritics`, combining
+/// marks are dropped too, along with the strokes and ligatures NFKD keeps
+pub fn fold_text(text: &str, ignore_diacritics: bool) -> String {
+    use unicode_normalization::char::is_combining_mark;
+    use unicode_normalization::UnicodeNormalization;
+
+    let mut folded = String::with_capacity(text.len());
+    let normalized: Box<dyn Iterator<Item = char>> = if ignore_diacritics {
+        Box::new(text.nfkd().filter(|c| !is_combining_mark(*c)))
+    } else {
+        Box::new(text.nfkc())
+    };
+    for c in normalized.flat_map(char::to_lowercase) {
+        match c {
+            'ß' => folded.push_str("ss"),
+            'ς' => folded.push('σ'),
+            'ſ' => folded.push('s'),
+            // Lowercasing İ leaves a combining dot behind
+            '\u{307}' if ignore_diacritics => {}
+            'ø' if ignore_diacritics => folded.push('o'),
+            'ł' if ignore_diacritics => folded.push('l'),
+            'đ' if ignore_diacritics => folded.push('d'),
+            'æ' if ignore_diacritics => folded.push_str("ae"),
+            'œ' if ignore_diacritics => folded.push_str("oe"),
+            _ => folded.push(c),
+        }
+    }
+    folded
+}
+
 /// Renders a branch name from a template by replacing `{task_id}` with the given task id.
 /// If the template has multiple placeholders, all are replaced. If there is no placeholder,
 /// the template is returned unchanged.
 pub fn render_branch_name(template: &str, task_id: &str) -> String {
     template.replace("{task_id}", task_id)
 }
+
+/// Strips paired inline Markdown emphasis (`**x**`, `*x*`, `_x_`, `~~x~~`) and
+/// backtick code spans so titles render as plain text. A marker without a
+/// partner (`a*b`, `_private`) is kept, as are underscores inside words
+/// (`snake_case`) and runs of them (`__init__`). Hashtags (`#123`) and emoji are kept.
+pub fn strip_markdown(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::with_capacity(s.len());
+    strip_emphasis(&chars, &mut out);
+    out
+}
+
+fn strip_emphasis(chars: &[char], out: &mut String) {
+    let mut i = 0;
+    while i < chars.len() {
+        match emphasis_at(chars, i) {
+            Some((len, close)) => {
+                let inner = &chars[i + len..close];
+                if chars[i] == '`' {
+                    out.extend(inner);
+                } else {
+                    strip_emphasis(inner, out);
+                }
+                i = close + len;
+            }
+            None => {
+                out.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+}
+
+/// The marker length and the closing marker's position of an emphasis or
+/// code span opening at `i`, if one does
+fn emphasis_at(chars: &[char], i: usize) -> Option<(usize, usize)> {
+    let c = chars[i];
+    if i > 0 && chars[i - 1] == c {
+        return None;
+    }
+    let run = chars[i..].iter().take_while(|&&x| x == c).count();
+    let len = match c {
+        '*' => run.min(2),
+        '~' if run >= 2 => 2,
+        '_' | '`' if run == 1 => 1,
+        _ => return None,
+    };
+    let in_word = |at: Option<&char>| at.is_some_and(|x| x.is_alphanumeric());
+    if c == '_' && i > 0 && in_word(chars.get(i - 1)) {
+        return None;
+    }
+    if chars.get(i + len).is_none_or(|x| x.is_whitespace()) {
+        return None;
+    }
+    (i + len + 1..=chars.len().saturating_sub(len)).find(|&j| {
+        chars[j..j + len].iter().all(|&x| x == c)
+            && chars[j - 1] != c
+            && !chars[j - 1].is_whitespace()
+            && chars.get(j + len) != Some(&c)
+            && !(c == '_' && in_word(chars.get(j + len)))
+    })
+    .map(|close| (len, close))
+}
+
+/// Opens `url` in the default browser with the platform's opener
+pub fn open_in_browser(url: &str) -> std::io::Result<()> {
+    use std::process::{Command, Stdio};
+
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    // The opener's chatter would land on top of the TUI
+    let status = command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!("browser opener exited with {}", status)))
+    }
+}
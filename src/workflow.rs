@@ -0,0 +1,593 @@
+//! Pick planning: decides what to cherry-pick for a PR given what the local
+//! clone actually contains. Free of UI and network code so it can be tested.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio::task::JoinSet;
+
+use crate::git::{CommitHeader, ConflictSignature};
+use crate::github::{PrInfo, PrState};
+
+/// What can be cherry-picked for a PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PickSource {
+    /// The PR's own commits are present locally
+    Commits,
+    /// The PR's commits are gone (e.g. the head branch was deleted after a rebase
+    /// and the objects garbage-collected) but it was merged, so its merge or
+    /// squash commit can be picked instead
+    MergeCommit { sha: String },
+    /// The commits are gone and there is no merge commit to fall back to
+    Unpickable,
+}
+
+/// True when every commit the pick needs exists locally. PRs without a
+/// commit list fall back to checking the head sha.
+pub fn commits_available(pr: &PrInfo, commit_exists: &dyn Fn(&str) -> bool) -> bool {
+    if pr.commits.is_empty() {
+        commit_exists(&pr.head_sha)
+    } else {
+        pr.commits.iter().all(|c| commit_exists(&c.sha))
+    }
+}
+
+/// Classifies `pr`. If commits are missing, `fetch` runs once (head branch,
+/// `refs/pull/N/head`, ...) before deciding they are really gone.
+pub fn resolve_pick_source(
+    pr: &PrInfo,
+    commit_exists: impl Fn(&str) -> bool,
+    fetch: impl FnOnce(),
+) -> PickSource {
+    if commits_available(pr, &commit_exists) {
+        return PickSource::Commits;
+    }
+
+    fetch();
+    if commits_available(pr, &commit_exists) {
+        return PickSource::Commits;
+    }
+
+    match &pr.merge_commit_sha {
+        Some(sha) if commit_exists(sha) => PickSource::MergeCommit { sha: sha.clone() },
+        _ => PickSource::Unpickable,
+    }
+}
+
+/// How a merged PR landed on its base branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStyle {
+    /// A merge commit whose second parent is the PR's head
+    Merge,
+    /// One new commit holding the whole PR
+    Squash,
+    /// The PR's commits copied onto the base one by one; the merge commit
+    /// is only the copy of the last of them
+    Rebase,
+}
+
+/// Judges from the local merge commit how `pr` was merged. A rebase keeps
+/// the last commit's message and author date; a squash writes a new message.
+/// With a single commit the two land the same change, so that counts as a squash.
+pub fn merge_style(pr: &PrInfo, merge_commit: &CommitHeader) -> MergeStyle {
+    if merge_commit.parent_count > 1 {
+        return MergeStyle::Merge;
+    }
+    match pr.commits.last() {
+        Some(last)
+            if pr.commits.len() > 1
+                && last.message.trim() == merge_commit.message.trim()
+                && last.date == merge_commit.authored_at =>
+        {
+            MergeStyle::Rebase
+        }
+        _ => MergeStyle::Squash,
+    }
+}
+
+/// The commit to pick for `pr` in place of its own commits: its merge or
+/// squash commit, which is the change that actually landed. `None` while the
+/// PR isn't merged, when the merge commit isn't in the local clone
+/// (`header` gives `None`), or after a rebase merge, where the merge commit
+/// carries only the last of the PR's commits.
+pub fn landed_commit(pr: &PrInfo, header: impl Fn(&str) -> Option<CommitHeader>) -> Option<String> {
+    if pr.state != PrState::Merged {
+        return None;
+    }
+    let sha = pr.merge_commit_sha.as_ref()?;
+    match merge_style(pr, &header(sha)?) {
+        MergeStyle::Merge | MergeStyle::Squash => Some(sha.clone()),
+        MergeStyle::Rebase => None,
+    }
+}
+
+/// Conflicts met during this session, to notice when the same one comes back
+#[derive(Debug, Default)]
+pub struct ConflictHistory {
+    seen: HashMap<ConflictSignature, u32>,
+}
+
+impl ConflictHistory {
+    /// Records a conflict and returns how many times it has now been seen
+    pub fn record(&mut self, signature: ConflictSignature) -> u32 {
+        let count = self.seen.entry(signature).or_default();
+        *count += 1;
+        *count
+    }
+}
+
+/// One target branch's share of a pick onto several, run in its own worktree
+/// so targets don't share an index or working tree
+pub trait TargetPick: Send + Sync + 'static {
+    type Outcome: Send + 'static;
+
+    /// Picks onto `target`. Runs on a blocking thread.
+    fn pick(&self, target: &str) -> Self::Outcome;
+
+    /// Removes the worktree `target`'s finished pick used, if the pick left
+    /// one behind
+    fn clean_up(&self, target: &str);
+}
+
+/// What became of one target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetOutcome<T> {
+    Finished(T),
+    /// Cancelled before it started
+    Cancelled,
+}
+
+/// Set from the UI to stop a pick from starting more targets (Esc or a quit
+/// key) or more commits (a quit key)
+#[derive(Debug, Clone, Default)]
+pub struct CancelFlag(Arc<AtomicBool>);
+
+impl CancelFlag {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Picks onto each of `targets`, at most `max_parallel` at once, calling
+/// `on_finished` as each completes. Once `cancel` is set no further target
+/// starts; those already running finish, and every finished target gets a
+/// [`TargetPick::clean_up`]. Outcomes come back in the order of `targets`.
+pub async fn pick_targets<P: TargetPick>(
+    picker: Arc<P>,
+    targets: &[String],
+    max_parallel: usize,
+    cancel: &CancelFlag,
+    mut on_finished: impl FnMut(&str, &P::Outcome),
+) -> Vec<TargetOutcome<P::Outcome>> {
+    let mut outcomes: Vec<TargetOutcome<P::Outcome>> =
+        targets.iter().map(|_| TargetOutcome::Cancelled).collect();
+    let mut queued = targets.iter().cloned().enumerate();
+    let mut running = JoinSet::new();
+
+    loop {
+        while running.len() < max_parallel.max(1) && !cancel.is_cancelled() {
+            let Some((i, target)) = queued.next() else {
+                break;
+            };
+            let picker = Arc::clone(&picker);
+            running.spawn_blocking(move || (i, picker.pick(&target)));
+        }
+        let Some(joined) = running.join_next().await else {
+            break;
+        };
+        let (i, outcome) = match joined {
+            Ok(done) => done,
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        };
+        on_finished(&targets[i], &outcome);
+        outcomes[i] = TargetOutcome::Finished(outcome);
+    }
+
+    if cancel.is_cancelled() {
+        for (target, outcome) in targets.iter().zip(&outcomes) {
+            if matches!(outcome, TargetOutcome::Finished(_)) {
+                picker.clean_up(target);
+            }
+        }
+    }
+    outcomes
+}
+
+/// Where a backport branch name is already taken
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchPresence {
+    pub local: bool,
+    /// On `origin`
+    pub remote: bool,
+}
+
+impl BranchPresence {
+    pub fn exists(self) -> bool {
+        self.local || self.remote
+    }
+}
+
+/// What to do without asking when the branch already exists (`--on-existing-branch`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExistingBranchPolicy {
+    Reuse,
+    Recreate,
+    Fail,
+}
+
+impl FromStr for ExistingBranchPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "reuse" => Ok(ExistingBranchPolicy::Reuse),
+            "recreate" => Ok(ExistingBranchPolicy::Recreate),
+            "fail" => Ok(ExistingBranchPolicy::Fail),
+            other => Err(format!("expected `reuse`, `recreate` or `fail`, got `{}`", other)),
+        }
+    }
+}
+
+/// The three answers to "this branch already exists"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExistingBranchChoice {
+    /// Check it out and append the picks
+    Reuse,
+    /// Delete it and start over from the target branch, force-pushing later
+    Recreate,
+    /// Use another name instead
+    Rename(String),
+}
+
+impl ExistingBranchPolicy {
+    /// The choice the policy makes; `None` for `fail`
+    pub fn choice(self) -> Option<ExistingBranchChoice> {
+        match self {
+            ExistingBranchPolicy::Reuse => Some(ExistingBranchChoice::Reuse),
+            ExistingBranchPolicy::Recreate => Some(ExistingBranchChoice::Recreate),
+            ExistingBranchPolicy::Fail => None,
+        }
+    }
+}
+
+/// How to arrive at the branch the picks go on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BranchPlan {
+    /// The name is free
+    Create { name: String },
+    /// Continue the existing branch; `from_remote` when only `origin` has it,
+    /// so the local branch must first be created tracking it
+    Reuse { name: String, from_remote: bool },
+    /// Start over: delete the local branch if there is one, and force-push
+    /// when `origin` has one, so the push can't be rejected as non-fast-forward
+    Recreate {
+        name: String,
+        delete_local: bool,
+        force_push: bool,
+    },
+}
+
+impl BranchPlan {
+    /// The branch the picks go on
+    pub fn name(&self) -> &str {
+        match self {
+            BranchPlan::Create { name } | BranchPlan::Reuse { name, .. } | BranchPlan::Recreate { name, .. } => name,
+        }
+    }
+}
+
+/// The plan for `name`, given where it already exists and, if it does, what
+/// the user or `--on-existing-branch` chose. A rename is planned against
+/// `rename_presence`, the new name's own presence.
+pub fn plan_branch(
+    name: &str,
+    presence: BranchPresence,
+    choice: Option<&ExistingBranchChoice>,
+    rename_presence: impl Fn(&str) -> BranchPresence,
+) -> Option<BranchPlan> {
+    if !presence.exists() {
+        return Some(BranchPlan::Create { name: name.to_string() });
+    }
+    match choice? {
+        ExistingBranchChoice::Reuse => Some(BranchPlan::Reuse {
+            name: name.to_string(),
+            from_remote: !presence.local,
+        }),
+        ExistingBranchChoice::Recreate => Some(BranchPlan::Recreate {
+            name: name.to_string(),
+            delete_local: presence.local,
+            force_push: presence.remote,
+        }),
+        // A taken new name asks again rather than stacking choices
+        ExistingBranchChoice::Rename(other) => {
+            let other_presence = rename_presence(other);
+            (!other_presence.exists()).then(|| BranchPlan::Create { name: other.clone() })
+        }
+    }
+}
+
+/// The rename to pre-fill: `name-2`, or the next free `-N`
+pub fn suggested_branch_name(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    (2..)
+        .map(|n| format!("{}-{}", name, n))
+        .find(|candidate| !taken(candidate))
+        .expect("some suffix is free")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::CommitInfo;
+    use chrono::{TimeZone, Utc};
+    use std::cell::Cell;
+
+    fn pr(commits: &[&str], merge_commit_sha: Option<&str>) -> PrInfo {
+        PrInfo {
+            number: 42,
+            title: "Fix".into(),
+            author: "alice".into(),
+            commits: commits
+                .iter()
+                .map(|sha| CommitInfo {
+                    sha: sha.to_string(),
+                    message: "change".into(),
+                    author: "alice".into(),
+                    date: Utc::now(),
+                })
+                .collect(),
+            head_sha: commits.last().unwrap_or(&"head").to_string(),
+            base_ref: "main".into(),
+            head_ref: "feature".into(),
+            merge_commit_sha: merge_commit_sha.map(String::from),
+            state: if merge_commit_sha.is_some() { PrState::Merged } else { PrState::Open },
+            ..Default::default()
+        }
+    }
+
+    fn merged_with(parent_count: usize, message: &str) -> CommitHeader {
+        CommitHeader {
+            parent_count,
+            message: message.into(),
+            authored_at: Utc.with_ymd_and_hms(2025, 3, 4, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn merged_prs_pick_their_merge_or_squash_commit() {
+        let mut merged = pr(&["a", "b"], Some("m"));
+        merged.commits[1].date = Utc.with_ymd_and_hms(2025, 3, 4, 12, 0, 0).unwrap();
+
+        let squash = merged_with(1, "Fix (#42)\n\n* change\n* change");
+        assert_eq!(merge_style(&merged, &squash), MergeStyle::Squash);
+        assert_eq!(landed_commit(&merged, |_| Some(squash.clone())).as_deref(), Some("m"));
+
+        let merge = merged_with(2, "Merge pull request #42 from alice/feature");
+        assert_eq!(merge_style(&merged, &merge), MergeStyle::Merge);
+        assert_eq!(landed_commit(&merged, |_| Some(merge.clone())).as_deref(), Some("m"));
+
+        // Not fetched yet
+        assert_eq!(landed_commit(&merged, |_| None), None);
+        // Not merged
+        assert_eq!(landed_commit(&pr(&["a", "b"], None), |_| Some(squash.clone())), None);
+    }
+
+    #[test]
+    fn rebase_merges_pick_the_prs_own_commits() {
+        let mut merged = pr(&["a", "b"], Some("m"));
+        merged.commits[1].date = Utc.with_ymd_and_hms(2025, 3, 4, 12, 0, 0).unwrap();
+        let rebased = merged_with(1, "change\n");
+        assert_eq!(merge_style(&merged, &rebased), MergeStyle::Rebase);
+        assert_eq!(landed_commit(&merged, |_| Some(rebased.clone())), None);
+
+        // A single rebased commit is the whole PR
+        let mut single = pr(&["a"], Some("m"));
+        single.commits[0].date = rebased.authored_at;
+        assert_eq!(landed_commit(&single, |_| Some(rebased.clone())).as_deref(), Some("m"));
+    }
+
+    #[test]
+    fn present_commits_are_picked_without_fetching() {
+        let fetched = Cell::new(false);
+        let source = resolve_pick_source(&pr(&["a", "b"], None), |_| true, || fetched.set(true));
+        assert_eq!(source, PickSource::Commits);
+        assert!(!fetched.get());
+    }
+
+    #[test]
+    fn fetch_recovers_missing_commits() {
+        let fetched = Cell::new(false);
+        let source = resolve_pick_source(
+            &pr(&["a", "b"], Some("m")),
+            |sha| fetched.get() || sha == "a",
+            || fetched.set(true),
+        );
+        assert_eq!(source, PickSource::Commits);
+        assert!(fetched.get());
+    }
+
+    #[test]
+    fn merged_pr_with_gone_commits_falls_back_to_merge_commit() {
+        let source = resolve_pick_source(&pr(&["a", "b"], Some("m")), |sha| sha == "m", || {});
+        assert_eq!(source, PickSource::MergeCommit { sha: "m".into() });
+    }
+
+    #[test]
+    fn gone_commits_without_usable_merge_commit_are_unpickable() {
+        // Never merged
+        assert_eq!(
+            resolve_pick_source(&pr(&["a"], None), |_| false, || {}),
+            PickSource::Unpickable
+        );
+        // Merged, but the merge commit isn't in this clone either
+        assert_eq!(
+            resolve_pick_source(&pr(&["a"], Some("m")), |_| false, || {}),
+            PickSource::Unpickable
+        );
+    }
+
+    #[test]
+    fn conflict_history_counts_repeats_per_signature() {
+        let sig = |text: &str| {
+            let conflict = format!("<<<<<<< HEAD\n{}\n=======\nother\n>>>>>>> pick\n", text);
+            crate::git::conflict_signature(&[("a.txt".into(), conflict)]).unwrap()
+        };
+        let mut history = ConflictHistory::default();
+        assert_eq!(history.record(sig("one")), 1);
+        assert_eq!(history.record(sig("two")), 1);
+        assert_eq!(history.record(sig("one")), 2);
+    }
+
+    #[test]
+    fn prs_without_commit_list_check_the_head_sha() {
+        let pr = pr(&[], None);
+        assert!(commits_available(&pr, &|sha| sha == "head"));
+        assert!(!commits_available(&pr, &|_| false));
+    }
+
+    /// Takes `delay` per pick, tracking how many run at once. Cancels
+    /// `cancel_on` when its pick starts.
+    #[derive(Default)]
+    struct SlowPicker {
+        delay: std::time::Duration,
+        running: std::sync::atomic::AtomicUsize,
+        most_at_once: std::sync::atomic::AtomicUsize,
+        cancel_on: Option<(String, CancelFlag)>,
+        cleaned: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl TargetPick for SlowPicker {
+        type Outcome = String;
+
+        fn pick(&self, target: &str) -> String {
+            let now = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.most_at_once.fetch_max(now, Ordering::SeqCst);
+            if let Some((on, flag)) = &self.cancel_on {
+                if on == target {
+                    flag.cancel();
+                }
+            }
+            std::thread::sleep(self.delay);
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            format!("picked onto {}", target)
+        }
+
+        fn clean_up(&self, target: &str) {
+            self.cleaned.lock().unwrap().push(target.to_string());
+        }
+    }
+
+    fn targets(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn targets_run_at_most_max_parallel_at_once() {
+        let picker = Arc::new(SlowPicker {
+            delay: std::time::Duration::from_millis(50),
+            ..Default::default()
+        });
+        let branches = targets(&["release/1", "release/2", "release/3", "release/4"]);
+        let mut finished = Vec::new();
+
+        let outcomes = pick_targets(
+            Arc::clone(&picker),
+            &branches,
+            2,
+            &CancelFlag::default(),
+            |target, _| finished.push(target.to_string()),
+        )
+        .await;
+
+        assert_eq!(picker.most_at_once.load(Ordering::SeqCst), 2);
+        assert_eq!(finished.len(), 4);
+        assert_eq!(outcomes[2], TargetOutcome::Finished("picked onto release/3".into()));
+        assert!(picker.cleaned.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn cancelling_skips_unstarted_targets_and_cleans_up_finished_ones() {
+        let cancel = CancelFlag::default();
+        let picker = Arc::new(SlowPicker {
+            delay: std::time::Duration::from_millis(20),
+            cancel_on: Some(("release/2".into(), cancel.clone())),
+            ..Default::default()
+        });
+        let branches = targets(&["release/1", "release/2", "release/3", "release/4"]);
+
+        let outcomes = pick_targets(Arc::clone(&picker), &branches, 1, &cancel, |_, _| {}).await;
+
+        assert_eq!(
+            outcomes,
+            [
+                TargetOutcome::Finished("picked onto release/1".to_string()),
+                TargetOutcome::Finished("picked onto release/2".to_string()),
+                TargetOutcome::Cancelled,
+                TargetOutcome::Cancelled,
+            ]
+        );
+        assert_eq!(*picker.cleaned.lock().unwrap(), ["release/1", "release/2"]);
+    }
+
+    const LOCAL: BranchPresence = BranchPresence { local: true, remote: false };
+    const REMOTE: BranchPresence = BranchPresence { local: false, remote: true };
+    const BOTH: BranchPresence = BranchPresence { local: true, remote: true };
+
+    #[test]
+    fn a_free_name_is_created_whatever_the_choice() {
+        let plan = plan_branch("cp/PAY-123", BranchPresence::default(), None, |_| BOTH);
+        assert_eq!(plan, Some(BranchPlan::Create { name: "cp/PAY-123".into() }));
+    }
+
+    #[test]
+    fn reusing_tracks_the_remote_when_only_it_has_the_branch() {
+        let reuse = Some(&ExistingBranchChoice::Reuse);
+        let from_remote = |presence| match plan_branch("cp/PAY-123", presence, reuse, |_| BOTH) {
+            Some(BranchPlan::Reuse { from_remote, .. }) => from_remote,
+            other => panic!("expected reuse, got {:?}", other),
+        };
+        assert!(!from_remote(LOCAL));
+        assert!(from_remote(REMOTE));
+        assert!(!from_remote(BOTH));
+    }
+
+    #[test]
+    fn recreating_deletes_and_force_pushes_only_what_exists() {
+        let recreate = Some(&ExistingBranchChoice::Recreate);
+        let plan = |presence| plan_branch("cp/PAY-123", presence, recreate, |_| BOTH).unwrap();
+        let expected = |delete_local, force_push| BranchPlan::Recreate {
+            name: "cp/PAY-123".into(),
+            delete_local,
+            force_push,
+        };
+        assert_eq!(plan(LOCAL), expected(true, false));
+        assert_eq!(plan(REMOTE), expected(false, true));
+        assert_eq!(plan(BOTH), expected(true, true));
+    }
+
+    #[test]
+    fn renames_must_be_free_and_fail_decides_nothing() {
+        let taken = |name: &str| name == "cp/PAY-123" || name == "cp/PAY-123-2";
+        let presence = |name: &str| BranchPresence {
+            local: false,
+            remote: taken(name),
+        };
+        assert_eq!(suggested_branch_name("cp/PAY-123", taken), "cp/PAY-123-3");
+
+        let rename = |to: &str| ExistingBranchChoice::Rename(to.into());
+        assert_eq!(
+            plan_branch("cp/PAY-123", REMOTE, Some(&rename("cp/PAY-123-3")), presence),
+            Some(BranchPlan::Create { name: "cp/PAY-123-3".into() })
+        );
+        assert_eq!(plan_branch("cp/PAY-123", REMOTE, Some(&rename("cp/PAY-123-2")), presence), None);
+
+        let fail = "fail".parse::<ExistingBranchPolicy>().unwrap();
+        assert_eq!(plan_branch("cp/PAY-123", LOCAL, fail.choice().as_ref(), presence), None);
+        assert!("overwrite".parse::<ExistingBranchPolicy>().is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use gh_cherry::config::Config;
+
+#[test]
+fn minimal_toml_fills_missing_fields_with_defaults() {
+    let toml = r#"
+[github]
+owner = "org"
+repo = "repo"
+"#;
+    let cfg: Config = toml::from_str(toml).expect("minimal config parses");
+    let defaults = Config::default();
+
+    assert_eq!(cfg.github.owner, "org");
+    assert_eq!(cfg.github.repo, "repo");
+    assert_eq!(cfg.github.base_branch, defaults.github.base_branch);
+    assert_eq!(cfg.github.branch_name_template, defaults.github.branch_name_template);
+    assert_eq!(cfg.tags.pending_tag, defaults.tags.pending_tag);
+    assert_eq!(cfg.tags.sprint_pattern, defaults.tags.sprint_pattern);
+    assert_eq!(cfg.ui.days_back, defaults.ui.days_back);
+    assert_eq!(cfg.ui.page_size, defaults.ui.page_size);
+    assert!(!cfg.ui.only_forked_repos);
+}
+
+#[test]
+fn empty_toml_parses_to_defaults() {
+    let cfg: Config = toml::from_str("").expect("empty config parses");
+    assert!(cfg.github.owner.is_empty());
+    assert_eq!(cfg.tags.environment, Config::default().tags.environment);
+}
+
+#[test]
+fn backfill_replaces_blank_values_but_keeps_discovery_fields_empty() {
+    let toml = r#"
+[github]
+owner = ""
+repo = ""
+target_branch = ""
+
+[tags]
+pending_tag = "  "
+
+[ui]
+page_size = 0
+"#;
+    let cfg = toml::from_str::<Config>(toml)
+        .expect("config parses")
+        .with_defaults_for_missing_fields();
+    let defaults = Config::default();
+
+    assert!(cfg.github.owner.is_empty());
+    assert!(cfg.github.repo.is_empty());
+    assert_eq!(cfg.github.target_branch, defaults.github.target_branch);
+    assert_eq!(cfg.tags.pending_tag, defaults.tags.pending_tag);
+    assert_eq!(cfg.ui.page_size, defaults.ui.page_size);
+}
+
+#[test]
+fn invalid_sprint_pattern_is_reported_as_config_error() {
+    use gh_cherry::error::ConfigError;
+
+    let mut cfg = Config::default();
+    assert!(cfg.validate().is_ok());
+
+    cfg.tags.sprint_pattern = "S(".into();
+    match cfg.validate() {
+        Err(ConfigError::Invalid { field, .. }) => assert_eq!(field, "tags.sprint_pattern"),
+        other => panic!("expected ConfigError::Invalid, got {:?}", other),
+    }
+}
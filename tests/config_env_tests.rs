@@ -1,4 +1,7 @@
 use std::fs;
+use std::path::PathBuf;
+
+use gh_cherry::config::{Config, ConfigSource};
 
 #[test]
 fn loads_env_overrides_from_cherry_env() {
@@ -14,13 +17,15 @@ CHERRY_PICK_SOURCE_BRANCH="main"
 BRANCH_NAME_TEMPLATE="ch/{task_id}"
 ONLY_FORKED_REPOS=true
 DAYS_BACK=14
+SPRINT_FILTER_MODE=any
+AUTO_PUSH=true
 "#).unwrap();
 
     // Change CWD for this test
     let prev = std::env::current_dir().unwrap();
     std::env::set_current_dir(dir).unwrap();
 
-    let cfg = gh_cherry::config::Config::load(None).expect("config load");
+    let cfg = gh_cherry::config::Config::load(None, None).expect("config load");
 
     // restore CWD
     std::env::set_current_dir(prev).unwrap();
@@ -33,4 +38,123 @@ DAYS_BACK=14
     assert_eq!(cfg.github.branch_name_template, "ch/{task_id}");
     assert!(cfg.ui.only_forked_repos);
     assert_eq!(cfg.ui.days_back, 14);
+    assert!(cfg.ui.auto_push);
+    assert_eq!(
+        cfg.tags.sprint_filter_mode,
+        gh_cherry::config::LabelFilterMode::Any
+    );
+}
+
+#[test]
+fn repo_path_flag_beats_cherry_env_which_beats_discovery() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    fs::write(dir.join("cherry.env"), "REPO_PATH=/srv/repos/widgets\n").unwrap();
+
+    // With a project directory, its cherry.env is read rather than the current directory's
+    let from_env = Config::load(None, Some(dir)).expect("config load");
+    assert_eq!(from_env.github.repo_path, Some(PathBuf::from("/srv/repos/widgets")));
+    assert_eq!(from_env.source_of("github.repo_path"), ConfigSource::ProjectEnv);
+
+    let kept = from_env.clone().with_repo_path(None);
+    assert_eq!(kept.github.repo_path, Some(PathBuf::from("/srv/repos/widgets")));
+
+    let flagged = from_env.with_repo_path(Some(dir.to_path_buf()));
+    assert_eq!(flagged.github.repo_path.as_deref(), Some(dir));
+    assert_eq!(flagged.source_of("github.repo_path"), ConfigSource::CommandLine);
+
+    // Neither set: the repository is discovered from the current directory
+    assert_eq!(Config::default().with_repo_path(None).github.repo_path, None);
+}
+
+#[test]
+fn repo_path_must_be_an_existing_git_repository() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let mut config = Config::default();
+
+    config.github.repo_path = Some(temp.path().join("missing"));
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("is not a directory"), "{}", err);
+
+    config.github.repo_path = Some(temp.path().to_path_buf());
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("is not a git repository"), "{}", err);
+
+    git2::Repository::init(temp.path()).unwrap();
+    config.validate().expect("a git repository is accepted");
+}
+
+#[test]
+fn target_branches_come_from_cherry_env_or_repeated_flags() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    fs::write(dir.join("cherry.env"), "TARGET_BRANCHES=\"release/1.1, release/1.2,release/1.3\"\n").unwrap();
+
+    let from_env = Config::load(None, Some(dir)).expect("config load");
+    assert_eq!(from_env.github.target_branches, ["release/1.1", "release/1.2", "release/1.3"]);
+    assert_eq!(from_env.github.pick_targets(), from_env.github.target_branches);
+    // The first names the target until the picks move on
+    assert_eq!(from_env.github.target_branch, "release/1.1");
+    assert_eq!(from_env.source_of("github.target_branches"), ConfigSource::ProjectEnv);
+
+    let flagged = from_env
+        .clone()
+        .with_overrides(None, None, None, vec!["hotfix/a".into(), "hotfix/b".into()], None, None, None);
+    assert_eq!(flagged.github.pick_targets(), ["hotfix/a", "hotfix/b"]);
+    assert_eq!(flagged.source_of("github.target_branches"), ConfigSource::CommandLine);
+
+    // One flagged branch replaces the list
+    let single = from_env.with_overrides(None, None, None, vec!["hotfix/a".into()], None, None, None);
+    assert_eq!(single.github.pick_targets(), ["hotfix/a"]);
+
+    let mut config = Config::default();
+    config.github.target_branches = vec!["release/1.1".into(), " ".into()];
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("github.target_branches"), "{}", err);
+}
+
+#[test]
+fn api_url_flag_beats_cherry_env_and_must_be_an_http_url() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    fs::write(dir.join("cherry.env"), "GITHUB_API_URL=https://ghe.example.com/api/v3\n").unwrap();
+
+    let from_env = Config::load(None, Some(dir)).expect("config load");
+    assert_eq!(from_env.github.api_base_url.as_deref(), Some("https://ghe.example.com/api/v3"));
+    assert_eq!(from_env.source_of("github.api_base_url"), ConfigSource::ProjectEnv);
+    let url = from_env.github.api_url().unwrap().expect("an Enterprise URL");
+    assert_eq!(url.host_str(), Some("ghe.example.com"));
+
+    let flagged = from_env.with_api_url(Some("http://ghe.internal/api/v3".into()));
+    assert_eq!(flagged.github.api_base_url.as_deref(), Some("http://ghe.internal/api/v3"));
+    assert_eq!(flagged.source_of("github.api_base_url"), ConfigSource::CommandLine);
+
+    // Unset means github.com
+    assert_eq!(Config::default().github.api_url().unwrap(), None);
+
+    let mut config = Config::default();
+    for malformed in ["ghe.example.com/api/v3", "ftp://ghe.example.com", "https://"] {
+        config.github.api_base_url = Some(malformed.into());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("github.api_base_url"), "{}: {}", malformed, err);
+    }
+}
+
+#[test]
+fn repo_flag_may_name_the_owner_too() {
+    let config = Config::default().with_overrides(
+        Some("someone-else".into()),
+        Some("acme/widgets".into()),
+        None,
+        Vec::new(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!((config.github.owner.as_str(), config.github.repo.as_str()), ("acme", "widgets"));
+    assert_eq!(config.source_of("github.owner"), ConfigSource::CommandLine);
+    assert!(!config.needs_auto_discovery());
+
+    let bare = Config::default().with_overrides(None, Some("widgets".into()), None, Vec::new(), None, None, None);
+    assert_eq!(bare.github.repo, "widgets");
 }
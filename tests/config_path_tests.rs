@@ -0,0 +1,44 @@
+//! `CHERRY_CONFIG` is process-wide, so these tests get a binary of their own
+//! and run one after another from a single test
+
+use std::fs;
+
+use gh_cherry::config::{Config, CONFIG_PATH_VAR};
+
+#[test]
+fn config_path_prefers_the_flag_then_cherry_config_then_the_default() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let from_env = temp.path().join("env.toml");
+    fs::write(&from_env, "[ui]\ndays_back = 3\n").unwrap();
+    let from_flag = temp.path().join("flag.toml");
+    fs::write(&from_flag, "[ui]\ndays_back = 5\n").unwrap();
+
+    std::env::remove_var(CONFIG_PATH_VAR);
+    let default = Config::resolve_config_path(None).unwrap();
+    assert!(default.ends_with("gh_cherry/config.toml"), "{}", default.display());
+
+    std::env::set_var(CONFIG_PATH_VAR, &from_env);
+    assert_eq!(Config::resolve_config_path(None).unwrap(), from_env);
+    assert_eq!(
+        Config::resolve_config_path(from_flag.to_str()).unwrap(),
+        from_flag
+    );
+    assert_eq!(Config::load(None, Some(temp.path())).unwrap().ui.days_back, 3);
+    assert_eq!(
+        Config::load(from_flag.to_str(), Some(temp.path())).unwrap().ui.days_back,
+        5
+    );
+
+    // A named file that isn't there is an error, not a silent fallback to defaults
+    let missing = temp.path().join("missing.toml");
+    let err = Config::load(missing.to_str(), Some(temp.path())).unwrap_err();
+    assert!(err.to_string().contains("from --config"), "{}", err);
+    std::env::set_var(CONFIG_PATH_VAR, &missing);
+    let err = Config::resolve_config_path(None).unwrap_err();
+    assert!(err.to_string().contains("from CHERRY_CONFIG"), "{}", err);
+
+    // Set but empty counts as unset
+    std::env::set_var(CONFIG_PATH_VAR, "");
+    assert_eq!(Config::resolve_config_path(None).unwrap(), default);
+    std::env::remove_var(CONFIG_PATH_VAR);
+}
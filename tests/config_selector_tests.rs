@@ -0,0 +1,27 @@
+use gh_cherry::ui::config_selector::{ConfigChoice, ConfigSelectorApp};
+
+fn choices(env_exists: bool) -> Vec<ConfigChoice> {
+    ConfigSelectorApp::options_for(env_exists)
+        .into_iter()
+        .map(|o| o.choice)
+        .collect()
+}
+
+#[test]
+fn existing_env_file_keeps_original_options() {
+    assert_eq!(
+        choices(true),
+        vec![
+            ConfigChoice::LoadFromEnv,
+            ConfigChoice::UseDefaults,
+            ConfigChoice::UseGlobalConfig
+        ]
+    );
+}
+
+#[test]
+fn missing_env_file_offers_to_create_one() {
+    let choices = choices(false);
+    assert!(choices.contains(&ConfigChoice::CreateNew));
+    assert!(!choices.contains(&ConfigChoice::LoadFromEnv));
+}
@@ -0,0 +1,64 @@
+use gh_cherry::config::{Config, GitEngine, LabelFilterMode};
+
+#[test]
+fn equal_configs_have_equal_snapshots() {
+    let a = Config::default();
+    let b = Config::default();
+    assert_eq!(a.snapshot(), b.snapshot());
+
+    let mut c = Config::default();
+    c.github.owner = "acme".into();
+    c.ui.assignees = vec!["alice".into()];
+    assert_eq!(c.snapshot(), c.clone().snapshot());
+}
+
+#[test]
+fn any_changed_field_changes_the_snapshot() {
+    let base = Config::default();
+    let changes: Vec<fn(&mut Config)> = vec![
+        |c| c.github.target_branch = "release".into(),
+        |c| c.github.target_branches.push("release/1.2".into()),
+        |c| c.github.source_owner = Some("upstream".into()),
+        |c| c.github.git_engine = GitEngine::Cli,
+        |c| c.tags.pending_tag = "backport".into(),
+        |c| c.tags.sprint_filter_mode = LabelFilterMode::Any,
+        |c| c.ui.days_back += 1,
+        |c| c.ui.assignees.push("bob".into()),
+        |c| c.storage.retention_days += 1,
+        |c| c.network.max_retries += 1,
+    ];
+
+    for change in changes {
+        let mut changed = base.clone();
+        change(&mut changed);
+        assert_ne!(base.snapshot(), changed.snapshot(), "{:?}", changed);
+    }
+}
+
+#[test]
+fn reloading_an_edited_env_file_keeps_flags_and_the_repository() {
+    let dir = tempfile::tempdir().unwrap();
+    let env = dir.path().join("cherry.env");
+    std::fs::write(&env, "GITHUB_OWNER=acme\nGITHUB_REPO=app\nDAYS_BACK=7\n").unwrap();
+    let mut started = Config::default();
+    started.github.owner = "acme".into();
+    started.github.repo = "app".into();
+    let config = started
+        .with_overrides(None, None, Some("develop".into()), Vec::new(), None, None, None)
+        .reload_env(Some(dir.path()))
+        .unwrap()
+        .expect("DAYS_BACK differs from the default");
+    assert_eq!(config.ui.days_back, 7);
+    assert_eq!(config.reload_env(Some(dir.path())).unwrap().map(|c| c.snapshot()), None);
+
+    std::fs::write(
+        &env,
+        "GITHUB_OWNER=other\nGITHUB_REPO=app\nDAYS_BACK=14\nBASE_BRANCH=main\n",
+    )
+    .unwrap();
+    let reloaded = config.reload_env(Some(dir.path())).unwrap().expect("DAYS_BACK changed");
+    assert_eq!(reloaded.ui.days_back, 14);
+    assert_eq!(reloaded.github.base_branch, "develop");
+    assert_eq!(reloaded.github.owner, "acme");
+    assert_eq!(reloaded.env_warnings.len(), 1, "{:?}", reloaded.env_warnings);
+}
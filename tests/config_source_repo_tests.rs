@@ -0,0 +1,31 @@
+use gh_cherry::config::Config;
+
+fn fork_config() -> Config {
+    let mut cfg = Config::default();
+    cfg.github.owner = "me".into();
+    cfg.github.repo = "tool".into();
+    cfg
+}
+
+#[test]
+fn no_source_repo_when_unset_or_identical() {
+    let mut cfg = fork_config();
+    assert!(cfg.source_config().is_none());
+
+    cfg.github.source_owner = Some("me".into());
+    cfg.github.source_repo = Some("tool".into());
+    assert!(cfg.source_config().is_none());
+}
+
+#[test]
+fn source_config_targets_upstream() {
+    let mut cfg = fork_config();
+    cfg.github.source_owner = Some("upstream-org".into());
+
+    let source = cfg.source_config().expect("differs from fork");
+    assert_eq!(source.github.owner, "upstream-org");
+    // Unset source_repo falls back to the fork's repo name
+    assert_eq!(source.github.repo, "tool");
+    // Writes keep going to the fork
+    assert_eq!(cfg.github.owner, "me");
+}
@@ -0,0 +1,110 @@
+use std::collections::BTreeSet;
+
+use gh_cherry::config::{Config, TEMPLATE};
+use gh_cherry::error::ConfigError;
+
+/// `section.key` for every setting in `table`
+fn keys(table: &toml::Table) -> BTreeSet<String> {
+    table
+        .iter()
+        .flat_map(|(section, value)| {
+            value
+                .as_table()
+                .into_iter()
+                .flat_map(move |fields| fields.keys().map(move |key| format!("{}.{}", section, key)))
+        })
+        .collect()
+}
+
+#[test]
+fn template_parses_to_the_defaults() {
+    let parsed: Config = toml::from_str(TEMPLATE).expect("template parses");
+    assert_eq!(parsed.snapshot(), Config::default().snapshot());
+    assert!(parsed.validation_errors().is_empty());
+}
+
+#[test]
+fn template_names_only_real_settings_and_all_of_them() {
+    // Bring the unset examples in, so they're checked too
+    let uncommented: String = TEMPLATE
+        .lines()
+        .map(|line| match line.strip_prefix("# ") {
+            Some(rest) if rest.split_once(" = ").is_some_and(|(key, _)| !key.contains(' ')) => rest,
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let written: toml::Table = toml::from_str(&uncommented).expect("uncommented template parses");
+    let config: Config = toml::from_str(&uncommented).expect("uncommented template is a config");
+    let known = toml::Table::try_from(&config).expect("config serializes");
+
+    assert_eq!(keys(&written), keys(&known));
+}
+
+#[test]
+fn check_file_lists_every_problem() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+
+    std::fs::write(&path, TEMPLATE).unwrap();
+    assert!(Config::check_file(&path).unwrap().is_empty());
+
+    std::fs::write(&path, invalid_config(dir.path())).unwrap();
+    let fields: Vec<String> = Config::check_file(&path)
+        .unwrap()
+        .into_iter()
+        .map(|e| match e {
+            ConfigError::Invalid { field, .. } => field,
+            other => panic!("expected ConfigError::Invalid, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(fields, ["tags.sprint_pattern", "github.repo_path"]);
+}
+
+/// A config with two problems: a bad sprint regex and a clone that isn't there
+fn invalid_config(dir: &std::path::Path) -> String {
+    let missing = dir.join("no-such-clone");
+    format!(
+        "[github]\nrepo_path = {:?}\n\n[tags]\nsprint_pattern = \"S(\"\n",
+        missing.display().to_string()
+    )
+}
+
+#[test]
+fn validate_config_flag_exits_nonzero_with_the_problems() {
+    let dir = tempfile::tempdir().unwrap();
+    let valid = dir.path().join("valid.toml");
+    let invalid = dir.path().join("invalid.toml");
+    std::fs::write(&valid, TEMPLATE).unwrap();
+    std::fs::write(&invalid, invalid_config(dir.path())).unwrap();
+
+    let run = |path: &std::path::Path| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_gh_cherry"))
+            .arg("--validate-config")
+            .arg(path)
+            .env("LANG", "C")
+            .output()
+            .expect("binary runs")
+    };
+
+    let ok = run(&valid);
+    assert_eq!(ok.status.code(), Some(0));
+
+    let failed = run(&invalid);
+    assert_eq!(failed.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&failed.stderr);
+    assert!(stderr.contains("2 problem(s)"), "{}", stderr);
+    assert!(stderr.contains("tags.sprint_pattern"), "{}", stderr);
+    assert!(stderr.contains("github.repo_path"), "{}", stderr);
+}
+
+#[test]
+fn check_file_fails_on_a_file_that_does_not_parse() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    std::fs::write(&path, "[ui]\ndays_back = \"soon\"\n").unwrap();
+
+    let err = Config::check_file(&path).unwrap_err();
+    assert!(err.to_string().contains("Failed to parse config file"));
+    assert!(Config::check_file(&dir.path().join("missing.toml")).is_err());
+}
@@ -0,0 +1,288 @@
+//! Runs `GitOperations` with `git_engine = "cli"` against temp repositories.
+//! Every test returns early when no `git` binary is installed.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use gh_cherry::config::GitEngine;
+use gh_cherry::git::GitOperations;
+
+fn git_available() -> bool {
+    Command::new("git")
+        .arg("--version")
+        .output()
+        .is_ok_and(|out| out.status.success())
+}
+
+/// Repo with `a.txt` on `master`, plus a `feature` branch that changes it
+/// to "feature" in one commit. Returns the feature commit's sha.
+fn repo_with_feature(dir: &Path, master_content: &str) -> (git2::Repository, String) {
+    let repo = git2::Repository::init(dir).expect("init repo");
+    {
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+    }
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let commit = |content: &str, parents: &[&git2::Commit], update_ref: Option<&str>| {
+        fs::write(dir.join("a.txt"), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(update_ref, &sig, &sig, content, &tree, parents)
+            .unwrap()
+    };
+
+    let base = repo.find_commit(commit("base\n", &[], Some("HEAD"))).unwrap();
+    let feature = commit("feature\n", &[&base], None);
+    repo.branch("feature", &repo.find_commit(feature).unwrap(), false)
+        .unwrap();
+    if master_content != "base\n" {
+        commit(master_content, &[&base], Some("HEAD"));
+    }
+    // Leave a clean working tree at master
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.reset(head.as_object(), git2::ResetType::Hard, None)
+        .unwrap();
+    let mut index = repo.index().unwrap();
+    index.read_tree(&head.tree().unwrap()).unwrap();
+    index.write().unwrap();
+    drop((base, head));
+    (repo, feature.to_string())
+}
+
+#[test]
+fn cli_engine_picks_cleanly() {
+    if !git_available() {
+        eprintln!("skipping: no git binary");
+        return;
+    }
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let (_repo, feature) = repo_with_feature(dir, "base\n");
+
+    let ops = GitOperations::new(dir).unwrap().with_engine(GitEngine::Cli);
+    let result = ops.cherry_pick(&feature).expect("cherry-pick");
+
+    assert!(result.success);
+    assert_eq!(result.commit_sha, Some(ops.head_sha().unwrap()));
+    assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "feature\n");
+    assert!(!ops.cherry_pick_in_progress());
+}
+
+#[test]
+fn cli_engine_reports_conflicts_then_aborts() {
+    if !git_available() {
+        eprintln!("skipping: no git binary");
+        return;
+    }
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let (_repo, feature) = repo_with_feature(dir, "diverged\n");
+
+    let ops = GitOperations::new(dir).unwrap().with_engine(GitEngine::Cli);
+    let head = ops.head_sha().unwrap();
+    let result = ops.cherry_pick(&feature).expect("cherry-pick runs");
+
+    assert!(!result.success);
+    assert_eq!(result.conflicts, vec!["a.txt"]);
+    assert!(ops.cherry_pick_in_progress());
+
+    ops.abort_cherry_pick().unwrap();
+    assert!(!ops.cherry_pick_in_progress());
+    assert_eq!(ops.head_sha().unwrap(), head);
+    assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "diverged\n");
+}
+
+#[test]
+fn a_stopped_cherry_pick_sequence_is_in_progress_until_aborted() {
+    if !git_available() {
+        eprintln!("skipping: no git binary");
+        return;
+    }
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let (repo, feature) = repo_with_feature(dir, "diverged\n");
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let first = repo.find_commit(git2::Oid::from_str(&feature).unwrap()).unwrap();
+    let mut builder = repo.treebuilder(Some(&first.tree().unwrap())).unwrap();
+    builder.insert("b.txt", repo.blob(b"more\n").unwrap(), 0o100644).unwrap();
+    let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+    let second = repo.commit(None, &sig, &sig, "more", &tree, &[&first]).unwrap().to_string();
+
+    // git stops on the first commit's conflict, with the second still queued
+    let ops = GitOperations::new(dir).unwrap();
+    let head = ops.head_sha().unwrap();
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["cherry-pick", &feature, &second])
+        .output()
+        .unwrap()
+        .status;
+    assert!(!status.success());
+    assert_eq!(repo.state(), git2::RepositoryState::CherryPickSequence);
+
+    assert!(ops.cherry_pick_in_progress());
+    assert_eq!(ops.cherry_pick_head(), Some(feature));
+    ops.abort_cherry_pick().unwrap();
+    assert!(!ops.cherry_pick_in_progress());
+    assert_eq!(ops.head_sha().unwrap(), head);
+}
+
+#[test]
+fn cli_engine_continues_after_resolution() {
+    if !git_available() {
+        eprintln!("skipping: no git binary");
+        return;
+    }
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let (_repo, feature) = repo_with_feature(dir, "diverged\n");
+
+    let ops = GitOperations::new(dir).unwrap().with_engine(GitEngine::Cli);
+    assert!(!ops.cherry_pick(&feature).unwrap().success);
+    // Conflicts not yet resolved and staged: continuing is refused
+    assert!(ops.continue_cherry_pick(None).is_err());
+
+    fs::write(dir.join("a.txt"), "resolved\n").unwrap();
+    let staged = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["add", "a.txt"])
+        .status()
+        .unwrap();
+    assert!(staged.success());
+    let sha = ops.continue_cherry_pick(None).expect("continue");
+
+    assert_eq!(sha, ops.head_sha().unwrap());
+    assert!(!ops.cherry_pick_in_progress());
+}
+
+#[test]
+fn cli_engine_checks_out_branches() {
+    if !git_available() {
+        eprintln!("skipping: no git binary");
+        return;
+    }
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let (_repo, feature) = repo_with_feature(dir, "base\n");
+
+    let ops = GitOperations::new(dir).unwrap().with_engine(GitEngine::Cli);
+    ops.checkout_branch("feature").unwrap();
+    assert_eq!(ops.current_branch().unwrap(), "feature");
+    assert_eq!(ops.head_sha().unwrap(), feature);
+
+    let err = ops.checkout_branch("no-such-branch").unwrap_err();
+    assert!(
+        matches!(err, gh_cherry::error::GitError::BranchNotFound { .. }),
+        "got {:?}",
+        err
+    );
+}
+
+#[test]
+fn cli_engine_finishes_picks_that_git_rerere_resolved() {
+    if !git_available() {
+        eprintln!("skipping: no git binary");
+        return;
+    }
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let (repo, feature) = repo_with_feature(dir, "diverged\n");
+    repo.config().unwrap().set_bool("rerere.enabled", true).unwrap();
+
+    // First time: resolve by hand so git records the resolution
+    let ops = GitOperations::new(dir).unwrap().with_engine(GitEngine::Cli);
+    let before = ops.head_sha().unwrap();
+    let first = ops.cherry_pick(&feature).unwrap();
+    assert!(!first.success);
+    assert!(first.rerere_resolved.is_empty());
+    fs::write(dir.join("a.txt"), "resolved\n").unwrap();
+    let staged = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["add", "a.txt"])
+        .status()
+        .unwrap();
+    assert!(staged.success());
+    ops.continue_cherry_pick(None).unwrap();
+
+    // Second time the same conflict resolves itself
+    ops.undo_last_commit().unwrap();
+    assert_eq!(ops.head_sha().unwrap(), before);
+    let second = ops.cherry_pick(&feature).unwrap();
+
+    assert!(second.success, "{:?}", second);
+    assert_eq!(second.rerere_resolved, vec!["a.txt"]);
+    assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "resolved\n");
+    assert!(!ops.cherry_pick_in_progress());
+}
+
+#[test]
+fn cli_engine_tracks_the_chosen_remote_when_several_have_the_branch() {
+    if !git_available() {
+        eprintln!("skipping: no git binary");
+        return;
+    }
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let (repo, feature) = repo_with_feature(dir, "base\n");
+    let feature = git2::Oid::from_str(&feature).unwrap();
+    for remote in ["origin", "upstream"] {
+        repo.remote(remote, &format!("https://github.com/{}/app.git", remote)).unwrap();
+        repo.reference(&format!("refs/remotes/{}/release", remote), feature, false, "test")
+            .unwrap();
+    }
+
+    // Plain `git checkout release` refuses to guess between the two
+    let ops = GitOperations::new(dir).unwrap().with_engine(GitEngine::Cli);
+    assert_eq!(ops.checkout_branch("release").unwrap(), None);
+    ops.ensure_on_branch("release").unwrap();
+    let config = repo.config().unwrap().snapshot().unwrap();
+    assert_eq!(config.get_string("branch.release.remote").unwrap(), "origin");
+    assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "feature\n");
+}
+
+#[test]
+fn cli_engine_pushes_to_the_configured_remote_with_a_lease() {
+    use gh_cherry::error::GitError;
+
+    if !git_available() {
+        eprintln!("skipping: no git binary");
+        return;
+    }
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let (repo, feature) = repo_with_feature(dir, "base\n");
+    let bare_dir = tempfile::tempdir().expect("tempdir");
+    let bare = git2::Repository::init_bare(bare_dir.path()).expect("init bare");
+    repo.remote("origin", "https://github.com/origin/app.git").unwrap();
+    repo.remote("upstream", bare_dir.path().to_str().unwrap()).unwrap();
+    let on_upstream = || bare.find_reference("refs/heads/feature").unwrap().target().unwrap().to_string();
+
+    let ops = GitOperations::new(dir).unwrap().with_engine(GitEngine::Cli).with_remote("upstream");
+    ops.push_branch("feature", false).unwrap();
+    assert_eq!(on_upstream(), feature);
+    let upstream_name = repo.branch_upstream_name("refs/heads/feature").unwrap();
+    assert_eq!(upstream_name.as_str(), Some("refs/remotes/upstream/feature"));
+
+    // Someone else replaced the branch on the remote since it was last fetched
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let base = repo.find_commit(git2::Oid::from_str(&feature).unwrap()).unwrap().parent(0).unwrap();
+    let base = bare.find_commit(base.id()).unwrap();
+    let elsewhere = bare
+        .commit(None, &sig, &sig, "Elsewhere", &base.tree().unwrap(), &[&base])
+        .unwrap();
+    bare.reference("refs/heads/feature", elsewhere, true, "moved").unwrap();
+    let stale = ops.push_branch("feature", true);
+    assert!(matches!(stale, Err(GitError::PushRejected { .. })), "{:?}", stale);
+    assert_eq!(on_upstream(), elsewhere.to_string());
+
+    ops.fetch_branch("feature").unwrap();
+    ops.push_branch("feature", true).unwrap();
+    assert_eq!(on_upstream(), feature);
+}
@@ -1,6 +1,8 @@
 use std::fs;
 use std::path::Path;
 
+use chrono::TimeZone;
+
 #[test]
 fn repo_clean_status_changes_with_untracked_file() {
     let temp = tempfile::tempdir().expect("tempdir");
@@ -27,3 +29,1389 @@ fn repo_clean_status_changes_with_untracked_file() {
     // Ensure the .git directory exists so test doesn't get optimized away
     assert!(Path::new(&dir.join(".git")).exists());
 }
+
+#[test]
+fn only_locked_git_errors_are_retryable() {
+    use git2::{Error, ErrorClass, ErrorCode};
+    use gh_cherry::git::is_retryable_git_error;
+
+    let locked = Error::new(ErrorCode::Locked, ErrorClass::Index, "index.lock exists");
+    assert!(is_retryable_git_error(&locked));
+
+    let conflict = Error::new(ErrorCode::Conflict, ErrorClass::Checkout, "conflict");
+    assert!(!is_retryable_git_error(&conflict));
+    let generic = Error::from_str("something else");
+    assert!(!is_retryable_git_error(&generic));
+}
+
+#[test]
+fn lock_and_disk_full_failures_get_their_own_errors() {
+    use gh_cherry::error::GitError;
+    use gh_cherry::git::classify_git_error;
+    use git2::{Error, ErrorClass, ErrorCode};
+
+    let git_dir = Path::new("/work/repo/.git");
+    let locked = GitError::Operation {
+        context: "Failed to checkout tree".into(),
+        source: Error::new(ErrorCode::Locked, ErrorClass::Index, "index.lock exists"),
+    };
+    let err = classify_git_error(locked, git_dir);
+    assert!(matches!(&err, GitError::IndexLocked { lock } if lock == &git_dir.join("index.lock")));
+    assert!(err.to_string().starts_with("/work/repo/.git/index.lock is held by another git process"));
+
+    let cli_locked = GitError::Command {
+        args: "cherry-pick abc".into(),
+        stderr: "fatal: Unable to create '/work/repo/.git/index.lock': File exists.".into(),
+    };
+    assert!(matches!(classify_git_error(cli_locked, git_dir), GitError::IndexLocked { .. }));
+
+    let full = GitError::Operation {
+        context: "Failed to write tree".into(),
+        source: Error::new(ErrorCode::GenericError, ErrorClass::Os, "could not write: No space left on device"),
+    };
+    let err = classify_git_error(full, git_dir);
+    assert!(matches!(&err, GitError::DiskFull { action } if action == "Failed to write tree"));
+    assert!(err.to_string().contains("git fsck"));
+
+    let other = GitError::Command {
+        args: "checkout x".into(),
+        stderr: "error: pathspec 'x' did not match".into(),
+    };
+    assert!(matches!(classify_git_error(other, git_dir), GitError::Command { .. }));
+}
+
+#[test]
+fn list_tags_returns_created_tags_and_filters_by_pattern() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let repo = git2::Repository::init(temp.path()).expect("init repo");
+
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree_id = repo.index().unwrap().write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    let commit_id = repo
+        .commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+        .unwrap();
+    let commit = repo.find_object(commit_id, None).unwrap();
+
+    repo.tag_lightweight("v1.0.0", &commit, false).unwrap();
+    repo.tag_lightweight("v1.1.0", &commit, false).unwrap();
+    repo.tag("release-2024", &commit, &sig, "annotated", false)
+        .unwrap();
+
+    let ops = gh_cherry::git::GitOperations::new(temp.path()).expect("git ops open");
+    assert_eq!(
+        ops.list_tags(None).unwrap(),
+        vec!["release-2024", "v1.0.0", "v1.1.0"]
+    );
+    assert_eq!(ops.list_tags(Some("v1.*")).unwrap(), vec!["v1.0.0", "v1.1.0"]);
+    assert!(ops.list_tags(Some("nightly-*")).unwrap().is_empty());
+    assert!(ops.list_tags(Some("[")).is_err());
+}
+
+#[test]
+fn unresolved_conflicts_surface_as_conflicts_error() {
+    use gh_cherry::error::GitError;
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    {
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+    }
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+    let commit_file = |content: &str, parents: &[&git2::Commit], update_ref: Option<&str>| {
+        fs::write(dir.join("a.txt"), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(update_ref, &sig, &sig, content, &tree, parents)
+            .unwrap()
+    };
+
+    let base = commit_file("base\n", &[], Some("HEAD"));
+    let base = repo.find_commit(base).unwrap();
+    let theirs = commit_file("theirs\n", &[&base], None);
+    // Back on the original branch, change the same line differently
+    let ours = commit_file("ours\n", &[&base], Some("HEAD"));
+    repo.checkout_tree(repo.find_commit(ours).unwrap().as_object(), None)
+        .unwrap();
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    let result = ops.cherry_pick(&theirs.to_string()).expect("cherry-pick runs");
+    assert!(!result.success);
+    assert_eq!(result.conflicts, vec!["a.txt"]);
+
+    match ops.continue_cherry_pick(None) {
+        Err(GitError::Conflicts { files }) => assert_eq!(files, vec!["a.txt"]),
+        other => panic!("expected GitError::Conflicts, got {:?}", other),
+    }
+}
+
+#[test]
+fn a_pick_resolved_in_another_terminal_resumes_with_the_original_message() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    {
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+    }
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+    let commit_file = |content: &str, parents: &[&git2::Commit], update_ref: Option<&str>| {
+        fs::write(dir.join("a.txt"), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(update_ref, &sig, &sig, content, &tree, parents)
+            .unwrap()
+    };
+
+    let base = commit_file("base\n", &[], Some("HEAD"));
+    let base = repo.find_commit(base).unwrap();
+    let theirs = commit_file("theirs\n", &[&base], None);
+    let ours = commit_file("ours\n", &[&base], Some("HEAD"));
+    repo.checkout_tree(repo.find_commit(ours).unwrap().as_object(), None)
+        .unwrap();
+
+    // A run that stopped on the conflict and was closed
+    let first_run = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    assert!(!first_run.cherry_pick(&theirs.to_string()).unwrap().success);
+    drop(first_run);
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    assert!(ops.cherry_pick_in_progress());
+    assert_eq!(ops.conflicted_files().unwrap(), ["a.txt"]);
+    assert_eq!(ops.cherry_pick_head(), Some(theirs.to_string()));
+
+    // Resolved and staged outside the app
+    fs::write(dir.join("a.txt"), "merged\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("a.txt")).unwrap();
+    index.write().unwrap();
+    assert!(ops.conflicted_files().unwrap().is_empty());
+
+    let new_sha = ops.resume_cherry_pick().expect("pick continues");
+    let commit = repo.find_commit(git2::Oid::from_str(&new_sha).unwrap()).unwrap();
+    assert_eq!(commit.message(), Some("theirs\n"));
+    assert_eq!(commit.parent_id(0).unwrap(), ours);
+    assert!(!ops.cherry_pick_in_progress());
+    assert_eq!(ops.cherry_pick_head(), None);
+}
+
+#[test]
+fn head_state_tells_branch_detached_and_unborn_apart() {
+    use gh_cherry::git::HeadState;
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    repo.set_head("refs/heads/main").unwrap();
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    assert_eq!(ops.head_state().unwrap(), HeadState::Unborn("main".into()));
+
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+    let first = repo.commit(Some("HEAD"), &sig, &sig, "first", &tree, &[]).unwrap();
+    assert_eq!(ops.head_state().unwrap(), HeadState::Branch("main".into()));
+
+    repo.set_head_detached(first).unwrap();
+    let detached = ops.head_state().unwrap();
+    assert_eq!(detached, HeadState::Detached(first.to_string()));
+    assert!(detached.to_string().starts_with("detached at "));
+}
+
+#[test]
+fn committing_needs_head_on_the_checked_out_branch() {
+    use gh_cherry::error::GitError;
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    repo.set_head("refs/heads/main").unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+    let first = repo.commit(Some("HEAD"), &sig, &sig, "first", &tree, &[]).unwrap();
+    repo.branch("release", &repo.find_commit(first).unwrap(), false).unwrap();
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    ops.ensure_on_branch("main").expect("on main");
+    match ops.ensure_on_branch("release") {
+        Err(GitError::HeadMismatch { expected, found }) => {
+            assert_eq!(expected, "release");
+            assert_eq!(found, "main");
+        }
+        other => panic!("expected GitError::HeadMismatch, got {:?}", other),
+    }
+
+    // Checking out from a detached HEAD attaches it to the branch
+    repo.set_head_detached(first).unwrap();
+    assert!(matches!(ops.ensure_on_branch("main"), Err(GitError::HeadMismatch { .. })));
+    ops.checkout_branch("release").expect("checkout attaches HEAD");
+    ops.ensure_on_branch("release").expect("on release");
+}
+
+#[test]
+fn fetch_branch_makes_missing_head_commit_available() {
+    let upstream_dir = tempfile::tempdir().expect("tempdir");
+    let upstream = git2::Repository::init(upstream_dir.path()).expect("init upstream");
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree_id = upstream.index().unwrap().write_tree().unwrap();
+    let tree = upstream.find_tree(tree_id).unwrap();
+    let head = upstream
+        .commit(Some("refs/heads/feature"), &sig, &sig, "feature work", &tree, &[])
+        .unwrap();
+
+    let local_dir = tempfile::tempdir().expect("tempdir");
+    let local = git2::Repository::init(local_dir.path()).expect("init local");
+    local
+        .remote("origin", upstream_dir.path().to_str().unwrap())
+        .unwrap();
+
+    let ops = gh_cherry::git::GitOperations::new(local_dir.path()).expect("git ops open");
+    assert!(!ops.commit_exists(&head.to_string()));
+    assert!(!ops.commit_exists("not-a-sha"));
+
+    ops.fetch_branch("feature").expect("fetch feature branch");
+    assert!(ops.commit_exists(&head.to_string()));
+    assert!(local.find_reference("refs/remotes/origin/feature").is_ok());
+
+    assert!(ops.fetch_branch("does-not-exist").is_err());
+}
+
+#[test]
+fn merge_commits_are_picked_against_their_first_parent() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    {
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+    }
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+    let commit_files = |files: &[(&str, &str)], parents: &[&git2::Commit], update_ref: Option<&str>| {
+        let mut index = repo.index().unwrap();
+        for (name, content) in files {
+            fs::write(dir.join(name), content).unwrap();
+            index.add_path(Path::new(name)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(update_ref, &sig, &sig, "commit", &tree, parents)
+            .unwrap()
+    };
+
+    let base = repo.find_commit(commit_files(&[("a.txt", "base\n")], &[], Some("HEAD"))).unwrap();
+    let feature = repo
+        .find_commit(commit_files(&[("feature.txt", "feature\n")], &[&base], None))
+        .unwrap();
+    // Merge of `feature` into a branch at `base`: tree has both files
+    let merge = commit_files(&[("a.txt", "base\n"), ("feature.txt", "feature\n")], &[&base, &feature], None);
+
+    // Reset the working tree to `base` and pick the merge commit onto it
+    repo.checkout_tree(base.as_object(), Some(git2::build::CheckoutBuilder::new().force()))
+        .unwrap();
+    fs::remove_file(dir.join("feature.txt")).ok();
+    let mut index = repo.index().unwrap();
+    index.read_tree(&base.tree().unwrap()).unwrap();
+    index.write().unwrap();
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    let header = ops.commit_header(&merge.to_string()).unwrap();
+    assert_eq!(header.parent_count, 2);
+    assert_eq!(header.message, "commit");
+    assert_eq!(header.authored_at.timestamp(), sig.when().seconds());
+    let result = ops.cherry_pick(&merge.to_string()).expect("cherry-pick merge commit");
+    assert!(result.success);
+    assert_eq!(result.mainline, Some(1));
+    assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    assert_eq!(fs::read_to_string(dir.join("feature.txt")).unwrap(), "feature\n");
+}
+
+#[test]
+fn merge_commits_can_be_picked_against_another_parent() {
+    use gh_cherry::error::GitError;
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    {
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+    }
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let commit_files = |files: &[(&str, &str)], parents: &[&git2::Commit], update_ref: Option<&str>| {
+        let mut index = repo.index().unwrap();
+        index.clear().unwrap();
+        for (name, content) in files {
+            fs::write(dir.join(name), content).unwrap();
+            index.add_path(Path::new(name)).unwrap();
+        }
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(update_ref, &sig, &sig, "commit", &tree, parents).unwrap()
+    };
+
+    let base = repo.find_commit(commit_files(&[("a.txt", "base\n")], &[], None)).unwrap();
+    let main = repo
+        .find_commit(commit_files(&[("a.txt", "base\n"), ("main.txt", "main\n")], &[&base], None))
+        .unwrap();
+    let feature = repo
+        .find_commit(commit_files(&[("a.txt", "base\n"), ("feature.txt", "feature\n")], &[&base], None))
+        .unwrap();
+    let all = [("a.txt", "base\n"), ("main.txt", "main\n"), ("feature.txt", "feature\n")];
+    let merge = commit_files(&all, &[&main, &feature], None).to_string();
+
+    // Start over on a branch at `base`
+    repo.branch("release", &base, false).unwrap();
+    repo.set_head("refs/heads/release").unwrap();
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force().remove_untracked(true)))
+        .unwrap();
+    fs::remove_file(dir.join("main.txt")).ok();
+    fs::remove_file(dir.join("feature.txt")).ok();
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    let invalid = |sha: &str, mainline| ops.cherry_pick_with_mainline(sha, Some(mainline)).unwrap_err();
+    assert!(matches!(invalid(&merge, 3), GitError::InvalidMainline { mainline: 3, parents: 2, .. }));
+    assert!(matches!(invalid(&merge, 0), GitError::InvalidMainline { mainline: 0, .. }));
+    assert!(matches!(
+        invalid(&main.id().to_string(), 1),
+        GitError::InvalidMainline { parents: 1, .. }
+    ));
+
+    // Against `feature`, the merge brought in what `main` had
+    let result = ops.cherry_pick_with_mainline(&merge, Some(2)).expect("cherry-pick merge commit");
+    assert!(result.success);
+    assert_eq!(result.mainline, Some(2));
+    assert_eq!(fs::read_to_string(dir.join("main.txt")).unwrap(), "main\n");
+    assert!(!dir.join("feature.txt").exists());
+
+    // Ordinary commits need no mainline
+    let result = ops.cherry_pick(&feature.id().to_string()).expect("cherry-pick feature");
+    assert!(result.success);
+    assert_eq!(result.mainline, None);
+    assert_eq!(fs::read_to_string(dir.join("feature.txt")).unwrap(), "feature\n");
+}
+
+#[test]
+fn origin_coords_parse_the_origin_remote() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let repo = git2::Repository::init(temp.path()).expect("init repo");
+    let ops = gh_cherry::git::GitOperations::new(temp.path()).expect("git ops open");
+    assert_eq!(ops.origin_coords(), None);
+
+    repo.remote("origin", "git@github.com:octo/widgets.git").unwrap();
+    let coords = ops.origin_coords().expect("origin parses");
+    assert_eq!(coords.full_name(), "octo/widgets");
+    assert_eq!(coords.host, "github.com");
+}
+
+#[test]
+fn undo_last_commit_and_delete_branch() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+    let commit_file = |content: &str, parents: &[&git2::Commit]| {
+        fs::write(dir.join("a.txt"), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, content, &tree, parents)
+            .unwrap()
+    };
+    let first = repo.find_commit(commit_file("one\n", &[])).unwrap();
+    let second = commit_file("two\n", &[&first]);
+    repo.branch("scratch", &first, false).unwrap();
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    assert_eq!(ops.head_sha().unwrap(), second.to_string());
+    assert_eq!(ops.undo_last_commit().unwrap(), second.to_string());
+    assert_eq!(ops.head_sha().unwrap(), first.id().to_string());
+    assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "one\n");
+    // The root commit has nothing to reset to
+    assert!(ops.undo_last_commit().is_err());
+
+    assert!(ops.branch_exists("scratch"));
+    ops.delete_branch("scratch").unwrap();
+    assert!(!ops.branch_exists("scratch"));
+    assert!(ops.delete_branch("scratch").is_err());
+}
+
+#[test]
+fn recorded_rerere_resolution_completes_a_conflicted_pick() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    {
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+        cfg.set_bool("rerere.enabled", true).unwrap();
+    }
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let commit = |content: &str, parents: &[&git2::Commit], update_ref: Option<&str>| {
+        fs::write(dir.join("a.txt"), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(update_ref, &sig, &sig, content, &tree, parents)
+            .unwrap()
+    };
+    let base = repo.find_commit(commit("base\n", &[], Some("HEAD"))).unwrap();
+    let feature = commit("feature\n", &[&base], None);
+    commit("diverged\n", &[&base], Some("HEAD"));
+
+    // A resolution recorded earlier, e.g. when backporting to another branch
+    let recorded = dir.join(".git/rr-cache/0123456789abcdef0123456789abcdef01234567");
+    fs::create_dir_all(&recorded).unwrap();
+    fs::write(recorded.join("preimage"), "<<<<<<<\ndiverged\n=======\nfeature\n>>>>>>>\n").unwrap();
+    fs::write(recorded.join("postimage"), "resolved\n").unwrap();
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    assert!(ops.rerere_enabled());
+    let result = ops.cherry_pick(&feature.to_string()).expect("cherry-pick");
+
+    assert!(result.success, "{:?}", result);
+    assert_eq!(result.rerere_resolved, vec!["a.txt"]);
+    assert_eq!(result.commit_sha, Some(ops.head_sha().unwrap()));
+    assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "resolved\n");
+    assert_eq!(repo.state(), git2::RepositoryState::Clean);
+}
+
+/// Clone-like setup: `release` in `origin` plus a local `release` with one
+/// extra commit that was never pushed. Returns (upstream dir, local dir, unpushed sha).
+fn repo_with_unpushed_commit() -> (tempfile::TempDir, tempfile::TempDir, String) {
+    let upstream_dir = tempfile::tempdir().expect("tempdir");
+    let upstream = git2::Repository::init(upstream_dir.path()).expect("init upstream");
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree = upstream
+        .find_tree(upstream.index().unwrap().write_tree().unwrap())
+        .unwrap();
+    upstream
+        .commit(Some("refs/heads/release"), &sig, &sig, "released", &tree, &[])
+        .unwrap();
+
+    let local_dir = tempfile::tempdir().expect("tempdir");
+    let local = git2::Repository::init(local_dir.path()).expect("init local");
+    {
+        let mut cfg = local.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+    }
+    local
+        .remote("origin", upstream_dir.path().to_str().unwrap())
+        .unwrap();
+    let ops = gh_cherry::git::GitOperations::new(local_dir.path()).expect("git ops open");
+    ops.fetch_branch("release").unwrap();
+    ops.checkout_branch("release").unwrap();
+
+    fs::write(local_dir.path().join("wip.txt"), "half-finished\n").unwrap();
+    let mut index = local.index().unwrap();
+    index.add_path(Path::new("wip.txt")).unwrap();
+    index.write().unwrap();
+    let tree = local.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = local.head().unwrap().peel_to_commit().unwrap();
+    let unpushed = local
+        .commit(Some("HEAD"), &sig, &sig, "Half-finished backport\n\nbody", &tree, &[&parent])
+        .unwrap();
+
+    (upstream_dir, local_dir, unpushed.to_string())
+}
+
+/// A commit on an unrelated branch that adds `name`, for picking onto `release`
+fn side_commit(dir: &Path, name: &str) -> String {
+    let repo = git2::Repository::open(dir).unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let mut builder = repo.treebuilder(None).unwrap();
+    let blob = repo.blob(b"fix\n").unwrap();
+    builder.insert(name, blob, 0o100644).unwrap();
+    let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+    let base = repo.find_reference("refs/remotes/origin/release").unwrap().peel_to_commit().unwrap();
+    repo.commit(None, &sig, &sig, "fix", &tree, &[&base]).unwrap().to_string()
+}
+
+#[test]
+fn picks_wait_for_another_process_to_release_the_index_lock() {
+    let (_upstream, local, _) = repo_with_unpushed_commit();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+    let fix = side_commit(local.path(), "fix.txt");
+    let lock = local.path().join(".git/index.lock");
+
+    // Released shortly after the pick starts, as a finishing `git status` would
+    fs::write(&lock, "").unwrap();
+    let releaser = {
+        let lock = lock.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(700));
+            fs::remove_file(lock).unwrap();
+        })
+    };
+    let result = ops.cherry_pick(&fix).expect("pick after the lock is released");
+    releaser.join().unwrap();
+    assert!(result.success);
+
+    // Never released: the error names the lock file
+    fs::write(&lock, "").unwrap();
+    let err = ops.undo_last_commit().unwrap_err();
+    assert!(
+        matches!(&err, gh_cherry::error::GitError::IndexLocked { lock: held } if held.ends_with(".git/index.lock")),
+        "{:?}",
+        err
+    );
+    fs::remove_file(&lock).unwrap();
+    ops.undo_last_commit().expect("undo once the lock is gone");
+}
+
+#[test]
+fn unpushed_commits_are_listed_with_ahead_behind_counts() {
+    let (_upstream, local, unpushed) = repo_with_unpushed_commit();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+
+    assert_eq!(ops.ahead_behind("release").unwrap(), Some((1, 0)));
+    let commits = ops.unpushed_commits("release").unwrap();
+    assert_eq!(commits.len(), 1);
+    assert_eq!(commits[0].sha, unpushed);
+    assert_eq!(commits[0].summary, "Half-finished backport");
+
+    // No origin counterpart, nothing to compare
+    assert_eq!(ops.ahead_behind("missing").unwrap(), None);
+    assert!(ops.unpushed_commits("missing").unwrap().is_empty());
+}
+
+#[test]
+fn continuing_picks_on_top_of_unpushed_commits() {
+    let (_upstream, local, unpushed) = repo_with_unpushed_commit();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+    let fix = side_commit(local.path(), "fix.txt");
+
+    assert!(ops.cherry_pick(&fix).unwrap().success);
+
+    let commits = ops.unpushed_commits("release").unwrap();
+    assert_eq!(commits.len(), 2);
+    assert_eq!(commits[1].sha, unpushed);
+    assert!(local.path().join("wip.txt").exists());
+}
+
+#[test]
+fn resetting_to_origin_discards_unpushed_commits() {
+    let (_upstream, local, _unpushed) = repo_with_unpushed_commit();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+
+    ops.reset_branch_to_remote("release").unwrap();
+
+    assert_eq!(ops.ahead_behind("release").unwrap(), Some((0, 0)));
+    assert!(!local.path().join("wip.txt").exists());
+    let fix = side_commit(local.path(), "fix.txt");
+    assert!(ops.cherry_pick(&fix).unwrap().success);
+    assert_eq!(ops.unpushed_commits("release").unwrap().len(), 1);
+
+    assert!(ops.reset_branch_to_remote("missing").is_err());
+}
+
+#[test]
+fn cancelling_leaves_the_branch_untouched() {
+    let (_upstream, local, unpushed) = repo_with_unpushed_commit();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+    let before = ops.head_sha().unwrap();
+
+    // Listing is read-only; cancelling does nothing further
+    ops.unpushed_commits("release").unwrap();
+
+    assert_eq!(ops.head_sha().unwrap(), before);
+    assert_eq!(before, unpushed);
+    assert_eq!(ops.ahead_behind("release").unwrap(), Some((1, 0)));
+}
+
+#[test]
+fn squash_message_lists_subjects_and_trailers_in_pick_order() {
+    let message = gh_cherry::git::squash_message(
+        "Fix login redirect ",
+        &[("aaa111".into(), "Handle expired session".into()), ("bbb222".into(), "fixup".into())],
+    );
+    assert_eq!(
+        message,
+        "Fix login redirect\n\n\
+         - Handle expired session\n\
+         - fixup\n\n\
+         (cherry picked from commit aaa111)\n\
+         (cherry picked from commit bbb222)\n"
+    );
+}
+
+#[test]
+fn squashed_picks_match_the_tree_of_individual_picks() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    {
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+    }
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+    fs::write(dir.join("a.txt"), "base\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("a.txt")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let base = repo.commit(Some("HEAD"), &sig, &sig, "base", &tree, &[]).unwrap();
+    let base = repo.find_commit(base).unwrap();
+    repo.branch("plain", &base, false).unwrap();
+    repo.branch("squashed", &base, false).unwrap();
+
+    // Three PR commits, off-branch, each changing one file on top of the last
+    let mut parent = base.clone();
+    let mut shas = Vec::new();
+    for (name, content, subject) in [
+        ("b.txt", "added\n", "Add b"),
+        ("a.txt", "changed\n", "Change a"),
+        ("c.txt", "added\n", "Add c"),
+    ] {
+        let mut builder = repo.treebuilder(Some(&parent.tree().unwrap())).unwrap();
+        builder.insert(name, repo.blob(content.as_bytes()).unwrap(), 0o100644).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        let oid = repo.commit(None, &sig, &sig, subject, &tree, &[&parent]).unwrap();
+        shas.push(oid.to_string());
+        parent = repo.find_commit(oid).unwrap();
+    }
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    ops.checkout_branch("plain").unwrap();
+    for sha in &shas {
+        assert!(ops.cherry_pick(sha).unwrap().success);
+    }
+    let plain_tree = repo.head().unwrap().peel_to_commit().unwrap().tree_id();
+
+    ops.checkout_branch("squashed").unwrap();
+    for sha in &shas {
+        assert!(ops.cherry_pick(sha).unwrap().success);
+    }
+    let squashed = ops
+        .squash_picks(&base.id().to_string(), "Fix the widget", &shas)
+        .expect("squash");
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.id().to_string(), squashed);
+    assert_eq!(head.tree_id(), plain_tree);
+    assert_eq!(head.parent_ids().collect::<Vec<_>>(), vec![base.id()]);
+    let message = head.message().unwrap();
+    assert!(message.starts_with("Fix the widget\n\n- Add b\n- Change a\n- Add c\n"));
+    for sha in &shas {
+        assert!(message.contains(&format!("(cherry picked from commit {})", sha)));
+    }
+    assert!(ops.is_clean().unwrap());
+    assert_eq!(repo.state(), git2::RepositoryState::Clean);
+}
+
+#[test]
+fn binary_conflict_is_resolved_by_taking_a_side() {
+    use gh_cherry::git::ConflictSide;
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    {
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+    }
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+    let commit_files = |logo: &[u8], notes: &str, parents: &[&git2::Commit], update_ref: Option<&str>| {
+        fs::write(dir.join("logo.png"), logo).unwrap();
+        fs::write(dir.join("notes.txt"), notes).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("logo.png")).unwrap();
+        index.add_path(Path::new("notes.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(update_ref, &sig, &sig, notes, &tree, parents)
+            .unwrap()
+    };
+
+    let base = commit_files(b"\x89PNG\0base", "base\n", &[], Some("HEAD"));
+    let base = repo.find_commit(base).unwrap();
+    let theirs = commit_files(b"\x89PNG\0theirs", "theirs\n", &[&base], None);
+    let ours = commit_files(b"\x89PNG\0ours", "ours\n", &[&base], Some("HEAD"));
+    repo.checkout_tree(repo.find_commit(ours).unwrap().as_object(), None)
+        .unwrap();
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    let result = ops.cherry_pick(&theirs.to_string()).expect("cherry-pick runs");
+    assert!(!result.success);
+    assert_eq!(result.conflicts, vec!["logo.png", "notes.txt"]);
+    assert_eq!(result.binary_conflicts, vec!["logo.png"]);
+
+    ops.resolve_conflict("logo.png", ConflictSide::Theirs)
+        .expect("take theirs");
+    assert_eq!(fs::read(dir.join("logo.png")).unwrap(), b"\x89PNG\0theirs");
+    // Only the text file is left for the editor
+    match ops.continue_cherry_pick(None) {
+        Err(gh_cherry::error::GitError::Conflicts { files }) => assert_eq!(files, vec!["notes.txt"]),
+        other => panic!("expected GitError::Conflicts, got {:?}", other),
+    }
+    assert!(ops.resolve_conflict("logo.png", ConflictSide::Ours).is_err());
+
+    let mut index = repo.index().unwrap();
+    index.read(true).unwrap();
+    let staged = index.get_path(Path::new("logo.png"), 0).expect("resolved entry");
+    assert_eq!(repo.find_blob(staged.id).unwrap().content(), b"\x89PNG\0theirs");
+}
+
+#[test]
+fn staged_changes_outside_the_pick_are_not_committed_with_it() {
+    use gh_cherry::error::GitError;
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    {
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+    }
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+    let stage = |name: &str, content: &str| {
+        fs::write(dir.join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        index.write_tree().unwrap()
+    };
+    let base = repo.find_tree(stage("a.txt", "base\n")).unwrap();
+    let base = repo.commit(Some("HEAD"), &sig, &sig, "base", &base, &[]).unwrap();
+    let base = repo.find_commit(base).unwrap();
+    let picked = repo.find_tree(stage("a.txt", "picked\n")).unwrap();
+    let picked = repo.commit(None, &sig, &sig, "picked", &picked, &[&base]).unwrap();
+    repo.reset(base.as_object(), git2::ResetType::Hard, None).unwrap();
+
+    // A previous step left an unrelated file staged
+    stage("stray.txt", "stray\n");
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    match ops.cherry_pick(&picked.to_string()) {
+        Err(GitError::StagedOutsidePick { paths, .. }) => assert_eq!(paths, vec!["stray.txt"]),
+        other => panic!("expected GitError::StagedOutsidePick, got {:?}", other),
+    }
+    assert_eq!(ops.head_sha().unwrap(), base.id().to_string());
+
+    let ops = ops.with_only_commit_paths(true);
+    let result = ops.cherry_pick(&picked.to_string()).expect("cherry-pick runs");
+    assert!(result.success);
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let files: Vec<String> = head
+        .tree()
+        .unwrap()
+        .iter()
+        .map(|entry| entry.name().unwrap().to_string())
+        .collect();
+    assert_eq!(files, vec!["a.txt"]);
+    assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "picked\n");
+
+    // The stray file is still staged, now against the new HEAD
+    let mut index = repo.index().unwrap();
+    index.read(true).unwrap();
+    assert!(index.get_path(Path::new("stray.txt"), 0).is_some());
+}
+
+#[test]
+fn predicted_picks_leave_the_repository_untouched() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+    let commit_file = |name: &str, content: &str, parents: &[&git2::Commit], update_ref: Option<&str>| {
+        fs::write(dir.join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(update_ref, &sig, &sig, content, &tree, parents)
+            .unwrap()
+            .to_string()
+    };
+
+    let base = commit_file("a.txt", "base\n", &[], Some("HEAD"));
+    let base = repo.find_commit(git2::Oid::from_str(&base).unwrap()).unwrap();
+    let target = repo.head().unwrap().shorthand().unwrap().to_string();
+    let clean = commit_file("b.txt", "new file\n", &[&base], None);
+    let conflicting = commit_file("a.txt", "theirs\n", &[&base], None);
+    repo.reset(base.as_object(), git2::ResetType::Hard, None).unwrap();
+    commit_file("a.txt", "ours\n", &[&base], Some("HEAD"));
+    let head = repo.head().unwrap().target().unwrap();
+
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    assert!(ops.predict_clean_pick(&target, std::slice::from_ref(&clean)).unwrap());
+    assert!(!ops.predict_clean_pick(&target, &[clean, conflicting]).unwrap());
+    assert!(ops.predict_clean_pick("missing", &[]).is_err());
+
+    assert_eq!(repo.head().unwrap().target().unwrap(), head);
+    assert!(ops.is_clean().unwrap());
+    assert!(!dir.join("b.txt").exists());
+}
+
+/// Commits a tree holding only `vendor/lib` as a gitlink to `target`, on top of `parent`
+fn commit_gitlink(repo: &git2::Repository, target: git2::Oid, parent: Option<&git2::Commit>) -> git2::Oid {
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let mut vendor = repo.treebuilder(None).unwrap();
+    vendor.insert("lib", target, 0o160000).unwrap();
+    let vendor = vendor.write().unwrap();
+    let mut root = repo.treebuilder(None).unwrap();
+    root.insert("vendor", vendor, 0o040000).unwrap();
+    let tree = repo.find_tree(root.write().unwrap()).unwrap();
+    let parents: Vec<&git2::Commit> = parent.into_iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, "bump vendor/lib", &tree, &parents)
+        .unwrap()
+}
+
+#[test]
+fn submodule_pointer_moves_are_detected() {
+    use gh_cherry::git::SubmoduleUpdate;
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let repo = git2::Repository::init(temp.path()).expect("init repo");
+    let old = git2::Oid::from_str("1111111111111111111111111111111111111111").unwrap();
+    let new = git2::Oid::from_str("2222222222222222222222222222222222222222").unwrap();
+    let first = commit_gitlink(&repo, old, None);
+    let bump = commit_gitlink(&repo, new, Some(&repo.find_commit(first).unwrap()));
+
+    let ops = gh_cherry::git::GitOperations::new(temp.path()).expect("git ops open");
+    let updates = ops.submodule_updates(&bump.to_string()).unwrap();
+    assert_eq!(
+        updates,
+        vec![SubmoduleUpdate {
+            path: "vendor/lib".into(),
+            sha: new.to_string(),
+        }]
+    );
+    // A root commit adds the pointer, which counts too
+    assert_eq!(ops.submodule_updates(&first.to_string()).unwrap()[0].sha, old.to_string());
+    // No .gitmodules entry says where to look
+    assert_eq!(ops.submodule_commit_exists(&updates[0]), None);
+}
+
+#[test]
+fn submodule_commits_are_looked_up_on_the_configured_remote() {
+    use gh_cherry::git::SubmoduleUpdate;
+
+    if !std::process::Command::new("git").arg("--version").output().is_ok_and(|o| o.status.success()) {
+        return;
+    }
+    let temp = tempfile::tempdir().expect("tempdir");
+    let upstream = git2::Repository::init(temp.path().join("lib")).expect("init upstream");
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree = upstream.find_tree(upstream.index().unwrap().write_tree().unwrap()).unwrap();
+    let published = upstream.commit(Some("HEAD"), &sig, &sig, "lib", &tree, &[]).unwrap();
+
+    let dir = temp.path().join("app");
+    let repo = git2::Repository::init(&dir).expect("init repo");
+    fs::write(
+        dir.join(".gitmodules"),
+        format!(
+            "[submodule \"vendor/lib\"]\n\tpath = vendor/lib\n\turl = {}\n",
+            temp.path().join("lib").display()
+        ),
+    )
+    .unwrap();
+    commit_gitlink(&repo, published, None);
+
+    let ops = gh_cherry::git::GitOperations::new(&dir).expect("git ops open");
+    let update = |sha: String| SubmoduleUpdate { path: "vendor/lib".into(), sha };
+    assert_eq!(ops.submodule_commit_exists(&update(published.to_string())), Some(true));
+    assert_eq!(
+        ops.submodule_commit_exists(&update("3333333333333333333333333333333333333333".into())),
+        Some(false)
+    );
+}
+
+#[test]
+fn commits_since_reads_the_branch_back_to_the_window() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let repo = git2::Repository::init(temp.path()).expect("init repo");
+    let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+    let mut parent = None;
+    for (day, message) in [(1, "old"), (5, "fix\n\n(cherry picked from commit abcdef1)"), (6, "newer")] {
+        let time = git2::Time::new(chrono::Utc.with_ymd_and_hms(2025, 8, day, 12, 0, 0).unwrap().timestamp(), 0);
+        let sig = git2::Signature::new("Test", "test@example.com", &time).unwrap();
+        let parents: Vec<git2::Commit> = parent.iter().map(|p| repo.find_commit(*p).unwrap()).collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        parent = Some(repo.commit(Some("refs/heads/release"), &sig, &sig, message, &tree, &parents).unwrap());
+    }
+
+    let ops = gh_cherry::git::GitOperations::new(temp.path()).unwrap();
+    let since = chrono::Utc.with_ymd_and_hms(2025, 8, 4, 0, 0, 0).unwrap();
+    let commits = ops.commits_since("release", since).unwrap();
+    let messages: Vec<&str> = commits.iter().map(|c| c.message.as_str()).collect();
+    assert_eq!(messages, ["newer", "fix\n\n(cherry picked from commit abcdef1)"]);
+    assert!(ops.commits_since("missing", since).is_err());
+}
+
+#[test]
+fn branch_presence_checks_the_local_clone_and_origin() {
+    use gh_cherry::workflow::BranchPresence;
+
+    let (upstream, local, _) = repo_with_unpushed_commit();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+    let local_repo = git2::Repository::open(local.path()).unwrap();
+    let upstream_repo = git2::Repository::open(upstream.path()).unwrap();
+    let head = local_repo.head().unwrap().peel_to_commit().unwrap();
+    local_repo.branch("cherry-pick/local-only", &head, false).unwrap();
+    let released = upstream_repo.find_branch("release", git2::BranchType::Local).unwrap();
+    upstream_repo
+        .branch("cherry-pick/remote-only", &released.get().peel_to_commit().unwrap(), false)
+        .unwrap();
+
+    let presence = |name: &str| ops.branch_presence(name).unwrap();
+    assert_eq!(presence("release"), BranchPresence { local: true, remote: true });
+    assert_eq!(presence("cherry-pick/local-only"), BranchPresence { local: true, remote: false });
+    assert_eq!(presence("cherry-pick/remote-only"), BranchPresence { local: false, remote: true });
+    assert!(!presence("cherry-pick/PAY-123").exists());
+}
+
+#[test]
+fn backport_branches_are_created_from_the_source() {
+    let (upstream, local, unpushed) = repo_with_unpushed_commit();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+
+    ops.create_and_checkout_branch("cherry-pick/PAY-123", "release").unwrap();
+    ops.ensure_on_branch("cherry-pick/PAY-123").expect("on the new branch");
+    assert_eq!(ops.head_sha().unwrap(), unpushed);
+    assert!(ops.create_and_checkout_branch("cherry-pick/PAY-123", "release").is_err());
+    assert!(ops.create_and_checkout_branch("cherry-pick/PAY-124", "missing").is_err());
+
+    // A source only on origin is branched from there
+    let upstream_repo = git2::Repository::open(upstream.path()).unwrap();
+    let released = upstream_repo.find_branch("release", git2::BranchType::Local).unwrap();
+    let released = released.get().peel_to_commit().unwrap();
+    upstream_repo.branch("hotfix", &released, false).unwrap();
+    ops.fetch_branch("hotfix").unwrap();
+    ops.create_and_checkout_branch("cherry-pick/PAY-125", "hotfix").unwrap();
+    assert_eq!(ops.head_sha().unwrap(), released.id().to_string());
+}
+
+#[test]
+fn planned_backport_branches_are_reused_or_recreated_from_the_source() {
+    use gh_cherry::workflow::BranchPlan;
+
+    let (_upstream, local, unpushed) = repo_with_unpushed_commit();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+    let name = "cherry-pick/PAY-123".to_string();
+
+    let create = BranchPlan::Create { name: name.clone() };
+    ops.checkout_planned_branch(&create, "release").unwrap();
+    assert_eq!(ops.head_sha().unwrap(), unpushed);
+    let fix = side_commit(local.path(), "fix.txt");
+    let picked = ops.cherry_pick(&fix).unwrap().commit_sha.unwrap();
+
+    ops.checkout_branch("release").unwrap();
+    let reuse = BranchPlan::Reuse {
+        name: name.clone(),
+        from_remote: false,
+    };
+    ops.checkout_planned_branch(&reuse, "release").unwrap();
+    ops.ensure_on_branch(&name).expect("back on the backport branch");
+    assert_eq!(ops.head_sha().unwrap(), picked);
+
+    // Recreating the checked-out branch drops what was picked onto it before
+    let recreate = BranchPlan::Recreate {
+        name: name.clone(),
+        delete_local: true,
+        force_push: false,
+    };
+    ops.checkout_planned_branch(&recreate, "release").unwrap();
+    ops.ensure_on_branch(&name).expect("on the recreated branch");
+    assert_eq!(ops.head_sha().unwrap(), unpushed);
+    assert!(ops.is_clean().unwrap());
+}
+
+#[test]
+fn remote_only_backport_branches_are_fetched_before_reuse_or_recreation() {
+    use gh_cherry::workflow::BranchPlan;
+
+    let (upstream, local, unpushed) = repo_with_unpushed_commit();
+    let bare_dir = tempfile::tempdir().expect("tempdir");
+    let bare = git2::Repository::init_bare(bare_dir.path()).expect("init bare");
+    let local_repo = git2::Repository::open(local.path()).unwrap();
+    local_repo.remote_delete("origin").unwrap();
+    local_repo.remote("origin", bare_dir.path().to_str().unwrap()).unwrap();
+    drop(upstream);
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+    ops.push_branch("release", false).unwrap();
+
+    // Backport branches pushed from another clone, never fetched into this one
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let release = bare.find_commit(git2::Oid::from_str(&unpushed).unwrap()).unwrap();
+    let earlier = bare
+        .commit(None, &sig, &sig, "Earlier backport", &release.tree().unwrap(), &[&release])
+        .unwrap();
+    for name in ["cherry-pick/PAY-9", "cherry-pick/PAY-10"] {
+        bare.reference(&format!("refs/heads/{}", name), earlier, false, "other clone").unwrap();
+    }
+
+    let reuse = BranchPlan::Reuse {
+        name: "cherry-pick/PAY-9".to_string(),
+        from_remote: true,
+    };
+    ops.checkout_planned_branch(&reuse, "release").unwrap();
+    ops.ensure_on_branch("cherry-pick/PAY-9").expect("on the reused branch");
+    assert_eq!(ops.head_sha().unwrap(), earlier.to_string());
+
+    let recreate = BranchPlan::Recreate {
+        name: "cherry-pick/PAY-10".to_string(),
+        delete_local: false,
+        force_push: true,
+    };
+    ops.checkout_planned_branch(&recreate, "release").unwrap();
+    ops.ensure_on_branch("cherry-pick/PAY-10").expect("on the recreated branch");
+    assert_eq!(ops.head_sha().unwrap(), unpushed);
+    // The lease is where the remote's branch really is, so the force-push goes through
+    ops.push_branch("cherry-pick/PAY-10", true).unwrap();
+    let pushed = bare.find_reference("refs/heads/cherry-pick/PAY-10").unwrap().target().unwrap();
+    assert_eq!(pushed.to_string(), unpushed);
+}
+
+#[test]
+fn pushes_to_a_bare_origin_and_forces_only_with_a_fresh_lease() {
+    use gh_cherry::error::GitError;
+
+    let (upstream, local, unpushed) = repo_with_unpushed_commit();
+    let bare_dir = tempfile::tempdir().expect("tempdir");
+    git2::Repository::init_bare(bare_dir.path()).expect("init bare");
+    let local_repo = git2::Repository::open(local.path()).unwrap();
+    local_repo.remote_delete("origin").unwrap();
+    local_repo.remote("origin", bare_dir.path().to_str().unwrap()).unwrap();
+    drop(upstream);
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+    let bare = git2::Repository::open_bare(bare_dir.path()).unwrap();
+    let on_origin = || bare.find_reference("refs/heads/release").unwrap().target().unwrap().to_string();
+
+    ops.push_branch("release", false).unwrap();
+    assert_eq!(on_origin(), unpushed);
+    assert_eq!(ops.unpushed_commits("release").unwrap(), []);
+    let upstream_name = local_repo.branch_upstream_name("refs/heads/release").unwrap();
+    assert_eq!(upstream_name.as_str(), Some("refs/remotes/origin/release"));
+
+    // Rewrite the pushed commit: a plain push can't replace it
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let pushed = local_repo.find_commit(git2::Oid::from_str(&unpushed).unwrap()).unwrap();
+    let parent = pushed.parent(0).unwrap();
+    let amended = local_repo
+        .commit(None, &sig, &sig, "Amended", &pushed.tree().unwrap(), &[&parent])
+        .unwrap();
+    local_repo.reference("refs/heads/release", amended, true, "amend").unwrap();
+    let refused = ops.push_branch("release", false);
+    assert!(matches!(refused, Err(GitError::PushRejected { .. })), "{:?}", refused);
+    assert_eq!(on_origin(), unpushed);
+
+    // Someone else moved origin's branch since the last fetch: the lease is stale
+    let base = bare.find_commit(parent.id()).unwrap();
+    let elsewhere = bare
+        .commit(None, &sig, &sig, "Elsewhere", &base.tree().unwrap(), &[&base])
+        .unwrap();
+    bare.reference("refs/heads/release", elsewhere, true, "moved").unwrap();
+    assert!(matches!(ops.push_branch("release", true), Err(GitError::PushRejected { .. })));
+    assert_eq!(on_origin(), elsewhere.to_string());
+
+    ops.fetch_branch("release").unwrap();
+    ops.push_branch("release", true).unwrap();
+    assert_eq!(on_origin(), amended.to_string());
+}
+
+#[test]
+fn a_push_gives_up_once_the_remote_refuses_the_credential() {
+    use std::io::{Read, Write};
+
+    // An HTTP remote that answers every request with a demand for credentials
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/acme/widgets.git", listener.local_addr().unwrap());
+    let asked = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    {
+        let asked = asked.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request);
+                asked.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let _ = stream.write_all(
+                    b"HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic realm=\"git\"\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                );
+            }
+        });
+    }
+
+    let (_upstream, local, _) = repo_with_unpushed_commit();
+    let local_repo = git2::Repository::open(local.path()).unwrap();
+    local_repo.remote_delete("origin").unwrap();
+    local_repo.remote("origin", &url).unwrap();
+    // A helper that always offers the same, refused, credential
+    local_repo
+        .config()
+        .unwrap()
+        .set_str("credential.helper", "!f() { echo username=bot; echo password=wrong; }; f")
+        .unwrap();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+
+    assert!(ops.push_branch("release", false).is_err());
+    assert!(asked.load(std::sync::atomic::Ordering::SeqCst) <= 3, "kept offering the credential");
+}
+
+/// A repository on `main` with remotes `origin`, `upstream` and `fork`, and
+/// a remote-tracking `release` for each of `remotes_with_release`
+fn repo_with_remotes(remotes_with_release: &[&str]) -> (tempfile::TempDir, git2::Repository) {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let repo = git2::Repository::init(temp.path()).expect("init repo");
+    repo.set_head("refs/heads/main").unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+    let first = repo.commit(Some("HEAD"), &sig, &sig, "first", &tree, &[]).unwrap();
+    for remote in ["origin", "upstream", "fork"] {
+        repo.remote(remote, &format!("https://github.com/{}/app.git", remote)).unwrap();
+    }
+    for remote in remotes_with_release {
+        repo.reference(&format!("refs/remotes/{}/release", remote), first, false, "test")
+            .unwrap();
+    }
+    drop(tree);
+    (temp, repo)
+}
+
+fn tracked_remote(repo: &git2::Repository) -> String {
+    repo.config().unwrap().snapshot().unwrap().get_string("branch.release.remote").unwrap()
+}
+
+#[test]
+fn a_branch_only_on_another_remote_is_tracked_from_there() {
+    let (temp, repo) = repo_with_remotes(&["upstream"]);
+    let ops = gh_cherry::git::GitOperations::new(temp.path()).expect("git ops open");
+
+    assert_eq!(ops.checkout_branch("release").unwrap().as_deref(), Some("upstream"));
+    ops.ensure_on_branch("release").expect("on release");
+    assert_eq!(tracked_remote(&repo), "upstream");
+    // Once it's local there's nothing to tell
+    assert_eq!(ops.checkout_branch("release").unwrap(), None);
+}
+
+#[test]
+fn the_configured_remote_wins_when_it_has_the_branch() {
+    let (temp, repo) = repo_with_remotes(&["origin", "upstream", "fork"]);
+    let ops = gh_cherry::git::GitOperations::new(temp.path())
+        .expect("git ops open")
+        .with_remote("fork");
+
+    assert_eq!(ops.checkout_branch("release").unwrap(), None);
+    assert_eq!(tracked_remote(&repo), "fork");
+}
+
+#[test]
+fn several_other_remotes_with_the_branch_are_listed() {
+    use gh_cherry::error::GitError;
+
+    let (temp, repo) = repo_with_remotes(&["upstream", "fork"]);
+    let ops = gh_cherry::git::GitOperations::new(temp.path()).expect("git ops open");
+    match ops.checkout_branch("release") {
+        Err(err @ GitError::AmbiguousRemoteBranch { .. }) => {
+            assert!(err.to_string().contains("github.remote_name"), "{}", err);
+            let GitError::AmbiguousRemoteBranch { branch, candidates } = err else {
+                unreachable!()
+            };
+            assert_eq!(branch, "release");
+            assert_eq!(candidates, ["fork/release", "upstream/release"]);
+        }
+        other => panic!("expected GitError::AmbiguousRemoteBranch, got {:?}", other),
+    }
+    assert!(repo.find_branch("release", git2::BranchType::Local).is_err());
+    ops.ensure_on_branch("main").expect("still on main");
+
+    // Naming one of them settles it
+    let ops = ops.with_remote("upstream");
+    assert_eq!(ops.checkout_branch("release").unwrap(), None);
+    assert_eq!(tracked_remote(&repo), "upstream");
+
+    assert!(matches!(ops.checkout_branch("missing"), Err(GitError::BranchNotFound { .. })));
+}
+
+#[test]
+fn changed_paths_and_files_on_a_branch_are_read_from_the_trees() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let repo = git2::Repository::init(temp.path()).expect("init repo");
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let commit = |files: &[(&str, &str)], parent: Option<&git2::Commit>| {
+        for (path, contents) in files {
+            let path = temp.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        let mut index = repo.index().unwrap();
+        index.add_all(["*"], git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parents: Vec<&git2::Commit> = parent.into_iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, "change", &tree, &parents).unwrap()
+    };
+    let first = commit(&[(".github/CODEOWNERS", "* @acme/core\n"), ("src/a.rs", "a\n")], None);
+    let second = commit(&[("src/a.rs", "b\n"), ("b.rs", "b\n")], Some(&repo.find_commit(first).unwrap()));
+    let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+    let ops = gh_cherry::git::GitOperations::new(temp.path()).expect("git ops open");
+    assert_eq!(ops.changed_paths(&second.to_string()).unwrap(), ["b.rs", "src/a.rs"]);
+    assert_eq!(
+        ops.file_on_branch(&branch, ".github/CODEOWNERS").unwrap().as_deref(),
+        Some("* @acme/core\n")
+    );
+    assert_eq!(ops.file_on_branch(&branch, "CODEOWNERS").unwrap(), None);
+    assert!(ops.file_on_branch("no-such-branch", "CODEOWNERS").is_err());
+}
+
+#[test]
+fn uncommitted_changes_follow_checkouts_through_the_stash() {
+    let (_upstream, local, unpushed) = repo_with_unpushed_commit();
+    let repo = git2::Repository::open(local.path()).unwrap();
+    repo.branch("other", &repo.find_commit(git2::Oid::from_str(&unpushed).unwrap()).unwrap(), false)
+        .unwrap();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+
+    fs::write(local.path().join("wip.txt"), "still going\n").unwrap();
+    fs::write(local.path().join("notes.txt"), "untracked\n").unwrap();
+    ops.checkout_branch("other").unwrap();
+    ops.ensure_on_branch("other").expect("on the other branch");
+    ops.checkout_branch("release").unwrap();
+
+    ops.ensure_on_branch("release").expect("back on release");
+    assert_eq!(fs::read_to_string(local.path().join("wip.txt")).unwrap(), "still going\n");
+    assert_eq!(fs::read_to_string(local.path().join("notes.txt")).unwrap(), "untracked\n");
+    let mut stashes = 0;
+    git2::Repository::open(local.path()).unwrap().stash_foreach(|_, _, _| {
+        stashes += 1;
+        true
+    }).unwrap();
+    assert_eq!(stashes, 0, "every stash was popped");
+}
+
+#[test]
+fn changes_that_conflict_with_the_branch_stay_stashed() {
+    use gh_cherry::error::GitError;
+
+    let (_upstream, local, _unpushed) = repo_with_unpushed_commit();
+    let ops = gh_cherry::git::GitOperations::new(local.path()).unwrap();
+    // `plain` has its own wip.txt, which the uncommitted change edits too
+    let repo = git2::Repository::open(local.path()).unwrap();
+    let released = repo.find_reference("refs/remotes/origin/release").unwrap().peel_to_commit().unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let mut builder = repo.treebuilder(Some(&released.tree().unwrap())).unwrap();
+    builder.insert("wip.txt", repo.blob(b"another take\n").unwrap(), 0o100644).unwrap();
+    let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+    let plain = repo.commit(None, &sig, &sig, "another take", &tree, &[&released]).unwrap();
+    repo.branch("plain", &repo.find_commit(plain).unwrap(), false).unwrap();
+
+    fs::write(local.path().join("wip.txt"), "still going\n").unwrap();
+    let err = ops.checkout_branch("plain").unwrap_err();
+
+    assert!(matches!(&err, GitError::StashConflicts { branch, .. } if branch == "plain"), "got {:?}", err);
+    assert!(err.to_string().contains("git stash pop"), "{}", err);
+    ops.ensure_on_branch("plain").expect("the checkout itself went through");
+    assert!(ops.is_clean().unwrap(), "nothing half-applied");
+    let mut messages = Vec::new();
+    let mut repo = git2::Repository::open(local.path()).unwrap();
+    repo.stash_foreach(|_, message, _| {
+        messages.push(message.to_string());
+        true
+    })
+    .unwrap();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].contains("gh_cherry: checking out plain"), "{:?}", messages);
+}
+
+#[test]
+fn worktree_picks_leave_conflicting_targets_and_the_working_tree_alone() {
+    use gh_cherry::git::{WorktreePick, WorktreePicker};
+    use gh_cherry::workflow::TargetPick;
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    let repo = git2::Repository::init(dir).expect("init repo");
+    {
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+    }
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let commit = |parent: Option<&git2::Commit>, content: &str, update_ref: Option<&str>| {
+        let mut builder = repo.treebuilder(parent.map(|p| p.tree().unwrap()).as_ref()).unwrap();
+        builder.insert("a.txt", repo.blob(content.as_bytes()).unwrap(), 0o100644).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        let parents: Vec<&git2::Commit> = parent.into_iter().collect();
+        repo.commit(update_ref, &sig, &sig, content, &tree, &parents).unwrap()
+    };
+    let base = commit(None, "base\n", Some("HEAD"));
+    let base = repo.find_commit(base).unwrap();
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force())).unwrap();
+    repo.branch("release-1", &base, false).unwrap();
+    let diverged = commit(Some(&base), "diverged\n", None);
+    repo.branch("release-2", &repo.find_commit(diverged).unwrap(), false).unwrap();
+    let fix = commit(Some(&base), "fix\n", None).to_string();
+
+    let worktrees = tempfile::tempdir().expect("tempdir");
+    let ops = gh_cherry::git::GitOperations::new(dir).expect("git ops open");
+    let head_before = ops.head_sha().unwrap();
+    let picker = WorktreePicker::new(
+        ops.path(),
+        worktrees.path().to_path_buf(),
+        &gh_cherry::config::GitHubConfig::default(),
+        vec![fix.clone()],
+    );
+
+    let picked = picker.pick("release-1");
+    let WorktreePick::Picked(commits) = picked else {
+        panic!("expected release-1 to take the pick, got {:?}", picked);
+    };
+    assert_eq!(commits.len(), 1);
+    assert_eq!(commits[0].source_sha, fix);
+    let tip = repo.revparse_single("release-1").unwrap().peel_to_commit().unwrap();
+    assert_eq!(tip.id().to_string(), commits[0].new_sha);
+    assert_eq!(tip.parent_id(0).unwrap(), base.id());
+
+    match picker.pick("release-2") {
+        WorktreePick::Conflicted { sha, files } => {
+            assert_eq!(sha, fix);
+            assert_eq!(files, vec!["a.txt"]);
+        }
+        other => panic!("expected a conflict on release-2, got {:?}", other),
+    }
+    assert_eq!(repo.revparse_single("release-2").unwrap().id(), diverged);
+
+    assert!(matches!(picker.pick("no-such-branch"), WorktreePick::Failed(_)));
+
+    // The second commit can't be picked, so the first comes off again too
+    repo.branch("release-3", &base, false).unwrap();
+    let missing = "0123456789abcdef0123456789abcdef01234567".to_string();
+    let failing = WorktreePicker::new(
+        ops.path(),
+        worktrees.path().to_path_buf(),
+        &gh_cherry::config::GitHubConfig::default(),
+        vec![fix.clone(), missing],
+    );
+    let failed = failing.pick("release-3");
+    assert!(matches!(failed, WorktreePick::Failed(_)), "{:?}", failed);
+    assert_eq!(repo.revparse_single("release-3").unwrap().id(), base.id());
+
+    assert!(repo.worktrees().unwrap().is_empty());
+    assert_eq!(fs::read_dir(worktrees.path()).unwrap().count(), 0);
+    assert_eq!(ops.head_sha().unwrap(), head_before);
+    assert!(ops.is_clean().unwrap());
+}
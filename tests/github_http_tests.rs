@@ -0,0 +1,860 @@
+mod support;
+
+use chrono::{TimeZone, Utc};
+use gh_cherry::config::{Config, RepoConfigFile};
+use gh_cherry::error::GitHubError;
+use gh_cherry::github::{
+    probe_org_repos, upsert_marked_comment, ApiCategory, CommentAction, GitHubOps, GitHubClient, OrganizationInfo,
+    PrState, RepoSlug, ReviewRequest, SimulatedOps, ORG_PROBE_TIME_BOX,
+};
+use support::fake_github::{fixture, FakeGitHub, Route};
+
+const PULLS: &str = "/repos/acme/widgets/pulls";
+
+fn test_config() -> Config {
+    let mut cfg = Config::default();
+    cfg.github.owner = "acme".into();
+    cfg.github.repo = "widgets".into();
+    cfg.github.base_branch = "main".into();
+    cfg.tags.sprint_pattern = r"S\d+".into();
+    cfg.tags.environment = "DEV".into();
+    cfg.tags.pending_tag = "pending cherrypick".into();
+    cfg.tags.completed_tag = "cherry picked".into();
+    cfg.ui.days_back = 7;
+    cfg
+}
+
+async fn client(server: &FakeGitHub) -> GitHubClient {
+    GitHubClient::new_with_base_url(test_config(), server.base_url(), "test-token")
+        .await
+        .expect("client for fake server")
+}
+
+fn user() -> String {
+    fixture("user.json", &[("login", "alice")])
+}
+
+fn pull(number: u64) -> String {
+    let number = number.to_string();
+    fixture(
+        "pull.json",
+        &[
+            ("number", &number),
+            ("title", &format!("Change {}", number)),
+            ("user", &user()),
+            ("updated_at", &Utc::now().to_rfc3339()),
+            ("head_sha", &format!("{:0>40}", number)),
+            ("merge_sha", &format!("{:f>40}", number)),
+        ],
+    )
+}
+
+fn issue(number: u64, labels: &[&str]) -> String {
+    fixture(
+        "issue.json",
+        &[
+            ("number", &number.to_string()),
+            ("user", &user()),
+            ("labels", &label_list(labels)),
+        ],
+    )
+}
+
+/// The repository [`test_config`] points at
+fn widgets() -> RepoSlug {
+    RepoSlug::new("acme", "widgets")
+}
+
+/// The labels GitHub answers a label change with
+fn label_list(names: &[&str]) -> String {
+    let labels: Vec<String> = names.iter().map(|name| fixture("label.json", &[("name", name)])).collect();
+    format!("[{}]", labels.join(","))
+}
+
+fn issue_path(number: u64) -> String {
+    format!("/repos/acme/widgets/issues/{}", number)
+}
+
+fn commit(sha: &str, message: &str) -> String {
+    fixture(
+        "commit.json",
+        &[("sha", sha), ("message", message), ("date", "2025-01-01T00:00:00Z"), ("user", &user())],
+    )
+}
+
+/// The commits route of PR `number` in `acme/widgets`, listing only its head commit
+fn head_commit(number: u64) -> Route {
+    Route::get(
+        &format!("{}/{}/commits", PULLS, number),
+        format!("[{}]", commit(&format!("{:0>40}", number), &format!("Change {}", number))),
+    )
+}
+
+fn comment(id: u64, body: &str) -> String {
+    fixture("comment.json", &[("id", &id.to_string()), ("body", body), ("user", &user())])
+}
+
+#[tokio::test]
+async fn list_matching_prs_follows_pagination_and_filters_labels() {
+    let matching = ["S12", "DEV", "pending cherrypick"];
+    let server = FakeGitHub::start(vec![
+        Route::get(&format!("{}?page=2", PULLS), format!("[{}]", pull(103))),
+        Route::get(PULLS, format!("[{},{}]", pull(101), pull(102)))
+            .header("Link", &format!("<{{{{base_url}}}}{}?page=2>; rel=\"next\"", PULLS)),
+        Route::get(&issue_path(101), issue(101, &matching)),
+        Route::get(&issue_path(102), issue(102, &["S12", "QA", "pending cherrypick"])),
+        Route::get(&issue_path(103), issue(103, &matching)),
+        head_commit(101),
+        head_commit(103),
+    ]);
+
+    let prs = client(&server).await.list_matching_prs().await.unwrap();
+
+    let numbers: Vec<u64> = prs.iter().map(|pr| pr.number).collect();
+    assert_eq!(numbers, vec![101, 103]);
+    assert_eq!(prs[0].head_ref, "feature-101");
+    assert_eq!(prs[0].merge_commit_sha.as_deref(), Some(&*format!("{:f>40}", 101)));
+    assert_eq!(server.requests_to("GET", PULLS).len(), 2);
+    // The non-matching PR never has its details fetched
+    assert!(server.requests_to("GET", &format!("{}/102", PULLS)).is_empty());
+}
+
+#[tokio::test]
+async fn the_search_api_lists_only_the_prs_github_matched() {
+    let searched = |number: u64, labels: &[&str]| {
+        issue(number, labels).replace(
+            r#""updated_at": "2025-01-01T00:00:00Z""#,
+            &format!(r#""updated_at": "{}""#, Utc::now().to_rfc3339()),
+        )
+    };
+    let items = [
+        searched(101, &["S12", "DEV", "pending cherrypick"]),
+        // Search can't apply the sprint pattern; the listing still does
+        searched(102, &["DEV", "pending cherrypick"]),
+    ]
+    .join(",");
+    let server = FakeGitHub::start(vec![
+        Route::get(
+            "/search/issues",
+            format!(r#"{{"total_count":2,"incomplete_results":false,"items":[{}]}}"#, items),
+        ),
+        Route::get(&format!("{}/101", PULLS), pull(101)),
+        head_commit(101),
+    ]);
+    let mut config = test_config();
+    config.github.use_search_api = true;
+    let client = GitHubClient::new_with_base_url(config, server.base_url(), "test-token")
+        .await
+        .unwrap();
+
+    let prs = client.list_matching_prs().await.unwrap();
+
+    assert_eq!(prs.iter().map(|pr| pr.number).collect::<Vec<_>>(), [101]);
+    assert_eq!(prs[0].labels, ["S12", "DEV", "pending cherrypick"]);
+    assert_eq!(prs[0].commits.len(), 1);
+    let searches = server.requests_to("GET", "/search/issues");
+    assert!(
+        searches[0]
+            .target
+            .contains("repo%3Aacme%2Fwidgets+is%3Apr+base%3Amain+label%3A%22pending+cherrypick%22+label%3A%22DEV%22"),
+        "unexpected query: {}",
+        searches[0].target
+    );
+    assert!(server.requests_to("GET", PULLS).is_empty());
+    assert!(server.requests_to("GET", &issue_path(101)).is_empty());
+    assert_eq!(client.api_usage().get(ApiCategory::Search), 1);
+}
+
+#[tokio::test]
+async fn a_pr_whose_labels_cannot_be_fetched_is_skipped() {
+    let matching = ["S12", "DEV", "pending cherrypick"];
+    let server = FakeGitHub::start(vec![
+        Route::get(PULLS, format!("[{},{},{}]", pull(101), pull(102), pull(103))),
+        Route::get(&issue_path(101), issue(101, &matching)),
+        Route::new("GET", &issue_path(102), 404, r#"{"message":"Not Found"}"#),
+        Route::get(&issue_path(103), issue(103, &matching)),
+        head_commit(101),
+        head_commit(103),
+    ]);
+    let client = client(&server).await;
+
+    let listing = client.listing_in(&RepoSlug::new("acme", "widgets"), client.filter()).await.unwrap();
+
+    let numbers: Vec<u64> = listing.prs.iter().map(|pr| pr.number).collect();
+    assert_eq!(numbers, vec![101, 103]);
+    assert_eq!(listing.warnings.len(), 1);
+    assert!(listing.warnings[0].contains("PR #102"), "unexpected warning: {}", listing.warnings[0]);
+}
+
+#[tokio::test]
+async fn a_failed_page_keeps_the_prs_already_matched() {
+    let matching = ["S12", "DEV", "pending cherrypick"];
+    let server = FakeGitHub::start(vec![
+        Route::new(
+            "GET",
+            &format!("{}?page=2", PULLS),
+            422,
+            r#"{"message":"Validation Failed"}"#,
+        ),
+        Route::get(PULLS, format!("[{},{}]", pull(101), pull(102)))
+            .header("Link", &format!("<{{{{base_url}}}}{}?page=2>; rel=\"next\"", PULLS)),
+        Route::get(&issue_path(101), issue(101, &matching)),
+        Route::get(&issue_path(102), issue(102, &matching)),
+        head_commit(101),
+        head_commit(102),
+    ]);
+
+    let err = client(&server).await.list_matching_prs().await.unwrap_err();
+
+    let message = err.to_string();
+    match err {
+        GitHubError::PartialListing { prs, source } => {
+            let numbers: Vec<u64> = prs.iter().map(|pr| pr.number).collect();
+            assert_eq!(numbers, vec![101, 102]);
+            assert!(
+                source.to_string().starts_with("GET pulls page 2 for acme/widgets"),
+                "unexpected context: {}",
+                source
+            );
+        }
+        other => panic!("expected PartialListing, got {:?}", other),
+    }
+    assert!(message.contains("2 matching PR(s)"), "unexpected message: {}", message);
+}
+
+#[tokio::test]
+async fn a_failure_before_anything_matched_is_returned_as_is() {
+    let server = FakeGitHub::start(vec![Route::new(
+        "GET",
+        PULLS,
+        422,
+        r#"{"message":"Validation Failed"}"#,
+    )]);
+
+    let err = client(&server).await.list_matching_prs().await.unwrap_err();
+
+    assert!(
+        matches!(&err, GitHubError::Api { context, .. } if context == "GET pulls page 1 for acme/widgets"),
+        "unexpected error: {:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn rate_limited_listing_reports_reset_time() {
+    let reset = 1_900_000_000_u64;
+    let server = FakeGitHub::start(vec![
+        Route::new("GET", PULLS, 403, fixture("rate_limited.json", &[])),
+        Route::get(
+            "/rate_limit",
+            fixture("rate_limit.json", &[("reset", &reset.to_string())]),
+        ),
+    ]);
+
+    let err = client(&server).await.list_matching_prs().await.unwrap_err();
+
+    match err {
+        GitHubError::RateLimited { reset: Some(at) } => {
+            assert_eq!(at, Utc.timestamp_opt(reset as i64, 0).unwrap());
+        }
+        other => panic!("expected RateLimited with reset, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn rate_limited_requests_are_retried_once_the_limit_resets() {
+    let reset = (Utc::now().timestamp() + 2).to_string();
+    let matching = ["S12", "DEV", "pending cherrypick"];
+    let routes = || {
+        vec![
+            Route::get(PULLS, format!("[{}]", pull(101))),
+            Route::new("GET", &issue_path(101), 403, fixture("rate_limited.json", &[])).once(),
+            Route::get(&issue_path(101), issue(101, &matching)),
+            Route::get("/rate_limit", fixture("rate_limit.json", &[("reset", &reset)])),
+            head_commit(101),
+        ]
+    };
+
+    let server = FakeGitHub::start(routes());
+    let client = client(&server).await;
+    let waits = client.rate_limit_waits();
+    let prs = client.list_matching_prs().await.unwrap();
+    assert_eq!(prs.len(), 1);
+    assert_eq!(server.requests_to("GET", &issue_path(101)).len(), 2);
+    assert_eq!(client.api_usage().get(ApiCategory::RateLimit), 1);
+    // The wait was announced and is over
+    assert!(waits.has_changed().unwrap());
+    assert_eq!(*waits.borrow(), None);
+
+    // Without retries the first refusal is the answer
+    let server = FakeGitHub::start(routes());
+    let mut config = test_config();
+    config.network.max_retries = 0;
+    let client = GitHubClient::new_with_base_url(config, server.base_url(), "test-token").await.unwrap();
+    let err = client.list_matching_prs().await.unwrap_err();
+    assert!(matches!(err, GitHubError::RateLimited { reset: Some(_) }), "got {:?}", err);
+    assert_eq!(server.requests_to("GET", &issue_path(101)).len(), 1);
+}
+
+#[tokio::test]
+async fn a_refused_connection_is_transient_and_retried() {
+    // Nothing listens on a port that was just freed
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let mut config = test_config();
+    config.network.max_retries = 1;
+    let client = GitHubClient::new_with_base_url(config, &format!("http://127.0.0.1:{}", port), "test-token")
+        .await
+        .unwrap();
+
+    let err = client.list_matching_prs().await.unwrap_err();
+
+    assert!(err.is_transient(), "got {:?}", err);
+    assert_eq!(client.api_usage().get(ApiCategory::PullsList), 2);
+}
+
+#[tokio::test]
+async fn rate_limits_and_failures_in_transit_share_the_retries() {
+    let reset = (Utc::now().timestamp() + 2).to_string();
+    let server = FakeGitHub::start(vec![
+        Route::new("GET", &issue_path(101), 403, fixture("rate_limited.json", &[])).once(),
+        Route::new("GET", &issue_path(101), 502, r#"{"message":"Bad Gateway"}"#).once(),
+        Route::new("GET", &issue_path(101), 502, r#"{"message":"Bad Gateway"}"#).once(),
+        Route::get(&issue_path(101), issue(101, &["pending cherrypick"])),
+        Route::get("/rate_limit", fixture("rate_limit.json", &[("reset", &reset)])),
+    ]);
+    let mut config = test_config();
+    config.network.max_retries = 2;
+    let client = GitHubClient::new_with_base_url(config, server.base_url(), "test-token").await.unwrap();
+
+    let err = client.update_pr_labels(101).await.unwrap_err();
+
+    assert!(err.is_transient(), "got {:?}", err);
+    assert_eq!(server.requests_to("GET", &issue_path(101)).len(), 3);
+}
+
+#[tokio::test]
+async fn a_cherry_pick_comment_is_resent_only_when_it_never_left() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let mut config = test_config();
+    config.network.max_retries = 1;
+    let unreachable = GitHubClient::new_with_base_url(config.clone(), &format!("http://127.0.0.1:{}", port), "test-token")
+        .await
+        .unwrap();
+    let err = unreachable.add_cherry_pick_comment(101, "release", &[], false, None, None).await.unwrap_err();
+    assert!(err.is_connect_failure(), "got {:?}", err);
+    assert_eq!(unreachable.api_usage().get(ApiCategory::Comment), 2);
+
+    // GitHub may have posted it before failing, so it isn't sent again
+    let comments = "/repos/acme/widgets/issues/101/comments";
+    let server = FakeGitHub::start(vec![
+        Route::new("POST", comments, 502, r#"{"message":"Bad Gateway"}"#).once(),
+        Route::new("POST", comments, 201, comment(1, "picked")),
+    ]);
+    let client = GitHubClient::new_with_base_url(config, server.base_url(), "test-token").await.unwrap();
+    let err = client.add_cherry_pick_comment(101, "release", &[], false, None, None).await.unwrap_err();
+    assert!(err.is_transient() && !err.is_connect_failure(), "got {:?}", err);
+    assert_eq!(server.requests_to("POST", comments).len(), 1);
+}
+
+#[tokio::test]
+async fn sso_protected_listing_names_the_authorization_page() {
+    let url = "https://github.com/orgs/acme/sso?authorization_request=A1B2C3";
+    let header = format!("required; url={}", url);
+    let server = FakeGitHub::start(vec![
+        Route::new("GET", PULLS, 403, fixture("sso_required.json", &[])).header("X-GitHub-SSO", &header),
+        Route::new("GET", "/repos/acme/widgets", 403, fixture("sso_required.json", &[]))
+            .header("X-GitHub-SSO", &header),
+    ]);
+
+    let err = client(&server).await.list_matching_prs().await.unwrap_err();
+
+    match &err {
+        GitHubError::SsoRequired { org, url: found } => {
+            assert_eq!(org.as_deref(), Some("acme"));
+            assert_eq!(found.as_deref(), Some(url));
+        }
+        other => panic!("expected SsoRequired, got {:?}", other),
+    }
+    assert_eq!(err.sso_url(), Some(url));
+}
+
+#[tokio::test]
+async fn update_pr_labels_swaps_pending_for_completed() {
+    let server = FakeGitHub::start(vec![
+        Route::get(&issue_path(101), issue(101, &["S12", "DEV", "pending cherrypick"])),
+        Route::new(
+            "PATCH",
+            &issue_path(101),
+            200,
+            issue(101, &["S12", "DEV", "cherry picked"]),
+        ),
+    ]);
+
+    client(&server).await.update_pr_labels(101).await.unwrap();
+
+    let patches = server.requests_to("PATCH", &issue_path(101));
+    assert_eq!(patches.len(), 1);
+    assert!(
+        patches[0]
+            .body
+            .contains(r#""labels":["S12","DEV","cherry picked"]"#),
+        "unexpected body: {}",
+        patches[0].body
+    );
+}
+
+#[tokio::test]
+async fn labelled_prs_are_searched_and_relabelled_in_their_own_repository() {
+    let items = [issue(5, &["pending cherrypick"]), issue(6, &["bug", "pending cherrypick"])].join(",");
+    let server = FakeGitHub::start(vec![
+        Route::get(
+            "/search/issues",
+            format!(r#"{{"total_count":2,"incomplete_results":false,"items":[{}]}}"#, items),
+        ),
+        Route::new("POST", &format!("{}/labels", issue_path(6)), 200, label_list(&["bug", "pending cherrypick", "backport/pending"])),
+        Route::new("DELETE", &format!("{}/labels/pending%20cherrypick", issue_path(6)), 200, label_list(&["bug", "backport/pending"])),
+    ]);
+    let client = client(&server).await;
+
+    let prs = client.search_labelled_prs("acme", "pending cherrypick").await.unwrap();
+    let searches = server.requests_to("GET", "/search/issues");
+    assert_eq!(searches.len(), 1);
+    assert!(
+        searches[0]
+            .target
+            .contains("q=is%3Apr+is%3Aopen+user%3Aacme+label%3A%22pending+cherrypick%22"),
+        "unexpected query: {}",
+        searches[0].target
+    );
+    assert_eq!(prs.iter().map(|pr| pr.number).collect::<Vec<_>>(), [5, 6]);
+    assert_eq!(prs[1].repo, RepoSlug::new("acme", "widgets"));
+    assert_eq!(prs[1].labels, ["bug", "pending cherrypick"]);
+
+    client
+        .add_pr_labels(&prs[1].repo, 6, &["backport/pending".to_string()])
+        .await
+        .unwrap();
+    let adds = server.requests_to("POST", &format!("{}/labels", issue_path(6)));
+    assert!(adds[0].body.contains(r#""labels":["backport/pending"]"#), "{}", adds[0].body);
+    client.remove_pr_label(&prs[1].repo, 6, "pending cherrypick").await.unwrap();
+    assert_eq!(client.api_usage().get(ApiCategory::Search), 1);
+    assert_eq!(client.api_usage().get(ApiCategory::LabelUpdate), 2);
+    assert!(server.requests_to("PATCH", &issue_path(6)).is_empty());
+}
+
+#[tokio::test]
+async fn removing_a_label_the_pr_no_longer_has_succeeds() {
+    let server = FakeGitHub::start(vec![Route::new(
+        "DELETE",
+        &format!("{}/labels/pending%20cherrypick", issue_path(6)),
+        404,
+        r#"{"message":"Label does not exist"}"#,
+    )]);
+
+    client(&server).await.remove_pr_label(&widgets(), 6, "pending cherrypick").await.unwrap();
+}
+
+#[tokio::test]
+async fn server_errors_are_retried() {
+    let server = FakeGitHub::start(vec![
+        Route::new("GET", &issue_path(101), 502, r#"{"message":"Bad Gateway"}"#).once(),
+        Route::get(&issue_path(101), issue(101, &["pending cherrypick"])),
+        Route::new("PATCH", &issue_path(101), 200, issue(101, &["cherry picked"])),
+    ]);
+
+    client(&server).await.update_pr_labels(101).await.unwrap();
+
+    assert_eq!(server.requests_to("GET", &issue_path(101)).len(), 2);
+}
+
+#[tokio::test]
+async fn fork_repository_returns_the_new_fork() {
+    let server = FakeGitHub::start(vec![Route::new(
+        "POST",
+        "/repos/acme/widgets/forks",
+        202,
+        fixture("fork.json", &[("owner", "alice"), ("user", &user())]),
+    )]);
+
+    let fork = client(&server)
+        .await
+        .fork_repository("acme", "widgets")
+        .await
+        .unwrap();
+
+    assert_eq!(fork.full_name, "alice/widgets");
+    assert!(fork.fork);
+    assert_eq!(fork.parent_full_name.as_deref(), Some("acme/widgets"));
+}
+
+#[tokio::test]
+async fn missing_repository_is_not_found() {
+    let server = FakeGitHub::start(vec![]);
+
+    let err = client(&server)
+        .await
+        .fork_repository("acme", "nope")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, GitHubError::NotFound { .. }), "got {:?}", err);
+}
+
+fn content(path: &str, base64: &str) -> String {
+    let name = path.rsplit('/').next().unwrap();
+    fixture(
+        "content.json",
+        &[("path", path), ("name", name), ("content", base64), ("size", "42")],
+    )
+}
+
+#[tokio::test]
+async fn repo_config_files_come_back_most_specific_first() {
+    const CONTENTS: &str = "/repos/acme/widgets/contents";
+    // `[tags]\npending_tag = "needs backport"\n` and `TARGET_BRANCH=release\n`
+    let server = FakeGitHub::start(vec![
+        Route::get(
+            &format!("{}/cherry.env", CONTENTS),
+            content("cherry.env", "VEFSR0VUX0JSQU5DSD1yZWxlYXNlCg=="),
+        ),
+        Route::get(
+            &format!("{}/.github/gh_cherry.toml", CONTENTS),
+            content(".github/gh_cherry.toml", "W3RhZ3NdCnBlbmRpbmdfdGFnID0gIm5lZWRzIGJhY2twb3J0Igo="),
+        ),
+    ]);
+
+    let files = client(&server).await.get_repo_config_file().await.unwrap();
+
+    assert_eq!(
+        files,
+        vec![
+            RepoConfigFile::Toml("[tags]\npending_tag = \"needs backport\"\n".into()),
+            RepoConfigFile::Env("TARGET_BRANCH=release\n".into()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn missing_repo_config_files_are_not_an_error() {
+    let server = FakeGitHub::start(vec![]);
+
+    let files = client(&server).await.get_repo_config_file().await.unwrap();
+
+    assert!(files.is_empty());
+    assert_eq!(server.requests_to("GET", "/repos/acme/widgets/contents/cherry.env").len(), 1);
+}
+
+#[tokio::test]
+async fn get_pr_fetches_one_pr_without_filters() {
+    // Labels that the listing would reject
+    let server = FakeGitHub::start(vec![
+        Route::get(&format!("{}/987", PULLS), pull(987)),
+        head_commit(987),
+        Route::get(&issue_path(987), issue(987, &["QA"])),
+    ]);
+
+    let pr = client(&server).await.get_pr_in(&widgets(), 987).await.unwrap();
+
+    assert_eq!(pr.number, 987);
+    assert_eq!(pr.labels, vec!["QA"]);
+    assert_eq!(pr.head_sha, format!("{:0>40}", 987));
+    assert!(server.requests_to("GET", PULLS).is_empty());
+}
+
+#[tokio::test]
+async fn get_pr_lists_every_commit_across_pages() {
+    let commits = format!("{}/42/commits", PULLS);
+    let server = FakeGitHub::start(vec![
+        Route::get(&format!("{}/42", PULLS), pull(42)),
+        Route::get(&issue_path(42), issue(42, &[])),
+        Route::get(&format!("{}?per_page=100&page=2", commits), format!("[{}]", commit("c3", "Third"))),
+        Route::get(&commits, format!("[{},{}]", commit("c1", "First"), commit("c2", "Second"))).header(
+            "Link",
+            &format!(
+                "<{{{{base_url}}}}{0}?per_page=100&page=2>; rel=\"next\", <{{{{base_url}}}}{0}?per_page=100&page=2>; rel=\"last\"",
+                commits
+            ),
+        ),
+    ]);
+    let client = client(&server).await;
+
+    let pr = client.get_pr_in(&widgets(), 42).await.unwrap();
+
+    let shas: Vec<_> = pr.commits.iter().map(|c| c.sha.as_str()).collect();
+    assert_eq!(shas, ["c1", "c2", "c3"]);
+    assert_eq!(pr.commits[1].message, "Second");
+    assert_eq!(pr.commits[1].author, "alice");
+    assert_eq!(client.api_usage().get(ApiCategory::PullCommits), 2);
+}
+
+#[tokio::test]
+async fn a_later_page_that_fails_in_transit_is_retried() {
+    let commits = format!("{}/42/commits", PULLS);
+    let second = format!("{}?per_page=100&page=2", commits);
+    let server = FakeGitHub::start(vec![
+        Route::get(&format!("{}/42", PULLS), pull(42)),
+        Route::get(&issue_path(42), issue(42, &[])),
+        Route::new("GET", &second, 502, r#"{"message":"Bad Gateway"}"#).once(),
+        Route::get(&second, format!("[{}]", commit("c2", "Second"))),
+        Route::get(&commits, format!("[{}]", commit("c1", "First")))
+            .header("Link", &format!("<{{{{base_url}}}}{}>; rel=\"next\"", second)),
+    ]);
+    let client = client(&server).await;
+
+    let pr = client.get_pr_in(&widgets(), 42).await.unwrap();
+
+    let shas: Vec<_> = pr.commits.iter().map(|c| c.sha.as_str()).collect();
+    assert_eq!(shas, ["c1", "c2"]);
+    assert_eq!(client.api_usage().get(ApiCategory::PullCommits), 3);
+}
+
+#[tokio::test]
+async fn prs_are_merged_only_when_merged_at_is_set() {
+    let unmerged = |number: u64, state: &str| {
+        pull(number)
+            .lines()
+            .map(|line| match line.trim_start() {
+                l if l.starts_with(r#""state":"#) => format!(r#""state": "{}","#, state),
+                l if l.starts_with(r#""merged_at":"#) => r#""merged_at": null,"#.to_string(),
+                _ => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let server = FakeGitHub::start(vec![
+        Route::get(&format!("{}/1", PULLS), pull(1)),
+        Route::get(&format!("{}/2", PULLS), unmerged(2, "open")),
+        Route::get(&format!("{}/3", PULLS), unmerged(3, "closed")),
+        Route::get(&issue_path(1), issue(1, &[])),
+        head_commit(1),
+        Route::get(&issue_path(2), issue(2, &[])),
+        head_commit(2),
+        Route::get(&issue_path(3), issue(3, &[])),
+        head_commit(3),
+    ]);
+    let client = client(&server).await;
+
+    let mut states = Vec::new();
+    for number in 1..=3 {
+        states.push(client.get_pr_in(&widgets(), number).await.unwrap().state);
+    }
+
+    assert_eq!(states, [PrState::Merged, PrState::Open, PrState::Closed]);
+}
+
+#[tokio::test]
+async fn one_client_serves_two_repositories() {
+    const GADGETS: &str = "/repos/acme/gadgets";
+    let matching = ["S12", "DEV", "pending cherrypick"];
+    let server = FakeGitHub::start(vec![
+        Route::get(&format!("{}/101", PULLS), pull(101)),
+        Route::get(&issue_path(101), issue(101, &matching)),
+        Route::get(&format!("{}/pulls", GADGETS), format!("[{},{}]", pull(7), pull(8))),
+        head_commit(101),
+        Route::get(&format!("{}/pulls/8", GADGETS), pull(8)),
+        Route::get(&format!("{}/pulls/7/commits", GADGETS), format!("[{}]", commit("7", "Change 7"))),
+        Route::get(&format!("{}/pulls/8/commits", GADGETS), format!("[{}]", commit("8", "Change 8"))),
+        Route::get(&format!("{}/issues/7", GADGETS), issue(7, &matching)),
+        Route::get(&format!("{}/issues/8", GADGETS), issue(8, &["QA"])),
+    ]);
+    let client = client(&server).await;
+    let gadgets = RepoSlug::new("acme", "gadgets");
+
+    // The configured repository and another, through the same client
+    assert_eq!(client.get_pr_in(&widgets(), 101).await.unwrap().number, 101);
+    assert_eq!(client.get_pr_in(&gadgets, 8).await.unwrap().labels, vec!["QA"]);
+
+    let listed = client.list_matching_prs_in(&gadgets, client.filter()).await.unwrap();
+    assert_eq!(listed.iter().map(|pr| pr.number).collect::<Vec<_>>(), vec![7]);
+    let unlabelled = client
+        .list_matching_prs_in(&gadgets, &client.filter().without_labels())
+        .await
+        .unwrap();
+    assert_eq!(unlabelled.len(), 2);
+
+    assert!(server.requests_to("GET", PULLS).is_empty());
+    assert_eq!(server.requests_to("GET", &format!("{}/pulls", GADGETS)).len(), 2);
+}
+
+#[tokio::test]
+async fn organization_details_count_members_from_the_last_page() {
+    let members = "/orgs/acme/public_members";
+    let server = FakeGitHub::start(vec![
+        Route::get(
+            "/orgs/acme",
+            fixture(
+                "org.json",
+                &[("login", "acme"), ("plan", r#", "plan": { "name": "team" }"#)],
+            ),
+        ),
+        Route::get(members, format!("[{}]", user())).header(
+            "Link",
+            &format!(
+                "<{{{{base_url}}}}{0}?per_page=1&page=2>; rel=\"next\", <{{{{base_url}}}}{0}?per_page=1&page=17>; rel=\"last\"",
+                members
+            ),
+        ),
+    ]);
+
+    let details = client(&server)
+        .await
+        .get_organization_details("acme")
+        .await
+        .unwrap();
+
+    assert_eq!(details.public_repos, 42);
+    assert_eq!(details.public_members, 17);
+    assert_eq!(details.created_at, Utc.with_ymd_and_hms(2015, 3, 1, 12, 0, 0).unwrap());
+    assert_eq!(details.plan.as_deref(), Some("team"));
+}
+
+#[tokio::test]
+async fn organization_details_without_plan_or_extra_pages() {
+    let server = FakeGitHub::start(vec![
+        Route::get("/orgs/acme", fixture("org.json", &[("login", "acme"), ("plan", "")])),
+        Route::get("/orgs/acme/public_members", "[]"),
+    ]);
+
+    let details = client(&server)
+        .await
+        .get_organization_details("acme")
+        .await
+        .unwrap();
+
+    assert_eq!(details.public_members, 0);
+    assert_eq!(details.plan, None);
+}
+
+#[tokio::test]
+async fn org_probes_count_repos_and_leave_out_failed_orgs() {
+    let repos = "/orgs/acme/repos";
+    let server = FakeGitHub::start(vec![
+        Route::get(repos, "[{}]").header(
+            "Link",
+            &format!(
+                "<{{{{base_url}}}}{0}?per_page=1&page=2>; rel=\"next\", <{{{{base_url}}}}{0}?per_page=1&page=12>; rel=\"last\"",
+                repos
+            ),
+        ),
+        Route::get("/orgs/archived/repos", "[]"),
+        Route::new("GET", "/orgs/gone/repos", 404, r#"{"message":"Not Found"}"#),
+    ]);
+    let org = |login: &str| OrganizationInfo {
+        login: login.into(),
+        name: String::new(),
+        description: String::new(),
+    };
+
+    let counts = probe_org_repos(
+        &client(&server).await,
+        &[org("acme"), org("archived"), org("gone")],
+        ORG_PROBE_TIME_BOX,
+    )
+    .await;
+
+    assert_eq!(counts.len(), 2, "{:?}", counts);
+    assert_eq!(counts["acme"], 12);
+    assert_eq!(counts["archived"], 0);
+}
+
+#[tokio::test]
+async fn simulated_writes_never_reach_the_server() {
+    let server = FakeGitHub::start(vec![Route::get("/repos/acme/widgets/issues/7/comments", "[]")]);
+    let ops = SimulatedOps::new(client(&server).await);
+
+    ops.update_pr_labels(7).await.unwrap();
+    ops.add_cherry_pick_comment(7, "release", &[], false, None, None).await.unwrap();
+    let action = upsert_marked_comment(&ops, 7, "<!-- marker -->", "deferred").await.unwrap();
+
+    assert_eq!(action, CommentAction::Create);
+    assert_eq!(ops.take_recorded().len(), 3);
+    let requests = server.requests();
+    assert!(requests.iter().all(|r| r.method == "GET"), "{:?}", requests);
+    assert_eq!(requests.len(), 1);
+}
+
+#[tokio::test]
+async fn listing_requests_are_counted_per_page_and_per_pr() {
+    let matching = ["S12", "DEV", "pending cherrypick"];
+    let server = FakeGitHub::start(vec![
+        Route::get(&format!("{}?page=2", PULLS), format!("[{}]", pull(103))),
+        Route::get(PULLS, format!("[{},{}]", pull(101), pull(102)))
+            .header("Link", &format!("<{{{{base_url}}}}{}?page=2>; rel=\"next\"", PULLS)),
+        Route::get(&issue_path(101), issue(101, &matching)),
+        Route::get(&issue_path(102), issue(102, &["QA"])),
+        Route::get(&issue_path(103), issue(103, &matching)),
+        head_commit(101),
+        head_commit(103),
+    ]);
+    let client = client(&server).await;
+
+    client.list_matching_prs().await.unwrap();
+
+    let usage = client.api_usage();
+    assert_eq!(usage.get(ApiCategory::PullsList), 2);
+    assert_eq!(usage.get(ApiCategory::IssueGet), 3);
+    assert_eq!(usage.get(ApiCategory::PullCommits), 2);
+    assert_eq!(usage.get(ApiCategory::PullGet), 0);
+    assert_eq!(usage.total(), server.requests().len() as u64);
+}
+
+#[tokio::test]
+async fn each_github_op_counts_into_its_own_category() {
+    let comments = "/repos/acme/widgets/issues/7/comments";
+    let server = FakeGitHub::start(vec![
+        Route::get(&issue_path(7), issue(7, &["pending cherrypick"])),
+        Route::new("PATCH", &issue_path(7), 200, issue(7, &["cherry picked"])),
+        Route::get(comments, format!("[{}]", comment(1, "hi"))),
+        Route::new("POST", comments, 201, comment(2, "picked")),
+        // GitHub documents PATCH for comment edits; octocrab sends POST
+        Route::new("PATCH", "/repos/acme/widgets/issues/comments/1", 200, comment(1, "edited")),
+        Route::new("POST", "/repos/acme/widgets/issues/comments/1", 200, comment(1, "edited")),
+        Route::new("POST", PULLS, 201, pull(9)),
+        Route::new("POST", &format!("{}/9/requested_reviewers", PULLS), 201, pull(9)),
+        Route::get(&format!("{}/7", PULLS), pull(7)),
+        Route::get(&format!("/repos/acme/widgets/commits/{:0>40}/pulls", 7), format!("[{}]", pull(7))),
+    ]);
+    let client = client(&server).await;
+    let ops: &dyn GitHubOps = &client;
+
+    ops.update_pr_labels(7).await.unwrap();
+    ops.add_cherry_pick_comment(7, "release", &[], false, None, None).await.unwrap();
+    ops.list_pr_comments(7).await.unwrap();
+    ops.create_comment(7, "deferred").await.unwrap();
+    ops.update_comment(1, "edited").await.unwrap();
+    ops.create_cherry_pick_pr("backport", "release", "Backport", "").await.unwrap();
+    let request = ReviewRequest {
+        reviewers: vec!["bob".into()],
+        team_reviewers: Vec::new(),
+    };
+    ops.request_reviewers(9, &request).await.unwrap();
+    ops.pr_overview(7).await.unwrap();
+    ops.pr_for_commit(&format!("{:0>40}", 7)).await.unwrap();
+
+    let usage = ops.api_usage();
+    let expected = [
+        (ApiCategory::LabelUpdate, 1),
+        (ApiCategory::Comment, 4),
+        (ApiCategory::PullCreate, 1),
+        (ApiCategory::ReviewRequest, 1),
+        (ApiCategory::PullGet, 1),
+        (ApiCategory::Commits, 1),
+    ];
+    for (category, count) in expected {
+        assert_eq!(usage.get(category), count, "{}", category);
+    }
+    // Swapping labels reads them first
+    assert_eq!(usage.get(ApiCategory::IssueGet), 1);
+    assert_eq!(usage.total(), server.requests().len() as u64);
+}
+
+#[tokio::test]
+async fn simulated_ops_report_the_reads_they_pass_through() {
+    let server = FakeGitHub::start(vec![Route::get("/repos/acme/widgets/issues/7/comments", "[]")]);
+    let ops = SimulatedOps::new(client(&server).await);
+
+    ops.update_pr_labels(7).await.unwrap();
+    ops.list_pr_comments(7).await.unwrap();
+
+    assert_eq!(ops.api_usage().to_string(), "1 request (comment 1)");
+}
@@ -0,0 +1,45 @@
+#![cfg(unix)]
+
+use gh_cherry::hook::{self, HookContext};
+
+fn context() -> HookContext {
+    HookContext {
+        pr_number: 42,
+        target_branch: "release/2025.08".into(),
+        new_shas: vec!["aaa111".into(), "bbb222".into()],
+    }
+}
+
+#[tokio::test]
+async fn the_hook_sees_the_pick_and_its_output_is_streamed() {
+    let dir = tempfile::tempdir().unwrap();
+    let command = "echo \"#$GH_CHERRY_PR_NUMBER onto $GH_CHERRY_TARGET_BRANCH\"; \
+                   echo \"$GH_CHERRY_NEW_SHAS\" >&2; pwd";
+    let mut lines = Vec::new();
+    let status = hook::run(command, dir.path(), &context(), |line| lines.push(line))
+        .await
+        .unwrap();
+
+    assert!(status.success());
+    lines.sort();
+    let cwd = dir.path().canonicalize().unwrap();
+    let mut expected = vec![
+        "#42 onto release/2025.08".to_string(),
+        "aaa111 bbb222".to_string(),
+        cwd.display().to_string(),
+    ];
+    expected.sort();
+    assert_eq!(lines, expected);
+}
+
+#[tokio::test]
+async fn a_failing_hook_reports_its_exit_code() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut lines = Vec::new();
+    let status = hook::run("echo 'tests failed'; exit 3", dir.path(), &context(), |line| lines.push(line))
+        .await
+        .unwrap();
+
+    assert_eq!(status.code(), Some(3));
+    assert_eq!(lines, ["tests failed"]);
+}
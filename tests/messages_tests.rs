@@ -0,0 +1,134 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use gh_cherry::messages::{detect, fill, lookup, Locale};
+use regex::Regex;
+
+/// Every locale the catalog ships
+const LOCALES: [Locale; 2] = [Locale::En, Locale::Es];
+
+fn referenced_keys() -> BTreeSet<String> {
+    let call = Regex::new(r#"\btf?\(\s*"([^"]+)""#).unwrap();
+    let mut keys = BTreeSet::new();
+    let mut dirs = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("src")];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|e| e == "rs") {
+                let source = std::fs::read_to_string(&path).unwrap();
+                keys.extend(call.captures_iter(&source).map(|c| c[1].to_string()));
+            }
+        }
+    }
+    keys
+}
+
+fn placeholders(template: &str) -> BTreeSet<String> {
+    Regex::new(r"\{([a-z_]+)\}")
+        .unwrap()
+        .captures_iter(template)
+        .map(|c| c[1].to_string())
+        .collect()
+}
+
+#[test]
+fn every_referenced_key_exists_in_every_catalog() {
+    let keys = referenced_keys();
+    assert!(keys.len() > 20, "key scan found too little: {:?}", keys);
+    for locale in LOCALES {
+        let missing: Vec<_> = keys
+            .iter()
+            .filter(|k| lookup(locale, k).is_none())
+            .collect();
+        assert!(missing.is_empty(), "{:?} is missing {:?}", locale, missing);
+    }
+}
+
+#[test]
+fn catalogs_share_keys_and_placeholders() {
+    let english = Locale::En.catalog();
+    for locale in LOCALES {
+        let catalog = locale.catalog();
+        assert_eq!(catalog.len(), english.len(), "{:?} has extra or missing keys", locale);
+        for (key, template) in english {
+            let translated = lookup(locale, key)
+                .unwrap_or_else(|| panic!("{:?} is missing {}", locale, key));
+            assert_eq!(
+                placeholders(translated),
+                placeholders(template),
+                "{:?} placeholders differ for {}",
+                locale,
+                key
+            );
+        }
+    }
+}
+
+#[test]
+fn locale_prefers_config_then_lang_then_english() {
+    assert_eq!(detect(Some("es"), Some("en_US.UTF-8")), Locale::Es);
+    assert_eq!(detect(None, Some("es_ES.UTF-8")), Locale::Es);
+    assert_eq!(detect(Some("fr"), Some("de_DE.UTF-8")), Locale::En);
+    assert_eq!(detect(None, None), Locale::En);
+}
+
+#[test]
+fn fill_replaces_named_placeholders() {
+    let template = lookup(Locale::En, "status.picked").unwrap();
+    assert_eq!(
+        fill(template, &[("number", &42)]),
+        "Successfully cherry-picked PR #42"
+    );
+}
+
+#[test]
+fn fill_does_not_rescan_substituted_text() {
+    assert_eq!(
+        fill("{title} onto {branch}", &[("title", &"Fix {branch} parsing"), ("branch", &"release")]),
+        "Fix {branch} parsing onto release"
+    );
+    assert_eq!(fill("{unknown} {n}", &[("n", &1)]), "{unknown} 1");
+}
+
+/// Lines under src/ui (before any test module) that put English straight into
+/// a widget or spell out a key hint instead of going through the catalog
+fn hard_coded_ui_text() -> Vec<String> {
+    let widget = Regex::new(r#"(Paragraph::new|Span::styled|Span::raw|Line::from|\.title|run_list|run_selector)\(\s*"[^"]*[A-Za-z]{2,}"#).unwrap();
+    let hint = Regex::new(r#""[^"]*\b(Enter|Esc|Backspace)\b[^"]*""#).unwrap();
+    let mut found = Vec::new();
+    let mut dirs = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("src/ui")];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+            if path.extension().is_none_or(|e| e != "rs") {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap();
+            for (number, line) in source.lines().enumerate() {
+                if line.trim_start() == "#[cfg(test)]" {
+                    break;
+                }
+                let code = line.trim_start();
+                if code.starts_with("//") {
+                    continue;
+                }
+                if widget.is_match(code) || hint.is_match(code) {
+                    found.push(format!("{}:{}: {}", path.display(), number + 1, code));
+                }
+            }
+        }
+    }
+    found
+}
+
+#[test]
+fn ui_text_goes_through_the_catalog() {
+    let found = hard_coded_ui_text();
+    assert!(found.is_empty(), "hard-coded UI text:\n{}", found.join("\n"));
+}
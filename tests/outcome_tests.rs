@@ -0,0 +1,36 @@
+use anyhow::Context;
+use gh_cherry::error::{GitError, GitHubError};
+use gh_cherry::outcome::{NothingMatched, Outcome};
+
+#[test]
+fn each_outcome_has_its_documented_code() {
+    assert_eq!(Outcome::Success.code(), 0);
+    assert_eq!(Outcome::Error.code(), 1);
+    assert_eq!(Outcome::Conflicts.code(), 2);
+    assert_eq!(Outcome::AuthFailure.code(), 3);
+    assert_eq!(Outcome::NothingMatched.code(), 4);
+}
+
+#[test]
+fn errors_are_classified_by_their_typed_cause() {
+    let auth = anyhow::Error::from(GitHubError::Auth { reason: "no token".into() });
+    assert_eq!(Outcome::from_error(&auth), Outcome::AuthFailure);
+
+    let rejected = Err::<(), _>(GitHubError::Unauthorized { message: "Bad credentials".into() })
+        .context("Failed to load PRs")
+        .unwrap_err();
+    assert_eq!(Outcome::from_error(&rejected), Outcome::AuthFailure);
+
+    let conflicts = anyhow::Error::from(GitError::Conflicts { files: vec!["a.txt".into()] });
+    assert_eq!(Outcome::from_error(&conflicts), Outcome::Conflicts);
+
+    let nothing = anyhow::Error::from(NothingMatched("No repositories found".into()));
+    assert_eq!(Outcome::from_error(&nothing), Outcome::NothingMatched);
+}
+
+#[test]
+fn other_errors_map_to_the_generic_code() {
+    let not_found = anyhow::Error::from(GitHubError::NotFound { what: "acme/widgets".into() });
+    assert_eq!(Outcome::from_error(&not_found), Outcome::Error);
+    assert_eq!(Outcome::from_error(&anyhow::anyhow!("boom")), Outcome::Error);
+}
@@ -0,0 +1,32 @@
+use std::fs;
+
+use gh_cherry::pr_notes::PrNotes;
+
+#[test]
+fn notes_round_trip_through_the_state_file() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let path = temp.path().join("acme__widgets").join("notes.json");
+
+    // Nothing saved yet
+    assert_eq!(PrNotes::load(&path), PrNotes::default());
+
+    let mut notes = PrNotes::default();
+    notes.set(7, "wait for #12");
+    notes.set(1001, "needs DB migration first");
+    notes.save(&path).expect("save");
+
+    let loaded = PrNotes::load(&path);
+    assert_eq!(loaded, notes);
+    assert_eq!(loaded.get(1001), Some("needs DB migration first"));
+}
+
+#[test]
+fn an_unreadable_notes_file_starts_with_no_notes() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let path = temp.path().join("notes.json");
+    fs::write(&path, "not json").unwrap();
+    assert_eq!(PrNotes::load(&path), PrNotes::default());
+
+    fs::write(&path, r#"{"notes":{"42":"needs DB migration first"}}"#).unwrap();
+    assert_eq!(PrNotes::load(&path).get(42), Some("needs DB migration first"));
+}
@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use chrono::{TimeZone, Utc};
+use gh_cherry::github::PickedCommit;
+use gh_cherry::run_info::RunInfo;
+use gh_cherry::session_log::{HeldBack, PickRecord, PickStatus, SessionLog, SessionRecords, SessionSummary};
+
+const OLDER_VERSION: &str = include_str!("fixtures/sessions/older_version.jsonl");
+const PARTIAL: &str = include_str!("fixtures/sessions/partial.jsonl");
+
+#[test]
+fn records_from_older_versions_get_defaults() {
+    let session = SessionRecords::parse(OLDER_VERSION);
+
+    assert_eq!(session.skipped_lines, 1);
+    assert_eq!(session.records.len(), 3);
+    let first = &session.records[0];
+    assert_eq!(first.status, PickStatus::Picked);
+    assert_eq!(first.recorded_at, None);
+    assert_eq!(first.pr_title, "");
+    assert_eq!(first.commits[0].new_sha, "bbbbbbb");
+    // Bookkeeping wasn't tracked back then, so it isn't offered for a re-run
+    assert!(first.labels_updated && first.comment_added);
+    assert!(first.held_back.is_none());
+    assert!(session.partial().is_empty());
+    // Statuses from newer versions don't fail the whole line
+    assert_eq!(session.records[2].status, PickStatus::Unknown);
+}
+
+#[test]
+fn partial_picks_ignore_failures_and_superseded_records() {
+    let session = SessionRecords::parse(PARTIAL);
+
+    assert_eq!(session.skipped_lines, 0);
+    let partial: Vec<u64> = session.partial().iter().map(|r| r.pr_number).collect();
+    // 202 was completed by a later re-run, 203 finished, 204 never landed
+    assert_eq!(partial, vec![201]);
+}
+
+#[test]
+fn summary_counts_outcomes() {
+    let session = SessionRecords::parse(PARTIAL);
+    let summary = SessionSummary::new(PathBuf::from("s.jsonl"), &session);
+
+    assert_eq!(summary.started_at, Some(Utc.with_ymd_and_hms(2025, 3, 4, 10, 15, 0).unwrap()));
+    assert_eq!(summary.target_branches, vec!["release/2.0"]);
+    assert_eq!(summary.picked, 3);
+    assert_eq!(summary.failed, 1);
+    assert_eq!(summary.partial, 1);
+}
+
+#[test]
+fn appended_records_read_back() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let log = SessionLog::new(temp.path().join("sessions").join("s.jsonl"));
+    let record = PickRecord {
+        pr_number: 7,
+        status: PickStatus::Picked,
+        recorded_at: Some(Utc::now()),
+        pr_title: "Fix".into(),
+        target_branch: "release".into(),
+        commits: vec![PickedCommit {
+            source_sha: "abc".into(),
+            new_sha: "def".into(),
+        }],
+        reordered: true,
+        labels_updated: true,
+        comment_added: false,
+        run: Some(RunInfo::new("0.3.1", None, Some("build-7"))),
+        sides_taken: Vec::new(),
+        simulated: Vec::new(),
+        unverified_submodules: Vec::new(),
+        api_requests: None,
+        held_back: HeldBack::default(),
+        note: Some("needs DB migration first".into()),
+    };
+
+    log.append(&record).unwrap();
+    log.append(&record).unwrap();
+
+    let session = SessionRecords::read(&temp.path().join("sessions").join("s.jsonl")).unwrap();
+    assert_eq!(session.records, vec![record.clone(), record]);
+    assert_eq!(session.partial().len(), 1);
+}
+
+#[test]
+fn held_back_bookkeeping_is_logged_and_left_outstanding() {
+    let line = r#"{"pr_number":9,"status":"picked","labels_updated":false,"comment_added":true,"held_back":{"labels":true}}"#;
+    let session = SessionRecords::parse(line);
+    let record = &session.records[0];
+    assert_eq!(record.held_back, HeldBack { labels: true, comment: false });
+    assert!(!record.labels_due() && !record.comment_due());
+    // Still offered for a re-run once it may be labelled
+    assert_eq!(session.partial().len(), 1);
+
+    let written = serde_json::to_string(record).unwrap();
+    assert!(written.contains(r#""held_back":{"labels":true,"comment":false}"#), "{}", written);
+    let nothing_held = PickRecord {
+        held_back: HeldBack::default(),
+        ..record.clone()
+    };
+    assert!(!serde_json::to_string(&nothing_held).unwrap().contains("held_back"));
+    // Released for a re-run, the labels are due again
+    assert!(nothing_held.labels_due());
+}
@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use gh_cherry::storage::{list_session_files, prune_older_than, repo_slug};
+
+fn touch(path: &Path, age: Duration) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, b"{}").unwrap();
+    let file = fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - age).unwrap();
+}
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[test]
+fn prunes_only_files_older_than_cutoff() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+
+    let old_cache = root.join("org__api").join("prs.json");
+    let fresh_cache = root.join("org__web").join("prs.json");
+    let old_session = root.join("org__web").join("sessions").join("2024-01-01.jsonl");
+    touch(&old_cache, DAY * 45);
+    touch(&fresh_cache, DAY * 2);
+    touch(&old_session, DAY * 31);
+
+    let cutoff = SystemTime::now() - DAY * 30;
+    let mut removed = prune_older_than(root, cutoff).expect("prune");
+    removed.sort();
+
+    assert_eq!(removed, vec![old_cache.clone(), old_session.clone()]);
+    assert!(fresh_cache.exists());
+    // Directories left empty are cleaned up, the root is kept
+    assert!(!root.join("org__api").exists());
+    assert!(!root.join("org__web").join("sessions").exists());
+    assert!(root.exists());
+}
+
+#[test]
+fn pruning_missing_root_is_a_noop() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let removed = prune_older_than(&temp.path().join("missing"), SystemTime::now()).unwrap();
+    assert!(removed.is_empty());
+}
+
+#[test]
+fn repo_slug_is_filesystem_safe() {
+    assert_eq!(repo_slug("my-org", "api.v2"), "my-org__api.v2");
+    assert_eq!(repo_slug("a/b", "c d"), "a_b__c_d");
+}
+
+#[test]
+fn session_files_are_listed_newest_first() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    for name in ["2025-01-02T08-00-00Z.jsonl", "2025-03-01T09-30-00Z.jsonl", "notes.txt"] {
+        touch(&dir.join(name), Duration::ZERO);
+    }
+
+    let files = list_session_files(dir).unwrap();
+
+    assert_eq!(
+        files,
+        vec![dir.join("2025-03-01T09-30-00Z.jsonl"), dir.join("2025-01-02T08-00-00Z.jsonl")]
+    );
+    assert!(list_session_files(&dir.join("missing")).unwrap().is_empty());
+}
@@ -0,0 +1,201 @@
+//! A scripted stand-in for the GitHub REST API. Serves canned responses over
+//! plain HTTP on localhost and records every request it receives, so tests can
+//! drive `GitHubClient` end-to-end through real octocrab.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A request as received by the fake server
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path including the query string
+    pub target: String,
+    pub body: String,
+}
+
+/// A canned response. Routes are tried in order; a route whose path contains
+/// `?` must match the request target exactly, otherwise only the path is compared.
+#[derive(Debug, Clone)]
+pub struct Route {
+    method: String,
+    path: String,
+    status: u16,
+    body: String,
+    headers: Vec<(String, String)>,
+    /// Remaining uses; `None` serves the route forever
+    remaining: Option<usize>,
+}
+
+impl Route {
+    pub fn new(method: &str, path: &str, status: u16, body: impl Into<String>) -> Self {
+        Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            status,
+            body: body.into(),
+            headers: Vec::new(),
+            remaining: None,
+        }
+    }
+
+    pub fn get(path: &str, body: impl Into<String>) -> Self {
+        Self::new("GET", path, 200, body)
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Serves this response only once, then falls through to later routes
+    pub fn once(mut self) -> Self {
+        self.remaining = Some(1);
+        self
+    }
+
+    fn matches(&self, method: &str, target: &str) -> bool {
+        if self.method != method || self.remaining == Some(0) {
+            return false;
+        }
+        if self.path.contains('?') {
+            self.path == target
+        } else {
+            target.split('?').next() == Some(self.path.as_str())
+        }
+    }
+}
+
+pub struct FakeGitHub {
+    base_url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl FakeGitHub {
+    /// Starts serving `routes` on an ephemeral port. `{{base_url}}` in response
+    /// bodies and headers is replaced with the server's address.
+    pub fn start(routes: Vec<Route>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind fake GitHub");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let routes: Vec<Route> = routes
+            .into_iter()
+            .map(|mut route| {
+                route.body = route.body.replace("{{base_url}}", &base_url);
+                for (_, value) in &mut route.headers {
+                    *value = value.replace("{{base_url}}", &base_url);
+                }
+                route
+            })
+            .collect();
+
+        let routes = Arc::new(Mutex::new(routes));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let routes = Arc::clone(&routes);
+                let recorded = Arc::clone(&recorded);
+                thread::spawn(move || handle(stream, &routes, &recorded));
+            }
+        });
+
+        Self { base_url, requests }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Requests with the given method whose path (without query) is `path`
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<RecordedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method && r.target.split('?').next() == Some(path))
+            .collect()
+    }
+}
+
+fn handle(stream: TcpStream, routes: &Mutex<Vec<Route>>, recorded: &Mutex<Vec<RecordedRequest>>) {
+    let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).ok();
+
+    recorded.lock().unwrap().push(RecordedRequest {
+        method: method.clone(),
+        target: target.clone(),
+        body: String::from_utf8_lossy(&body).into_owned(),
+    });
+
+    let route = {
+        let mut routes = routes.lock().unwrap();
+        routes.iter_mut().find(|r| r.matches(&method, &target)).map(|r| {
+            if let Some(remaining) = &mut r.remaining {
+                *remaining -= 1;
+            }
+            r.clone()
+        })
+    };
+    let route = route.unwrap_or_else(|| {
+        Route::new(
+            &method,
+            &target,
+            404,
+            format!(r#"{{"message":"No fake route for {} {}"}}"#, method, target),
+        )
+    });
+
+    let mut response = format!(
+        "HTTP/1.1 {} Fake\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        route.status,
+        route.body.len()
+    );
+    for (name, value) in &route.headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    response.push_str("\r\n");
+    response.push_str(&route.body);
+
+    let mut stream = stream;
+    stream.write_all(response.as_bytes()).ok();
+    stream.flush().ok();
+}
+
+/// Reads `tests/fixtures/github/<name>` and replaces each `{{key}}` with its value
+pub fn fixture(name: &str, vars: &[(&str, &str)]) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/github")
+        .join(name);
+    let template = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("read fixture {}: {}", path.display(), e));
+    vars.iter().fold(template, |acc, (key, value)| {
+        acc.replace(&format!("{{{{{}}}}}", key), value)
+    })
+}
@@ -0,0 +1,5 @@
+//! Shared helpers for integration tests. Not every test crate uses every helper.
+#![allow(dead_code)]
+
+pub mod fake_github;
+pub mod snapshot;
@@ -0,0 +1,86 @@
+//! Golden-text snapshots of rendered screens. A screen is drawn into a
+//! fixed-size `TestBackend` and compared, as plain text, with
+//! `tests/fixtures/snapshots/<name>.txt`. Colours and styles aren't compared.
+//!
+//! After an intended UI change, re-bless the snapshots and review the diff:
+//!
+//! ```text
+//! UPDATE_SNAPSHOTS=1 cargo test --test ui_snapshot_tests
+//! ```
+
+use std::path::PathBuf;
+
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::{Frame, Terminal};
+use unicode_width::UnicodeWidthStr;
+
+/// Draws `render` into a `width` x `height` screen and returns it as text
+pub fn render(width: u16, height: u16, render: impl FnOnce(&mut Frame)) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("test terminal");
+    terminal.draw(render).expect("draw");
+    buffer_text(terminal.backend().buffer())
+}
+
+/// One line per row with trailing spaces trimmed. The cells a wide character
+/// covers are skipped so the text lines up as it does on screen.
+pub fn buffer_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut covered = 0;
+        for x in area.left()..area.right() {
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            let symbol = buffer[(x, y)].symbol();
+            line.push_str(symbol);
+            covered = symbol.width().saturating_sub(1);
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/snapshots")
+        .join(format!("{}.txt", name))
+}
+
+/// Fails unless `actual` matches the `name` snapshot. With `UPDATE_SNAPSHOTS`
+/// set the snapshot is rewritten instead.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "no snapshot at {}; run with UPDATE_SNAPSHOTS=1 to create it. Rendered:\n{}",
+            path.display(),
+            actual
+        )
+    });
+    if expected != actual {
+        let first_difference = expected
+            .lines()
+            .zip(actual.lines())
+            .position(|(e, a)| e != a)
+            .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+        panic!(
+            "{} differs from {} from line {}; run with UPDATE_SNAPSHOTS=1 if the change is intended.\n\
+             --- expected\n{}\n--- rendered\n{}",
+            name,
+            path.display(),
+            first_difference + 1,
+            expected,
+            actual
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use std::fs;
+
+use chrono::{TimeZone, Utc};
+use gh_cherry::target_history::{TargetHistory, TargetUse};
+
+#[test]
+fn history_round_trips_through_the_state_file() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let path = temp.path().join("acme__widgets").join("targets.json");
+
+    // Nothing saved yet
+    assert_eq!(TargetHistory::load(&path), TargetHistory::default());
+
+    let used = Utc.with_ymd_and_hms(2025, 8, 4, 9, 30, 0).unwrap();
+    let mut history = TargetHistory::default();
+    history.record_use("release/2025.08", used);
+    history.toggle_pin("release/lts");
+    history.save(&path).expect("save");
+
+    let loaded = TargetHistory::load(&path);
+    assert_eq!(loaded, history);
+    assert_eq!(
+        loaded.targets,
+        [
+            TargetUse {
+                branch: "release/2025.08".into(),
+                last_used: Some(used),
+                pinned: false,
+            },
+            TargetUse {
+                branch: "release/lts".into(),
+                last_used: None,
+                pinned: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn an_unreadable_state_file_starts_an_empty_history() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let path = temp.path().join("targets.json");
+    fs::write(&path, "not json").unwrap();
+    assert_eq!(TargetHistory::load(&path), TargetHistory::default());
+
+    // Only the branch name is required
+    fs::write(&path, r#"{"targets":[{"branch":"release/1.0"}]}"#).unwrap();
+    let history = TargetHistory::load(&path);
+    assert_eq!(history.targets[0].branch, "release/1.0");
+    assert!(!history.targets[0].pinned);
+}
@@ -0,0 +1,193 @@
+//! Renders the main screens from fixed fixtures and compares them with the
+//! golden text in `tests/fixtures/snapshots`. See `support::snapshot` for
+//! re-blessing them after an intended change.
+
+mod support;
+
+use chrono::{TimeZone, Utc};
+use gh_cherry::config::Config;
+use gh_cherry::github::{CommitInfo, PrInfo, PrState};
+use gh_cherry::session_log::{HeldBack, SessionRecords};
+use gh_cherry::ui::components::{ConflictList, ErrorView, MainMenu, PrList, ProgressView};
+use gh_cherry::ui::listing_changes::ChangeBadge;
+use gh_cherry::ui::state::{AppState, GitOperation, PendingConfirmation};
+use support::snapshot::{assert_snapshot, render};
+
+const WIDE: u16 = 150;
+const NARROW: u16 = 80;
+const HEIGHT: u16 = 14;
+
+/// Snapshots are English whatever `LANG` says
+fn state() -> AppState {
+    gh_cherry::messages::init(Some("en"));
+    AppState::new()
+}
+
+fn config() -> Config {
+    let mut config = Config::default();
+    config.github.owner = "acme".into();
+    config.github.repo = "app".into();
+    config.github.base_branch = "main".into();
+    config.github.target_branch = "release/2025.08".into();
+    config
+}
+
+fn pr(number: u64, title: &str, author: &str, labels: &[&str]) -> PrInfo {
+    let at = Utc.with_ymd_and_hms(2025, 3, 4, 12, 0, 0).unwrap();
+    PrInfo {
+        number,
+        title: title.into(),
+        author: author.into(),
+        created_at: at,
+        updated_at: at,
+        labels: labels.iter().map(|l| l.to_string()).collect(),
+        commits: vec![CommitInfo {
+            sha: format!("{:040x}", number),
+            message: title.into(),
+            author: author.into(),
+            date: at,
+        }],
+        head_sha: format!("{:040x}", number),
+        base_ref: "main".into(),
+        head_ref: format!("feature/{}", number),
+        state: PrState::Merged,
+        ..Default::default()
+    }
+}
+
+/// A listing with the cases that have broken layouts before: long titles,
+/// many labels, wide characters, badges and a follow-up
+fn listed_state() -> AppState {
+    let mut state = state();
+    let mut follow_up = pr(1042, "Follow-up: handle empty sprint labels", "bob", &["S41", "DEV"]);
+    follow_up.related_to = vec![1040];
+    state.set_prs(vec![
+        pr(
+            1040,
+            "Refactor the payment reconciliation job so retries back off exponentially and stop after the configured limit",
+            "alice",
+            &["S41", "DEV", "pending cherrypick", "backend", "payments"],
+        ),
+        follow_up,
+        pr(1037, "Übersetzung der Fehlermeldungen 修正", "zoë", &["S41", "DEV", "i18n"]),
+        pr(1031, "Bump tokio", "dependabot", &["S40", "DEV"]),
+    ]);
+    state.change_badges.insert(1037, ChangeBadge::New);
+    state.change_badges.insert(1031, ChangeBadge::Updated);
+    state.skipped_prs.insert(1031);
+    state
+}
+
+#[test]
+fn main_menu() {
+    let state = state();
+    assert_snapshot("main_menu", &render(WIDE, HEIGHT, |f| MainMenu::render(f, &state)));
+}
+
+#[test]
+fn pr_list_with_long_titles_and_labels() {
+    let state = listed_state();
+    let config = config();
+    assert_snapshot(
+        "pr_list_wide",
+        &render(WIDE, HEIGHT, |f| PrList::render(f, &state, &config)),
+    );
+}
+
+#[test]
+fn pr_list_truncates_when_narrow() {
+    let state = listed_state();
+    let config = config();
+    assert_snapshot(
+        "pr_list_narrow",
+        &render(NARROW, HEIGHT, |f| PrList::render(f, &state, &config)),
+    );
+}
+
+#[test]
+fn pr_list_with_a_filter_and_the_pick_options_prompt() {
+    let mut state = listed_state();
+    state.set_filter_query(Some("payment".into()));
+    state.confirmation = Some(PendingConfirmation::PickOptions {
+        pr_index: 0,
+        number: 1040,
+        held_back: HeldBack {
+            labels: true,
+            comment: false,
+        },
+    });
+    let config = config();
+    assert_snapshot(
+        "pr_list_pick_options",
+        &render(WIDE, HEIGHT, |f| PrList::render(f, &state, &config)),
+    );
+}
+
+#[test]
+fn pr_list_shows_notes_under_their_rows() {
+    let mut state = listed_state();
+    state.pr_notes.set(1042, "needs DB migration first");
+    state.start_note_edit(1037);
+    let config = config();
+    assert_snapshot(
+        "pr_list_notes",
+        &render(WIDE, HEIGHT, |f| PrList::render(f, &state, &config)),
+    );
+}
+
+#[test]
+fn pr_list_badges_a_dry_run() {
+    let mut state = listed_state();
+    state.dry_run = true;
+    state.show_dry_run(1040, "release/2025.08", &["1".repeat(40), "2".repeat(40)]);
+    let config = config();
+    assert_snapshot(
+        "pr_list_dry_run",
+        &render(NARROW, HEIGHT, |f| PrList::render(f, &state, &config)),
+    );
+}
+
+#[test]
+fn empty_pr_list_explains_the_criteria() {
+    let mut state = state();
+    state.set_prs(Vec::new());
+    let config = config();
+    assert_snapshot(
+        "pr_list_empty",
+        &render(NARROW, HEIGHT, |f| PrList::render(f, &state, &config)),
+    );
+}
+
+#[test]
+fn progress() {
+    let mut state = state();
+    state.set_loading("Cherry-picking PR #1040: Refactor the payment reconciliation job");
+    state.current_git_op = GitOperation::Checkout("release/2025.08".into());
+    assert_snapshot("progress", &render(NARROW, HEIGHT, |f| ProgressView::render(f, &state)));
+}
+
+#[test]
+fn error_wraps_a_long_message() {
+    let mut state = state();
+    state.set_error(
+        "Failed to check out the target branch: Branch 'release/2025.08' is on several remotes: \
+         fork/release/2025.08, upstream/release/2025.08. Set github.remote_name to the one to track"
+            .into(),
+    );
+    assert_snapshot("error", &render(NARROW, HEIGHT, |f| ErrorView::render(f, &state)));
+}
+
+#[test]
+fn conflicts() {
+    let mut state = state();
+    let record = SessionRecords::parse(r#"{"pr_number":1040,"status":"conflicted","labels_updated":false,"comment_added":false}"#)
+        .records
+        .remove(0);
+    state.set_conflicts(
+        &["src/payments/reconcile.rs".into(), "assets/logo.png".into(), "README.md".into()],
+        &["assets/logo.png".into()],
+        record,
+    );
+    state.refresh_conflicts(&["src/payments/reconcile.rs".into(), "assets/logo.png".into()]);
+    assert_snapshot("conflicts", &render(NARROW, HEIGHT, |f| ConflictList::render(f, &state)));
+}